@@ -0,0 +1,184 @@
+//! Blocked drag example.
+//!
+//! Translate drags stop at the first wall in their path, like pushing
+//! furniture around a room. The sweep provider here tests the cube against a
+//! list of static axis-aligned boxes; a real game would shape-cast with its
+//! physics engine instead. The gizmo turns orange while the cube is in
+//! contact.
+//!
+//! Controls:
+//! - B: Toggle blocking
+//! - S: Toggle sliding along walls for plane drags
+
+use bevy::prelude::*;
+use bevy_transform_tools::{
+    GizmoActive, GizmoSweepProvider, SweepHit, TransformGizmoCamera, TransformGizmoConfig,
+    TransformGizmoPlugin, TransformGizmoState, TransformGizmoTarget,
+};
+
+/// Half extents of the dragged cube.
+const CUBE_HALF: Vec3 = Vec3::splat(0.5);
+/// Distance kept between the cube and a wall at contact, so the next sweep
+/// starts cleanly outside it.
+const SKIN: f32 = 1.0e-3;
+/// Static walls as (center, size). The first one is the floor.
+const WALLS: [(Vec3, Vec3); 5] = [
+    (Vec3::new(0.0, -0.5, 0.0), Vec3::new(20.0, 1.0, 20.0)),
+    (Vec3::new(4.0, 1.0, 0.0), Vec3::new(0.5, 2.0, 8.0)),
+    (Vec3::new(-4.0, 1.0, 0.0), Vec3::new(0.5, 2.0, 8.0)),
+    (Vec3::new(0.0, 1.0, -4.0), Vec3::new(8.5, 2.0, 0.5)),
+    (Vec3::new(1.0, 0.5, 1.5), Vec3::new(1.5, 1.0, 1.5)),
+];
+
+#[derive(Component)]
+struct Hud;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(TransformGizmoPlugin)
+        .insert_resource(TransformGizmoConfig {
+            blocked_translation: true,
+            slide_on_contact: true,
+            ..default()
+        })
+        .insert_resource(GizmoSweepProvider::new(|_, from, to| sweep_cube(from, to)))
+        .add_systems(Startup, setup)
+        .add_systems(Update, (keyboard_controls, update_hud))
+        .run();
+}
+
+/// First wall the cube touches moving from `from` to `to`.
+///
+/// Each wall is grown by the cube's half extents, so the cube becomes a
+/// point and the sweep a segment test against the grown boxes. Walls the
+/// cube already overlaps are ignored so it can always be pulled free.
+fn sweep_cube(from: Vec3, to: Vec3) -> Option<SweepHit> {
+    let delta = to - from;
+    let length = delta.length();
+    if length <= f32::EPSILON {
+        return None;
+    }
+
+    let mut first: Option<(f32, Vec3)> = None;
+    for (center, size) in WALLS {
+        let min = center - size * 0.5 - CUBE_HALF;
+        let max = center + size * 0.5 + CUBE_HALF;
+        let mut enter = f32::NEG_INFINITY;
+        let mut exit = f32::INFINITY;
+        let mut normal = Vec3::ZERO;
+        let mut misses = false;
+        for i in 0..3 {
+            if delta[i].abs() <= f32::EPSILON {
+                // Moving parallel to this slab: only inside it can we hit,
+                // and touching its face counts as outside so the cube can
+                // slide along walls it rests against.
+                misses |= from[i] <= min[i] || from[i] >= max[i];
+                continue;
+            }
+            let a = (min[i] - from[i]) / delta[i];
+            let b = (max[i] - from[i]) / delta[i];
+            let (near, far) = if a < b { (a, b) } else { (b, a) };
+            if near > enter {
+                enter = near;
+                normal = Vec3::ZERO;
+                normal[i] = -delta[i].signum();
+            }
+            exit = exit.min(far);
+        }
+        let entered = !misses && enter <= exit && (0.0..=1.0).contains(&enter);
+        if entered && first.is_none_or(|(t, _)| enter < t) {
+            first = Some((enter, normal));
+        }
+    }
+
+    first.map(|(t, normal)| SweepHit {
+        position: from + delta * (t - SKIN / length).max(0.0),
+        normal,
+    })
+}
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    // Camera
+    commands.spawn((
+        Camera3d::default(),
+        Transform::from_xyz(0.0, 9.0, 9.0).looking_at(Vec3::ZERO, Vec3::Y),
+        TransformGizmoCamera,
+    ));
+
+    // Light
+    commands.spawn((
+        DirectionalLight {
+            shadow_maps_enabled: true,
+            ..default()
+        },
+        Transform::from_xyz(6.0, 12.0, 8.0).looking_at(Vec3::ZERO, Vec3::Y),
+    ));
+
+    // Walls, including the floor
+    let wall_material = materials.add(Color::srgb(0.55, 0.55, 0.6));
+    for (center, size) in WALLS {
+        commands.spawn((
+            Mesh3d(meshes.add(Cuboid::from_size(size))),
+            MeshMaterial3d(wall_material.clone()),
+            Transform::from_translation(center),
+        ));
+    }
+
+    // Cube with gizmo
+    commands.spawn((
+        Mesh3d(meshes.add(Cuboid::from_size(CUBE_HALF * 2.0))),
+        MeshMaterial3d(materials.add(Color::srgb(0.2, 0.7, 1.0))),
+        Transform::from_xyz(-1.5, 0.5, 0.0),
+        TransformGizmoTarget,
+        GizmoActive,
+    ));
+
+    // HUD
+    commands.spawn((
+        Text::new(""),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(12.0),
+            left: Val::Px(12.0),
+            ..default()
+        },
+        Hud,
+    ));
+}
+
+fn keyboard_controls(keys: Res<ButtonInput<KeyCode>>, mut config: ResMut<TransformGizmoConfig>) {
+    if keys.just_pressed(KeyCode::KeyB) {
+        config.blocked_translation = !config.blocked_translation;
+    }
+    if keys.just_pressed(KeyCode::KeyS) {
+        config.slide_on_contact = !config.slide_on_contact;
+    }
+}
+
+fn update_hud(
+    config: Res<TransformGizmoConfig>,
+    state: Res<TransformGizmoState>,
+    mut hud: Query<&mut Text, With<Hud>>,
+) {
+    let blocked = state.drag.as_ref().is_some_and(|drag| drag.blocked);
+    for mut text in &mut hud {
+        text.0 = format!(
+            "Drag the cube into the walls\n\
+             B: blocking {}\n\
+             S: slide on contact {}\n\
+             {}",
+            if config.blocked_translation {
+                "on"
+            } else {
+                "off"
+            },
+            if config.slide_on_contact { "on" } else { "off" },
+            if blocked { "In contact" } else { "" },
+        );
+    }
+}
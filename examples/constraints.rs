@@ -0,0 +1,156 @@
+//! Per-entity gizmo constraints example.
+//!
+//! A turret only turns about Y, and a pickup only slides along the ground:
+//! their gizmos offer just those handles, whatever the style shows. The
+//! crate in the middle is unconstrained for comparison. Click an object to
+//! select it.
+//!
+//! Controls:
+//! - Q: Cycle world/local space
+//! - Arrow keys / Page Up/Down: Nudge, which respects the constraints too
+
+use bevy::prelude::*;
+use bevy_transform_tools::{
+    GizmoActive, GizmoAxis, GizmoConstraints, GizmoSelectable, NudgeSettings, TransformGizmoCamera,
+    TransformGizmoConfig, TransformGizmoPlugin, TransformGizmoSpace, TransformGizmoState,
+    TransformGizmoTarget,
+};
+
+#[derive(Component)]
+struct Hud;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(TransformGizmoPlugin)
+        .insert_resource(TransformGizmoConfig {
+            click_to_select: true,
+            keyboard_nudge: Some(NudgeSettings::default()),
+            ..default()
+        })
+        .add_systems(Startup, setup)
+        .add_systems(Update, (cycle_space, update_hud))
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    // Camera
+    commands.spawn((
+        Camera3d::default(),
+        Transform::from_xyz(0.0, 6.0, 11.0).looking_at(Vec3::ZERO, Vec3::Y),
+        TransformGizmoCamera,
+    ));
+
+    // Light
+    commands.spawn((
+        DirectionalLight {
+            shadow_maps_enabled: true,
+            ..default()
+        },
+        Transform::from_xyz(4.0, 10.0, 6.0).looking_at(Vec3::ZERO, Vec3::Y),
+    ));
+
+    // Ground
+    commands.spawn((
+        Mesh3d(meshes.add(Plane3d::new(Vec3::Y, Vec2::splat(12.0)))),
+        MeshMaterial3d(materials.add(Color::srgb(0.25, 0.3, 0.25))),
+    ));
+
+    // Turret: a squat base with a barrel, turning about Y only
+    let metal = materials.add(Color::srgb(0.5, 0.55, 0.6));
+    commands
+        .spawn((
+            Name::new("Turret"),
+            Mesh3d(meshes.add(Cylinder::new(0.8, 0.8))),
+            MeshMaterial3d(metal.clone()),
+            Transform::from_xyz(-4.0, 0.4, 0.0),
+            TransformGizmoTarget,
+            GizmoActive,
+            GizmoSelectable { radius: 1.2 },
+            GizmoConstraints::rotate_only(GizmoAxis::Y),
+        ))
+        .with_children(|turret| {
+            turret.spawn((
+                Mesh3d(meshes.add(Cuboid::new(0.25, 0.25, 1.6))),
+                MeshMaterial3d(metal),
+                Transform::from_xyz(0.0, 0.3, -0.9),
+            ));
+        });
+
+    // Unconstrained crate
+    commands.spawn((
+        Name::new("Crate"),
+        Mesh3d(meshes.add(Cuboid::from_length(1.2))),
+        MeshMaterial3d(materials.add(Color::srgb(0.6, 0.45, 0.25))),
+        Transform::from_xyz(0.0, 0.6, 0.0),
+        TransformGizmoTarget,
+        GizmoSelectable { radius: 1.0 },
+    ));
+
+    // Pickup: slides on the ground plane only
+    commands.spawn((
+        Name::new("Pickup"),
+        Mesh3d(meshes.add(Sphere::new(0.5))),
+        MeshMaterial3d(materials.add(StandardMaterial {
+            base_color: Color::srgb(1.0, 0.8, 0.2),
+            emissive: LinearRgba::rgb(0.6, 0.4, 0.0),
+            ..default()
+        })),
+        Transform::from_xyz(4.0, 0.5, 0.0),
+        TransformGizmoTarget,
+        GizmoSelectable { radius: 0.8 },
+        GizmoConstraints::translate_plane(GizmoAxis::Y),
+    ));
+
+    // HUD
+    commands.spawn((
+        Text::new(""),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(12.0),
+            left: Val::Px(12.0),
+            ..default()
+        },
+        Hud,
+    ));
+}
+
+fn cycle_space(keys: Res<ButtonInput<KeyCode>>, mut state: ResMut<TransformGizmoState>) {
+    if keys.just_pressed(KeyCode::KeyQ) {
+        state.space = match state.space {
+            TransformGizmoSpace::World => TransformGizmoSpace::Local,
+            _ => TransformGizmoSpace::World,
+        };
+    }
+}
+
+fn update_hud(
+    state: Res<TransformGizmoState>,
+    targets: Query<(&Name, Option<&GizmoConstraints>)>,
+    mut hud: Query<&mut Text, With<Hud>>,
+) {
+    let selected = state
+        .active_target
+        .and_then(|entity| targets.get(entity).ok());
+    let allowed = match selected {
+        Some((_, Some(constraints))) if constraints.lock_to_plane.is_some() => {
+            "slides on the ground"
+        }
+        Some((_, Some(_))) => "turns about Y",
+        Some((_, None)) => "unconstrained",
+        None => "nothing selected",
+    };
+    let name = selected.map_or("-", |(name, _)| name.as_str());
+    for mut text in &mut hud {
+        text.0 = format!(
+            "Click the turret, crate, or pickup\n\
+             Selected: {name} ({allowed})\n\
+             Q: {:?} space",
+            state.space,
+        );
+    }
+}
@@ -0,0 +1,131 @@
+//! Gizmo depth modes example.
+//!
+//! The target is a large box whose own mesh encloses the gizmo origin, with
+//! a pillar standing in front of it. D cycles the depth mode: everything on
+//! top, handles on top with depth-tested guides, and everything depth
+//! tested. Hold Ctrl while dragging to snap and show the snap grid; the drag
+//! feedback and grid are the guides.
+
+use bevy::prelude::*;
+use bevy_transform_tools::{
+    GizmoActive, GizmoDepthMode, TransformGizmoCamera, TransformGizmoPlugin, TransformGizmoSnap,
+    TransformGizmoStyle, TransformGizmoTarget,
+};
+
+#[derive(Component)]
+struct Hud;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(TransformGizmoPlugin)
+        .insert_resource(
+            TransformGizmoSnap::builder()
+                .translate(0.5)
+                .rotate_degrees(15.0)
+                .scale(0.25)
+                .while_key_held(KeyCode::ControlLeft)
+                .build(),
+        )
+        .add_systems(Startup, setup)
+        .add_systems(Update, (cycle_depth_mode, update_hud))
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut style: ResMut<TransformGizmoStyle>,
+) {
+    style.snap_grid.enabled = true;
+
+    // Camera
+    commands.spawn((
+        Camera3d::default(),
+        Transform::from_xyz(6.0, 5.0, 10.0).looking_at(Vec3::ZERO, Vec3::Y),
+        TransformGizmoCamera,
+    ));
+
+    // Light
+    commands.spawn((
+        DirectionalLight {
+            shadow_maps_enabled: true,
+            ..default()
+        },
+        Transform::from_xyz(10.0, 15.0, 10.0).looking_at(Vec3::ZERO, Vec3::Y),
+    ));
+
+    // Ground
+    commands.spawn((
+        Mesh3d(meshes.add(Plane3d::new(Vec3::Y, Vec2::splat(10.0)))),
+        MeshMaterial3d(materials.add(Color::srgb(0.2, 0.35, 0.18))),
+        Transform::from_xyz(0.0, -1.5, 0.0),
+    ));
+
+    // A box larger than the gizmo, so its faces hide the gizmo origin.
+    commands.spawn((
+        Mesh3d(meshes.add(Cuboid::from_length(3.0))),
+        MeshMaterial3d(materials.add(Color::srgb(0.8, 0.5, 0.2))),
+        Transform::IDENTITY,
+        TransformGizmoTarget,
+        GizmoActive,
+    ));
+
+    // A pillar between the camera and the gizmo.
+    commands.spawn((
+        Mesh3d(meshes.add(Cuboid::new(0.6, 4.0, 0.6))),
+        MeshMaterial3d(materials.add(Color::srgb(0.5, 0.5, 0.6))),
+        Transform::from_xyz(2.5, 0.5, 4.0),
+    ));
+
+    // HUD
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(10.0),
+                left: Val::Px(10.0),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.7)),
+        ))
+        .with_children(|p| {
+            p.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: FontSize::Px(14.0),
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+                Hud,
+            ));
+        });
+}
+
+fn cycle_depth_mode(keys: Res<ButtonInput<KeyCode>>, mut style: ResMut<TransformGizmoStyle>) {
+    if keys.just_pressed(KeyCode::KeyD) {
+        style.depth_mode = match style.depth_mode {
+            GizmoDepthMode::AlwaysOnTop => GizmoDepthMode::Split {
+                handles_on_top: true,
+                guides_on_top: false,
+            },
+            GizmoDepthMode::Split { .. } => GizmoDepthMode::RespectDepth,
+            GizmoDepthMode::RespectDepth => GizmoDepthMode::AlwaysOnTop,
+        };
+    }
+}
+
+fn update_hud(style: Res<TransformGizmoStyle>, mut query: Query<&mut Text, With<Hud>>) {
+    let Ok(mut text) = query.single_mut() else {
+        return;
+    };
+    let on_top = |on_top: bool| if on_top { "on top" } else { "depth tested" };
+    text.0 = format!(
+        "Handles: {} | Guides: {}\n\n\
+         [D] cycle depth mode\n\
+         hold [Ctrl] while dragging to snap",
+        on_top(style.depth_mode.handles_on_top()),
+        on_top(style.depth_mode.guides_on_top()),
+    );
+}
@@ -0,0 +1,184 @@
+//! Secondary edit channel example.
+//!
+//! A hand carries a `Socket` offset that positions an attached sword. With
+//! the Secondary channel selected, the gizmo sits at the socket and edits the
+//! `Socket` component instead of the hand's `Transform`; the sword follows.
+//! Use C to switch channels, Q to cycle the coordinate space.
+
+use bevy::prelude::*;
+use bevy_transform_tools::{
+    GizmoActive, GizmoChannelAccessor, GizmoChannelParts, GizmoEditChannel, TransformGizmoAppExt,
+    TransformGizmoCamera, TransformGizmoPlugin, TransformGizmoState, TransformGizmoTarget,
+};
+
+#[derive(Component)]
+struct Hud;
+
+/// Sword attachment point relative to the hand.
+#[derive(Component)]
+struct Socket {
+    offset: Vec3,
+    rotation: Quat,
+}
+
+/// The sword mesh, positioned from its parent's `Socket`.
+#[derive(Component)]
+struct Sword;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(TransformGizmoPlugin)
+        .register_gizmo_channel(
+            GizmoChannelAccessor::<Socket>::new(
+                |socket| Transform::from_translation(socket.offset).with_rotation(socket.rotation),
+                |socket, transform| {
+                    socket.offset = transform.translation;
+                    socket.rotation = transform.rotation;
+                },
+            )
+            .with_parts(GizmoChannelParts::TRANSLATE_ROTATE),
+        )
+        .add_systems(Startup, setup)
+        .add_systems(Update, (keyboard_controls, follow_socket, update_hud))
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    // Camera
+    commands.spawn((
+        Camera3d::default(),
+        Transform::from_xyz(4.0, 4.0, 7.0).looking_at(Vec3::new(0.0, 1.0, 0.0), Vec3::Y),
+        TransformGizmoCamera,
+    ));
+
+    // Light
+    commands.spawn((
+        DirectionalLight {
+            shadow_maps_enabled: true,
+            ..default()
+        },
+        Transform::from_xyz(10.0, 15.0, 10.0).looking_at(Vec3::ZERO, Vec3::Y),
+    ));
+
+    // Ground
+    commands.spawn((
+        Mesh3d(meshes.add(Plane3d::new(Vec3::Y, Vec2::splat(10.0)))),
+        MeshMaterial3d(materials.add(Color::srgb(0.2, 0.35, 0.18))),
+    ));
+
+    // Hand with a socket, editing the socket first
+    commands
+        .spawn((
+            Mesh3d(meshes.add(Cuboid::new(0.4, 0.4, 0.6))),
+            MeshMaterial3d(materials.add(Color::srgb(0.9, 0.7, 0.55))),
+            Transform::from_xyz(0.0, 1.0, 0.0).with_rotation(Quat::from_rotation_y(0.5)),
+            Socket {
+                offset: Vec3::new(0.0, 0.0, -0.5),
+                rotation: Quat::from_rotation_x(-0.4),
+            },
+            GizmoEditChannel::Secondary,
+            TransformGizmoTarget,
+            GizmoActive,
+        ))
+        .with_children(|hand| {
+            hand.spawn((
+                Mesh3d(meshes.add(Cuboid::new(0.08, 0.08, 1.6))),
+                MeshMaterial3d(materials.add(Color::srgb(0.8, 0.8, 0.85))),
+                Transform::default(),
+                Sword,
+            ));
+        });
+
+    // HUD
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(10.0),
+                left: Val::Px(10.0),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.7)),
+        ))
+        .with_children(|p| {
+            p.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: FontSize::Px(14.0),
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+                Hud,
+            ));
+        });
+}
+
+fn keyboard_controls(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut state: ResMut<TransformGizmoState>,
+    mut channels: Query<&mut GizmoEditChannel>,
+) {
+    if keys.just_pressed(KeyCode::KeyC) {
+        for mut channel in channels.iter_mut() {
+            *channel = match *channel {
+                GizmoEditChannel::Transform => GizmoEditChannel::Secondary,
+                GizmoEditChannel::Secondary => GizmoEditChannel::Transform,
+            };
+        }
+    }
+    if keys.just_pressed(KeyCode::KeyQ) {
+        state.space = state.space.next();
+    }
+}
+
+/// Place the sword at its parent's socket.
+fn follow_socket(
+    sockets: Query<&Socket, Changed<Socket>>,
+    mut swords: Query<(&ChildOf, &mut Transform), With<Sword>>,
+) {
+    for (child_of, mut transform) in swords.iter_mut() {
+        if let Ok(socket) = sockets.get(child_of.parent()) {
+            transform.translation = socket.offset;
+            transform.rotation = socket.rotation;
+        }
+    }
+}
+
+fn update_hud(
+    state: Res<TransformGizmoState>,
+    hands: Query<(&GizmoEditChannel, &Socket, &Transform)>,
+    mut query: Query<&mut Text, With<Hud>>,
+) {
+    let Ok(mut text) = query.single_mut() else {
+        return;
+    };
+    let Some((channel, socket, transform)) = hands.iter().next() else {
+        return;
+    };
+
+    let channel = match channel {
+        GizmoEditChannel::Transform => "Transform",
+        GizmoEditChannel::Secondary => "Socket",
+    };
+
+    text.0 = format!(
+        "Editing: {} | Space: {}\n\
+         Hand: ({:.2}, {:.2}, {:.2})\n\
+         Socket: ({:.2}, {:.2}, {:.2})\n\n\
+         [C] switch channel\n\
+         [Q] cycle space",
+        channel,
+        state.space,
+        transform.translation.x,
+        transform.translation.y,
+        transform.translation.z,
+        socket.offset.x,
+        socket.offset.y,
+        socket.offset.z,
+    );
+}
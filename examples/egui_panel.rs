@@ -0,0 +1,57 @@
+//! egui settings panel example.
+//!
+//! The side panel picks the tool and space, edits snap increments per axis,
+//! and restyles the gizmo live. Clicks on the panel never reach the gizmo,
+//! even where the panel covers it.
+
+use bevy::prelude::*;
+use bevy_transform_tools::{
+    GizmoActive, TransformGizmoCamera, TransformGizmoEguiPlugin, TransformGizmoPlugin,
+    TransformGizmoTarget,
+};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins((TransformGizmoPlugin, TransformGizmoEguiPlugin))
+        .add_systems(Startup, setup)
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    // Camera
+    commands.spawn((
+        Camera3d::default(),
+        Transform::from_xyz(5.0, 4.0, 8.0).looking_at(Vec3::ZERO, Vec3::Y),
+        TransformGizmoCamera,
+    ));
+
+    // Light
+    commands.spawn((
+        DirectionalLight {
+            shadow_maps_enabled: true,
+            ..default()
+        },
+        Transform::from_xyz(10.0, 15.0, 10.0).looking_at(Vec3::ZERO, Vec3::Y),
+    ));
+
+    // Ground
+    commands.spawn((
+        Mesh3d(meshes.add(Plane3d::new(Vec3::Y, Vec2::splat(10.0)))),
+        MeshMaterial3d(materials.add(Color::srgb(0.2, 0.35, 0.18))),
+        Transform::from_xyz(0.0, -1.0, 0.0),
+    ));
+
+    // A tilted cube, so local and world space differ.
+    commands.spawn((
+        Mesh3d(meshes.add(Cuboid::from_length(1.0))),
+        MeshMaterial3d(materials.add(Color::srgb(0.8, 0.5, 0.2))),
+        Transform::from_rotation(Quat::from_euler(EulerRot::YXZ, 0.6, 0.3, 0.0)),
+        TransformGizmoTarget,
+        GizmoActive,
+    ));
+}
@@ -0,0 +1,190 @@
+//! Endpoint pipe example.
+//!
+//! A pipe spans two walls studded with anchor points. Drag either yellow end
+//! handle: the pipe re-solves its position, rotation, and length so the
+//! dragged end follows the cursor while the other end stays put. Translate
+//! snapping is set to the anchor spacing, so ends click onto anchors, which
+//! light up while an end rests on them.
+//!
+//! Controls:
+//! - G: Toggle snapping
+
+use bevy::prelude::*;
+use bevy_transform_tools::{
+    EndpointSign, GizmoActive, GizmoAxis, GizmoEndpoints, TransformGizmoCamera,
+    TransformGizmoPlugin, TransformGizmoSnap, TransformGizmoStyle, TransformGizmoTarget,
+};
+
+/// Distance between neighbouring anchors, also used as the snap increment.
+const ANCHOR_SPACING: f32 = 0.5;
+/// X position of the left and right wall faces the anchors sit on.
+const WALL_FACES: [f32; 2] = [-3.0, 3.0];
+
+#[derive(Component)]
+struct Anchor;
+
+#[derive(Component)]
+struct Pipe;
+
+#[derive(Resource)]
+struct AnchorMaterials {
+    idle: Handle<StandardMaterial>,
+    occupied: Handle<StandardMaterial>,
+}
+
+#[derive(Component)]
+struct Hud;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(TransformGizmoPlugin)
+        .insert_resource(
+            TransformGizmoSnap::builder()
+                .translate(ANCHOR_SPACING)
+                .build(),
+        )
+        .insert_resource(TransformGizmoStyle {
+            // Only the endpoint handles: the pipe is placed by its ends.
+            show_translate: false,
+            show_rotate: false,
+            show_scale: false,
+            show_axis_lines: false,
+            ..default()
+        })
+        .add_systems(Startup, setup)
+        .add_systems(Update, (toggle_snap, light_occupied_anchors, update_hud))
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    // Camera
+    commands.spawn((
+        Camera3d::default(),
+        Transform::from_xyz(0.0, 3.0, 9.0).looking_at(Vec3::new(0.0, 1.5, 0.0), Vec3::Y),
+        TransformGizmoCamera,
+    ));
+
+    // Light
+    commands.spawn((
+        DirectionalLight {
+            shadow_maps_enabled: true,
+            ..default()
+        },
+        Transform::from_xyz(4.0, 8.0, 6.0).looking_at(Vec3::ZERO, Vec3::Y),
+    ));
+
+    // Walls with a grid of anchors on their inner faces
+    let wall_mesh = meshes.add(Cuboid::new(0.4, 4.0, 4.0));
+    let wall_material = materials.add(Color::srgb(0.5, 0.5, 0.55));
+    let anchor_mesh = meshes.add(Sphere::new(0.08));
+    let anchor_materials = AnchorMaterials {
+        idle: materials.add(Color::srgb(0.3, 0.3, 0.35)),
+        occupied: materials.add(StandardMaterial {
+            base_color: Color::srgb(1.0, 0.8, 0.2),
+            emissive: LinearRgba::rgb(2.0, 1.5, 0.3),
+            ..default()
+        }),
+    };
+    for face in WALL_FACES {
+        commands.spawn((
+            Mesh3d(wall_mesh.clone()),
+            MeshMaterial3d(wall_material.clone()),
+            Transform::from_xyz(face + face.signum() * 0.2, 2.0, 0.0),
+        ));
+        for row in 1..=7 {
+            for column in -3..=3 {
+                commands.spawn((
+                    Mesh3d(anchor_mesh.clone()),
+                    MeshMaterial3d(anchor_materials.idle.clone()),
+                    Transform::from_xyz(
+                        face,
+                        row as f32 * ANCHOR_SPACING,
+                        column as f32 * ANCHOR_SPACING,
+                    ),
+                    Anchor,
+                ));
+            }
+        }
+    }
+    commands.insert_resource(anchor_materials);
+
+    // The pipe: a cylinder is 2 units long along Y, so its ends sit at
+    // ±1 along local Y at scale 1. Scaling Y by 3 spans the 6 units between
+    // the walls.
+    commands.spawn((
+        Mesh3d(meshes.add(Cylinder::new(0.1, 2.0))),
+        MeshMaterial3d(materials.add(Color::srgb(0.7, 0.4, 0.2))),
+        Transform::from_xyz(0.0, 1.5, 0.0)
+            .with_rotation(Quat::from_rotation_z(std::f32::consts::FRAC_PI_2))
+            .with_scale(Vec3::new(1.0, 3.0, 1.0)),
+        TransformGizmoTarget,
+        GizmoActive,
+        GizmoEndpoints::new(GizmoAxis::Y, 1.0),
+        Pipe,
+    ));
+
+    // HUD
+    commands.spawn((
+        Text::new(""),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(12.0),
+            left: Val::Px(12.0),
+            ..default()
+        },
+        Hud,
+    ));
+}
+
+fn toggle_snap(keys: Res<ButtonInput<KeyCode>>, mut snap: ResMut<TransformGizmoSnap>) {
+    if keys.just_pressed(KeyCode::KeyG) {
+        let enabled = !snap.enabled;
+        snap.enable_all(enabled);
+    }
+}
+
+/// Light up anchors that one of the pipe's ends rests on.
+fn light_occupied_anchors(
+    materials: Res<AnchorMaterials>,
+    pipes: Query<(&GlobalTransform, &GizmoEndpoints), With<Pipe>>,
+    mut anchors: Query<(&Transform, &mut MeshMaterial3d<StandardMaterial>), With<Anchor>>,
+) {
+    let ends: Vec<Vec3> = pipes
+        .iter()
+        .flat_map(|(transform, endpoints)| {
+            [EndpointSign::Negative, EndpointSign::Positive]
+                .map(|end| transform.transform_point(endpoints.local_position(end)))
+        })
+        .collect();
+    for (transform, mut material) in &mut anchors {
+        let occupied = ends
+            .iter()
+            .any(|end| end.distance(transform.translation) < 1.0e-3);
+        let wanted = if occupied {
+            &materials.occupied
+        } else {
+            &materials.idle
+        };
+        if material.0 != *wanted {
+            material.0 = wanted.clone();
+        }
+    }
+}
+
+fn update_hud(snap: Res<TransformGizmoSnap>, mut hud: Query<&mut Text, With<Hud>>) {
+    if !snap.is_changed() {
+        return;
+    }
+    for mut text in &mut hud {
+        text.0 = format!(
+            "Drag either end of the pipe onto an anchor\n\
+             G: snapping {}",
+            if snap.enabled { "on" } else { "off" },
+        );
+    }
+}
@@ -0,0 +1,172 @@
+//! Many targets stress example.
+//!
+//! Spawns a grid of 500 gizmo targets, every one drawing its gizmo via
+//! `TransformGizmoStyle::draw_inactive_targets`, and shows the frame rate
+//! next to the gizmo's per-frame counters. Toggle off-screen culling and the
+//! distant-gizmo level of detail to compare; fly the camera over the grid
+//! with WASD to bring targets in and out of view.
+
+use bevy::diagnostic::{DiagnosticsStore, FrameTimeDiagnosticsPlugin};
+use bevy::prelude::*;
+use bevy_transform_tools::{
+    GizmoFrameStats, TransformGizmoCamera, TransformGizmoPlugin, TransformGizmoStyle,
+    TransformGizmoTarget,
+};
+
+/// Targets along the grid's X side.
+const GRID_X: i32 = 25;
+/// Targets along the grid's Z side.
+const GRID_Z: i32 = 20;
+/// Distance between neighbouring targets.
+const SPACING: f32 = 2.5;
+/// On-screen axis length, in pixels, below which a gizmo is drawn as a dot.
+const MIN_SCREEN_SIZE: f32 = 24.0;
+/// Camera speed in world units per second.
+const CAMERA_SPEED: f32 = 20.0;
+
+#[derive(Component)]
+struct Hud;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(FrameTimeDiagnosticsPlugin::default())
+        .add_plugins(TransformGizmoPlugin)
+        .insert_resource(TransformGizmoStyle {
+            draw_inactive_targets: true,
+            min_screen_size: MIN_SCREEN_SIZE,
+            ..default()
+        })
+        .add_systems(Startup, setup)
+        .add_systems(Update, (toggle_culling, fly_camera, update_hud))
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let extent = GRID_X as f32 * SPACING;
+
+    // Camera
+    commands.spawn((
+        Camera3d::default(),
+        Transform::from_xyz(0.0, extent * 0.4, extent * 0.6).looking_at(Vec3::ZERO, Vec3::Y),
+        TransformGizmoCamera,
+    ));
+
+    // Light
+    commands.spawn((
+        DirectionalLight::default(),
+        Transform::from_xyz(10.0, 15.0, 10.0).looking_at(Vec3::ZERO, Vec3::Y),
+    ));
+
+    // Grid of targets
+    let cube = meshes.add(Cuboid::from_length(0.5));
+    let material = materials.add(Color::srgb(0.6, 0.6, 0.65));
+    let half_x = (GRID_X - 1) as f32 * SPACING * 0.5;
+    let half_z = (GRID_Z - 1) as f32 * SPACING * 0.5;
+    for x in 0..GRID_X {
+        for z in 0..GRID_Z {
+            commands.spawn((
+                Mesh3d(cube.clone()),
+                MeshMaterial3d(material.clone()),
+                Transform::from_xyz(
+                    x as f32 * SPACING - half_x,
+                    0.25,
+                    z as f32 * SPACING - half_z,
+                ),
+                TransformGizmoTarget,
+            ));
+        }
+    }
+
+    // HUD
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(10.0),
+                left: Val::Px(10.0),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.7)),
+        ))
+        .with_children(|p| {
+            p.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: FontSize::Px(14.0),
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+                Hud,
+            ));
+        });
+}
+
+fn toggle_culling(keys: Res<ButtonInput<KeyCode>>, mut style: ResMut<TransformGizmoStyle>) {
+    if keys.just_pressed(KeyCode::KeyC) {
+        style.cull_offscreen = !style.cull_offscreen;
+    }
+    if keys.just_pressed(KeyCode::KeyL) {
+        style.min_screen_size = if style.min_screen_size > 0.0 {
+            0.0
+        } else {
+            MIN_SCREEN_SIZE
+        };
+    }
+}
+
+fn fly_camera(
+    keys: Res<ButtonInput<KeyCode>>,
+    time: Res<Time>,
+    mut cameras: Query<&mut Transform, With<TransformGizmoCamera>>,
+) {
+    let mut direction = Vec3::ZERO;
+    for (key, step) in [
+        (KeyCode::KeyW, Vec3::NEG_Z),
+        (KeyCode::KeyS, Vec3::Z),
+        (KeyCode::KeyA, Vec3::NEG_X),
+        (KeyCode::KeyD, Vec3::X),
+    ] {
+        if keys.pressed(key) {
+            direction += step;
+        }
+    }
+    for mut transform in &mut cameras {
+        transform.translation += direction.normalize_or_zero() * CAMERA_SPEED * time.delta_secs();
+    }
+}
+
+fn update_hud(
+    diagnostics: Res<DiagnosticsStore>,
+    stats: Res<GizmoFrameStats>,
+    style: Res<TransformGizmoStyle>,
+    mut query: Query<&mut Text, With<Hud>>,
+) {
+    let Ok(mut text) = query.single_mut() else {
+        return;
+    };
+    let fps = diagnostics
+        .get(&FrameTimeDiagnosticsPlugin::FPS)
+        .and_then(|fps| fps.smoothed())
+        .unwrap_or_default();
+    let on_off = |on: bool| if on { "on" } else { "off" };
+    text.0 = format!(
+        "{} targets | {fps:.0} FPS\n\
+         Hover: {:.0} us, {} targets tested\n\
+         Draw: {:.0} us, {} segments\n\n\
+         [C] off-screen culling: {}\n\
+         [L] distant gizmos as dots: {}\n\
+         [WASD] fly the camera",
+        GRID_X * GRID_Z,
+        stats.hover_time_us,
+        stats.targets_tested,
+        stats.draw_time_us,
+        stats.draw_segments,
+        on_off(style.cull_offscreen),
+        on_off(style.min_screen_size > 0.0),
+    );
+}
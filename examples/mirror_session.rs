@@ -0,0 +1,271 @@
+//! Mirroring drags in a second app through serialized transform messages.
+//!
+//! Two apps run in one process: the windowed editor, and a headless "remote"
+//! standing in for another machine in a shared session. The remote knows the
+//! crates only by their `GizmoStableId`. Every `GizmoTransformMessage` the
+//! editor's drags write is sent to it as RON text, applied to its own copy of
+//! the crate, and each drag is counted as one undo step by its `drag_id`. The
+//! remote's crates are drawn back into the window as ghosts behind the
+//! originals, so you can see the messages carry the whole motion, including
+//! turns about a crate's local axes.
+//!
+//! Controls:
+//! - Q: Cycle world/local space
+//! - E: Cycle message emission: every frame, every 100 ms, on drag end
+
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy_transform_tools::{
+    GizmoActive, GizmoMessageEmission, GizmoMessageTarget, GizmoSelectable, GizmoStableId,
+    GizmoTransformMessage, TransformGizmoCamera, TransformGizmoConfig, TransformGizmoPlugin,
+    TransformGizmoState, TransformGizmoTarget,
+};
+
+/// Where the ghost of each remote crate is drawn, relative to the original.
+const GHOST_OFFSET: Vec3 = Vec3::new(0.0, 0.0, -3.5);
+
+/// The crates both apps start with, by stable id.
+fn crates() -> [(&'static str, Transform); 2] {
+    [
+        (
+            "crates/left",
+            Transform::from_xyz(-2.0, 0.6, 1.5).with_rotation(Quat::from_rotation_y(0.6)),
+        ),
+        (
+            "crates/right",
+            Transform::from_xyz(2.0, 0.9, 1.5).with_rotation(Quat::from_euler(
+                EulerRot::XYZ,
+                0.4,
+                -0.7,
+                0.3,
+            )),
+        ),
+    ]
+}
+
+/// The headless remote app, updated once per editor frame.
+struct Remote(App);
+
+/// Serialized messages waiting to be applied by the remote.
+#[derive(Resource, Default)]
+struct Inbox(Vec<String>);
+
+/// The drags the remote has committed, one undo step each.
+#[derive(Resource, Default)]
+struct UndoSteps(Vec<u32>);
+
+/// Messages and bytes the editor has sent.
+#[derive(Resource, Default)]
+struct Sent {
+    messages: usize,
+    bytes: usize,
+}
+
+/// An editor entity showing where the remote has the crate with this id.
+#[derive(Component)]
+struct Ghost(GizmoStableId);
+
+#[derive(Component)]
+struct Hud;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(TransformGizmoPlugin)
+        .insert_resource(TransformGizmoConfig {
+            click_to_select: true,
+            message_emission: GizmoMessageEmission::EveryFrame,
+            ..default()
+        })
+        .init_resource::<Sent>()
+        .insert_non_send_resource(Remote(remote_app()))
+        .add_systems(Startup, setup)
+        .add_systems(
+            Update,
+            (
+                (send_to_remote, show_ghosts).chain(),
+                cycle_space,
+                cycle_emission,
+                update_hud,
+            ),
+        )
+        .run();
+}
+
+/// The remote: the crates by stable id, and a system applying the inbox.
+fn remote_app() -> App {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .init_resource::<Inbox>()
+        .init_resource::<UndoSteps>()
+        .add_systems(Update, apply_messages);
+    for (id, transform) in crates() {
+        app.world_mut().spawn((GizmoStableId::from(id), transform));
+    }
+    app.finish();
+    app.cleanup();
+    app
+}
+
+fn apply_messages(
+    mut inbox: ResMut<Inbox>,
+    mut undo: ResMut<UndoSteps>,
+    mut crates: Query<(&GizmoStableId, &mut Transform)>,
+) {
+    for text in inbox.0.drain(..) {
+        let message: GizmoTransformMessage =
+            ron::from_str(&text).expect("the editor sends valid messages");
+        let GizmoMessageTarget::Stable(id) = &message.target else {
+            continue;
+        };
+        if let Some((_, mut transform)) = crates.iter_mut().find(|(other, _)| *other == id) {
+            *transform = message.transform();
+        }
+        // Every message of a drag shares its id, so the drag is one step.
+        if message.ended && undo.0.last() != Some(&message.drag_id) {
+            undo.0.push(message.drag_id);
+        }
+    }
+}
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    // Camera
+    commands.spawn((
+        Camera3d::default(),
+        Transform::from_xyz(0.0, 6.0, 9.0).looking_at(Vec3::new(0.0, 0.0, -1.0), Vec3::Y),
+        TransformGizmoCamera,
+    ));
+
+    // Light
+    commands.spawn((
+        DirectionalLight::default(),
+        Transform::from_xyz(4.0, 10.0, 6.0).looking_at(Vec3::ZERO, Vec3::Y),
+    ));
+
+    // Ground
+    commands.spawn((
+        Mesh3d(meshes.add(Plane3d::new(Vec3::Y, Vec2::splat(10.0)))),
+        MeshMaterial3d(materials.add(Color::srgb(0.25, 0.3, 0.25))),
+    ));
+
+    // Crates, long on one side so turns about their own axes are easy to see
+    let mesh = meshes.add(Cuboid::new(1.6, 0.8, 0.8));
+    let wood = materials.add(Color::srgb(0.6, 0.45, 0.25));
+    let ghost = materials.add(StandardMaterial {
+        base_color: Color::srgba(0.4, 0.7, 1.0, 0.5),
+        alpha_mode: AlphaMode::Blend,
+        ..default()
+    });
+    for (i, (id, transform)) in crates().into_iter().enumerate() {
+        let mut entity = commands.spawn((
+            Name::new(id),
+            Mesh3d(mesh.clone()),
+            MeshMaterial3d(wood.clone()),
+            transform,
+            TransformGizmoTarget,
+            GizmoSelectable { radius: 1.0 },
+            GizmoStableId::from(id),
+        ));
+        if i == 0 {
+            entity.insert(GizmoActive);
+        }
+        commands.spawn((
+            Mesh3d(mesh.clone()),
+            MeshMaterial3d(ghost.clone()),
+            transform.with_translation(transform.translation + GHOST_OFFSET),
+            Ghost(GizmoStableId::from(id)),
+        ));
+    }
+
+    // HUD
+    commands.spawn((
+        Text::new(""),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(12.0),
+            left: Val::Px(12.0),
+            ..default()
+        },
+        Hud,
+    ));
+}
+
+/// Serialize this frame's messages into the remote's inbox, and run it.
+fn send_to_remote(
+    mut messages: MessageReader<GizmoTransformMessage>,
+    mut remote: NonSendMut<Remote>,
+    mut sent: ResMut<Sent>,
+) {
+    let world = remote.0.world_mut();
+    for message in messages.read() {
+        let text = ron::to_string(message).expect("messages serialize");
+        sent.messages += 1;
+        sent.bytes += text.len();
+        world.resource_mut::<Inbox>().0.push(text);
+    }
+    remote.0.update();
+}
+
+/// Move each ghost to where the remote has its crate.
+fn show_ghosts(mut remote: NonSendMut<Remote>, mut ghosts: Query<(&Ghost, &mut Transform)>) {
+    let world = remote.0.world_mut();
+    let mut crates = world.query::<(&GizmoStableId, &Transform)>();
+    for (id, remote_transform) in crates.iter(world) {
+        for (ghost, mut transform) in &mut ghosts {
+            if ghost.0 == *id {
+                *transform =
+                    remote_transform.with_translation(remote_transform.translation + GHOST_OFFSET);
+            }
+        }
+    }
+}
+
+fn cycle_space(keys: Res<ButtonInput<KeyCode>>, mut state: ResMut<TransformGizmoState>) {
+    if keys.just_pressed(KeyCode::KeyQ) {
+        state.space = state.space.next();
+    }
+}
+
+fn cycle_emission(keys: Res<ButtonInput<KeyCode>>, mut config: ResMut<TransformGizmoConfig>) {
+    if keys.just_pressed(KeyCode::KeyE) {
+        config.message_emission = match config.message_emission {
+            GizmoMessageEmission::EveryFrame => {
+                GizmoMessageEmission::Interval(Duration::from_millis(100))
+            }
+            GizmoMessageEmission::Interval(_) => GizmoMessageEmission::OnEnd,
+            _ => GizmoMessageEmission::EveryFrame,
+        };
+    }
+}
+
+fn update_hud(
+    state: Res<TransformGizmoState>,
+    config: Res<TransformGizmoConfig>,
+    sent: Res<Sent>,
+    remote: NonSend<Remote>,
+    mut hud: Query<&mut Text, With<Hud>>,
+) {
+    let undo = &remote.0.world().resource::<UndoSteps>().0;
+    let last = undo
+        .last()
+        .map_or(String::new(), |id| format!(", last drag {id}"));
+    for mut text in &mut hud {
+        text.0 = format!(
+            "Drag a crate; its ghost follows from the remote app\n\
+             Q: {:?} space\n\
+             E: {:?}\n\
+             Sent: {} messages, {} bytes\n\
+             Remote undo steps: {}{last}",
+            state.space,
+            config.message_emission,
+            sent.messages,
+            sent.bytes,
+            undo.len(),
+        );
+    }
+}
@@ -2,12 +2,14 @@
 //!
 //! Demonstrates multiple entities with the gizmo.
 //! The cubes use different rotations so world/local toggles are visible.
-//! Use T/R/S to toggle handles (and set the tool), Q to toggle space.
+//! Use T/R/S to toggle handles (and set the tool), Q to cycle the coordinate space.
+//! Click a cube to select it; click empty space to clear the selection.
+//! G turns the gizmo and click selection off and on again.
 
 use bevy::prelude::*;
 use bevy_transform_tools::{
-    TransformGizmoCamera, TransformGizmoMode, TransformGizmoPlugin, TransformGizmoSpace,
-    TransformGizmoState, TransformGizmoStyle, TransformGizmoTarget,
+    GizmoActive, GizmoSelectable, TransformGizmoCamera, TransformGizmoConfig, TransformGizmoMode,
+    TransformGizmoPlugin, TransformGizmoState, TransformGizmoStyle, TransformGizmoTarget,
 };
 
 #[derive(Component)]
@@ -17,6 +19,11 @@ fn main() {
     App::new()
         .add_plugins(DefaultPlugins)
         .add_plugins(TransformGizmoPlugin)
+        .insert_resource(TransformGizmoConfig {
+            click_to_select: true,
+            clear_selection_on_empty_click: true,
+            ..default()
+        })
         .add_systems(Startup, setup)
         .add_systems(Update, (keyboard_controls, update_hud))
         .run();
@@ -49,12 +56,12 @@ fn setup(
         MeshMaterial3d(materials.add(Color::srgb(0.2, 0.35, 0.18))),
     ));
 
-    // Three cubes - first one starts active
+    // Three cubes, selectable by clicking within their bounding spheres
     let cube = meshes.add(Cuboid::from_length(1.5));
     let colors = [
-        Color::srgb(0.9, 0.3, 0.3),
-        Color::srgb(0.3, 0.9, 0.3),
-        Color::srgb(0.3, 0.3, 0.9),
+        ("Red", Color::srgb(0.9, 0.3, 0.3)),
+        ("Green", Color::srgb(0.3, 0.9, 0.3)),
+        ("Blue", Color::srgb(0.3, 0.3, 0.9)),
     ];
     let positions = [
         Vec3::new(-4.0, 0.75, 0.0),
@@ -67,8 +74,9 @@ fn setup(
         Quat::from_rotation_z(0.7),
     ];
 
-    for ((color, pos), rotation) in colors.into_iter().zip(positions).zip(rotations) {
+    for (((name, color), pos), rotation) in colors.into_iter().zip(positions).zip(rotations) {
         commands.spawn((
+            Name::new(name),
             Mesh3d(cube.clone()),
             MeshMaterial3d(materials.add(color)),
             Transform {
@@ -77,6 +85,7 @@ fn setup(
                 ..default()
             },
             TransformGizmoTarget,
+            GizmoSelectable { radius: 1.3 },
         ));
     }
 
@@ -122,16 +131,17 @@ fn keyboard_controls(
         style.show_scale = !style.show_scale;
     }
     if keys.just_pressed(KeyCode::KeyQ) {
-        state.space = match state.space {
-            TransformGizmoSpace::World => TransformGizmoSpace::Local,
-            TransformGizmoSpace::Local => TransformGizmoSpace::World,
-        };
+        state.space = state.space.next();
+    }
+    if keys.just_pressed(KeyCode::KeyG) {
+        state.enabled = !state.enabled;
     }
 }
 
 fn update_hud(
     state: Res<TransformGizmoState>,
     style: Res<TransformGizmoStyle>,
+    selected: Query<&Name, With<GizmoActive>>,
     mut query: Query<&mut Text, With<Hud>>,
 ) {
     let Ok(mut text) = query.single_mut() else {
@@ -141,14 +151,19 @@ fn update_hud(
     let on = |b: bool| if b { "on" } else { "off" };
 
     text.0 = format!(
-        "Tool: {} | Space: {}\n\
+        "Selected: {}\n\
+         Tool: {} | Space: {}\n\
          Handles: T({}) R({}) S({})\n\n\
+         [Click] select cube, empty space to clear\n\
          [T/R/S] toggle handles (set tool)\n\
-         [Q] toggle world/local",
+         [Q] cycle space\n\
+         [G] toggle the gizmo ({})",
+        selected.iter().next().map_or("none", Name::as_str),
         state.mode,
         state.space,
         on(style.show_translate),
         on(style.show_rotate),
         on(style.show_scale),
+        on(state.enabled),
     );
 }
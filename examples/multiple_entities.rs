@@ -1,22 +1,23 @@
 //! Multiple entity selection with shared pivot example.
 //!
 //! Demonstrates manipulating multiple entities through a shared pivot point.
-//! The gizmo controls the pivot, which in turn moves all selected entities.
+//! The gizmo controls the pivot, and the `GizmoDragging` deltas it reports
+//! move all selected entities along with it.
 //!
 //! Controls:
 //! - 1/2/3: Toggle selection of cubes
 //! - A/D: Select all / Deselect all
 //! - T/R/S: Toggle translate/rotate/scale handles (also sets tool)
-//! - Q: Toggle world/local space
+//! - Q: Cycle world/local/parent/view space
 //! - P: Cycle pivot mode (First/Last/Centroid/Keep Offset)
-//! - Z/X/C: Toggle translate snap X/Y/Z
-//! - V: Toggle rotation snap
-//! - B: Toggle scale snap
+//! - Hold Ctrl while dragging: Snap (Shift as well for finer steps)
+//! - Ctrl+Z / Ctrl+Y: Undo / redo, moving the whole selection back and forth
 
 use bevy::prelude::*;
 use bevy_transform_tools::{
-    GizmoActive, TransformGizmoCamera, TransformGizmoMode, TransformGizmoPlugin,
-    TransformGizmoSnap, TransformGizmoSpace, TransformGizmoState, TransformGizmoStyle,
+    GizmoActive, GizmoDragEnded, GizmoDragStarted, GizmoDragging, GizmoEdit, GizmoEditChannel,
+    GizmoHistory, TransformGizmoCamera, TransformGizmoMode, TransformGizmoPlugin,
+    TransformGizmoSnap, TransformGizmoState, TransformGizmoStyle, TransformGizmoSystems,
     TransformGizmoTarget,
 };
 use std::{collections::HashMap, fmt};
@@ -73,16 +74,6 @@ impl fmt::Display for PivotMode {
     }
 }
 
-#[derive(Clone, Copy)]
-struct PivotFrame {
-    translation: Vec3,
-    rotation: Quat,
-    scale: Vec3,
-}
-
-#[derive(Resource, Default)]
-struct PivotHistory(Option<PivotFrame>);
-
 #[derive(Clone, Copy)]
 struct PivotOffset {
     translation: Vec3,
@@ -147,20 +138,34 @@ fn main() {
         .init_resource::<Selection>()
         .init_resource::<PivotMode>()
         .init_resource::<PivotOffsets>()
-        .init_resource::<PivotHistory>()
+        .insert_resource(GizmoHistory::new(64))
+        .insert_resource(
+            TransformGizmoSnap::builder()
+                .translate(0.5)
+                .rotate_degrees(15.0)
+                .scale(0.25)
+                .while_key_held(KeyCode::ControlLeft)
+                .fine_modifier(KeyCode::ShiftLeft, 5.0)
+                .build(),
+        )
         .add_systems(Startup, setup)
         .add_systems(
             Update,
             (
                 handle_mode_keys,
                 handle_pivot_mode_key,
-                handle_snap_keys,
+                handle_undo_keys,
                 selection_input,
                 update_pivot,
-                apply_pivot_delta,
                 update_hud,
             ),
         )
+        .add_systems(
+            Update,
+            (record_selection_history, apply_pivot_delta)
+                .chain()
+                .after(TransformGizmoSystems::Input),
+        )
         .run();
 }
 
@@ -265,10 +270,7 @@ fn handle_mode_keys(
         style.show_scale = !style.show_scale;
     }
     if keys.just_pressed(KeyCode::KeyQ) {
-        state.space = match state.space {
-            TransformGizmoSpace::World => TransformGizmoSpace::Local,
-            TransformGizmoSpace::Local => TransformGizmoSpace::World,
-        };
+        state.space = state.space.next();
     }
 }
 
@@ -297,33 +299,6 @@ fn handle_pivot_mode_key(
     }
 }
 
-fn handle_snap_keys(keys: Res<ButtonInput<KeyCode>>, mut snap: ResMut<TransformGizmoSnap>) {
-    if keys.just_pressed(KeyCode::KeyZ) {
-        snap.translate.x = snap.translate.x.map_or(Some(0.5), |_| None);
-    }
-    if keys.just_pressed(KeyCode::KeyX) {
-        snap.translate.y = snap.translate.y.map_or(Some(0.5), |_| None);
-    }
-    if keys.just_pressed(KeyCode::KeyC) {
-        snap.translate.z = snap.translate.z.map_or(Some(0.5), |_| None);
-    }
-
-    if keys.just_pressed(KeyCode::KeyV) {
-        let val = snap.rotate.x.map_or(Some(15f32.to_radians()), |_| None);
-        snap.rotate.x = val;
-        snap.rotate.y = val;
-        snap.rotate.z = val;
-    }
-
-    // Scale snap
-    if keys.just_pressed(KeyCode::KeyB) {
-        let val = snap.scale.x.map_or(Some(0.25), |_| None);
-        snap.scale.x = val;
-        snap.scale.y = val;
-        snap.scale.z = val;
-    }
-}
-
 fn selection_input(
     keys: Res<ButtonInput<KeyCode>>,
     pivot_mode: Res<PivotMode>,
@@ -447,77 +422,110 @@ fn update_pivot(
     }
 }
 
+fn handle_undo_keys(keys: Res<ButtonInput<KeyCode>>, mut history: ResMut<GizmoHistory>) {
+    if !keys.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]) {
+        return;
+    }
+    if keys.just_pressed(KeyCode::KeyZ) {
+        history.undo_requested = true;
+    }
+    if keys.just_pressed(KeyCode::KeyY) {
+        history.redo_requested = true;
+    }
+}
+
+/// Add the selection's moves to the history entry of the pivot drag that
+/// carried them, so undo restores the cubes and not just the pivot.
+fn record_selection_history(
+    mut started: MessageReader<GizmoDragStarted>,
+    mut ended: MessageReader<GizmoDragEnded>,
+    selection: Res<Selection>,
+    mut history: ResMut<GizmoHistory>,
+    mut before: Local<Vec<(Entity, Transform)>>,
+    targets: Query<&Transform, SelectableOnly>,
+) {
+    if started.read().count() > 0 {
+        before.clear();
+        before.extend(
+            selection
+                .0
+                .iter()
+                .filter_map(|&entity| Some((entity, *targets.get(entity).ok()?))),
+        );
+    }
+    for ended in ended.read() {
+        let Some(entry) = history.drag_entry_mut(ended) else {
+            continue;
+        };
+        entry
+            .edits
+            .extend(before.drain(..).filter_map(|(entity, before)| {
+                let after = *targets.get(entity).ok()?;
+                (after != before).then_some(GizmoEdit {
+                    entity,
+                    channel: GizmoEditChannel::Transform,
+                    before,
+                    after,
+                })
+            }));
+    }
+}
+
+/// Carry the selection along with this frame's drag of the pivot.
 fn apply_pivot_delta(
-    state: Res<TransformGizmoState>,
+    mut dragging: MessageReader<GizmoDragging>,
     selection: Res<Selection>,
     pivot_mode: Res<PivotMode>,
     mut offsets: ResMut<PivotOffsets>,
-    mut history: ResMut<PivotHistory>,
-    pivots: Query<&Transform, PivotOnly>,
+    pivots: Query<(Entity, &Transform), PivotOnly>,
     mut targets: Query<(Entity, &mut Transform), SelectableOnly>,
 ) {
-    let current = {
-        let Some(pivot) = pivots.iter().next() else {
-            return;
-        };
-        PivotFrame {
-            translation: pivot.translation,
-            rotation: pivot.rotation,
-            scale: pivot.scale,
-        }
+    let Ok((pivot_entity, pivot)) = pivots.single() else {
+        return;
     };
-
-    if state.drag.is_none() {
-        history.0 = Some(current);
+    let deltas: Vec<GizmoDragging> = dragging
+        .read()
+        .filter(|delta| delta.handle.target == pivot_entity)
+        .copied()
+        .collect();
+    if deltas.is_empty() {
         return;
     }
 
     if matches!(*pivot_mode, PivotMode::KeepOffset) {
-        let pivot = Transform {
-            translation: current.translation,
-            rotation: current.rotation,
-            scale: current.scale,
-        };
         for &entity in &selection.0 {
             if let Ok((_entity, mut t)) = targets.get_mut(entity) {
                 let offset = offsets.0.get(&entity).copied().unwrap_or_else(|| {
-                    let captured = capture_offset(&pivot, &t);
+                    let captured = capture_offset(pivot, &t);
                     offsets.0.insert(entity, captured);
                     captured
                 });
-                apply_offset(&pivot, &offset, &mut t);
+                apply_offset(pivot, &offset, &mut t);
             }
         }
-        history.0 = Some(current);
         return;
     }
 
-    let Some(last) = history.0 else {
-        history.0 = Some(current);
-        return;
-    };
-
-    let delta_r = current.rotation * last.rotation.conjugate();
-    let delta_s = current.scale / last.scale;
-    let old_p = last.translation;
-    let new_p = current.translation;
-
-    for &entity in &selection.0 {
-        if let Ok((_entity, mut t)) = targets.get_mut(entity) {
-            let mut offset = t.translation - old_p;
-            offset = Vec3::new(
-                offset.x * delta_s.x,
-                offset.y * delta_s.y,
-                offset.z * delta_s.z,
-            );
-            offset = delta_r * offset;
-            t.translation = new_p + offset;
-            t.rotation = delta_r * t.rotation;
-            t.scale *= delta_s;
+    // Replay the deltas from where the pivot was before them.
+    let mut old_p = pivot.translation
+        - deltas
+            .iter()
+            .map(|delta| delta.delta_translation)
+            .sum::<Vec3>();
+    for delta in deltas {
+        let new_p = old_p + delta.delta_translation;
+        for &entity in &selection.0 {
+            if let Ok((_entity, mut t)) = targets.get_mut(entity) {
+                let mut offset = t.translation - old_p;
+                offset *= delta.delta_scale;
+                offset = delta.delta_rotation * offset;
+                t.translation = new_p + offset;
+                t.rotation = delta.delta_rotation * t.rotation;
+                t.scale *= delta.delta_scale;
+            }
         }
+        old_p = new_p;
     }
-
-    history.0 = Some(current);
 }
 
 fn update_hud(
@@ -525,6 +533,7 @@ fn update_hud(
     style: Res<TransformGizmoStyle>,
     selection: Res<Selection>,
     snap: Res<TransformGizmoSnap>,
+    keys: Res<ButtonInput<KeyCode>>,
     pivot_mode: Res<PivotMode>,
     mut query: Query<&mut Text, With<Hud>>,
 ) {
@@ -533,28 +542,33 @@ fn update_hud(
     };
 
     let on = |b: bool| if b { "on" } else { "off" };
+    let resolved = snap.resolve(&keys);
+    let snapping = if resolved.translate.x.is_none() {
+        "off"
+    } else if resolved.translate.x != snap.translate.x {
+        "fine"
+    } else {
+        "on"
+    };
 
     text.0 = format!(
         "Tool: {} | Space: {}\n\
          Handles: T({}) R({}) S({})\n\
-         Pivot: {} | Snap: T({}/{}/{}) R({}) S({})\n\
+         Pivot: {} | Snap: {}\n\
          Selected: {}\n\n\
          [1/2/3] toggle cubes  [A] all  [D] none\n\
          [T/R/S] toggle handles (set tool)\n\
-         [Q] toggle world/local\n\
+         [Q] cycle space\n\
          [P] pivot mode\n\
-         [Z/X/C] snap translate  [V] snap rotate  [B] snap scale",
+         hold [Ctrl] to snap, [Ctrl+Shift] for fine steps\n\
+         [Ctrl+Z/Ctrl+Y] undo/redo",
         state.mode,
         state.space,
         on(style.show_translate),
         on(style.show_rotate),
         on(style.show_scale),
         *pivot_mode,
-        on(snap.translate.x.is_some()),
-        on(snap.translate.y.is_some()),
-        on(snap.translate.z.is_some()),
-        on(snap.rotate.x.is_some()),
-        on(snap.scale.x.is_some()),
+        snapping,
         selection.0.len(),
     );
 }
@@ -0,0 +1,238 @@
+//! Off-center projection example.
+//!
+//! Renders through a custom off-center perspective projection (as used for
+//! CAVE-like multi-display setups) and draws a marker that stays a constant
+//! 48 pixels wide around the active target, sized with
+//! `world_units_per_pixel_at`. Because the helper goes through the actual
+//! projection matrix, the marker keeps its pixel size even though the frustum
+//! is asymmetric.
+//! Use the arrow keys to shift the projection center, W/S to dolly the camera.
+
+use bevy::camera::{CameraProjection, SubCameraView};
+use bevy::math::Vec3A;
+use bevy::prelude::*;
+use bevy_transform_tools::{
+    world_units_per_pixel_at, GizmoActive, TransformGizmoCamera, TransformGizmoPlugin,
+    TransformGizmoTarget,
+};
+
+/// Marker size on screen, in logical pixels.
+const MARKER_PIXELS: f32 = 48.0;
+
+#[derive(Component)]
+struct Hud;
+
+/// Perspective projection whose center is shifted in normalized device space.
+#[derive(Debug, Clone)]
+struct OffCenterProjection {
+    perspective: PerspectiveProjection,
+    shift: Vec2,
+}
+
+impl OffCenterProjection {
+    fn apply_shift(&self, mut clip_from_view: Mat4) -> Mat4 {
+        // clip.xy += shift * clip.w, with clip.w = -view.z.
+        clip_from_view.z_axis.x -= self.shift.x;
+        clip_from_view.z_axis.y -= self.shift.y;
+        clip_from_view
+    }
+}
+
+impl CameraProjection for OffCenterProjection {
+    fn get_clip_from_view(&self) -> Mat4 {
+        self.apply_shift(self.perspective.get_clip_from_view())
+    }
+
+    fn get_clip_from_view_for_sub(&self, sub_view: &SubCameraView) -> Mat4 {
+        self.apply_shift(self.perspective.get_clip_from_view_for_sub(sub_view))
+    }
+
+    fn update(&mut self, width: f32, height: f32) {
+        self.perspective.update(width, height);
+    }
+
+    fn far(&self) -> f32 {
+        self.perspective.far()
+    }
+
+    fn get_frustum_corners(&self, z_near: f32, z_far: f32) -> [Vec3A; 8] {
+        self.perspective.get_frustum_corners(z_near, z_far)
+    }
+}
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(TransformGizmoPlugin)
+        .add_systems(Startup, setup)
+        .add_systems(Update, (camera_controls, draw_marker, update_hud))
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    // Camera with an off-center projection
+    commands.spawn((
+        Camera3d::default(),
+        Projection::custom(OffCenterProjection {
+            perspective: PerspectiveProjection::default(),
+            shift: Vec2::new(0.4, -0.2),
+        }),
+        Transform::from_xyz(4.0, 5.0, 9.0).looking_at(Vec3::ZERO, Vec3::Y),
+        TransformGizmoCamera,
+    ));
+
+    // Light
+    commands.spawn((
+        DirectionalLight {
+            shadow_maps_enabled: true,
+            ..default()
+        },
+        Transform::from_xyz(10.0, 15.0, 10.0).looking_at(Vec3::ZERO, Vec3::Y),
+    ));
+
+    // Ground
+    commands.spawn((
+        Mesh3d(meshes.add(Plane3d::new(Vec3::Y, Vec2::splat(10.0)))),
+        MeshMaterial3d(materials.add(Color::srgb(0.2, 0.35, 0.18))),
+    ));
+
+    // Cube with gizmo
+    commands.spawn((
+        Mesh3d(meshes.add(Cuboid::from_length(1.0))),
+        MeshMaterial3d(materials.add(Color::srgb(0.2, 0.7, 1.0))),
+        Transform::from_xyz(0.0, 0.5, 0.0),
+        TransformGizmoTarget,
+        GizmoActive,
+    ));
+
+    // HUD
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(10.0),
+                left: Val::Px(10.0),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.7)),
+        ))
+        .with_children(|p| {
+            p.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: FontSize::Px(14.0),
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+                Hud,
+            ));
+        });
+}
+
+fn camera_controls(
+    keys: Res<ButtonInput<KeyCode>>,
+    time: Res<Time>,
+    mut cameras: Query<(&mut Transform, &mut Projection), With<TransformGizmoCamera>>,
+) {
+    let Ok((mut transform, mut projection)) = cameras.single_mut() else {
+        return;
+    };
+
+    let dt = time.delta_secs();
+    let forward = *transform.forward();
+    if keys.pressed(KeyCode::KeyW) {
+        transform.translation += forward * 5.0 * dt;
+    }
+    if keys.pressed(KeyCode::KeyS) {
+        transform.translation -= forward * 5.0 * dt;
+    }
+
+    let mut shift = Vec2::ZERO;
+    if keys.pressed(KeyCode::ArrowLeft) {
+        shift.x -= 1.0;
+    }
+    if keys.pressed(KeyCode::ArrowRight) {
+        shift.x += 1.0;
+    }
+    if keys.pressed(KeyCode::ArrowDown) {
+        shift.y -= 1.0;
+    }
+    if keys.pressed(KeyCode::ArrowUp) {
+        shift.y += 1.0;
+    }
+    if shift != Vec2::ZERO {
+        if let Projection::Custom(custom) = projection.as_mut() {
+            if let Some(off_center) = custom.get_mut::<OffCenterProjection>() {
+                off_center.shift += shift * dt;
+            }
+        }
+    }
+}
+
+/// Draw a camera-facing square that stays `MARKER_PIXELS` wide on screen.
+fn draw_marker(
+    cameras: Query<(&Camera, &GlobalTransform), With<TransformGizmoCamera>>,
+    targets: Query<&GlobalTransform, With<GizmoActive>>,
+    mut gizmos: Gizmos,
+) {
+    let Ok((camera, camera_transform)) = cameras.single() else {
+        return;
+    };
+    for target in &targets {
+        let center = target.translation();
+        let Some(per_pixel) = world_units_per_pixel_at(center, camera, camera_transform) else {
+            continue;
+        };
+        let half = per_pixel * (MARKER_PIXELS * 0.5);
+        let right: Vec3 = camera_transform.right().into();
+        let up: Vec3 = camera_transform.up().into();
+        let r = right * half.x;
+        let u = up * half.y;
+        let color = Color::srgb(1.0, 0.85, 0.2);
+        gizmos.line(center - r - u, center + r - u, color);
+        gizmos.line(center + r - u, center + r + u, color);
+        gizmos.line(center + r + u, center - r + u, color);
+        gizmos.line(center - r + u, center - r - u, color);
+    }
+}
+
+fn update_hud(
+    cameras: Query<(&Camera, &GlobalTransform, &Projection), With<TransformGizmoCamera>>,
+    targets: Query<&GlobalTransform, With<GizmoActive>>,
+    mut query: Query<&mut Text, With<Hud>>,
+) {
+    let Ok(mut text) = query.single_mut() else {
+        return;
+    };
+    let Ok((camera, camera_transform, projection)) = cameras.single() else {
+        return;
+    };
+
+    let shift = match projection {
+        Projection::Custom(custom) => custom
+            .get::<OffCenterProjection>()
+            .map_or(Vec2::ZERO, |p| p.shift),
+        _ => Vec2::ZERO,
+    };
+    let per_pixel = targets
+        .iter()
+        .next()
+        .and_then(|t| world_units_per_pixel_at(t.translation(), camera, camera_transform));
+
+    text.0 = format!(
+        "Projection shift: ({:.2}, {:.2})\n\
+         World units per pixel: {}\n\n\
+         [Arrows] shift projection center\n\
+         [W/S] dolly camera",
+        shift.x,
+        shift.y,
+        per_pixel.map_or("behind camera".to_string(), |f| format!(
+            "{:.4} x {:.4}",
+            f.x, f.y
+        )),
+    );
+}
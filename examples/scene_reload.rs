@@ -0,0 +1,195 @@
+//! Scene reload example.
+//!
+//! Three crates carry a `GizmoStableId`. Pick one, give it an off-center
+//! visual pivot, then reload the scene: every crate is despawned and spawned
+//! again with nothing but its id, yet the pivot and the selection come back
+//! from the `GizmoPreferenceStore`.
+//!
+//! Controls:
+//! - Tab: Select the next crate
+//! - V: Toggle an off-center visual pivot on the selected crate
+//! - R: Reload the scene
+
+use bevy::prelude::*;
+use bevy_transform_tools::{
+    GizmoActive, GizmoPreferenceStore, GizmoStableId, GizmoVisualPivot, TransformGizmoCamera,
+    TransformGizmoPlugin, TransformGizmoTarget,
+};
+
+/// Scene node paths, standing in for ids a real scene format would provide.
+const CRATES: [(&str, Vec3); 3] = [
+    ("props/crate_a", Vec3::new(-2.5, 0.5, 0.0)),
+    ("props/crate_b", Vec3::new(0.0, 0.5, 0.0)),
+    ("props/crate_c", Vec3::new(2.5, 0.5, 0.0)),
+];
+
+/// Everything a reload despawns.
+#[derive(Component)]
+struct SceneRoot;
+
+#[derive(Resource)]
+struct CrateAssets {
+    mesh: Handle<Mesh>,
+    material: Handle<StandardMaterial>,
+}
+
+#[derive(Resource, Default)]
+struct Reloads(u32);
+
+#[derive(Component)]
+struct Hud;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(TransformGizmoPlugin)
+        .init_resource::<Reloads>()
+        .add_systems(Startup, (setup, spawn_scene).chain())
+        .add_systems(Update, (keyboard_controls, update_hud))
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    // Camera
+    commands.spawn((
+        Camera3d::default(),
+        Transform::from_xyz(0.0, 4.0, 8.0).looking_at(Vec3::ZERO, Vec3::Y),
+        TransformGizmoCamera,
+    ));
+
+    // Light
+    commands.spawn((
+        DirectionalLight {
+            shadow_maps_enabled: true,
+            ..default()
+        },
+        Transform::from_xyz(4.0, 8.0, 4.0).looking_at(Vec3::ZERO, Vec3::Y),
+    ));
+
+    // Ground
+    commands.spawn((
+        Mesh3d(meshes.add(Plane3d::new(Vec3::Y, Vec2::splat(10.0)))),
+        MeshMaterial3d(materials.add(Color::srgb(0.3, 0.3, 0.35))),
+    ));
+
+    commands.insert_resource(CrateAssets {
+        mesh: meshes.add(Cuboid::from_length(1.0)),
+        material: materials.add(Color::srgb(0.7, 0.5, 0.3)),
+    });
+
+    // HUD
+    commands.spawn((
+        Text::new(""),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(12.0),
+            left: Val::Px(12.0),
+            ..default()
+        },
+        Hud,
+    ));
+}
+
+/// Spawn the crates the way a scene loader would: ids and geometry only, no
+/// gizmo settings.
+fn spawn_scene(mut commands: Commands, assets: Res<CrateAssets>) {
+    spawn_crates(&mut commands, &assets);
+}
+
+fn spawn_crates(commands: &mut Commands, assets: &CrateAssets) {
+    for (id, position) in CRATES {
+        commands.spawn((
+            Mesh3d(assets.mesh.clone()),
+            MeshMaterial3d(assets.material.clone()),
+            Transform::from_translation(position),
+            TransformGizmoTarget,
+            GizmoStableId::from(id),
+            SceneRoot,
+        ));
+    }
+}
+
+fn keyboard_controls(
+    mut commands: Commands,
+    keys: Res<ButtonInput<KeyCode>>,
+    assets: Res<CrateAssets>,
+    mut reloads: ResMut<Reloads>,
+    scene: Query<Entity, With<SceneRoot>>,
+    crates: Query<(
+        Entity,
+        &GizmoStableId,
+        Has<GizmoActive>,
+        Has<GizmoVisualPivot>,
+    )>,
+) {
+    if keys.just_pressed(KeyCode::KeyR) {
+        for entity in &scene {
+            commands.entity(entity).despawn();
+        }
+        spawn_crates(&mut commands, &assets);
+        reloads.0 += 1;
+        return;
+    }
+
+    let mut crates: Vec<_> = crates.iter().collect();
+    crates.sort_by(|a, b| a.1 .0.cmp(&b.1 .0));
+    let selected = crates.iter().position(|(_, _, active, _)| *active);
+
+    if keys.just_pressed(KeyCode::Tab) && !crates.is_empty() {
+        let next = selected.map_or(0, |index| (index + 1) % crates.len());
+        if let Some(index) = selected {
+            commands.entity(crates[index].0).remove::<GizmoActive>();
+        }
+        commands.entity(crates[next].0).insert(GizmoActive);
+    }
+
+    if keys.just_pressed(KeyCode::KeyV) {
+        if let Some(&(entity, _, _, has_pivot)) = selected.map(|index| &crates[index]) {
+            if has_pivot {
+                commands.entity(entity).remove::<GizmoVisualPivot>();
+            } else {
+                commands
+                    .entity(entity)
+                    .insert(GizmoVisualPivot::manual(Vec3::new(0.5, 0.5, 0.5)));
+            }
+        }
+    }
+}
+
+fn update_hud(
+    store: Res<GizmoPreferenceStore>,
+    reloads: Res<Reloads>,
+    mut hud: Query<&mut Text, With<Hud>>,
+) {
+    if !store.is_changed() && !reloads.is_changed() {
+        return;
+    }
+    let mut ids: Vec<_> = store
+        .iter()
+        .map(|(id, preferences)| {
+            let pivot = if preferences.visual_pivot.is_some() {
+                "pivot"
+            } else {
+                "-"
+            };
+            format!("  {}: {pivot}", id.0)
+        })
+        .collect();
+    ids.sort();
+    for mut text in &mut hud {
+        text.0 = format!(
+            "Tab: select next crate\n\
+             V: toggle visual pivot\n\
+             R: reload scene (reloaded {} times)\n\
+             Active: {}\n\
+             Stored:\n{}",
+            reloads.0,
+            store.active_id().map_or("none", |id| id.0.as_str()),
+            ids.join("\n"),
+        );
+    }
+}
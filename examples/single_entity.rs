@@ -1,23 +1,46 @@
 //! Single entity gizmo example.
 //!
 //! Demonstrates the basic usage of the transform gizmo with a single entity.
-//! Use T/R/S to toggle handles (and set the active tool), Q to toggle coordinate space.
+//! Use T/R/S to toggle handles (and set the active tool), Q to cycle the coordinate space.
+//! Zoom with the mouse wheel, Z to keep the gizmo a constant size on screen,
+//! and O to switch between perspective and orthographic projection.
+//! While dragging, X/Y/Z constrain the drag to an axis (with Shift, to the
+//! plane perpendicular to it) and Escape cancels it.
+//! C cycles the rotation handles between arcs, full rings, and full rings with
+//! a screen-facing outer ring, and M switches between line and solid mesh
+//! handles. G turns the whole gizmo off and on again, as a game would while
+//! playing. The HUD shows how far the current drag has moved, turned, or
+//! scaled the cube, and a tooltip placed from `GizmoScreenInfo` names the
+//! hovered handle and the value it edits.
 
+use bevy::camera::ScalingMode;
+use bevy::input::mouse::AccumulatedMouseScroll;
 use bevy::prelude::*;
 use bevy_transform_tools::{
-    GizmoActive, TransformGizmoCamera, TransformGizmoMode, TransformGizmoPlugin,
-    TransformGizmoSpace, TransformGizmoState, TransformGizmoStyle, TransformGizmoTarget,
+    GizmoActive, GizmoAxis, GizmoOperation, GizmoRenderMode, GizmoScaleMode, GizmoScreenInfo,
+    RotationRingStyle, TransformGizmoCamera, TransformGizmoMode, TransformGizmoPlugin,
+    TransformGizmoState, TransformGizmoStyle, TransformGizmoSystems, TransformGizmoTarget,
 };
 
+/// On-screen length of the gizmo axes in screen-space mode, in logical pixels.
+const SCREEN_AXIS_PIXELS: f32 = 120.0;
+
 #[derive(Component)]
 struct Hud;
 
+#[derive(Component)]
+struct Tooltip;
+
 fn main() {
     App::new()
         .add_plugins(DefaultPlugins)
         .add_plugins(TransformGizmoPlugin)
         .add_systems(Startup, setup)
-        .add_systems(Update, (keyboard_controls, update_hud))
+        .add_systems(Update, (keyboard_controls, zoom_camera, update_hud))
+        .add_systems(
+            PostUpdate,
+            place_tooltip.after(TransformGizmoSystems::Hover),
+        )
         .run();
 }
 
@@ -79,6 +102,24 @@ fn setup(
                 Hud,
             ));
         });
+
+    // Tooltip, moved next to the hovered handle every frame
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            padding: UiRect::axes(Val::Px(6.0), Val::Px(3.0)),
+            ..default()
+        },
+        BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.8)),
+        Text::new(""),
+        TextFont {
+            font_size: FontSize::Px(13.0),
+            ..default()
+        },
+        TextColor(Color::WHITE),
+        Visibility::Hidden,
+        Tooltip,
+    ));
 }
 
 fn keyboard_controls(
@@ -99,9 +140,68 @@ fn keyboard_controls(
         style.show_scale = !style.show_scale;
     }
     if keys.just_pressed(KeyCode::KeyQ) {
-        state.space = match state.space {
-            TransformGizmoSpace::World => TransformGizmoSpace::Local,
-            TransformGizmoSpace::Local => TransformGizmoSpace::World,
+        state.space = state.space.next();
+    }
+    // Z constrains the drag to the Z axis while one is active.
+    if keys.just_pressed(KeyCode::KeyZ) && state.drag.is_none() {
+        style.scale_mode = match style.scale_mode {
+            GizmoScaleMode::Fixed(_) => GizmoScaleMode::ScreenSpace(SCREEN_AXIS_PIXELS),
+            GizmoScaleMode::ScreenSpace(_) => GizmoScaleMode::Fixed(1.0),
+        };
+    }
+    if keys.just_pressed(KeyCode::KeyC) {
+        (style.rotation_ring_style, style.show_rotate_view) =
+            match (style.rotation_ring_style, style.show_rotate_view) {
+                (RotationRingStyle::Arc { .. }, _) => (RotationRingStyle::FullCircle, false),
+                (RotationRingStyle::FullCircle, false) => (RotationRingStyle::FullCircle, true),
+                (RotationRingStyle::FullCircle, true) => (RotationRingStyle::default(), false),
+            };
+        // Full rings read better with more segments than short arcs.
+        style.rotation_arc_segments = match style.rotation_ring_style {
+            RotationRingStyle::Arc { .. } => 20,
+            RotationRingStyle::FullCircle => 64,
+        };
+    }
+    if keys.just_pressed(KeyCode::KeyM) {
+        style.render_mode = match style.render_mode {
+            GizmoRenderMode::Lines => GizmoRenderMode::Mesh,
+            GizmoRenderMode::Mesh => GizmoRenderMode::Lines,
+        };
+    }
+    // Turning the gizmo off ends a drag in progress; back on, it returns on
+    // the same cube with the same tool.
+    if keys.just_pressed(KeyCode::KeyG) {
+        state.enabled = !state.enabled;
+    }
+}
+
+/// Dolly the camera with the mouse wheel and toggle its projection with O.
+/// Orthographic views are sized to the camera distance so zooming works in
+/// both.
+fn zoom_camera(
+    keys: Res<ButtonInput<KeyCode>>,
+    scroll: Res<AccumulatedMouseScroll>,
+    mut cameras: Query<(&mut Transform, &mut Projection), With<TransformGizmoCamera>>,
+) {
+    let Ok((mut transform, mut projection)) = cameras.single_mut() else {
+        return;
+    };
+    if scroll.delta.y != 0.0 {
+        let distance =
+            (transform.translation.length() * (1.0 - scroll.delta.y * 0.1)).clamp(2.0, 200.0);
+        transform.translation = transform.translation.normalize() * distance;
+    }
+    let distance = transform.translation.length();
+
+    if keys.just_pressed(KeyCode::KeyO) {
+        *projection = match *projection {
+            Projection::Perspective(_) => Projection::from(OrthographicProjection::default_3d()),
+            _ => Projection::from(PerspectiveProjection::default()),
+        };
+    }
+    if let Projection::Orthographic(ortho) = projection.as_mut() {
+        ortho.scaling_mode = ScalingMode::FixedVertical {
+            viewport_height: distance * 0.8,
         };
     }
 }
@@ -118,13 +218,92 @@ fn update_hud(
     let on = |b: bool| if b { "on" } else { "off" };
 
     text.0 = format!(
-        "Space: {}\n\
-         Handles: T({}) R({}) S({})\n\n\
+        "Gizmo: {}\n\
+         Space: {}\n\
+         Handles: T({}) R({}) S({})\n\
+         Gizmo size: {}\n\
+         Rotation rings: {}\n\
+         Handles drawn as: {}\n\
+         Drag: {}\n\n\
          [T/R/S] toggle handles (set tool)\n\
-         [Q] toggle world/local",
+         [Q] cycle space\n\
+         [Wheel] zoom  [Z] toggle screen-space size  [O] toggle orthographic\n\
+         [C] cycle rotation rings  [M] toggle mesh handles\n\
+         [G] toggle the gizmo\n\
+         While dragging: [X/Y/Z] constrain (Shift: plane)  [Esc] cancel",
+        on(state.enabled),
         state.space,
         on(style.show_translate),
         on(style.show_rotate),
         on(style.show_scale),
+        match style.scale_mode {
+            GizmoScaleMode::Fixed(_) => "fixed",
+            GizmoScaleMode::ScreenSpace(_) => "screen space",
+        },
+        match (style.rotation_ring_style, style.show_rotate_view) {
+            (RotationRingStyle::Arc { .. }, _) => "arcs",
+            (RotationRingStyle::FullCircle, false) => "full",
+            (RotationRingStyle::FullCircle, true) => "full + view ring",
+        },
+        match style.render_mode {
+            GizmoRenderMode::Lines => "lines",
+            GizmoRenderMode::Mesh => "meshes",
+        },
+        state
+            .drag_delta()
+            .map_or_else(|| "-".to_string(), |delta| delta.to_string()),
     );
 }
+
+/// Show what the hovered handle does, and the value it edits, just past the
+/// handle's clickable area.
+fn place_tooltip(
+    info: Res<GizmoScreenInfo>,
+    targets: Query<&Transform, With<TransformGizmoTarget>>,
+    mut tooltip: Query<(&mut Node, &mut Text, &mut Visibility), With<Tooltip>>,
+) {
+    let Ok((mut node, mut text, mut visibility)) = tooltip.single_mut() else {
+        return;
+    };
+    let hovered = info
+        .hovered
+        .and_then(|(handle, screen)| Some((handle, screen, targets.get(handle.target).ok()?)));
+    let Some((handle, screen, transform)) = hovered else {
+        *visibility = Visibility::Hidden;
+        return;
+    };
+
+    let axis = handle.axis;
+    let component = |v: Vec3| v[axis.index()];
+    let (yaw, pitch, roll) = transform.rotation.to_euler(EulerRot::YXZ);
+    text.0 = match handle.op {
+        GizmoOperation::TranslateAxis => {
+            format!("Move along {axis}: {:.2}", component(transform.translation))
+        }
+        GizmoOperation::TranslatePlane => {
+            let (a, b) = axis.others();
+            format!("Move in the {a}{b} plane")
+        }
+        GizmoOperation::TranslateView => "Move in view".to_string(),
+        GizmoOperation::Rotate => {
+            let degrees = match handle.axis {
+                GizmoAxis::X => pitch,
+                GizmoAxis::Y => yaw,
+                GizmoAxis::Z => roll,
+            }
+            .to_degrees();
+            format!("Rotate about {axis}: {degrees:.1}°")
+        }
+        GizmoOperation::RotateView => "Rotate in view".to_string(),
+        GizmoOperation::RotateTrackball => "Rotate freely".to_string(),
+        GizmoOperation::ScaleAxis => {
+            format!("Scale along {axis}: {:.2}", component(transform.scale))
+        }
+        GizmoOperation::ScaleUniform => format!("Scale: {:.2}", transform.scale.x),
+        GizmoOperation::Endpoint { .. } => "Move endpoint".to_string(),
+        GizmoOperation::OriginDot => "Origin".to_string(),
+    };
+    node.left = Val::Px(screen.position.x + screen.radius + 8.0);
+    node.top = Val::Px(screen.position.y - screen.radius - 8.0);
+    *visibility = Visibility::Inherited;
+}
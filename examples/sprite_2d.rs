@@ -0,0 +1,159 @@
+//! 2D sprite example.
+//!
+//! A `Camera2d` looking down `-Z` at a few sprites, with the gizmo in its
+//! planar 2D layout: X/Y arrows and scale cubes, the Z rotation ring, and a
+//! free-move square around the center. The gizmo is sized in screen pixels,
+//! since a 2D world unit is a pixel at the default zoom.
+//! Use T/R/S to toggle handles, Q to cycle the coordinate space, P to switch
+//! between the 2D and the full 3D layout. Click a sprite to select it.
+
+use bevy::prelude::*;
+use bevy_transform_tools::{
+    GizmoActive, GizmoScaleMode, GizmoSelectable, TransformGizmoCamera, TransformGizmoConfig,
+    TransformGizmoPlugin, TransformGizmoState, TransformGizmoStyle, TransformGizmoTarget,
+};
+
+#[derive(Component)]
+struct Hud;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(TransformGizmoPlugin)
+        .insert_resource(TransformGizmoConfig {
+            click_to_select: true,
+            ..default()
+        })
+        .insert_resource(TransformGizmoStyle {
+            planar_2d: true,
+            scale_mode: GizmoScaleMode::ScreenSpace(120.0),
+            ..default()
+        })
+        .add_systems(Startup, setup)
+        .add_systems(Update, (keyboard_controls, update_hud))
+        .run();
+}
+
+fn setup(mut commands: Commands) {
+    // Camera
+    commands.spawn((Camera2d, TransformGizmoCamera));
+
+    // Sprites, selectable by clicking within their bounding circles
+    let sprites = [
+        (
+            "Red",
+            Color::srgb(0.9, 0.3, 0.3),
+            Vec2::new(120.0, 80.0),
+            -250.0,
+        ),
+        (
+            "Green",
+            Color::srgb(0.3, 0.9, 0.3),
+            Vec2::new(80.0, 80.0),
+            0.0,
+        ),
+        (
+            "Blue",
+            Color::srgb(0.3, 0.3, 0.9),
+            Vec2::new(60.0, 140.0),
+            250.0,
+        ),
+    ];
+    for (i, (name, color, size, x)) in sprites.into_iter().enumerate() {
+        let mut sprite = commands.spawn((
+            Name::new(name),
+            Sprite::from_color(color, size),
+            Transform::from_xyz(x, 0.0, i as f32)
+                .with_rotation(Quat::from_rotation_z(0.3 * i as f32)),
+            TransformGizmoTarget,
+            GizmoSelectable {
+                radius: size.length() * 0.5,
+            },
+        ));
+        if i == 1 {
+            sprite.insert(GizmoActive);
+        }
+    }
+
+    // HUD
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(10.0),
+                left: Val::Px(10.0),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.7)),
+        ))
+        .with_children(|p| {
+            p.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: FontSize::Px(14.0),
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+                Hud,
+            ));
+        });
+}
+
+fn keyboard_controls(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut state: ResMut<TransformGizmoState>,
+    mut style: ResMut<TransformGizmoStyle>,
+) {
+    if keys.just_pressed(KeyCode::KeyT) {
+        style.show_translate = !style.show_translate;
+    }
+    if keys.just_pressed(KeyCode::KeyR) {
+        style.show_rotate = !style.show_rotate;
+    }
+    if keys.just_pressed(KeyCode::KeyS) {
+        style.show_scale = !style.show_scale;
+    }
+    if keys.just_pressed(KeyCode::KeyP) {
+        style.planar_2d = !style.planar_2d;
+    }
+    if keys.just_pressed(KeyCode::KeyQ) {
+        state.space = state.space.next();
+    }
+}
+
+fn update_hud(
+    state: Res<TransformGizmoState>,
+    style: Res<TransformGizmoStyle>,
+    active: Query<(&Name, &Transform), With<GizmoActive>>,
+    mut query: Query<&mut Text, With<Hud>>,
+) {
+    let Ok(mut text) = query.single_mut() else {
+        return;
+    };
+
+    let selected = active.single().map_or_else(
+        |_| "none".to_string(),
+        |(name, transform)| {
+            let (_, _, angle) = transform.rotation.to_euler(EulerRot::XYZ);
+            format!(
+                "{name} at ({:.0}, {:.0}), {:.1}°, scale ({:.2}, {:.2})",
+                transform.translation.x,
+                transform.translation.y,
+                angle.to_degrees(),
+                transform.scale.x,
+                transform.scale.y,
+            )
+        },
+    );
+
+    text.0 = format!(
+        "Space: {} | Layout: {}\n\
+         Selected: {selected}\n\n\
+         [T/R/S] toggle handles\n\
+         [Q] cycle space\n\
+         [P] toggle 2D layout\n\
+         Click a sprite to select it",
+        state.space,
+        if style.planar_2d { "2D" } else { "3D" },
+    );
+}
@@ -0,0 +1,149 @@
+//! Style presets example.
+//!
+//! Shows the gizmo in the look of familiar editors. Press P to cycle through
+//! the `TransformGizmoStyle` presets; the HUD names the one in use.
+//! Use T/R/S to switch between the translate, rotate, and scale handles.
+
+use bevy::prelude::*;
+use bevy_transform_tools::{
+    GizmoActive, TransformGizmoCamera, TransformGizmoMode, TransformGizmoPlugin,
+    TransformGizmoState, TransformGizmoStyle, TransformGizmoTarget,
+};
+
+/// The presets P cycles through, by name.
+const PRESETS: [(&str, fn() -> TransformGizmoStyle); 5] = [
+    ("Default", TransformGizmoStyle::default),
+    ("Blender", TransformGizmoStyle::blender),
+    ("Unity", TransformGizmoStyle::unity),
+    ("Unreal", TransformGizmoStyle::unreal),
+    ("Compact", TransformGizmoStyle::compact),
+];
+
+/// Index into [`PRESETS`] of the preset in use.
+#[derive(Resource, Default)]
+struct CurrentPreset(usize);
+
+#[derive(Component)]
+struct Hud;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(TransformGizmoPlugin)
+        .init_resource::<CurrentPreset>()
+        .add_systems(Startup, setup)
+        .add_systems(Update, (keyboard_controls, update_hud))
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    // Camera
+    commands.spawn((
+        Camera3d::default(),
+        Transform::from_xyz(6.0, 6.0, 10.0).looking_at(Vec3::ZERO, Vec3::Y),
+        TransformGizmoCamera,
+    ));
+
+    // Light
+    commands.spawn((
+        DirectionalLight {
+            shadow_maps_enabled: true,
+            ..default()
+        },
+        Transform::from_xyz(10.0, 15.0, 10.0).looking_at(Vec3::ZERO, Vec3::Y),
+    ));
+
+    // Ground
+    commands.spawn((
+        Mesh3d(meshes.add(Plane3d::new(Vec3::Y, Vec2::splat(10.0)))),
+        MeshMaterial3d(materials.add(Color::srgb(0.2, 0.35, 0.18))),
+    ));
+
+    // Cube with gizmo
+    commands.spawn((
+        Mesh3d(meshes.add(Cuboid::from_length(1.0))),
+        MeshMaterial3d(materials.add(Color::srgb(0.2, 0.7, 1.0))),
+        Transform::from_xyz(0.0, 0.5, 0.0),
+        TransformGizmoTarget,
+        GizmoActive,
+    ));
+
+    // HUD
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(10.0),
+                left: Val::Px(10.0),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.7)),
+        ))
+        .with_children(|p| {
+            p.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: FontSize::Px(14.0),
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+                Hud,
+            ));
+        });
+}
+
+fn keyboard_controls(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut preset: ResMut<CurrentPreset>,
+    mut state: ResMut<TransformGizmoState>,
+    mut style: ResMut<TransformGizmoStyle>,
+) {
+    // Swapping the style mid-drag would change the handle under the cursor.
+    if keys.just_pressed(KeyCode::KeyP) && state.drag.is_none() {
+        preset.0 = (preset.0 + 1) % PRESETS.len();
+        // Keep the handles of the current tool.
+        *style = TransformGizmoStyle {
+            show_translate: style.show_translate,
+            show_rotate: style.show_rotate,
+            show_scale: style.show_scale,
+            ..(PRESETS[preset.0].1)()
+        };
+    }
+    let tool = if keys.just_pressed(KeyCode::KeyT) {
+        Some(TransformGizmoMode::Translate)
+    } else if keys.just_pressed(KeyCode::KeyR) {
+        Some(TransformGizmoMode::Rotate)
+    } else if keys.just_pressed(KeyCode::KeyS) {
+        Some(TransformGizmoMode::Scale)
+    } else {
+        None
+    };
+    if let Some(tool) = tool {
+        state.mode = tool;
+        style.show_translate = tool == TransformGizmoMode::Translate;
+        style.show_rotate = tool == TransformGizmoMode::Rotate;
+        style.show_scale = tool == TransformGizmoMode::Scale;
+    }
+}
+
+fn update_hud(
+    preset: Res<CurrentPreset>,
+    state: Res<TransformGizmoState>,
+    mut query: Query<&mut Text, With<Hud>>,
+) {
+    let Ok(mut text) = query.single_mut() else {
+        return;
+    };
+
+    text.0 = format!(
+        "Preset: {}\n\
+         Tool: {}\n\n\
+         [P] next preset\n\
+         [T/R/S] translate/rotate/scale handles",
+        PRESETS[preset.0].0, state.mode,
+    );
+}
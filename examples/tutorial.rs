@@ -0,0 +1,214 @@
+//! Interactive tutorial example.
+//!
+//! Highlights gizmo handles with a UI ring while the camera orbits, and
+//! advances to the next step only when a drag ends on the highlighted handle.
+//! Handle positions come from `GizmoHandleScreenInfo`, and the step check
+//! compares the `GizmoHandleId` carried by `GizmoDragEnded`.
+
+use bevy::prelude::*;
+use bevy_transform_tools::{
+    GizmoActive, GizmoAxis, GizmoDragEnded, GizmoHandleId, GizmoHandleScreenInfo, GizmoOperation,
+    TransformGizmoCamera, TransformGizmoConfig, TransformGizmoPlugin, TransformGizmoSystems,
+    TransformGizmoTarget,
+};
+
+/// Radians per second the camera orbits the target.
+const ORBIT_SPEED: f32 = 0.25;
+
+/// The tutorial steps: which handle to drag and what to tell the user.
+const STEPS: [(GizmoOperation, GizmoAxis, &str); 3] = [
+    (
+        GizmoOperation::TranslateAxis,
+        GizmoAxis::X,
+        "Drag the red arrow to move the cube along X",
+    ),
+    (
+        GizmoOperation::TranslateAxis,
+        GizmoAxis::Y,
+        "Now drag the green arrow to lift it",
+    ),
+    (
+        GizmoOperation::Rotate,
+        GizmoAxis::Y,
+        "Finally drag the green arc to turn it",
+    ),
+];
+
+#[derive(Resource, Default)]
+struct TutorialStep(usize);
+
+#[derive(Component)]
+struct Highlight;
+
+#[derive(Component)]
+struct Instructions;
+
+#[derive(Component)]
+struct Student;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(TransformGizmoPlugin)
+        .insert_resource(TransformGizmoConfig {
+            handle_screen_info: true,
+            ..default()
+        })
+        .init_resource::<TutorialStep>()
+        .add_systems(Startup, setup)
+        .add_systems(
+            Update,
+            (orbit_camera, advance_tutorial, update_instructions),
+        )
+        .add_systems(
+            PostUpdate,
+            place_highlight.after(TransformGizmoSystems::Hover),
+        )
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    // Camera
+    commands.spawn((
+        Camera3d::default(),
+        Transform::from_xyz(0.0, 4.0, 8.0).looking_at(Vec3::ZERO, Vec3::Y),
+        TransformGizmoCamera,
+    ));
+
+    // Light
+    commands.spawn((
+        DirectionalLight {
+            shadow_maps_enabled: true,
+            ..default()
+        },
+        Transform::from_xyz(4.0, 8.0, 4.0).looking_at(Vec3::ZERO, Vec3::Y),
+    ));
+
+    // Ground
+    commands.spawn((
+        Mesh3d(meshes.add(Plane3d::new(Vec3::Y, Vec2::splat(10.0)))),
+        MeshMaterial3d(materials.add(Color::srgb(0.3, 0.3, 0.35))),
+        Transform::from_xyz(0.0, -0.5, 0.0),
+    ));
+
+    // The cube the tutorial is about
+    commands.spawn((
+        Mesh3d(meshes.add(Cuboid::from_length(1.0))),
+        MeshMaterial3d(materials.add(Color::srgb(0.8, 0.6, 0.3))),
+        Transform::default(),
+        TransformGizmoTarget,
+        GizmoActive,
+        Student,
+    ));
+
+    // Highlight ring, positioned every frame over the current step's handle
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            border: UiRect::all(Val::Px(3.0)),
+            border_radius: BorderRadius::MAX,
+            ..default()
+        },
+        BorderColor::all(Color::srgb(1.0, 0.85, 0.2)),
+        Visibility::Hidden,
+        Highlight,
+    ));
+
+    // Instructions
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(10.0),
+                left: Val::Px(10.0),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.7)),
+        ))
+        .with_children(|p| {
+            p.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: FontSize::Px(16.0),
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+                Instructions,
+            ));
+        });
+}
+
+fn orbit_camera(time: Res<Time>, mut cameras: Query<&mut Transform, With<TransformGizmoCamera>>) {
+    for mut transform in cameras.iter_mut() {
+        let rotation = Quat::from_rotation_y(ORBIT_SPEED * time.delta_secs());
+        transform.translation = rotation * transform.translation;
+        transform.look_at(Vec3::ZERO, Vec3::Y);
+    }
+}
+
+/// The handle the current step asks for, if the tutorial is not finished.
+fn current_handle(step: &TutorialStep, student: Entity) -> Option<GizmoHandleId> {
+    let (op, axis, _) = STEPS.get(step.0)?;
+    Some(GizmoHandleId::new(student, *op, *axis))
+}
+
+fn advance_tutorial(
+    mut ended: MessageReader<GizmoDragEnded>,
+    mut step: ResMut<TutorialStep>,
+    student: Query<Entity, With<Student>>,
+) {
+    let Ok(student) = student.single() else {
+        return;
+    };
+    for drag in ended.read() {
+        if current_handle(&step, student) == Some(drag.handle) {
+            step.0 += 1;
+        }
+    }
+}
+
+fn place_highlight(
+    info: Res<GizmoHandleScreenInfo>,
+    step: Res<TutorialStep>,
+    student: Query<Entity, With<Student>>,
+    mut highlight: Query<(&mut Node, &mut Visibility), With<Highlight>>,
+) {
+    let Ok((mut node, mut visibility)) = highlight.single_mut() else {
+        return;
+    };
+    let screen = student
+        .single()
+        .ok()
+        .and_then(|student| current_handle(&step, student))
+        .and_then(|handle| info.get(handle))
+        .filter(|screen| screen.visible);
+    let Some(screen) = screen else {
+        *visibility = Visibility::Hidden;
+        return;
+    };
+
+    // Leave some room around the clickable area.
+    let radius = screen.radius + 8.0;
+    node.left = Val::Px(screen.position.x - radius);
+    node.top = Val::Px(screen.position.y - radius);
+    node.width = Val::Px(radius * 2.0);
+    node.height = Val::Px(radius * 2.0);
+    *visibility = Visibility::Inherited;
+}
+
+fn update_instructions(step: Res<TutorialStep>, mut text: Query<&mut Text, With<Instructions>>) {
+    if !step.is_changed() {
+        return;
+    }
+    let Ok(mut text) = text.single_mut() else {
+        return;
+    };
+    text.0 = match STEPS.get(step.0) {
+        Some((_, _, message)) => format!("Step {} of {}: {}", step.0 + 1, STEPS.len(), message),
+        None => "Tutorial complete!".to_string(),
+    };
+}
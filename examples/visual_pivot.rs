@@ -0,0 +1,218 @@
+//! Visual pivot example.
+//!
+//! Mimics a glTF asset whose node origin sits far away from its mesh: the
+//! gizmo target is an empty root node and the visible cube lives on a child
+//! offset by three units. `GizmoVisualPivot` anchors the gizmo at the cube's
+//! bounds center, so rotating and scaling pivot the cube in place while the
+//! root's origin orbits around it.
+//!
+//! A second target is a single mesh whose vertices sit well above and beside
+//! its origin, as if exported with the origin at its feet. Its
+//! `GizmoAnchorOverride` puts the gizmo at the center of its bounds instead.
+//! Use T/R/S to toggle handles, Q to cycle the coordinate space, V to toggle
+//! the visual pivot on and off, B to toggle the bounds anchor on and off.
+
+use bevy::prelude::*;
+use bevy_transform_tools::{
+    GizmoActive, GizmoAnchor, GizmoAnchorOverride, GizmoVisualPivot, TransformGizmoCamera,
+    TransformGizmoPlugin, TransformGizmoState, TransformGizmoStyle, TransformGizmoTarget,
+};
+
+#[derive(Component)]
+struct Root;
+
+#[derive(Component)]
+struct Column;
+
+#[derive(Component)]
+struct Hud;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(TransformGizmoPlugin)
+        .add_systems(Startup, setup)
+        .add_systems(Update, (keyboard_controls, draw_root_origin, update_hud))
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    // Camera
+    commands.spawn((
+        Camera3d::default(),
+        Transform::from_xyz(6.0, 7.0, 12.0).looking_at(Vec3::new(1.5, 0.0, 0.0), Vec3::Y),
+        TransformGizmoCamera,
+    ));
+
+    // Light
+    commands.spawn((
+        DirectionalLight {
+            shadow_maps_enabled: true,
+            ..default()
+        },
+        Transform::from_xyz(10.0, 15.0, 10.0).looking_at(Vec3::ZERO, Vec3::Y),
+    ));
+
+    // Ground
+    commands.spawn((
+        Mesh3d(meshes.add(Plane3d::new(Vec3::Y, Vec2::splat(10.0)))),
+        MeshMaterial3d(materials.add(Color::srgb(0.2, 0.35, 0.18))),
+    ));
+
+    // Scene root authored at the origin, mesh offset on a child node.
+    commands
+        .spawn((
+            Root,
+            Transform::from_xyz(-1.5, 0.0, 0.0),
+            Visibility::default(),
+            TransformGizmoTarget,
+            GizmoActive,
+            GizmoVisualPivot::from_descendant_bounds(),
+        ))
+        .with_children(|p| {
+            p.spawn((
+                Mesh3d(meshes.add(Cuboid::new(1.0, 1.0, 2.0))),
+                MeshMaterial3d(materials.add(Color::srgb(0.9, 0.6, 0.2))),
+                Transform::from_xyz(3.0, 0.5, 0.0),
+            ));
+        });
+
+    // A column exported with its origin at its foot, off to one side.
+    commands.spawn((
+        Column,
+        Mesh3d(
+            meshes.add(
+                Mesh::from(Cuboid::new(0.6, 3.0, 0.6)).translated_by(Vec3::new(0.8, 1.5, 0.0)),
+            ),
+        ),
+        MeshMaterial3d(materials.add(Color::srgb(0.3, 0.55, 0.9))),
+        Transform::from_xyz(-3.0, 0.0, 3.0),
+        TransformGizmoTarget,
+        GizmoAnchorOverride(GizmoAnchor::BoundsCenter),
+    ));
+
+    // HUD
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(10.0),
+                left: Val::Px(10.0),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.7)),
+        ))
+        .with_children(|p| {
+            p.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: FontSize::Px(14.0),
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+                Hud,
+            ));
+        });
+}
+
+fn keyboard_controls(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut commands: Commands,
+    mut state: ResMut<TransformGizmoState>,
+    mut style: ResMut<TransformGizmoStyle>,
+    roots: Query<(Entity, Has<GizmoVisualPivot>), With<Root>>,
+    columns: Query<(Entity, Has<GizmoAnchorOverride>), With<Column>>,
+) {
+    if keys.just_pressed(KeyCode::KeyT) {
+        style.show_translate = !style.show_translate;
+    }
+    if keys.just_pressed(KeyCode::KeyR) {
+        style.show_rotate = !style.show_rotate;
+    }
+    if keys.just_pressed(KeyCode::KeyS) {
+        style.show_scale = !style.show_scale;
+    }
+    if keys.just_pressed(KeyCode::KeyQ) {
+        state.space = state.space.next();
+    }
+    if keys.just_pressed(KeyCode::KeyV) && state.drag.is_none() {
+        for (entity, has_pivot) in &roots {
+            if has_pivot {
+                commands.entity(entity).remove::<GizmoVisualPivot>();
+            } else {
+                commands
+                    .entity(entity)
+                    .insert(GizmoVisualPivot::from_descendant_bounds());
+            }
+        }
+    }
+    if keys.just_pressed(KeyCode::KeyB) && state.drag.is_none() {
+        for (entity, anchored) in &columns {
+            if anchored {
+                commands.entity(entity).remove::<GizmoAnchorOverride>();
+            } else {
+                commands
+                    .entity(entity)
+                    .insert(GizmoAnchorOverride(GizmoAnchor::BoundsCenter));
+            }
+        }
+    }
+}
+
+/// Mark the authored origin so it is visible where it orbits.
+fn draw_root_origin(
+    roots: Query<&GlobalTransform, Or<(With<Root>, With<Column>)>>,
+    mut gizmos: Gizmos,
+) {
+    for transform in &roots {
+        gizmos.sphere(transform.translation(), 0.1, Color::srgb(1.0, 0.2, 0.8));
+    }
+}
+
+fn update_hud(
+    state: Res<TransformGizmoState>,
+    roots: Query<(&Transform, Has<GizmoVisualPivot>), With<Root>>,
+    columns: Query<Has<GizmoAnchorOverride>, With<Column>>,
+    mut query: Query<&mut Text, With<Hud>>,
+) {
+    let Ok(mut text) = query.single_mut() else {
+        return;
+    };
+    let Ok((root, has_pivot)) = roots.single() else {
+        return;
+    };
+    let anchored = columns.iter().any(|anchored| anchored);
+
+    let (origin_delta, pivot_delta) = state
+        .drag
+        .as_ref()
+        .map_or((Vec3::ZERO, Vec3::ZERO), |drag| {
+            (drag.origin_delta, drag.pivot_delta)
+        });
+
+    text.0 = format!(
+        "Space: {} | Visual pivot: {} | Bounds anchor: {}\n\
+         Root origin: ({:.2}, {:.2}, {:.2})\n\
+         Drag delta: origin ({:.2}, {:.2}, {:.2}) pivot ({:.2}, {:.2}, {:.2})\n\n\
+         [T/R/S] toggle handles\n\
+         [Q] cycle space\n\
+         [V] toggle visual pivot\n\
+         [B] toggle bounds anchor",
+        state.space,
+        if has_pivot { "on" } else { "off" },
+        if anchored { "on" } else { "off" },
+        root.translation.x,
+        root.translation.y,
+        root.translation.z,
+        origin_delta.x,
+        origin_delta.y,
+        origin_delta.z,
+        pivot_delta.x,
+        pivot_delta.y,
+        pivot_delta.z,
+    );
+}
@@ -0,0 +1,198 @@
+//! Align drags, which snap a translate drag's target onto other entities.
+//!
+//! While [`AlignSnapSettings::modifier`](crate::AlignSnapSettings::modifier)
+//! is held, a translate drag puts its target exactly where the nearest other
+//! [`TransformGizmoTarget`](crate::TransformGizmoTarget) or [`GizmoSnapPoint`] is, once that entity is
+//! within [`AlignSnapSettings::threshold_pixels`](crate::AlignSnapSettings::threshold_pixels)
+//! of the cursor on screen.
+
+#[cfg(feature = "translate")]
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+
+use crate::types::GizmoHandleId;
+#[cfg(feature = "translate")]
+use crate::types::TransformGizmoTarget;
+
+/// Marks an entity as a point align drags snap onto, besides the other
+/// [`TransformGizmoTarget`](crate::TransformGizmoTarget)s, see
+/// [`TransformGizmoSnap::align`](crate::TransformGizmoSnap::align).
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Component, Clone, Copy, Debug, Default, Reflect)]
+#[reflect(Component, Default)]
+pub struct GizmoSnapPoint;
+
+/// Written when an align drag snaps its target onto an entity, moves it on
+/// to another one, or lets it go, for example to play a click.
+#[derive(Message, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GizmoAlignSnapped {
+    /// The dragged handle.
+    pub handle: GizmoHandleId,
+    /// The entity the target snapped onto, or `None` once the drag lets go.
+    pub snapped_to: Option<Entity>,
+}
+
+/// Entities an align drag can snap onto, besides the dragged target.
+#[cfg(feature = "translate")]
+type AlignPoint = Or<(With<TransformGizmoTarget>, With<GizmoSnapPoint>)>;
+
+/// The entities an align drag can snap its target onto.
+#[cfg(feature = "translate")]
+#[derive(SystemParam)]
+pub struct AlignCandidates<'w, 's> {
+    points: Query<'w, 's, (Entity, &'static GlobalTransform), AlignPoint>,
+    parents: Query<'w, 's, &'static ChildOf>,
+}
+
+#[cfg(feature = "translate")]
+impl AlignCandidates<'_, '_> {
+    /// The candidate nearest to `cursor` (logical window pixels) on screen,
+    /// within `threshold_pixels`, with its world transform. The dragged
+    /// `target` and its descendants are never candidates.
+    pub(crate) fn nearest(
+        &self,
+        camera: &Camera,
+        camera_transform: &GlobalTransform,
+        cursor: Vec2,
+        threshold_pixels: f32,
+        target: Entity,
+    ) -> Option<(Entity, GlobalTransform)> {
+        let viewport = camera.logical_viewport_rect()?;
+        let on_screen = self
+            .points
+            .iter()
+            .filter(|(entity, _)| {
+                *entity != target && !self.parents.iter_ancestors(*entity).any(|e| e == target)
+            })
+            .filter_map(|(entity, global)| {
+                let position = camera
+                    .world_to_viewport(camera_transform, global.translation())
+                    .ok()?;
+                Some((entity, position + viewport.min))
+            });
+        let entity = nearest_on_screen(cursor, threshold_pixels, on_screen)?;
+        Some((entity, *self.points.get(entity).ok()?.1))
+    }
+}
+
+/// The entity among `candidates` (entity and screen position) nearest to
+/// `cursor`, if it is within `threshold` pixels. Ties go to the lowest
+/// entity id.
+#[cfg(feature = "translate")]
+fn nearest_on_screen(
+    cursor: Vec2,
+    threshold: f32,
+    candidates: impl IntoIterator<Item = (Entity, Vec2)>,
+) -> Option<Entity> {
+    candidates
+        .into_iter()
+        .map(|(entity, position)| (entity, position.distance(cursor)))
+        .filter(|(_, distance)| *distance <= threshold)
+        .min_by(|(a, a_distance), (b, b_distance)| {
+            a_distance
+                .total_cmp(b_distance)
+                .then(a.index_u32().cmp(&b.index_u32()))
+        })
+        .map(|(entity, _)| entity)
+}
+
+#[cfg(all(test, feature = "translate"))]
+mod tests {
+    use bevy::ecs::message::Messages;
+
+    use super::*;
+    use crate::test_utils::GizmoTestHarness;
+    use crate::types::{GizmoAxis, GizmoOperation};
+
+    #[test]
+    fn the_nearest_candidate_within_the_threshold_wins() {
+        let mut world = World::new();
+        let [a, b, c] = [(); 3].map(|_| world.spawn_empty().id());
+        let candidates = [
+            (a, Vec2::new(110.0, 100.0)),
+            (b, Vec2::new(104.0, 103.0)),
+            (c, Vec2::new(200.0, 100.0)),
+        ];
+        let cursor = Vec2::new(100.0, 100.0);
+
+        assert_eq!(nearest_on_screen(cursor, 20.0, candidates), Some(b));
+        assert_eq!(nearest_on_screen(cursor, 4.0, candidates), None);
+        // Halfway between two candidates the lower entity id wins.
+        assert_eq!(
+            nearest_on_screen(
+                Vec2::new(155.0, 100.0),
+                50.0,
+                [candidates[2], candidates[0]]
+            ),
+            Some(a)
+        );
+    }
+
+    #[test]
+    fn alt_drags_snap_onto_other_targets() {
+        fn snapped(harness: &mut GizmoTestHarness) -> Vec<Option<Entity>> {
+            harness
+                .app_mut()
+                .world_mut()
+                .resource_mut::<Messages<GizmoAlignSnapped>>()
+                .drain()
+                .map(|message| message.snapped_to)
+                .collect()
+        }
+        fn keys(harness: &mut GizmoTestHarness) -> Mut<'_, ButtonInput<KeyCode>> {
+            harness
+                .app_mut()
+                .world_mut()
+                .resource_mut::<ButtonInput<KeyCode>>()
+        }
+
+        let mut harness = GizmoTestHarness::new();
+        let turned = Quat::from_rotation_y(0.5);
+        let other = harness
+            .app_mut()
+            .world_mut()
+            .spawn((
+                Transform::from_xyz(2.0, 0.0, 0.0).with_rotation(turned),
+                TransformGizmoTarget,
+            ))
+            .id();
+        harness.step();
+        harness.grab(GizmoOperation::TranslateAxis, GizmoAxis::X);
+        harness.press_left();
+        harness.step();
+
+        // Near the other target the drag snaps onto it while Alt is held.
+        let near = harness.world_to_cursor(Vec3::new(2.0, 0.0, 0.0)).unwrap() + Vec2::splat(6.0);
+        harness.move_cursor(near);
+        harness.step();
+        assert_ne!(harness.target_transform().translation.x, 2.0);
+        keys(&mut harness).press(KeyCode::AltLeft);
+        harness.step();
+        assert_eq!(
+            harness.target_transform(),
+            Transform::from_xyz(2.0, 0.0, 0.0)
+        );
+        assert_eq!(snapped(&mut harness), [Some(other)]);
+        assert!(harness.drag_state().unwrap().aligned.is_some());
+
+        // Shift takes its rotation too.
+        keys(&mut harness).press(KeyCode::ShiftLeft);
+        harness.step();
+        let transform = harness.target_transform();
+        assert_eq!(transform.translation, Vec3::new(2.0, 0.0, 0.0));
+        assert!(transform.rotation.abs_diff_eq(turned, 1.0e-5));
+        assert!(snapped(&mut harness).is_empty());
+
+        // Letting go carries on from the snapped pose without a jump.
+        keys(&mut harness).release(KeyCode::AltLeft);
+        keys(&mut harness).release(KeyCode::ShiftLeft);
+        harness.step();
+        assert_eq!(harness.target_transform(), transform);
+        assert_eq!(snapped(&mut harness), [None]);
+        harness.move_cursor(near + harness.screen_axis(Vec3::X) * 30.0);
+        harness.step();
+        let translation = harness.target_transform().translation;
+        assert!(translation.x > 2.1, "{translation}");
+        assert_eq!(translation.yz(), Vec2::ZERO);
+    }
+}
@@ -0,0 +1,558 @@
+//! Gizmo camera selection.
+//!
+//! Only one camera drives the gizmo at a time. This module picks it from all
+//! [`TransformGizmoCamera`] entities so every system works against the same
+//! view, and warns when the choice is ambiguous. It also maps the
+//! [`GizmoPointer`] to a ray through that camera, honouring the window it
+//! renders to and its viewport rectangle.
+//!
+//! In split-viewport editors the camera whose viewport holds the cursor
+//! drives the gizmo, so any view can hover and grab handles, and a drag
+//! stays with the camera it started from
+//! ([`TransformGizmoDrag::camera`](crate::TransformGizmoDrag::camera)). The
+//! other views show the result; camera-facing parts such as the origin dot
+//! and the center handles face the driving camera.
+
+use bevy::camera::RenderTarget;
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+
+use crate::types::{
+    GizmoCameraPriority, GizmoCameraSelection, GizmoPointer, TransformGizmoCamera,
+    TransformGizmoState,
+};
+
+/// A gizmo camera considered for selection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CameraCandidate {
+    /// Camera entity.
+    pub entity: Entity,
+    /// Explicit priority, if the camera has a [`GizmoCameraPriority`].
+    pub priority: Option<i32>,
+    /// Whether the camera can be used at all (active, with a viewport).
+    pub eligible: bool,
+    /// Whether the pointer is inside the camera's viewport, on its window.
+    pub under_pointer: bool,
+}
+
+impl CameraCandidate {
+    fn key(&self) -> (bool, i32) {
+        (self.under_pointer, self.priority.unwrap_or(0))
+    }
+
+    fn rank(&self) -> ((bool, i32), std::cmp::Reverse<u32>) {
+        (self.key(), std::cmp::Reverse(self.entity.index_u32()))
+    }
+}
+
+/// Pick the camera that drives the gizmo.
+///
+/// Ineligible cameras are skipped. Among the rest, cameras under the pointer
+/// come first, then the highest priority wins and ties go to the lowest
+/// entity id.
+pub fn pick_gizmo_camera(candidates: &[CameraCandidate]) -> Option<Entity> {
+    candidates
+        .iter()
+        .filter(|candidate| candidate.eligible)
+        .max_by_key(|candidate| candidate.rank())
+        .map(|candidate| candidate.entity)
+}
+
+/// Eligible cameras tied for the top priority when at least one of them has
+/// no explicit [`GizmoCameraPriority`], sorted by entity, written to
+/// `competing`.
+///
+/// Whether a camera is under the pointer is left out, so moving the cursor
+/// between viewports does not change the set. Leaves `competing` empty when
+/// the choice is unambiguous.
+pub fn competing_cameras(candidates: &[CameraCandidate], competing: &mut Vec<Entity>) {
+    competing.clear();
+    let eligible = candidates.iter().filter(|candidate| candidate.eligible);
+    let priority = |candidate: &CameraCandidate| candidate.priority.unwrap_or(0);
+    let Some(top) = eligible.clone().map(priority).max() else {
+        return;
+    };
+
+    let tied = eligible.filter(|candidate| priority(candidate) == top);
+    let (count, all_explicit) = tied.clone().fold((0, true), |(count, all), candidate| {
+        (count + 1, all && candidate.priority.is_some())
+    });
+    if count < 2 || all_explicit {
+        return;
+    }
+
+    competing.extend(tied.map(|candidate| candidate.entity));
+    competing.sort_by_key(|entity| entity.index_u32());
+}
+
+/// Whether exactly one eligible camera is under the pointer, which then
+/// drives the gizmo however the others are prioritized.
+fn pointer_decides(candidates: &[CameraCandidate]) -> bool {
+    candidates
+        .iter()
+        .filter(|candidate| candidate.eligible && candidate.under_pointer)
+        .count()
+        == 1
+}
+
+/// Record the current set of competing cameras.
+///
+/// Returns `true` when the set is non-empty and differs from the last one
+/// warned about, so each conflict is reported once.
+fn should_warn(selection: &mut GizmoCameraSelection, competing: &[Entity]) -> bool {
+    if selection.warned == competing {
+        return false;
+    }
+    selection.warned.clear();
+    selection.warned.extend_from_slice(competing);
+    !selection.warned.is_empty()
+}
+
+/// The window a camera with `target` renders to; `None` for image and
+/// texture targets.
+pub(crate) fn camera_window(
+    target: Option<&RenderTarget>,
+    primary_window: Option<Entity>,
+) -> Option<Entity> {
+    match target {
+        None => primary_window,
+        Some(RenderTarget::Window(window)) => window
+            .normalize(primary_window)
+            .map(|window| window.entity()),
+        Some(_) => None,
+    }
+}
+
+/// Whether `pointer` is inside `viewport` (logical pixels) on `window`.
+///
+/// A pointer or camera without a known window matches any window.
+pub(crate) fn pointer_in_viewport(
+    pointer: &GizmoPointer,
+    window: Option<Entity>,
+    viewport: Rect,
+) -> bool {
+    let same_window = match (pointer.window, window) {
+        (Some(pointer_window), Some(window)) => pointer_window == window,
+        _ => true,
+    };
+    same_window
+        && pointer
+            .position
+            .is_some_and(|position| viewport.contains(position))
+}
+
+/// World-space ray under the pointer through the selected camera.
+///
+/// `None` when the pointer is over another window than the camera renders
+/// to, outside the camera's viewport, or [`captured`](GizmoPointer::captured)
+/// by UI, so hover and clicks there never reach the gizmo.
+pub(crate) fn pointer_ray(
+    selection: &GizmoCameraSelection,
+    camera: &Camera,
+    camera_transform: &GlobalTransform,
+    pointer: &GizmoPointer,
+) -> Option<Ray3d> {
+    if pointer.captured {
+        return None;
+    }
+    let viewport = camera.logical_viewport_rect()?;
+    if !pointer_in_viewport(pointer, selection.window, viewport) {
+        return None;
+    }
+    // `viewport_to_world` takes window coordinates and removes the viewport
+    // offset itself. The pointer and the viewport rect are both in logical
+    // pixels, so the window's scale factor cancels out.
+    camera
+        .viewport_to_world(camera_transform, pointer.position?)
+        .ok()
+}
+
+/// Like [`pointer_ray`], but past the viewport's edges too, so an active
+/// drag keeps following a cursor that leaves the view.
+pub(crate) fn drag_pointer_ray(
+    selection: &GizmoCameraSelection,
+    camera: &Camera,
+    camera_transform: &GlobalTransform,
+    pointer: &GizmoPointer,
+) -> Option<Ray3d> {
+    if let (Some(pointer_window), Some(window)) = (pointer.window, selection.window) {
+        if pointer_window != window {
+            return None;
+        }
+    }
+    camera
+        .viewport_to_world(camera_transform, pointer.position?)
+        .ok()
+}
+
+/// What [`select_gizmo_camera`] reads from each gizmo camera.
+type CameraData = (
+    Entity,
+    &'static Camera,
+    Option<&'static GizmoCameraPriority>,
+    Option<&'static RenderTarget>,
+);
+
+/// Select the gizmo camera for this frame.
+pub fn select_gizmo_camera(
+    mut selection: ResMut<GizmoCameraSelection>,
+    state: Res<TransformGizmoState>,
+    pointer: Res<GizmoPointer>,
+    primary_window: Query<Entity, With<PrimaryWindow>>,
+    cameras: Query<CameraData, With<TransformGizmoCamera>>,
+    mut candidates: Local<Vec<CameraCandidate>>,
+    mut competing: Local<Vec<Entity>>,
+) {
+    let primary_window = primary_window.iter().next();
+    candidates.clear();
+    candidates.extend(
+        cameras
+            .iter()
+            .map(|(entity, camera, priority, target)| CameraCandidate {
+                entity,
+                priority: priority.map(|priority| priority.0),
+                eligible: camera.is_active && camera.logical_viewport_size().is_some(),
+                under_pointer: camera.logical_viewport_rect().is_some_and(|viewport| {
+                    pointer_in_viewport(&pointer, camera_window(target, primary_window), viewport)
+                }),
+            }),
+    );
+
+    // An active drag keeps the camera it started from even when the cursor
+    // crosses into another view.
+    let held = state
+        .drag
+        .as_ref()
+        .and_then(|drag| drag.camera.or(selection.camera))
+        .filter(|camera| {
+            candidates
+                .iter()
+                .any(|candidate| candidate.entity == *camera && candidate.eligible)
+        });
+    let camera = held.or_else(|| pick_gizmo_camera(&candidates));
+    let window = camera
+        .and_then(|camera| cameras.get(camera).ok())
+        .and_then(|(_, _, _, target)| camera_window(target, primary_window));
+    if selection.camera != camera || selection.window != window {
+        selection.camera = camera;
+        selection.window = window;
+    }
+
+    // The pointer settles the choice while it is inside a single viewport;
+    // the conflict is kept as it was so leaving and re-entering that
+    // viewport does not warn again.
+    if pointer_decides(&candidates) {
+        return;
+    }
+    competing_cameras(&candidates, &mut competing);
+    if should_warn(&mut selection, &competing) {
+        let listing: Vec<String> = selection
+            .warned
+            .iter()
+            .map(|&entity| match cameras.get(entity) {
+                Ok((_, _, _, Some(target))) => format!("{entity} ({target:?})"),
+                _ => format!("{entity}"),
+            })
+            .collect();
+        warn!(
+            "multiple active TransformGizmoCamera entities without a GizmoCameraPriority: {}; \
+             using {:?}. Add GizmoCameraPriority to pick one explicitly.",
+            listing.join(", "),
+            camera,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entities(count: usize) -> Vec<Entity> {
+        let mut world = World::new();
+        (0..count).map(|_| world.spawn_empty().id()).collect()
+    }
+
+    fn competing(candidates: &[CameraCandidate]) -> Vec<Entity> {
+        let mut competing = Vec::new();
+        competing_cameras(candidates, &mut competing);
+        competing
+    }
+
+    fn candidate(entity: Entity, priority: Option<i32>) -> CameraCandidate {
+        CameraCandidate {
+            entity,
+            priority,
+            eligible: true,
+            under_pointer: false,
+        }
+    }
+
+    #[test]
+    fn highest_priority_camera_wins() {
+        let e = entities(3);
+        let candidates = [
+            candidate(e[0], Some(1)),
+            candidate(e[1], Some(5)),
+            candidate(e[2], None),
+        ];
+        assert_eq!(pick_gizmo_camera(&candidates), Some(e[1]));
+        assert!(competing(&candidates).is_empty());
+    }
+
+    #[test]
+    fn inactive_cameras_are_skipped() {
+        let e = entities(2);
+        let candidates = [
+            CameraCandidate {
+                eligible: false,
+                ..candidate(e[0], Some(10))
+            },
+            candidate(e[1], None),
+        ];
+        assert_eq!(pick_gizmo_camera(&candidates), Some(e[1]));
+
+        let none = [CameraCandidate {
+            eligible: false,
+            ..candidate(e[0], None)
+        }];
+        assert_eq!(pick_gizmo_camera(&none), None);
+    }
+
+    #[test]
+    fn ties_are_broken_by_entity_regardless_of_order() {
+        let e = entities(3);
+        let forward = [
+            candidate(e[0], None),
+            candidate(e[1], None),
+            candidate(e[2], None),
+        ];
+        let reversed = [
+            candidate(e[2], None),
+            candidate(e[1], None),
+            candidate(e[0], None),
+        ];
+        assert_eq!(pick_gizmo_camera(&forward), Some(e[0]));
+        assert_eq!(pick_gizmo_camera(&reversed), Some(e[0]));
+        assert_eq!(competing(&reversed), e);
+    }
+
+    #[test]
+    fn camera_under_the_pointer_beats_priority() {
+        let e = entities(2);
+        let candidates = [
+            candidate(e[0], Some(10)),
+            CameraCandidate {
+                under_pointer: true,
+                ..candidate(e[1], None)
+            },
+        ];
+        assert_eq!(pick_gizmo_camera(&candidates), Some(e[1]));
+        assert!(competing(&candidates).is_empty());
+    }
+
+    #[test]
+    fn pointer_outside_an_offset_viewport_misses_it() {
+        let e = entities(2);
+        let (window, other_window) = (e[0], e[1]);
+        // `Viewport { physical_position: (200, 100), physical_size: (400, 300) }`
+        // at a scale factor of 1.
+        let viewport = Rect::new(200.0, 100.0, 600.0, 400.0);
+        let pointer = |position: Vec2, window: Option<Entity>| GizmoPointer {
+            position: Some(position),
+            window,
+            ..default()
+        };
+
+        let center = Vec2::new(400.0, 250.0);
+        assert!(pointer_in_viewport(
+            &pointer(center, Some(window)),
+            Some(window),
+            viewport
+        ));
+        // Inside the window, but left of and above the viewport.
+        assert!(!pointer_in_viewport(
+            &pointer(Vec2::new(150.0, 250.0), Some(window)),
+            Some(window),
+            viewport
+        ));
+        assert!(!pointer_in_viewport(
+            &pointer(Vec2::new(400.0, 50.0), Some(window)),
+            Some(window),
+            viewport
+        ));
+        // Same coordinates on another window.
+        assert!(!pointer_in_viewport(
+            &pointer(center, Some(other_window)),
+            Some(window),
+            viewport
+        ));
+        // A manually fed pointer without a window matches any.
+        assert!(pointer_in_viewport(
+            &pointer(center, None),
+            Some(window),
+            viewport
+        ));
+    }
+
+    #[test]
+    fn cameras_resolve_their_target_window() {
+        use bevy::window::WindowRef;
+
+        let e = entities(2);
+        let (primary, secondary) = (e[0], e[1]);
+        assert_eq!(camera_window(None, Some(primary)), Some(primary));
+        assert_eq!(
+            camera_window(
+                Some(&RenderTarget::Window(WindowRef::Primary)),
+                Some(primary)
+            ),
+            Some(primary)
+        );
+        assert_eq!(
+            camera_window(
+                Some(&RenderTarget::Window(WindowRef::Entity(secondary))),
+                Some(primary)
+            ),
+            Some(secondary)
+        );
+        assert_eq!(
+            camera_window(
+                Some(&RenderTarget::None { size: UVec2::ONE }),
+                Some(primary)
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn conflict_warning_fires_once_per_camera_set() {
+        let e = entities(3);
+        let mut selection = GizmoCameraSelection::default();
+
+        let pair = [candidate(e[0], None), candidate(e[1], None)];
+        assert!(should_warn(&mut selection, &competing(&pair)));
+        assert!(!should_warn(&mut selection, &competing(&pair)));
+
+        let triple = [
+            candidate(e[0], None),
+            candidate(e[1], None),
+            candidate(e[2], None),
+        ];
+        assert!(should_warn(&mut selection, &competing(&triple)));
+        assert!(!should_warn(&mut selection, &competing(&triple)));
+
+        // Resolving the conflict resets the warning, so it fires again if
+        // the same cameras compete later.
+        let resolved = [candidate(e[0], Some(1)), candidate(e[1], None)];
+        assert!(!should_warn(&mut selection, &competing(&resolved)));
+        assert!(should_warn(&mut selection, &competing(&pair)));
+    }
+
+    #[test]
+    fn moving_the_pointer_between_viewports_keeps_the_conflict() {
+        let e = entities(3);
+        let hovered = |index: usize| {
+            let mut candidates = [
+                candidate(e[0], None),
+                candidate(e[1], None),
+                candidate(e[2], None),
+            ];
+            candidates[index].under_pointer = true;
+            candidates
+        };
+        let mut selection = GizmoCameraSelection::default();
+        assert!(should_warn(&mut selection, &competing(&hovered(0))));
+        assert_eq!(competing(&hovered(0)), competing(&hovered(1)));
+        assert!(!should_warn(&mut selection, &competing(&hovered(1))));
+
+        // A single viewport under the pointer decides on its own; two that
+        // overlap there still tie.
+        assert!(pointer_decides(&hovered(2)));
+        let mut overlapping = hovered(2);
+        overlapping[0].under_pointer = true;
+        assert!(!pointer_decides(&overlapping));
+        assert!(!pointer_decides(&[
+            candidate(e[0], None),
+            candidate(e[1], None)
+        ]));
+    }
+
+    #[cfg(feature = "translate")]
+    #[test]
+    fn split_viewports_each_drag_with_their_own_camera() {
+        use crate::test_utils::GizmoTestHarness;
+        use crate::types::{GizmoAxis, GizmoOperation};
+        use bevy::camera::Viewport;
+
+        let half = |x: u32| Viewport {
+            physical_position: UVec2::new(x, 0),
+            physical_size: UVec2::new(640, 720),
+            ..default()
+        };
+        let mut harness = GizmoTestHarness::new();
+        // Keep rings seen edge-on from above off the X cone.
+        harness.style_mut().fade_backfacing = true;
+        let perspective = harness.camera();
+        harness
+            .app_mut()
+            .world_mut()
+            .get_mut::<Camera>(perspective)
+            .unwrap()
+            .viewport = Some(half(0));
+        // A top-down view in the right half, where world +X is screen right.
+        let top = harness
+            .app_mut()
+            .world_mut()
+            .spawn((
+                Camera3d::default(),
+                Camera {
+                    order: 1,
+                    viewport: Some(half(640)),
+                    ..default()
+                },
+                Transform::from_xyz(0.0, 10.0, 0.0).looking_at(Vec3::ZERO, Vec3::NEG_Z),
+                TransformGizmoCamera,
+            ))
+            .id();
+        let selected = |harness: &GizmoTestHarness| {
+            harness
+                .app()
+                .world()
+                .resource::<GizmoCameraSelection>()
+                .camera()
+        };
+
+        // Grab the X cone in the top view and carry the drag into the
+        // perspective view; the top camera keeps driving it. The new
+        // viewports are laid out by the end of the first frame.
+        harness.move_cursor(Vec2::new(960.0, 360.0));
+        harness.step();
+        harness.step();
+        assert_eq!(selected(&harness), Some(top));
+        let start = harness.grab(GizmoOperation::TranslateAxis, GizmoAxis::X);
+        harness.press_left();
+        harness.step();
+        assert_eq!(harness.drag_state().unwrap().camera, Some(top));
+        harness.move_cursor(start + Vec2::new(40.0, 0.0));
+        harness.step();
+        let moved = harness.target_transform().translation.x;
+        assert!(moved > 0.1, "{moved}");
+        harness.move_cursor(Vec2::new(320.0, 360.0));
+        harness.step();
+        assert_eq!(selected(&harness), Some(top));
+        harness.move_cursor(start + Vec2::new(40.0, 0.0));
+        harness.step();
+        harness.release_left();
+        harness.step();
+        assert!(harness.drag_state().is_none());
+
+        // Then grab the same cube from the perspective view.
+        harness.move_cursor(Vec2::new(320.0, 360.0));
+        harness.step();
+        assert_eq!(selected(&harness), Some(perspective));
+        let before = harness.target_transform().translation.x;
+        let start = harness.grab(GizmoOperation::TranslateAxis, GizmoAxis::X);
+        harness.drag(start, start + harness.screen_axis(Vec3::X) * 40.0);
+        assert!(harness.target_transform().translation.x > before + 0.1);
+    }
+}
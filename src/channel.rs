@@ -0,0 +1,249 @@
+//! Secondary edit channels.
+//!
+//! A target normally edits its `Transform`. Registering a channel lets the
+//! gizmo edit another component instead (a socket offset, a collider offset)
+//! through an accessor that converts it to and from a pseudo-transform
+//! relative to the entity's `GlobalTransform`.
+
+use bevy::ecs::component::Mutable;
+use bevy::prelude::*;
+
+use crate::interaction::{begin_drag, drag_gizmo};
+use crate::types::{GizmoChannelParts, GizmoEditChannel, TransformGizmoSystems};
+
+/// Converts a component to and from the pseudo-transform the gizmo edits.
+///
+/// The pseudo-transform is relative to the entity's `GlobalTransform`, so
+/// handles appear at `global * get(component)`.
+#[derive(Resource)]
+pub struct GizmoChannelAccessor<C: Component> {
+    get: fn(&C) -> Transform,
+    set: fn(&mut C, Transform),
+    parts: GizmoChannelParts,
+}
+
+impl<C: Component> GizmoChannelAccessor<C> {
+    /// Accessor supporting translation, rotation, and scale.
+    pub fn new(get: fn(&C) -> Transform, set: fn(&mut C, Transform)) -> Self {
+        Self {
+            get,
+            set,
+            parts: GizmoChannelParts::ALL,
+        }
+    }
+
+    /// Restrict the handles shown for this channel.
+    pub fn with_parts(mut self, parts: GizmoChannelParts) -> Self {
+        self.parts = parts;
+        self
+    }
+}
+
+/// Cached pseudo-transform of a target's secondary channel.
+///
+/// The gizmo systems read and edit this instead of the channel component, so
+/// they stay independent of the registered component types.
+#[derive(Component, Debug, Clone, Default)]
+pub(crate) struct GizmoChannelValue {
+    pub(crate) local: Transform,
+    pub(crate) parts: GizmoChannelParts,
+    pub(crate) dirty: bool,
+}
+
+/// The secondary channel value to edit, if the target uses one.
+pub(crate) fn secondary_channel<'a>(
+    channel: Option<&GizmoEditChannel>,
+    value: Option<&'a GizmoChannelValue>,
+) -> Option<&'a GizmoChannelValue> {
+    match channel {
+        Some(GizmoEditChannel::Secondary) => value,
+        _ => None,
+    }
+}
+
+/// World-space transform the gizmo frame derives from.
+pub(crate) fn edit_global(
+    global: &GlobalTransform,
+    secondary: Option<&GizmoChannelValue>,
+) -> GlobalTransform {
+    secondary.map_or(*global, |value| global.mul_transform(value.local))
+}
+
+/// Extension trait for registering secondary edit channels.
+pub trait TransformGizmoAppExt {
+    /// Let the gizmo edit `C` on targets with [`GizmoEditChannel::Secondary`].
+    ///
+    /// Each entity can carry at most one registered channel component.
+    fn register_gizmo_channel<C: Component<Mutability = Mutable>>(
+        &mut self,
+        accessor: GizmoChannelAccessor<C>,
+    ) -> &mut Self;
+}
+
+impl TransformGizmoAppExt for App {
+    fn register_gizmo_channel<C: Component<Mutability = Mutable>>(
+        &mut self,
+        accessor: GizmoChannelAccessor<C>,
+    ) -> &mut Self {
+        self.insert_resource(accessor)
+            .register_required_components::<C, GizmoChannelValue>()
+            .add_systems(
+                Update,
+                (
+                    read_gizmo_channel::<C>.before(begin_drag),
+                    write_gizmo_channel::<C>.after(drag_gizmo),
+                )
+                    .in_set(TransformGizmoSystems::Drag),
+            )
+            // Catch changes made after the drag, so hover and drawing see them.
+            .add_systems(
+                PostUpdate,
+                read_gizmo_channel::<C>.before(TransformGizmoSystems::Hover),
+            )
+    }
+}
+
+/// Refresh cached channel values from their components.
+pub fn read_gizmo_channel<C: Component>(
+    accessor: Res<GizmoChannelAccessor<C>>,
+    mut channels: Query<(&C, &mut GizmoChannelValue), Changed<C>>,
+) {
+    for (component, mut value) in channels.iter_mut() {
+        let local = (accessor.get)(component);
+        if value.local != local || value.parts != accessor.parts {
+            value.local = local;
+            value.parts = accessor.parts;
+        }
+    }
+}
+
+/// Write channel values edited by a drag back into their components.
+pub fn write_gizmo_channel<C: Component<Mutability = Mutable>>(
+    accessor: Res<GizmoChannelAccessor<C>>,
+    mut channels: Query<(&mut C, &mut GizmoChannelValue)>,
+) {
+    for (mut component, mut value) in channels.iter_mut() {
+        if value.dirty {
+            (accessor.set)(&mut component, value.local);
+            value.dirty = false;
+        }
+    }
+}
+
+#[cfg(all(test, feature = "translate"))]
+mod tests {
+    use super::*;
+    use crate::test_utils::GizmoTestHarness;
+    use crate::types::{GizmoAxis, GizmoOperation};
+    use crate::TransformGizmoAppExt;
+
+    #[test]
+    fn secondary_channel_drag_edits_only_the_channel_component() {
+        #[derive(Component)]
+        struct Socket {
+            offset: Vec3,
+            rotation: Quat,
+        }
+
+        let mut harness = GizmoTestHarness::new();
+        harness.app_mut().register_gizmo_channel(
+            GizmoChannelAccessor::<Socket>::new(
+                |socket| Transform::from_translation(socket.offset).with_rotation(socket.rotation),
+                |socket, transform| {
+                    socket.offset = transform.translation;
+                    socket.rotation = transform.rotation;
+                },
+            )
+            .with_parts(GizmoChannelParts::TRANSLATE_ROTATE),
+        );
+        let hand = Transform::from_xyz(0.5, 0.0, -0.5).with_rotation(Quat::from_rotation_y(0.6));
+        let target = harness.target();
+        harness.app_mut().world_mut().entity_mut(target).insert((
+            hand,
+            Socket {
+                offset: Vec3::new(0.5, 0.0, 0.0),
+                rotation: Quat::IDENTITY,
+            },
+            GizmoEditChannel::Secondary,
+        ));
+        harness.step();
+        let socket_world = |harness: &GizmoTestHarness| {
+            let world = harness.app().world();
+            let offset = world.get::<Socket>(target).unwrap().offset;
+            world
+                .get::<GlobalTransform>(target)
+                .unwrap()
+                .transform_point(offset)
+        };
+        let start = socket_world(&harness);
+
+        // The gizmo sits at the socket, so its X cone is the socket's X.
+        let cone = harness.grab(GizmoOperation::TranslateAxis, GizmoAxis::X);
+        harness.press_left();
+        harness.step();
+        let axis = harness.drag_state().unwrap().axis_dir;
+        assert!(axis.abs_diff_eq(hand.rotation * Vec3::X, 1.0e-5));
+        let toward = harness.world_to_cursor(start + axis).unwrap()
+            - harness.world_to_cursor(start).unwrap();
+        harness.move_cursor(cone + toward.normalize() * 60.0);
+        harness.step();
+        harness.release_left();
+        harness.step();
+
+        let world = harness.app().world();
+        assert_eq!(*world.get::<Transform>(target).unwrap(), hand);
+        assert_eq!(
+            world.get::<Socket>(target).unwrap().rotation,
+            Quat::IDENTITY
+        );
+        // Anything attached at the socket follows the drag along the axis.
+        let moved = socket_world(&harness) - start;
+        assert!(moved.dot(axis) > 0.1, "{moved}");
+        assert!(moved.reject_from(axis).length() < 1.0e-4, "{moved}");
+        let world = harness.app().world();
+        assert!(!world.get::<GizmoChannelValue>(target).unwrap().dirty);
+    }
+
+    #[test]
+    fn channel_changes_after_the_drag_reach_hover_the_same_frame() {
+        #[derive(Component)]
+        struct Socket(Vec3);
+
+        let mut harness = GizmoTestHarness::new();
+        harness
+            .app_mut()
+            .register_gizmo_channel(GizmoChannelAccessor::<Socket>::new(
+                |socket| Transform::from_translation(socket.0),
+                |socket, transform| socket.0 = transform.translation,
+            ));
+        let target = harness.target();
+        harness
+            .app_mut()
+            .world_mut()
+            .entity_mut(target)
+            .insert((Socket(Vec3::ZERO), GizmoEditChannel::Secondary));
+        harness.step();
+        let before = harness
+            .handle_position(GizmoOperation::TranslateAxis, GizmoAxis::X)
+            .unwrap();
+
+        // An app system moves the socket after the gizmo's drag systems.
+        harness.app_mut().add_systems(
+            Update,
+            (|mut sockets: Query<&mut Socket>| {
+                for mut socket in sockets.iter_mut() {
+                    socket.0 = Vec3::Y;
+                }
+            })
+            .after(TransformGizmoSystems::Drag),
+        );
+        harness.step();
+        let world = harness.app().world();
+        let value = world.get::<GizmoChannelValue>(target).unwrap();
+        assert_eq!(value.local.translation, Vec3::Y);
+        let after = harness
+            .handle_position(GizmoOperation::TranslateAxis, GizmoAxis::X)
+            .unwrap();
+        assert!(before.distance(after) > 10.0, "{before} {after}");
+    }
+}
@@ -0,0 +1,268 @@
+//! Per-frame gizmo diagnostics.
+//!
+//! Hover and draw fill [`GizmoFrameStats`] through scope guards that write on
+//! drop, so early returns still record. The stats are then published to
+//! Bevy's `DiagnosticsStore`, where `LogDiagnosticsPlugin` and diagnostics
+//! overlays pick them up.
+
+use bevy::diagnostic::{Diagnostic, DiagnosticPath, Diagnostics, RegisterDiagnostic};
+use bevy::platform::time::Instant;
+use bevy::prelude::*;
+
+use crate::types::GizmoFrameStats;
+
+/// Diagnostic paths registered by the transform gizmo.
+pub struct TransformGizmoDiagnostics;
+
+impl TransformGizmoDiagnostics {
+    /// Time spent in hover detection, in microseconds.
+    pub const HOVER_TIME: DiagnosticPath =
+        DiagnosticPath::const_new("transform_gizmo/hover_time_us");
+    /// Targets whose coarse bounds were hit by the cursor ray.
+    pub const TARGETS_TESTED: DiagnosticPath =
+        DiagnosticPath::const_new("transform_gizmo/targets_tested");
+    /// Exact handle intersection tests performed during hover.
+    pub const EXACT_INTERSECTIONS: DiagnosticPath =
+        DiagnosticPath::const_new("transform_gizmo/exact_intersections");
+    /// Line segments submitted while drawing.
+    pub const DRAW_SEGMENTS: DiagnosticPath =
+        DiagnosticPath::const_new("transform_gizmo/draw_segments");
+    /// Time spent drawing, in microseconds.
+    pub const DRAW_TIME: DiagnosticPath = DiagnosticPath::const_new("transform_gizmo/draw_time_us");
+}
+
+/// Register the gizmo diagnostics and the system that publishes them.
+pub(crate) fn register_gizmo_diagnostics(app: &mut App) {
+    app.init_resource::<GizmoFrameStats>()
+        .register_diagnostic(
+            Diagnostic::new(TransformGizmoDiagnostics::HOVER_TIME).with_suffix("us"),
+        )
+        .register_diagnostic(Diagnostic::new(TransformGizmoDiagnostics::TARGETS_TESTED))
+        .register_diagnostic(Diagnostic::new(
+            TransformGizmoDiagnostics::EXACT_INTERSECTIONS,
+        ))
+        .register_diagnostic(Diagnostic::new(TransformGizmoDiagnostics::DRAW_SEGMENTS))
+        .register_diagnostic(
+            Diagnostic::new(TransformGizmoDiagnostics::DRAW_TIME).with_suffix("us"),
+        );
+}
+
+/// Publish this frame's [`GizmoFrameStats`] to the diagnostics store.
+pub fn publish_gizmo_diagnostics(stats: Res<GizmoFrameStats>, mut diagnostics: Diagnostics) {
+    diagnostics.add_measurement(&TransformGizmoDiagnostics::HOVER_TIME, || {
+        stats.hover_time_us
+    });
+    diagnostics.add_measurement(&TransformGizmoDiagnostics::TARGETS_TESTED, || {
+        stats.targets_tested as f64
+    });
+    diagnostics.add_measurement(&TransformGizmoDiagnostics::EXACT_INTERSECTIONS, || {
+        stats.exact_intersections as f64
+    });
+    diagnostics.add_measurement(&TransformGizmoDiagnostics::DRAW_SEGMENTS, || {
+        stats.draw_segments as f64
+    });
+    diagnostics.add_measurement(&TransformGizmoDiagnostics::DRAW_TIME, || stats.draw_time_us);
+}
+
+/// Hover counters, written to [`GizmoFrameStats`] when dropped.
+pub(crate) struct HoverStatsScope<'a> {
+    stats: &'a mut GizmoFrameStats,
+    start: Instant,
+    pub(crate) targets_tested: u32,
+    pub(crate) exact_intersections: u32,
+}
+
+impl<'a> HoverStatsScope<'a> {
+    pub(crate) fn new(stats: &'a mut GizmoFrameStats) -> Self {
+        Self {
+            stats,
+            start: Instant::now(),
+            targets_tested: 0,
+            exact_intersections: 0,
+        }
+    }
+}
+
+impl Drop for HoverStatsScope<'_> {
+    fn drop(&mut self) {
+        self.stats.hover_time_us = self.start.elapsed().as_secs_f64() * 1.0e6;
+        self.stats.targets_tested = self.targets_tested;
+        self.stats.exact_intersections = self.exact_intersections;
+    }
+}
+
+/// Draw counters, written to [`GizmoFrameStats`] when dropped.
+pub(crate) struct DrawStatsScope<'a> {
+    stats: &'a mut GizmoFrameStats,
+    start: Instant,
+    pub(crate) draw_segments: u32,
+}
+
+impl<'a> DrawStatsScope<'a> {
+    pub(crate) fn new(stats: &'a mut GizmoFrameStats) -> Self {
+        Self {
+            stats,
+            start: Instant::now(),
+            draw_segments: 0,
+        }
+    }
+}
+
+impl Drop for DrawStatsScope<'_> {
+    fn drop(&mut self) {
+        self.stats.draw_time_us = self.start.elapsed().as_secs_f64() * 1.0e6;
+        self.stats.draw_segments = self.draw_segments;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::camera::select_gizmo_camera;
+    use crate::interaction::update_hovered_axis;
+    use crate::pointer::update_gizmo_pointer;
+    use crate::types::{
+        GizmoCameraSelection, GizmoPointer, TransformGizmoCamera, TransformGizmoConfig,
+        TransformGizmoState, TransformGizmoStyle, TransformGizmoTarget,
+    };
+    use bevy::diagnostic::{DiagnosticsPlugin, DiagnosticsStore};
+    use bevy::input::mouse::MouseWheel;
+    use bevy::input::touch::TouchInput;
+    use bevy::window::PrimaryWindow;
+
+    #[test]
+    fn diagnostics_are_registered_and_updated_every_frame() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, DiagnosticsPlugin))
+            .init_resource::<TransformGizmoState>()
+            .init_resource::<TransformGizmoStyle>()
+            .init_resource::<TransformGizmoConfig>()
+            .init_resource::<GizmoCameraSelection>()
+            .init_resource::<GizmoPointer>()
+            .add_message::<TouchInput>()
+            .add_message::<MouseWheel>()
+            .add_systems(
+                Update,
+                (
+                    update_gizmo_pointer,
+                    select_gizmo_camera,
+                    update_hovered_axis,
+                    publish_gizmo_diagnostics,
+                )
+                    .chain(),
+            );
+        register_gizmo_diagnostics(&mut app);
+
+        let mut window = Window::default();
+        window.set_cursor_position(Some(Vec2::new(320.0, 240.0)));
+        app.world_mut().spawn((window, PrimaryWindow));
+        app.world_mut().spawn((
+            Camera3d::default(),
+            Transform::from_xyz(0.0, 2.0, 8.0).looking_at(Vec3::ZERO, Vec3::Y),
+            TransformGizmoCamera,
+        ));
+        for x in 0..4 {
+            app.world_mut().spawn((
+                Transform::from_xyz(x as f32 * 2.0, 0.0, 0.0),
+                GlobalTransform::from_xyz(x as f32 * 2.0, 0.0, 0.0),
+                TransformGizmoTarget,
+            ));
+        }
+
+        app.update();
+        app.update();
+
+        let store = app.world().resource::<DiagnosticsStore>();
+        for path in [
+            TransformGizmoDiagnostics::HOVER_TIME,
+            TransformGizmoDiagnostics::TARGETS_TESTED,
+            TransformGizmoDiagnostics::EXACT_INTERSECTIONS,
+            TransformGizmoDiagnostics::DRAW_SEGMENTS,
+            TransformGizmoDiagnostics::DRAW_TIME,
+        ] {
+            let diagnostic = store
+                .get(&path)
+                .unwrap_or_else(|| panic!("{path} not registered"));
+            assert_eq!(diagnostic.history_len(), 2, "{path} not updated");
+        }
+        // Without a rendered viewport hover returns early, but still reports
+        // its (zero) counts and its time.
+        let hover = store.get(&TransformGizmoDiagnostics::HOVER_TIME).unwrap();
+        assert!(hover.value().is_some_and(|us| us >= 0.0));
+        let tested = store
+            .get(&TransformGizmoDiagnostics::TARGETS_TESTED)
+            .unwrap();
+        assert_eq!(tested.value(), Some(0.0));
+    }
+
+    #[cfg(feature = "translate")]
+    #[test]
+    fn frame_stats_follow_drawing_and_hover() {
+        use crate::draw::draw_gizmo;
+        use crate::test_utils::GizmoTestHarness;
+        use crate::types::{
+            GizmoAxis, GizmoOperation, TransformGizmoConfigGroup, TransformGizmoGuideConfigGroup,
+            TransformGizmoSystems,
+        };
+        use bevy::gizmos::GizmoAsset;
+
+        let mut harness = GizmoTestHarness::new();
+        let app = harness.app_mut();
+        app.add_plugins(DiagnosticsPlugin)
+            .init_resource::<Assets<GizmoAsset>>()
+            .init_gizmo_group::<DefaultGizmoConfigGroup>()
+            .init_gizmo_group::<TransformGizmoConfigGroup>()
+            .init_gizmo_group::<TransformGizmoGuideConfigGroup>()
+            .add_systems(
+                PostUpdate,
+                (draw_gizmo, publish_gizmo_diagnostics)
+                    .chain()
+                    .in_set(TransformGizmoSystems::Draw),
+            );
+        register_gizmo_diagnostics(app);
+        let stats =
+            |harness: &GizmoTestHarness| *harness.app().world().resource::<GizmoFrameStats>();
+
+        // Away from the gizmo, hover tests nothing but the gizmo is drawn.
+        harness.move_cursor(Vec2::new(5.0, 5.0));
+        harness.step();
+        let idle = stats(&harness);
+        assert!(idle.draw_segments > 0);
+        assert_eq!((idle.targets_tested, idle.exact_intersections), (0, 0));
+
+        // Over a cone, hover tests the target's handles.
+        harness.grab(GizmoOperation::TranslateAxis, GizmoAxis::X);
+        let hovering = stats(&harness);
+        assert_eq!(hovering.targets_tested, 1);
+        assert!(hovering.exact_intersections > 0);
+        assert!(hovering.draw_segments > 0);
+        let store = harness.app().world().resource::<DiagnosticsStore>();
+        let published = |path| store.get(&path).and_then(|diagnostic| diagnostic.value());
+        assert_eq!(
+            published(TransformGizmoDiagnostics::DRAW_SEGMENTS),
+            Some(hovering.draw_segments as f64)
+        );
+        assert_eq!(
+            published(TransformGizmoDiagnostics::EXACT_INTERSECTIONS),
+            Some(hovering.exact_intersections as f64)
+        );
+
+        // Without a target, nothing is drawn or tested.
+        let target = harness.target();
+        harness
+            .app_mut()
+            .world_mut()
+            .entity_mut(target)
+            .remove::<TransformGizmoTarget>();
+        harness.step();
+        let empty = stats(&harness);
+        assert_eq!(
+            (
+                empty.draw_segments,
+                empty.targets_tested,
+                empty.exact_intersections
+            ),
+            (0, 0, 0)
+        );
+    }
+}
@@ -3,37 +3,329 @@
 //! This module handles drawing the visual representation of the transform
 //! gizmo using Bevy's `Gizmos` API.
 
-use std::f32::consts::PI;
+use std::borrow::Cow;
+use std::f32::consts::TAU;
 
+use bevy::camera::primitives::Frustum;
 use bevy::prelude::*;
 
-/// Number of line segments used to draw translation cones.
-const CONE_SEGMENTS: usize = 16;
+/// Length of the full axis guide on each side of the origin, in axis
+/// lengths, long enough to run off screen.
+const FULL_AXIS_LENGTH: f32 = 1000.0;
 
-use crate::gizmo_frame::{plane_axes, AxisKind, GizmoFrame};
+/// Alpha multiplier that dims the full axis guide against the axis lines.
+const FULL_AXIS_ALPHA: f32 = 0.4;
+
+/// How far the origin dot is drawn toward the camera, in dot sizes.
+const ORIGIN_DOT_NUDGE: f32 = 0.2;
+
+/// Number of line segments used to draw each endpoint circle.
+#[cfg(feature = "translate")]
+const ENDPOINT_SEGMENTS: usize = 16;
+
+/// Most snap grid lines drawn on each side of the origin, so a tiny snap step
+/// cannot flood the frame with lines.
+#[cfg(feature = "translate")]
+const MAX_SNAP_GRID_LINES: i64 = 64;
+
+/// Number of pieces each snap grid line is drawn in, so it can fade out
+/// toward the edge of the grid.
+#[cfg(feature = "translate")]
+const SNAP_GRID_LINE_PIECES: usize = 8;
+
+/// Most snap ticks drawn on each side of the grab point around a ring.
+#[cfg(feature = "rotate")]
+const MAX_SNAP_RING_TICKS: i64 = 180;
+
+/// Number of line segments used to draw the proportional editing radius.
+const INFLUENCE_SEGMENTS: usize = 48;
+
+/// Number of line segments used to draw the view translation circle.
+#[cfg(feature = "translate")]
+const TRANSLATE_VIEW_SEGMENTS: usize = 24;
+
+/// Length on screen, in pixels, of each segment of an adaptive circle, see
+/// [`adaptive_segments`].
+#[cfg(any(feature = "translate", feature = "rotate"))]
+const PIXELS_PER_SEGMENT: f32 = 6.0;
+
+/// Most line segments an adaptive circle is drawn with, whatever the style
+/// asks for, so a gizmo filling the screen stays cheap to draw.
+#[cfg(any(feature = "translate", feature = "rotate"))]
+const MAX_ADAPTIVE_SEGMENTS: usize = 256;
+
+use crate::diagnostics::DrawStatsScope;
+use crate::gizmo_frame::{plane_axes, AxisKind, GizmoDetail, GizmoFrame, GizmoSizing};
+#[cfg(feature = "planes")]
+use crate::handle_geometry::plane_rect;
+#[cfg(feature = "translate")]
+use crate::handle_geometry::translate_cone;
+#[cfg(feature = "rotate")]
+use crate::handle_geometry::{rotation_ring, RingGeometry};
+#[cfg(feature = "scale")]
+use crate::handle_geometry::{scale_cube, CubeGeometry};
+#[cfg(any(feature = "translate", feature = "rotate", feature = "scale"))]
+use crate::interaction::handle_faces_away;
+use crate::interaction::{held_plane_signs, target_frame, GizmoTargetQuery};
+#[cfg(any(feature = "translate", feature = "rotate"))]
 use crate::math::axis_basis;
+#[cfg(feature = "rotate")]
+use crate::math::twist_angle;
+use crate::origin_dot::OriginDotPress;
+use crate::proportional::GizmoProportionalEdit;
+#[cfg(any(feature = "translate", feature = "rotate", feature = "scale"))]
+use crate::types::AxisColors;
+#[cfg(feature = "translate")]
+use crate::types::EndpointSign;
+#[cfg(any(feature = "translate", feature = "scale"))]
+use crate::types::GizmoRenderMode;
+#[cfg(feature = "rotate")]
+use crate::types::GizmoStateColors;
 use crate::types::{
-    AxisColors, GizmoAxis, GizmoOperation, TransformGizmoCamera, TransformGizmoState,
-    TransformGizmoStyle, TransformGizmoTarget,
+    DragIsolation, GizmoAxis, GizmoCameraSelection, GizmoColorTint, GizmoDelta, GizmoFrameStats,
+    GizmoLocked, GizmoOperation, TransformGizmoCamera, TransformGizmoConfigGroup,
+    TransformGizmoDrag, TransformGizmoGuideConfigGroup, TransformGizmoState, TransformGizmoStyle,
+    TransformGizmoTarget,
 };
+#[cfg(any(feature = "translate", feature = "rotate"))]
+use crate::types::{SnapGridStyle, SnapMode, TransformGizmoSnap};
+use crate::visual_pivot::anchor_pivot;
+
+/// `Gizmos` wrapper that routes handles and guides to their config groups
+/// and counts submitted line segments for diagnostics.
+struct GizmoLines<'a, 'w, 's, 'gw, 'gs, 'dw, 'ds> {
+    gizmos: &'a mut Gizmos<'w, 's, TransformGizmoConfigGroup>,
+    guide_gizmos: &'a mut Gizmos<'gw, 'gs, TransformGizmoGuideConfigGroup>,
+    /// Bevy's default group, drawn through instead with
+    /// [`TransformGizmoStyle::draw_in_default_gizmo_group`].
+    default_gizmos: Option<&'a mut Gizmos<'dw, 'ds>>,
+    /// Whether lines are drawn as guides, see [`GizmoLines::guides`].
+    drawing_guides: bool,
+    segments: u32,
+}
+
+impl GizmoLines<'_, '_, '_, '_, '_, '_, '_> {
+    fn line(&mut self, start: Vec3, end: Vec3, color: Color) {
+        match &mut self.default_gizmos {
+            Some(gizmos) => gizmos.line(start, end, color),
+            None if self.drawing_guides => self.guide_gizmos.line(start, end, color),
+            None => self.gizmos.line(start, end, color),
+        }
+        self.segments += 1;
+    }
+
+    /// Draw the lines `draw` submits through the guide group.
+    fn guides(&mut self, draw: impl FnOnce(&mut Self)) {
+        let drawing_guides = std::mem::replace(&mut self.drawing_guides, true);
+        draw(self);
+        self.drawing_guides = drawing_guides;
+    }
+}
+
+/// Unit circle points for the circles drawn in a fixed number of segments,
+/// so their trig is done once rather than once per target.
+///
+/// Cones and rotation rings pick their segment count from their size on
+/// screen instead, see [`adaptive_segments`], and step around [`arc_points`].
+pub struct CircleTables {
+    #[cfg(feature = "translate")]
+    endpoint: Vec<Vec2>,
+    #[cfg(feature = "translate")]
+    translate_view: Vec<Vec2>,
+    influence: Vec<Vec2>,
+}
+
+impl CircleTables {
+    fn new() -> Self {
+        Self {
+            #[cfg(feature = "translate")]
+            endpoint: unit_arc(ENDPOINT_SEGMENTS, TAU),
+            #[cfg(feature = "translate")]
+            translate_view: unit_arc(TRANSLATE_VIEW_SEGMENTS, TAU),
+            influence: unit_arc(INFLUENCE_SEGMENTS, TAU),
+        }
+    }
+}
+
+/// `segments + 1` evenly spaced points on the unit circle, from angle zero
+/// to `extent`.
+fn unit_arc(segments: usize, extent: f32) -> Vec<Vec2> {
+    (0..=segments)
+        .map(|i| Vec2::from_angle(extent * i as f32 / segments as f32))
+        .collect()
+}
+
+/// `segments + 1` evenly spaced points on the unit circle, from `start`
+/// through `sweep` radians, stepped by rotation so only two angles are
+/// evaluated however many segments there are.
+#[cfg(any(feature = "translate", feature = "rotate"))]
+fn arc_points(start: f32, sweep: f32, segments: usize) -> impl Iterator<Item = Vec2> + Clone {
+    let step = Vec2::from_angle(sweep / segments.max(1) as f32);
+    std::iter::successors(Some(Vec2::from_angle(start)), move |p| {
+        Some(step.rotate(*p))
+    })
+    .take(segments + 1)
+}
+
+/// Segment count for a circle or arc `pixels` long on screen: one segment
+/// per [`PIXELS_PER_SEGMENT`], kept between the style's `min` and `max` and
+/// under [`MAX_ADAPTIVE_SEGMENTS`]. Without a length on screen, such as
+/// with no viewport, the arc gets `max`.
+#[cfg(any(feature = "translate", feature = "rotate"))]
+fn adaptive_segments(pixels: Option<f32>, min: usize, max: usize) -> usize {
+    let max = max.clamp(2, MAX_ADAPTIVE_SEGMENTS);
+    let min = min.clamp(2, max);
+    pixels.map_or(max, |pixels| {
+        ((pixels / PIXELS_PER_SEGMENT).ceil() as usize).clamp(min, max)
+    })
+}
+
+/// Connect the unit circle `points`, laid out with `radius` in the plane
+/// of `u` and `v` around `center`.
+fn draw_unit_arc(
+    gizmos: &mut GizmoLines,
+    points: impl IntoIterator<Item = Vec2>,
+    center: Vec3,
+    (u, v): (Vec3, Vec3),
+    radius: f32,
+    color: Color,
+) {
+    let point = |p: Vec2| center + (u * p.x + v * p.y) * radius;
+    let mut points = points.into_iter().map(point);
+    let Some(mut prev) = points.next() else {
+        return;
+    };
+    for point in points {
+        gizmos.line(prev, point, color);
+        prev = point;
+    }
+}
+
+/// A set of axes, one bit per axis.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct AxisMask(u8);
+
+impl AxisMask {
+    pub(crate) const NONE: Self = Self(0);
+    pub(crate) const ALL: Self = Self(0b111);
+
+    pub(crate) const fn of(axis: GizmoAxis) -> Self {
+        Self(1 << axis.index())
+    }
+
+    pub(crate) const fn with(self, axis: GizmoAxis) -> Self {
+        Self(self.0 | Self::of(axis).0)
+    }
+
+    pub(crate) const fn contains(self, axis: GizmoAxis) -> bool {
+        self.0 & Self::of(axis).0 != 0
+    }
+}
 
 /// Which axis lines should visually respond to a handle interaction.
-fn axes_involved(op: GizmoOperation, axis: GizmoAxis) -> Vec<GizmoAxis> {
+fn axes_involved(op: GizmoOperation, axis: GizmoAxis) -> AxisMask {
     match op {
-        GizmoOperation::TranslateAxis | GizmoOperation::ScaleAxis => vec![axis],
+        GizmoOperation::TranslateAxis | GizmoOperation::ScaleAxis => AxisMask::of(axis),
         GizmoOperation::TranslatePlane => {
             let (a, b) = plane_axes(axis);
-            vec![a, b]
-        }
-        GizmoOperation::Rotate => vec![axis],
-        GizmoOperation::ScaleUniform => {
-            vec![GizmoAxis::X, GizmoAxis::Y, GizmoAxis::Z]
+            AxisMask::of(a).with(b)
         }
+        GizmoOperation::Rotate => AxisMask::of(axis),
+        GizmoOperation::RotateView
+        | GizmoOperation::RotateTrackball
+        | GizmoOperation::TranslateView => AxisMask::NONE,
+        GizmoOperation::ScaleUniform => AxisMask::ALL,
+        GizmoOperation::Endpoint { .. } | GizmoOperation::OriginDot => AxisMask::NONE,
+    }
+}
+
+/// Whether the handle `(op, axis)` takes part in `drag`: the dragged handle
+/// itself, the axis cones of a dragged plane, or every scale cube of a
+/// uniform scale.
+#[cfg(any(feature = "translate", feature = "rotate", feature = "scale"))]
+fn handle_involved(drag: &TransformGizmoDrag, op: GizmoOperation, axis: GizmoAxis) -> bool {
+    if drag.op == op && drag.axis == axis {
+        return true;
+    }
+    let follows = match drag.op {
+        GizmoOperation::TranslatePlane => GizmoOperation::TranslateAxis,
+        GizmoOperation::ScaleUniform => GizmoOperation::ScaleAxis,
+        _ => return false,
+    };
+    op == follows && axes_involved(drag.op, drag.axis).contains(axis)
+}
+
+/// Alpha multiplier [`TransformGizmoStyle::drag_isolation`] gives a part of
+/// the gizmo on `target`: `1.0` if it is `involved` in the drag or the
+/// target is not being dragged, `0.0` if it is hidden.
+pub(crate) fn isolation_alpha(
+    state: &TransformGizmoState,
+    style: &TransformGizmoStyle,
+    target: Entity,
+    involved: bool,
+) -> f32 {
+    let dragged = state
+        .drag
+        .as_ref()
+        .is_some_and(|drag| drag.target == target);
+    if !dragged || involved {
+        return 1.0;
+    }
+    match style.drag_isolation {
+        DragIsolation::None => 1.0,
+        DragIsolation::DimOthers(factor) => factor,
+        DragIsolation::HideOthers => 0.0,
+    }
+}
+
+/// Whether the handle `(op, axis)` on `target` takes part in its drag.
+#[cfg(any(feature = "translate", feature = "rotate", feature = "scale"))]
+pub(crate) fn is_handle_involved(
+    state: &TransformGizmoState,
+    target: Entity,
+    op: GizmoOperation,
+    axis: GizmoAxis,
+) -> bool {
+    state
+        .drag
+        .as_ref()
+        .is_some_and(|drag| drag.target == target && handle_involved(drag, op, axis))
+}
+
+/// Factor the scale handle `(op, axis)` on `target` is pushed out by: the
+/// scale factor along `axis` while a drag scales that handle and
+/// [`TransformGizmoStyle::scale_handles_follow_drag`] is set, `1.0`
+/// otherwise. Uniform scale keeps the factor equal on every axis.
+#[cfg(feature = "scale")]
+pub(crate) fn scale_handle_factor(
+    state: &TransformGizmoState,
+    style: &TransformGizmoStyle,
+    target: Entity,
+    op: GizmoOperation,
+    axis: GizmoAxis,
+) -> f32 {
+    let Some(drag) = state
+        .drag
+        .as_ref()
+        .filter(|drag| style.scale_handles_follow_drag && drag.target == target)
+    else {
+        return 1.0;
+    };
+    let follows = match drag.op {
+        GizmoOperation::ScaleAxis => op == drag.op && axis == drag.axis,
+        GizmoOperation::ScaleUniform => op.is_scale(),
+        _ => false,
+    };
+    if follows {
+        drag.delta().scale().dot(axis.to_vec3())
+    } else {
+        1.0
     }
 }
 
 /// Determine whether a given (operation, axis) is currently active (being dragged).
-fn is_axis_active(
+#[cfg(any(feature = "translate", feature = "rotate", feature = "scale"))]
+pub(crate) fn is_axis_active(
     state: &TransformGizmoState,
     target: Entity,
     op: GizmoOperation,
@@ -51,21 +343,67 @@ struct GizmoDrawContext<'a> {
     style: &'a TransformGizmoStyle,
     frame: &'a GizmoFrame,
     target: Entity,
-    hover_axes: Vec<GizmoAxis>,
-    active_axes: Vec<GizmoAxis>,
+    hover_axes: AxisMask,
+    active_axes: AxisMask,
+    #[cfg(feature = "translate")]
+    circles: &'a CircleTables,
+    /// Pixels a world unit spans at the gizmo's origin, if the camera can
+    /// tell.
+    #[cfg(any(feature = "translate", feature = "rotate"))]
+    pixels_per_unit: Option<f32>,
 }
 
 impl<'a> GizmoDrawContext<'a> {
-    fn color(&self, group: &AxisColors, axis: GizmoAxis, op: GizmoOperation) -> Color {
-        gizmo_display_color(self.state, self.target, group, axis, op)
+    /// Display color of a handle, or `None` if
+    /// [`TransformGizmoStyle::fade_backfacing`] or the target's
+    /// [`GizmoConstraints`](crate::GizmoConstraints) hide it.
+    #[cfg(any(feature = "translate", feature = "rotate", feature = "scale"))]
+    fn color(&self, group: &AxisColors, axis: GizmoAxis, op: GizmoOperation) -> Option<Color> {
+        if !self.frame.allows(op, axis) {
+            return None;
+        }
+        let color = gizmo_display_color(self.state, self.style, self.target, group, axis, op);
+        let alpha = backface_alpha(self.state, self.style, self.frame, self.target, op, axis);
+        let involved = is_handle_involved(self.state, self.target, op, axis);
+        self.isolated(color.with_alpha(color.alpha() * alpha), involved)
+    }
+
+    /// `color` after [`TransformGizmoStyle::drag_isolation`], or `None` if
+    /// the part is hidden, given whether it is `involved` in the drag.
+    fn isolated(&self, color: Color, involved: bool) -> Option<Color> {
+        let alpha = color.alpha() * isolation_alpha(self.state, self.style, self.target, involved);
+        (alpha > 0.0).then(|| color.with_alpha(alpha))
+    }
+
+    /// [`isolated`](Self::isolated) for the handle `(op, axis)`, or `None`
+    /// if the target's constraints rule it out.
+    #[cfg(feature = "translate")]
+    fn isolated_handle(&self, color: Color, op: GizmoOperation, axis: GizmoAxis) -> Option<Color> {
+        if !self.frame.allows(op, axis) {
+            return None;
+        }
+        self.isolated(color, is_handle_involved(self.state, self.target, op, axis))
+    }
+
+    /// Segments for an arc of `radius` through `sweep` radians at the
+    /// origin, between `min` and `max` by its length on screen, see
+    /// [`adaptive_segments`].
+    #[cfg(any(feature = "translate", feature = "rotate"))]
+    fn circle_segments(&self, radius: f32, sweep: f32, min: usize, max: usize) -> usize {
+        let pixels = self
+            .pixels_per_unit
+            .map(|per_unit| radius * sweep.abs() * per_unit);
+        adaptive_segments(pixels, min, max)
     }
 
     fn axis_line_color(&self, axis: GizmoAxis) -> Color {
         let colors = self.style.axis_lines.for_axis(axis);
-        let is_active = self.active_axes.contains(&axis);
-        let is_hovered = self.hover_axes.contains(&axis);
+        let is_active = self.active_axes.contains(axis);
+        let is_hovered = self.hover_axes.contains(axis);
 
-        if is_active {
+        if is_active && drag_blocked(self.state) {
+            self.style.blocked_color
+        } else if is_active {
             colors.active
         } else if is_hovered {
             colors.hover
@@ -75,9 +413,20 @@ impl<'a> GizmoDrawContext<'a> {
     }
 }
 
+/// Whether the current drag is held back by a blocking contact or pinned at
+/// one of the target's limits.
+fn drag_blocked(state: &TransformGizmoState) -> bool {
+    state
+        .drag
+        .as_ref()
+        .is_some_and(|drag| drag.blocked || drag.limit_hit.is_some())
+}
+
 /// Lookup the display color for a gizmo element based on the style and state.
-fn gizmo_display_color(
+#[cfg(any(feature = "translate", feature = "rotate", feature = "scale"))]
+pub(crate) fn gizmo_display_color(
     state: &TransformGizmoState,
+    style: &TransformGizmoStyle,
     target: Entity,
     group: &AxisColors,
     axis: GizmoAxis,
@@ -85,11 +434,13 @@ fn gizmo_display_color(
 ) -> Color {
     let colors = group.for_axis(axis);
     let is_active = is_axis_active(state, target, op, axis);
-    let is_hovered = state.active_target == Some(target)
+    let is_hovered = state.hovered_target == Some(target)
         && state.hovered_axis == Some(axis)
         && state.hovered_op == Some(op);
 
-    if is_active {
+    if is_active && drag_blocked(state) {
+        style.blocked_color
+    } else if is_active {
         colors.active
     } else if is_hovered {
         colors.hover
@@ -98,95 +449,621 @@ fn gizmo_display_color(
     }
 }
 
-/// Draw rotation arc for a given axis using an explicit center angle and basis.
-///
-/// The arc is drawn between the two other axes (e.g. the X-rotation ring lies
-/// in the YZ plane, roughly between the +Y and +Z axes).
-#[allow(clippy::too_many_arguments)]
-fn draw_rotation_arc(
-    gizmos: &mut Gizmos,
+/// Alpha multiplier [`TransformGizmoStyle::fade_backfacing`] gives a handle:
+/// [`backface_fade_alpha`](TransformGizmoStyle::backface_fade_alpha) if it
+/// faces away from the camera and is not being dragged, else `1.0`.
+#[cfg(any(feature = "translate", feature = "rotate", feature = "scale"))]
+pub(crate) fn backface_alpha(
+    state: &TransformGizmoState,
+    style: &TransformGizmoStyle,
+    frame: &GizmoFrame,
+    target: Entity,
+    op: GizmoOperation,
+    axis: GizmoAxis,
+) -> f32 {
+    if handle_faces_away(frame, style, op, axis) && !is_axis_active(state, target, op, axis) {
+        style.backface_fade_alpha
+    } else {
+        1.0
+    }
+}
+
+/// Draw the arc of `ring` in `segments` line segments.
+#[cfg(feature = "rotate")]
+fn draw_ring_arc(gizmos: &mut GizmoLines, ring: &RingGeometry, segments: usize, color: Color) {
+    draw_unit_arc(
+        gizmos,
+        arc_points(ring.start_angle(), ring.sweep, segments),
+        ring.origin,
+        ring.basis,
+        ring.radius,
+        color,
+    );
+}
+
+/// Draw a small camera-facing cross (used for the origin dot).
+fn draw_origin_dot(
+    gizmos: &mut GizmoLines,
     origin: Vec3,
-    axis_dir: Vec3,
-    neighbor1_dir: Vec3,
-    neighbor2_dir: Vec3,
+    size: f32,
     color: Color,
-    radius: f32,
-    total_angle_radians: f32,
-    segments: usize,
+    camera_transform: &GlobalTransform,
+) {
+    let right: Vec3 = camera_transform.right().into();
+    let up: Vec3 = camera_transform.up().into();
+    let half = size * 0.5;
+
+    // Nudge the origin dot slightly toward the camera so it renders clearly
+    // on top of other gizmo elements. The nudge grows with the dot, so it
+    // still clears them where the gizmo is sized up, such as zoomed-out
+    // orthographic views.
+    let forward = camera_transform.forward();
+    let origin = origin - *forward * size * ORIGIN_DOT_NUDGE;
+
+    let d1 = (right + up).normalize_or_zero() * half;
+    let d2 = (right - up).normalize_or_zero() * half;
+    gizmos.line(origin - d1, origin + d1, color);
+    gizmos.line(origin - d2, origin + d2, color);
+}
+
+/// Draw the [`GizmoProportionalEdit`] radius as a camera-facing circle
+/// around `center`.
+fn draw_influence_circle(
+    gizmos: &mut GizmoLines,
+    circles: &CircleTables,
+    center: Vec3,
+    proportional: &GizmoProportionalEdit,
+    camera_transform: &GlobalTransform,
 ) {
-    let axis_dir = axis_dir.normalize_or_zero();
-    if axis_dir.length_squared() < 1e-6 {
+    let right: Vec3 = camera_transform.right().into();
+    let up: Vec3 = camera_transform.up().into();
+    draw_unit_arc(
+        gizmos,
+        circles.influence.iter().copied(),
+        center,
+        (right, up),
+        proportional.radius,
+        proportional.color,
+    );
+}
+
+/// Draw a dashed line from `start` to `end`, with dashes and gaps of `dash`.
+fn draw_dashed_line(gizmos: &mut GizmoLines, start: Vec3, end: Vec3, dash: f32, color: Color) {
+    let length = start.distance(end);
+    if length < 1e-5 {
         return;
     }
+    let dashes = (length / (dash * 2.0)).ceil().clamp(1.0, 256.0) as usize;
+    let step = (end - start) / (dashes * 2) as f32;
+    for i in 0..dashes {
+        let from = start + step * (2 * i) as f32;
+        gizmos.line(from, from + step, color);
+    }
+}
 
-    // Build an orthonormal basis in the rotation plane.
-    let (t1, t2) = axis_basis(axis_dir);
-
-    // Project neighbors into the rotation plane.
-    let proj = |v: Vec3| {
-        let v = v.normalize_or_zero();
-        let n = axis_dir * axis_dir.dot(v);
-        (v - n).normalize_or_zero()
+/// Draw how far the active drag on `ctx.target` has gone: a dashed line
+/// back to a ghost of the starting origin for translation, the pie swept
+/// out on the ring for rotation, and a line as long as the scale factor
+/// times the axis length for scale.
+fn draw_drag_feedback(
+    ctx: &GizmoDrawContext,
+    gizmos: &mut GizmoLines,
+    camera_transform: &GlobalTransform,
+) {
+    let Some(drag) = ctx
+        .state
+        .drag
+        .as_ref()
+        .filter(|drag| drag.target == ctx.target)
+    else {
+        return;
     };
-    let n1 = proj(neighbor1_dir);
-    let n2 = proj(neighbor2_dir);
-
-    let mid = (n1 + n2).normalize_or_zero();
-    let center_angle = if mid.length_squared() > 1e-6 {
-        let x = mid.dot(t1);
-        let y = mid.dot(t2);
-        y.atan2(x)
-    } else {
-        // Fallback: arbitrary direction in the plane.
-        0.0
+    let style = ctx.style;
+    let origin = ctx.frame.origin;
+    match drag.delta() {
+        GizmoDelta::Translation(_) => {
+            // The gizmo sits at the visual pivot if there is one, so follow
+            // the pivot rather than the target's own origin.
+            let start = origin - drag.pivot_delta;
+            let color = style.drag_guide_color;
+            draw_dashed_line(gizmos, start, origin, style.drag_guide_dash_length, color);
+            draw_origin_dot(
+                gizmos,
+                start,
+                style.origin_dot_size,
+                color,
+                camera_transform,
+            );
+        }
+        GizmoDelta::Rotation { axis, angle } => {
+            let radius = drag_ring_radius(style, drag);
+            let from = ring_grab_direction(drag, axis);
+            let sweep = angle.clamp(-std::f32::consts::TAU, std::f32::consts::TAU);
+            let fraction = sweep.abs() / std::f32::consts::TAU;
+            let segments = (fraction * style.rotation_arc_segments as f32)
+                .ceil()
+                .max(1.0) as usize;
+            let color = style.drag_fill_color;
+            let mut previous = origin + from * radius;
+            gizmos.line(origin, previous, color);
+            for i in 1..=segments {
+                let turn = Quat::from_axis_angle(axis, sweep * i as f32 / segments as f32);
+                let point = origin + turn * from * radius;
+                // Spokes from the origin fill the pie in.
+                gizmos.line(origin, point, color);
+                gizmos.line(previous, point, color);
+                previous = point;
+            }
+            if style.show_rotation_readout {
+                let size = style.rotation_readout_size;
+                let end = Quat::from_axis_angle(axis, sweep) * from;
+                draw_readout(
+                    gizmos,
+                    &rotation_readout(angle),
+                    origin + end * (radius + size * 1.5),
+                    size,
+                    style.drag_guide_color,
+                    camera_transform,
+                );
+            }
+        }
+        GizmoDelta::Scale(ratio) => {
+            let axes = match drag.op {
+                GizmoOperation::ScaleAxis => vec![drag.axis],
+                _ => GizmoAxis::ALL.to_vec(),
+            };
+            for axis in axes {
+                let dir = ctx.frame.axis_dir(axis, AxisKind::Scale);
+                let length = style.axis_length * ratio.dot(axis.to_vec3());
+                gizmos.line(origin, origin + dir * length, style.drag_guide_color);
+            }
+        }
+    }
+}
+
+/// Draw the line from where the active drag on `ctx.target` would have put
+/// it to the entity an align drag snapped it onto.
+#[cfg(feature = "translate")]
+fn draw_align_line(ctx: &GizmoDrawContext, gizmos: &mut GizmoLines) {
+    let Some(lock) = ctx
+        .state
+        .drag
+        .as_ref()
+        .filter(|drag| drag.target == ctx.target)
+        .and_then(|drag| drag.aligned)
+    else {
+        return;
     };
+    gizmos.line(lock.unsnapped, lock.position, ctx.style.align_line_color);
+}
+
+/// Text of the rotation readout: `angle` in whole degrees.
+fn rotation_readout(angle: f32) -> String {
+    let degrees = angle.to_degrees().round();
+    // Never read "-0°".
+    format!("{}°", if degrees == 0.0 { 0.0 } else { degrees })
+}
 
-    let half_angle = total_angle_radians * 0.5;
-    let start_angle = center_angle - half_angle;
-    let end_angle = center_angle + half_angle;
+/// Segments of a seven-segment digit, in a box one unit tall centered on the
+/// glyph: top, top right, bottom right, bottom, bottom left, top left, and
+/// middle.
+const SEVEN_SEGMENTS: [(Vec2, Vec2); 7] = {
+    const W: f32 = 0.25;
+    const H: f32 = 0.5;
+    [
+        (Vec2::new(-W, H), Vec2::new(W, H)),
+        (Vec2::new(W, H), Vec2::new(W, 0.0)),
+        (Vec2::new(W, 0.0), Vec2::new(W, -H)),
+        (Vec2::new(-W, -H), Vec2::new(W, -H)),
+        (Vec2::new(-W, 0.0), Vec2::new(-W, -H)),
+        (Vec2::new(-W, H), Vec2::new(-W, 0.0)),
+        (Vec2::new(-W, 0.0), Vec2::new(W, 0.0)),
+    ]
+};
 
-    let steps = segments.max(2);
+/// Which of [`SEVEN_SEGMENTS`] each digit lights, one bit per segment.
+const DIGIT_SEGMENTS: [u8; 10] = [
+    0b011_1111, 0b000_0110, 0b101_1011, 0b100_1111, 0b110_0110, 0b110_1101, 0b111_1101, 0b000_0111,
+    0b111_1111, 0b110_1111,
+];
 
-    let mut prev_point: Option<Vec3> = None;
-    for i in 0..=steps {
-        let t = i as f32 / steps as f32;
-        let angle = start_angle + (end_angle - start_angle) * t;
-        let dir_in_plane = t1 * angle.cos() + t2 * angle.sin();
-        let point = origin + radius * dir_in_plane;
+/// Width each readout glyph takes up, in glyph heights.
+const READOUT_ADVANCE: f32 = 0.75;
 
-        if let Some(prev) = prev_point {
-            gizmos.line(prev, point, color);
+/// Strokes of one readout character, in the [`SEVEN_SEGMENTS`] box. Only
+/// digits, `-`, and `°` are drawn.
+fn readout_glyph(c: char) -> Vec<(Vec2, Vec2)> {
+    let lit = match c {
+        '-' => 0b100_0000,
+        '°' => {
+            let (a, b) = (Vec2::new(-0.25, 0.5), Vec2::new(-0.05, 0.3));
+            return vec![
+                (a, Vec2::new(b.x, a.y)),
+                (Vec2::new(b.x, a.y), b),
+                (b, Vec2::new(a.x, b.y)),
+                (Vec2::new(a.x, b.y), a),
+            ];
         }
-        prev_point = Some(point);
-    }
+        c => match c.to_digit(10) {
+            Some(digit) => DIGIT_SEGMENTS[digit as usize],
+            None => 0,
+        },
+    };
+    SEVEN_SEGMENTS
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| lit & (1 << i) != 0)
+        .map(|(_, &stroke)| stroke)
+        .collect()
 }
 
-/// Draw a small camera-facing cross (used for the origin dot).
-fn draw_origin_dot(
-    gizmos: &mut Gizmos,
-    origin: Vec3,
+/// Draw `text` in stroked digits `size` tall, centered on `center` and laid
+/// out in the camera's right and up directions.
+fn draw_readout(
+    gizmos: &mut GizmoLines,
+    text: &str,
+    center: Vec3,
     size: f32,
     color: Color,
     camera_transform: &GlobalTransform,
 ) {
     let right: Vec3 = camera_transform.right().into();
     let up: Vec3 = camera_transform.up().into();
-    let half = size * 0.5;
+    let count = text.chars().count();
+    let first = -(count.saturating_sub(1) as f32) * READOUT_ADVANCE * 0.5;
+    for (i, c) in text.chars().enumerate() {
+        let x = first + i as f32 * READOUT_ADVANCE;
+        let point = |p: Vec2| center + (right * (p.x + x) + up * p.y) * size;
+        for (a, b) in readout_glyph(c) {
+            gizmos.line(point(a), point(b), color);
+        }
+    }
+}
 
-    // Nudge the origin dot slightly toward the camera so it renders clearly
-    // on top of other gizmo elements.
-    let forward = camera_transform.forward();
-    let origin = origin - *forward * 0.02;
+/// Radius of the ring a rotation drag turns.
+fn drag_ring_radius(style: &TransformGizmoStyle, drag: &TransformGizmoDrag) -> f32 {
+    if drag.op == GizmoOperation::RotateView {
+        style.rotate_view_radius
+    } else {
+        style.axis_length
+    }
+}
 
-    let d1 = (right + up).normalize_or_zero() * half;
-    let d2 = (right - up).normalize_or_zero() * half;
-    gizmos.line(origin - d1, origin + d1, color);
-    gizmos.line(origin - d2, origin + d2, color);
+/// Direction from the origin to where the cursor grabbed the ring around
+/// `axis`.
+fn ring_grab_direction(drag: &TransformGizmoDrag, axis: Vec3) -> Vec3 {
+    let grabbed = drag.start_vector - axis * axis.dot(drag.start_vector);
+    grabbed
+        .try_normalize()
+        .unwrap_or_else(|| axis.any_orthonormal_vector())
+}
+
+/// Snap positions within `extent` of the origin, `step` apart, with the
+/// origin `phase` past the position the snap is measured from. Yields the
+/// signed offset of each from the origin and whether it is a major step.
+#[cfg(any(feature = "translate", feature = "rotate"))]
+fn snap_steps(
+    step: f32,
+    phase: f32,
+    extent: f32,
+    major_every: u32,
+    max_steps: i64,
+) -> impl Iterator<Item = (f32, bool)> {
+    let first = (phase / step).floor();
+    let behind = phase - first * step;
+    let count = ((extent + behind) / step).floor().min(max_steps as f32) as i64;
+    (-count..=count).filter_map(move |k| {
+        let offset = k as f32 * step - behind;
+        let index = first as i64 + k;
+        let major = major_every > 0 && index.rem_euclid(i64::from(major_every)) == 0;
+        (offset.abs() <= extent).then_some((offset, major))
+    })
+}
+
+/// Alpha multiplier for a snap grid line `distance` from the origin.
+#[cfg(any(feature = "translate", feature = "rotate"))]
+fn snap_grid_fade(grid: &SnapGridStyle, distance: f32) -> f32 {
+    if distance <= grid.fade_distance {
+        return 1.0;
+    }
+    ((grid.extent - distance) / (grid.extent - grid.fade_distance)).clamp(0.0, 1.0)
+}
+
+/// How far the target is past the snap position along `dir`, measured like
+/// the drag's snapping: from where it started, or from the world origin.
+#[cfg(feature = "translate")]
+fn translation_snap_phase(drag: &TransformGizmoDrag, mode: SnapMode, dir: Vec3) -> f32 {
+    match mode {
+        SnapMode::Relative => drag.origin_delta.dot(dir),
+        SnapMode::Absolute => (drag.start_translation + drag.origin_delta).dot(dir),
+    }
+}
+
+/// Draw the positions the active drag on `ctx.target` snaps to: a grid in
+/// the drag plane, ticks along the drag axis, or ticks around the ring, one
+/// per step of `snap`.
+#[cfg(any(feature = "translate", feature = "rotate"))]
+fn draw_snap_grid(ctx: &GizmoDrawContext, gizmos: &mut GizmoLines, snap: &TransformGizmoSnap) {
+    let Some(drag) = ctx
+        .state
+        .drag
+        .as_ref()
+        .filter(|drag| drag.target == ctx.target)
+    else {
+        return;
+    };
+    let grid = &ctx.style.snap_grid;
+    let origin = ctx.frame.origin;
+    let step_of = |step: Option<f32>| step.filter(|step| *step > 0.0);
+    let color = |major: bool, distance: f32| {
+        let color = if major {
+            grid.major_color
+        } else {
+            grid.minor_color
+        };
+        color.with_alpha(color.alpha() * snap_grid_fade(grid, distance))
+    };
+
+    match drag.op {
+        #[cfg(feature = "translate")]
+        GizmoOperation::TranslateAxis => {
+            let Some(step) = step_of(snap.translate.get(drag.axis)) else {
+                return;
+            };
+            let dir = drag.axis_dir;
+            let side = dir
+                .cross(ctx.frame.view_dir)
+                .try_normalize()
+                .unwrap_or_else(|| dir.any_orthonormal_vector());
+            let phase = translation_snap_phase(drag, snap.mode, dir);
+            for (offset, major) in snap_steps(
+                step,
+                phase,
+                grid.extent,
+                grid.major_every,
+                MAX_SNAP_GRID_LINES,
+            ) {
+                let half = if major {
+                    grid.tick_length
+                } else {
+                    grid.tick_length * 0.5
+                };
+                let center = origin + dir * offset;
+                let color = color(major, offset.abs());
+                gizmos.line(center - side * half, center + side * half, color);
+            }
+        }
+        #[cfg(feature = "translate")]
+        GizmoOperation::TranslatePlane | GizmoOperation::TranslateView => {
+            let lanes = [
+                (drag.plane_dir1, drag.plane_dir2, drag.plane_axis1),
+                (drag.plane_dir2, drag.plane_dir1, drag.plane_axis2),
+            ];
+            for (across, along, axis) in lanes {
+                let Some(step) = step_of(snap.translate.get(axis)) else {
+                    continue;
+                };
+                let phase = translation_snap_phase(drag, snap.mode, across);
+                for (offset, major) in snap_steps(
+                    step,
+                    phase,
+                    grid.extent,
+                    grid.major_every,
+                    MAX_SNAP_GRID_LINES,
+                ) {
+                    // Clipped to a disc, so the grid fades out evenly.
+                    let reach = (grid.extent * grid.extent - offset * offset)
+                        .max(0.0)
+                        .sqrt();
+                    let center = origin + across * offset;
+                    let piece = 2.0 * reach / SNAP_GRID_LINE_PIECES as f32;
+                    for i in 0..SNAP_GRID_LINE_PIECES {
+                        let from = -reach + piece * i as f32;
+                        let color = color(major, offset.hypot(from + piece * 0.5));
+                        gizmos.line(
+                            center + along * from,
+                            center + along * (from + piece),
+                            color,
+                        );
+                    }
+                }
+            }
+        }
+        #[cfg(feature = "rotate")]
+        GizmoOperation::Rotate | GizmoOperation::RotateView => {
+            let Some(step) = step_of(snap.rotate.get(drag.axis)) else {
+                return;
+            };
+            let axis = drag.axis_dir;
+            let radius = drag_ring_radius(ctx.style, drag);
+            let from = ring_grab_direction(drag, axis);
+            let phase = match snap.mode {
+                SnapMode::Relative => 0.0,
+                SnapMode::Absolute => twist_angle(drag.start_rotation, axis),
+            };
+            for (angle, major) in snap_steps(
+                step,
+                phase,
+                std::f32::consts::PI,
+                grid.major_every,
+                MAX_SNAP_RING_TICKS,
+            ) {
+                let length = if major {
+                    grid.tick_length * 2.0
+                } else {
+                    grid.tick_length
+                };
+                let dir = Quat::from_axis_angle(axis, angle) * from;
+                let color = color(major, 0.0);
+                gizmos.line(
+                    origin + dir * radius,
+                    origin + dir * (radius + length),
+                    color,
+                );
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Signed offsets of ruler ticks from `0` to `distance`, `step` apart, and
+/// whether each is major. At most `max_ticks` are yielded.
+#[cfg(feature = "translate")]
+fn ruler_ticks(
+    distance: f32,
+    step: f32,
+    major_every: u32,
+    max_ticks: u32,
+) -> impl Iterator<Item = (f32, bool)> {
+    let count = ((distance.abs() / step).floor() as u32)
+        .saturating_add(1)
+        .min(max_ticks);
+    let step = step.copysign(distance);
+    (0..count).map(move |k| (k as f32 * step, major_every > 0 && k % major_every == 0))
+}
+
+/// Draw a ruler along the active translate drag on `ctx.target`, from where
+/// it started to where the gizmo is now: ticks along the axis, or along both
+/// plane directions, one per step of `snap`, or of
+/// [`TransformGizmoStyle::ruler_default_step`] where the drag does not snap.
+#[cfg(feature = "translate")]
+fn draw_drag_ruler(
+    ctx: &GizmoDrawContext,
+    gizmos: &mut GizmoLines,
+    snap: Option<&TransformGizmoSnap>,
+) {
+    let Some(drag) = ctx
+        .state
+        .drag
+        .as_ref()
+        .filter(|drag| drag.target == ctx.target)
+    else {
+        return;
+    };
+    let ruler = &ctx.style.ruler;
+    let lanes = match drag.op {
+        GizmoOperation::TranslateAxis => vec![(drag.axis_dir, drag.axis)],
+        GizmoOperation::TranslatePlane | GizmoOperation::TranslateView => vec![
+            (drag.plane_dir1, drag.plane_axis1),
+            (drag.plane_dir2, drag.plane_axis2),
+        ],
+        _ => return,
+    };
+    // The gizmo sits at the visual pivot if there is one, so measure from
+    // where the pivot started.
+    let start = ctx.frame.origin - drag.pivot_delta;
+    for (dir, axis) in lanes {
+        let Some(dir) = dir.try_normalize() else {
+            continue;
+        };
+        let step = snap
+            .and_then(|snap| snap.translate.get(axis))
+            .filter(|step| *step > 0.0)
+            .unwrap_or(ctx.style.ruler_default_step);
+        if step <= 0.0 {
+            continue;
+        }
+        let side = dir
+            .cross(ctx.frame.view_dir)
+            .try_normalize()
+            .unwrap_or_else(|| dir.any_orthonormal_vector());
+        let distance = drag.pivot_delta.dot(dir);
+        for (offset, major) in ruler_ticks(distance, step, ruler.major_every, ruler.max_ticks) {
+            let (half, color) = if major {
+                (ruler.tick_length, ruler.major_color)
+            } else {
+                (ruler.tick_length * 0.5, ruler.minor_color)
+            };
+            let center = start + dir * offset;
+            gizmos.line(center - side * half, center + side * half, color);
+        }
+    }
+}
+
+/// Draw the segment between a target's endpoints and a camera-facing circle
+/// at each end.
+#[cfg(feature = "translate")]
+fn draw_endpoint_handles(
+    ctx: &GizmoDrawContext,
+    gizmos: &mut GizmoLines,
+    camera_transform: &GlobalTransform,
+) {
+    let right: Vec3 = camera_transform.right().into();
+    let up: Vec3 = camera_transform.up().into();
+    let radius = ctx.style.endpoint_size * 0.5;
+    let colors = &ctx.style.endpoint_colors;
+
+    let mut ends = Vec::with_capacity(2);
+    let mut involved = false;
+    for end in [EndpointSign::Negative, EndpointSign::Positive] {
+        let Some((axis, center)) = ctx.frame.endpoint(end) else {
+            return;
+        };
+        let op = GizmoOperation::Endpoint { end };
+        involved |= is_handle_involved(ctx.state, ctx.target, op, axis);
+        let color = if is_axis_active(ctx.state, ctx.target, op, axis) {
+            colors.active
+        } else if ctx.state.hovered_target == Some(ctx.target) && ctx.state.hovered_op == Some(op) {
+            colors.hover
+        } else {
+            colors.idle
+        };
+
+        if let Some(color) = ctx.isolated_handle(color, op, axis) {
+            draw_unit_arc(
+                gizmos,
+                ctx.circles.endpoint.iter().copied(),
+                center,
+                (right, up),
+                radius,
+                color,
+            );
+        }
+        ends.push(center);
+    }
+    // The line between the ends stays while either end is dragged.
+    if let Some(color) = ctx.isolated(colors.idle, involved) {
+        gizmos.line(ends[0], ends[1], color);
+    }
+}
+
+/// Draw a camera-facing circle at the origin (view translation handle).
+#[cfg(feature = "translate")]
+fn draw_translate_view_circle(
+    ctx: &GizmoDrawContext,
+    gizmos: &mut GizmoLines,
+    camera_transform: &GlobalTransform,
+) {
+    let right: Vec3 = camera_transform.right().into();
+    let up: Vec3 = camera_transform.up().into();
+    let radius = ctx.style.translate_view_radius;
+    let colors = &ctx.style.translate_view_colors;
+    let op = GizmoOperation::TranslateView;
+    let color = if is_axis_active(ctx.state, ctx.target, op, GizmoAxis::X) {
+        colors.active
+    } else if ctx.state.hovered_target == Some(ctx.target) && ctx.state.hovered_op == Some(op) {
+        colors.hover
+    } else {
+        colors.idle
+    };
+    let Some(color) = ctx.isolated_handle(color, op, GizmoAxis::X) else {
+        return;
+    };
+
+    draw_unit_arc(
+        gizmos,
+        ctx.circles.translate_view.iter().copied(),
+        ctx.frame.origin,
+        (right, up),
+        radius,
+        color,
+    );
 }
 
 /// Draw a camera-facing square at the origin (uniform scale handle).
+#[cfg(feature = "uniform-scale")]
 fn draw_uniform_scale_square(
-    gizmos: &mut Gizmos,
+    gizmos: &mut GizmoLines,
     origin: Vec3,
     size: f32,
     color: Color,
@@ -210,91 +1087,221 @@ fn draw_uniform_scale_square(
     gizmos.line(p3, p0, color);
 }
 
-fn draw_axis_lines(ctx: &GizmoDrawContext, gizmos: &mut Gizmos, axis_length: f32) {
-    for axis in [GizmoAxis::X, GizmoAxis::Y, GizmoAxis::Z] {
-        let dir = ctx
-            .frame
-            .axis_dir(axis, AxisKind::Translate)
-            .normalize_or_zero();
-        if dir.length_squared() < 1e-6 {
+fn draw_axis_lines(
+    ctx: &GizmoDrawContext,
+    gizmos: &mut GizmoLines,
+    axis_length: f32,
+    #[cfg(feature = "scale")] scale_cubes: bool,
+) {
+    let start = axis_length * ctx.style.axis_line_start;
+    for axis in GizmoAxis::ALL {
+        let dir = ctx.frame.axis_dir(axis, AxisKind::Translate);
+        // The Z axis points straight at a 2D camera.
+        if dir.length_squared() < 1e-6 || (ctx.style.planar_2d && axis == GizmoAxis::Z) {
+            continue;
+        }
+
+        let involved = ctx.active_axes.contains(axis);
+        let Some(color) = ctx.isolated(ctx.axis_line_color(axis), involved) else {
             continue;
+        };
+        #[cfg(feature = "scale")]
+        let (near, far) =
+            scale_cube_gap(ctx, axis, dir, scale_cubes).unwrap_or((axis_length, axis_length));
+        #[cfg(not(feature = "scale"))]
+        let (near, far) = (axis_length, axis_length);
+        for (from, to) in [(start, near), (far.max(start), axis_length)] {
+            if to > from {
+                gizmos.line(
+                    ctx.frame.origin + dir * from,
+                    ctx.frame.origin + dir * to,
+                    color,
+                );
+            }
         }
+    }
+
+    if !ctx.style.show_full_axis_during_drag && ctx.style.drag_isolation == DragIsolation::None {
+        return;
+    }
+    let Some(drag) = ctx
+        .state
+        .drag
+        .as_ref()
+        .filter(|drag| drag.target == ctx.target && drag.op == GizmoOperation::TranslateAxis)
+    else {
+        return;
+    };
+    let dir = drag.axis_dir.normalize_or_zero();
+    let color = ctx.axis_line_color(drag.axis);
+    let reach = dir * axis_length * FULL_AXIS_LENGTH;
+    gizmos.guides(|gizmos| {
+        gizmos.line(
+            ctx.frame.origin - reach,
+            ctx.frame.origin + reach,
+            color.with_alpha(color.alpha() * FULL_AXIS_ALPHA),
+        );
+    });
+}
 
-        let color = ctx.axis_line_color(axis);
-        let end = ctx.frame.origin + dir * axis_length;
-        gizmos.line(ctx.frame.origin, end, color);
+/// Stretch of the `axis` line, as distances from the origin, taken up by
+/// the scale cube sitting on it, if
+/// [`TransformGizmoStyle::axis_line_gap_for_scale_cubes`] leaves it out.
+#[cfg(feature = "scale")]
+fn scale_cube_gap(
+    ctx: &GizmoDrawContext,
+    axis: GizmoAxis,
+    line_dir: Vec3,
+    scale_cubes: bool,
+) -> Option<(f32, f32)> {
+    let style = ctx.style;
+    if !scale_cubes
+        || !style.axis_line_gap_for_scale_cubes
+        || !style.scale_axes.enabled(axis)
+        || style.hidden_in_2d(GizmoOperation::ScaleAxis, axis)
+        || ctx
+            .color(&style.scale, axis, GizmoOperation::ScaleAxis)
+            .is_none()
+    {
+        return None;
+    }
+    // The cubes follow the target's own axes, which only run along the
+    // line in local space or for an unrotated target.
+    let cube_dir = ctx.frame.axis_dir(axis, AxisKind::Scale);
+    if cube_dir.dot(line_dir) < 1.0 - 1e-4 {
+        return None;
     }
+    let factor = scale_handle_factor(
+        ctx.state,
+        style,
+        ctx.target,
+        GizmoOperation::ScaleAxis,
+        axis,
+    );
+    let cube = scale_cube(ctx.frame, style, axis)?.displaced(ctx.frame.origin, factor);
+    let center = (cube.center - ctx.frame.origin).dot(line_dir);
+    Some((center - cube.half_size, center + cube.half_size))
 }
 
-fn draw_translation_cones(ctx: &GizmoDrawContext, gizmos: &mut Gizmos, axis_length: f32) {
-    for axis in [GizmoAxis::X, GizmoAxis::Y, GizmoAxis::Z] {
-        if !ctx.style.translate_axes.enabled(axis) {
+/// Draw the translation cones, with `segments` line segments around each base.
+#[cfg(feature = "translate")]
+fn draw_translation_cones(ctx: &GizmoDrawContext, gizmos: &mut GizmoLines, segments: usize) {
+    for axis in GizmoAxis::ALL {
+        if !ctx.style.translate_axes.enabled(axis)
+            || ctx.style.hidden_in_2d(GizmoOperation::TranslateAxis, axis)
+        {
             continue;
         }
-        let axis_dir = ctx
-            .frame
-            .axis_dir(axis, AxisKind::Translate)
-            .normalize_or_zero();
-        if axis_dir.length_squared() < 1e-6 {
+        let Some(cone) = translate_cone(ctx.frame, ctx.style, axis) else {
             continue;
-        }
-
-        let color = ctx.color(&ctx.style.translate, axis, GizmoOperation::TranslateAxis);
-
-        let line_end = ctx.frame.origin + axis_dir * axis_length;
-        let cone_tip = line_end + axis_dir * ctx.style.translate_cone_length;
+        };
 
-        let (t1, t2) = axis_basis(axis_dir);
-        for i in 0..CONE_SEGMENTS {
-            let a0 = 2.0 * PI * (i as f32) / (CONE_SEGMENTS as f32);
-            let a1 = 2.0 * PI * (i as f32 + 1.0) / (CONE_SEGMENTS as f32);
-            let dir0 = t1 * a0.cos() + t2 * a0.sin();
-            let dir1 = t1 * a1.cos() + t2 * a1.sin();
+        let Some(color) = ctx.color(&ctx.style.translate, axis, GizmoOperation::TranslateAxis)
+        else {
+            continue;
+        };
 
-            let base0 = line_end + dir0 * ctx.style.translate_cone_radius;
-            let base1 = line_end + dir1 * ctx.style.translate_cone_radius;
+        let (t1, t2) = axis_basis(cone.axis_dir);
+        let dirs = arc_points(0.0, TAU, segments).map(|p| t1 * p.x + t2 * p.y);
+        for (dir0, dir1) in dirs.clone().zip(dirs.skip(1)) {
+            let base0 = cone.base + dir0 * cone.radius;
+            let base1 = cone.base + dir1 * cone.radius;
 
-            gizmos.line(cone_tip, base0, color);
-            gizmos.line(cone_tip, base1, color);
+            gizmos.line(cone.tip, base0, color);
+            gizmos.line(cone.tip, base1, color);
             gizmos.line(base0, base1, color);
         }
     }
 }
 
-fn draw_translation_planes(ctx: &GizmoDrawContext, gizmos: &mut Gizmos) {
-    for axis in [GizmoAxis::X, GizmoAxis::Y, GizmoAxis::Z] {
-        if !ctx.style.translate_axes.enabled(axis) {
+/// Strokes of the "X", "Y", and "Z" label glyphs, in a box one unit tall
+/// centered on the label position, with +x right and +y up on screen.
+fn axis_label_strokes(axis: GizmoAxis) -> &'static [(Vec2, Vec2)] {
+    const W: f32 = 0.35;
+    const H: f32 = 0.5;
+    const X: [(Vec2, Vec2); 2] = [
+        (Vec2::new(-W, -H), Vec2::new(W, H)),
+        (Vec2::new(-W, H), Vec2::new(W, -H)),
+    ];
+    const Y: [(Vec2, Vec2); 3] = [
+        (Vec2::new(-W, H), Vec2::ZERO),
+        (Vec2::new(W, H), Vec2::ZERO),
+        (Vec2::ZERO, Vec2::new(0.0, -H)),
+    ];
+    const Z: [(Vec2, Vec2); 3] = [
+        (Vec2::new(-W, H), Vec2::new(W, H)),
+        (Vec2::new(W, H), Vec2::new(-W, -H)),
+        (Vec2::new(-W, -H), Vec2::new(W, -H)),
+    ];
+    match axis {
+        GizmoAxis::X => &X,
+        GizmoAxis::Y => &Y,
+        GizmoAxis::Z => &Z,
+    }
+}
+
+/// Draw an axis letter beyond each translation cone tip. The glyphs are
+/// laid out in the camera's right and up directions, so they stay upright
+/// on screen however the camera is rolled.
+fn draw_axis_labels(
+    ctx: &GizmoDrawContext,
+    gizmos: &mut GizmoLines,
+    axis_length: f32,
+    camera_transform: &GlobalTransform,
+) {
+    let right: Vec3 = camera_transform.right().into();
+    let up: Vec3 = camera_transform.up().into();
+    let size = ctx.style.axis_label_size;
+    for axis in GizmoAxis::ALL {
+        // The drag guide runs along the axis, right through the label.
+        if ctx.active_axes.contains(axis)
+            || ctx.style.hidden_in_2d(GizmoOperation::TranslateAxis, axis)
+        {
+            continue;
+        }
+        let axis_dir = ctx.frame.axis_dir(axis, AxisKind::Translate);
+        if axis_dir.length_squared() < 1e-6 {
             continue;
         }
-        let (d1_axis, d2_axis) = plane_axes(axis);
-
-        let n = ctx
-            .frame
-            .axis_dir(axis, AxisKind::Translate)
-            .normalize_or_zero();
-        let dir1 = ctx
-            .frame
-            .axis_dir(d1_axis, AxisKind::Translate)
-            .normalize_or_zero();
-        let dir2 = ctx
-            .frame
-            .axis_dir(d2_axis, AxisKind::Translate)
-            .normalize_or_zero();
-        if n.length_squared() < 1e-6 || dir1.length_squared() < 1e-6 || dir2.length_squared() < 1e-6
+
+        let color = ctx
+            .style
+            .axis_label_color
+            .unwrap_or(ctx.style.translate.for_axis(axis).idle);
+        let Some(color) = ctx.isolated(color, false) else {
+            continue;
+        };
+        let center =
+            ctx.frame.origin + axis_dir * (axis_length + ctx.style.translate_cone_length + size);
+        for &(a, b) in axis_label_strokes(axis) {
+            gizmos.line(
+                center + (right * a.x + up * a.y) * size,
+                center + (right * b.x + up * b.y) * size,
+                color,
+            );
+        }
+    }
+}
+
+#[cfg(feature = "planes")]
+fn draw_translation_planes(ctx: &GizmoDrawContext, gizmos: &mut GizmoLines) {
+    for axis in GizmoAxis::ALL {
+        if !ctx.style.translate_axes.enabled(axis)
+            || ctx.style.hidden_in_2d(GizmoOperation::TranslatePlane, axis)
         {
             continue;
         }
+        // Flipped towards the camera, like the hit test.
+        let Some(plane) = plane_rect(ctx.frame, ctx.style, axis) else {
+            continue;
+        };
 
-        let color = ctx.color(&ctx.style.translate, axis, GizmoOperation::TranslatePlane);
+        let Some(color) = ctx.color(&ctx.style.translate, axis, GizmoOperation::TranslatePlane)
+        else {
+            continue;
+        };
 
-        let base = ctx.frame.origin
-            + dir1 * ctx.style.translate_plane_offset
-            + dir2 * ctx.style.translate_plane_offset;
-        let p0 = base;
-        let p1 = base + dir1 * ctx.style.translate_plane_size;
-        let p2 =
-            base + dir1 * ctx.style.translate_plane_size + dir2 * ctx.style.translate_plane_size;
-        let p3 = base + dir2 * ctx.style.translate_plane_size;
+        let [p0, p1, p2, p3] = plane.corners();
 
         gizmos.line(p0, p1, color);
         gizmos.line(p1, p2, color);
@@ -303,133 +1310,225 @@ fn draw_translation_planes(ctx: &GizmoDrawContext, gizmos: &mut Gizmos) {
     }
 }
 
-fn draw_scale_cubes(ctx: &GizmoDrawContext, gizmos: &mut Gizmos, axis_length: f32) {
-    let half = ctx.style.scale_cube_size * 0.5;
-    for axis in [GizmoAxis::X, GizmoAxis::Y, GizmoAxis::Z] {
-        if !ctx.style.scale_axes.enabled(axis) {
+#[cfg(feature = "scale")]
+fn draw_scale_cubes(ctx: &GizmoDrawContext, gizmos: &mut GizmoLines) {
+    for axis in GizmoAxis::ALL {
+        if !ctx.style.scale_axes.enabled(axis)
+            || ctx.style.hidden_in_2d(GizmoOperation::ScaleAxis, axis)
+        {
             continue;
         }
-        let axis_dir = ctx
-            .frame
-            .axis_dir(axis, AxisKind::Scale)
-            .normalize_or_zero();
-        if axis_dir.length_squared() < 1e-6 {
+        // The cubes line up with the scale axes, like their hit boxes.
+        let Some(rest) = scale_cube(ctx.frame, ctx.style, axis) else {
+            continue;
+        };
+
+        let Some(color) = ctx.color(&ctx.style.scale, axis, GizmoOperation::ScaleAxis) else {
             continue;
+        };
+
+        let op = GizmoOperation::ScaleAxis;
+        let factor = scale_handle_factor(ctx.state, ctx.style, ctx.target, op, axis);
+        let cube = rest.displaced(ctx.frame.origin, factor);
+        if factor != 1.0 {
+            gizmos.line(rest.center, cube.center, ctx.style.drag_guide_color);
         }
+        draw_cube_edges(gizmos, &cube, color);
+    }
+}
 
-        let color = ctx.color(&ctx.style.scale, axis, GizmoOperation::ScaleAxis);
-
-        let center = ctx.frame.origin + axis_dir * (axis_length * ctx.style.scale_cube_offset);
-
-        let corners = [
-            Vec3::new(-half, -half, -half),
-            Vec3::new(-half, -half, half),
-            Vec3::new(-half, half, -half),
-            Vec3::new(-half, half, half),
-            Vec3::new(half, -half, -half),
-            Vec3::new(half, -half, half),
-            Vec3::new(half, half, -half),
-            Vec3::new(half, half, half),
-        ];
-        let corners: Vec<Vec3> = corners.iter().map(|c| center + *c).collect();
-
-        let edges = [
-            (0, 1),
-            (0, 2),
-            (0, 4),
-            (1, 3),
-            (1, 5),
-            (2, 3),
-            (2, 6),
-            (3, 7),
-            (4, 5),
-            (4, 6),
-            (5, 7),
-            (6, 7),
-        ];
-
-        for (i0, i1) in edges {
-            gizmos.line(corners[i0], corners[i1], color);
-        }
-    }
-}
-
-fn draw_rotation_arcs(ctx: &GizmoDrawContext, gizmos: &mut Gizmos, axis_length: f32) {
-    let total_angle_radians = ctx.style.rotation_arc_degrees.to_radians();
-    let radius = axis_length;
-    let segments = ctx.style.rotation_arc_segments;
-
-    for (axis, axis_vec, n1, n2) in [
-        (
-            GizmoAxis::X,
-            ctx.frame.axis_dir(GizmoAxis::X, AxisKind::Rotate),
-            ctx.frame.axis_dir(GizmoAxis::Y, AxisKind::Rotate),
-            ctx.frame.axis_dir(GizmoAxis::Z, AxisKind::Rotate),
-        ),
-        (
-            GizmoAxis::Y,
-            ctx.frame.axis_dir(GizmoAxis::Y, AxisKind::Rotate),
-            ctx.frame.axis_dir(GizmoAxis::Z, AxisKind::Rotate),
-            ctx.frame.axis_dir(GizmoAxis::X, AxisKind::Rotate),
-        ),
-        (
-            GizmoAxis::Z,
-            ctx.frame.axis_dir(GizmoAxis::Z, AxisKind::Rotate),
-            ctx.frame.axis_dir(GizmoAxis::X, AxisKind::Rotate),
-            ctx.frame.axis_dir(GizmoAxis::Y, AxisKind::Rotate),
-        ),
-    ] {
-        if !ctx.style.rotate_axes.enabled(axis) {
+/// Draw the twelve edges of `cube`.
+#[cfg(feature = "scale")]
+fn draw_cube_edges(gizmos: &mut GizmoLines, cube: &CubeGeometry, color: Color) {
+    let corners = cube.corners();
+    // Corners one bit apart share an edge.
+    for i in 0..corners.len() {
+        for bit in [1, 2, 4] {
+            if i & bit == 0 {
+                gizmos.line(corners[i], corners[i | bit], color);
+            }
+        }
+    }
+}
+
+/// Draw the rotation arcs or rings, each in `segments` line segments.
+#[cfg(feature = "rotate")]
+fn draw_rotation_arcs(ctx: &GizmoDrawContext, gizmos: &mut GizmoLines, segments: usize) {
+    for axis in GizmoAxis::ALL {
+        if !ctx.style.rotate_axes.enabled(axis)
+            || ctx.style.hidden_in_2d(GizmoOperation::Rotate, axis)
+        {
             continue;
         }
-        draw_rotation_arc(
-            gizmos,
-            ctx.frame.origin,
-            axis_vec,
-            n1,
-            n2,
-            ctx.color(&ctx.style.rotate, axis, GizmoOperation::Rotate),
-            radius,
-            total_angle_radians,
-            segments,
-        );
+        let Some(ring) = rotation_ring(ctx.frame, ctx.style, axis) else {
+            continue;
+        };
+        let Some(color) = ctx.color(&ctx.style.rotate, axis, GizmoOperation::Rotate) else {
+            continue;
+        };
+        draw_ring_arc(gizmos, &ring, segments, color);
+    }
+}
+
+/// Draw a screen-facing circle of `radius` around the origin for the `op`
+/// handle: the ring that rotates around the view direction, or the outline
+/// of the trackball.
+#[cfg(feature = "rotate")]
+fn draw_view_circle(
+    ctx: &GizmoDrawContext,
+    gizmos: &mut GizmoLines,
+    op: GizmoOperation,
+    colors: &GizmoStateColors,
+    radius: f32,
+) {
+    if !ctx.frame.allows(op, GizmoAxis::X) {
+        return;
     }
+    let is_active = matches!(
+        ctx.state.drag.as_ref(),
+        Some(drag) if drag.target == ctx.target && drag.op == op
+    );
+    let is_hovered =
+        ctx.state.hovered_target == Some(ctx.target) && ctx.state.hovered_op == Some(op);
+    let color = if is_active {
+        colors.active
+    } else if is_hovered {
+        colors.hover
+    } else {
+        colors.idle
+    };
+    let Some(color) = ctx.isolated(color, is_active) else {
+        return;
+    };
+
+    let segments = ctx.circle_segments(
+        radius,
+        TAU,
+        ctx.style.rotation_arc_segments_min,
+        ctx.style.rotation_arc_segments,
+    );
+    draw_unit_arc(
+        gizmos,
+        arc_points(0.0, TAU, segments),
+        ctx.frame.origin,
+        axis_basis(ctx.frame.view_dir),
+        radius,
+        color,
+    );
 }
 
+/// What [`draw_gizmo`] reads from each target.
+type DrawnTarget = (
+    GizmoTargetQuery,
+    Option<&'static GizmoColorTint>,
+    Has<GizmoLocked>,
+);
+
 /// Draw the transform gizmo at the active target (if any).
+#[allow(clippy::too_many_arguments)]
 pub fn draw_gizmo(
     state: Res<TransformGizmoState>,
     style: Res<TransformGizmoStyle>,
-    targets: Query<(Entity, &GlobalTransform), With<TransformGizmoTarget>>,
-    cameras: Query<(&Camera, &GlobalTransform), With<TransformGizmoCamera>>,
-    mut gizmos: Gizmos,
+    targets: Query<DrawnTarget, With<TransformGizmoTarget>>,
+    global_transforms: Query<&GlobalTransform>,
+    camera_selection: Res<GizmoCameraSelection>,
+    cameras: Query<(&Camera, &GlobalTransform, Option<&Frustum>), With<TransformGizmoCamera>>,
+    #[cfg(any(feature = "translate", feature = "rotate"))] snap: Res<TransformGizmoSnap>,
+    #[cfg(any(feature = "translate", feature = "rotate"))] keys: Res<ButtonInput<KeyCode>>,
+    mut gizmos: Gizmos<TransformGizmoConfigGroup>,
+    mut guide_gizmos: Gizmos<TransformGizmoGuideConfigGroup>,
+    mut default_gizmos: Gizmos,
+    origin_press: Res<OriginDotPress>,
+    proportional: Res<GizmoProportionalEdit>,
+    mut stats: ResMut<GizmoFrameStats>,
+    mut circle_tables: Local<Option<CircleTables>>,
 ) {
-    let Some((_camera, camera_transform)) = cameras.iter().next() else {
+    let mut scope = DrawStatsScope::new(&mut stats);
+    // The increments the drag is snapping to this frame, if it is.
+    #[cfg(any(feature = "translate", feature = "rotate"))]
+    let snap = state.drag.as_ref().map(|_| snap.resolve(&keys));
+    let Some((camera, camera_transform, frustum)) = camera_selection
+        .camera()
+        .and_then(|camera| cameras.get(camera).ok())
+    else {
         return;
     };
 
-    for (entity, transform) in targets.iter() {
-        let frame = GizmoFrame::new(transform, state.space);
+    let mut lines = GizmoLines {
+        gizmos: &mut gizmos,
+        guide_gizmos: &mut guide_gizmos,
+        default_gizmos: style
+            .draw_in_default_gizmo_group
+            .then_some(&mut default_gizmos),
+        drawing_guides: false,
+        segments: 0,
+    };
+    let sizing = GizmoSizing::new(&style, camera, camera_transform);
+    // The tables never change, so they are kept between frames.
+    let circles = &*circle_tables.get_or_insert_with(CircleTables::new);
+    for (
+        (entity, transform, anchor, channel, channel_value, endpoints, parent, constraints),
+        tint,
+        locked,
+    ) in targets.iter()
+    {
+        if !style.shows_target(&state, entity) || (locked && !style.show_locked_gizmos) {
+            continue;
+        }
+        let framed = target_frame(
+            transform,
+            parent.and_then(|parent| global_transforms.get(parent.parent()).ok()),
+            anchor_pivot(anchor, &style).as_ref(),
+            channel,
+            channel_value,
+            endpoints,
+            constraints,
+            state.frame_space(entity),
+            &sizing,
+        );
+        let frame = held_plane_signs(framed.0, &state, entity);
+        #[cfg(any(feature = "translate", feature = "rotate", feature = "scale"))]
+        let parts = framed.1;
+        let mut style = frame.sized_style(&style);
+        let detail = sizing.detail(&frame, &style, frustum);
+        if detail == GizmoDetail::Culled {
+            continue;
+        }
+        if locked {
+            style = Cow::Owned(style.locked());
+        } else if let Some(tint) = tint {
+            style = Cow::Owned(style.tinted(tint.0));
+        }
+        if state.active_target != Some(entity) {
+            style = Cow::Owned(style.faded(style.inactive_target_alpha));
+        }
+        if let GizmoDetail::OriginDot { grow } = detail {
+            draw_origin_dot(
+                &mut lines,
+                frame.origin,
+                style.origin_dot_size * grow,
+                style.origin_dot_colors.idle,
+                camera_transform,
+            );
+            continue;
+        }
         let axis_length = style.axis_length;
+        // In mesh mode the active target's cones, planes, and cubes are
+        // mesh entities (see `meshes`), so only the rest is drawn here.
+        #[cfg(any(feature = "translate", feature = "scale"))]
+        let meshed =
+            style.render_mode == GizmoRenderMode::Mesh && state.active_target == Some(entity);
 
-        let hover_axes: Vec<GizmoAxis> = if state.active_target == Some(entity) {
-            if let (Some(axis), Some(op)) = (state.hovered_axis, state.hovered_op) {
+        let hover_axes = match (state.hovered_axis, state.hovered_op) {
+            (Some(axis), Some(op)) if state.hovered_target == Some(entity) => {
                 axes_involved(op, axis)
-            } else {
-                Vec::new()
             }
-        } else {
-            Vec::new()
+            _ => AxisMask::NONE,
         };
 
-        let active_axes: Vec<GizmoAxis> = if let Some(drag) = &state.drag {
-            if drag.target == entity {
-                axes_involved(drag.op, drag.axis)
-            } else {
-                Vec::new()
-            }
-        } else {
-            Vec::new()
+        let active_axes = match &state.drag {
+            Some(drag) if drag.target == entity => axes_involved(drag.op, drag.axis),
+            _ => AxisMask::NONE,
         };
 
         let ctx = GizmoDrawContext {
@@ -439,66 +1538,254 @@ pub fn draw_gizmo(
             target: entity,
             hover_axes,
             active_axes,
+            #[cfg(feature = "translate")]
+            circles,
+            #[cfg(any(feature = "translate", feature = "rotate"))]
+            pixels_per_unit: sizing.pixels_per_unit(frame.origin),
         };
 
-        let show_translate = style.show_translate;
-        let show_rotate = style.show_rotate;
-        let show_scale = style.show_scale;
+        #[cfg(feature = "translate")]
+        let show_translate = style.show_translate && parts.translate;
+        #[cfg(feature = "rotate")]
+        let show_rotate = style.show_rotate && parts.rotate;
+        #[cfg(feature = "scale")]
+        let show_scale = style.show_scale && parts.scale;
 
         if style.show_axis_lines {
-            draw_axis_lines(&ctx, &mut gizmos, axis_length);
+            draw_axis_lines(
+                &ctx,
+                &mut lines,
+                axis_length,
+                #[cfg(feature = "scale")]
+                show_scale,
+            );
         }
 
-        if show_translate {
-            draw_translation_cones(&ctx, &mut gizmos, axis_length);
+        #[cfg(feature = "translate")]
+        if show_translate && style.show_translate_view {
+            draw_translate_view_circle(&ctx, &mut lines, camera_transform);
+        }
+
+        #[cfg(feature = "translate")]
+        if show_translate && !meshed {
+            let segments = ctx.circle_segments(
+                style.translate_cone_radius,
+                TAU,
+                style.translate_cone_segments_min,
+                style.translate_cone_segments,
+            );
+            draw_translation_cones(&ctx, &mut lines, segments);
+            #[cfg(feature = "planes")]
             if style.show_translate_planes {
-                draw_translation_planes(&ctx, &mut gizmos);
+                draw_translation_planes(&ctx, &mut lines);
             }
         }
 
-        if show_scale {
-            draw_scale_cubes(&ctx, &mut gizmos, axis_length);
+        if style.show_axis_labels {
+            draw_axis_labels(&ctx, &mut lines, axis_length, camera_transform);
+        }
 
-            if style.show_scale_uniform {
-                let colors = &style.scale_uniform_colors;
-                let is_active = matches!(
-                    state.drag.as_ref(),
-                    Some(drag)
-                        if drag.target == entity && matches!(drag.op, GizmoOperation::ScaleUniform)
-                );
-                let is_hovered = state.active_target == Some(entity)
-                    && matches!(state.hovered_op, Some(GizmoOperation::ScaleUniform));
+        #[cfg(feature = "scale")]
+        if show_scale && !meshed {
+            draw_scale_cubes(&ctx, &mut lines);
+        }
 
-                let color = if is_active {
-                    colors.active
-                } else if is_hovered {
-                    colors.hover
-                } else {
-                    colors.idle
-                };
+        #[cfg(feature = "uniform-scale")]
+        if show_scale
+            && style.show_scale_uniform
+            && frame.allows(GizmoOperation::ScaleUniform, GizmoAxis::X)
+        {
+            let colors = &style.scale_uniform_colors;
+            let is_active = matches!(
+                state.drag.as_ref(),
+                Some(drag)
+                    if drag.target == entity && matches!(drag.op, GizmoOperation::ScaleUniform)
+            );
+            let is_hovered = state.hovered_target == Some(entity)
+                && matches!(state.hovered_op, Some(GizmoOperation::ScaleUniform));
 
+            let color = if is_active {
+                colors.active
+            } else if is_hovered {
+                colors.hover
+            } else {
+                colors.idle
+            };
+
+            if let Some(color) = ctx.isolated(color, is_active) {
+                let op = GizmoOperation::ScaleUniform;
+                let factor = scale_handle_factor(ctx.state, ctx.style, entity, op, GizmoAxis::X);
                 draw_uniform_scale_square(
-                    &mut gizmos,
+                    &mut lines,
                     frame.origin,
-                    style.scale_uniform_size,
+                    style.scale_uniform_size * factor.abs(),
                     color,
                     camera_transform,
                 );
             }
         }
 
+        #[cfg(feature = "rotate")]
         if show_rotate {
-            draw_rotation_arcs(&ctx, &mut gizmos, axis_length);
+            let segments = ctx.circle_segments(
+                axis_length,
+                style.rotation_ring_style.extent_radians(),
+                style.rotation_arc_segments_min,
+                style.rotation_arc_segments,
+            );
+            draw_rotation_arcs(&ctx, &mut lines, segments);
+            if style.show_rotate_view
+                && !style.hidden_in_2d(GizmoOperation::RotateView, GizmoAxis::X)
+            {
+                draw_view_circle(
+                    &ctx,
+                    &mut lines,
+                    GizmoOperation::RotateView,
+                    &style.rotate_view_colors,
+                    style.rotate_view_radius,
+                );
+            }
+            if style.show_rotate_trackball
+                && !style.hidden_in_2d(GizmoOperation::RotateTrackball, GizmoAxis::X)
+            {
+                draw_view_circle(
+                    &ctx,
+                    &mut lines,
+                    GizmoOperation::RotateTrackball,
+                    &style.rotate_trackball_colors,
+                    axis_length,
+                );
+            }
+        }
+
+        #[cfg(feature = "translate")]
+        if style.show_endpoints && parts.translate {
+            draw_endpoint_handles(&ctx, &mut lines, camera_transform);
+        }
+
+        if style.show_drag_feedback {
+            lines.guides(|lines| draw_drag_feedback(&ctx, lines, camera_transform));
+        }
+
+        #[cfg(feature = "translate")]
+        lines.guides(|lines| draw_align_line(&ctx, lines));
+
+        #[cfg(any(feature = "translate", feature = "rotate"))]
+        if style.snap_grid.enabled {
+            if let Some(snap) = &snap {
+                lines.guides(|lines| draw_snap_grid(&ctx, lines, snap));
+            }
+        }
+
+        #[cfg(feature = "translate")]
+        if style.ruler.enabled {
+            lines.guides(|lines| draw_drag_ruler(&ctx, lines, snap.as_ref()));
         }
 
         if style.show_origin_dot {
-            draw_origin_dot(
-                &mut gizmos,
-                frame.origin,
-                style.origin_dot_size,
-                style.origin_dot_color,
-                camera_transform,
-            );
+            let colors = &style.origin_dot_colors;
+            let is_pressed = matches!(origin_press.0, Some((target, _)) if target == entity);
+            let is_hovered = state.hovered_target == Some(entity)
+                && matches!(state.hovered_op, Some(GizmoOperation::OriginDot));
+            let color = if is_pressed {
+                colors.active
+            } else if is_hovered {
+                colors.hover
+            } else {
+                colors.idle
+            };
+            let involved = is_pressed
+                || matches!(&state.drag, Some(drag) if drag.op == GizmoOperation::OriginDot);
+            if let Some(color) = ctx.isolated(color, involved) {
+                draw_origin_dot(
+                    &mut lines,
+                    frame.origin,
+                    style.origin_dot_size,
+                    color,
+                    camera_transform,
+                );
+            }
         }
+
+        if proportional.enabled && state.active_target == Some(entity) {
+            lines.guides(|lines| {
+                draw_influence_circle(
+                    lines,
+                    circles,
+                    frame.origin,
+                    &proportional,
+                    camera_transform,
+                );
+            });
+        }
+    }
+    scope.draw_segments = lines.segments;
+}
+
+#[cfg(all(
+    test,
+    any(feature = "translate", feature = "rotate", feature = "scale")
+))]
+mod tests {
+    #[cfg(any(feature = "translate", feature = "rotate"))]
+    use std::f32::consts::{FRAC_PI_2, PI};
+
+    use super::*;
+    #[cfg(feature = "scale")]
+    use crate::test_utils::GizmoTestHarness;
+
+    #[cfg(any(feature = "translate", feature = "rotate"))]
+    #[test]
+    fn adaptive_segments_follow_the_arc_length_on_screen() {
+        // A 30 pixel arc gets one segment per `PIXELS_PER_SEGMENT`.
+        assert_eq!(adaptive_segments(Some(30.0), 4, 20), 5);
+        assert_eq!(adaptive_segments(Some(1.0), 4, 20), 4);
+        assert_eq!(adaptive_segments(Some(10_000.0), 4, 20), 20);
+        assert_eq!(adaptive_segments(None, 4, 20), 20);
+        // A gizmo filling the screen stays under the cap, however many
+        // segments the style asks for.
+        assert_eq!(
+            adaptive_segments(Some(100_000.0), 4, 10_000),
+            MAX_ADAPTIVE_SEGMENTS
+        );
+        assert_eq!(adaptive_segments(Some(f32::NAN), 0, 0), 2);
+    }
+
+    #[cfg(any(feature = "translate", feature = "rotate"))]
+    #[test]
+    fn arc_points_step_evenly_from_start_through_the_sweep() {
+        let points: Vec<_> = arc_points(FRAC_PI_2, PI, 64).collect();
+        assert_eq!(points.len(), 65);
+        assert!(points[0].distance(Vec2::Y) < 1e-5);
+        assert!(points[32].distance(Vec2::NEG_X) < 1e-4);
+        assert!(points[64].distance(Vec2::NEG_Y) < 1e-4);
+    }
+
+    #[cfg(feature = "scale")]
+    #[test]
+    fn scale_cubes_follow_the_drag_until_release() {
+        let factor = |harness: &GizmoTestHarness, axis: GizmoAxis| {
+            let style = harness.app().world().resource::<TransformGizmoStyle>();
+            let op = GizmoOperation::ScaleAxis;
+            scale_handle_factor(harness.state(), style, harness.target(), op, axis)
+        };
+        let mut harness = GizmoTestHarness::new();
+        let start = harness.grab(GizmoOperation::ScaleAxis, GizmoAxis::X);
+        harness.press_left();
+        harness.step();
+        harness.move_cursor(start + harness.screen_axis(Vec3::X) * 40.0);
+        harness.step();
+
+        let scale = harness.target_transform().scale.x;
+        assert!(scale > 1.05, "{scale}");
+        assert!((factor(&harness, GizmoAxis::X) - scale).abs() < 1.0e-4);
+        assert_eq!(factor(&harness, GizmoAxis::Y), 1.0);
+        harness.style_mut().scale_handles_follow_drag = false;
+        assert_eq!(factor(&harness, GizmoAxis::X), 1.0);
+
+        harness.style_mut().scale_handles_follow_drag = true;
+        harness.release_left();
+        harness.step();
+        assert_eq!(factor(&harness, GizmoAxis::X), 1.0);
     }
 }
@@ -0,0 +1,250 @@
+//! Settings panel for `bevy_egui`.
+//!
+//! [`gizmo_settings_ui`] draws the gizmo's common settings into any egui
+//! `Ui`: the tool, the coordinate space, snap increments, and the style's
+//! colors and sizes. [`TransformGizmoEguiPlugin`] shows it in a side panel
+//! and keeps [`GizmoPointer::captured`] set while the cursor is over egui,
+//! so clicks on the panel never reach the gizmo behind it.
+
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts, EguiPlugin, EguiPrimaryContextPass};
+
+use crate::types::{
+    AngleUnit, AxisColors, AxisSnap, GizmoPointer, TransformGizmoMode, TransformGizmoSnap,
+    TransformGizmoSpace, TransformGizmoState, TransformGizmoStyle,
+};
+
+/// Shows [`gizmo_settings_ui`] in a side panel of the primary egui context.
+///
+/// Adds `bevy_egui`'s `EguiPlugin` unless it is already added. While the
+/// cursor is over any egui area, or egui is using the pointer for a drag,
+/// [`GizmoPointer::captured`] is set.
+pub struct TransformGizmoEguiPlugin;
+
+impl Plugin for TransformGizmoEguiPlugin {
+    fn build(&self, app: &mut App) {
+        if !app.is_plugin_added::<EguiPlugin>() {
+            app.add_plugins(EguiPlugin::default());
+        }
+        app.add_systems(EguiPrimaryContextPass, gizmo_settings_panel);
+    }
+}
+
+/// Draw the settings panel and report whether egui holds the pointer.
+fn gizmo_settings_panel(
+    mut contexts: EguiContexts,
+    mut state: ResMut<TransformGizmoState>,
+    mut style: ResMut<TransformGizmoStyle>,
+    mut snap: ResMut<TransformGizmoSnap>,
+    mut pointer: ResMut<GizmoPointer>,
+    mut was_captured: Local<bool>,
+) -> Result {
+    let ctx = contexts.ctx_mut()?;
+    // Only edits mark the settings changed, not every frame the panel shows.
+    let mut changed = false;
+    // Panels are laid out inside a Ui covering the whole viewport.
+    let mut viewport_ui = egui::Ui::new(
+        ctx.clone(),
+        "transform_gizmo_viewport".into(),
+        egui::UiBuilder::new()
+            .layer_id(egui::LayerId::background())
+            .max_rect(ctx.viewport_rect()),
+    );
+    let panel = egui::Panel::left("transform_gizmo_settings").show_inside(&mut viewport_ui, |ui| {
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            changed = gizmo_settings_ui(
+                ui,
+                state.bypass_change_detection(),
+                style.bypass_change_detection(),
+                snap.bypass_change_detection(),
+            );
+        });
+    });
+    if changed {
+        state.set_changed();
+        style.set_changed();
+        snap.set_changed();
+    }
+
+    // Only write on change, so other UI can share the flag. The panel is on
+    // the background layer, which `is_pointer_over_egui` does not count.
+    let captured = panel.response.contains_pointer()
+        || ctx.is_pointer_over_egui()
+        || ctx.egui_is_using_pointer();
+    if *was_captured != captured {
+        pointer.captured = captured;
+        *was_captured = captured;
+    }
+    Ok(())
+}
+
+/// Draw the gizmo settings into `ui`, returning whether any were edited.
+///
+/// Picking a tool sets [`TransformGizmoState::mode`] and shows only that
+/// tool's handles; the checkboxes below combine them. Rotation snap
+/// increments are shown in degrees. When drawing this in your own window,
+/// set [`GizmoPointer::captured`] while the cursor is over it.
+pub fn gizmo_settings_ui(
+    ui: &mut egui::Ui,
+    state: &mut TransformGizmoState,
+    style: &mut TransformGizmoStyle,
+    snap: &mut TransformGizmoSnap,
+) -> bool {
+    let mut changed = false;
+
+    ui.heading("Tool");
+    ui.horizontal(|ui| {
+        for (mode, label) in [
+            (TransformGizmoMode::Translate, "T"),
+            (TransformGizmoMode::Rotate, "R"),
+            (TransformGizmoMode::Scale, "S"),
+        ] {
+            let picked = ui.selectable_value(&mut state.mode, mode, label);
+            if picked.on_hover_text(mode.to_string()).clicked() {
+                style.show_translate = mode == TransformGizmoMode::Translate;
+                style.show_rotate = mode == TransformGizmoMode::Rotate;
+                style.show_scale = mode == TransformGizmoMode::Scale;
+                changed = true;
+            }
+        }
+    });
+    ui.horizontal(|ui| {
+        changed |= ui.checkbox(&mut style.show_translate, "Move").changed();
+        changed |= ui.checkbox(&mut style.show_rotate, "Rotate").changed();
+        changed |= ui.checkbox(&mut style.show_scale, "Scale").changed();
+    });
+
+    ui.heading("Space");
+    ui.horizontal(|ui| {
+        for space in [
+            TransformGizmoSpace::World,
+            TransformGizmoSpace::Local,
+            TransformGizmoSpace::Parent,
+            TransformGizmoSpace::View,
+        ] {
+            changed |= ui
+                .selectable_value(&mut state.space, space, space.to_string())
+                .changed();
+        }
+        if let TransformGizmoSpace::Custom(_) = state.space {
+            ui.label("Custom");
+        }
+    });
+
+    ui.heading("Snapping");
+    changed |= ui.checkbox(&mut snap.enabled, "Enabled").changed();
+    egui::Grid::new("transform_gizmo_snap").show(ui, |ui| {
+        changed |= axis_snap_rows(ui, "Move", &mut snap.translate, 1.0, 0.5);
+        if axis_snap_rows(
+            ui,
+            "Rotate (°)",
+            &mut snap.rotate,
+            1f32.to_degrees(),
+            15f32.to_radians(),
+        ) {
+            snap.rotate_unit = AngleUnit::Degrees;
+            changed = true;
+        }
+        changed |= axis_snap_rows(ui, "Scale", &mut snap.scale, 1.0, 0.1);
+    });
+
+    ui.collapsing("Colors", |ui| {
+        egui::Grid::new("transform_gizmo_colors").show(ui, |ui| {
+            ui.label("");
+            ui.label("Idle");
+            ui.label("Hover");
+            ui.label("Active");
+            ui.end_row();
+            changed |= axis_color_rows(ui, "Axis lines", &mut style.axis_lines);
+            changed |= axis_color_rows(ui, "Move", &mut style.translate);
+            changed |= axis_color_rows(ui, "Rotate", &mut style.rotate);
+            changed |= axis_color_rows(ui, "Scale", &mut style.scale);
+        });
+    });
+
+    ui.collapsing("Sizes", |ui| {
+        egui::Grid::new("transform_gizmo_sizes").show(ui, |ui| {
+            changed |= size_row(ui, "Line width", &mut style.line_width, 0.1);
+            changed |= size_row(ui, "Axis length", &mut style.axis_length, 0.01);
+            changed |= size_row(ui, "Cone length", &mut style.translate_cone_length, 0.01);
+            changed |= size_row(ui, "Cone radius", &mut style.translate_cone_radius, 0.01);
+            changed |= size_row(ui, "Cube size", &mut style.scale_cube_size, 0.01);
+            changed |= size_row(ui, "Arc thickness", &mut style.rotation_arc_thickness, 0.01);
+            changed |= size_row(ui, "View ring radius", &mut style.rotate_view_radius, 0.01);
+        });
+    });
+
+    changed
+}
+
+/// Grid rows turning each axis of `snap` on and off and editing its
+/// increment, shown multiplied by `display`. Turning an axis on starts it at
+/// `default`.
+fn axis_snap_rows(
+    ui: &mut egui::Ui,
+    label: &str,
+    snap: &mut AxisSnap,
+    display: f32,
+    default: f32,
+) -> bool {
+    let mut changed = false;
+    for (axis, increment) in [("X", &mut snap.x), ("Y", &mut snap.y), ("Z", &mut snap.z)] {
+        let mut enabled = increment.is_some();
+        let mut value = increment.unwrap_or(default) * display;
+        ui.label(format!("{label} {axis}"));
+        let toggled = ui.checkbox(&mut enabled, "").changed();
+        let edited = ui
+            .add_enabled(
+                enabled,
+                egui::DragValue::new(&mut value)
+                    .speed(0.01 * display)
+                    .range(0.0..=f32::MAX),
+            )
+            .changed();
+        if toggled || edited {
+            *increment = enabled.then_some(value / display);
+            changed = true;
+        }
+        ui.end_row();
+    }
+    changed
+}
+
+/// Grid rows with idle, hover, and active color pickers for each axis.
+fn axis_color_rows(ui: &mut egui::Ui, label: &str, colors: &mut AxisColors) -> bool {
+    let mut changed = false;
+    for (axis, colors) in [
+        ("X", &mut colors.x),
+        ("Y", &mut colors.y),
+        ("Z", &mut colors.z),
+    ] {
+        ui.label(format!("{label} {axis}"));
+        changed |= color_button(ui, &mut colors.idle);
+        changed |= color_button(ui, &mut colors.hover);
+        changed |= color_button(ui, &mut colors.active);
+        ui.end_row();
+    }
+    changed
+}
+
+fn color_button(ui: &mut egui::Ui, color: &mut Color) -> bool {
+    let mut rgba = color.to_srgba().to_f32_array();
+    let changed = ui.color_edit_button_rgba_unmultiplied(&mut rgba).changed();
+    if changed {
+        *color = Color::srgba(rgba[0], rgba[1], rgba[2], rgba[3]);
+    }
+    changed
+}
+
+fn size_row(ui: &mut egui::Ui, label: &str, value: &mut f32, speed: f32) -> bool {
+    ui.label(label);
+    let changed = ui
+        .add(
+            egui::DragValue::new(value)
+                .speed(speed)
+                .range(0.0..=f32::MAX),
+        )
+        .changed();
+    ui.end_row();
+    changed
+}
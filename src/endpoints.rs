@@ -0,0 +1,143 @@
+//! Endpoint handles for two-ended targets.
+//!
+//! Beams, pipes, and rope segments are easier to place by their ends than by
+//! their origin. For targets with [`GizmoEndpoints`], dragging one end
+//! re-solves the whole local transform so that end lands where the cursor
+//! puts it while the other end stays where it is.
+
+use bevy::prelude::*;
+
+use crate::types::{EndpointSign, GizmoAxis, GizmoEndpoints};
+
+/// Shortest distance between the ends an endpoint drag can produce, in
+/// parent-space units.
+pub(crate) const MIN_ENDPOINT_LENGTH: f32 = 1.0e-3;
+
+/// Update `transform` so that `end` lies at `moved` and the opposite end at
+/// `fixed`, both in the parent's space.
+///
+/// The long axis is turned by the shortest arc from its current direction,
+/// so twist about it is preserved. Ends closer than
+/// [`MIN_ENDPOINT_LENGTH`] are pushed apart along the current direction
+/// instead of collapsing the scale or producing an undefined rotation.
+pub(crate) fn solve_endpoint_drag(
+    transform: &mut Transform,
+    endpoints: &GizmoEndpoints,
+    end: EndpointSign,
+    fixed: Vec3,
+    moved: Vec3,
+) {
+    if endpoints.half_length_basis <= 0.0 {
+        return;
+    }
+    let axis = endpoints.local_axis.to_vec3();
+    let current = (transform.rotation * axis * end.signum()).normalize();
+    let offset = moved - fixed;
+    let length = offset.length();
+    let (direction, length) = match offset.try_normalize() {
+        Some(direction) if length >= MIN_ENDPOINT_LENGTH => (direction, length),
+        _ => (current, MIN_ENDPOINT_LENGTH),
+    };
+
+    transform.rotation =
+        (Quat::from_rotation_arc(current, direction) * transform.rotation).normalize();
+    transform.translation = fixed + direction * (length * 0.5);
+    let scale = length * 0.5 / endpoints.half_length_basis;
+    match endpoints.local_axis {
+        GizmoAxis::X => transform.scale.x = scale,
+        GizmoAxis::Y => transform.scale.y = scale,
+        GizmoAxis::Z => transform.scale.z = scale,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn end_in_parent(transform: &Transform, endpoints: &GizmoEndpoints, end: EndpointSign) -> Vec3 {
+        transform.transform_point(endpoints.local_position(end))
+    }
+
+    #[test]
+    fn fixed_end_stays_put_under_rotated_parent() {
+        let endpoints = GizmoEndpoints::new(GizmoAxis::Y, 1.0);
+        let parent = GlobalTransform::from(
+            Transform::from_xyz(2.0, -1.0, 0.5).with_rotation(Quat::from_euler(
+                EulerRot::XYZ,
+                0.4,
+                -1.1,
+                0.7,
+            )),
+        );
+        let mut transform = Transform::from_xyz(0.3, 1.0, -0.2)
+            .with_rotation(Quat::from_rotation_z(0.5))
+            .with_scale(Vec3::new(0.5, 1.5, 0.5));
+        let fixed_world = parent.transform_point(end_in_parent(
+            &transform,
+            &endpoints,
+            EndpointSign::Negative,
+        ));
+        let fixed = end_in_parent(&transform, &endpoints, EndpointSign::Negative);
+        let to_parent = parent.affine().inverse();
+
+        // Drag the positive end along a curved world-space path that passes
+        // straight through the fixed end halfway.
+        for frame in 0..=60 {
+            let t = frame as f32 / 60.0;
+            let target_world = fixed_world + (1.0 - 2.0 * t) * Vec3::new(3.0, 1.0 + t, -2.0);
+            let moved = to_parent.transform_point3(target_world);
+            solve_endpoint_drag(
+                &mut transform,
+                &endpoints,
+                EndpointSign::Positive,
+                fixed,
+                moved,
+            );
+
+            assert!(
+                transform.is_finite(),
+                "non-finite transform at frame {frame}"
+            );
+            let fixed_now = parent.transform_point(end_in_parent(
+                &transform,
+                &endpoints,
+                EndpointSign::Negative,
+            ));
+            assert!(
+                fixed_now.distance(fixed_world) < 1.0e-4,
+                "fixed end drifted by {} at frame {frame}",
+                fixed_now.distance(fixed_world)
+            );
+            if moved.distance(fixed) >= MIN_ENDPOINT_LENGTH {
+                let moved_now = parent.transform_point(end_in_parent(
+                    &transform,
+                    &endpoints,
+                    EndpointSign::Positive,
+                ));
+                assert!(moved_now.distance(target_world) < 1.0e-3);
+            }
+        }
+    }
+
+    #[test]
+    fn collapsed_ends_clamp_to_minimum_length() {
+        let endpoints = GizmoEndpoints::new(GizmoAxis::X, 0.5);
+        let mut transform = Transform::from_xyz(1.0, 0.0, 0.0);
+        let fixed = end_in_parent(&transform, &endpoints, EndpointSign::Positive);
+        let rotation = transform.rotation;
+
+        solve_endpoint_drag(
+            &mut transform,
+            &endpoints,
+            EndpointSign::Negative,
+            fixed,
+            fixed,
+        );
+
+        assert!(transform.is_finite());
+        assert!(transform.rotation.abs_diff_eq(rotation, 1.0e-6));
+        assert!((transform.scale.x - MIN_ENDPOINT_LENGTH).abs() < 1.0e-6);
+        let fixed_now = end_in_parent(&transform, &endpoints, EndpointSign::Positive);
+        assert!(fixed_now.abs_diff_eq(fixed, 1.0e-5));
+    }
+}
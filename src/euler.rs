@@ -0,0 +1,201 @@
+//! Continuous Euler angles for properties panels.
+//!
+//! Decomposing a live rotation into Euler angles every frame flips signs and
+//! jumps near gimbal lock: past 90° of pitch, `Quat::to_euler` starts
+//! reporting the same rotation with the other two angles turned half a turn.
+//! [`GizmoEulerTracker`] instead keeps angles that follow on from the
+//! previous frame's while the gizmo drags, so a panel can show them as they
+//! change.
+
+use std::f32::consts::{PI, TAU};
+
+use bevy::prelude::*;
+
+use crate::types::TransformGizmoState;
+
+/// Largest sine of half the angle between two rotations taken as the same,
+/// about a hundredth of a degree.
+const MATCH_EPSILON: f32 = 1.0e-4;
+
+/// Tracks a target's local rotation as Euler angles that stay continuous
+/// during gizmo drags.
+///
+/// While any drag is active, each change to the rotation picks the Euler
+/// angles that reproduce it and lie closest to the previous ones, so they
+/// move smoothly through 90° of pitch and keep counting past a full turn.
+/// A rotation changed outside a drag is decomposed afresh, unless the
+/// tracked angles already reproduce it: a panel that writes `angles` and
+/// sets the rotation from [`rotation`](Self::rotation) keeps its values,
+/// such as a pitch of 100°.
+///
+/// # Example
+///
+/// ```ignore
+/// commands.spawn((
+///     Transform::default(),
+///     TransformGizmoTarget,
+///     GizmoEulerTracker::new(EulerRot::YXZ),
+/// ));
+///
+/// fn show_rotation(trackers: Query<&GizmoEulerTracker, Changed<GizmoEulerTracker>>) {
+///     for tracker in &trackers {
+///         let degrees = tracker.degrees();
+///     }
+/// }
+/// ```
+#[derive(Component, Reflect, Clone, Copy, Debug, PartialEq)]
+#[reflect(Component, Default)]
+pub struct GizmoEulerTracker {
+    /// Order the angles are applied in.
+    pub order: EulerRot,
+    /// Angles in radians around the first, second, and third axis of
+    /// `order`.
+    pub angles: Vec3,
+}
+
+impl Default for GizmoEulerTracker {
+    fn default() -> Self {
+        Self::new(EulerRot::XYZ)
+    }
+}
+
+impl GizmoEulerTracker {
+    /// Tracker using `order`. It picks up the target's rotation the first
+    /// time it runs.
+    pub fn new(order: EulerRot) -> Self {
+        Self {
+            order,
+            angles: Vec3::ZERO,
+        }
+    }
+
+    /// The tracked angles in degrees.
+    pub fn degrees(&self) -> Vec3 {
+        Vec3::new(
+            self.angles.x.to_degrees(),
+            self.angles.y.to_degrees(),
+            self.angles.z.to_degrees(),
+        )
+    }
+
+    /// The rotation the tracked angles describe.
+    pub fn rotation(&self) -> Quat {
+        Quat::from_euler(self.order, self.angles.x, self.angles.y, self.angles.z)
+    }
+
+    /// Whether the tracked angles describe `rotation`.
+    fn matches(&self, rotation: Quat) -> bool {
+        same_rotation(self.rotation(), rotation)
+    }
+
+    /// Angles describing `rotation` that lie closest to the tracked ones.
+    fn closest(&self, rotation: Quat) -> Vec3 {
+        let (a, b, c) = rotation.to_euler(self.order);
+        // The other triple giving the same rotation: `(a + π, π - b, c + π)`
+        // for three distinct axes, `(a + π, -b, c + π)` when the first and
+        // last repeat. Checking both covers every order.
+        [
+            Vec3::new(a, b, c),
+            Vec3::new(a + PI, PI - b, c + PI),
+            Vec3::new(a + PI, -b, c + PI),
+        ]
+        .into_iter()
+        .filter(|&candidate| {
+            same_rotation(
+                Quat::from_euler(self.order, candidate.x, candidate.y, candidate.z),
+                rotation,
+            )
+        })
+        .map(|candidate| unwrap_towards(candidate, self.angles))
+        .min_by(|p, q| {
+            let p = p.distance_squared(self.angles);
+            let q = q.distance_squared(self.angles);
+            p.total_cmp(&q)
+        })
+        .unwrap_or(Vec3::new(a, b, c))
+    }
+}
+
+/// Whether `p` and `q` are the same rotation, whichever sign they carry.
+fn same_rotation(p: Quat, q: Quat) -> bool {
+    (p * q.inverse()).xyz().length() < MATCH_EPSILON
+}
+
+/// `angles` moved by whole turns to lie within half a turn of `reference`.
+fn unwrap_towards(angles: Vec3, reference: Vec3) -> Vec3 {
+    let unwrap = |angle: f32, reference: f32| angle + ((reference - angle) / TAU).round() * TAU;
+    Vec3::new(
+        unwrap(angles.x, reference.x),
+        unwrap(angles.y, reference.y),
+        unwrap(angles.z, reference.z),
+    )
+}
+
+/// Update every [`GizmoEulerTracker`] whose target's rotation changed.
+///
+/// Runs right after the drag is applied and before it can end, so the last
+/// frame of a drag is still tracked continuously.
+pub fn track_euler_angles(
+    state: Res<TransformGizmoState>,
+    mut trackers: Query<(Ref<Transform>, &mut GizmoEulerTracker)>,
+) {
+    let dragging = state.drag.is_some();
+    for (transform, mut tracker) in trackers.iter_mut() {
+        let added = tracker.is_added();
+        if !added && !transform.is_changed() {
+            continue;
+        }
+        let rotation = transform.rotation;
+        if !added && tracker.matches(rotation) {
+            continue;
+        }
+        let angles = if dragging && !added {
+            tracker.closest(rotation)
+        } else {
+            let (a, b, c) = rotation.to_euler(tracker.order);
+            Vec3::new(a, b, c)
+        };
+        tracker.angles = angles;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pitching_past_a_right_angle_keeps_counting() {
+        let mut tracker = GizmoEulerTracker::new(EulerRot::YXZ);
+        for degrees in (0..=175).step_by(7) {
+            let rotation = Quat::from_rotation_x((degrees as f32).to_radians());
+            tracker.angles = tracker.closest(rotation);
+            let expected = Vec3::new(0.0, (degrees as f32).to_radians(), 0.0);
+            assert!(
+                tracker.angles.abs_diff_eq(expected, 1.0e-3),
+                "{degrees}: {}",
+                tracker.degrees()
+            );
+        }
+        // Decomposing afresh flips yaw and roll instead.
+        let (yaw, pitch, _) = Quat::from_rotation_x(170f32.to_radians()).to_euler(EulerRot::YXZ);
+        assert!(yaw.abs() > 3.0 && pitch.abs() < 1.0);
+    }
+
+    #[test]
+    fn whole_turns_are_counted() {
+        let mut tracker = GizmoEulerTracker::new(EulerRot::XYZ);
+        for degrees in (0..=400).step_by(10) {
+            let rotation = Quat::from_rotation_z((degrees as f32).to_radians());
+            tracker.angles = tracker.closest(rotation);
+        }
+        assert!((tracker.degrees().z - 400.0).abs() < 1.0e-2);
+    }
+
+    #[test]
+    fn angles_that_reproduce_the_rotation_are_kept() {
+        let mut tracker = GizmoEulerTracker::new(EulerRot::XYZ);
+        tracker.angles = Vec3::new(0.0, 100f32.to_radians(), 0.0);
+        assert!(tracker.matches(tracker.rotation()));
+        assert!(!tracker.matches(Quat::from_rotation_x(0.3)));
+    }
+}
@@ -0,0 +1,87 @@
+//! Dragging targets whose transform another system owns.
+//!
+//! Animation rigs and physics bodies may have no `Transform` of their own, or
+//! have it overwritten every frame, so the gizmo's usual write is lost or
+//! fought over. Marking such a target [`GizmoExternalDriver`] makes its drags
+//! edit a world-space copy of its `GlobalTransform` instead, and publish the
+//! result of every drag frame as a [`GizmoTransformRequest`] for the app to
+//! route on, for example to a kinematic body's target pose or an animation
+//! override.
+
+use bevy::prelude::*;
+
+/// Makes the gizmo publish [`GizmoTransformRequest`]s for this target instead
+/// of writing its `Transform`.
+///
+/// Drags start from the target's `GlobalTransform` and work in world space,
+/// whatever its parent, so the target needs no `Transform` at all. The gizmo
+/// keeps following the `GlobalTransform`, so it trails the drag until the app
+/// applies the requests. Undo and redo of these drags through
+/// [`GizmoHistory`](crate::GizmoHistory) are published the same way. Targets
+/// edited through a secondary channel are unaffected.
+///
+/// ```ignore
+/// commands.spawn((rig_root, TransformGizmoTarget, GizmoExternalDriver));
+///
+/// fn drive_rig(mut requests: MessageReader<GizmoTransformRequest>, mut rigs: Query<&mut RigPose>) {
+///     for request in requests.read() {
+///         if let Ok(mut pose) = rigs.get_mut(request.target) {
+///             pose.root = request.transform;
+///         }
+///     }
+/// }
+/// ```
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct GizmoExternalDriver;
+
+/// The world-space transform the gizmo wants a [`GizmoExternalDriver`]
+/// target to have.
+///
+/// Written by every drag frame that changes it, and by a cancelled drag, undo,
+/// or redo putting it back. The change since the previous frame is in the
+/// matching [`GizmoDragging`](crate::GizmoDragging) message.
+#[derive(Message, Clone, Copy, Debug, PartialEq)]
+pub struct GizmoTransformRequest {
+    /// The target to move.
+    pub target: Entity,
+    /// Its desired transform, in world space.
+    pub transform: Transform,
+}
+
+#[cfg(all(test, feature = "translate"))]
+mod tests {
+    use super::*;
+    use bevy::ecs::message::Messages;
+
+    use crate::test_utils::GizmoTestHarness;
+    use crate::types::{GizmoAxis, GizmoOperation};
+
+    #[test]
+    fn externally_driven_targets_publish_their_drags() {
+        let mut harness = GizmoTestHarness::new();
+        let target = harness.target();
+        harness
+            .app_mut()
+            .world_mut()
+            .entity_mut(target)
+            .remove::<Transform>()
+            .insert(GizmoExternalDriver);
+        harness.step();
+        let start = harness.grab(GizmoOperation::TranslateAxis, GizmoAxis::X);
+        harness.drag(start, start + harness.screen_axis(Vec3::X) * 60.0);
+
+        let world = harness.app_mut().world_mut();
+        let requests: Vec<_> = world
+            .resource_mut::<Messages<GizmoTransformRequest>>()
+            .drain()
+            .collect();
+        let last = requests.last().unwrap();
+        assert_eq!(last.target, target);
+        assert!(last.transform.translation.x > 0.1, "{last:?}");
+        // Nothing was written back; the app applies the requests.
+        assert_eq!(
+            world.get::<GlobalTransform>(target).unwrap().translation(),
+            Vec3::ZERO
+        );
+    }
+}
@@ -4,20 +4,36 @@
 //! (origin and axis directions) for a gizmo based on the target entity's
 //! transform and the selected coordinate space.
 
+use std::borrow::Cow;
+
+use bevy::camera::primitives::{Frustum, Sphere};
 use bevy::prelude::*;
 
-use crate::types::{GizmoAxis, TransformGizmoSpace};
+use crate::math::{toward_camera, world_units_per_pixel};
+use crate::types::{
+    EndpointSign, GizmoAxis, GizmoConstraints, GizmoEndpoints, GizmoOperation, GizmoScaleMode,
+    TransformGizmoSpace, TransformGizmoStyle,
+};
 
 /// Which flavor of axes to request from a gizmo frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum AxisKind {
+    /// Axes of the translation handles, in the gizmo's space.
     Translate,
+    /// Axes of the rotation rings, the same as the translation axes.
     Rotate,
+    /// Axes of the scale handles, always the target's local axes.
     Scale,
 }
 
-/// Precomputed basis vectors for a gizmo target, respecting world/local space.
-#[derive(Clone, Copy)]
+/// Precomputed basis vectors for a gizmo target, respecting its
+/// [`TransformGizmoSpace`].
+///
+/// Pass one to [`pick_gizmo_handle`](crate::pick_gizmo_handle) to hit test
+/// the handles of a gizmo drawn at the target.
+#[derive(Debug, Clone, Copy)]
 pub struct GizmoFrame {
+    /// World-space position the handles are drawn around.
     pub origin: Vec3,
     tx_x: Vec3,
     tx_y: Vec3,
@@ -25,24 +41,71 @@ pub struct GizmoFrame {
     sc_x: Vec3,
     sc_y: Vec3,
     sc_z: Vec3,
+    /// Long axis and world positions of the negative and positive ends of a
+    /// [`GizmoEndpoints`] target.
+    endpoints: Option<(GizmoAxis, [Vec3; 2])>,
+    /// Factor applied to the style's world-unit sizes for this gizmo.
+    pub size: f32,
+    /// Camera view direction, the axis of the screen-facing rotation ring.
+    pub view_dir: Vec3,
+    /// Unit direction from the origin toward the camera.
+    to_camera: Vec3,
+    /// Per-axis signs (`1.0` or `-1.0`) that turn the translate axes towards
+    /// the camera, so plane handles sit between the camera and the origin.
+    plane_signs: Vec3,
+    /// The target's [`GizmoConstraints`], which hide the handles they rule
+    /// out.
+    constraints: Option<GizmoConstraints>,
 }
 
 impl GizmoFrame {
+    /// Frame of a target with the given global transform, with its axes in
+    /// `space`, at size `1.0` and seen along `-Z`.
+    ///
+    /// Without the parent and camera this takes, [`TransformGizmoSpace::Parent`]
+    /// and [`TransformGizmoSpace::View`] fall back to world axes; see
+    /// [`from_context`](Self::from_context).
     pub fn new(transform: &GlobalTransform, space: TransformGizmoSpace) -> Self {
-        let origin = transform.translation();
-        let rotation = transform.rotation();
-        let local_x = rotation * Vec3::X;
-        let local_y = rotation * Vec3::Y;
-        let local_z = rotation * Vec3::Z;
-
-        // Translation / rotation may be world or local.
-        let (tx_x, tx_y, tx_z) = match space {
-            TransformGizmoSpace::World => (Vec3::X, Vec3::Y, Vec3::Z),
-            TransformGizmoSpace::Local => (local_x, local_y, local_z),
+        Self::from_context(transform, space, None, None)
+    }
+
+    /// Frame of a target with the given global transform, with its axes in
+    /// `space`, at size `1.0` and seen along `-Z`.
+    ///
+    /// `parent` is the global transform the target's `Transform` is
+    /// relative to, for [`TransformGizmoSpace::Parent`], and `camera` the
+    /// gizmo camera's, for [`TransformGizmoSpace::View`]. Either space
+    /// falls back to world axes without it.
+    pub fn from_context(
+        transform: &GlobalTransform,
+        space: TransformGizmoSpace,
+        parent: Option<&GlobalTransform>,
+        camera: Option<&GlobalTransform>,
+    ) -> Self {
+        let (_, rotation, origin) = transform.to_scale_rotation_translation();
+        let local = local_axes(transform, rotation);
+
+        // Translation and rotation follow the space, through the rotation
+        // of whatever it is aligned to.
+        let space_rotation = match space {
+            TransformGizmoSpace::World => Quat::IDENTITY,
+            TransformGizmoSpace::Local => rotation,
+            TransformGizmoSpace::Parent => {
+                parent.map_or(Quat::IDENTITY, |parent| parent.rotation())
+            }
+            TransformGizmoSpace::View => camera.map_or(Quat::IDENTITY, |camera| camera.rotation()),
+            TransformGizmoSpace::Custom(rotation) => rotation.normalize(),
+        };
+        // Normalized once here, so handles never have to.
+        let [tx_x, tx_y, tx_z] = match space {
+            TransformGizmoSpace::Local => local,
+            _ => {
+                [Vec3::X, Vec3::Y, Vec3::Z].map(|axis| (space_rotation * axis).normalize_or_zero())
+            }
         };
 
         // Scale is always local to avoid surprising behaviour.
-        let (sc_x, sc_y, sc_z) = (local_x, local_y, local_z);
+        let [sc_x, sc_y, sc_z] = local;
 
         Self {
             origin,
@@ -52,9 +115,149 @@ impl GizmoFrame {
             sc_x,
             sc_y,
             sc_z,
+            endpoints: None,
+            size: 1.0,
+            view_dir: Vec3::NEG_Z,
+            to_camera: Vec3::Z,
+            plane_signs: Vec3::ONE,
+            constraints: None,
+        }
+    }
+
+    /// Returns a copy of the frame with sizes scaled by `size`.
+    pub fn with_size(mut self, size: f32) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// Returns a copy of the frame seen along `view_dir`.
+    pub fn with_view_dir(mut self, view_dir: Vec3) -> Self {
+        self.view_dir = view_dir.normalize_or(Vec3::NEG_Z);
+        self.to_camera = -self.view_dir;
+        self
+    }
+
+    /// Returns a copy of the frame whose plane handles face a camera at
+    /// `camera_position`.
+    ///
+    /// Call this after the origin is final: the signs depend on which side
+    /// of the origin the camera is on.
+    pub fn with_camera_position(self, camera_position: Vec3) -> Self {
+        let to_camera = (camera_position - self.origin).normalize_or(-self.view_dir);
+        self.with_camera_direction(to_camera)
+    }
+
+    /// Returns a copy of the frame whose plane handles face a camera in the
+    /// `to_camera` direction from the origin.
+    ///
+    /// Use this for orthographic cameras, whose position says nothing about
+    /// the side the gizmo is seen from: pass the camera's back direction.
+    pub fn with_camera_direction(mut self, to_camera: Vec3) -> Self {
+        let to_camera = to_camera.normalize_or(-self.view_dir);
+        let sign = |dir: Vec3| if dir.dot(to_camera) < 0.0 { -1.0 } else { 1.0 };
+        self.plane_signs = Vec3::new(sign(self.tx_x), sign(self.tx_y), sign(self.tx_z));
+        self.to_camera = to_camera;
+        self
+    }
+
+    /// Returns a copy of the frame with fixed plane handle signs, such as
+    /// the ones a drag started with.
+    pub fn with_plane_signs(mut self, plane_signs: Vec3) -> Self {
+        self.plane_signs = plane_signs;
+        self
+    }
+
+    /// Unit direction from the origin toward the camera, as given to
+    /// [`with_camera_direction`](Self::with_camera_direction) or opposite
+    /// the view direction.
+    pub fn to_camera(&self) -> Vec3 {
+        self.to_camera
+    }
+
+    /// Per-axis signs that turn the plane handles towards the camera.
+    pub fn plane_signs(&self) -> Vec3 {
+        self.plane_signs
+    }
+
+    /// Unit translate axis, flipped towards the camera like the plane
+    /// handles that span it.
+    pub fn plane_dir(&self, axis: GizmoAxis) -> Vec3 {
+        self.axis_dir(axis, AxisKind::Translate) * self.plane_signs.dot(axis.to_vec3())
+    }
+
+    /// Unit normal of the translate plane spanned by the other two axes, on
+    /// the side of `axis`. This is `axis` itself unless the translate axes
+    /// follow a sheared target and are not perpendicular.
+    pub(crate) fn plane_normal(&self, axis: GizmoAxis) -> Vec3 {
+        let (a1, a2) = plane_axes(axis);
+        let axis_dir = self.axis_dir(axis, AxisKind::Translate);
+        let normal = self
+            .axis_dir(a1, AxisKind::Translate)
+            .cross(self.axis_dir(a2, AxisKind::Translate))
+            .normalize_or_zero();
+        if normal.dot(axis_dir) < 0.0 {
+            -normal
+        } else {
+            normal
         }
     }
 
+    /// Returns a copy of the frame that only offers the handles
+    /// `constraints` allow.
+    pub fn with_constraints(mut self, constraints: GizmoConstraints) -> Self {
+        self.constraints = Some(constraints);
+        self
+    }
+
+    /// Whether the frame's [`GizmoConstraints`], if it has any, allow the
+    /// `(op, axis)` handle.
+    pub fn allows(&self, op: GizmoOperation, axis: GizmoAxis) -> bool {
+        self.constraints
+            .is_none_or(|constraints| constraints.allows(op, axis))
+    }
+
+    /// `style` with its sizes scaled for this gizmo.
+    pub fn sized_style<'a>(&self, style: &'a TransformGizmoStyle) -> Cow<'a, TransformGizmoStyle> {
+        if self.size == 1.0 {
+            Cow::Borrowed(style)
+        } else {
+            Cow::Owned(style.scaled(self.size))
+        }
+    }
+
+    /// Returns a copy of the frame anchored at a different world-space origin.
+    pub fn with_origin(mut self, origin: Vec3) -> Self {
+        self.origin = origin;
+        self
+    }
+
+    /// Returns a copy of the frame with endpoint handles for a target with
+    /// the given global transform.
+    pub fn with_endpoints(
+        mut self,
+        transform: &GlobalTransform,
+        endpoints: &GizmoEndpoints,
+    ) -> Self {
+        let world = |end| transform.transform_point(endpoints.local_position(end));
+        self.endpoints = Some((
+            endpoints.local_axis,
+            [world(EndpointSign::Negative), world(EndpointSign::Positive)],
+        ));
+        self
+    }
+
+    /// Long axis and world position of one endpoint handle, if the frame has
+    /// them.
+    pub fn endpoint(&self, end: EndpointSign) -> Option<(GizmoAxis, Vec3)> {
+        self.endpoints
+            .map(|(axis, [negative, positive])| match end {
+                EndpointSign::Negative => (axis, negative),
+                EndpointSign::Positive => (axis, positive),
+            })
+    }
+
+    /// World-space unit direction of `axis` for the `kind` of handle, or
+    /// zero if the target has collapsed along it.
     pub fn axis_dir(&self, axis: GizmoAxis, kind: AxisKind) -> Vec3 {
         match kind {
             AxisKind::Translate | AxisKind::Rotate => match axis {
@@ -71,11 +274,177 @@ impl GizmoFrame {
     }
 }
 
-/// Axes that bound the plane whose normal is `normal_axis`.
-pub fn plane_axes(normal_axis: GizmoAxis) -> (GizmoAxis, GizmoAxis) {
-    match normal_axis {
-        GizmoAxis::X => (GizmoAxis::Y, GizmoAxis::Z),
-        GizmoAxis::Y => (GizmoAxis::X, GizmoAxis::Z),
-        GizmoAxis::Z => (GizmoAxis::X, GizmoAxis::Y),
+/// Largest cosine between two of a target's axes that still counts as
+/// perpendicular.
+const SHEAR_EPSILON: f32 = 1.0e-3;
+
+/// Unit directions of the local axes of `transform`, whose decomposed
+/// rotation is `rotation`.
+///
+/// These are the rotation's axes, unless a parent with non-uniform scale
+/// shears the target: then no rotation lines up with its edges, and the
+/// normalized columns of its affine are used instead, so handles follow the
+/// object as drawn. Each column keeps the sign of the rotation's axis, which
+/// differs for mirrored targets.
+fn local_axes(transform: &GlobalTransform, rotation: Quat) -> [Vec3; 3] {
+    let rotated = [Vec3::X, Vec3::Y, Vec3::Z].map(|axis| rotation * axis);
+    let linear = transform.affine().matrix3;
+    let columns =
+        [linear.x_axis, linear.y_axis, linear.z_axis].map(|c| Vec3::from(c).normalize_or_zero());
+    let [x, y, z] = columns;
+    let sheared = x.dot(y).abs().max(y.dot(z).abs()).max(z.dot(x).abs()) > SHEAR_EPSILON;
+    if !sheared || columns.contains(&Vec3::ZERO) {
+        return rotated;
+    }
+    [0, 1, 2].map(|i| columns[i] * columns[i].dot(rotated[i]).signum())
+}
+
+/// Camera projection captured once per pass to size gizmos per
+/// [`GizmoScaleMode`].
+pub struct GizmoSizing {
+    mode: GizmoScaleMode,
+    axis_length: f32,
+    clip_from_view: Mat4,
+    camera_transform: GlobalTransform,
+    viewport_size: Option<Vec2>,
+}
+
+impl GizmoSizing {
+    pub fn new(
+        style: &TransformGizmoStyle,
+        camera: &Camera,
+        camera_transform: &GlobalTransform,
+    ) -> Self {
+        Self::from_projection(
+            style,
+            camera.clip_from_view(),
+            camera_transform,
+            camera.logical_viewport_size(),
+        )
+    }
+
+    pub fn from_projection(
+        style: &TransformGizmoStyle,
+        clip_from_view: Mat4,
+        camera_transform: &GlobalTransform,
+        viewport_size: Option<Vec2>,
+    ) -> Self {
+        Self {
+            mode: style.scale_mode,
+            axis_length: style.axis_length,
+            clip_from_view,
+            camera_transform: *camera_transform,
+            viewport_size,
+        }
+    }
+
+    /// The camera's global transform.
+    pub fn camera_transform(&self) -> &GlobalTransform {
+        &self.camera_transform
+    }
+
+    /// Direction the camera looks in.
+    pub fn view_dir(&self) -> Vec3 {
+        *self.camera_transform.forward()
     }
+
+    /// Direction from `point` toward the camera, see
+    /// [`GizmoFrame::with_camera_direction`].
+    pub fn toward_camera(&self, point: Vec3) -> Vec3 {
+        toward_camera(self.clip_from_view, &self.camera_transform, point)
+    }
+
+    /// Logical pixels one world unit spans at `point`, or `None` where the
+    /// projection cannot resolve a pixel size, such as behind the camera.
+    pub fn pixels_per_unit(&self, point: Vec3) -> Option<f32> {
+        let size = self.viewport_size?;
+        let per_pixel =
+            world_units_per_pixel(self.clip_from_view, &self.camera_transform, size, point)?;
+        Some(1.0 / per_pixel.max_element().max(f32::EPSILON))
+    }
+
+    /// Size factor for a gizmo drawn at `origin`.
+    ///
+    /// Screen-space sizing falls back to `1.0` where the projection cannot
+    /// resolve a pixel size, such as behind the camera.
+    pub fn factor(&self, origin: Vec3) -> f32 {
+        match self.mode {
+            GizmoScaleMode::Fixed(factor) => factor,
+            GizmoScaleMode::ScreenSpace(pixels) => self
+                .viewport_size
+                .filter(|_| self.axis_length > 0.0)
+                .and_then(|size| {
+                    world_units_per_pixel(self.clip_from_view, &self.camera_transform, size, origin)
+                })
+                .map_or(1.0, |per_pixel| {
+                    pixels * (per_pixel.x + per_pixel.y) * 0.5 / self.axis_length
+                }),
+        }
+    }
+}
+
+/// How much of a gizmo is drawn and hit tested, from
+/// [`GizmoSizing::detail`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum GizmoDetail {
+    /// Every handle.
+    Full,
+    /// Only the origin dot, which is not hit tested, grown by `grow` to the
+    /// size it has on a gizmo at the minimum screen size.
+    OriginDot { grow: f32 },
+    /// Nothing.
+    Culled,
+}
+
+impl GizmoSizing {
+    /// How much of the gizmo in `frame`, laid out with the `sized` style,
+    /// is worth drawing and hit testing.
+    ///
+    /// With [`cull_offscreen`](TransformGizmoStyle::cull_offscreen) a gizmo
+    /// whose bounds (and endpoint handles) are outside `frustum` is culled.
+    /// Below [`min_screen_size`](TransformGizmoStyle::min_screen_size) it is
+    /// reduced to the origin dot, or culled without
+    /// [`distant_origin_dot`](TransformGizmoStyle::distant_origin_dot).
+    pub(crate) fn detail(
+        &self,
+        frame: &GizmoFrame,
+        sized: &TransformGizmoStyle,
+        frustum: Option<&Frustum>,
+    ) -> GizmoDetail {
+        if let Some(frustum) = frustum.filter(|_| sized.cull_offscreen) {
+            let radius = [EndpointSign::Negative, EndpointSign::Positive]
+                .into_iter()
+                .filter_map(|end| frame.endpoint(end))
+                .map(|(_, center)| center.distance(frame.origin) + sized.endpoint_hit_radius)
+                .fold(sized.effective_bounds_radius(), f32::max);
+            let bounds = Sphere {
+                center: frame.origin.into(),
+                radius,
+            };
+            if !frustum.intersects_sphere(&bounds, true) {
+                return GizmoDetail::Culled;
+            }
+        }
+        if sized.min_screen_size > 0.0 {
+            let pixels = self
+                .pixels_per_unit(frame.origin)
+                .map(|per_unit| sized.axis_length * per_unit);
+            if let Some(pixels) = pixels.filter(|pixels| *pixels < sized.min_screen_size) {
+                return if sized.distant_origin_dot {
+                    GizmoDetail::OriginDot {
+                        grow: sized.min_screen_size / pixels.max(f32::EPSILON),
+                    }
+                } else {
+                    GizmoDetail::Culled
+                };
+            }
+        }
+        GizmoDetail::Full
+    }
+}
+
+/// Axes that bound the plane whose normal is `normal_axis`, see
+/// [`GizmoAxis::others`].
+pub const fn plane_axes(normal_axis: GizmoAxis) -> (GizmoAxis, GizmoAxis) {
+    normal_axis.others()
 }
@@ -0,0 +1,520 @@
+//! Handle geometry shared by drawing and picking.
+//!
+//! Each function here lays out one handle of a gizmo from its frame and
+//! sized style: where it sits, the shape that is drawn, and the region the
+//! hover hit test accepts. The line drawing in `draw`, the mesh handles in
+//! `meshes`, and the hit test in `interaction` all read these, so a handle is
+//! always picked where it is drawn.
+
+use bevy::prelude::*;
+
+#[cfg(any(feature = "rotate", feature = "planes"))]
+use crate::gizmo_frame::plane_axes;
+use crate::gizmo_frame::{AxisKind, GizmoFrame};
+#[cfg(feature = "rotate")]
+use crate::math::axis_basis;
+#[cfg(feature = "planes")]
+use crate::math::plane_coordinates;
+#[cfg(feature = "scale")]
+use crate::math::ray_obb_intersection;
+#[cfg(feature = "planes")]
+use crate::math::ray_plane_intersection;
+#[cfg(feature = "translate")]
+use crate::math::{closest_points_ray_segment, ray_capsule_intersection, ray_cone_intersection};
+#[cfg(feature = "rotate")]
+use crate::math::{ray_arc_closest_approach, ray_sphere_intersection};
+use crate::types::{GizmoAxis, TransformGizmoStyle};
+
+/// Epsilon for zero-length vector checks.
+const EPSILON: f32 = 1e-6;
+
+/// An axis translation cone, pointing out from the end of its axis line.
+#[cfg(feature = "translate")]
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct ConeGeometry {
+    /// Unit direction the cone points in.
+    pub(crate) axis_dir: Vec3,
+    /// Center of the base disc, at the end of the axis line.
+    pub(crate) base: Vec3,
+    pub(crate) tip: Vec3,
+    pub(crate) length: f32,
+    pub(crate) radius: f32,
+    /// Radius of the capsule around the cone's axis that still picks it.
+    pub(crate) hit_radius: f32,
+    /// Start of the axis line leading up to the base, when
+    /// [`TransformGizmoStyle::pick_axis_lines`] lets it pick the cone too.
+    pub(crate) line_start: Option<Vec3>,
+}
+
+#[cfg(feature = "translate")]
+impl ConeGeometry {
+    /// Midway between the base and the tip.
+    pub(crate) fn center(&self) -> Vec3 {
+        (self.base + self.tip) * 0.5
+    }
+
+    /// Distance along `ray` to the cone, and whether the ray only passes
+    /// within [`hit_radius`](Self::hit_radius) of it, or of its pickable
+    /// axis line, rather than through it.
+    pub(crate) fn hit(&self, ray: &Ray3d) -> Option<(f32, bool)> {
+        ray_cone_intersection(ray, self.base, self.axis_dir, self.length, self.radius)
+            .map(|t| (t, false))
+            .or_else(|| {
+                ray_capsule_intersection(ray, self.base, self.tip, self.hit_radius)
+                    .map(|t| (t, true))
+            })
+            .or_else(|| {
+                let (t, _, distance) = closest_points_ray_segment(ray, self.line_start?, self.base);
+                (distance <= self.hit_radius).then_some((t, true))
+            })
+    }
+}
+
+/// The translation cone on `axis`, or `None` if the axis has collapsed.
+#[cfg(feature = "translate")]
+pub(crate) fn translate_cone(
+    frame: &GizmoFrame,
+    style: &TransformGizmoStyle,
+    axis: GizmoAxis,
+) -> Option<ConeGeometry> {
+    let axis_dir = frame.axis_dir(axis, AxisKind::Translate);
+    if axis_dir.length_squared() < EPSILON {
+        return None;
+    }
+    let base = frame.origin + axis_dir * style.axis_length;
+    Some(ConeGeometry {
+        axis_dir,
+        base,
+        tip: base + axis_dir * style.translate_cone_length,
+        length: style.translate_cone_length,
+        radius: style.translate_cone_radius,
+        hit_radius: style.translate_hit_radius,
+        line_start: (style.pick_axis_lines && style.show_axis_lines)
+            .then(|| frame.origin + axis_dir * style.axis_length * style.axis_line_start),
+    })
+}
+
+/// Edge directions of the scale cubes, which line up with the scale axes.
+#[cfg(feature = "scale")]
+pub(crate) fn scale_cube_axes(frame: &GizmoFrame) -> [Vec3; 3] {
+    GizmoAxis::ALL.map(|axis| frame.axis_dir(axis, AxisKind::Scale))
+}
+
+/// An axis scale cube.
+#[cfg(feature = "scale")]
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct CubeGeometry {
+    pub(crate) center: Vec3,
+    /// Edge directions, see [`scale_cube_axes`].
+    pub(crate) axes: [Vec3; 3],
+    pub(crate) half_size: f32,
+    /// Half the size of the box around the cube that still picks it.
+    pub(crate) hit_half_size: f32,
+}
+
+#[cfg(feature = "scale")]
+impl CubeGeometry {
+    /// The eight corners, indexed by bits: 1 for +X, 2 for +Y, 4 for +Z.
+    pub(crate) fn corners(&self) -> [Vec3; 8] {
+        let [ax, ay, az] = self.axes;
+        std::array::from_fn(|i| {
+            let side = |bit: usize| if i & bit == 0 { -1.0 } else { 1.0 };
+            self.center + (ax * side(1) + ay * side(2) + az * side(4)) * self.half_size
+        })
+    }
+
+    /// The cube with its distance from `origin` multiplied by `factor`.
+    pub(crate) fn displaced(self, origin: Vec3, factor: f32) -> Self {
+        Self {
+            center: origin + (self.center - origin) * factor,
+            ..self
+        }
+    }
+
+    /// Distance along `ray` to the cube, and whether the ray only passes
+    /// through the padding around it rather than the cube itself.
+    pub(crate) fn hit(&self, ray: &Ray3d) -> Option<(f32, bool)> {
+        ray_obb_intersection(ray, self.center, self.axes, Vec3::splat(self.half_size))
+            .map(|t| (t, false))
+            .or_else(|| {
+                let padded = Vec3::splat(self.hit_half_size);
+                ray_obb_intersection(ray, self.center, self.axes, padded).map(|t| (t, true))
+            })
+    }
+}
+
+/// The scale cube on `axis`, or `None` if the axis has collapsed.
+#[cfg(feature = "scale")]
+pub(crate) fn scale_cube(
+    frame: &GizmoFrame,
+    style: &TransformGizmoStyle,
+    axis: GizmoAxis,
+) -> Option<CubeGeometry> {
+    let axis_dir = frame.axis_dir(axis, AxisKind::Scale);
+    if axis_dir.length_squared() < EPSILON {
+        return None;
+    }
+    let half_size = style.scale_cube_size * 0.5;
+    Some(CubeGeometry {
+        center: frame.origin + axis_dir * (style.axis_length * style.scale_cube_offset),
+        axes: scale_cube_axes(frame),
+        half_size,
+        hit_half_size: style.scale_hit_radius.max(half_size),
+    })
+}
+
+/// Normal and the two in-plane directions of a planar translation handle.
+/// The in-plane directions are flipped towards the camera, see
+/// [`GizmoFrame::plane_dir`].
+#[cfg(feature = "planes")]
+pub(crate) fn translate_plane_basis(
+    frame: &GizmoFrame,
+    axis: GizmoAxis,
+) -> Option<(Vec3, Vec3, Vec3)> {
+    let (d1_axis, d2_axis) = plane_axes(axis);
+    let normal = frame.plane_normal(axis);
+    let dir1 = frame.plane_dir(d1_axis);
+    let dir2 = frame.plane_dir(d2_axis);
+    if normal.length_squared() < EPSILON
+        || dir1.length_squared() < EPSILON
+        || dir2.length_squared() < EPSILON
+    {
+        return None;
+    }
+    Some((normal, dir1, dir2))
+}
+
+/// A planar translation square, spanning `near..far` along both in-plane
+/// directions from the origin.
+#[cfg(feature = "planes")]
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct PlaneGeometry {
+    pub(crate) origin: Vec3,
+    pub(crate) normal: Vec3,
+    pub(crate) dir1: Vec3,
+    pub(crate) dir2: Vec3,
+    pub(crate) near: f32,
+    pub(crate) far: f32,
+    /// How far past each edge the square still picks.
+    pub(crate) hit_padding: f32,
+}
+
+#[cfg(feature = "planes")]
+impl PlaneGeometry {
+    /// The point `u` along `dir1` and `v` along `dir2` from the origin.
+    pub(crate) fn point(&self, u: f32, v: f32) -> Vec3 {
+        self.origin + self.dir1 * u + self.dir2 * v
+    }
+
+    /// The corners, in order around the square.
+    pub(crate) fn corners(&self) -> [Vec3; 4] {
+        let (near, far) = (self.near, self.far);
+        [
+            self.point(near, near),
+            self.point(far, near),
+            self.point(far, far),
+            self.point(near, far),
+        ]
+    }
+
+    pub(crate) fn center(&self) -> Vec3 {
+        let middle = (self.near + self.far) * 0.5;
+        self.point(middle, middle)
+    }
+
+    /// Distance along `ray` to where it crosses the padded square.
+    pub(crate) fn hit(&self, ray: &Ray3d) -> Option<f32> {
+        let hit_point = ray_plane_intersection(ray, self.origin, self.normal)?;
+        let local = hit_point - self.origin;
+        let (near, far) = (self.near - self.hit_padding, self.far + self.hit_padding);
+        let inside = |d: f32| (near..=far).contains(&d);
+        let coordinates = plane_coordinates(local, self.dir1, self.dir2);
+        if !inside(coordinates.x) || !inside(coordinates.y) {
+            return None;
+        }
+        let t = (hit_point - ray.origin).dot(*ray.direction);
+        (t >= 0.0).then_some(t)
+    }
+}
+
+/// The planar translation square normal to `axis`, or `None` if the plane
+/// has collapsed.
+#[cfg(feature = "planes")]
+pub(crate) fn plane_rect(
+    frame: &GizmoFrame,
+    style: &TransformGizmoStyle,
+    axis: GizmoAxis,
+) -> Option<PlaneGeometry> {
+    let (normal, dir1, dir2) = translate_plane_basis(frame, axis)?;
+    let (near, far) = style.translate_plane_extent();
+    Some(PlaneGeometry {
+        origin: frame.origin,
+        normal,
+        dir1,
+        dir2,
+        near,
+        far,
+        hit_padding: style.translate_plane_hit_thickness,
+    })
+}
+
+/// An axis rotation ring, or the arc of it that is drawn, centered between
+/// the two other axes.
+#[cfg(feature = "rotate")]
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct RingGeometry {
+    pub(crate) origin: Vec3,
+    /// Orthonormal basis of the ring's plane that angles are measured in.
+    pub(crate) basis: (Vec3, Vec3),
+    /// Angle of the middle of the arc.
+    pub(crate) center_angle: f32,
+    /// Angular extent of the arc, a full turn for a full circle.
+    pub(crate) sweep: f32,
+    pub(crate) radius: f32,
+    /// How far from the ring a hit may land.
+    pub(crate) hit_thickness: f32,
+    /// See [`TransformGizmoStyle::rotation_backface_cutoff`].
+    pub(crate) backface_cutoff: Option<f32>,
+}
+
+#[cfg(feature = "rotate")]
+impl RingGeometry {
+    /// Angle the arc starts at, half its sweep before the middle.
+    pub(crate) fn start_angle(&self) -> f32 {
+        self.center_angle - self.sweep * 0.5
+    }
+
+    /// The point on the ring at `angle`.
+    pub(crate) fn point(&self, angle: f32) -> Vec3 {
+        let (t1, t2) = self.basis;
+        self.origin + (t1 * angle.cos() + t2 * angle.sin()) * self.radius
+    }
+
+    /// The middle of the arc.
+    pub(crate) fn middle(&self) -> Vec3 {
+        self.point(self.center_angle)
+    }
+
+    /// Distance along `ray` to the ring, where it passes within
+    /// `hit_thickness` of the arc. Measured from the ray's closest approach
+    /// rather than where it crosses the ring's plane, so a ring seen edge-on
+    /// is still picked along the line it is drawn as.
+    pub(crate) fn hit(&self, ray: &Ray3d) -> Option<f32> {
+        let (_, distance, angle) = ray_arc_closest_approach(
+            ray,
+            self.origin,
+            self.basis,
+            self.radius,
+            self.start_angle(),
+            self.sweep,
+        );
+        if distance > self.hit_thickness || self.radius < 1e-4 {
+            return None;
+        }
+        let point = self.point(angle);
+
+        // Skip the part of the ring on the far side of the gizmo.
+        if let Some(cutoff) = self.backface_cutoff {
+            let outward = (point - self.origin) / self.radius;
+            if outward.dot(-*ray.direction) < -cutoff {
+                return None;
+            }
+        }
+
+        ray_sphere_intersection(ray, point, self.hit_thickness)
+    }
+}
+
+/// The rotation ring around `axis`, or `None` if the axis has collapsed.
+#[cfg(feature = "rotate")]
+pub(crate) fn rotation_ring(
+    frame: &GizmoFrame,
+    style: &TransformGizmoStyle,
+    axis: GizmoAxis,
+) -> Option<RingGeometry> {
+    let axis_dir = frame.axis_dir(axis, AxisKind::Rotate);
+    if axis_dir.length_squared() < EPSILON {
+        return None;
+    }
+    // The middle of the arc lies between the two other axes.
+    let (a1, a2) = plane_axes(axis);
+    let mid = frame.axis_dir(a1, AxisKind::Rotate) + frame.axis_dir(a2, AxisKind::Rotate);
+    let mid = mid.normalize_or_zero();
+    let mid = (mid - axis_dir * axis_dir.dot(mid)).normalize_or_zero();
+    let (t1, t2) = axis_basis(axis_dir);
+    Some(RingGeometry {
+        origin: frame.origin,
+        basis: (t1, t2),
+        center_angle: mid.dot(t2).atan2(mid.dot(t1)),
+        sweep: style.rotation_ring_style.extent_radians(),
+        radius: style.axis_length,
+        hit_thickness: style.rotation_hit_thickness,
+        backface_cutoff: style.rotation_backface_cutoff,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::TransformGizmoSpace;
+
+    const CAMERA: Vec3 = Vec3::new(3.0, 4.0, 5.0);
+
+    /// A rotated target seen from [`CAMERA`], so no handle is edge-on.
+    fn frame() -> GizmoFrame {
+        let transform = Transform::from_rotation(Quat::from_euler(EulerRot::XYZ, 0.3, 0.7, -0.2));
+        GizmoFrame::new(
+            &GlobalTransform::from(transform),
+            TransformGizmoSpace::Local,
+        )
+        .with_view_dir(-CAMERA)
+        .with_camera_position(CAMERA)
+    }
+
+    fn styles() -> Vec<TransformGizmoStyle> {
+        vec![
+            TransformGizmoStyle::default(),
+            TransformGizmoStyle {
+                axis_length: 2.5,
+                translate_cone_length: 0.6,
+                translate_cone_radius: 0.02,
+                translate_hit_radius: 0.01,
+                scale_cube_size: 0.5,
+                scale_hit_radius: 0.1,
+                scale_cube_offset: 0.6,
+                translate_plane_offset: 0.1,
+                translate_plane_size: 0.6,
+                translate_plane_hit_thickness: 0.0,
+                rotation_hit_thickness: 0.02,
+                ..default()
+            },
+            TransformGizmoStyle {
+                translate_cone_radius: 0.4,
+                rotation_ring_style: crate::types::RotationRingStyle::FullCircle,
+                planar_2d: true,
+                ..default()
+            },
+        ]
+    }
+
+    /// Whether a ray from the camera through `point`, moved a hair toward
+    /// `inside`, lands on the handle.
+    #[cfg(any(feature = "translate", feature = "scale", feature = "planes"))]
+    fn picks(point: Vec3, inside: Vec3, hit: impl Fn(&Ray3d) -> bool) -> bool {
+        let target = point.lerp(inside, 0.01);
+        hit(&Ray3d::new(CAMERA, Dir3::new(target - CAMERA).unwrap()))
+    }
+
+    #[cfg(feature = "translate")]
+    #[test]
+    fn cones_are_picked_wherever_drawn() {
+        let frame = frame();
+        for style in styles() {
+            for axis in GizmoAxis::ALL {
+                let cone = translate_cone(&frame, &style, axis).unwrap();
+                let (t1, t2) = crate::math::axis_basis(cone.axis_dir);
+                let rim = (0..16).map(|i| {
+                    let (sin, cos) = (i as f32 / 16.0 * std::f32::consts::TAU).sin_cos();
+                    cone.base + (t1 * cos + t2 * sin) * cone.radius
+                });
+                for point in rim.chain([cone.tip]) {
+                    assert!(
+                        picks(point, cone.center(), |ray| cone.hit(ray).is_some()),
+                        "{axis:?} {point}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[cfg(feature = "translate")]
+    #[test]
+    fn axis_lines_pick_their_cone_when_enabled() {
+        let frame = frame();
+        let mut style = TransformGizmoStyle::default();
+        let cone = translate_cone(&frame, &style, GizmoAxis::X).unwrap();
+        let middle = frame.origin + cone.axis_dir * style.axis_length * 0.5;
+        let ray = Ray3d::new(CAMERA, Dir3::new(middle - CAMERA).unwrap());
+        assert!(cone.hit(&ray).is_none());
+
+        style.pick_axis_lines = true;
+        let cone = translate_cone(&frame, &style, GizmoAxis::X).unwrap();
+        // A near miss, behind handles the cursor is directly over.
+        assert_eq!(cone.hit(&ray).map(|(_, missed)| missed), Some(true));
+    }
+
+    #[cfg(feature = "scale")]
+    #[test]
+    fn cubes_are_picked_wherever_drawn() {
+        let frame = frame();
+        for style in styles() {
+            for axis in GizmoAxis::ALL {
+                let cube = scale_cube(&frame, &style, axis).unwrap();
+                for corner in cube.corners() {
+                    assert!(
+                        picks(corner, cube.center, |ray| cube.hit(ray).is_some()),
+                        "{axis:?} {corner}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[cfg(feature = "planes")]
+    #[test]
+    fn planes_are_picked_wherever_drawn() {
+        let frame = frame();
+        for style in styles() {
+            for axis in GizmoAxis::ALL {
+                let plane = plane_rect(&frame, &style, axis).unwrap();
+                for corner in plane.corners() {
+                    assert!(
+                        picks(corner, plane.center(), |ray| plane.hit(ray).is_some()),
+                        "{axis:?} {corner}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[cfg(feature = "rotate")]
+    #[test]
+    fn rings_are_picked_wherever_drawn() {
+        let frame = frame();
+        for style in styles() {
+            for axis in GizmoAxis::ALL {
+                let ring = rotation_ring(&frame, &style, axis).unwrap();
+                for i in 0..=16 {
+                    // Stay a hair inside the ends of the arc.
+                    let along = (i as f32 / 16.0).clamp(0.001, 0.999);
+                    let point = ring.point(ring.start_angle() + ring.sweep * along);
+                    let ray = Ray3d::new(CAMERA, Dir3::new(point - CAMERA).unwrap());
+                    assert!(ring.hit(&ray).is_some(), "{axis:?} {point}");
+                }
+                // The arc is centered between the two other axes.
+                let (a1, a2) = plane_axes(axis);
+                let middle = ring.middle() - ring.origin;
+                let d1 = frame.axis_dir(a1, AxisKind::Rotate).normalize();
+                let d2 = frame.axis_dir(a2, AxisKind::Rotate).normalize();
+                assert!((middle.dot(d1) - middle.dot(d2)).abs() < 1.0e-4);
+            }
+        }
+    }
+
+    #[cfg(feature = "rotate")]
+    #[test]
+    fn edge_on_rings_are_picked_along_their_line() {
+        let frame = GizmoFrame::new(&GlobalTransform::IDENTITY, TransformGizmoSpace::Local);
+        let style = TransformGizmoStyle {
+            rotation_ring_style: crate::types::RotationRingStyle::FullCircle,
+            ..default()
+        };
+        let ring = rotation_ring(&frame, &style, GizmoAxis::Y).unwrap();
+        // In the ring's plane, which the ray never crosses.
+        let x = style.axis_length * 0.5;
+        let edge_on = Ray3d::new(Vec3::new(x, 0.0, 10.0), Dir3::NEG_Z);
+        assert!(ring.hit(&edge_on).is_some());
+        let above = Vec3::new(x, style.rotation_hit_thickness * 2.0, 10.0);
+        assert!(ring.hit(&Ray3d::new(above, Dir3::NEG_Z)).is_none());
+    }
+}
@@ -0,0 +1,843 @@
+//! Handle identification and screen-space queries.
+//!
+//! Tutorial and onboarding tools need to find handles on screen ("now drag
+//! the red arrow") and check which one the user grabbed. Both queries here
+//! use the handle layout and hit test that hover uses, so they always agree
+//! with what is clickable.
+
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+
+use crate::gizmo_frame::{GizmoFrame, GizmoSizing};
+use crate::interaction::{
+    handle_anchors, handle_outlines, pick_handle, target_frame, GizmoTargetQuery, HandleAnchor,
+    HandleOutline, HandleShape, HoverBias, RankedHit, RankedHits,
+};
+#[cfg(feature = "rotate")]
+use crate::math::toward_camera;
+use crate::math::{viewport_position, world_units_per_pixel};
+use crate::types::{
+    GizmoCameraSelection, GizmoChannelParts, GizmoHandleId, GizmoHandleScreen,
+    GizmoHandleScreenInfo, GizmoOperation, GizmoScreenInfo, HitTestMode, TransformGizmoCamera,
+    TransformGizmoConfig, TransformGizmoState, TransformGizmoStyle, TransformGizmoTarget,
+};
+use crate::visual_pivot::anchor_pivot;
+
+/// Fill [`GizmoHandleScreenInfo`] when enabled in the config.
+///
+/// Lays handles out through [`GizmoHandleLookup`], so the published positions
+/// match what it hit tests.
+pub fn update_handle_screen_info(
+    config: Res<TransformGizmoConfig>,
+    lookup: GizmoHandleLookup,
+    mut info: ResMut<GizmoHandleScreenInfo>,
+    mut anchors: Local<Vec<HandleAnchor>>,
+) {
+    if !config.handle_screen_info {
+        if info.camera.is_some() || !info.handles.is_empty() {
+            *info = GizmoHandleScreenInfo::default();
+        }
+        return;
+    }
+
+    let GizmoHandleLookup {
+        state,
+        style,
+        camera_selection,
+        cameras,
+        targets,
+        global_transforms,
+    } = &lookup;
+    let info = info.into_inner();
+    info.handles.clear();
+    info.camera = camera_selection.camera();
+    let Some((camera, camera_transform)) = info.camera.and_then(|camera| cameras.get(camera).ok())
+    else {
+        return;
+    };
+    let Some(viewport) = camera.logical_viewport_rect() else {
+        return;
+    };
+    let clip_from_view = camera.clip_from_view();
+    let sizing = GizmoSizing::new(style, camera, camera_transform);
+
+    for (entity, transform, anchor, channel, channel_value, endpoints, parent, constraints) in
+        targets
+    {
+        if !style.shows_target(state, entity) {
+            continue;
+        }
+        let (frame, parts) = target_frame(
+            transform,
+            parent.and_then(|parent| global_transforms.get(parent.parent()).ok()),
+            anchor_pivot(anchor, style).as_ref(),
+            channel,
+            channel_value,
+            endpoints,
+            constraints,
+            state.frame_space(entity),
+            &sizing,
+        );
+        anchors.clear();
+        handle_anchors(&frame, &frame.sized_style(style), parts, &mut anchors);
+        for anchor in anchors.iter() {
+            if let Some(screen) = project_handle(anchor, clip_from_view, camera_transform, viewport)
+            {
+                info.handles
+                    .insert(GizmoHandleId::new(entity, anchor.op, anchor.axis), screen);
+            }
+        }
+    }
+}
+
+/// Fill [`GizmoScreenInfo`] from the active target's gizmo and the hovered
+/// handle.
+#[allow(clippy::too_many_arguments)]
+pub fn update_gizmo_screen_info(
+    state: Res<TransformGizmoState>,
+    style: Res<TransformGizmoStyle>,
+    camera_selection: Res<GizmoCameraSelection>,
+    cameras: Query<(&Camera, &GlobalTransform), With<TransformGizmoCamera>>,
+    targets: Query<GizmoTargetQuery, With<TransformGizmoTarget>>,
+    global_transforms: Query<&GlobalTransform>,
+    mut info: ResMut<GizmoScreenInfo>,
+    mut anchors: Local<Vec<HandleAnchor>>,
+) {
+    let mut next = GizmoScreenInfo {
+        camera: camera_selection.camera().filter(|_| state.enabled),
+        ..default()
+    };
+    let Some((camera, camera_transform)) = next.camera.and_then(|camera| cameras.get(camera).ok())
+    else {
+        info.set_if_neq(next);
+        return;
+    };
+    let Some(viewport) = camera.logical_viewport_rect() else {
+        info.set_if_neq(next);
+        return;
+    };
+    let clip_from_view = camera.clip_from_view();
+    let sizing = GizmoSizing::new(&style, camera, camera_transform);
+    let frame_of = |entity: Entity| {
+        let (_, transform, anchor, channel, channel_value, endpoints, parent, constraints) =
+            targets.get(entity).ok()?;
+        Some(target_frame(
+            transform,
+            parent.and_then(|parent| global_transforms.get(parent.parent()).ok()),
+            anchor_pivot(anchor, &style).as_ref(),
+            channel,
+            channel_value,
+            endpoints,
+            constraints,
+            state.frame_space(entity),
+            &sizing,
+        ))
+    };
+
+    let active = state
+        .drag
+        .as_ref()
+        .map_or(state.active_target, |drag| Some(drag.target));
+    next.origin = active.and_then(frame_of).and_then(|(frame, _)| {
+        viewport_position(
+            clip_from_view,
+            camera_transform,
+            viewport.size(),
+            frame.origin,
+        )
+        .map(|(position, _)| position + viewport.min)
+    });
+
+    // Hover is held on the grabbed handle for the whole drag.
+    let handle = match (
+        state.hovered_target.or(active),
+        state.hovered_op,
+        state.hovered_axis,
+    ) {
+        (Some(target), Some(op), Some(axis)) => Some(GizmoHandleId::new(target, op, axis)),
+        _ => None,
+    };
+    next.hovered = handle.and_then(|handle| {
+        let (frame, parts) = frame_of(handle.target)?;
+        anchors.clear();
+        handle_anchors(&frame, &frame.sized_style(&style), parts, &mut anchors);
+        let anchor = anchors
+            .iter()
+            .find(|anchor| anchor.op == handle.op && anchor.axis == handle.axis)?;
+        let screen = project_handle(anchor, clip_from_view, camera_transform, viewport)?;
+        Some((handle, screen))
+    });
+    info.set_if_neq(next);
+}
+
+/// Screen placement of one handle through a camera with the given
+/// projection and logical viewport.
+fn project_handle(
+    anchor: &HandleAnchor,
+    clip_from_view: Mat4,
+    camera_transform: &GlobalTransform,
+    viewport: Rect,
+) -> Option<GizmoHandleScreen> {
+    let size = viewport.size();
+    let (position, depth) =
+        viewport_position(clip_from_view, camera_transform, size, anchor.center)?;
+    let radius = world_units_per_pixel(clip_from_view, camera_transform, size, anchor.center)
+        .map_or(0.0, |factors| anchor.radius * 2.0 / (factors.x + factors.y));
+    let inside = position.cmpge(Vec2::ZERO).all() && position.cmple(size).all();
+    Some(GizmoHandleScreen {
+        position: position + viewport.min,
+        radius,
+        visible: inside && (0.0..=1.0).contains(&depth),
+    })
+}
+
+/// A camera's projection onto its logical viewport, for measuring handles in
+/// pixels.
+#[derive(Clone, Copy)]
+pub(crate) struct ScreenProjection<'a> {
+    pub(crate) clip_from_view: Mat4,
+    pub(crate) camera_transform: &'a GlobalTransform,
+    pub(crate) viewport_size: Vec2,
+}
+
+impl ScreenProjection<'_> {
+    /// Viewport position of `point`, if it is in front of the camera.
+    fn project(&self, point: Vec3) -> Option<Vec2> {
+        viewport_position(
+            self.clip_from_view,
+            self.camera_transform,
+            self.viewport_size,
+            point,
+        )
+        .map(|(position, _)| position)
+    }
+
+    /// On-screen radius in pixels of a world-space radius at `point`.
+    fn pixel_radius(&self, point: Vec3, radius: f32) -> f32 {
+        world_units_per_pixel(
+            self.clip_from_view,
+            self.camera_transform,
+            self.viewport_size,
+            point,
+        )
+        .map_or(0.0, |factors| radius * 2.0 / (factors.x + factors.y))
+    }
+
+    /// Direction from `point` toward the camera's eye.
+    #[cfg(feature = "rotate")]
+    fn toward_camera(&self, point: Vec3) -> Vec3 {
+        toward_camera(self.clip_from_view, self.camera_transform, point)
+    }
+
+    /// Distance of `point` in front of the camera, along its view direction.
+    fn depth(&self, point: Vec3) -> f32 {
+        (point - self.camera_transform.translation()).dot(*self.camera_transform.forward())
+    }
+}
+
+/// The handle [`HitTestMode::Screen`] picks under `cursor`, in logical pixels
+/// from the viewport's top-left corner.
+///
+/// Every handle whose projected outline passes within
+/// [`TransformGizmoStyle::hover_pixel_radius`] of the cursor is a candidate.
+/// The one whose nearest point is closest to the camera wins, unless one
+/// ranked higher in [`TransformGizmoStyle::pick_priority`] is within
+/// [`TransformGizmoStyle::pick_priority_tolerance`] of it; equally close
+/// handles of one rank go to the first in [`handle_outlines`] order.
+/// `hover` keeps the held handle within its grown pixel radius and favours
+/// the active target, see [`HoverBias`].
+///
+/// [`HitTestMode::Screen`]: crate::HitTestMode::Screen
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn pick_handle_on_screen(
+    cursor: Vec2,
+    projection: &ScreenProjection,
+    gizmos: impl IntoIterator<Item = (Entity, GizmoFrame, GizmoChannelParts)>,
+    style: &TransformGizmoStyle,
+    hover: HoverBias,
+    outlines: &mut Vec<HandleOutline>,
+    targets_tested: &mut u32,
+    exact_intersections: &mut u32,
+) -> Option<GizmoHandleId> {
+    // The trackball disc covers the inside of the rings, so it ranks behind
+    // every other handle and only takes the cursor when nothing else does.
+    // The origin dot goes between them, so it never blocks the center
+    // handles drawn over it.
+    let fallback = |op| match op {
+        GizmoOperation::OriginDot => 1,
+        GizmoOperation::RotateTrackball => 2,
+        _ => 0,
+    };
+    let mut best = None;
+    let mut held_rank = None;
+
+    for (entity, frame, parts) in gizmos {
+        let style = frame.sized_style(style);
+        *targets_tested += 1;
+        outlines.clear();
+        handle_outlines(&frame, &style, parts, outlines);
+        let held = hover.held.filter(|held| held.target == entity);
+        let held_radius = style.hover_pixel_radius * (1.0 + hover.margin);
+        let mut hits = RankedHits::default();
+        let mut origin_dot: Option<RankedHit> = None;
+        #[cfg(feature = "rotate")]
+        let mut trackball: Option<RankedHit> = None;
+        for outline in outlines.iter() {
+            *exact_intersections += 1;
+            let Some((distance, point)) = outline_distance(
+                &outline.shape,
+                cursor,
+                projection,
+                #[cfg(feature = "rotate")]
+                &style,
+            ) else {
+                continue;
+            };
+            let is_held =
+                held.is_some_and(|held| (held.op, held.axis) == (outline.op, outline.axis));
+            if is_held && distance <= held_radius {
+                held_rank = Some((fallback(outline.op), projection.depth(point)));
+            }
+            if distance > style.hover_pixel_radius {
+                continue;
+            }
+            let hit = RankedHit::exact(outline.op, outline.axis, projection.depth(point));
+            if outline.op == GizmoOperation::OriginDot {
+                origin_dot = origin_dot
+                    .filter(|best| best.score <= hit.score)
+                    .or(Some(hit));
+                continue;
+            }
+            #[cfg(feature = "rotate")]
+            if outline.op == GizmoOperation::RotateTrackball {
+                trackball = trackball
+                    .filter(|best| best.score <= hit.score)
+                    .or(Some(hit));
+                continue;
+            }
+            hits.offer(&style, hit);
+        }
+        let hit = hits.resolve(style.pick_priority_tolerance).or(origin_dot);
+        #[cfg(feature = "rotate")]
+        let hit = hit.or(trackball);
+        if let Some(hit) = hit {
+            let rank = (fallback(hit.op), hit.score);
+            if hover.beats(entity, rank, best) {
+                best = Some((GizmoHandleId::new(entity, hit.op, hit.axis), rank));
+            }
+        }
+    }
+
+    hover.settle(best, held_rank)
+}
+
+/// Pixel distance from `cursor` to the projected `shape`, and the world
+/// point of the shape nearest to it on screen.
+fn outline_distance(
+    shape: &HandleShape,
+    cursor: Vec2,
+    projection: &ScreenProjection,
+    #[cfg(feature = "rotate")] style: &TransformGizmoStyle,
+) -> Option<(f32, Vec3)> {
+    // Nearest of several projected world-space segments.
+    #[cfg(any(feature = "translate", feature = "rotate"))]
+    let nearest_edge = |edges: &mut dyn Iterator<Item = (Vec3, Vec3)>| {
+        edges
+            .filter_map(|(a, b)| {
+                let (distance, s) =
+                    segment_distance(cursor, projection.project(a)?, projection.project(b)?);
+                Some((distance, a.lerp(b, s)))
+            })
+            .min_by(|x, y| x.0.total_cmp(&y.0))
+    };
+
+    match *shape {
+        #[cfg(feature = "translate")]
+        HandleShape::Segment(a, b) => nearest_edge(&mut std::iter::once((a, b))),
+        HandleShape::Disc { center, radius } => {
+            let position = projection.project(center)?;
+            let radius = projection.pixel_radius(center, radius);
+            Some(((position.distance(cursor) - radius).max(0.0), center))
+        }
+        #[cfg(feature = "rotate")]
+        HandleShape::Arc {
+            center,
+            basis: (t1, t2),
+            radius,
+            start,
+            sweep,
+            cull_back,
+        } => {
+            let segments = style.rotation_arc_segments.max(1);
+            let cutoff = style.rotation_backface_cutoff.filter(|_| cull_back);
+            let samples = (0..=segments).map(|i| {
+                let angle = start + sweep * i as f32 / segments as f32;
+                let dir = t1 * angle.cos() + t2 * angle.sin();
+                let point = center + dir * radius;
+                // Skip the part of the ring on the far side of the gizmo.
+                let hidden =
+                    cutoff.is_some_and(|cutoff| dir.dot(projection.toward_camera(point)) < -cutoff);
+                (!hidden).then_some(point)
+            });
+            let samples: Vec<_> = samples.collect();
+            nearest_edge(
+                &mut samples
+                    .windows(2)
+                    .filter_map(|pair| Some((pair[0]?, pair[1]?))),
+            )
+        }
+        #[cfg(feature = "planes")]
+        HandleShape::Quad(corners) => {
+            let projected = corners.map(|corner| projection.project(corner));
+            let projected = [projected[0]?, projected[1]?, projected[2]?, projected[3]?];
+            let crosses = [0, 1, 2, 3].map(|i| {
+                let (a, b) = (projected[i], projected[(i + 1) % 4]);
+                (b - a).perp_dot(cursor - a)
+            });
+            if crosses.iter().all(|c| *c >= 0.0) || crosses.iter().all(|c| *c <= 0.0) {
+                let middle = corners.iter().sum::<Vec3>() / 4.0;
+                return Some((0.0, middle));
+            }
+            nearest_edge(&mut (0..4).map(|i| (corners[i], corners[(i + 1) % 4])))
+        }
+    }
+}
+
+/// Distance from `point` to the segment `a`-`b`, and the fraction along the
+/// segment of the nearest point.
+#[cfg(any(feature = "translate", feature = "rotate"))]
+fn segment_distance(point: Vec2, a: Vec2, b: Vec2) -> (f32, f32) {
+    let ab = b - a;
+    let s = if ab.length_squared() > f32::EPSILON {
+        ((point - a).dot(ab) / ab.length_squared()).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    (point.distance(a + ab * s), s)
+}
+
+/// System parameter for finding the gizmo handle at a screen position.
+///
+/// ```ignore
+/// fn tutorial(lookup: GizmoHandleLookup, windows: Query<&Window>) {
+///     let Some(cursor) = windows.single().ok().and_then(Window::cursor_position) else {
+///         return;
+///     };
+///     if let Some(handle) = lookup.handle_at_screen_position(cursor) {
+///         info!("cursor is over {handle}");
+///     }
+/// }
+/// ```
+#[derive(SystemParam)]
+pub struct GizmoHandleLookup<'w, 's> {
+    state: Res<'w, TransformGizmoState>,
+    style: Res<'w, TransformGizmoStyle>,
+    camera_selection: Res<'w, GizmoCameraSelection>,
+    cameras: Query<'w, 's, (&'static Camera, &'static GlobalTransform), With<TransformGizmoCamera>>,
+    targets: Query<'w, 's, GizmoTargetQuery, With<TransformGizmoTarget>>,
+    global_transforms: Query<'w, 's, &'static GlobalTransform>,
+}
+
+impl GizmoHandleLookup<'_, '_> {
+    /// The handle under `position` (logical pixels, like the cursor) as seen
+    /// by the gizmo camera, without touching hover state.
+    ///
+    /// Runs the same hit test as hover, without pen tilt bias or velocity
+    /// compensation. Positions outside the camera's viewport hit nothing.
+    pub fn handle_at_screen_position(&self, position: Vec2) -> Option<GizmoHandleId> {
+        let (camera, camera_transform) = self
+            .camera_selection
+            .camera()
+            .and_then(|camera| self.cameras.get(camera).ok())?;
+        let viewport = camera.logical_viewport_rect()?;
+        if !viewport.contains(position) {
+            return None;
+        }
+        let ray = camera.viewport_to_world(camera_transform, position).ok()?;
+        let sizing = GizmoSizing::new(&self.style, camera, camera_transform);
+        let gizmos = self
+            .targets
+            .iter()
+            .filter(|(entity, ..)| self.style.shows_target(&self.state, *entity))
+            .map(
+                |(
+                    entity,
+                    transform,
+                    anchor,
+                    channel,
+                    channel_value,
+                    endpoints,
+                    parent,
+                    constraints,
+                )| {
+                    let (frame, parts) = target_frame(
+                        transform,
+                        parent.and_then(|parent| self.global_transforms.get(parent.parent()).ok()),
+                        anchor_pivot(anchor, &self.style).as_ref(),
+                        channel,
+                        channel_value,
+                        endpoints,
+                        constraints,
+                        self.state.frame_space(entity),
+                        &sizing,
+                    );
+                    (entity, frame, parts)
+                },
+            );
+        match self.style.hit_test_mode {
+            HitTestMode::World => pick_handle(
+                &ray,
+                gizmos,
+                &self.style,
+                HoverBias::default(),
+                0.0,
+                |_, _| 0.0,
+                &mut 0,
+                &mut 0,
+            ),
+            HitTestMode::Screen => {
+                let projection = ScreenProjection {
+                    clip_from_view: camera.clip_from_view(),
+                    camera_transform,
+                    viewport_size: viewport.size(),
+                };
+                pick_handle_on_screen(
+                    position - viewport.min,
+                    &projection,
+                    gizmos,
+                    &self.style,
+                    HoverBias::default(),
+                    &mut Vec::new(),
+                    &mut 0,
+                    &mut 0,
+                )
+            }
+        }
+    }
+}
+
+#[cfg(all(test, feature = "translate"))]
+mod tests {
+    use super::*;
+    use crate::gizmo_frame::GizmoFrame;
+    use crate::interaction::hit_test_handles;
+    use crate::test_utils::GizmoTestHarness;
+    use crate::types::{
+        GizmoAxis, GizmoChannelParts, GizmoOperation, GizmoScaleMode, TransformGizmoSpace,
+    };
+    use bevy::math::Ray3d;
+
+    const VIEWPORT: Vec2 = Vec2::new(800.0, 600.0);
+
+    fn viewport() -> Rect {
+        Rect::from_corners(Vec2::ZERO, VIEWPORT)
+    }
+
+    fn translate_only() -> TransformGizmoStyle {
+        TransformGizmoStyle {
+            show_rotate: false,
+            show_scale: false,
+            show_translate_planes: false,
+            ..default()
+        }
+    }
+
+    /// Ray through a logical viewport pixel, by unprojecting two depths.
+    fn pixel_ray(clip_from_view: Mat4, camera: &GlobalTransform, pixel: Vec2) -> Ray3d {
+        let ndc = Vec2::new(
+            pixel.x / VIEWPORT.x * 2.0 - 1.0,
+            1.0 - pixel.y / VIEWPORT.y * 2.0,
+        );
+        let world_from_clip = Mat4::from(camera.affine()) * clip_from_view.inverse();
+        let near = world_from_clip.project_point3(ndc.extend(1.0));
+        let far = world_from_clip.project_point3(ndc.extend(0.5));
+        Ray3d::new(near, Dir3::new(far - near).unwrap())
+    }
+
+    fn anchors_of(frame: &GizmoFrame, style: &TransformGizmoStyle) -> Vec<HandleAnchor> {
+        let mut anchors = Vec::new();
+        handle_anchors(frame, style, GizmoChannelParts::ALL, &mut anchors);
+        anchors
+    }
+
+    #[test]
+    fn screen_positions_track_camera_movement() {
+        let style = translate_only();
+        let frame = GizmoFrame::new(&GlobalTransform::IDENTITY, TransformGizmoSpace::World);
+        let anchors = anchors_of(&frame, &style);
+        let x_cone = anchors
+            .iter()
+            .find(|anchor| {
+                anchor.op == GizmoOperation::TranslateAxis && anchor.axis == GizmoAxis::X
+            })
+            .unwrap();
+
+        // Orthographic view: panning the camera shifts the handle by exactly
+        // the pan distance in pixels.
+        let ortho = Mat4::orthographic_rh(-8.0, 8.0, -6.0, 6.0, 0.0, 100.0);
+        let pixels_per_unit = VIEWPORT.x / 16.0;
+        let camera_at = |x: f32| GlobalTransform::from_xyz(x, 0.0, 10.0);
+        let before = project_handle(x_cone, ortho, &camera_at(0.0), viewport()).unwrap();
+        let after = project_handle(x_cone, ortho, &camera_at(1.5), viewport()).unwrap();
+        assert!(before.visible && after.visible);
+        assert!((before.position.x - after.position.x - 1.5 * pixels_per_unit).abs() < 1.0e-3);
+        assert!((before.position.y - after.position.y).abs() < 1.0e-3);
+        assert!((before.radius - style.translate_hit_radius * pixels_per_unit).abs() < 1.0e-3);
+
+        // Orbiting perspective camera: the listed position stays on the
+        // handle, and the handle leaves the screen when behind the camera.
+        let perspective = Mat4::perspective_infinite_reverse_rh(1.0, VIEWPORT.x / VIEWPORT.y, 0.1);
+        for step in 0..8 {
+            let angle = step as f32 * std::f32::consts::FRAC_PI_4;
+            let camera = GlobalTransform::from(
+                Transform::from_xyz(8.0 * angle.cos(), 3.0, 8.0 * angle.sin())
+                    .looking_at(Vec3::ZERO, Vec3::Y),
+            );
+            let screen = project_handle(x_cone, perspective, &camera, viewport()).unwrap();
+            assert!(screen.visible);
+            let ray = pixel_ray(perspective, &camera, screen.position);
+            let closest =
+                ray.origin + *ray.direction * (x_cone.center - ray.origin).dot(*ray.direction);
+            assert!(closest.distance(x_cone.center) < 1.0e-3);
+        }
+        let behind = GlobalTransform::from(
+            Transform::from_xyz(3.0, 0.0, 0.0).looking_at(Vec3::new(10.0, 0.0, 0.0), Vec3::Y),
+        );
+        assert!(project_handle(x_cone, perspective, &behind, viewport())
+            .is_none_or(|screen| !screen.visible));
+    }
+
+    #[test]
+    fn screen_space_sizing_keeps_handles_constant_on_screen() {
+        let style = TransformGizmoStyle {
+            scale_mode: GizmoScaleMode::ScreenSpace(100.0),
+            ..translate_only()
+        };
+        let perspective = Mat4::perspective_infinite_reverse_rh(1.0, VIEWPORT.x / VIEWPORT.y, 0.1);
+        let orthographic = |height: f32| {
+            let half = Vec2::new(height * VIEWPORT.x / VIEWPORT.y, height) * 0.5;
+            // Reverse-Z like Bevy's own orthographic projection.
+            Mat4::orthographic_rh(-half.x, half.x, -half.y, half.y, 1000.0, 0.0)
+        };
+        let expected_radius = 100.0 * style.translate_hit_radius / style.axis_length;
+
+        for (clip_from_view, distance) in [
+            (perspective, 4.0),
+            (perspective, 40.0),
+            (perspective, 400.0),
+            (orthographic(3.0), 10.0),
+            (orthographic(300.0), 10.0),
+        ] {
+            let camera = GlobalTransform::from(
+                Transform::from_xyz(0.0, 0.0, distance).looking_at(Vec3::ZERO, Vec3::Y),
+            );
+            let sizing =
+                GizmoSizing::from_projection(&style, clip_from_view, &camera, Some(VIEWPORT));
+            let frame = GizmoFrame::new(&GlobalTransform::IDENTITY, TransformGizmoSpace::World);
+            let frame = frame.with_size(sizing.factor(frame.origin));
+            let sized = frame.sized_style(&style);
+
+            // The drawn and clickable size is the same on screen at every
+            // distance and projection.
+            let anchors = anchors_of(&frame, &sized);
+            let x_cone = anchors
+                .iter()
+                .find(|anchor| {
+                    anchor.op == GizmoOperation::TranslateAxis && anchor.axis == GizmoAxis::X
+                })
+                .unwrap();
+            let screen = project_handle(x_cone, clip_from_view, &camera, viewport()).unwrap();
+            assert!(
+                (screen.radius - expected_radius).abs() < 0.05,
+                "radius {} at distance {distance}",
+                screen.radius
+            );
+
+            // Hover picks the handle where it is drawn.
+            let ray = pixel_ray(clip_from_view, &camera, screen.position);
+            let picked = pick_handle(
+                &ray,
+                [(Entity::PLACEHOLDER, frame, GizmoChannelParts::ALL)],
+                &style,
+                HoverBias::default(),
+                0.0,
+                |_, _| 0.0,
+                &mut 0,
+                &mut 0,
+            );
+            assert_eq!(
+                picked,
+                Some(GizmoHandleId::new(
+                    Entity::PLACEHOLDER,
+                    GizmoOperation::TranslateAxis,
+                    GizmoAxis::X
+                ))
+            );
+        }
+    }
+
+    #[test]
+    fn lookup_agrees_with_exhaustive_hit_test_over_a_grid() {
+        let style = TransformGizmoStyle::default();
+        let perspective = Mat4::perspective_infinite_reverse_rh(0.9, VIEWPORT.x / VIEWPORT.y, 0.1);
+        let camera = GlobalTransform::from(
+            Transform::from_xyz(4.0, 5.0, 9.0).looking_at(Vec3::new(1.0, 0.0, 0.0), Vec3::Y),
+        );
+        let mut world = World::new();
+        let gizmos: Vec<_> = [
+            Vec3::ZERO,
+            Vec3::new(2.5, 0.0, -1.0),
+            Vec3::new(0.8, 0.3, 1.2),
+        ]
+        .into_iter()
+        .enumerate()
+        .map(|(index, origin)| {
+            let transform = GlobalTransform::from(
+                Transform::from_translation(origin)
+                    .with_rotation(Quat::from_rotation_y(index as f32 * 0.7)),
+            );
+            (
+                world.spawn_empty().id(),
+                GizmoFrame::new(&transform, TransformGizmoSpace::Local),
+                GizmoChannelParts::ALL,
+            )
+        })
+        .collect();
+
+        let mut hits = 0;
+        for x in (0..800).step_by(8) {
+            for y in (0..600).step_by(8) {
+                let ray = pixel_ray(perspective, &camera, Vec2::new(x as f32, y as f32));
+                let picked = pick_handle(
+                    &ray,
+                    gizmos.iter().copied(),
+                    &style,
+                    HoverBias::default(),
+                    0.0,
+                    |_, _| 0.0,
+                    &mut 0,
+                    &mut 0,
+                );
+                let exhaustive = gizmos
+                    .iter()
+                    .filter_map(|(entity, frame, parts)| {
+                        hit_test_handles(&ray, frame, &style, *parts, &|_| 0.0, &mut 0)
+                            .map(|(t, op, axis)| (t, GizmoHandleId::new(*entity, op, axis)))
+                    })
+                    .min_by(|a, b| a.0.total_cmp(&b.0))
+                    .map(|(_, handle)| handle);
+                assert_eq!(picked, exhaustive, "disagreement at pixel ({x}, {y})");
+                hits += usize::from(picked.is_some());
+            }
+        }
+        assert!(hits > 0);
+    }
+
+    #[test]
+    fn screen_hit_testing_measures_pixels_and_prefers_the_nearest_handle() {
+        let style = TransformGizmoStyle {
+            hit_test_mode: HitTestMode::Screen,
+            hover_pixel_radius: 8.0,
+            ..translate_only()
+        };
+        let perspective = Mat4::perspective_infinite_reverse_rh(0.9, VIEWPORT.x / VIEWPORT.y, 0.1);
+        let mut world = World::new();
+        let entity = world.spawn_empty().id();
+        let frame = GizmoFrame::new(&GlobalTransform::IDENTITY, TransformGizmoSpace::World);
+        let gizmos = [(entity, frame, GizmoChannelParts::ALL)];
+        let pick = |camera: &GlobalTransform, style: &TransformGizmoStyle, cursor: Vec2| {
+            let projection = ScreenProjection {
+                clip_from_view: perspective,
+                camera_transform: camera,
+                viewport_size: VIEWPORT,
+            };
+            pick_handle_on_screen(
+                cursor,
+                &projection,
+                gizmos,
+                style,
+                HoverBias::default(),
+                &mut Vec::new(),
+                &mut 0,
+                &mut 0,
+            )
+        };
+
+        // From far away the X cone is a few pixels thick: the world-space
+        // test misses a cursor just beside it, the pixel radius does not.
+        let far = GlobalTransform::from_xyz(0.0, 0.0, 100.0);
+        let x_tip = Vec3::X * (style.axis_length + style.translate_cone_length);
+        let (tip, _) = viewport_position(perspective, &far, VIEWPORT, x_tip).unwrap();
+        let beside = tip + Vec2::new(0.0, 6.0);
+        let ray = pixel_ray(perspective, &far, beside);
+        assert_eq!(
+            hit_test_handles(
+                &ray,
+                &frame,
+                &style,
+                GizmoChannelParts::ALL,
+                &|_| 0.0,
+                &mut 0
+            ),
+            None
+        );
+        assert_eq!(
+            pick(&far, &style, beside),
+            Some(GizmoHandleId::new(
+                entity,
+                GizmoOperation::TranslateAxis,
+                GizmoAxis::X
+            ))
+        );
+        assert_eq!(pick(&far, &style, tip + Vec2::new(0.0, 12.0)), None);
+
+        // Looking along the X axis, its cone and the view translation circle
+        // overlap at the center of the screen; whichever is nearer wins.
+        let style = TransformGizmoStyle {
+            show_translate_view: true,
+            ..style
+        };
+        let center = VIEWPORT * 0.5;
+        for (side, expected) in [
+            (1.0, GizmoOperation::TranslateAxis),
+            (-1.0, GizmoOperation::TranslateView),
+        ] {
+            let camera = GlobalTransform::from(
+                Transform::from_xyz(10.0 * side, 0.0, 0.0).looking_at(Vec3::ZERO, Vec3::Y),
+            );
+            let picked = pick(&camera, &style, center).unwrap();
+            assert_eq!(picked.op, expected, "camera on side {side}");
+        }
+    }
+
+    #[test]
+    fn screen_info_follows_the_hovered_handle() {
+        let info = |harness: &GizmoTestHarness| {
+            harness.app().world().resource::<GizmoScreenInfo>().clone()
+        };
+        let mut harness = GizmoTestHarness::new();
+        let cone = harness.grab(GizmoOperation::TranslateAxis, GizmoAxis::Y);
+        let origin = harness.world_to_cursor(Vec3::ZERO).unwrap();
+        let hovered = info(&harness);
+        assert_eq!(hovered.camera, Some(harness.camera()));
+        assert!(hovered.origin.unwrap().distance(origin) < 1.0e-2);
+        let (handle, screen) = hovered.hovered.unwrap();
+        assert_eq!(
+            handle,
+            GizmoHandleId::new(
+                harness.target(),
+                GizmoOperation::TranslateAxis,
+                GizmoAxis::Y
+            )
+        );
+        assert_eq!(screen.position, cone);
+        assert!(screen.radius > 0.0);
+
+        // Away from the gizmo only the origin is left.
+        harness.move_cursor(origin + Vec2::new(400.0, 300.0));
+        harness.step();
+        let away = info(&harness);
+        assert_eq!(away.hovered, None);
+        assert_eq!(away.origin, hovered.origin);
+    }
+}
@@ -0,0 +1,410 @@
+//! Undo and redo for gizmo edits.
+//!
+//! Inserting a [`GizmoHistory`] opts in to recording: every completed drag
+//! becomes an entry holding the edited transform before and after it. Set
+//! [`GizmoHistory::undo_requested`] or [`GizmoHistory::redo_requested`] (for
+//! example from Ctrl+Z / Ctrl+Y) and the plugin applies them.
+
+use std::collections::VecDeque;
+
+use bevy::prelude::*;
+
+use crate::channel::GizmoChannelValue;
+use crate::external::{GizmoExternalDriver, GizmoTransformRequest};
+use crate::types::{GizmoDragEnded, GizmoDragStarted, GizmoEditChannel, TransformGizmoState};
+
+/// One entity's transform change within a [`GizmoHistoryEntry`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GizmoEdit {
+    /// The edited entity.
+    pub entity: Entity,
+    /// Whether `before` and `after` are the entity's `Transform` or its
+    /// secondary channel value.
+    pub channel: GizmoEditChannel,
+    /// The transform to restore on undo.
+    pub before: Transform,
+    /// The transform to restore on redo.
+    pub after: Transform,
+}
+
+/// Edits undone or redone together, such as one drag.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GizmoHistoryEntry {
+    /// Every entity the step changed, the dragged target first.
+    pub edits: Vec<GizmoEdit>,
+}
+
+/// Opt-in undo/redo history of gizmo drags.
+///
+/// Not inserted by the plugin; insert it to start recording. Each drag that
+/// ends with a changed transform (and is not cancelled) is recorded as one
+/// entry. Systems that move other entities along with a drag, such as a
+/// multi-selection following a shared pivot, can add their edits to that
+/// entry through [`GizmoHistory::drag_entry_mut`] when they see the
+/// [`GizmoDragEnded`] message, after [`TransformGizmoSystems::Input`].
+///
+/// Starting a new drag discards everything that could be redone. Edits for
+/// entities that no longer exist are skipped when undoing or redoing.
+///
+/// ```ignore
+/// app.insert_resource(GizmoHistory::new(100));
+///
+/// fn undo_keys(keys: Res<ButtonInput<KeyCode>>, mut history: ResMut<GizmoHistory>) {
+///     if keys.pressed(KeyCode::ControlLeft) && keys.just_pressed(KeyCode::KeyZ) {
+///         history.undo_requested = true;
+///     }
+/// }
+/// ```
+///
+/// [`TransformGizmoSystems::Input`]: crate::TransformGizmoSystems::Input
+#[derive(Resource, Debug, Clone)]
+pub struct GizmoHistory {
+    entries: VecDeque<GizmoHistoryEntry>,
+    capacity: usize,
+    /// Number of entries currently applied; entries past it can be redone.
+    cursor: usize,
+    /// Undo one entry the next time the history systems run. Ignored while
+    /// a drag is active.
+    pub undo_requested: bool,
+    /// Redo one entry the next time the history systems run. Ignored while
+    /// a drag is active.
+    pub redo_requested: bool,
+}
+
+impl Default for GizmoHistory {
+    fn default() -> Self {
+        Self::new(100)
+    }
+}
+
+impl GizmoHistory {
+    /// Creates an empty history keeping at most `capacity` entries; the
+    /// oldest are dropped first.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: VecDeque::with_capacity(capacity),
+            capacity,
+            cursor: 0,
+            undo_requested: false,
+            redo_requested: false,
+        }
+    }
+
+    /// Record an entry, discarding anything that could be redone.
+    pub fn push(&mut self, entry: GizmoHistoryEntry) {
+        self.truncate_redo();
+        if self.capacity == 0 {
+            return;
+        }
+        if self.entries.len() == self.capacity {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(entry);
+        self.cursor = self.entries.len();
+    }
+
+    /// The entry recorded for the drag `ended` reports, while it is the
+    /// latest one. `None` if the drag was not recorded (it was cancelled or
+    /// changed nothing).
+    pub fn drag_entry_mut(&mut self, ended: &GizmoDragEnded) -> Option<&mut GizmoHistoryEntry> {
+        let index = self.cursor.checked_sub(1)?;
+        self.entries.get_mut(index).filter(|entry| {
+            entry.edits.first().is_some_and(|edit| {
+                edit.entity == ended.handle.target
+                    && edit.before == ended.initial_transform
+                    && edit.after == ended.final_transform
+            })
+        })
+    }
+
+    /// Discard every entry that could be redone.
+    pub fn truncate_redo(&mut self) {
+        self.entries.truncate(self.cursor);
+    }
+
+    /// Whether there is an entry to undo.
+    pub fn can_undo(&self) -> bool {
+        self.cursor > 0
+    }
+
+    /// Whether there is an entry to redo.
+    pub fn can_redo(&self) -> bool {
+        self.cursor < self.entries.len()
+    }
+
+    /// Number of recorded entries, including ones that can be redone.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether nothing is recorded.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Forget every entry.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.cursor = 0;
+    }
+
+    /// Step back one entry, returning it to be restored to its `before`
+    /// transforms.
+    fn step_undo(&mut self) -> Option<&GizmoHistoryEntry> {
+        self.cursor = self.cursor.checked_sub(1)?;
+        self.entries.get(self.cursor)
+    }
+
+    /// Step forward one entry, returning it to be restored to its `after`
+    /// transforms.
+    fn step_redo(&mut self) -> Option<&GizmoHistoryEntry> {
+        let entry = self.entries.get(self.cursor)?;
+        self.cursor += 1;
+        Some(entry)
+    }
+}
+
+/// Record finished drags into the [`GizmoHistory`], if present.
+pub fn record_gizmo_history(
+    history: Option<ResMut<GizmoHistory>>,
+    mut started: MessageReader<GizmoDragStarted>,
+    mut ended: MessageReader<GizmoDragEnded>,
+    targets: Query<Option<&GizmoEditChannel>>,
+) {
+    let Some(mut history) = history else {
+        started.clear();
+        ended.clear();
+        return;
+    };
+    if started.read().count() > 0 {
+        history.truncate_redo();
+    }
+    for ended in ended.read() {
+        if ended.cancelled || ended.initial_transform == ended.final_transform {
+            continue;
+        }
+        let entity = ended.handle.target;
+        let channel = targets
+            .get(entity)
+            .ok()
+            .flatten()
+            .copied()
+            .unwrap_or_default();
+        history.push(GizmoHistoryEntry {
+            edits: vec![GizmoEdit {
+                entity,
+                channel,
+                before: ended.initial_transform,
+                after: ended.final_transform,
+            }],
+        });
+    }
+}
+
+/// What [`apply_gizmo_history`] restores on an edited entity.
+type HistoryTarget = (
+    Option<&'static mut Transform>,
+    Option<&'static mut GizmoChannelValue>,
+    Has<GizmoExternalDriver>,
+);
+
+/// Apply requested undo and redo steps from the [`GizmoHistory`].
+///
+/// Steps that put back a [`GizmoExternalDriver`]'s transform are published
+/// as [`GizmoTransformRequest`]s instead.
+pub fn apply_gizmo_history(
+    history: Option<ResMut<GizmoHistory>>,
+    state: Res<TransformGizmoState>,
+    mut targets: Query<HistoryTarget>,
+    mut requests: MessageWriter<GizmoTransformRequest>,
+) {
+    let Some(mut history) = history else {
+        return;
+    };
+    if !history.undo_requested && !history.redo_requested {
+        return;
+    }
+    let undo = std::mem::take(&mut history.undo_requested);
+    let redo = std::mem::take(&mut history.redo_requested);
+    if state.drag.is_some() {
+        return;
+    }
+
+    let history = history.into_inner();
+    if undo {
+        if let Some(entry) = history.step_undo() {
+            for edit in entry.edits.iter().rev() {
+                restore(&mut targets, &mut requests, edit, edit.before);
+            }
+        }
+    }
+    if redo {
+        if let Some(entry) = history.step_redo() {
+            for edit in &entry.edits {
+                restore(&mut targets, &mut requests, edit, edit.after);
+            }
+        }
+    }
+}
+
+/// Write `value` to the transform `edit` changed, skipping entities that no
+/// longer exist.
+fn restore(
+    targets: &mut Query<HistoryTarget>,
+    requests: &mut MessageWriter<GizmoTransformRequest>,
+    edit: &GizmoEdit,
+    value: Transform,
+) {
+    let Ok((transform, channel_value, external)) = targets.get_mut(edit.entity) else {
+        return;
+    };
+    match (edit.channel, channel_value) {
+        (GizmoEditChannel::Secondary, Some(mut channel_value)) => {
+            channel_value.local = value;
+            channel_value.dirty = true;
+        }
+        (GizmoEditChannel::Secondary, None) => {}
+        (GizmoEditChannel::Transform, _) if external => {
+            requests.write(GizmoTransformRequest {
+                target: edit.entity,
+                transform: value,
+            });
+        }
+        (GizmoEditChannel::Transform, _) => {
+            if let Some(mut transform) = transform {
+                *transform = value;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{GizmoAxis, GizmoHandleId, GizmoOperation};
+    use bevy::ecs::message::Messages;
+    use bevy::ecs::system::RunSystemOnce;
+
+    fn edit(entity: Entity, before: f32, after: f32) -> GizmoHistoryEntry {
+        GizmoHistoryEntry {
+            edits: vec![GizmoEdit {
+                entity,
+                channel: GizmoEditChannel::Transform,
+                before: Transform::from_xyz(before, 0.0, 0.0),
+                after: Transform::from_xyz(after, 0.0, 0.0),
+            }],
+        }
+    }
+
+    fn world_with(history: GizmoHistory) -> World {
+        let mut world = World::new();
+        world.init_resource::<TransformGizmoState>();
+        world.init_resource::<Messages<GizmoTransformRequest>>();
+        world.insert_resource(history);
+        world
+    }
+
+    fn x_of(world: &World, entity: Entity) -> f32 {
+        world.get::<Transform>(entity).unwrap().translation.x
+    }
+
+    fn request(world: &mut World, undo: bool) {
+        let mut history = world.resource_mut::<GizmoHistory>();
+        if undo {
+            history.undo_requested = true;
+        } else {
+            history.redo_requested = true;
+        }
+        world.run_system_once(apply_gizmo_history).unwrap();
+    }
+
+    #[test]
+    fn undo_and_redo_walk_the_history() {
+        let mut world = world_with(GizmoHistory::new(8));
+        let entity = world.spawn(Transform::from_xyz(2.0, 0.0, 0.0)).id();
+        {
+            let mut history = world.resource_mut::<GizmoHistory>();
+            history.push(edit(entity, 0.0, 1.0));
+            history.push(edit(entity, 1.0, 2.0));
+        }
+
+        request(&mut world, true);
+        assert_eq!(x_of(&world, entity), 1.0);
+        request(&mut world, true);
+        assert_eq!(x_of(&world, entity), 0.0);
+        request(&mut world, true);
+        assert_eq!(x_of(&world, entity), 0.0);
+        request(&mut world, false);
+        assert_eq!(x_of(&world, entity), 1.0);
+        assert!(world.resource::<GizmoHistory>().can_redo());
+    }
+
+    #[test]
+    fn starting_a_drag_after_undo_truncates_redo() {
+        let mut world = world_with(GizmoHistory::new(8));
+        world.init_resource::<Messages<GizmoDragStarted>>();
+        world.init_resource::<Messages<GizmoDragEnded>>();
+        let entity = world.spawn(Transform::from_xyz(1.0, 0.0, 0.0)).id();
+        world
+            .resource_mut::<GizmoHistory>()
+            .push(edit(entity, 0.0, 1.0));
+        request(&mut world, true);
+
+        world.write_message(GizmoDragStarted {
+            handle: GizmoHandleId::new(entity, GizmoOperation::TranslateAxis, GizmoAxis::X),
+        });
+        world.run_system_once(record_gizmo_history).unwrap();
+
+        let history = world.resource::<GizmoHistory>();
+        assert!(!history.can_redo());
+        assert!(history.is_empty());
+    }
+
+    #[test]
+    fn capacity_drops_oldest_and_despawned_entities_are_skipped() {
+        let mut world = world_with(GizmoHistory::new(2));
+        let kept = world.spawn(Transform::from_xyz(3.0, 0.0, 0.0)).id();
+        let gone = world.spawn(Transform::default()).id();
+        {
+            let mut history = world.resource_mut::<GizmoHistory>();
+            history.push(edit(kept, 0.0, 1.0));
+            history.push(edit(kept, 1.0, 2.0));
+            history.push(GizmoHistoryEntry {
+                edits: vec![edit(kept, 2.0, 3.0).edits[0], edit(gone, 0.0, 5.0).edits[0]],
+            });
+        }
+        world.despawn(gone);
+
+        request(&mut world, true);
+        assert_eq!(x_of(&world, kept), 2.0);
+        request(&mut world, true);
+        assert_eq!(x_of(&world, kept), 1.0);
+        // The first entry fell off the front.
+        request(&mut world, true);
+        assert_eq!(x_of(&world, kept), 1.0);
+        assert_eq!(world.resource::<GizmoHistory>().len(), 2);
+    }
+
+    #[test]
+    fn undoing_an_externally_driven_edit_publishes_it() {
+        let mut world = world_with(GizmoHistory::new(8));
+        let entity = world.spawn(GizmoExternalDriver).id();
+        world
+            .resource_mut::<GizmoHistory>()
+            .push(edit(entity, 0.0, 1.0));
+
+        request(&mut world, true);
+        let requests: Vec<_> = world
+            .resource_mut::<Messages<GizmoTransformRequest>>()
+            .drain()
+            .collect();
+        assert_eq!(
+            requests,
+            [GizmoTransformRequest {
+                target: entity,
+                transform: Transform::from_xyz(0.0, 0.0, 0.0),
+            }]
+        );
+        assert!(world.get::<Transform>(entity).is_none());
+    }
+}
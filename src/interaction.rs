@@ -3,313 +3,1355 @@
 //! This module contains systems for detecting mouse hover over gizmo elements,
 //! starting/ending drag operations, and applying transforms during drags.
 
+use bevy::camera::primitives::Frustum;
+use bevy::ecs::entity::EntityHashMap;
+use bevy::ecs::system::SystemParam;
 use bevy::gizmos::config::{DefaultGizmoConfigGroup, GizmoConfigStore};
 use bevy::input::mouse::MouseButton;
 use bevy::input::ButtonInput;
+use bevy::math::Affine3A;
 use bevy::prelude::*;
-use bevy::window::PrimaryWindow;
 
 /// Epsilon for zero-length vector checks.
 const EPSILON: f32 = 1e-6;
 
+/// Axes closer than this to the view direction, in degrees, are dragged in
+/// screen space: no plane through them faces the camera well enough to
+/// intersect the cursor ray with.
+const GRAZING_AXIS_DEGREES: f32 = 12.0;
+
+/// Rotation rings whose axis is further than this from the view direction,
+/// in degrees, are seen nearly edge-on. The cursor ray meets their plane too
+/// obliquely to give a stable angle, so they turn with the cursor's angle
+/// around the origin on screen instead.
+const EDGE_ON_RING_DEGREES: f32 = 78.0;
+
+/// Camera-facing drag planes are re-aimed once the camera has turned this far
+/// from them, in degrees, so small camera jitter never rebases a drag.
+const REAIM_PLANE_DEGREES: f32 = 0.25;
+
 /// Minimum divisor to prevent division by zero in scale calculations.
+#[cfg(feature = "scale")]
 const MIN_SCALE_DIVISOR: f32 = 1e-3;
 
-use crate::gizmo_frame::{plane_axes, AxisKind, GizmoFrame};
-use crate::math::{axis_basis, ray_plane_intersection, ray_sphere_intersection};
+/// How much closer, as a fraction of its distance, another handle's hit
+/// must be to take hover from the handle that has it, or from a handle of
+/// the active target.
+const HOVER_SWITCH_TOLERANCE: f32 = 1e-3;
+
+#[cfg(feature = "translate")]
+use crate::align::{AlignCandidates, GizmoAlignSnapped};
+use crate::camera::{drag_pointer_ray, pointer_ray};
+use crate::channel::{edit_global, secondary_channel, GizmoChannelValue};
+use crate::diagnostics::HoverStatsScope;
+#[cfg(feature = "translate")]
+use crate::endpoints::solve_endpoint_drag;
+use crate::external::{GizmoExternalDriver, GizmoTransformRequest};
+use crate::gizmo_frame::{plane_axes, AxisKind, GizmoDetail, GizmoFrame, GizmoSizing};
+#[cfg(feature = "planes")]
+use crate::handle_geometry::plane_rect;
+#[cfg(feature = "rotate")]
+use crate::handle_geometry::rotation_ring;
+#[cfg(feature = "scale")]
+use crate::handle_geometry::scale_cube;
+#[cfg(feature = "translate")]
+use crate::handle_geometry::translate_cone;
+use crate::handles::{pick_handle_on_screen, ScreenProjection};
+#[cfg(feature = "translate")]
+use crate::math::plane_coordinates;
+use crate::math::{
+    axis_basis, nearest_transform, ray_near, ray_plane_intersection, ray_sphere_intersection,
+    ray_sphere_intersections, scale_ratio, toward_camera,
+};
+#[cfg(feature = "rotate")]
+use crate::math::{axis_in_parent, has_non_uniform_scale, trackball_rotation, twist_angle};
+use crate::preferences::GizmoStableId;
+use crate::smoothing::GizmoTargetTransform;
+#[cfg(feature = "translate")]
+use crate::surface::{SurfaceHit, SurfaceRaycast};
+use crate::sweep::{sweep_translation, GizmoBlockedTranslation, GizmoSweepProvider};
+#[cfg(any(feature = "translate", feature = "rotate"))]
+use crate::types::SnapMode;
 use crate::types::{
-    GizmoAxis, GizmoOperation, TransformGizmoCamera, TransformGizmoDrag, TransformGizmoSnap,
-    TransformGizmoState, TransformGizmoStyle, TransformGizmoTarget,
+    DragHistory, DragInterruptPolicy, EndpointSign, ExternalChangePolicy, GizmoActive, GizmoAxis,
+    GizmoCameraSelection, GizmoCenterHandle, GizmoChannelParts, GizmoConstraints, GizmoDragEnded,
+    GizmoDragStarted, GizmoDragging, GizmoDuplicated, GizmoEditChannel, GizmoEndpoints,
+    GizmoFrameStats, GizmoHandleClicked, GizmoHandleId, GizmoHit, GizmoLimits, GizmoLocked,
+    GizmoOperation, GizmoPointer, GizmoVisualPivot, HitTestMode, TransformGizmoCamera,
+    TransformGizmoConfig, TransformGizmoConfigGroup, TransformGizmoDrag,
+    TransformGizmoGuideConfigGroup, TransformGizmoKeybinds, TransformGizmoSnap,
+    TransformGizmoSpace, TransformGizmoState, TransformGizmoStyle, TransformGizmoTarget,
 };
+#[cfg(feature = "translate")]
+use crate::types::{GizmoAlignLock, SurfaceSnapSettings};
+#[cfg(feature = "rotate")]
+use crate::visual_pivot::rotate_about_pivot;
+#[cfg(feature = "scale")]
+use crate::visual_pivot::scale_about_pivot;
+use crate::visual_pivot::{anchor_pivot, AnchorData};
+
+/// Depth bias Bevy draws gizmo lines over every other geometry with.
+const ON_TOP_DEPTH_BIAS: f32 = -1.0;
 
-/// Configure Bevy's built-in gizmo renderer using our style resource.
+/// Apply the style's line width and depth to the gizmo config groups the
+/// gizmo draws through, whenever the style changes.
 pub fn configure_gizmos(
     mut config_store: ResMut<GizmoConfigStore>,
     style: Res<TransformGizmoStyle>,
 ) {
-    let (config, _) = config_store.config_mut::<DefaultGizmoConfigGroup>();
+    if !style.is_changed() {
+        return;
+    }
+    let depth_bias = |on_top: bool| {
+        if on_top {
+            ON_TOP_DEPTH_BIAS
+        } else {
+            style.depth_bias
+        }
+    };
+    let handles_depth = depth_bias(style.depth_mode.handles_on_top());
+    if style.draw_in_default_gizmo_group {
+        let config = config_store.config_mut::<DefaultGizmoConfigGroup>().0;
+        config.line.width = style.line_width;
+        config.depth_bias = handles_depth;
+        return;
+    }
+    let config = config_store.config_mut::<TransformGizmoConfigGroup>().0;
     config.line.width = style.line_width;
-    config.depth_bias = style.depth_bias;
+    config.depth_bias = handles_depth;
+    let guides = config_store
+        .config_mut::<TransformGizmoGuideConfigGroup>()
+        .0;
+    guides.line.width = style.line_width;
+    guides.depth_bias = depth_bias(style.depth_mode.guides_on_top());
+}
+
+/// Whether the handling code for `op` was compiled into this build.
+///
+/// Operation variants always exist for API stability, but their hover tests,
+/// drawing, and drag math are only compiled when the matching cargo feature
+/// is enabled.
+pub const fn operation_compiled(op: GizmoOperation) -> bool {
+    match op {
+        GizmoOperation::TranslateAxis => cfg!(feature = "translate"),
+        GizmoOperation::TranslatePlane => cfg!(feature = "planes"),
+        GizmoOperation::Rotate | GizmoOperation::RotateView | GizmoOperation::RotateTrackball => {
+            cfg!(feature = "rotate")
+        }
+        GizmoOperation::ScaleAxis => cfg!(feature = "scale"),
+        GizmoOperation::ScaleUniform => cfg!(feature = "uniform-scale"),
+        GizmoOperation::TranslateView => cfg!(feature = "translate"),
+        GizmoOperation::Endpoint { .. } => cfg!(feature = "translate"),
+        GizmoOperation::OriginDot => true,
+    }
+}
+
+/// Frame-to-frame origin motion of gizmo targets, used to extrapolate hover.
+#[derive(Default)]
+pub struct TargetMotion {
+    origins: EntityHashMap<(Vec3, u32)>,
+    frame: u32,
+}
+
+impl TargetMotion {
+    /// Start a hover pass, evicting targets not seen in the previous one.
+    fn begin_frame(&mut self) {
+        let previous = self.frame;
+        self.origins.retain(|_, (_, seen)| *seen == previous);
+        self.frame = self.frame.wrapping_add(1);
+    }
+
+    /// Record `origin` for `entity` and return its motion since the
+    /// previous pass (zero for targets not seen then).
+    fn observe(&mut self, entity: Entity, origin: Vec3) -> Vec3 {
+        let previous = self.frame.wrapping_sub(1);
+        let velocity = match self.origins.get(&entity) {
+            Some(&(last, seen)) if seen == previous => origin - last,
+            _ => Vec3::ZERO,
+        };
+        self.origins.insert(entity, (origin, self.frame));
+        velocity
+    }
 }
 
 /// Determine which gizmo part (if any) is currently hovered.
+#[allow(clippy::too_many_arguments)]
 pub fn update_hovered_axis(
     mut state: ResMut<TransformGizmoState>,
     style: Res<TransformGizmoStyle>,
-    cameras: Query<(&Camera, &GlobalTransform), With<TransformGizmoCamera>>,
-    windows: Query<&Window, With<PrimaryWindow>>,
-    targets: Query<(Entity, &GlobalTransform), With<TransformGizmoTarget>>,
+    camera_selection: Res<GizmoCameraSelection>,
+    cameras: Query<(&Camera, &GlobalTransform, Option<&Frustum>), With<TransformGizmoCamera>>,
+    pointer: Res<GizmoPointer>,
+    targets: Query<GizmoTargetQuery, (With<TransformGizmoTarget>, Without<GizmoLocked>)>,
+    global_transforms: Query<&GlobalTransform>,
+    config: Res<TransformGizmoConfig>,
+    mut motion: Local<TargetMotion>,
+    mut outlines: Local<Vec<HandleOutline>>,
+    mut stats: ResMut<GizmoFrameStats>,
 ) {
+    let mut scope = HoverStatsScope::new(&mut stats);
+    motion.begin_frame();
+
     // We only care about hover when we are not currently dragging.
     if state.drag.is_some() {
         return;
     }
 
-    let Some((camera, camera_transform)) = cameras.iter().next() else {
+    let Some((camera, camera_transform, frustum)) = camera_selection
+        .camera()
+        .and_then(|camera| cameras.get(camera).ok())
+    else {
+        state.hovered_target = None;
         state.hovered_axis = None;
         state.hovered_op = None;
         return;
     };
-    let Some(window) = windows.iter().next() else {
+    let Some(ray) = pointer_ray(&camera_selection, camera, camera_transform, &pointer) else {
+        state.hovered_target = None;
         state.hovered_axis = None;
         state.hovered_op = None;
         return;
     };
 
-    let Some(cursor_pos) = window.cursor_position() else {
-        state.hovered_axis = None;
-        state.hovered_op = None;
-        return;
+    // Pen tilt toward an axis handle's on-screen direction favours it.
+    let tilt = pointer
+        .tilt
+        .filter(|tilt| config.tilt_axis_bias > 0.0 && *tilt != Vec2::ZERO);
+    let max_bias = if tilt.is_some() {
+        config.tilt_axis_bias
+    } else {
+        0.0
+    };
+
+    let space = state.space;
+    let sizing = GizmoSizing::new(&style, camera, camera_transform);
+    let shown = |entity: &Entity| style.shows_target(&state, *entity);
+    let gizmos = targets
+        .iter()
+        .filter(|(entity, ..)| shown(entity))
+        .filter_map(
+            |(
+                entity,
+                transform,
+                anchor,
+                channel,
+                channel_value,
+                endpoints,
+                parent,
+                constraints,
+            )| {
+                let (mut frame, parts) = target_frame(
+                    transform,
+                    parent.and_then(|parent| global_transforms.get(parent.parent()).ok()),
+                    anchor_pivot(anchor, &style).as_ref(),
+                    channel,
+                    channel_value,
+                    endpoints,
+                    constraints,
+                    space,
+                    &sizing,
+                );
+                if config.hover_velocity_compensation {
+                    // Test the handles where the target will be next frame.
+                    let velocity = motion.observe(entity, frame.origin);
+                    frame = frame.with_origin(frame.origin + velocity);
+                }
+                // Off-screen and distant gizmos skip the hit math entirely.
+                let detail = sizing.detail(&frame, &frame.sized_style(&style), frustum);
+                (detail == GizmoDetail::Full).then_some((entity, frame, parts))
+            },
+        );
+    let axis_bias = |frame: &GizmoFrame, axis_dir: Vec3| {
+        tilt.zip(screen_direction(
+            camera,
+            camera_transform,
+            frame.origin,
+            axis_dir,
+        ))
+        .map_or(0.0, |(tilt, screen)| {
+            tilt_axis_score(screen, tilt, config.tilt_axis_bias)
+        })
+    };
+    // Last frame's hover holds on through small cursor motion, and the
+    // active target wins ties with targets whose gizmos overlap it.
+    let hover = HoverBias {
+        held: state
+            .hovered_target
+            .zip(state.hovered_op)
+            .zip(state.hovered_axis)
+            .map(|((target, op), axis)| GizmoHandleId::new(target, op, axis)),
+        margin: config.hover_hysteresis,
+        preferred: state.active_target,
+    };
+    let hovered = match style.hit_test_mode {
+        HitTestMode::World => pick_handle(
+            &ray,
+            gizmos,
+            &style,
+            hover,
+            max_bias,
+            axis_bias,
+            &mut scope.targets_tested,
+            &mut scope.exact_intersections,
+        ),
+        HitTestMode::Screen => {
+            // `pointer_ray` only gives a ray for a cursor inside the viewport.
+            let viewport = camera.logical_viewport_rect();
+            let cursor = pointer.position.zip(viewport).map(|(position, viewport)| {
+                let projection = ScreenProjection {
+                    clip_from_view: camera.clip_from_view(),
+                    camera_transform,
+                    viewport_size: viewport.size(),
+                };
+                (position - viewport.min, projection)
+            });
+            cursor.and_then(|(cursor, projection)| {
+                pick_handle_on_screen(
+                    cursor,
+                    &projection,
+                    gizmos,
+                    &style,
+                    hover,
+                    &mut outlines,
+                    &mut scope.targets_tested,
+                    &mut scope.exact_intersections,
+                )
+            })
+        }
     };
 
-    let Ok(ray) = camera.viewport_to_world(camera_transform, cursor_pos) else {
+    if let Some(handle) = hovered {
+        state.hovered_target = Some(handle.target);
+        state.hovered_axis = Some(handle.axis);
+        state.hovered_op = Some(handle.op);
+    } else {
+        state.hovered_target = None;
         state.hovered_axis = None;
         state.hovered_op = None;
-        return;
+    }
+}
+
+/// What [`target_frame`] lays out a target's gizmo from, as hover, drawing,
+/// and the handle screen info query it.
+pub(crate) type GizmoTargetQuery = (
+    Entity,
+    &'static GlobalTransform,
+    AnchorData,
+    Option<&'static GizmoEditChannel>,
+    Option<&'static GizmoChannelValue>,
+    Option<&'static GizmoEndpoints>,
+    Option<&'static ChildOf>,
+    Option<&'static GizmoConstraints>,
+);
+
+/// Gizmo frame and editable parts of a target, as hover and drawing see it.
+///
+/// `parent` is the global transform of the target's parent, if it has one.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn target_frame(
+    transform: &GlobalTransform,
+    parent: Option<&GlobalTransform>,
+    pivot: Option<&GizmoVisualPivot>,
+    channel: Option<&GizmoEditChannel>,
+    channel_value: Option<&GizmoChannelValue>,
+    endpoints: Option<&GizmoEndpoints>,
+    constraints: Option<&GizmoConstraints>,
+    space: TransformGizmoSpace,
+    sizing: &GizmoSizing,
+) -> (GizmoFrame, GizmoChannelParts) {
+    let secondary = secondary_channel(channel, channel_value);
+    // A secondary channel's value is relative to the target itself.
+    let parent = match secondary {
+        Some(_) => Some(transform),
+        None => parent,
     };
+    let mut frame = GizmoFrame::from_context(
+        &edit_global(transform, secondary),
+        space,
+        parent,
+        Some(sizing.camera_transform()),
+    );
+    if let (Some(pivot), None) = (pivot, secondary) {
+        frame = frame.with_origin(pivot.world_position(transform));
+    }
+    // Endpoints edit the target's own `Transform`, never a secondary channel.
+    if let (Some(endpoints), None) = (endpoints, secondary) {
+        frame = frame.with_endpoints(transform, endpoints);
+    }
+    if let Some(constraints) = constraints {
+        frame = frame.with_constraints(*constraints);
+    }
+    let frame = frame
+        .with_size(sizing.factor(frame.origin))
+        .with_view_dir(sizing.view_dir());
+    let frame = frame.with_camera_direction(sizing.toward_camera(frame.origin));
+    let parts = secondary.map_or(GizmoChannelParts::ALL, |value| value.parts);
+    (frame, parts)
+}
+
+/// `frame` with the plane handle signs of a drag on `target`, so the plane
+/// handles do not flip while the camera moves mid-drag.
+pub(crate) fn held_plane_signs(
+    frame: GizmoFrame,
+    state: &TransformGizmoState,
+    target: Entity,
+) -> GizmoFrame {
+    match &state.drag {
+        Some(drag) if drag.target == target => frame.with_plane_signs(drag.plane_signs),
+        _ => frame,
+    }
+}
+
+/// How hover favours the handles it already has, see
+/// [`TransformGizmoConfig::hover_hysteresis`].
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct HoverBias {
+    /// The handle hovered last frame. It keeps hover while the cursor stays
+    /// within its hit radius grown by `margin`, unless another handle's hit
+    /// is closer by more than [`HOVER_SWITCH_TOLERANCE`].
+    pub held: Option<GizmoHandleId>,
+    /// Fraction the held handle's hit radius grows by.
+    pub margin: f32,
+    /// The target whose handles win ties with other targets', the active
+    /// one.
+    pub preferred: Option<Entity>,
+}
+
+/// Rank of a hover candidate, lowest first: a fallback class, then the hit
+/// score.
+pub(crate) type HoverRank = (u8, f32);
+
+impl HoverBias {
+    /// Whether a hit on `target` ranked `rank` beats the best hit so far.
+    pub(crate) fn beats(
+        &self,
+        target: Entity,
+        rank: HoverRank,
+        best: Option<(GizmoHandleId, HoverRank)>,
+    ) -> bool {
+        let Some((best, best_rank)) = best else {
+            return true;
+        };
+        let preferred = |target| self.preferred == Some(target);
+        let tolerance = match (preferred(target), preferred(best.target)) {
+            (true, false) => -HOVER_SWITCH_TOLERANCE,
+            (false, true) => HOVER_SWITCH_TOLERANCE,
+            _ => 0.0,
+        };
+        outranks(rank, best_rank, tolerance)
+    }
+
+    /// The `best` hit, unless the held handle, ranked `held` with its grown
+    /// radius, is still under the cursor and `best` does not outrank it by
+    /// more than [`HOVER_SWITCH_TOLERANCE`].
+    pub(crate) fn settle(
+        &self,
+        best: Option<(GizmoHandleId, HoverRank)>,
+        held: Option<HoverRank>,
+    ) -> Option<GizmoHandleId> {
+        match (best, self.held.zip(held)) {
+            (Some((handle, rank)), Some((held, held_rank)))
+                if handle != held && !outranks(rank, held_rank, HOVER_SWITCH_TOLERANCE) =>
+            {
+                Some(held)
+            }
+            (None, Some((held, _))) => Some(held),
+            _ => best.map(|(handle, _)| handle),
+        }
+    }
+}
+
+/// Whether `rank` is in a better fallback class than `other`, or in the
+/// same one and scores lower by more than `tolerance` of `other`'s score.
+fn outranks(rank: HoverRank, other: HoverRank, tolerance: f32) -> bool {
+    rank.0 < other.0 || (rank.0 == other.0 && rank.1 < other.1 - tolerance * other.1.abs())
+}
 
-    // Search across *all* targets for the closest gizmo element under the cursor.
+/// Closest handle hit by `ray` across several gizmos.
+///
+/// This is the hit test shared by hover and [`GizmoHandleLookup`]. Targets
+/// whose bounding sphere is hit further away than the best hit so far (plus
+/// `max_bias`, the largest bonus `axis_bias` can give) are skipped. `hover`
+/// keeps the held handle and favours the active target, see [`HoverBias`].
+///
+/// [`GizmoHandleLookup`]: crate::GizmoHandleLookup
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn pick_handle(
+    ray: &Ray3d,
+    gizmos: impl IntoIterator<Item = (Entity, GizmoFrame, GizmoChannelParts)>,
+    style: &TransformGizmoStyle,
+    hover: HoverBias,
+    max_bias: f32,
+    axis_bias: impl Fn(&GizmoFrame, Vec3) -> f32,
+    targets_tested: &mut u32,
+    exact_intersections: &mut u32,
+) -> Option<GizmoHandleId> {
     let mut best_t = f32::MAX;
-    let mut best_target: Option<Entity> = None;
-    let mut best: Option<(GizmoOperation, GizmoAxis)> = None;
+    let mut best = None;
+    let mut held_rank = None;
+
+    for (entity, frame, parts) in gizmos {
+        let style = frame.sized_style(style);
+        let bias = |axis_dir: Vec3| axis_bias(&frame, axis_dir);
 
-    for (entity, transform) in targets.iter() {
-        let frame = GizmoFrame::new(transform, state.space);
-        let origin = frame.origin;
+        // The held handle is tested alone with its grown radius, before the
+        // bounds test can skip its target.
+        if let Some(held) = hover.held.filter(|held| held.target == entity) {
+            let widened = style.widened(1.0 + hover.margin);
+            held_rank = best_handle_hit(
+                ray,
+                &frame,
+                &widened,
+                parts,
+                &bias,
+                Some((held.op, held.axis)),
+                exact_intersections,
+            )
+            .map(|(t, _)| (0, t));
+        }
 
         // Coarse bounds test: if the ray misses the gizmo's bounding sphere
-        // sooner than our current best hit, skip this target.
-        let Some(bounds_t) = ray_sphere_intersection(&ray, origin, style.bounds_radius) else {
+        // (and its endpoint handles, which can be far from the origin)
+        // sooner than our current best hit, skip this target. A camera
+        // inside the bounds always tests the handles.
+        let bounds_entry = |center, radius| {
+            ray_sphere_intersections(ray, center, radius).map(|(near, _)| near.max(0.0))
+        };
+        let endpoint_bounds = [EndpointSign::Negative, EndpointSign::Positive]
+            .into_iter()
+            .filter_map(|end| frame.endpoint(end))
+            .filter_map(|(_, center)| bounds_entry(center, style.endpoint_hit_radius));
+        let bounds_radius =
+            if handle_enabled(&style, parts, GizmoOperation::RotateView, GizmoAxis::X) {
+                style
+                    .effective_bounds_radius()
+                    .max(style.rotate_view_radius + style.rotation_hit_thickness)
+            } else {
+                style.effective_bounds_radius()
+            };
+        let Some(bounds_t) = bounds_entry(frame.origin, bounds_radius)
+            .into_iter()
+            .chain(endpoint_bounds)
+            .min_by(f32::total_cmp)
+        else {
             continue;
         };
-        if bounds_t > best_t {
+        if bounds_t - max_bias > best_t * (1.0 + HOVER_SWITCH_TOLERANCE) {
             continue;
         }
+        *targets_tested += 1;
 
-        // --- Axis translation cones ---
-        let allow_translate = style.show_translate;
-        let allow_rotate = style.show_rotate;
-        let allow_scale = style.show_scale;
-
-        if allow_translate {
-            for axis in [GizmoAxis::X, GizmoAxis::Y, GizmoAxis::Z] {
-                if !style.translate_axes.enabled(axis) {
-                    continue;
-                }
+        if let Some((t, op, axis)) =
+            hit_test_handles(ray, &frame, &style, parts, &bias, exact_intersections)
+        {
+            if hover.beats(entity, (0, t), best) {
+                best_t = t;
+                best = Some((GizmoHandleId::new(entity, op, axis), (0, t)));
+            }
+        }
+    }
 
-                let axis_dir = frame
-                    .axis_dir(axis, AxisKind::Translate)
-                    .normalize_or_zero();
-                if axis_dir.length_squared() < EPSILON {
-                    continue;
-                }
+    hover.settle(best, held_rank)
+}
 
-                // Match the drawn cone: centered between the end of the axis
-                // line and the cone tip.
-                let line_end = origin + axis_dir * style.axis_length;
-                let cone_tip = line_end + axis_dir * style.translate_cone_length;
-                let center = (line_end + cone_tip) * 0.5;
-
-                if let Some(t) = ray_sphere_intersection(&ray, center, style.translate_hit_radius) {
-                    if t < best_t {
-                        best_t = t;
-                        best_target = Some(entity);
-                        best = Some((GizmoOperation::TranslateAxis, axis));
-                    }
-                }
+/// Whether the `(op, axis)` handle is shown, and therefore hit tested.
+pub(crate) fn handle_enabled(
+    style: &TransformGizmoStyle,
+    parts: GizmoChannelParts,
+    op: GizmoOperation,
+    axis: GizmoAxis,
+) -> bool {
+    operation_compiled(op)
+        && !style.hidden_in_2d(op, axis)
+        && match op {
+            GizmoOperation::TranslateAxis => {
+                style.show_translate && parts.translate && style.translate_axes.enabled(axis)
+            }
+            GizmoOperation::TranslatePlane => {
+                style.show_translate
+                    && style.show_translate_planes
+                    && parts.translate
+                    && style.translate_axes.enabled(axis)
+            }
+            GizmoOperation::Rotate => {
+                style.show_rotate && parts.rotate && style.rotate_axes.enabled(axis)
+            }
+            GizmoOperation::RotateView => {
+                style.show_rotate && style.show_rotate_view && parts.rotate
+            }
+            GizmoOperation::RotateTrackball => {
+                style.show_rotate && style.show_rotate_trackball && parts.rotate
+            }
+            GizmoOperation::ScaleAxis => {
+                style.show_scale && parts.scale && style.scale_axes.enabled(axis)
+            }
+            GizmoOperation::ScaleUniform => {
+                style.show_scale && style.show_scale_uniform && parts.scale
+            }
+            GizmoOperation::TranslateView => {
+                style.show_translate && style.show_translate_view && parts.translate
             }
+            GizmoOperation::Endpoint { .. } => style.show_endpoints && parts.translate,
+            GizmoOperation::OriginDot => style.show_origin_dot,
         }
+}
 
-        // --- Axis scale cubes ---
-        if allow_scale {
-            for axis in [GizmoAxis::X, GizmoAxis::Y, GizmoAxis::Z] {
-                if !style.scale_axes.enabled(axis) {
-                    continue;
-                }
+/// Whether [`TransformGizmoStyle::fade_backfacing`] fades the `(op, axis)`
+/// handle, because the camera sees its axis within
+/// [`backface_fade_angle`](TransformGizmoStyle::backface_fade_angle) of
+/// end-on, or its plane or ring within that angle of edge-on. The origin,
+/// the uniform scale square, and the screen-facing handles never fade.
+pub(crate) fn handle_faces_away(
+    frame: &GizmoFrame,
+    style: &TransformGizmoStyle,
+    op: GizmoOperation,
+    axis: GizmoAxis,
+) -> bool {
+    if !style.fade_backfacing {
+        return false;
+    }
+    let (kind, along_axis) = match op {
+        GizmoOperation::TranslateAxis => (AxisKind::Translate, true),
+        GizmoOperation::ScaleAxis => (AxisKind::Scale, true),
+        // Planes and rings are seen edge-on when their normal is
+        // perpendicular to the line of sight.
+        GizmoOperation::TranslatePlane => (AxisKind::Translate, false),
+        GizmoOperation::Rotate => (AxisKind::Rotate, false),
+        _ => return false,
+    };
+    let dir = frame.axis_dir(axis, kind);
+    let facing = dir.dot(frame.to_camera()).abs();
+    let limit = style.backface_fade_angle.to_radians();
+    if along_axis {
+        facing > limit.cos()
+    } else {
+        facing < limit.sin()
+    }
+}
 
-                let axis_dir = frame.axis_dir(axis, AxisKind::Scale).normalize_or_zero();
-                if axis_dir.length_squared() < EPSILON {
-                    continue;
-                }
+/// Whether the `(op, axis)` handle can be hovered and grabbed: it is shown
+/// and not faded by [`handle_faces_away`].
+fn handle_pickable(
+    frame: &GizmoFrame,
+    style: &TransformGizmoStyle,
+    parts: GizmoChannelParts,
+    op: GizmoOperation,
+    axis: GizmoAxis,
+) -> bool {
+    handle_enabled(style, parts, op, axis)
+        && frame.allows(op, axis)
+        && !handle_faces_away(frame, style, op, axis)
+}
 
-                let center = origin + axis_dir * (style.axis_length * style.scale_cube_offset);
+/// Center and clickable radius of one handle.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct HandleAnchor {
+    pub(crate) op: GizmoOperation,
+    pub(crate) axis: GizmoAxis,
+    pub(crate) center: Vec3,
+    pub(crate) radius: f32,
+}
 
-                if let Some(t) = ray_sphere_intersection(&ray, center, style.scale_hit_radius) {
-                    if t < best_t {
-                        best_t = t;
-                        best_target = Some(entity);
-                        best = Some((GizmoOperation::ScaleAxis, axis));
-                    }
-                }
+/// Anchors of every pickable handle of one gizmo, from the same layout
+/// [`hit_test_handles`] tests against.
+pub(crate) fn handle_anchors(
+    frame: &GizmoFrame,
+    style: &TransformGizmoStyle,
+    parts: GizmoChannelParts,
+    anchors: &mut Vec<HandleAnchor>,
+) {
+    let enabled = |op, axis| handle_pickable(frame, style, parts, op, axis);
+    #[cfg(any(feature = "translate", feature = "rotate", feature = "scale"))]
+    for axis in GizmoAxis::ALL {
+        #[cfg(feature = "translate")]
+        if enabled(GizmoOperation::TranslateAxis, axis) {
+            if let Some(cone) = translate_cone(frame, style, axis) {
+                anchors.push(HandleAnchor {
+                    op: GizmoOperation::TranslateAxis,
+                    axis,
+                    center: cone.center(),
+                    radius: cone.hit_radius,
+                });
+            }
+        }
+        #[cfg(feature = "scale")]
+        if enabled(GizmoOperation::ScaleAxis, axis) {
+            if let Some(cube) = scale_cube(frame, style, axis) {
+                anchors.push(HandleAnchor {
+                    op: GizmoOperation::ScaleAxis,
+                    axis,
+                    center: cube.center,
+                    radius: style.scale_hit_radius,
+                });
+            }
+        }
+        #[cfg(feature = "rotate")]
+        if enabled(GizmoOperation::Rotate, axis) {
+            if let Some(ring) = rotation_ring(frame, style, axis) {
+                anchors.push(HandleAnchor {
+                    op: GizmoOperation::Rotate,
+                    axis,
+                    center: ring.middle(),
+                    radius: ring.hit_thickness,
+                });
+            }
+        }
+        #[cfg(feature = "planes")]
+        if enabled(GizmoOperation::TranslatePlane, axis) {
+            if let Some(plane) = plane_rect(frame, style, axis) {
+                anchors.push(HandleAnchor {
+                    op: GizmoOperation::TranslatePlane,
+                    axis,
+                    center: plane.center(),
+                    radius: style.translate_plane_size * 0.5,
+                });
             }
         }
+    }
+    #[cfg(feature = "rotate")]
+    if enabled(GizmoOperation::RotateView, GizmoAxis::X) {
+        // The top of the ring as seen by the camera.
+        let (_, up) = axis_basis(frame.view_dir);
+        anchors.push(HandleAnchor {
+            op: GizmoOperation::RotateView,
+            axis: GizmoAxis::X,
+            center: frame.origin + up * style.rotate_view_radius,
+            radius: style.rotation_hit_thickness,
+        });
+    }
+    #[cfg(feature = "rotate")]
+    if enabled(GizmoOperation::RotateTrackball, GizmoAxis::X) {
+        anchors.push(HandleAnchor {
+            op: GizmoOperation::RotateTrackball,
+            axis: GizmoAxis::X,
+            center: frame.origin,
+            radius: style.axis_length,
+        });
+    }
+    #[cfg(feature = "translate")]
+    if enabled(GizmoOperation::TranslateView, GizmoAxis::X) {
+        anchors.push(HandleAnchor {
+            op: GizmoOperation::TranslateView,
+            axis: GizmoAxis::X,
+            center: frame.origin,
+            radius: style.translate_view_hit_radius,
+        });
+    }
+    #[cfg(feature = "uniform-scale")]
+    if enabled(GizmoOperation::ScaleUniform, GizmoAxis::X) {
+        anchors.push(HandleAnchor {
+            op: GizmoOperation::ScaleUniform,
+            axis: GizmoAxis::X,
+            center: frame.origin,
+            radius: style.scale_uniform_hit_radius,
+        });
+    }
+    #[cfg(feature = "translate")]
+    for end in [EndpointSign::Negative, EndpointSign::Positive] {
+        let op = GizmoOperation::Endpoint { end };
+        if let Some((axis, center)) = frame.endpoint(end).filter(|(axis, _)| enabled(op, *axis)) {
+            anchors.push(HandleAnchor {
+                op,
+                axis,
+                center,
+                radius: style.endpoint_hit_radius,
+            });
+        }
+    }
+    if enabled(GizmoOperation::OriginDot, GizmoAxis::X) {
+        anchors.push(HandleAnchor {
+            op: GizmoOperation::OriginDot,
+            axis: GizmoAxis::X,
+            center: frame.origin,
+            radius: style.origin_dot_size,
+        });
+    }
+}
 
-        // --- Rotation arcs ---
-        if allow_rotate {
-            for (axis, axis_vec, n1, n2) in [
-                (
-                    GizmoAxis::X,
-                    frame.axis_dir(GizmoAxis::X, AxisKind::Rotate),
-                    frame.axis_dir(GizmoAxis::Y, AxisKind::Rotate),
-                    frame.axis_dir(GizmoAxis::Z, AxisKind::Rotate),
-                ),
-                (
-                    GizmoAxis::Y,
-                    frame.axis_dir(GizmoAxis::Y, AxisKind::Rotate),
-                    frame.axis_dir(GizmoAxis::Z, AxisKind::Rotate),
-                    frame.axis_dir(GizmoAxis::X, AxisKind::Rotate),
-                ),
-                (
-                    GizmoAxis::Z,
-                    frame.axis_dir(GizmoAxis::Z, AxisKind::Rotate),
-                    frame.axis_dir(GizmoAxis::X, AxisKind::Rotate),
-                    frame.axis_dir(GizmoAxis::Y, AxisKind::Rotate),
-                ),
-            ] {
-                if !style.rotate_axes.enabled(axis) {
-                    continue;
-                }
+/// Outline of one handle, which [`HitTestMode::Screen`] projects onto the
+/// screen and measures the cursor against.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum HandleShape {
+    /// A line segment, such as a cone from its base to its tip.
+    #[cfg(feature = "translate")]
+    Segment(Vec3, Vec3),
+    /// A camera-facing disc, such as a cube or a center handle.
+    Disc { center: Vec3, radius: f32 },
+    /// Part of a ring around `center`, from angle `start` over `sweep`
+    /// radians in the plane spanned by `basis`.
+    #[cfg(feature = "rotate")]
+    Arc {
+        center: Vec3,
+        basis: (Vec3, Vec3),
+        radius: f32,
+        start: f32,
+        sweep: f32,
+        /// Whether the part facing away from the camera is skipped, see
+        /// [`TransformGizmoStyle::rotation_backface_cutoff`].
+        cull_back: bool,
+    },
+    /// A planar quad, corners in winding order.
+    #[cfg(feature = "planes")]
+    Quad([Vec3; 4]),
+}
 
-                let axis_dir = axis_vec.normalize_or_zero();
-                if axis_dir.length_squared() < EPSILON {
-                    continue;
-                }
+/// Outline of one handle of a gizmo.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct HandleOutline {
+    pub(crate) op: GizmoOperation,
+    pub(crate) axis: GizmoAxis,
+    pub(crate) shape: HandleShape,
+}
 
-                let Some(hit_point) = crate::math::ray_plane_intersection(&ray, origin, axis_dir)
-                else {
-                    continue;
-                };
+/// Outlines of every pickable handle of one gizmo, from the same layout
+/// [`hit_test_handles`] tests against. The center handles come in
+/// [`TransformGizmoStyle::center_handle_priority`] order, so the first of
+/// two equally close outlines is the one to pick, and the centered square of
+/// [`TransformGizmoStyle::planar_2d`] comes after them.
+pub(crate) fn handle_outlines(
+    frame: &GizmoFrame,
+    style: &TransformGizmoStyle,
+    parts: GizmoChannelParts,
+    outlines: &mut Vec<HandleOutline>,
+) {
+    let enabled = |op, axis| handle_pickable(frame, style, parts, op, axis);
+    let mut push = |op, axis, shape| outlines.push(HandleOutline { op, axis, shape });
+    #[cfg(feature = "planes")]
+    let plane_quad = |axis| {
+        if !enabled(GizmoOperation::TranslatePlane, axis) {
+            return None;
+        }
+        Some(HandleShape::Quad(plane_rect(frame, style, axis)?.corners()))
+    };
+    #[cfg(any(feature = "translate", feature = "rotate", feature = "scale"))]
+    for axis in GizmoAxis::ALL {
+        #[cfg(feature = "translate")]
+        if enabled(GizmoOperation::TranslateAxis, axis) {
+            if let Some(cone) = translate_cone(frame, style, axis) {
+                push(
+                    GizmoOperation::TranslateAxis,
+                    axis,
+                    HandleShape::Segment(cone.base, cone.tip),
+                );
+            }
+        }
+        #[cfg(feature = "scale")]
+        if enabled(GizmoOperation::ScaleAxis, axis) {
+            if let Some(cube) = scale_cube(frame, style, axis) {
+                push(
+                    GizmoOperation::ScaleAxis,
+                    axis,
+                    HandleShape::Disc {
+                        center: cube.center,
+                        radius: cube.half_size,
+                    },
+                );
+            }
+        }
+        #[cfg(feature = "rotate")]
+        if enabled(GizmoOperation::Rotate, axis) {
+            if let Some(ring) = rotation_ring(frame, style, axis) {
+                push(
+                    GizmoOperation::Rotate,
+                    axis,
+                    HandleShape::Arc {
+                        center: ring.origin,
+                        basis: ring.basis,
+                        radius: ring.radius,
+                        start: ring.start_angle(),
+                        sweep: ring.sweep,
+                        cull_back: true,
+                    },
+                );
+            }
+        }
+        // The centered 2D square goes after the center handles it surrounds.
+        #[cfg(feature = "planes")]
+        if !style.planar_2d {
+            if let Some(quad) = plane_quad(axis) {
+                push(GizmoOperation::TranslatePlane, axis, quad);
+            }
+        }
+    }
+    #[cfg(feature = "rotate")]
+    if enabled(GizmoOperation::RotateView, GizmoAxis::X) {
+        push(
+            GizmoOperation::RotateView,
+            GizmoAxis::X,
+            HandleShape::Arc {
+                center: frame.origin,
+                basis: axis_basis(frame.view_dir),
+                radius: style.rotate_view_radius,
+                start: 0.0,
+                sweep: std::f32::consts::TAU,
+                cull_back: false,
+            },
+        );
+    }
+    let centers = match style.center_handle_priority {
+        GizmoCenterHandle::TranslateView => {
+            [GizmoOperation::TranslateView, GizmoOperation::ScaleUniform]
+        }
+        GizmoCenterHandle::ScaleUniform => {
+            [GizmoOperation::ScaleUniform, GizmoOperation::TranslateView]
+        }
+    };
+    for op in centers {
+        if enabled(op, GizmoAxis::X) {
+            let radius = match op {
+                GizmoOperation::TranslateView => style.translate_view_radius,
+                _ => style.scale_uniform_size * 0.5,
+            };
+            let center = frame.origin;
+            push(op, GizmoAxis::X, HandleShape::Disc { center, radius });
+        }
+    }
+    #[cfg(feature = "planes")]
+    if style.planar_2d {
+        if let Some(quad) = plane_quad(GizmoAxis::Z) {
+            push(GizmoOperation::TranslatePlane, GizmoAxis::Z, quad);
+        }
+    }
+    #[cfg(feature = "translate")]
+    for end in [EndpointSign::Negative, EndpointSign::Positive] {
+        let op = GizmoOperation::Endpoint { end };
+        if let Some((axis, center)) = frame.endpoint(end).filter(|(axis, _)| enabled(op, *axis)) {
+            let radius = style.endpoint_size * 0.5;
+            push(op, axis, HandleShape::Disc { center, radius });
+        }
+    }
+    if enabled(GizmoOperation::OriginDot, GizmoAxis::X) {
+        let (center, radius) = (frame.origin, style.origin_dot_size * 0.5);
+        push(
+            GizmoOperation::OriginDot,
+            GizmoAxis::X,
+            HandleShape::Disc { center, radius },
+        );
+    }
+    #[cfg(feature = "rotate")]
+    if enabled(GizmoOperation::RotateTrackball, GizmoAxis::X) {
+        let (center, radius) = (frame.origin, style.axis_length);
+        push(
+            GizmoOperation::RotateTrackball,
+            GizmoAxis::X,
+            HandleShape::Disc { center, radius },
+        );
+    }
+}
 
-                let v = hit_point - origin;
-                let radius = v.length();
-                if radius < 1e-4 {
-                    continue;
-                }
+/// Best handle of a single gizmo hit by `ray`, as `(score, op, axis)`.
+///
+/// See [`best_handle_hit`] for how handles are scored.
+pub(crate) fn hit_test_handles(
+    ray: &Ray3d,
+    frame: &GizmoFrame,
+    style: &TransformGizmoStyle,
+    parts: GizmoChannelParts,
+    axis_bias: &dyn Fn(Vec3) -> f32,
+    exact_intersections: &mut u32,
+) -> Option<(f32, GizmoOperation, GizmoAxis)> {
+    best_handle_hit(
+        ray,
+        frame,
+        style,
+        parts,
+        axis_bias,
+        None,
+        exact_intersections,
+    )
+    .map(|(score, hit)| (score, hit.op, hit.axis))
+}
 
-                let ring_radius = style.axis_length;
-                if (radius - ring_radius).abs() > style.rotation_hit_thickness {
-                    continue;
-                }
+/// The gizmo handle of `frame` that `ray` picks, if any.
+///
+/// This is the same hit test the plugin hovers handles with, so custom
+/// pickers (VR controllers, touch, or a ray from another camera) select
+/// exactly what the mouse would. `style` is scaled by the frame's size, so
+/// pass the unscaled [`TransformGizmoStyle`] resource. Every handle compiled
+/// into the build is considered; pen tilt has no effect.
+pub fn pick_gizmo_handle(
+    ray: &Ray3d,
+    frame: &GizmoFrame,
+    style: &TransformGizmoStyle,
+) -> Option<GizmoHit> {
+    let style = frame.sized_style(style);
+    best_handle_hit(
+        ray,
+        frame,
+        &style,
+        GizmoChannelParts::ALL,
+        &|_| 0.0,
+        None,
+        &mut 0,
+    )
+    .map(|(_, hit)| hit)
+}
 
-                let (t1, t2) = axis_basis(axis_dir);
-                let proj = v.normalize_or_zero();
-                let x = proj.dot(t1);
-                let y = proj.dot(t2);
-                let angle = y.atan2(x);
-
-                let mid = (n1 + n2).normalize_or_zero();
-                let mid = mid - axis_dir * axis_dir.dot(mid);
-                let mid = mid.normalize_or_zero();
-                let mx = mid.dot(t1);
-                let my = mid.dot(t2);
-                let centre = my.atan2(mx);
-
-                let half = style.rotation_arc_degrees.to_radians() * 0.5;
-                let diff = (angle - centre + std::f32::consts::PI)
-                    .rem_euclid(2.0 * std::f32::consts::PI)
-                    - std::f32::consts::PI;
-
-                if diff.abs() > half {
-                    continue;
-                }
+/// Best handle of a single gizmo hit by `ray`, with its score.
+///
+/// The score is the hit distance, reduced for axis handles by
+/// `axis_bias(axis_dir)` so tilt can favour one of several overlapping axes.
+/// Cones and cubes are tested against their drawn shape first; a ray that
+/// only passes within their hit radius scores an extra twice the
+/// [`TransformGizmoStyle::effective_bounds_radius`], so a handle the ray
+/// really goes through always wins over a near miss. Overlapping hits are
+/// then decided by [`RankedHits`]. The origin dot scores an extra three
+/// times that radius and the trackball four times, behind every other hit.
+/// With `only`, no other handle is tested.
+fn best_handle_hit(
+    ray: &Ray3d,
+    frame: &GizmoFrame,
+    style: &TransformGizmoStyle,
+    parts: GizmoChannelParts,
+    axis_bias: &dyn Fn(Vec3) -> f32,
+    only: Option<(GizmoOperation, GizmoAxis)>,
+    exact_intersections: &mut u32,
+) -> Option<(f32, GizmoHit)> {
+    let origin = frame.origin;
+    // Test from just before the gizmo, and measure from the caller's ray
+    // origin again at the end.
+    let (near_ray, shift) = ray_near(ray, origin, gizmo_reach(frame, style));
+    let ray = &near_ray;
+    let mut hits = RankedHits::default();
+    let enabled = |op, axis| {
+        handle_pickable(frame, style, parts, op, axis) && only.is_none_or(|only| only == (op, axis))
+    };
+    #[cfg(any(feature = "translate", feature = "scale"))]
+    let near_miss = style.effective_bounds_radius() * 2.0;
+    // Only the axis cones and cubes lean toward the pen's tilt.
+    #[cfg(not(any(feature = "translate", feature = "scale")))]
+    let _ = axis_bias;
+
+    // --- Axis translation cones ---
+    #[cfg(feature = "translate")]
+    for axis in GizmoAxis::ALL {
+        if !enabled(GizmoOperation::TranslateAxis, axis) {
+            continue;
+        }
+        let Some(cone) = translate_cone(frame, style, axis) else {
+            continue;
+        };
+
+        *exact_intersections += 1;
+        let hit = cone.hit(ray).map(|(t, missed)| {
+            let score = if missed { t + near_miss } else { t };
+            (score, t, missed)
+        });
+        if let Some((score, distance, near_miss)) = hit {
+            hits.offer(
+                style,
+                RankedHit {
+                    score: score - axis_bias(cone.axis_dir),
+                    op: GizmoOperation::TranslateAxis,
+                    axis,
+                    distance,
+                    near_miss,
+                },
+            );
+        }
+    }
+
+    // --- Axis scale cubes ---
+    #[cfg(feature = "scale")]
+    for axis in GizmoAxis::ALL {
+        if !enabled(GizmoOperation::ScaleAxis, axis) {
+            continue;
+        }
+        let Some(cube) = scale_cube(frame, style, axis) else {
+            continue;
+        };
+
+        *exact_intersections += 1;
+        let hit = cube.hit(ray).map(|(t, missed)| {
+            let score = if missed { t + near_miss } else { t };
+            (score, t, missed)
+        });
+        if let Some((score, distance, near_miss)) = hit {
+            let bias = axis_bias(frame.axis_dir(axis, AxisKind::Scale));
+            hits.offer(
+                style,
+                RankedHit {
+                    score: score - bias,
+                    op: GizmoOperation::ScaleAxis,
+                    axis,
+                    distance,
+                    near_miss,
+                },
+            );
+        }
+    }
+
+    // --- Rotation arcs ---
+    #[cfg(feature = "rotate")]
+    for axis in GizmoAxis::ALL {
+        if !enabled(GizmoOperation::Rotate, axis) {
+            continue;
+        }
+        let Some(ring) = rotation_ring(frame, style, axis) else {
+            continue;
+        };
+
+        *exact_intersections += 1;
+        if let Some(t) = ring.hit(ray) {
+            hits.offer(style, RankedHit::exact(GizmoOperation::Rotate, axis, t));
+        }
+    }
 
+    // --- Screen-facing rotation ring ---
+    #[cfg(feature = "rotate")]
+    if enabled(GizmoOperation::RotateView, GizmoAxis::X) {
+        *exact_intersections += 1;
+        if let Some(hit_point) = crate::math::ray_plane_intersection(ray, origin, frame.view_dir) {
+            let radius = (hit_point - origin).length();
+            if (radius - style.rotate_view_radius).abs() <= style.rotation_hit_thickness {
                 if let Some(t) =
-                    ray_sphere_intersection(&ray, hit_point, style.rotation_hit_thickness)
+                    ray_sphere_intersection(ray, hit_point, style.rotation_hit_thickness)
                 {
-                    if t < best_t {
-                        best_t = t;
-                        best_target = Some(entity);
-                        best = Some((GizmoOperation::Rotate, axis));
-                    }
+                    // Axis is unused for view rotation, but we must provide one.
+                    let hit = RankedHit::exact(GizmoOperation::RotateView, GizmoAxis::X, t);
+                    hits.offer(style, hit);
                 }
             }
         }
+    }
 
-        // --- Planar translation rectangles ---
-        if allow_translate && style.show_translate_planes {
-            for axis in [GizmoAxis::X, GizmoAxis::Y, GizmoAxis::Z] {
-                if !style.translate_axes.enabled(axis) {
-                    continue;
-                }
-                let (d1_axis, d2_axis) = plane_axes(axis);
-
-                let plane_normal = frame
-                    .axis_dir(axis, AxisKind::Translate)
-                    .normalize_or_zero();
-                let dir1 = frame
-                    .axis_dir(d1_axis, AxisKind::Translate)
-                    .normalize_or_zero();
-                let dir2 = frame
-                    .axis_dir(d2_axis, AxisKind::Translate)
-                    .normalize_or_zero();
-                if plane_normal.length_squared() < EPSILON
-                    || dir1.length_squared() < EPSILON
-                    || dir2.length_squared() < EPSILON
-                {
-                    continue;
-                }
+    // --- Planar translation rectangles ---
+    #[cfg(feature = "planes")]
+    for axis in GizmoAxis::ALL {
+        if !enabled(GizmoOperation::TranslatePlane, axis) {
+            continue;
+        }
+        let Some(plane) = plane_rect(frame, style, axis) else {
+            continue;
+        };
 
-                let Some(hit_point) =
-                    crate::math::ray_plane_intersection(&ray, origin, plane_normal)
-                else {
-                    continue;
-                };
+        *exact_intersections += 1;
+        if let Some(t) = plane.hit(ray) {
+            hits.offer(
+                style,
+                RankedHit::exact(GizmoOperation::TranslatePlane, axis, t),
+            );
+        }
+    }
 
-                let local = hit_point - origin;
-                let u = local.dot(dir1);
-                let v = local.dot(dir2);
-
-                let offset = style.translate_plane_offset;
-                let size = style.translate_plane_size;
-                let pad = style.translate_plane_hit_thickness;
-
-                let inside = u >= offset - pad
-                    && u <= offset + size + pad
-                    && v >= offset - pad
-                    && v <= offset + size + pad;
-
-                if inside {
-                    let t = (hit_point - ray.origin).dot(*ray.direction);
-                    if t >= 0.0 && t < best_t {
-                        best_t = t;
-                        best_target = Some(entity);
-                        best = Some((GizmoOperation::TranslatePlane, axis));
-                    }
-                }
-            }
+    // --- View translation circle and uniform scale square at the origin ---
+    // Both are treated as small spheres around the origin. They overlap, so
+    // when the ray hits both the configured priority decides, not distance.
+    #[cfg(any(feature = "translate", feature = "uniform-scale"))]
+    let mut center_hit = |op, radius| {
+        if !enabled(op, GizmoAxis::X) {
+            return None;
         }
-        // --- Uniform scale square at the origin ---
-        if allow_scale && style.show_scale_uniform {
-            // Treat the uniform scale handle as a small sphere around the origin.
-            if let Some(t) = ray_sphere_intersection(&ray, origin, style.scale_uniform_hit_radius) {
-                if t < best_t {
-                    best_t = t;
-                    best_target = Some(entity);
-                    // Axis is unused for uniform scale, but we must provide one.
-                    best = Some((GizmoOperation::ScaleUniform, GizmoAxis::X));
-                }
-            }
+        *exact_intersections += 1;
+        ray_sphere_intersection(ray, origin, radius).map(|t| (op, t))
+    };
+    #[cfg(feature = "translate")]
+    let translate_view = center_hit(
+        GizmoOperation::TranslateView,
+        style.translate_view_hit_radius,
+    );
+    #[cfg(not(feature = "translate"))]
+    let translate_view = None;
+    #[cfg(feature = "uniform-scale")]
+    let scale_uniform = center_hit(GizmoOperation::ScaleUniform, style.scale_uniform_hit_radius);
+    #[cfg(not(feature = "uniform-scale"))]
+    let scale_uniform = None;
+    let center = match style.center_handle_priority {
+        GizmoCenterHandle::TranslateView => translate_view.or(scale_uniform),
+        GizmoCenterHandle::ScaleUniform => scale_uniform.or(translate_view),
+    };
+    if let Some((op, t)) = center {
+        // The axis is unused for center handles, but we must provide one.
+        hits.offer(style, RankedHit::exact(op, GizmoAxis::X, t));
+    }
+
+    // --- Endpoint spheres ---
+    #[cfg(feature = "translate")]
+    for end in [EndpointSign::Negative, EndpointSign::Positive] {
+        let op = GizmoOperation::Endpoint { end };
+        let Some((axis, center)) = frame.endpoint(end).filter(|(axis, _)| enabled(op, *axis))
+        else {
+            continue;
+        };
+
+        *exact_intersections += 1;
+        if let Some(t) = ray_sphere_intersection(ray, center, style.endpoint_hit_radius) {
+            hits.offer(style, RankedHit::exact(op, axis, t));
         }
     }
 
-    if let (Some(target), Some((op, axis))) = (best_target, best) {
-        state.active_target = Some(target);
-        state.hovered_axis = Some(axis);
-        state.hovered_op = Some(op);
-    } else {
-        state.hovered_axis = None;
-        state.hovered_op = None;
+    let best = hits.resolve(style.pick_priority_tolerance);
+
+    // --- Origin dot ---
+    // Like the trackball it only takes rays no other handle wants, but it
+    // goes ahead of the trackball it sits inside.
+    let best = best.or_else(|| {
+        if !enabled(GizmoOperation::OriginDot, GizmoAxis::X) {
+            return None;
+        }
+        *exact_intersections += 1;
+        let t = ray_sphere_intersection(ray, origin, style.origin_dot_size)?;
+        Some(RankedHit {
+            score: t + style.effective_bounds_radius() * 3.0,
+            ..RankedHit::exact(GizmoOperation::OriginDot, GizmoAxis::X, t)
+        })
+    });
+
+    // --- Trackball inside the rotation rings ---
+    // It only takes rays no other handle wants, so its score is pushed
+    // behind near misses, on this gizmo and any other.
+    #[cfg(feature = "rotate")]
+    let best = best.or_else(|| {
+        if !enabled(GizmoOperation::RotateTrackball, GizmoAxis::X) {
+            return None;
+        }
+        *exact_intersections += 1;
+        let t = ray_sphere_intersection(ray, origin, style.axis_length)?;
+        Some(RankedHit {
+            score: t + style.effective_bounds_radius() * 4.0,
+            ..RankedHit::exact(GizmoOperation::RotateTrackball, GizmoAxis::X, t)
+        })
+    });
+
+    best.map(|best| {
+        let hit = GizmoHit {
+            op: best.op,
+            axis: best.axis,
+            distance: best.distance + shift,
+            point: ray.get_point(best.distance),
+        };
+        (best.score + shift, hit)
+    })
+}
+
+/// Distance from the gizmo origin that every handle's hit area of `frame`
+/// stays within, with room to spare.
+fn gizmo_reach(frame: &GizmoFrame, style: &TransformGizmoStyle) -> f32 {
+    let rings = style.rotate_view_radius + style.rotation_hit_thickness;
+    [EndpointSign::Negative, EndpointSign::Positive]
+        .into_iter()
+        .filter_map(|end| frame.endpoint(end))
+        .map(|(_, center)| center.distance(frame.origin) + style.endpoint_hit_radius)
+        .fold(style.effective_bounds_radius().max(rings), f32::max)
+        * 2.0
+}
+
+/// Number of [`TransformGizmoStyle::pick_rank`] values: one per
+/// [`TransformGizmoStyle::pick_priority`] entry, and one for unlisted
+/// operations.
+const PICK_RANKS: usize = 6;
+
+/// A handle hit competing for hover.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct RankedHit {
+    /// Lower is better: the distance along the ray or from the camera, plus
+    /// any near-miss penalty and tilt bias.
+    pub score: f32,
+    pub op: GizmoOperation,
+    pub axis: GizmoAxis,
+    /// Real distance of the hit along the ray.
+    pub distance: f32,
+    /// Whether the ray missed the handle's drawn shape and only passed
+    /// within its hit radius.
+    pub near_miss: bool,
+}
+
+impl RankedHit {
+    /// A hit on the drawn shape at `distance`, scored by that distance.
+    pub(crate) fn exact(op: GizmoOperation, axis: GizmoAxis, distance: f32) -> Self {
+        Self {
+            score: distance,
+            op,
+            axis,
+            distance,
+            near_miss: false,
+        }
+    }
+}
+
+/// The best hit of each [`TransformGizmoStyle::pick_priority`] rank of one
+/// gizmo, to choose between overlapping handles.
+#[derive(Default)]
+pub(crate) struct RankedHits {
+    best: [Option<RankedHit>; PICK_RANKS],
+}
+
+impl RankedHits {
+    /// Consider `hit`, keeping it if it scores best within its rank.
+    pub(crate) fn offer(&mut self, style: &TransformGizmoStyle, hit: RankedHit) {
+        let rank = style.pick_rank(hit.op).min(PICK_RANKS - 1);
+        if self.best[rank].is_none_or(|best| hit.score < best.score) {
+            self.best[rank] = Some(hit);
+        }
+    }
+
+    /// The winning hit: of those within `tolerance` of the best score, the
+    /// one of the highest rank. Near misses are dropped when a higher rank
+    /// has any hit, so a high-priority handle does not lose the cursor to the
+    /// padding of a low-priority one around it.
+    pub(crate) fn resolve(&self, tolerance: f32) -> Option<RankedHit> {
+        let first = self.best.iter().position(Option::is_some)?;
+        let candidates = || {
+            self.best
+                .iter()
+                .enumerate()
+                .filter_map(move |(rank, hit)| hit.filter(|hit| rank == first || !hit.near_miss))
+        };
+        let nearest = candidates().map(|hit| hit.score).fold(f32::MAX, f32::min);
+        candidates().find(|hit| hit.score <= nearest + tolerance)
     }
 }
 
+/// On-screen direction of `axis_dir` at `origin`, in viewport pixels.
+fn screen_direction(
+    camera: &Camera,
+    camera_transform: &GlobalTransform,
+    origin: Vec3,
+    axis_dir: Vec3,
+) -> Option<Vec2> {
+    let start = camera.world_to_viewport(camera_transform, origin).ok()?;
+    let end = camera
+        .world_to_viewport(camera_transform, origin + axis_dir)
+        .ok()?;
+    (end - start).try_normalize()
+}
+
+/// Hit-distance bonus for an axis whose on-screen direction is
+/// `screen_axis`, given pen `tilt` (direction and amount) and the configured
+/// `weight`.
+fn tilt_axis_score(screen_axis: Vec2, tilt: Vec2, weight: f32) -> f32 {
+    let amount = tilt.length().min(1.0);
+    let alignment = screen_axis
+        .normalize_or_zero()
+        .dot(tilt.normalize_or_zero());
+    weight * amount * alignment.max(0.0)
+}
+
+/// What [`begin_drag`] reads from the target under the cursor.
+type GrabbedTarget = (
+    GizmoTargetQuery,
+    Option<&'static Transform>,
+    Has<GizmoExternalDriver>,
+);
+
+#[allow(clippy::too_many_arguments)]
 pub fn begin_drag(
+    mut commands: Commands,
     buttons: Res<ButtonInput<MouseButton>>,
+    keys: Res<ButtonInput<KeyCode>>,
+    config: Res<TransformGizmoConfig>,
+    style: Res<TransformGizmoStyle>,
     mut state: ResMut<TransformGizmoState>,
+    camera_selection: Res<GizmoCameraSelection>,
     cameras: Query<(&Camera, &GlobalTransform), With<TransformGizmoCamera>>,
-    windows: Query<&Window, With<PrimaryWindow>>,
-    targets: Query<(Entity, &GlobalTransform, &Transform), With<TransformGizmoTarget>>,
+    pointer: Res<GizmoPointer>,
+    targets: Query<GrabbedTarget, (With<TransformGizmoTarget>, Without<GizmoLocked>)>,
+    global_transforms: Query<&GlobalTransform>,
+    mut started: MessageWriter<GizmoDragStarted>,
+    mut duplicated: MessageWriter<GizmoDuplicated>,
 ) {
-    if !buttons.just_pressed(MouseButton::Left) {
+    if !buttons.just_pressed(config.drag_input.button) || !config.drag_input.modifiers_allow(&keys)
+    {
         return;
     }
 
@@ -323,76 +1365,221 @@ pub fn begin_drag(
     let Some(op) = state.hovered_op else {
         return;
     };
+    if !operation_compiled(op) {
+        debug_assert!(false, "hovered {op:?} without its cargo feature enabled");
+        return;
+    }
+    // The origin dot is clicked rather than dragged, see `click_origin_dot`.
+    if op == GizmoOperation::OriginDot {
+        return;
+    }
 
-    let Some((camera, camera_transform)) = cameras.iter().next() else {
+    let Some((camera, camera_transform)) = camera_selection
+        .camera()
+        .and_then(|camera| cameras.get(camera).ok())
+    else {
         return;
     };
-    let Some(window) = windows.iter().next() else {
+    let Some(ray) = pointer_ray(&camera_selection, camera, camera_transform, &pointer) else {
         return;
     };
-    let Some(cursor_pos) = window.cursor_position() else {
+
+    let Some(target_entity) = state.hovered_target else {
         return;
     };
-    let Ok(ray) = camera.viewport_to_world(camera_transform, cursor_pos) else {
+    let Ok((
+        (entity, target_global, anchor, channel, channel_value, endpoints, parent, constraints),
+        target_local,
+        external,
+    )) = targets.get(target_entity)
+    else {
         return;
     };
+    if constraints.is_some_and(|constraints| !constraints.allows(op, axis)) {
+        return;
+    }
 
-    let Some(target_entity) = state.active_target else {
+    // Secondary channels edit a pseudo-transform relative to the target's
+    // global transform instead of the target's own `Transform`, and
+    // externally driven targets a world-space copy of their global one.
+    let secondary = secondary_channel(channel, channel_value);
+    let global = &edit_global(target_global, secondary);
+    let external = (external && secondary.is_none()).then(|| target_global.compute_transform());
+    let Some(local_transform) = secondary
+        .map(|value| &value.local)
+        .or(external.as_ref())
+        .or(target_local)
+    else {
         return;
     };
-    let Ok((entity, global, local_transform)) = targets.get(target_entity) else {
-        return;
+    let parent_global = match secondary {
+        Some(_) => Some(*target_global),
+        None => parent.and_then(|parent| global_transforms.get(parent.parent()).ok().copied()),
     };
+    let start_parent = parent_global.filter(|_| external.is_none());
+    let pivot = match secondary {
+        Some(_) => None,
+        None => anchor_pivot(anchor, &style).map(|pivot| pivot.world_position(global)),
+    };
+    let mut frame = GizmoFrame::from_context(
+        global,
+        state.space,
+        parent_global.as_ref(),
+        Some(camera_transform),
+    );
+    if let Some(pivot) = pivot {
+        frame = frame.with_origin(pivot);
+    }
 
-    let frame = GizmoFrame::new(global, state.space);
-    let origin = frame.origin;
+    // Endpoint drags hold the opposite end in the parent's space.
+    let endpoint = match op {
+        GizmoOperation::Endpoint { end } => {
+            let (Some(endpoints), None) = (endpoints, secondary) else {
+                return;
+            };
+            frame = frame.with_endpoints(global, endpoints);
+            Some((end, *endpoints))
+        }
+        _ => None,
+    };
+    let fixed_endpoint = endpoint.map_or(Vec3::ZERO, |(end, endpoints)| {
+        local_transform.transform_point(endpoints.local_position(end.opposite()))
+    });
+    let frame = frame.with_view_dir(*camera_transform.forward());
+    let frame = frame.with_camera_direction(toward_camera(
+        camera.clip_from_view(),
+        camera_transform,
+        frame.origin,
+    ));
+
+    let mut history = DragHistory::default();
+    if config.drag_history.enabled {
+        history = DragHistory::new(
+            config.drag_history.capacity,
+            config.drag_history.sample_interval,
+        );
+        history.push(*local_transform);
+    }
+
+    // A duplicate drag moves a copy and leaves the original in place. The
+    // copy starts out identical, so the drag is set up from the original.
+    let duplicate = op.is_translate() && secondary.is_none() && config.drag_input.duplicates(&keys);
+    let entity = if duplicate {
+        let copy = commands
+            .entity(entity)
+            .clone_and_spawn_with_opt_out(|builder| {
+                builder
+                    .deny::<(GizmoActive, GizmoStableId, GizmoTargetTransform)>()
+                    .linked_cloning(true);
+            })
+            .id();
+        duplicated.write(GizmoDuplicated {
+            source: entity,
+            copy,
+        });
+        state.hovered_target = Some(copy);
+        copy
+    } else {
+        entity
+    };
+
+    // A smoothed drag moves a goal that the target eases toward, starting
+    // from wherever the target is now.
+    if config.smoothing.is_some() && secondary.is_none() && external.is_none() {
+        commands
+            .entity(entity)
+            .insert(GizmoTargetTransform::new(*local_transform));
+    }
+
+    // Grabbing an inactive target's gizmo makes it the active target.
+    if config.select_on_drag && state.active_target != Some(entity) {
+        if let Some(previous) = state.active_target {
+            commands.entity(previous).try_remove::<GizmoActive>();
+        }
+        commands.entity(entity).insert(GizmoActive);
+        state.active_target = Some(entity);
+    }
+    let size = GizmoSizing::new(&style, camera, camera_transform).factor(frame.origin);
+    // The view space is held where the camera looks now, so orbiting
+    // mid-drag turns neither the drag nor the drawn axes.
+    let space = match state.space {
+        TransformGizmoSpace::View => TransformGizmoSpace::Custom(camera_transform.rotation()),
+        space => space,
+    };
+    // The drag waits for the cursor to move before it edits anything, so a
+    // click leaves the target alone.
+    let pending_from = pointer
+        .position
+        .filter(|_| config.drag_threshold_pixels > 0.0 && !duplicate);
+    state.drag = Some(TransformGizmoDrag {
+        channel: channel.copied().unwrap_or_default(),
+        camera: camera_selection.camera(),
+        space: Some(space),
+        pivot,
+        history,
+        start_parent,
+        endpoints: endpoint.map(|(_, endpoints)| endpoints),
+        fixed_endpoint,
+        trackball_radius: style.axis_length * size,
+        trackball_sensitivity: style.rotate_trackball_sensitivity,
+        external: external.is_some(),
+        pending_from,
+        ..begin_drag_from_ray(&ray, op, axis, entity, &frame, global, local_transform)
+    });
+    if pending_from.is_none() {
+        started.write(GizmoDragStarted {
+            handle: GizmoHandleId::new(entity, op, axis),
+        });
+    }
+}
+
+/// Start a drag of the `(op, axis)` handle of `frame` at the point where
+/// `ray` grabs it, for example after [`pick_gizmo_handle`] returned that
+/// handle.
+///
+/// `global` and `local` are the target's global and local transforms, and
+/// `frame` should be seen from the camera the ray comes from (see
+/// [`GizmoFrame::with_view_dir`] and [`GizmoFrame::with_camera_position`],
+/// or [`GizmoFrame::with_camera_direction`] for an orthographic camera).
+/// Endpoint drags start at the frame's endpoint handle, but the returned
+/// drag only moves the grabbed end once its `endpoints` and `fixed_endpoint`
+/// are filled in. The drag edits the target's `Transform` about the frame
+/// origin, without a visual pivot, drag history, or parent space; set those
+/// fields on the result where they apply. Trackball drags use the default
+/// style's sphere radius and sensitivity.
+pub fn begin_drag_from_ray(
+    ray: &Ray3d,
+    op: GizmoOperation,
+    axis: GizmoAxis,
+    target: Entity,
+    frame: &GizmoFrame,
+    global: &GlobalTransform,
+    local: &Transform,
+) -> TransformGizmoDrag {
+    let view_dir = frame.view_dir;
+    let origin = match op {
+        GizmoOperation::Endpoint { end } => frame.endpoint(end).map(|(_, center)| center),
+        _ => None,
+    }
+    .unwrap_or(frame.origin);
 
     // Axis direction or plane normal depending on operation.
     let axis_vec = match op {
-        GizmoOperation::TranslateAxis | GizmoOperation::TranslatePlane => {
-            frame.axis_dir(axis, AxisKind::Translate)
-        }
+        GizmoOperation::TranslateAxis => frame.axis_dir(axis, AxisKind::Translate),
+        GizmoOperation::TranslatePlane => frame.plane_normal(axis),
         GizmoOperation::Rotate => frame.axis_dir(axis, AxisKind::Rotate),
+        GizmoOperation::RotateView | GizmoOperation::RotateTrackball => view_dir,
         GizmoOperation::ScaleAxis => frame.axis_dir(axis, AxisKind::Scale),
-        GizmoOperation::ScaleUniform => *camera_transform.forward(),
+        GizmoOperation::ScaleUniform
+        | GizmoOperation::TranslateView
+        | GizmoOperation::Endpoint { .. }
+        | GizmoOperation::OriginDot => view_dir,
     };
     let axis_dir = axis_vec.normalize_or_zero();
 
     // Plane normal used to project mouse movement.
-    let plane_normal = match op {
-        GizmoOperation::Rotate => axis_dir,
-        GizmoOperation::TranslateAxis | GizmoOperation::ScaleAxis => {
-            // Plane that is perpendicular to both axis and camera view.
-            let view_dir: Vec3 = -*camera_transform.forward();
-            let n = axis_dir.cross(view_dir).cross(axis_dir).normalize_or_zero();
-            if n.length_squared() < EPSILON {
-                axis_dir
-            } else {
-                n
-            }
-        }
-        GizmoOperation::TranslatePlane => {
-            // Movement constrained to a fixed plane: use the plane normal directly.
-            axis_dir
-        }
-        GizmoOperation::ScaleUniform => {
-            // For uniform scale, use a plane whose normal is perpendicular to
-            // the view direction so that mouse motion produces a sensible
-            // distance change.
-            let view_dir: Vec3 = -*camera_transform.forward();
-            let helper = if view_dir.abs().dot(Vec3::Y) < 0.9 {
-                Vec3::Y
-            } else {
-                Vec3::X
-            };
-            let n = view_dir.cross(helper).normalize_or_zero();
-            if n.length_squared() < EPSILON {
-                view_dir
-            } else {
-                n
-            }
-        }
-    };
+    let plane_normal = interaction_plane_normal(op, axis_dir, -view_dir);
+    let screen_rotation = rotates_on_screen(op, axis_dir, -view_dir);
 
     // For planar translation, intersect the ray with the plane that passes
     // through the "L" corner so that the handle stays under the cursor.
@@ -402,22 +1589,23 @@ pub fn begin_drag(
     let mut plane_axis1 = GizmoAxis::X;
     let mut plane_axis2 = GizmoAxis::Y;
 
-    let hit_point = ray_plane_intersection(&ray, plane_origin, plane_normal).unwrap_or(origin);
+    let hit_point = ray_plane_intersection(ray, plane_origin, plane_normal).unwrap_or(origin);
     let v = hit_point - origin;
 
     let start_t = match op {
-        GizmoOperation::TranslateAxis | GizmoOperation::ScaleAxis => v.dot(axis_dir),
-        GizmoOperation::Rotate => {
-            // Angle around axis.
-            let (t1, t2) = axis_basis(axis_dir);
-            let proj = v.normalize_or_zero();
-            let x = proj.dot(t1);
-            let y = proj.dot(t2);
-            y.atan2(x)
+        GizmoOperation::TranslateAxis | GizmoOperation::ScaleAxis => {
+            axis_drag_parameter(axis_dir, plane_normal, v)
         }
-        GizmoOperation::TranslatePlane => 0.0,
+        GizmoOperation::Rotate | GizmoOperation::RotateView => {
+            ring_angle(axis_dir, plane_normal, screen_rotation, v)
+        }
+        GizmoOperation::TranslatePlane
+        | GizmoOperation::TranslateView
+        | GizmoOperation::RotateTrackball
+        | GizmoOperation::Endpoint { .. }
+        | GizmoOperation::OriginDot => 0.0,
         GizmoOperation::ScaleUniform => {
-            // Distance along camera forward.
+            // Distance from the origin, proportional to screen distance.
             v.length()
         }
     };
@@ -427,18 +1615,32 @@ pub fn begin_drag(
             let (a1, a2) = plane_axes(axis);
             plane_axis1 = a1;
             plane_axis2 = a2;
-            plane_dir1 = frame.axis_dir(a1, AxisKind::Translate).normalize_or_zero();
-            plane_dir2 = frame.axis_dir(a2, AxisKind::Translate).normalize_or_zero();
+            plane_dir1 = frame.plane_dir(a1);
+            plane_dir2 = frame.plane_dir(a2);
 
             let n = plane_normal;
             v - n * v.dot(n)
         }
-        GizmoOperation::Rotate => v,
+        GizmoOperation::TranslateView => {
+            // Snap along the camera's right and up directions, with the X
+            // and Y increments.
+            (plane_dir1, plane_dir2) = axis_basis(view_dir);
+            v
+        }
+        // Where on the endpoint sphere the cursor grabbed it.
+        GizmoOperation::Rotate
+        | GizmoOperation::RotateView
+        | GizmoOperation::RotateTrackball
+        | GizmoOperation::ScaleUniform
+        | GizmoOperation::Endpoint { .. } => v,
         _ => Vec3::ZERO,
     };
 
-    state.drag = Some(TransformGizmoDrag {
-        target: entity,
+    TransformGizmoDrag {
+        target,
+        channel: GizmoEditChannel::Transform,
+        camera: None,
+        space: None,
         op,
         axis,
         origin,
@@ -449,180 +1651,3929 @@ pub fn begin_drag(
         plane_dir2,
         plane_axis1,
         plane_axis2,
+        plane_signs: frame.plane_signs(),
         start_translation: global.translation(),
         start_rotation: global.rotation(),
         start_scale: global.to_scale_rotation_translation().0,
-        start_local_translation: local_transform.translation,
-        start_local_scale: local_transform.scale,
+        start_local_translation: local.translation,
+        start_local_rotation: local.rotation,
+        start_local_scale: local.scale,
+        initial_transform: *local,
         start_t,
         start_vector,
+        screen_rotation,
+        rotation_angle: 0.0,
+        trackball_radius: frame.size * TransformGizmoStyle::default().axis_length,
+        trackball_sensitivity: 1.0,
+        pivot: None,
+        origin_delta: Vec3::ZERO,
+        pivot_delta: Vec3::ZERO,
+        history: DragHistory::default(),
+        start_parent: None,
+        last_cursor_offset: v,
+        precise_cursor_offset: v,
+        blocked: false,
+        limit_hit: None,
+        endpoints: None,
+        fixed_endpoint: Vec3::ZERO,
+        last_transform: *local,
+        external: false,
+        paused: false,
+        pending_from: None,
+        aligned: None,
+    }
+}
+
+/// Plane the cursor ray is intersected with while dragging `op`, given its
+/// `axis_dir` and the direction toward the camera.
+fn interaction_plane_normal(op: GizmoOperation, axis_dir: Vec3, view_dir: Vec3) -> Vec3 {
+    match op {
+        // An edge-on ring is turned on a plane facing the camera instead.
+        GizmoOperation::Rotate if rotates_on_screen(op, axis_dir, view_dir) => view_dir,
+        GizmoOperation::Rotate | GizmoOperation::RotateView => axis_dir,
+        GizmoOperation::TranslateAxis | GizmoOperation::ScaleAxis => {
+            // An axis pointing nearly at the camera is dragged on a plane
+            // facing the camera instead, see `axis_drag_parameter`.
+            if axis_dir.dot(view_dir).abs() > GRAZING_AXIS_DEGREES.to_radians().cos() {
+                return view_dir;
+            }
+            // Plane that is perpendicular to both axis and camera view.
+            let n = axis_dir.cross(view_dir).cross(axis_dir).normalize_or_zero();
+            if n.length_squared() < EPSILON {
+                axis_dir
+            } else {
+                n
+            }
+        }
+        GizmoOperation::TranslatePlane | GizmoOperation::Endpoint { .. } => {
+            // Movement constrained to a fixed plane: use the plane normal directly.
+            axis_dir
+        }
+        // The cursor rolls the trackball from the plane facing the camera.
+        GizmoOperation::RotateTrackball => view_dir,
+        GizmoOperation::ScaleUniform
+        | GizmoOperation::TranslateView
+        | GizmoOperation::OriginDot => {
+            // A plane parallel to the screen through the origin projects
+            // onto the screen at a uniform scale, so the cursor's distance
+            // from the origin on it is proportional to its pixel distance
+            // from the origin on screen, in every direction.
+            view_dir
+        }
+    }
+}
+
+/// Whether a drag of `op` around `axis_dir` measures the cursor's angle
+/// around the origin on screen, because its ring is seen nearly edge-on from
+/// `view_dir` (toward the camera). See [`EDGE_ON_RING_DEGREES`].
+fn rotates_on_screen(op: GizmoOperation, axis_dir: Vec3, view_dir: Vec3) -> bool {
+    op == GizmoOperation::Rotate
+        && axis_dir.dot(view_dir).abs() < EDGE_ON_RING_DEGREES.to_radians().cos()
+}
+
+/// Angle of the cursor offset `v` around a rotation drag's axis.
+///
+/// With `screen_rotation` the angle is measured around the camera-facing
+/// `plane_normal` instead, in the direction that matches the axis:
+/// counterclockwise on screen turns counterclockwise around an axis tilted
+/// toward the camera.
+fn ring_angle(axis_dir: Vec3, plane_normal: Vec3, screen_rotation: bool, v: Vec3) -> f32 {
+    let normal = if !screen_rotation {
+        axis_dir
+    } else if axis_dir.dot(plane_normal) < 0.0 {
+        -plane_normal
+    } else {
+        plane_normal
+    };
+    let (t1, t2) = axis_basis(normal);
+    let proj = v.normalize_or_zero();
+    proj.dot(t2).atan2(proj.dot(t1))
+}
+
+/// `angle` wrapped into `[-PI, PI)`.
+#[cfg(feature = "rotate")]
+fn wrap_angle(angle: f32) -> f32 {
+    (angle + std::f32::consts::PI).rem_euclid(std::f32::consts::TAU) - std::f32::consts::PI
+}
+
+/// Position of the cursor offset `v` along a dragged axis with unit
+/// direction `axis_dir`, on the drag's interaction plane.
+///
+/// On a plane through the axis this is the plain projection onto it. On the
+/// camera-facing plane of an axis that points nearly at the camera, it is the
+/// cursor's motion along the axis's on-screen direction, divided by how
+/// foreshortened the axis is, but never more than an axis at
+/// [`GRAZING_AXIS_DEGREES`] would be, so tiny cursor motion cannot throw the
+/// target far away.
+fn axis_drag_parameter(axis_dir: Vec3, plane_normal: Vec3, v: Vec3) -> f32 {
+    let toward_camera = axis_dir.dot(plane_normal);
+    if toward_camera.abs() < 1e-3 {
+        return v.dot(axis_dir);
+    }
+    let on_screen = axis_dir - plane_normal * toward_camera;
+    let min_length = GRAZING_AXIS_DEGREES.to_radians().sin();
+    v.dot(on_screen.normalize_or_zero()) / on_screen.length().max(min_length)
+}
+
+/// What [`apply_drag_keybinds`] restores on the dragged target when a
+/// keybind cancels or re-constrains the drag.
+type RestoredTarget = (
+    Option<&'static mut Transform>,
+    Option<&'static ChildOf>,
+    Option<&'static mut GizmoChannelValue>,
+    Option<&'static mut GizmoTargetTransform>,
+);
+
+/// Apply [`TransformGizmoKeybinds`] to the active drag: cancel it, or
+/// re-constrain it to another axis or plane.
+#[allow(clippy::too_many_arguments)]
+pub fn apply_drag_keybinds(
+    keys: Res<ButtonInput<KeyCode>>,
+    keybinds: Res<TransformGizmoKeybinds>,
+    config: Res<TransformGizmoConfig>,
+    mut state: ResMut<TransformGizmoState>,
+    camera_selection: Res<GizmoCameraSelection>,
+    cameras: Query<(&Camera, &GlobalTransform), With<TransformGizmoCamera>>,
+    pointer: Res<GizmoPointer>,
+    mut targets: Query<RestoredTarget, With<TransformGizmoTarget>>,
+    global_transforms: Query<&GlobalTransform>,
+    mut dragging: MessageWriter<GizmoDragging>,
+    mut ended: MessageWriter<GizmoDragEnded>,
+    mut requests: MessageWriter<GizmoTransformRequest>,
+) {
+    let space = state.space;
+    let Some(drag) = state.drag.as_mut() else {
+        return;
+    };
+    let cancel = keybinds.cancel.is_some_and(|key| keys.just_pressed(key));
+    let plane = keybinds
+        .plane_modifiers
+        .iter()
+        .any(|key| keys.pressed(*key));
+    let constraint = [
+        (keybinds.constrain_x, GizmoAxis::X),
+        (keybinds.constrain_y, GizmoAxis::Y),
+        (keybinds.constrain_z, GizmoAxis::Z),
+    ]
+    .into_iter()
+    .find(|(key, _)| key.is_some_and(|key| keys.just_pressed(key)))
+    .and_then(|(_, axis)| Some((constrained_operation(drag.op, plane)?, axis)))
+    .filter(|&(op, axis)| (op, axis) != (drag.op, drag.axis));
+    if !cancel && constraint.is_none() {
+        return;
+    }
+
+    // Targets that are gone or switched channel are ended by `drag_gizmo`.
+    let Ok((target_transform, parent, channel_value, smoothed)) = targets.get_mut(drag.target)
+    else {
+        return;
+    };
+    let mut channel_dirty = None;
+    let mut external_transform = drag.last_transform;
+    let (transform, parent_global): (&mut Transform, _) = match (channel_value, target_transform) {
+        (Some(value), _) if drag.channel == GizmoEditChannel::Secondary => {
+            let value = value.into_inner();
+            channel_dirty = Some(&mut value.dirty);
+            (&mut value.local, global_transforms.get(drag.target).ok())
+        }
+        _ if drag.external => (&mut external_transform, None),
+        (_, Some(target_transform)) => (
+            smoothed_or(&config, smoothed, target_transform),
+            parent.and_then(|parent| global_transforms.get(parent.parent()).ok()),
+        ),
+        (_, None) => return,
+    };
+
+    if cancel {
+        cancel_drag(
+            drag,
+            transform,
+            channel_dirty,
+            &mut dragging,
+            &mut ended,
+            &mut requests,
+        );
+        state.drag = None;
+        return;
+    }
+
+    let Some((op, axis)) = constraint else {
+        return;
+    };
+    let Some((camera, camera_transform)) = camera_selection
+        .camera()
+        .and_then(|camera| cameras.get(camera).ok())
+    else {
+        return;
+    };
+    let Some(ray) = drag_pointer_ray(&camera_selection, camera, camera_transform, &pointer) else {
+        return;
+    };
+    // Same drag space as `drag_gizmo`, so its next frame continues smoothly.
+    let start_parent = drag
+        .start_parent
+        .filter(|_| config.drag_follows_external_motion);
+    let (ray, parent_global) = match (start_parent.as_ref(), parent_global) {
+        (Some(start), Some(now)) => (carry_ray(ray, now, start), Some(start)),
+        _ => (ray, parent_global),
+    };
+    reconstrain_drag(
+        drag,
+        op,
+        axis,
+        drag.space.unwrap_or(space),
+        camera_transform,
+        &ray,
+        transform,
+        parent_global,
+    );
+}
+
+/// Put `transform` back to how it was when `drag` started and write the
+/// cancelled [`GizmoDragEnded`], unless the drag never left
+/// [pending](TransformGizmoDrag::is_pending). The caller clears the drag.
+fn cancel_drag(
+    drag: &TransformGizmoDrag,
+    transform: &mut Transform,
+    channel_dirty: Option<&mut bool>,
+    dragging: &mut MessageWriter<GizmoDragging>,
+    ended: &mut MessageWriter<GizmoDragEnded>,
+    requests: &mut MessageWriter<GizmoTransformRequest>,
+) {
+    let initial = drag.initial_transform;
+    if *transform != initial {
+        dragging.write(GizmoDragging {
+            origin_delta: Vec3::ZERO,
+            pivot_delta: Vec3::ZERO,
+            ..drag_delta(drag, transform, &initial)
+        });
+        *transform = initial;
+        if let Some(dirty) = channel_dirty {
+            *dirty = true;
+        }
+        if drag.external {
+            requests.write(GizmoTransformRequest {
+                target: drag.target,
+                transform: initial,
+            });
+        }
+    }
+    if !drag.is_pending() {
+        ended.write(GizmoDragEnded {
+            origin_delta: Vec3::ZERO,
+            pivot_delta: Vec3::ZERO,
+            cancelled: true,
+            ..drag_ended(drag, initial)
+        });
+    }
+}
+
+/// The transform a drag of a target's own `Transform` edits: the goal of its
+/// [`GizmoTargetTransform`] while smoothing is on, else the `Transform`
+/// itself.
+fn smoothed_or<'a>(
+    config: &TransformGizmoConfig,
+    smoothed: Option<Mut<'a, GizmoTargetTransform>>,
+    transform: Mut<'a, Transform>,
+) -> &'a mut Transform {
+    match smoothed.filter(|_| config.smoothing.is_some()) {
+        Some(smoothed) => &mut smoothed.into_inner().goal,
+        None => transform.into_inner(),
+    }
+}
+
+/// The operation an axis key switches a drag of `op` to, if it has one in
+/// this build.
+fn constrained_operation(op: GizmoOperation, plane: bool) -> Option<GizmoOperation> {
+    let constrained = if op.is_translate() && plane {
+        GizmoOperation::TranslatePlane
+    } else if op.is_translate() {
+        GizmoOperation::TranslateAxis
+    } else if op.is_rotate() {
+        GizmoOperation::Rotate
+    } else if op.is_scale() {
+        GizmoOperation::ScaleAxis
+    } else {
+        return None;
+    };
+    operation_compiled(constrained).then_some(constrained)
+}
+
+/// Switch `drag` to `op` on `axis`, starting over from `transform` and the
+/// cursor's current position on the new interaction plane so the target
+/// does not jump.
+///
+/// The visual pivot moves along with translation so far, and the origin and
+/// pivot deltas restart from zero.
+#[allow(clippy::too_many_arguments)]
+fn reconstrain_drag(
+    drag: &mut TransformGizmoDrag,
+    op: GizmoOperation,
+    axis: GizmoAxis,
+    space: TransformGizmoSpace,
+    camera_transform: &GlobalTransform,
+    ray: &Ray3d,
+    transform: &Transform,
+    parent_global: Option<&GlobalTransform>,
+) {
+    let global = parent_global.map_or(GlobalTransform::from(*transform), |parent| {
+        parent.mul_transform(*transform)
     });
+    let frame = GizmoFrame::from_context(&global, space, parent_global, Some(camera_transform))
+        .with_plane_signs(drag.plane_signs);
+    let view_dir = -*camera_transform.forward();
+    let pivot = drag.pivot.map(|pivot| pivot + drag.pivot_delta);
+    let origin = pivot.unwrap_or(frame.origin);
+    let kind = match op {
+        GizmoOperation::Rotate => AxisKind::Rotate,
+        GizmoOperation::ScaleAxis => AxisKind::Scale,
+        _ => AxisKind::Translate,
+    };
+    let axis_dir = match op {
+        GizmoOperation::TranslatePlane => frame.plane_normal(axis),
+        _ => frame.axis_dir(axis, kind),
+    };
+    let plane_normal = interaction_plane_normal(op, axis_dir, view_dir);
+
+    drag.op = op;
+    drag.screen_rotation = rotates_on_screen(op, axis_dir, view_dir);
+    drag.axis = axis;
+    drag.origin = origin;
+    drag.axis_dir = axis_dir;
+    drag.plane_normal = plane_normal;
+    drag.plane_origin = origin;
+    drag.pivot = pivot;
+    drag.origin_delta = Vec3::ZERO;
+    drag.pivot_delta = Vec3::ZERO;
+    if op == GizmoOperation::TranslatePlane {
+        let (a1, a2) = plane_axes(axis);
+        drag.plane_axis1 = a1;
+        drag.plane_axis2 = a2;
+        drag.plane_dir1 = frame.plane_dir(a1);
+        drag.plane_dir2 = frame.plane_dir(a2);
+    }
+
+    let hit_point = ray_plane_intersection(ray, origin, plane_normal).unwrap_or(origin);
+    rebase_drag(drag, transform, parent_global, hit_point - origin);
+}
+
+/// The messages [`drag_gizmo`] writes about the drag.
+#[derive(SystemParam)]
+pub struct DragMessages<'w> {
+    started: MessageWriter<'w, GizmoDragStarted>,
+    dragging: MessageWriter<'w, GizmoDragging>,
+    ended: MessageWriter<'w, GizmoDragEnded>,
+    requests: MessageWriter<'w, GizmoTransformRequest>,
+    #[cfg(feature = "translate")]
+    aligned: MessageWriter<'w, GizmoAlignSnapped>,
 }
 
+/// What [`drag_gizmo`] reads and writes on the dragged target.
+type DragTarget = (
+    Option<&'static mut Transform>,
+    Option<&'static ChildOf>,
+    Option<&'static GizmoEditChannel>,
+    Option<&'static mut GizmoChannelValue>,
+    Option<&'static GizmoBlockedTranslation>,
+    Has<GizmoExternalDriver>,
+    Option<&'static mut GizmoTargetTransform>,
+    Option<&'static GizmoConstraints>,
+    Option<&'static GizmoLimits>,
+);
+
 /// Update the drag operation while the mouse is held down.
+#[allow(clippy::too_many_arguments)]
 pub fn drag_gizmo(
     buttons: Res<ButtonInput<MouseButton>>,
+    keys: Res<ButtonInput<KeyCode>>,
+    config: Res<TransformGizmoConfig>,
     mut state: ResMut<TransformGizmoState>,
     snap: Res<TransformGizmoSnap>,
+    camera_selection: Res<GizmoCameraSelection>,
     cameras: Query<(&Camera, &GlobalTransform), With<TransformGizmoCamera>>,
-    windows: Query<&Window, With<PrimaryWindow>>,
-    mut targets: Query<(&mut Transform, Option<&ChildOf>), With<TransformGizmoTarget>>,
+    pointer: Res<GizmoPointer>,
+    windows: Query<&Window>,
+    sweep: Option<Res<GizmoSweepProvider>>,
+    mut targets: Query<DragTarget, With<TransformGizmoTarget>>,
     global_transforms: Query<&GlobalTransform>,
+    #[cfg(feature = "translate")] mut surfaces: SurfaceRaycast,
+    #[cfg(feature = "translate")] align: AlignCandidates,
+    messages: DragMessages,
 ) {
+    let DragMessages {
+        mut started,
+        mut dragging,
+        mut ended,
+        mut requests,
+        #[cfg(feature = "translate")]
+        mut aligned,
+    } = messages;
+    let space = state.space;
     let Some(drag) = state.drag.as_mut() else {
         return;
     };
 
-    if !buttons.pressed(MouseButton::Left) {
+    if !buttons.pressed(config.drag_input.button) {
         return;
     }
 
-    let Some((camera, camera_transform)) = cameras.iter().next() else {
+    let Some((camera, camera_transform)) = camera_selection
+        .camera()
+        .and_then(|camera| cameras.get(camera).ok())
+    else {
         return;
     };
-    let Some(window) = windows.iter().next() else {
+    let ray = drag_pointer_ray(&camera_selection, camera, camera_transform, &pointer);
+    let focused = camera_selection
+        .window()
+        .and_then(|window| windows.get(window).ok())
+        .is_none_or(|window| window.focused);
+
+    // Never carry a drag across a channel switch, a change of driver, or
+    // past the target.
+    let Some((
+        target_transform,
+        parent,
+        _,
+        channel_value,
+        blocking,
+        _,
+        smoothed,
+        constraints,
+        limits,
+    )) = targets.get_mut(drag.target).ok().filter(
+        |(transform, _, channel, value, _, external, ..)| {
+            let secondary = drag.channel == GizmoEditChannel::Secondary && value.is_some();
+            channel.copied().unwrap_or_default() == drag.channel
+                && drag.external == (*external && !secondary)
+                && (drag.external || secondary || transform.is_some())
+        },
+    )
+    else {
+        if !drag.is_pending() {
+            ended.write(drag_ended(drag, drag.last_transform));
+        }
+        state.drag = None;
         return;
     };
-    let Some(cursor_pos) = window.cursor_position() else {
+
+    // Edit the target's `Transform`, its secondary channel value, which is
+    // relative to the target's own global transform, or for an externally
+    // driven target a world-space copy that is published instead.
+    let mut channel_dirty = None;
+    let mut external_transform = drag.last_transform;
+    let (transform, parent_global): (&mut Transform, _) = match (channel_value, target_transform) {
+        (Some(value), _) if drag.channel == GizmoEditChannel::Secondary => {
+            let value = value.into_inner();
+            channel_dirty = Some(&mut value.dirty);
+            (&mut value.local, global_transforms.get(drag.target).ok())
+        }
+        _ if drag.external => (&mut external_transform, None),
+        (_, Some(target_transform)) => (
+            smoothed_or(&config, smoothed, target_transform),
+            parent.and_then(|parent| global_transforms.get(parent.parent()).ok()),
+        ),
+        (_, None) => return,
+    };
+
+    // The cursor left the camera's window, or the window lost focus.
+    let Some(ray) = ray.filter(|_| focused) else {
+        match config.on_focus_loss {
+            DragInterruptPolicy::Cancel => {
+                cancel_drag(
+                    drag,
+                    transform,
+                    channel_dirty,
+                    &mut dragging,
+                    &mut ended,
+                    &mut requests,
+                );
+                state.drag = None;
+            }
+            DragInterruptPolicy::Pause => drag.paused = true,
+            DragInterruptPolicy::Commit => {
+                if !drag.is_pending() {
+                    ended.write(drag_ended(drag, *transform));
+                }
+                state.drag = None;
+            }
+        }
         return;
     };
-    let Ok(ray) = camera.viewport_to_world(camera_transform, cursor_pos) else {
+
+    // Sweeps and surface casts run in world space, through the parent as it
+    // is now.
+    let sweep_parent = parent_global;
+    #[cfg(feature = "translate")]
+    let world_ray = ray;
+
+    // Optionally re-express the cursor ray relative to the parent as it was
+    // at drag start, so parent motion carries the drag along with it.
+    let start_parent = drag
+        .start_parent
+        .filter(|_| config.drag_follows_external_motion);
+    let view_ray = Ray3d::new(camera_transform.translation(), camera_transform.forward());
+    let (ray, view_ray, parent_global) = match (start_parent.as_ref(), parent_global) {
+        (Some(start), Some(now)) => (
+            carry_ray(ray, now, start),
+            carry_ray(view_ray, now, start),
+            Some(start),
+        ),
+        _ => (ray, view_ray, parent_global),
+    };
+
+    // Something other than the drag changed the target since last frame.
+    let mut rebased = false;
+    if *transform != drag.last_transform {
+        match config.external_change_policy {
+            ExternalChangePolicy::GizmoWins => {}
+            ExternalChangePolicy::Rebase => {
+                let local = drag.space.unwrap_or(space) == TransformGizmoSpace::Local;
+                follow_external_change(drag, transform, parent_global, local);
+                rebased = true;
+            }
+            ExternalChangePolicy::Abort => {
+                if !drag.is_pending() {
+                    ended.write(GizmoDragEnded {
+                        cancelled: true,
+                        ..drag_ended(drag, *transform)
+                    });
+                }
+                state.drag = None;
+                return;
+            }
+        }
+    }
+
+    // The camera may have moved since last frame, for example orbiting
+    // while the drag is held.
+    reaim_view_plane(drag, -*view_ray.direction, &ray);
+    let v = drag_cursor_offset(drag, &ray);
+    if rebased {
+        // The cursor's current offset becomes the new zero, so the drag
+        // carries on from where the change left the target.
+        rebase_drag(drag, transform, parent_global, v);
+        drag.last_transform = *transform;
+        return;
+    }
+
+    // Resume a paused drag from where the target was left, wherever the
+    // cursor came back.
+    if drag.paused {
+        drag.paused = false;
+        rebase_drag(drag, transform, parent_global, v);
+        return;
+    }
+
+    // A pressed handle starts dragging once the cursor has moved far enough,
+    // from where the cursor is then, so the target does not jump by the
+    // threshold.
+    if let Some(pressed) = drag.pending_from {
+        let moved = pointer
+            .position
+            .is_some_and(|position| position.distance(pressed) > config.drag_threshold_pixels);
+        if moved {
+            drag.pending_from = None;
+            rebase_drag(drag, transform, parent_global, v);
+            started.write(GizmoDragStarted {
+                handle: drag.handle(),
+            });
+        }
+        return;
+    }
+
+    let scrub = &config.drag_history;
+    if scrub.enabled {
+        let stepped = if keys.just_pressed(scrub.step_back_key) {
+            drag.history.step_back()
+        } else if keys.just_pressed(scrub.step_forward_key) {
+            drag.history.step_forward()
+        } else {
+            None
+        };
+        if let Some(rewound) = stepped {
+            dragging.write(drag_delta(drag, transform, &rewound));
+            *transform = rewound;
+            drag.last_transform = rewound;
+            if let Some(dirty) = channel_dirty {
+                *dirty = true;
+            }
+            if drag.external {
+                requests.write(GizmoTransformRequest {
+                    target: drag.target,
+                    transform: rewound,
+                });
+            }
+            rebase_drag(drag, &rewound, parent_global, v);
+            return;
+        }
+    }
+
+    // The entity an align drag snaps onto this frame. Letting go carries on
+    // dragging from where the snap left the target.
+    #[cfg(feature = "translate")]
+    let align_to = snap
+        .align
+        .as_ref()
+        .filter(|settings| {
+            keys.pressed(settings.modifier)
+                && matches!(
+                    drag.op,
+                    GizmoOperation::TranslateAxis
+                        | GizmoOperation::TranslatePlane
+                        | GizmoOperation::TranslateView
+                )
+                && drag.channel == GizmoEditChannel::Transform
+        })
+        .and_then(|settings| {
+            let (entity, global) = align.nearest(
+                camera,
+                camera_transform,
+                pointer.position?,
+                settings.threshold_pixels,
+                drag.target,
+            )?;
+            let match_rotation = settings
+                .rotation_modifier
+                .is_some_and(|key| keys.pressed(key));
+            Some((entity, global, match_rotation))
+        });
+    #[cfg(feature = "translate")]
+    if drag.aligned.is_some() && align_to.is_none() {
+        drag.aligned = None;
+        aligned.write(GizmoAlignSnapped {
+            handle: drag.handle(),
+            snapped_to: None,
+        });
+        rebase_drag(drag, transform, parent_global, v);
         return;
+    }
+
+    let v = match config.pressure_precision {
+        Some(curve) => {
+            let factor = pointer
+                .pressure
+                .map_or(1.0, |pressure| curve.factor(pressure));
+            accumulate_precision(drag, v, factor)
+        }
+        None => {
+            drag.last_cursor_offset = v;
+            drag.precise_cursor_offset = v;
+            v
+        }
     };
 
-    let Ok((mut transform, parent)) = targets.get_mut(drag.target) else {
+    let before = *transform;
+    let snap = snap.resolve(&keys);
+    apply_drag_motion(drag, v, &snap, transform, parent_global);
+    #[cfg(feature = "translate")]
+    if let Some(settings) = snap.surface.as_ref().filter(|_| {
+        matches!(
+            drag.op,
+            GizmoOperation::TranslatePlane | GizmoOperation::TranslateView
+        ) && drag.channel == GizmoEditChannel::Transform
+    }) {
+        let hit = surfaces.cast(world_ray, settings, drag.target);
+        place_on_surface(drag, settings, hit, transform, sweep_parent);
+    }
+    #[cfg(feature = "translate")]
+    if let Some((entity, global, match_rotation)) = align_to {
+        if align_onto(
+            drag,
+            entity,
+            &global,
+            match_rotation,
+            transform,
+            sweep_parent,
+        ) {
+            aligned.write(GizmoAlignSnapped {
+                handle: drag.handle(),
+                snapped_to: Some(entity),
+            });
+        }
+    }
+    drag.blocked = false;
+    let blocked_translation = blocking.map_or(config.blocked_translation, |blocking| blocking.0);
+    if let Some(sweep) = sweep
+        .as_deref()
+        .filter(|_| blocked_translation && drag.channel == GizmoEditChannel::Transform)
+    {
+        block_translation(
+            drag,
+            sweep,
+            config.slide_on_contact,
+            before.translation,
+            transform,
+            sweep_parent,
+            parent_global,
+        );
+    }
+    // After snapping, so a snapped value past a limit is pinned to it.
+    drag.limit_hit = limits
+        .filter(|_| drag.channel == GizmoEditChannel::Transform)
+        .and_then(|limits| limits.clamp(transform, sweep_parent));
+    // Last, so nothing above can carry the target off its plane.
+    if let Some(constraints) = constraints.filter(|_| drag.channel == GizmoEditChannel::Transform) {
+        transform.translation =
+            constraints.lock_translation(before.translation, transform.translation);
+    }
+    if *transform != before {
+        if let Some(dirty) = channel_dirty {
+            *dirty = true;
+        }
+        if scrub.enabled {
+            drag.history.record(*transform);
+        }
+        drag.last_transform = *transform;
+        dragging.write(drag_delta(drag, &before, transform));
+        if drag.external {
+            requests.write(GizmoTransformRequest {
+                target: drag.target,
+                transform: *transform,
+            });
+        }
+    }
+}
+
+/// The [`GizmoDragging`] message for a drag frame that moved the edited
+/// transform from `before` to `after`.
+fn drag_delta(drag: &TransformGizmoDrag, before: &Transform, after: &Transform) -> GizmoDragging {
+    GizmoDragging {
+        handle: drag.handle(),
+        delta_translation: after.translation - before.translation,
+        delta_rotation: (after.rotation * before.rotation.inverse()).normalize(),
+        delta_scale: scale_ratio(after.scale, before.scale),
+        origin_delta: drag.origin_delta,
+        pivot_delta: drag.pivot_delta,
+    }
+}
+
+/// The [`GizmoDragEnded`] message for `drag` ending at `final_transform`.
+pub(crate) fn drag_ended(drag: &TransformGizmoDrag, final_transform: Transform) -> GizmoDragEnded {
+    GizmoDragEnded {
+        handle: drag.handle(),
+        initial_transform: drag.initial_transform,
+        final_transform,
+        origin_delta: drag.origin_delta,
+        pivot_delta: drag.pivot_delta,
+        cancelled: false,
+    }
+}
+
+/// Map `ray` from a space attached to `from` into the same space attached to
+/// `to`.
+fn carry_ray(ray: Ray3d, from: &GlobalTransform, to: &GlobalTransform) -> Ray3d {
+    let carry = to.affine() * from.affine().inverse();
+    Ray3d {
+        origin: carry.transform_point3(ray.origin),
+        direction: Dir3::new(carry.transform_vector3(*ray.direction)).unwrap_or(ray.direction),
+    }
+}
+
+/// Turn the interaction plane of an axis or scale drag to face the camera
+/// again, now looking along `-view_dir`, without moving the target.
+///
+/// Only these drags pick their plane by the view; plane, view, rotation and
+/// endpoint drags stay on the plane they started on. The drag parameter the
+/// cursor `ray` gives on the new plane is rebased onto the one it gives on
+/// the old plane, so the target carries on from where it is.
+fn reaim_view_plane(drag: &mut TransformGizmoDrag, view_dir: Vec3, ray: &Ray3d) {
+    if !matches!(
+        drag.op,
+        GizmoOperation::TranslateAxis | GizmoOperation::ScaleAxis | GizmoOperation::ScaleUniform
+    ) {
+        return;
+    }
+    let plane_normal = interaction_plane_normal(drag.op, drag.axis_dir, view_dir);
+    if plane_normal.dot(drag.plane_normal) >= REAIM_PLANE_DEGREES.to_radians().cos() {
         return;
+    }
+
+    // The offset the drag would run on this frame, under either plane.
+    let parameter = |drag: &TransformGizmoDrag, v: Vec3| match drag.op {
+        GizmoOperation::ScaleUniform => v.length(),
+        _ => axis_drag_parameter(drag.axis_dir, drag.plane_normal, v),
     };
-    let parent_global = parent.and_then(|parent| global_transforms.get(parent.parent()).ok());
+    let old_v = drag_cursor_offset(drag, ray);
+    let old_t = parameter(
+        drag,
+        drag.precise_cursor_offset + old_v - drag.last_cursor_offset,
+    );
+    drag.plane_normal = plane_normal;
+    let new_v = drag_cursor_offset(drag, ray);
+    drag.last_cursor_offset += new_v - old_v;
+    drag.precise_cursor_offset += new_v - old_v;
+    let new_t = parameter(
+        drag,
+        drag.precise_cursor_offset + new_v - drag.last_cursor_offset,
+    );
+
+    // Translation follows the change of the parameter, scale its ratio.
+    match drag.op {
+        GizmoOperation::TranslateAxis => drag.start_t += new_t - old_t,
+        _ if old_t.abs() > EPSILON && new_t * old_t > 0.0 => drag.start_t *= new_t / old_t,
+        _ => drag.start_t += new_t - old_t,
+    }
+}
+
+/// Vector from the drag origin to where `ray` meets the drag's interaction
+/// plane.
+///
+/// While the ray misses the plane, for example above the horizon of a ground
+/// plane, the cursor stays where it last met it, so the target holds still
+/// instead of jumping by the offset it was grabbed at.
+fn drag_cursor_offset(drag: &TransformGizmoDrag, ray: &Ray3d) -> Vec3 {
+    ray_plane_intersection(ray, drag.plane_origin, drag.plane_normal)
+        .map_or(drag.last_cursor_offset, |hit_point| hit_point - drag.origin)
+}
+
+/// Move `transform` for one frame of `drag`, with the cursor now along
+/// `ray`.
+///
+/// `transform` is the edited local transform, in the space of the drag's
+/// `start_parent` if it has one. Snapping uses `snap`'s increments as
+/// given; resolve its keys and [`enabled`](TransformGizmoSnap::enabled) flag
+/// first with [`TransformGizmoSnap::resolve`] if needed. Unlike the
+/// plugin's own drag, this never sweeps for blocking contacts, scales
+/// motion by pen pressure, turns camera-facing planes after a moving camera,
+/// records drag history, or writes messages.
+pub fn apply_drag(
+    drag: &mut TransformGizmoDrag,
+    ray: &Ray3d,
+    snap: &TransformGizmoSnap,
+    transform: &mut Transform,
+) {
+    let v = drag_cursor_offset(drag, ray);
+    drag.last_cursor_offset = v;
+    drag.precise_cursor_offset = v;
+    let parent_global = drag.start_parent;
+    apply_drag_motion(drag, v, snap, transform, parent_global.as_ref());
+    drag.last_transform = *transform;
+}
+
+/// Advance the precision-scaled cursor offset by this frame's cursor motion
+/// scaled by `factor`, returning the offset to drive the drag with.
+fn accumulate_precision(drag: &mut TransformGizmoDrag, v: Vec3, factor: f32) -> Vec3 {
+    drag.precise_cursor_offset += (v - drag.last_cursor_offset) * factor;
+    drag.last_cursor_offset = v;
+    drag.precise_cursor_offset
+}
 
-    let hit_point =
-        ray_plane_intersection(&ray, drag.plane_origin, drag.plane_normal).unwrap_or(drag.origin);
-    let v = hit_point - drag.origin;
+/// Turn the target by the world-space `delta_rot` from where the drag
+/// started, about the visual pivot if there is one.
+///
+/// Under a parent with non-uniform scale the target's world rotation is not
+/// a rotation the parent can carry, so the turn is taken about the
+/// parent-space axis the parent's affine maps onto the world axis instead.
+#[cfg(feature = "rotate")]
+fn rotate_from_start(
+    drag: &mut TransformGizmoDrag,
+    delta_rot: Quat,
+    transform: &mut Transform,
+    parent_global: Option<&GlobalTransform>,
+) {
+    transform.rotation = match parent_global {
+        Some(parent) if has_non_uniform_scale(parent) => {
+            let (axis, angle) = delta_rot.to_axis_angle();
+            let (local_axis, sign) = axis_in_parent(parent, axis);
+            Quat::from_axis_angle(local_axis, angle * sign) * drag.start_local_rotation
+        }
+        Some(parent) => parent.rotation().inverse() * delta_rot * drag.start_rotation,
+        None => delta_rot * drag.start_rotation,
+    };
+    // The pivot stays put and the origin swings around it, or stays put too
+    // when the pivot is the origin.
+    drag.pivot_delta = Vec3::ZERO;
+    drag.origin_delta = Vec3::ZERO;
+    if let Some(pivot) = drag.pivot {
+        let world_translation = rotate_about_pivot(drag.start_translation, pivot, delta_rot);
+        transform.translation = world_point_to_local(parent_global, world_translation);
+        drag.origin_delta = world_translation - drag.start_translation;
+    }
+}
 
+/// Apply one frame of drag motion, where `v` is the vector from the drag
+/// origin to the cursor's hit point on the interaction plane.
+#[cfg(any(feature = "translate", feature = "rotate", feature = "scale"))]
+fn apply_drag_motion(
+    drag: &mut TransformGizmoDrag,
+    v: Vec3,
+    snap: &TransformGizmoSnap,
+    transform: &mut Transform,
+    parent_global: Option<&GlobalTransform>,
+) {
     match drag.op {
+        #[cfg(feature = "translate")]
         GizmoOperation::TranslateAxis => {
-            let t = v.dot(drag.axis_dir);
-            let mut delta = t - drag.start_t;
-            if let Some(step) = snap.translate.get(drag.axis) {
-                if step > 0.0 {
-                    delta = (delta / step).round() * step;
-                }
-            }
+            let t = axis_drag_parameter(drag.axis_dir, drag.plane_normal, v);
+            let delta = snap_offset(
+                snap.mode,
+                drag.start_translation.dot(drag.axis_dir),
+                t - drag.start_t,
+                snap.translate.get(drag.axis),
+            );
             let world_delta = delta * drag.axis_dir;
             transform.translation =
                 drag.start_local_translation + world_vector_to_local(parent_global, world_delta);
+            drag.origin_delta = world_delta;
+            drag.pivot_delta = world_delta;
         }
+        #[cfg(feature = "planes")]
         GizmoOperation::TranslatePlane => {
-            let n = drag.plane_normal;
-            let proj = v - n * v.dot(n);
-            let mut delta = proj - drag.start_vector;
-
-            // Snap along the two plane axes independently.
-            let mut u = delta.dot(drag.plane_dir1);
-            let mut w = delta.dot(drag.plane_dir2);
-            if let Some(step) = snap.translate.get(drag.plane_axis1) {
-                if step > 0.0 {
-                    u = (u / step).round() * step;
-                }
-            }
-            if let Some(step) = snap.translate.get(drag.plane_axis2) {
-                if step > 0.0 {
-                    w = (w / step).round() * step;
-                }
-            }
-            delta = drag.plane_dir1 * u + drag.plane_dir2 * w;
-
-            transform.translation =
-                drag.start_local_translation + world_vector_to_local(parent_global, delta);
+            translate_in_plane(drag, v, snap, transform, parent_global);
+        }
+        #[cfg(feature = "translate")]
+        GizmoOperation::TranslateView => {
+            translate_in_plane(drag, v, snap, transform, parent_global);
         }
+        #[cfg(feature = "scale")]
         GizmoOperation::ScaleAxis => {
-            let t = v.dot(drag.axis_dir);
-            // Guard against division by zero when start_t is near zero
-            let delta = (t - drag.start_t) / drag.start_t.max(MIN_SCALE_DIVISOR);
+            let t = axis_drag_parameter(drag.axis_dir, drag.plane_normal, v);
+            // The ratio of distances along the axis keeps the cube under the
+            // cursor, and turns negative once the cursor crosses the origin.
+            let ratio = scale_drag_ratio(t, drag.start_t);
             let mut scale = drag.start_local_scale;
-            match drag.axis {
-                GizmoAxis::X => scale.x *= snap_scale(scale.x, delta, snap.scale.get(GizmoAxis::X)),
-                GizmoAxis::Y => scale.y *= snap_scale(scale.y, delta, snap.scale.get(GizmoAxis::Y)),
-                GizmoAxis::Z => scale.z *= snap_scale(scale.z, delta, snap.scale.get(GizmoAxis::Z)),
-            }
+            let base = scale[drag.axis.index()];
+            scale[drag.axis.index()] = limit_scale(
+                base,
+                snap_scale(base * ratio, snap.scale.get(drag.axis)),
+                snap,
+            );
             transform.scale = scale;
+            apply_scale_pivot(drag, transform, parent_global);
         }
+        #[cfg(feature = "uniform-scale")]
         GizmoOperation::ScaleUniform => {
+            // Past the origin from where the drag started, the distance
+            // counts as negative when mirroring is allowed.
             let t = v.length();
-            let factor = if drag.start_t.abs() > MIN_SCALE_DIVISOR {
-                t / drag.start_t
+            let t = if snap.allow_negative_scale && v.dot(drag.start_vector) < 0.0 {
+                -t
             } else {
-                1.0
+                t
             };
+            let factor = scale_drag_ratio(t, drag.start_t);
             let base = drag.start_local_scale;
-            let snap_step = snap.scale.get(GizmoAxis::X).unwrap_or(0.0);
-            let snapped_factor = if snap_step > 0.0 {
-                let target = base.x * factor;
-                let snapped = (target / snap_step).round() * snap_step;
-                if base.x.abs() > EPSILON {
-                    snapped / base.x
-                } else {
-                    factor
-                }
+            // Snap the X scale and scale the other axes along with it.
+            let factor = if base.x.abs() > EPSILON {
+                snap_scale(base.x * factor, snap.scale.get(GizmoAxis::X)) / base.x
             } else {
                 factor
             };
-            transform.scale = base * snapped_factor.max(0.001);
-        }
-        GizmoOperation::Rotate => {
-            let (t1, t2) = axis_basis(drag.axis_dir);
-            let proj = v.normalize_or_zero();
-            let x = proj.dot(t1);
-            let y = proj.dot(t2);
-            let angle = y.atan2(x);
-            let mut delta_angle = angle - drag.start_t;
-            if let Some(step) = snap.rotate.get(drag.axis) {
-                if step > 0.0 {
-                    delta_angle = (delta_angle / step).round() * step;
+            let scaled = |base: f32| limit_scale(base, base * factor, snap);
+            transform.scale = Vec3::new(scaled(base.x), scaled(base.y), scaled(base.z));
+            apply_scale_pivot(drag, transform, parent_global);
+        }
+        #[cfg(feature = "rotate")]
+        GizmoOperation::Rotate | GizmoOperation::RotateView => {
+            let angle = ring_angle(drag.axis_dir, drag.plane_normal, drag.screen_rotation, v);
+            // Unwrap across half turns, so a drag can spin any number of
+            // times and snapping keeps counting up.
+            drag.rotation_angle += wrap_angle(angle - drag.start_t - drag.rotation_angle);
+            let delta_angle = snap_offset(
+                snap.mode,
+                twist_angle(drag.start_rotation, drag.axis_dir),
+                drag.rotation_angle,
+                snap.rotate.get(drag.axis),
+            );
+            let delta_rot = Quat::from_axis_angle(drag.axis_dir, delta_angle);
+            rotate_from_start(drag, delta_rot, transform, parent_global);
+        }
+        // Free rotation is not snapped.
+        #[cfg(feature = "rotate")]
+        GizmoOperation::RotateTrackball => {
+            let delta_rot = trackball_rotation(
+                drag.start_vector,
+                v,
+                drag.plane_normal,
+                drag.trackball_radius,
+                drag.trackball_sensitivity,
+            );
+            rotate_from_start(drag, delta_rot, transform, parent_global);
+        }
+        #[cfg(feature = "translate")]
+        GizmoOperation::Endpoint { end } => {
+            let Some(endpoints) = drag.endpoints else {
+                return;
+            };
+            let mut moved = drag.origin + v - drag.start_vector;
+            for (i, axis) in GizmoAxis::ALL.into_iter().enumerate() {
+                if let Some(step) = snap.translate.get(axis).filter(|step| *step > 0.0) {
+                    moved[i] = (moved[i] / step).round() * step;
                 }
             }
-            let delta_rot = Quat::from_axis_angle(drag.axis_dir, delta_angle);
-            let world_rotation = delta_rot * drag.start_rotation;
-            transform.rotation = parent_global.map_or(world_rotation, |parent| {
-                parent.rotation().inverse() * world_rotation
+            solve_endpoint_drag(
+                transform,
+                &endpoints,
+                end,
+                drag.fixed_endpoint,
+                world_point_to_local(parent_global, moved),
+            );
+            let world_translation = parent_global.map_or(transform.translation, |parent| {
+                parent.transform_point(transform.translation)
             });
+            drag.origin_delta = world_translation - drag.start_translation;
+            drag.pivot_delta = drag.origin_delta;
+        }
+        // The origin dot is clicked rather than dragged, see `click_origin_dot`.
+        GizmoOperation::OriginDot => {}
+        #[cfg(not(all(
+            feature = "translate",
+            feature = "rotate",
+            feature = "scale",
+            feature = "planes",
+            feature = "uniform-scale"
+        )))]
+        op => {
+            debug_assert!(false, "dragging {op:?} without its cargo feature enabled");
         }
     }
 }
 
-fn world_vector_to_local(parent: Option<&GlobalTransform>, world_vector: Vec3) -> Vec3 {
-    parent.map_or(world_vector, |parent| {
-        parent.affine().inverse().transform_vector3(world_vector)
-    })
+/// Without any handle compiled in there is nothing to drag.
+#[cfg(not(any(feature = "translate", feature = "rotate", feature = "scale")))]
+fn apply_drag_motion(
+    drag: &mut TransformGizmoDrag,
+    _: Vec3,
+    _: &TransformGizmoSnap,
+    _: &mut Transform,
+    _: Option<&GlobalTransform>,
+) {
+    debug_assert!(
+        drag.op == GizmoOperation::OriginDot,
+        "dragging {:?} without its cargo feature enabled",
+        drag.op
+    );
 }
 
-fn snap_scale(base: f32, delta: f32, step: Option<f32>) -> f32 {
-    let raw = 1.0 + delta;
-    match step {
-        Some(step) if step > 0.0 => {
-            let target = base * raw;
-            let snapped = (target / step).round() * step;
-            if base.abs() > EPSILON {
-                snapped / base
-            } else {
-                raw
-            }
-        }
-        _ => raw,
-    }
-}
+/// Move `transform` by the cursor's motion on the drag's interaction plane,
+/// snapped along `plane_dir1` and `plane_dir2` independently.
+#[cfg(feature = "translate")]
+fn translate_in_plane(
+    drag: &mut TransformGizmoDrag,
+    v: Vec3,
+    snap: &TransformGizmoSnap,
+    transform: &mut Transform,
+    parent_global: Option<&GlobalTransform>,
+) {
+    let n = drag.plane_normal;
+    let proj = v - n * v.dot(n);
+    let mut delta = proj - drag.start_vector;
+
+    // Snap along the two plane axes independently. They are only
+    // perpendicular when the target is not sheared.
+    let (dir1, dir2) = (drag.plane_dir1, drag.plane_dir2);
+    let start = plane_coordinates(drag.start_translation, dir1, dir2);
+    let moved = plane_coordinates(delta, dir1, dir2);
+    let u = snap_offset(
+        snap.mode,
+        start.x,
+        moved.x,
+        snap.translate.get(drag.plane_axis1),
+    );
+    let w = snap_offset(
+        snap.mode,
+        start.y,
+        moved.y,
+        snap.translate.get(drag.plane_axis2),
+    );
+    delta = drag.plane_dir1 * u + drag.plane_dir2 * w;
+
+    transform.translation =
+        drag.start_local_translation + world_vector_to_local(parent_global, delta);
+    drag.origin_delta = delta;
+    drag.pivot_delta = delta;
+}
+
+/// Move a plane or view drag's target onto the surface `hit`, `offset` away
+/// along its normal, or leave the plane result in `transform` when the
+/// cursor missed every surface.
+///
+/// With normal alignment the target's world rotation is its rotation at drag
+/// start turned by the shortest arc from its local Y to the normal, and goes
+/// back to the start rotation off surfaces. `parent_global` is the parent as
+/// it is now.
+#[cfg(feature = "translate")]
+fn place_on_surface(
+    drag: &mut TransformGizmoDrag,
+    settings: &SurfaceSnapSettings,
+    hit: Option<SurfaceHit>,
+    transform: &mut Transform,
+    parent_global: Option<&GlobalTransform>,
+) {
+    let normal = hit.and_then(|hit| hit.normal.try_normalize());
+    if settings.align_to_normal {
+        let world_rotation = normal.map_or(drag.start_rotation, |normal| {
+            Quat::from_rotation_arc(drag.start_rotation * Vec3::Y, normal) * drag.start_rotation
+        });
+        transform.rotation = parent_global.map_or(world_rotation, |parent| {
+            parent.rotation().inverse() * world_rotation
+        });
+    }
+    let Some(hit) = hit else {
+        return;
+    };
+    let world_translation = hit.position + normal.unwrap_or(Vec3::ZERO) * settings.offset;
+    transform.translation = world_point_to_local(parent_global, world_translation);
+    drag.origin_delta = world_translation - drag.start_translation;
+    drag.pivot_delta = drag.origin_delta;
+}
+
+/// Move a translate drag's target onto `entity`, whose world transform is
+/// `onto`, and take its rotation too with `match_rotation`. Without it the
+/// target keeps the rotation it started the drag with. `parent_global` is
+/// the parent as it is now.
+///
+/// Returns whether the target snapped onto another entity than last frame.
+#[cfg(feature = "translate")]
+fn align_onto(
+    drag: &mut TransformGizmoDrag,
+    entity: Entity,
+    onto: &GlobalTransform,
+    match_rotation: bool,
+    transform: &mut Transform,
+    parent_global: Option<&GlobalTransform>,
+) -> bool {
+    let unsnapped = parent_global.map_or(transform.translation, |parent| {
+        parent.transform_point(transform.translation)
+    });
+    let position = onto.translation();
+    transform.translation = world_point_to_local(parent_global, position);
+    transform.rotation = if match_rotation {
+        parent_global.map_or(onto.rotation(), |parent| {
+            parent.rotation().inverse() * onto.rotation()
+        })
+    } else {
+        drag.start_local_rotation
+    };
+    drag.origin_delta = position - drag.start_translation;
+    drag.pivot_delta = drag.origin_delta;
+    let moved_on = drag.aligned.is_none_or(|lock| lock.entity != entity);
+    drag.aligned = Some(GizmoAlignLock {
+        entity,
+        position,
+        unsnapped,
+    });
+    moved_on
+}
+
+/// Clamp this frame's translate drag motion, from `previous` to the
+/// candidate already in `transform` (both parent-local), against the sweep
+/// provider.
+///
+/// `sweep_parent` is the parent as it is now, used to sweep in world space;
+/// `parent_global` is the space the drag math runs in, which differs from it
+/// when the drag follows external motion. Snapping has already been applied
+/// to the candidate, so a blocked target rests exactly at the contact.
+fn block_translation(
+    drag: &mut TransformGizmoDrag,
+    sweep: &GizmoSweepProvider,
+    slide_on_contact: bool,
+    previous: Vec3,
+    transform: &mut Transform,
+    sweep_parent: Option<&GlobalTransform>,
+    parent_global: Option<&GlobalTransform>,
+) {
+    if !drag.op.is_translate() {
+        return;
+    }
+    let to_world = sweep_parent.map_or(Affine3A::IDENTITY, GlobalTransform::affine);
+    let in_plane = matches!(
+        drag.op,
+        GizmoOperation::TranslatePlane | GizmoOperation::TranslateView
+    );
+    let slide_plane = (slide_on_contact && in_plane).then(|| {
+        let carry = match parent_global {
+            Some(frame) => to_world * frame.affine().inverse(),
+            None => to_world,
+        };
+        carry.transform_vector3(drag.plane_normal)
+    });
+
+    let (reached, blocked) = sweep_translation(
+        sweep,
+        drag.target,
+        to_world.transform_point3(previous),
+        to_world.transform_point3(transform.translation),
+        slide_plane,
+    );
+    drag.blocked = blocked;
+    if blocked {
+        transform.translation = to_world.inverse().transform_point3(reached);
+        let local_delta = transform.translation - drag.start_local_translation;
+        drag.origin_delta = parent_global.map_or(local_delta, |parent| {
+            parent.affine().transform_vector3(local_delta)
+        });
+        drag.pivot_delta = drag.origin_delta;
+    }
+}
+
+/// Re-derive the drag baseline so the current cursor maps to `transform`.
+///
+/// Used after stepping through the drag history: the rewound transform
+/// becomes the new starting point and the cursor's current parameter becomes
+/// the new zero, so resuming mouse movement continues smoothly instead of
+/// jumping back to the cursor-implied value.
+fn rebase_drag(
+    drag: &mut TransformGizmoDrag,
+    transform: &Transform,
+    parent_global: Option<&GlobalTransform>,
+    v: Vec3,
+) {
+    let global = parent_global.map_or(GlobalTransform::from(*transform), |parent| {
+        parent.mul_transform(*transform)
+    });
+    let (scale, rotation, translation) = global.to_scale_rotation_translation();
+    drag.start_translation = translation;
+    drag.start_rotation = rotation;
+    drag.start_scale = scale;
+    drag.start_local_translation = transform.translation;
+    drag.start_local_rotation = transform.rotation;
+    drag.start_local_scale = transform.scale;
+    drag.last_cursor_offset = v;
+    drag.precise_cursor_offset = v;
+
+    match drag.op {
+        GizmoOperation::TranslateAxis | GizmoOperation::ScaleAxis => {
+            drag.start_t = axis_drag_parameter(drag.axis_dir, drag.plane_normal, v);
+        }
+        GizmoOperation::TranslatePlane | GizmoOperation::TranslateView => {
+            let n = drag.plane_normal;
+            drag.start_vector = v - n * v.dot(n);
+        }
+        GizmoOperation::Rotate | GizmoOperation::RotateView => {
+            drag.start_t = ring_angle(drag.axis_dir, drag.plane_normal, drag.screen_rotation, v);
+            drag.rotation_angle = 0.0;
+            drag.start_vector = v;
+        }
+        GizmoOperation::RotateTrackball => {
+            drag.start_vector = v;
+        }
+        GizmoOperation::ScaleUniform => {
+            drag.start_t = v.length();
+            drag.start_vector = v;
+        }
+        GizmoOperation::Endpoint { end } => {
+            // Re-anchor the grab so the cursor maps to the rewound end.
+            if let Some(endpoints) = drag.endpoints {
+                let local = transform.transform_point(endpoints.local_position(end));
+                let world = parent_global.map_or(local, |parent| parent.transform_point(local));
+                drag.start_vector = v - (world - drag.origin);
+            }
+        }
+        GizmoOperation::OriginDot => {}
+    }
+}
+
+/// Carry the world-space fields of `drag` from where the drag last left its
+/// target to where `transform` (parent-local) puts it now, so the gizmo's
+/// plane and pivot move with a change made by another system. The axes turn
+/// with the target only when the drag is laid out in its `local` space.
+fn follow_external_change(
+    drag: &mut TransformGizmoDrag,
+    transform: &Transform,
+    parent_global: Option<&GlobalTransform>,
+    local: bool,
+) {
+    let global = |local: &Transform| {
+        let affine = local.compute_affine();
+        parent_global.map_or(affine, |parent| parent.affine() * affine)
+    };
+    let (_, last_rotation, last_translation) =
+        global(&drag.last_transform).to_scale_rotation_translation();
+    let (_, rotation, translation) = global(transform).to_scale_rotation_translation();
+    let turn = if local {
+        rotation * last_rotation.inverse()
+    } else {
+        Quat::IDENTITY
+    };
+    carry_drag(
+        drag,
+        Affine3A::from_rotation_translation(turn, translation - turn * last_translation),
+    );
+}
+
+/// Re-express a drag's parent-local baseline after its target moved from
+/// `old_parent` to `new_parent` (world affines, `None` for the root) mid-drag.
+///
+/// World-space fields are kept, so the cursor keeps driving the same world
+/// motion. When `follows` is set and the drag tracked its old parent's motion,
+/// they are first carried to where that parent is now, since from here on
+/// the drag follows the new parent instead.
+pub(crate) fn rebase_drag_to_parent(
+    drag: &mut TransformGizmoDrag,
+    old_parent: Option<Affine3A>,
+    new_parent: Option<Affine3A>,
+    follows: bool,
+) {
+    if let (true, Some(start), Some(now)) = (follows, drag.start_parent, old_parent) {
+        carry_drag(drag, now * start.affine().inverse());
+    }
+
+    let from_old = old_parent.unwrap_or(Affine3A::IDENTITY);
+    let to_new = new_parent.map_or(Affine3A::IDENTITY, |parent| parent.inverse());
+    let remap = |local: Transform| nearest_transform(to_new * from_old * local.compute_affine());
+
+    let start_world = Affine3A::from_scale_rotation_translation(
+        drag.start_scale,
+        drag.start_rotation,
+        drag.start_translation,
+    );
+    let start_local = nearest_transform(to_new * start_world);
+    drag.start_local_translation = start_local.translation;
+    drag.start_local_rotation = start_local.rotation;
+    drag.start_local_scale = start_local.scale;
+    drag.initial_transform = remap(drag.initial_transform);
+    drag.last_transform = remap(drag.last_transform);
+    drag.history.remap(remap);
+    drag.fixed_endpoint = (to_new * from_old).transform_point3(drag.fixed_endpoint);
+    drag.start_parent = new_parent.map(GlobalTransform::from);
+}
+
+/// Move every world-space field of `drag` by `carry`.
+fn carry_drag(drag: &mut TransformGizmoDrag, carry: Affine3A) {
+    let direction = |dir: Vec3| carry.transform_vector3(dir).normalize_or_zero();
+    let (_, rotation, _) = carry.to_scale_rotation_translation();
+    drag.origin = carry.transform_point3(drag.origin);
+    drag.plane_origin = carry.transform_point3(drag.plane_origin);
+    drag.axis_dir = direction(drag.axis_dir);
+    drag.plane_normal = rotation * drag.plane_normal;
+    drag.plane_dir1 = direction(drag.plane_dir1);
+    drag.plane_dir2 = direction(drag.plane_dir2);
+    drag.start_translation = carry.transform_point3(drag.start_translation);
+    drag.start_rotation = rotation * drag.start_rotation;
+    drag.start_vector = carry.transform_vector3(drag.start_vector);
+    drag.pivot = drag.pivot.map(|pivot| carry.transform_point3(pivot));
+    drag.last_cursor_offset = carry.transform_vector3(drag.last_cursor_offset);
+    drag.precise_cursor_offset = carry.transform_vector3(drag.precise_cursor_offset);
+}
+
+/// Keep the visual pivot fixed while scaling by moving the target's origin.
+#[cfg(feature = "scale")]
+fn apply_scale_pivot(
+    drag: &mut TransformGizmoDrag,
+    transform: &mut Transform,
+    parent_global: Option<&GlobalTransform>,
+) {
+    drag.pivot_delta = Vec3::ZERO;
+    drag.origin_delta = Vec3::ZERO;
+    let Some(pivot) = drag.pivot else {
+        return;
+    };
+    let factor = scale_ratio(transform.scale, drag.start_local_scale);
+    let world_translation =
+        scale_about_pivot(drag.start_translation, drag.start_rotation, pivot, factor);
+    transform.translation = world_point_to_local(parent_global, world_translation);
+    drag.origin_delta = world_translation - drag.start_translation;
+}
+
+#[cfg(any(feature = "translate", feature = "rotate", feature = "scale"))]
+fn world_point_to_local(parent: Option<&GlobalTransform>, world_point: Vec3) -> Vec3 {
+    parent.map_or(world_point, |parent| {
+        parent.affine().inverse().transform_point3(world_point)
+    })
+}
+
+#[cfg(feature = "translate")]
+fn world_vector_to_local(parent: Option<&GlobalTransform>, world_vector: Vec3) -> Vec3 {
+    parent.map_or(world_vector, |parent| {
+        parent.affine().inverse().transform_vector3(world_vector)
+    })
+}
+
+/// Snap a drag's change `delta` to `step`: the change itself for
+/// [`SnapMode::Relative`], or the resulting `start + delta` for
+/// [`SnapMode::Absolute`].
+#[cfg(any(feature = "translate", feature = "rotate"))]
+fn snap_offset(mode: SnapMode, start: f32, delta: f32, step: Option<f32>) -> f32 {
+    let Some(step) = step.filter(|step| *step > 0.0) else {
+        return delta;
+    };
+    match mode {
+        SnapMode::Relative => (delta / step).round() * step,
+        SnapMode::Absolute => ((start + delta) / step).round() * step - start,
+    }
+}
+
+/// The scale factor for a drag parameter `t` that started at `start_t`.
+#[cfg(feature = "scale")]
+fn scale_drag_ratio(t: f32, start_t: f32) -> f32 {
+    if start_t.abs() > MIN_SCALE_DIVISOR {
+        t / start_t
+    } else {
+        1.0
+    }
+}
+
+/// Round the magnitude of `scale` to a multiple of `step`, keeping its sign.
+#[cfg(feature = "scale")]
+fn snap_scale(scale: f32, step: Option<f32>) -> f32 {
+    match step {
+        Some(step) if step > 0.0 => scale.signum() * (scale.abs() / step).round() * step,
+        _ => scale,
+    }
+}
+
+/// Keep a dragged `scale` at least [`TransformGizmoSnap::min_scale`] from
+/// zero, on the side of `base` unless
+/// [`TransformGizmoSnap::allow_negative_scale`] lets it cross.
+#[cfg(feature = "scale")]
+fn limit_scale(base: f32, scale: f32, snap: &TransformGizmoSnap) -> f32 {
+    let sign = if snap.allow_negative_scale {
+        scale.signum()
+    } else {
+        base.signum()
+    };
+    sign * (scale * sign).max(snap.min_scale.max(0.0))
+}
+
+/// End the drag operation when the mouse button is released.
+///
+/// Releasing a handle before the drag left
+/// [pending](TransformGizmoDrag::is_pending) is a click instead.
+pub fn end_drag(
+    buttons: Res<ButtonInput<MouseButton>>,
+    config: Res<TransformGizmoConfig>,
+    mut state: ResMut<TransformGizmoState>,
+    targets: Query<(
+        Option<&Transform>,
+        Option<&GizmoChannelValue>,
+        Option<&GizmoTargetTransform>,
+    )>,
+    mut ended: MessageWriter<GizmoDragEnded>,
+    mut clicked: MessageWriter<GizmoHandleClicked>,
+) {
+    if buttons.just_released(config.drag_input.button) {
+        if let Some(drag) = state.drag.take() {
+            if drag.is_pending() {
+                clicked.write(GizmoHandleClicked {
+                    handle: drag.handle(),
+                });
+                return;
+            }
+            // An externally driven target's edit is the last one published,
+            // and a smoothed target's is its goal.
+            let current = targets
+                .get(drag.target)
+                .ok()
+                .and_then(|(transform, value, smoothed)| {
+                    match value.filter(|_| drag.channel == GizmoEditChannel::Secondary) {
+                        Some(value) => Some(value.local),
+                        None if drag.external => None,
+                        None => smoothed
+                            .filter(|_| config.smoothing.is_some())
+                            .map(|smoothed| smoothed.goal)
+                            .or(transform.copied()),
+                    }
+                });
+            ended.write(drag_ended(&drag, current.unwrap_or(drag.last_transform)));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "translate")]
+    use crate::test_utils::GizmoTestHarness;
+    use crate::types::GizmoDragInput;
+    #[cfg(feature = "rotate")]
+    use crate::types::RotationRingStyle;
+
+    #[test]
+    fn hover_only_picks_compiled_handles() {
+        let style = TransformGizmoStyle {
+            show_translate_planes: true,
+            show_scale_uniform: true,
+            show_rotate_view: true,
+            ..default()
+        };
+        let frame = GizmoFrame::new(&GlobalTransform::IDENTITY, TransformGizmoSpace::World);
+        let eye = Vec3::new(4.0, 5.0, 9.0);
+        let mut picked = Vec::new();
+        for x in -30..=30 {
+            for y in -30..=30 {
+                let toward = Vec3::new(x as f32, y as f32, 0.0) * 0.1;
+                let ray = Ray3d::new(eye, Dir3::new(toward - eye).unwrap());
+                if let Some((_, op, _)) = hit_test_handles(
+                    &ray,
+                    &frame,
+                    &style,
+                    GizmoChannelParts::ALL,
+                    &|_| 0.0,
+                    &mut 0,
+                ) {
+                    assert!(operation_compiled(op), "{op:?} picked but not compiled");
+                    picked.push(op);
+                }
+            }
+        }
+        #[cfg(feature = "translate")]
+        assert!(picked.contains(&GizmoOperation::TranslateAxis));
+        #[cfg(feature = "rotate")]
+        assert!(picked.contains(&GizmoOperation::Rotate));
+        #[cfg(feature = "scale")]
+        assert!(picked.contains(&GizmoOperation::ScaleAxis));
+    }
+
+    #[cfg(feature = "translate")]
+    #[test]
+    fn orbiting_the_camera_mid_drag_keeps_an_axis_drag_continuous() {
+        let snap = TransformGizmoSnap::default();
+        let mut transform = Transform::default();
+        let mut drag = TransformGizmoDrag::translate_x(transform);
+        let ray_through = |eye: Vec3, point: Vec3| Ray3d::new(eye, Dir3::new(point - eye).unwrap());
+        let mut step = |drag: &mut TransformGizmoDrag, eye: Vec3, point: Vec3| {
+            let ray = ray_through(eye, point);
+            reaim_view_plane(drag, eye.normalize(), &ray);
+            apply_drag(drag, &ray, &snap, &mut transform);
+            transform.translation
+        };
+
+        // Looking down -Z the drag runs on the XY plane.
+        let front = Vec3::new(0.0, 0.0, 10.0);
+        let point = Vec3::new(1.0, 3.0, 0.0);
+        let moved = step(&mut drag, front, point);
+        assert!(moved.abs_diff_eq(Vec3::X, 1.0e-5));
+
+        // The camera orbits up over the X axis while the cursor stays on the
+        // same point. The plane turns to face it and the target stays put,
+        // though the new plane alone would put it elsewhere.
+        let above = Quat::from_rotation_x(-1.0) * front;
+        let normal = above.normalize();
+        let hit = ray_plane_intersection(&ray_through(above, point), Vec3::ZERO, normal).unwrap();
+        assert!((hit.x - 1.0).abs() > 0.1, "{hit}");
+        let moved = step(&mut drag, above, point);
+        assert!(drag.plane_normal.abs_diff_eq(normal, 1.0e-5));
+        assert!(moved.abs_diff_eq(Vec3::X, 1.0e-5), "{moved}");
+
+        // Cursor motion along the axis on the new plane moves the target by
+        // as much.
+        let moved = step(&mut drag, above, hit + Vec3::X * 0.5);
+        assert!(moved.abs_diff_eq(Vec3::X * 1.5, 1.0e-4), "{moved}");
+
+        // Plane drags stay on the plane they started on.
+        drag.op = GizmoOperation::TranslatePlane;
+        drag.plane_normal = Vec3::Y;
+        reaim_view_plane(&mut drag, front.normalize(), &ray_through(front, point));
+        assert_eq!(drag.plane_normal, Vec3::Y);
+    }
+
+    #[cfg(feature = "translate")]
+    #[test]
+    fn stepping_back_through_history_resumes_without_jumping() {
+        let snap = TransformGizmoSnap::default();
+        let mut transform = Transform::default();
+        let mut drag = TransformGizmoDrag::translate_x(transform);
+        drag.history.push(transform);
+
+        let cursor = |frame: usize| Vec3::new(0.1 * frame as f32, 0.0, 0.0);
+        let mut frames = Vec::new();
+        for frame in 1..=30 {
+            apply_drag_motion(&mut drag, cursor(frame), &snap, &mut transform, None);
+            drag.history.record(transform);
+            frames.push(transform);
+        }
+
+        let mut rewound = None;
+        for _ in 0..10 {
+            rewound = drag.history.step_back();
+        }
+        let rewound = rewound.unwrap();
+        assert_eq!(rewound, frames[19]);
+        transform = rewound;
+        rebase_drag(&mut drag, &rewound, None, cursor(30));
+
+        let mut previous = transform.translation;
+        for frame in 31..=35 {
+            apply_drag_motion(&mut drag, cursor(frame), &snap, &mut transform, None);
+            drag.history.record(transform);
+            let step = transform.translation.distance(previous);
+            assert!(
+                step <= 0.1 + 1.0e-4,
+                "discontinuity of {step} at frame {frame}"
+            );
+            previous = transform.translation;
+        }
+        assert!(transform
+            .translation
+            .abs_diff_eq(Vec3::new(2.5, 0.0, 0.0), 1.0e-4));
+        // Scrubbing forward after resuming has nothing left to redo.
+        assert!(drag.history.step_forward().is_none());
+    }
+
+    #[cfg(feature = "translate")]
+    #[test]
+    fn hover_tracks_moving_target_and_predicts_next_position() {
+        let style = TransformGizmoStyle {
+            show_rotate: false,
+            show_scale: false,
+            show_translate_planes: false,
+            translate_hit_radius: 0.08,
+            ..default()
+        };
+        let cone_center = |origin: Vec3| {
+            origin + Vec3::X * (style.axis_length + style.translate_cone_length * 0.5)
+        };
+        let ray_at = |point: Vec3| Ray3d {
+            origin: point + Vec3::Y * 10.0,
+            direction: Dir3::NEG_Y,
+        };
+        let hits = |origin: Vec3, ray: &Ray3d| {
+            let frame = GizmoFrame::new(
+                &GlobalTransform::from_translation(origin),
+                TransformGizmoSpace::World,
+            );
+            hit_test_handles(
+                ray,
+                &frame,
+                &style,
+                GizmoChannelParts::ALL,
+                &|_| 0.0,
+                &mut 0,
+            )
+            .is_some()
+        };
+
+        // Target moving 0.2 units per frame.
+        let mut motion = TargetMotion::default();
+        let mut predicted = Vec3::ZERO;
+        for frame in 0..5 {
+            let position = Vec3::new(0.0, 0.0, 0.2 * frame as f32);
+            motion.begin_frame();
+            predicted = position + motion.observe(Entity::PLACEHOLDER, position);
+            assert!(hits(position, &ray_at(cone_center(position))));
+        }
+
+        let current = Vec3::new(0.0, 0.0, 0.8);
+        let next = Vec3::new(0.0, 0.0, 1.0);
+        assert!(predicted.abs_diff_eq(next, 1.0e-5));
+        let next_ray = ray_at(cone_center(next));
+        assert!(!hits(current, &next_ray));
+        assert!(hits(predicted, &next_ray));
+
+        // A target missing from a pass loses its velocity.
+        motion.begin_frame();
+        motion.begin_frame();
+        assert_eq!(motion.observe(Entity::PLACEHOLDER, next), Vec3::ZERO);
+    }
+
+    #[cfg(feature = "translate")]
+    #[test]
+    fn drag_on_moving_platform_applies_platform_relative_delta() {
+        let platform_start = GlobalTransform::IDENTITY;
+        let start = Transform::from_xyz(1.0, 0.0, 0.0);
+        let mut drag = TransformGizmoDrag::translate_x(start);
+        drag.start_parent = Some(platform_start);
+
+        // The platform moves 0.2 units per frame for five frames while the
+        // cursor follows it and adds 0.5 units of its own along X.
+        let platform_now = GlobalTransform::from_xyz(1.0, 0.0, 0.0);
+        let cursor_world = platform_now.transform_point(start.translation) + Vec3::X * 0.5;
+        let ray = Ray3d {
+            origin: cursor_world + Vec3::Z * 10.0,
+            direction: Dir3::NEG_Z,
+        };
+
+        let carried = carry_ray(ray, &platform_now, &platform_start);
+        let hit = ray_plane_intersection(&carried, drag.plane_origin, drag.plane_normal).unwrap();
+        let v = hit - drag.origin;
+        let mut transform = start;
+        apply_drag_motion(
+            &mut drag,
+            v,
+            &TransformGizmoSnap::default(),
+            &mut transform,
+            Some(&platform_start),
+        );
+
+        assert!(transform
+            .translation
+            .abs_diff_eq(Vec3::new(1.5, 0.0, 0.0), 1.0e-4));
+        assert!(platform_now
+            .transform_point(transform.translation)
+            .abs_diff_eq(cursor_world, 1.0e-4));
+    }
+
+    #[cfg(feature = "translate")]
+    #[test]
+    fn pen_pressure_scales_drag_motion_by_the_pressure_curve() {
+        use crate::types::PressureCurve;
+
+        let curve = PressureCurve {
+            min_factor: 0.1,
+            max_factor: 1.0,
+            exponent: 2.0,
+        };
+        let snap = TransformGizmoSnap::default();
+        let mut transform = Transform::default();
+        let mut drag = TransformGizmoDrag::translate_x(transform);
+
+        // Cursor moves 0.1 units per frame while pressure ramps from light
+        // to firm and back.
+        let mut expected = 0.0;
+        let mut previous = transform.translation.x;
+        for frame in 1..=40 {
+            let pressure = 1.0 - (frame as f32 / 20.0 - 1.0).abs();
+            let factor = curve.factor(pressure);
+            expected += 0.1 * factor;
+
+            let v = accumulate_precision(&mut drag, Vec3::X * 0.1 * frame as f32, factor);
+            apply_drag_motion(&mut drag, v, &snap, &mut transform, None);
+
+            let step = transform.translation.x - previous;
+            assert!(
+                (step - 0.1 * factor).abs() < 1.0e-4,
+                "jump at frame {frame}"
+            );
+            previous = transform.translation.x;
+        }
+        assert!((transform.translation.x - expected).abs() < 1.0e-3);
+        assert!(transform.translation.x < 4.0 * 0.75);
+
+        // Full pressure tracks the cursor one to one.
+        let mut drag = TransformGizmoDrag::translate_x(Transform::default());
+        let v = accumulate_precision(&mut drag, Vec3::X * 2.0, curve.factor(1.0));
+        assert_eq!(v, Vec3::X * 2.0);
+    }
+
+    #[cfg(feature = "translate")]
+    #[test]
+    fn custom_pickers_can_grab_and_drag_handles() {
+        let style = TransformGizmoStyle {
+            show_rotate: false,
+            show_scale: false,
+            show_translate_planes: false,
+            ..default()
+        };
+        let frame =
+            GizmoFrame::new(&GlobalTransform::IDENTITY, TransformGizmoSpace::World).with_size(2.0);
+        // Straight down onto the X cone, a quarter of the way to its tip.
+        let x = (style.axis_length + style.translate_cone_length * 0.25) * 2.0;
+        let ray_at = |x: f32| Ray3d {
+            origin: Vec3::new(x, 0.0, 10.0),
+            direction: Dir3::NEG_Z,
+        };
+
+        let hit = pick_gizmo_handle(&ray_at(x), &frame, &style).unwrap();
+        assert_eq!(
+            (hit.op, hit.axis),
+            (GizmoOperation::TranslateAxis, GizmoAxis::X)
+        );
+        // The point lies on the scaled cone's surface, not its hit padding.
+        let radius = style.translate_cone_radius * 2.0 * 0.75;
+        assert!((hit.point - Vec3::new(x, 0.0, radius)).length() < 1.0e-3);
+        assert!((hit.distance - (10.0 - radius)).abs() < 1.0e-3);
+        // The plugin's hover test picks the same handle.
+        let hovered = hit_test_handles(
+            &ray_at(x),
+            &frame,
+            &frame.sized_style(&style),
+            GizmoChannelParts::ALL,
+            &|_| 0.0,
+            &mut 0,
+        );
+        assert_eq!(
+            hovered.map(|(_, op, axis)| (op, axis)),
+            Some((hit.op, hit.axis))
+        );
+        assert!(pick_gizmo_handle(&ray_at(-x), &frame, &style).is_none());
+
+        let mut transform = Transform::IDENTITY;
+        let mut drag = begin_drag_from_ray(
+            &ray_at(x),
+            hit.op,
+            hit.axis,
+            Entity::PLACEHOLDER,
+            &frame,
+            &GlobalTransform::IDENTITY,
+            &transform,
+        );
+        let snap = TransformGizmoSnap::default();
+        apply_drag(&mut drag, &ray_at(x + 1.5), &snap, &mut transform);
+        assert!((transform.translation - Vec3::X * 1.5).length() < 1.0e-4);
+        assert_eq!(drag.last_transform, transform);
+    }
+
+    #[cfg(feature = "translate")]
+    #[test]
+    fn orthographic_rays_from_far_behind_still_pick_thin_handles() {
+        let style = TransformGizmoStyle {
+            show_rotate: false,
+            show_scale: false,
+            show_translate_planes: false,
+            ..default()
+        };
+        // An orthographic view down -Z, with its near plane a thousand units
+        // behind the gizmo.
+        let frame = GizmoFrame::new(&GlobalTransform::IDENTITY, TransformGizmoSpace::World)
+            .with_view_dir(Vec3::NEG_Z)
+            .with_camera_direction(Vec3::Z);
+        let x = style.axis_length + style.translate_cone_length * 0.25;
+        let ray = Ray3d {
+            origin: Vec3::new(x, 0.0, 1000.0),
+            direction: Dir3::NEG_Z,
+        };
+
+        let hit = pick_gizmo_handle(&ray, &frame, &style).unwrap();
+        assert_eq!(
+            (hit.op, hit.axis),
+            (GizmoOperation::TranslateAxis, GizmoAxis::X)
+        );
+        let radius = style.translate_cone_radius * 0.75;
+        assert!((hit.point - Vec3::new(x, 0.0, radius)).length() < 1.0e-2);
+        assert!((hit.distance - (1000.0 - radius)).abs() < 1.0e-2);
+    }
+
+    #[cfg(feature = "translate")]
+    #[test]
+    fn axis_drags_stay_bounded_when_looking_down_the_axis() {
+        let snap = TransformGizmoSnap::default();
+        let max_gain = 1.0 / GRAZING_AXIS_DEGREES.to_radians().sin();
+        for degrees in [0.0_f32, 2.0, 5.0, 10.0] {
+            // Camera looking down +X, turned slightly about Z.
+            let (sin, cos) = degrees.to_radians().sin_cos();
+            let forward = Vec3::new(cos, sin, 0.0);
+            let camera = -forward * 10.0;
+            let frame = GizmoFrame::new(&GlobalTransform::IDENTITY, TransformGizmoSpace::World)
+                .with_view_dir(forward)
+                .with_camera_position(camera);
+            let (_, up) = axis_basis(forward);
+            let ray_to = |offset: f32| Ray3d {
+                origin: camera,
+                direction: Dir3::new(up * offset - camera).unwrap(),
+            };
+
+            let mut transform = Transform::IDENTITY;
+            let mut drag = begin_drag_from_ray(
+                &ray_to(0.2),
+                GizmoOperation::TranslateAxis,
+                GizmoAxis::X,
+                Entity::PLACEHOLDER,
+                &frame,
+                &GlobalTransform::IDENTITY,
+                &transform,
+            );
+            apply_drag(&mut drag, &ray_to(0.3), &snap, &mut transform);
+
+            let moved = transform.translation;
+            assert!(moved.is_finite());
+            assert_eq!(moved.yz(), Vec2::ZERO);
+            assert!(
+                moved.x.abs() <= 0.1 * max_gain + 1.0e-4,
+                "{degrees} degrees moved {moved}"
+            );
+            if degrees > 0.0 {
+                assert!(moved.x.abs() >= 0.1, "{degrees} degrees moved {moved}");
+            }
+        }
+    }
+
+    #[cfg(feature = "translate")]
+    #[test]
+    fn axis_drags_are_anchored_at_the_grab_point() {
+        let frame = GizmoFrame::new(&GlobalTransform::IDENTITY, TransformGizmoSpace::World)
+            .with_view_dir(Vec3::NEG_Z)
+            .with_camera_direction(Vec3::Z);
+        // Grabbing the X cone above the axis, not on it.
+        let ray_at = |x: f32| Ray3d::new(Vec3::new(x, 0.08, 10.0), Dir3::NEG_Z);
+        let snap = TransformGizmoSnap::default();
+
+        let mut transform = Transform::IDENTITY;
+        let mut drag = begin_drag_from_ray(
+            &ray_at(1.1),
+            GizmoOperation::TranslateAxis,
+            GizmoAxis::X,
+            Entity::PLACEHOLDER,
+            &frame,
+            &GlobalTransform::IDENTITY,
+            &transform,
+        );
+        apply_drag(&mut drag, &ray_at(1.1), &snap, &mut transform);
+        assert_eq!(transform.translation, Vec3::ZERO);
+        apply_drag(&mut drag, &ray_at(1.6), &snap, &mut transform);
+        assert!(transform.translation.abs_diff_eq(Vec3::X * 0.5, 1.0e-4));
+        assert!(drag
+            .axis_distance()
+            .is_some_and(|distance| (distance - 0.5).abs() < 1.0e-4));
+        assert_eq!(drag.plane_distance(), None);
+    }
+
+    #[cfg(feature = "planes")]
+    #[test]
+    fn plane_drags_keep_the_grabbed_point_under_the_cursor() {
+        let global = GlobalTransform::from_xyz(3.0, 0.0, -2.0);
+        let camera = Vec3::new(2.0, 6.0, 8.0);
+        let frame = GizmoFrame::new(&global, TransformGizmoSpace::World)
+            .with_view_dir((global.translation() - camera).normalize())
+            .with_camera_position(camera);
+        let ray_to = |point: Vec3| Ray3d::new(camera, Dir3::new(point - camera).unwrap());
+        let snap = TransformGizmoSnap::default();
+
+        // The XZ square, grabbed away from the origin.
+        let grabbed = global.translation() + Vec3::new(0.3, 0.0, 0.4);
+        let start = global.compute_transform();
+        let mut transform = start;
+        let mut drag = begin_drag_from_ray(
+            &ray_to(grabbed),
+            GizmoOperation::TranslatePlane,
+            GizmoAxis::Y,
+            Entity::PLACEHOLDER,
+            &frame,
+            &global,
+            &start,
+        );
+        apply_drag(&mut drag, &ray_to(grabbed), &snap, &mut transform);
+        assert!(transform.translation.abs_diff_eq(start.translation, 1.0e-5));
+
+        let moved = grabbed + Vec3::new(-1.5, 0.0, 2.5);
+        apply_drag(&mut drag, &ray_to(moved), &snap, &mut transform);
+        assert!(transform
+            .translation
+            .abs_diff_eq(start.translation + moved - grabbed, 1.0e-4));
+        let distance = drag.plane_distance().unwrap();
+        assert!((distance.length() - (moved - grabbed).length()).abs() < 1.0e-4);
+        assert_eq!(drag.axis_distance(), None);
+
+        // Above the horizon the ray misses the plane, and the target stays.
+        let sky = Ray3d::new(camera, Dir3::Y);
+        let before = transform;
+        apply_drag(&mut drag, &sky, &snap, &mut transform);
+        assert_eq!(transform, before);
+    }
+
+    #[cfg(feature = "translate")]
+    #[test]
+    fn pen_tilt_breaks_ties_between_overlapping_axes() {
+        use crate::types::AxisToggles;
+
+        // Oversized hit spheres make the X and Y cones overlap; the ray hits
+        // both at the same distance.
+        let style = TransformGizmoStyle {
+            show_rotate: false,
+            show_scale: false,
+            show_translate_planes: false,
+            translate_axes: AxisToggles {
+                x: true,
+                y: true,
+                z: false,
+            },
+            translate_hit_radius: 3.0,
+            ..default()
+        };
+        let frame = GizmoFrame::new(&GlobalTransform::IDENTITY, TransformGizmoSpace::World);
+        let ray = Ray3d {
+            origin: Vec3::new(1.6, 1.6, 10.0),
+            direction: Dir3::NEG_Z,
+        };
+        // Orthographic view down -Z: world +Y is screen up (negative y).
+        let screen = |axis_dir: Vec3| Vec2::new(axis_dir.x, -axis_dir.y);
+        let pick = |tilt: Option<Vec2>| {
+            let bias = |axis_dir: Vec3| {
+                tilt.map_or(0.0, |tilt| tilt_axis_score(screen(axis_dir), tilt, 0.5))
+            };
+            hit_test_handles(&ray, &frame, &style, GizmoChannelParts::ALL, &bias, &mut 0)
+                .map(|(_, op, axis)| (op, axis))
+        };
+
+        assert_eq!(
+            pick(None),
+            Some((GizmoOperation::TranslateAxis, GizmoAxis::X))
+        );
+        // Tilting the pen toward the top of the screen prefers Y.
+        assert_eq!(
+            pick(Some(Vec2::new(0.0, -0.8))),
+            Some((GizmoOperation::TranslateAxis, GizmoAxis::Y))
+        );
+        // Tilting away from both axes changes nothing.
+        assert_eq!(
+            pick(Some(Vec2::new(-0.8, 0.8))),
+            Some((GizmoOperation::TranslateAxis, GizmoAxis::X))
+        );
+    }
+
+    #[cfg(feature = "translate")]
+    #[test]
+    fn handles_under_the_cursor_beat_near_misses_in_front_of_them() {
+        // A generous hit radius puts the X cone's padding between the camera
+        // and the Z cone, which the ray goes straight through.
+        let style = TransformGizmoStyle {
+            show_rotate: false,
+            show_scale: false,
+            show_translate_planes: false,
+            translate_hit_radius: 2.5,
+            ..default()
+        };
+        let frame = GizmoFrame::new(&GlobalTransform::IDENTITY, TransformGizmoSpace::World);
+        let cone_middle = style.axis_length + style.translate_cone_length * 0.5;
+        let ray = Ray3d::new(Vec3::new(10.0, 0.0, cone_middle), Dir3::NEG_X);
+        let hit = hit_test_handles(
+            &ray,
+            &frame,
+            &style,
+            GizmoChannelParts::ALL,
+            &|_| 0.0,
+            &mut 0,
+        );
+        assert!(matches!(
+            hit,
+            Some((_, GizmoOperation::TranslateAxis, GizmoAxis::Z))
+        ));
+
+        // Just past the Z cone's tip the padding alone still picks it.
+        let ray = Ray3d::new(Vec3::new(10.0, 0.0, cone_middle + 0.5), Dir3::NEG_X);
+        let hit = hit_test_handles(
+            &ray,
+            &frame,
+            &style,
+            GizmoChannelParts::ALL,
+            &|_| 0.0,
+            &mut 0,
+        );
+        assert!(matches!(
+            hit,
+            Some((t, GizmoOperation::TranslateAxis, GizmoAxis::Z)) if t > style.effective_bounds_radius() * 2.0
+        ));
+    }
+
+    #[cfg(feature = "translate")]
+    #[test]
+    fn handles_seen_end_on_fade_out_of_hit_testing() {
+        let style = TransformGizmoStyle {
+            show_rotate: false,
+            show_scale: false,
+            show_translate_planes: false,
+            ..default()
+        };
+        let faded = TransformGizmoStyle {
+            fade_backfacing: true,
+            ..style.clone()
+        };
+        // Looking almost straight down the X axis.
+        let camera = Vec3::new(10.0, 0.5, 0.0);
+        let frame = GizmoFrame::new(&GlobalTransform::IDENTITY, TransformGizmoSpace::World)
+            .with_camera_position(camera);
+        let cone_middle = style.axis_length + style.translate_cone_length * 0.5;
+        let pick = |style: &TransformGizmoStyle, point: Vec3| {
+            let ray = Ray3d::new(camera, Dir3::new(point - camera).unwrap());
+            hit_test_handles(
+                &ray,
+                &frame,
+                style,
+                GizmoChannelParts::ALL,
+                &|_| 0.0,
+                &mut 0,
+            )
+            .map(|(_, op, axis)| (op, axis))
+        };
+
+        let x_cone = Vec3::X * cone_middle;
+        assert_eq!(
+            pick(&style, x_cone),
+            Some((GizmoOperation::TranslateAxis, GizmoAxis::X))
+        );
+        assert_ne!(
+            pick(&faded, x_cone),
+            Some((GizmoOperation::TranslateAxis, GizmoAxis::X))
+        );
+        assert!(handle_faces_away(
+            &frame,
+            &faded,
+            GizmoOperation::TranslateAxis,
+            GizmoAxis::X
+        ));
+
+        // The Y cone is seen side-on and stays pickable.
+        let y_cone = Vec3::Y * cone_middle;
+        assert_eq!(
+            pick(&faded, y_cone),
+            Some((GizmoOperation::TranslateAxis, GizmoAxis::Y))
+        );
+    }
+
+    #[cfg(feature = "planes")]
+    #[test]
+    fn plane_handles_flip_to_face_the_camera() {
+        let style = TransformGizmoStyle {
+            show_rotate: false,
+            show_scale: false,
+            ..default()
+        };
+        let camera = Vec3::new(-10.0, 10.0, -10.0);
+        let frame = GizmoFrame::new(&GlobalTransform::IDENTITY, TransformGizmoSpace::World);
+        assert_eq!(
+            frame.with_camera_position(camera).plane_signs(),
+            Vec3::new(-1.0, 1.0, -1.0)
+        );
+
+        // Straight down onto the middle of the flipped XZ handle.
+        let middle = style.translate_plane_offset + style.translate_plane_size * 0.5;
+        let ray = Ray3d::new(Vec3::new(-middle, 10.0, -middle), Dir3::NEG_Y);
+        let pick = |frame: &GizmoFrame| {
+            hit_test_handles(
+                &ray,
+                frame,
+                &style,
+                GizmoChannelParts::ALL,
+                &|_| 0.0,
+                &mut 0,
+            )
+            .map(|(_, op, axis)| (op, axis))
+        };
+        assert_eq!(pick(&frame), None);
+        assert_eq!(
+            pick(&frame.with_camera_position(camera)),
+            Some((GizmoOperation::TranslatePlane, GizmoAxis::Y))
+        );
+
+        // A drag keeps the signs it started with.
+        let mut drag = TransformGizmoDrag::translate_x(Transform::default());
+        drag.plane_signs = Vec3::new(-1.0, 1.0, -1.0);
+        reconstrain_drag(
+            &mut drag,
+            GizmoOperation::TranslatePlane,
+            GizmoAxis::Y,
+            TransformGizmoSpace::World,
+            &GlobalTransform::from(
+                Transform::from_xyz(0.0, 10.0, 0.0).looking_at(Vec3::ZERO, Vec3::Z),
+            ),
+            &ray,
+            &Transform::default(),
+            None,
+        );
+        assert_eq!(drag.plane_dir1, Vec3::NEG_X);
+        assert_eq!(drag.plane_dir2, Vec3::NEG_Z);
+    }
+
+    #[cfg(all(
+        feature = "translate",
+        feature = "rotate",
+        feature = "scale",
+        feature = "planes",
+        feature = "uniform-scale"
+    ))]
+    #[test]
+    fn planar_2d_keeps_the_handles_a_2d_camera_can_use() {
+        use GizmoAxis::{X, Y, Z};
+        use GizmoOperation::{
+            Rotate, RotateTrackball, RotateView, ScaleAxis, ScaleUniform, TranslateAxis,
+            TranslatePlane,
+        };
+
+        let style = TransformGizmoStyle {
+            planar_2d: true,
+            show_rotate_view: true,
+            show_rotate_trackball: true,
+            ..default()
+        };
+        let enabled = |op, axis| handle_enabled(&style, GizmoChannelParts::ALL, op, axis);
+        assert!(enabled(TranslateAxis, X) && enabled(TranslateAxis, Y));
+        assert!(!enabled(TranslateAxis, Z) && !enabled(ScaleAxis, Z));
+        assert!(!enabled(Rotate, X) && !enabled(Rotate, Y) && enabled(Rotate, Z));
+        assert!(!enabled(RotateView, X) && !enabled(RotateTrackball, X));
+        assert!(!enabled(TranslatePlane, X) && !enabled(TranslatePlane, Y));
+
+        // A `Camera2d` sits level with the sprites it looks at, so the plane
+        // handles face its back rather than its position, and its rays are
+        // parallel.
+        let global = GlobalTransform::from_xyz(5.0, -3.0, 0.0);
+        let frame = GizmoFrame::new(&global, TransformGizmoSpace::World)
+            .with_view_dir(Vec3::NEG_Z)
+            .with_camera_direction(Vec3::Z);
+        let ray_at = |x: f32, y: f32| Ray3d::new(Vec3::new(5.0 + x, -3.0 + y, 1000.0), Dir3::NEG_Z);
+        let pick =
+            |x, y| pick_gizmo_handle(&ray_at(x, y), &frame, &style).map(|hit| (hit.op, hit.axis));
+        assert_eq!(pick(0.0, 0.0), Some((ScaleUniform, X)));
+        // The free-move square surrounds the center handles on every side.
+        for (x, y) in [(0.6, 0.0), (-0.6, 0.3), (0.2, -0.7)] {
+            assert_eq!(pick(x, y), Some((TranslatePlane, Z)), "at ({x}, {y})");
+        }
+
+        let start = Transform::from_xyz(5.0, -3.0, 0.0);
+        let mut drag = begin_drag_from_ray(
+            &ray_at(-0.6, 0.3),
+            TranslatePlane,
+            Z,
+            Entity::PLACEHOLDER,
+            &frame,
+            &global,
+            &start,
+        );
+        let mut transform = start;
+        apply_drag(
+            &mut drag,
+            &ray_at(0.4, 1.3),
+            &TransformGizmoSnap::default(),
+            &mut transform,
+        );
+        assert!(transform
+            .translation
+            .abs_diff_eq(Vec3::new(6.0, -2.0, 0.0), 1.0e-4));
+    }
+
+    #[cfg(feature = "rotate")]
+    fn rotate_only() -> TransformGizmoStyle {
+        TransformGizmoStyle {
+            show_translate: false,
+            show_scale: false,
+            ..default()
+        }
+    }
+
+    #[cfg(feature = "rotate")]
+    #[test]
+    fn full_circle_rings_are_hit_outside_the_arc_window() {
+        let frame = GizmoFrame::new(&GlobalTransform::IDENTITY, TransformGizmoSpace::World);
+        // Looking down -Z at the bottom of the Z ring, opposite its arc.
+        let ray = Ray3d::new(Vec3::new(0.0, -2.0, 10.0), Dir3::NEG_Z);
+        let pick = |style: &TransformGizmoStyle| {
+            hit_test_handles(
+                &ray,
+                &frame,
+                style,
+                GizmoChannelParts::ALL,
+                &|_| 0.0,
+                &mut 0,
+            )
+            .map(|(_, op, axis)| (op, axis))
+        };
+
+        assert_eq!(pick(&rotate_only()), None);
+        let full = TransformGizmoStyle {
+            rotation_ring_style: RotationRingStyle::FullCircle,
+            ..rotate_only()
+        };
+        assert_eq!(pick(&full), Some((GizmoOperation::Rotate, GizmoAxis::Z)));
+    }
+
+    #[cfg(feature = "rotate")]
+    #[test]
+    fn back_facing_ring_hits_can_be_ignored() {
+        let frame = GizmoFrame::new(&GlobalTransform::IDENTITY, TransformGizmoSpace::World);
+        let camera = Vec3::new(0.0, 5.0, 10.0);
+        let pick = |style: &TransformGizmoStyle, point: Vec3| {
+            let ray = Ray3d::new(camera, Dir3::new(point - camera).unwrap());
+            hit_test_handles(
+                &ray,
+                &frame,
+                style,
+                GizmoChannelParts::ALL,
+                &|_| 0.0,
+                &mut 0,
+            )
+            .map(|(_, op, axis)| (op, axis))
+        };
+        // The X ring is seen edge-on, as a line through both points.
+        let full = TransformGizmoStyle {
+            rotation_ring_style: RotationRingStyle::FullCircle,
+            rotate_axes: crate::types::AxisToggles {
+                x: false,
+                y: true,
+                z: true,
+            },
+            ..rotate_only()
+        };
+        let culled = TransformGizmoStyle {
+            rotation_backface_cutoff: Some(0.2),
+            ..full.clone()
+        };
+
+        // Front and back of the Y ring, which lies flat under the camera.
+        let front = Vec3::Z * full.axis_length;
+        let back = -front;
+        let y_ring = Some((GizmoOperation::Rotate, GizmoAxis::Y));
+        assert_eq!(pick(&full, front), y_ring);
+        assert_eq!(pick(&full, back), y_ring);
+        assert_eq!(pick(&culled, front), y_ring);
+        assert_eq!(pick(&culled, back), None);
+    }
+
+    #[cfg(all(feature = "translate", feature = "rotate"))]
+    #[test]
+    fn view_ring_rotates_around_the_camera_forward_axis() {
+        let view_dir = Vec3::new(1.0, -1.0, -1.0).normalize();
+        let style = TransformGizmoStyle {
+            show_rotate_view: true,
+            rotate_axes: crate::types::AxisToggles {
+                x: false,
+                y: false,
+                z: false,
+            },
+            ..rotate_only()
+        };
+        let frame = GizmoFrame::new(&GlobalTransform::IDENTITY, TransformGizmoSpace::World)
+            .with_view_dir(view_dir);
+        let (t1, t2) = axis_basis(view_dir);
+        let on_ring = t2 * style.rotate_view_radius;
+        let ray = Ray3d::new(on_ring - view_dir * 10.0, Dir3::new(view_dir).unwrap());
+        let hit = hit_test_handles(
+            &ray,
+            &frame,
+            &style,
+            GizmoChannelParts::ALL,
+            &|_| 0.0,
+            &mut 0,
+        );
+        assert!(matches!(
+            hit,
+            Some((_, GizmoOperation::RotateView, GizmoAxis::X))
+        ));
+
+        let start = Transform::default();
+        let mut transform = start;
+        let mut drag = TransformGizmoDrag::translate_x(start);
+        drag.op = GizmoOperation::RotateView;
+        drag.axis_dir = view_dir;
+        drag.plane_normal = view_dir;
+        let cursor = Quat::from_axis_angle(view_dir, 0.4) * t1;
+        apply_drag_motion(
+            &mut drag,
+            cursor,
+            &TransformGizmoSnap::default(),
+            &mut transform,
+            None,
+        );
+
+        let expected = Quat::from_axis_angle(view_dir, 0.4);
+        assert!(transform.rotation.abs_diff_eq(expected, 1.0e-5));
+    }
+
+    #[cfg(feature = "rotate")]
+    #[test]
+    fn trackball_drag_rolls_the_sphere_under_the_cursor() {
+        let style = TransformGizmoStyle {
+            show_rotate_trackball: true,
+            rotation_ring_style: RotationRingStyle::FullCircle,
+            ..rotate_only()
+        };
+        let frame = GizmoFrame::new(&GlobalTransform::IDENTITY, TransformGizmoSpace::World)
+            .with_view_dir(Vec3::NEG_Z);
+        let pick = |x: f32, y: f32| {
+            let ray = Ray3d::new(Vec3::new(x, y, 10.0), Dir3::NEG_Z);
+            hit_test_handles(
+                &ray,
+                &frame,
+                &style,
+                GizmoChannelParts::ALL,
+                &|_| 0.0,
+                &mut 0,
+            )
+            .map(|(_, op, axis)| (op, axis))
+        };
+        // Inside the rings the trackball takes the cursor, but never over a
+        // ring itself, including the X and Y rings seen edge-on.
+        let trackball = Some((GizmoOperation::RotateTrackball, GizmoAxis::X));
+        let inside = style.axis_length * 0.4;
+        assert!(inside > style.rotation_hit_thickness);
+        assert_eq!(pick(inside, inside), trackball);
+        assert_eq!(
+            pick(0.0, -style.axis_length),
+            Some((GizmoOperation::Rotate, GizmoAxis::Z))
+        );
+        assert_eq!(pick(style.axis_length * 1.5, 0.0), None);
+
+        // Dragging the front of the sphere to the right turns it about the
+        // camera's up axis, by the angle between the two points on it.
+        let ray_at = |x: f32| Ray3d::new(Vec3::new(x, 0.0, 10.0), Dir3::NEG_Z);
+        let start = Transform::default();
+        let global = GlobalTransform::IDENTITY;
+        let mut drag = begin_drag_from_ray(
+            &ray_at(0.0),
+            GizmoOperation::RotateTrackball,
+            GizmoAxis::X,
+            Entity::PLACEHOLDER,
+            &frame,
+            &global,
+            &start,
+        );
+        let mut transform = start;
+        let x = 0.5 * drag.trackball_radius;
+        apply_drag(
+            &mut drag,
+            &ray_at(x),
+            &TransformGizmoSnap::default(),
+            &mut transform,
+        );
+        let expected = Quat::from_rotation_y((x / drag.trackball_radius).asin());
+        assert!(transform.rotation.abs_diff_eq(expected, 1.0e-5));
+    }
+
+    /// An app running the pointer, hover and drag systems for a 2x window
+    /// whose camera draws into an inset viewport, with a gizmo target at the
+    /// origin. Returns the app, the window, the camera and the target.
+    #[cfg(feature = "translate")]
+    fn windowed_app(target: impl Bundle) -> (App, Entity, Entity, Entity) {
+        use crate::camera::select_gizmo_camera;
+        use crate::pointer::update_gizmo_pointer;
+        use bevy::asset::AssetEvent;
+        use bevy::camera::Viewport;
+        use bevy::input::mouse::MouseWheel;
+        use bevy::input::touch::TouchInput;
+        use bevy::render::camera::camera_system;
+        use bevy::render::texture::ManualTextureViews;
+        use bevy::window::{
+            PrimaryWindow, WindowCreated, WindowResized, WindowResolution, WindowScaleFactorChanged,
+        };
+
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .init_resource::<TransformGizmoState>()
+            .init_resource::<TransformGizmoStyle>()
+            .init_resource::<TransformGizmoConfig>()
+            .init_resource::<TransformGizmoSnap>()
+            .init_resource::<GizmoCameraSelection>()
+            .init_resource::<GizmoPointer>()
+            .init_resource::<GizmoFrameStats>()
+            .init_resource::<ButtonInput<MouseButton>>()
+            .init_resource::<ButtonInput<KeyCode>>()
+            .init_resource::<Assets<Image>>()
+            .init_resource::<ManualTextureViews>()
+            .add_message::<TouchInput>()
+            .add_message::<MouseWheel>()
+            .add_message::<WindowCreated>()
+            .add_message::<WindowResized>()
+            .add_message::<WindowScaleFactorChanged>()
+            .add_message::<AssetEvent<Image>>()
+            .add_message::<GizmoDragStarted>()
+            .add_message::<GizmoDuplicated>()
+            .add_message::<GizmoDragging>()
+            .add_message::<GizmoDragEnded>()
+            .add_message::<GizmoTransformRequest>()
+            .add_message::<GizmoAlignSnapped>()
+            .add_systems(
+                Update,
+                (
+                    camera_system,
+                    update_gizmo_pointer,
+                    select_gizmo_camera,
+                    begin_drag,
+                    drag_gizmo,
+                    update_hovered_axis,
+                )
+                    .chain(),
+            );
+
+        let window = app
+            .world_mut()
+            .spawn((
+                Window {
+                    resolution: WindowResolution::new(1600, 1200).with_scale_factor_override(2.0),
+                    ..default()
+                },
+                PrimaryWindow,
+            ))
+            .id();
+        let camera_transform = Transform::from_xyz(0.0, 2.0, 8.0).looking_at(Vec3::ZERO, Vec3::Y);
+        let camera = app
+            .world_mut()
+            .spawn((
+                Camera3d::default(),
+                Camera {
+                    viewport: Some(Viewport {
+                        physical_position: UVec2::new(400, 200),
+                        physical_size: UVec2::new(1000, 800),
+                        ..default()
+                    }),
+                    ..default()
+                },
+                camera_transform,
+                GlobalTransform::from(camera_transform),
+                TransformGizmoCamera,
+            ))
+            .id();
+        let target = app
+            .world_mut()
+            .spawn((target, GlobalTransform::IDENTITY, TransformGizmoTarget))
+            .id();
+        app.world_mut()
+            .resource_mut::<TransformGizmoState>()
+            .active_target = Some(target);
+        app.update();
+        (app, window, camera, target)
+    }
+
+    /// Logical cursor position over the X translate cone of the gizmo at the
+    /// origin, as seen by `camera` of a [`windowed_app`].
+    #[cfg(feature = "translate")]
+    fn x_cone_cursor(app: &App, camera: Entity) -> Vec2 {
+        let camera_transform = *app.world().get::<GlobalTransform>(camera).unwrap();
+        let style = app.world().resource::<TransformGizmoStyle>().clone();
+        let frame = GizmoFrame::new(&GlobalTransform::IDENTITY, TransformGizmoSpace::World)
+            .with_camera_position(camera_transform.translation());
+        let mut anchors = Vec::new();
+        handle_anchors(&frame, &style, GizmoChannelParts::ALL, &mut anchors);
+        let cone = anchors
+            .iter()
+            .find(|anchor| {
+                anchor.op == GizmoOperation::TranslateAxis && anchor.axis == GizmoAxis::X
+            })
+            .unwrap()
+            .center;
+        app.world()
+            .get::<Camera>(camera)
+            .unwrap()
+            .world_to_viewport(&camera_transform, cone)
+            .unwrap()
+    }
+
+    #[cfg(feature = "translate")]
+    #[test]
+    fn hidpi_cursor_over_a_cone_in_a_custom_viewport_hovers_its_axis() {
+        let (mut app, window, camera, _) = windowed_app(());
+        let cursor = x_cone_cursor(&app, camera);
+        // Far from where the cone would be with physical pixels or without
+        // the viewport offset.
+        assert!(cursor.x > 200.0 && cursor.y > 100.0, "{cursor}");
+
+        let mut hover_at = |logical: Vec2| {
+            app.world_mut()
+                .get_mut::<Window>(window)
+                .unwrap()
+                .set_cursor_position(Some(logical));
+            app.update();
+            let state = app.world().resource::<TransformGizmoState>();
+            state.hovered_op.zip(state.hovered_axis)
+        };
+        assert_eq!(
+            hover_at(cursor),
+            Some((GizmoOperation::TranslateAxis, GizmoAxis::X))
+        );
+        // Outside the viewport the gizmo never sees the cursor.
+        assert_eq!(hover_at(Vec2::new(50.0, 50.0)), None);
+    }
+
+    #[cfg(feature = "translate")]
+    #[test]
+    fn shift_translate_drag_moves_a_copy_of_the_target() {
+        use bevy::ecs::message::Messages;
+
+        let (mut app, window, camera, source) = windowed_app((
+            Transform::IDENTITY,
+            Name::new("crate"),
+            GizmoActive,
+            GizmoStableId::from("level/crate"),
+        ));
+        let cursor = x_cone_cursor(&app, camera);
+        let set_cursor = |app: &mut App, logical: Vec2| {
+            app.world_mut()
+                .get_mut::<Window>(window)
+                .unwrap()
+                .set_cursor_position(Some(logical));
+        };
+        set_cursor(&mut app, cursor);
+        app.update();
+
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(KeyCode::ShiftLeft);
+        app.world_mut()
+            .resource_mut::<ButtonInput<MouseButton>>()
+            .press(MouseButton::Left);
+        app.update();
+
+        let copy = app
+            .world()
+            .resource::<TransformGizmoState>()
+            .drag
+            .as_ref()
+            .unwrap()
+            .target;
+        assert_ne!(copy, source);
+        let duplicated: Vec<_> = app
+            .world_mut()
+            .resource_mut::<Messages<GizmoDuplicated>>()
+            .drain()
+            .collect();
+        assert_eq!(duplicated, [GizmoDuplicated { source, copy }]);
+        let world = app.world();
+        assert_eq!(world.get::<Name>(copy).unwrap().as_str(), "crate");
+        assert!(world.get::<TransformGizmoTarget>(copy).is_some());
+        assert!(world.get::<GizmoActive>(copy).is_some());
+        assert!(world.get::<GizmoStableId>(copy).is_none());
+        assert!(world.get::<GizmoActive>(source).is_none());
+        assert_eq!(
+            world.resource::<TransformGizmoState>().active_target,
+            Some(copy)
+        );
+
+        app.world_mut()
+            .resource_mut::<ButtonInput<MouseButton>>()
+            .clear_just_pressed(MouseButton::Left);
+        set_cursor(&mut app, cursor + Vec2::new(40.0, 0.0));
+        app.update();
+
+        let world = app.world();
+        assert!(world.get::<Transform>(copy).unwrap().translation.x > 0.1);
+        assert_eq!(
+            *world.get::<Transform>(source).unwrap(),
+            Transform::IDENTITY
+        );
+    }
+
+    /// Sweep provider for a wall filling `x >= 1`, touching only points that
+    /// move into it.
+    #[cfg(feature = "translate")]
+    fn wall_at_x_one() -> GizmoSweepProvider {
+        use crate::sweep::SweepHit;
+
+        GizmoSweepProvider::new(|_, from: Vec3, to: Vec3| {
+            if to.x <= 1.0 || from.x > 1.0 {
+                return None;
+            }
+            let t = (1.0 - from.x) / (to.x - from.x);
+            Some(SweepHit {
+                position: from.lerp(to, t),
+                normal: Vec3::NEG_X,
+            })
+        })
+    }
+
+    /// Apply one frame of drag motion followed by blocking, like `drag_gizmo`.
+    #[cfg(feature = "translate")]
+    fn blocked_step(
+        drag: &mut TransformGizmoDrag,
+        sweep: &GizmoSweepProvider,
+        slide: bool,
+        cursor: Vec3,
+        transform: &mut Transform,
+    ) {
+        let previous = transform.translation;
+        apply_drag_motion(
+            drag,
+            cursor,
+            &TransformGizmoSnap::default(),
+            transform,
+            None,
+        );
+        block_translation(drag, sweep, slide, previous, transform, None, None);
+    }
+
+    #[cfg(feature = "translate")]
+    #[test]
+    fn blocked_translate_stops_at_contact_and_resumes() {
+        let sweep = wall_at_x_one();
+        let mut transform = Transform::default();
+        let mut drag = TransformGizmoDrag::translate_x(transform);
+
+        for frame in 1..=20 {
+            let cursor = Vec3::X * 0.15 * frame as f32;
+            blocked_step(&mut drag, &sweep, false, cursor, &mut transform);
+            assert!(transform.translation.x <= 1.0 + 1.0e-5);
+            assert_eq!(drag.blocked, cursor.x > 1.0, "frame {frame}");
+        }
+        assert!((transform.translation.x - 1.0).abs() < 1.0e-5);
+        assert!(drag.origin_delta.abs_diff_eq(Vec3::X, 1.0e-5));
+
+        // The cursor far past the wall neither tunnels nor moves the target.
+        for _ in 0..3 {
+            blocked_step(&mut drag, &sweep, false, Vec3::X * 50.0, &mut transform);
+            assert!((transform.translation.x - 1.0).abs() < 1.0e-5);
+            assert!(drag.blocked);
+        }
+
+        // Pulling back frees the target and it follows the cursor again.
+        blocked_step(&mut drag, &sweep, false, Vec3::X * 0.4, &mut transform);
+        assert!(!drag.blocked);
+        assert!(transform
+            .translation
+            .abs_diff_eq(Vec3::new(0.4, 0.0, 0.0), 1.0e-5));
+    }
+
+    #[cfg(feature = "planes")]
+    #[test]
+    fn blocked_plane_drag_slides_along_the_wall() {
+        let sweep = wall_at_x_one();
+        let mut transform = Transform::default();
+        let mut drag = TransformGizmoDrag::translate_x(transform);
+        drag.op = GizmoOperation::TranslatePlane;
+        drag.axis = GizmoAxis::Y;
+        drag.plane_normal = Vec3::Y;
+        drag.plane_dir1 = Vec3::X;
+        drag.plane_dir2 = Vec3::Z;
+        drag.plane_axis1 = GizmoAxis::X;
+        drag.plane_axis2 = GizmoAxis::Z;
+
+        // Diagonal cursor path through the wall.
+        for frame in 1..=30 {
+            let cursor = Vec3::new(0.1, 0.0, 0.1) * frame as f32;
+            blocked_step(&mut drag, &sweep, true, cursor, &mut transform);
+        }
+        assert!(drag.blocked);
+        assert!(transform
+            .translation
+            .abs_diff_eq(Vec3::new(1.0, 0.0, 3.0), 1.0e-4));
+
+        // Without sliding the target stays where it first touched.
+        let mut stuck = Transform::default();
+        for frame in 1..=30 {
+            let cursor = Vec3::new(0.1, 0.0, 0.1) * frame as f32;
+            blocked_step(&mut drag, &sweep, false, cursor, &mut stuck);
+        }
+        assert!(stuck
+            .translation
+            .abs_diff_eq(Vec3::new(1.0, 0.0, 1.0), 1.0e-4));
+    }
+
+    #[cfg(feature = "planes")]
+    #[test]
+    fn plane_drag_follows_surfaces_and_falls_back_to_the_plane() {
+        use crate::types::SurfaceSnapSettings;
+
+        let mut transform = Transform::default();
+        let mut drag = TransformGizmoDrag::translate_x(transform);
+        drag.op = GizmoOperation::TranslatePlane;
+        drag.axis = GizmoAxis::Y;
+        drag.plane_normal = Vec3::Y;
+        drag.plane_dir1 = Vec3::X;
+        drag.plane_dir2 = Vec3::Z;
+        drag.plane_axis1 = GizmoAxis::X;
+        drag.plane_axis2 = GizmoAxis::Z;
+        let settings = SurfaceSnapSettings {
+            align_to_normal: true,
+            offset: 0.5,
+            ..default()
+        };
+        let mut step = |hit: Option<SurfaceHit>, transform: &mut Transform| {
+            let cursor = Vec3::new(2.0, 0.0, 1.0);
+            let snap = TransformGizmoSnap::default();
+            apply_drag_motion(&mut drag, cursor, &snap, transform, None);
+            place_on_surface(&mut drag, &settings, hit, transform, None);
+        };
+
+        // On a 45 degree slope the target sits above it, standing on it.
+        let normal = Vec3::new(-1.0, 1.0, 0.0).normalize();
+        let hit = SurfaceHit {
+            position: Vec3::new(2.0, 1.0, 1.0),
+            // Hits may report unnormalized normals.
+            normal: normal * 3.0,
+        };
+        step(Some(hit), &mut transform);
+        assert!(transform
+            .translation
+            .abs_diff_eq(hit.position + normal * 0.5, 1.0e-5));
+        assert!((transform.rotation * Vec3::Y).abs_diff_eq(normal, 1.0e-5));
+
+        // Off every surface the plane math and the start rotation take over.
+        step(None, &mut transform);
+        assert!(transform
+            .translation
+            .abs_diff_eq(Vec3::new(2.0, 0.0, 1.0), 1.0e-5));
+        assert!(transform.rotation.abs_diff_eq(Quat::IDENTITY, 1.0e-5));
+    }
+
+    #[test]
+    fn pick_priority_decides_between_nearly_equal_hits() {
+        let mut style = TransformGizmoStyle::default();
+        let pick = |style: &TransformGizmoStyle, hits: &[RankedHit]| {
+            let mut ranked = RankedHits::default();
+            for hit in hits {
+                ranked.offer(style, *hit);
+            }
+            let best = ranked.resolve(style.pick_priority_tolerance).unwrap();
+            best.op
+        };
+        let ring = RankedHit::exact(GizmoOperation::Rotate, GizmoAxis::Y, 5.0);
+        let cone =
+            |distance| RankedHit::exact(GizmoOperation::TranslateAxis, GizmoAxis::X, distance);
+        let cube = |distance, near_miss| RankedHit {
+            near_miss,
+            ..RankedHit::exact(GizmoOperation::ScaleAxis, GizmoAxis::X, distance)
+        };
+
+        // A cone just behind the ring still wins, one well behind it does not.
+        assert_eq!(
+            pick(&style, &[ring, cone(5.2)]),
+            GizmoOperation::TranslateAxis
+        );
+        assert_eq!(pick(&style, &[ring, cone(5.5)]), GizmoOperation::Rotate);
+        // A cube clearly in front of the ring wins, but not with its padding.
+        assert_eq!(
+            pick(&style, &[ring, cube(4.0, false)]),
+            GizmoOperation::ScaleAxis
+        );
+        assert_eq!(
+            pick(&style, &[ring, cube(4.0, true)]),
+            GizmoOperation::Rotate
+        );
+        assert_eq!(pick(&style, &[cube(4.0, true)]), GizmoOperation::ScaleAxis);
+        // Ties follow the configured order, and unlisted operations come last.
+        style.pick_priority = [
+            GizmoOperation::ScaleAxis,
+            GizmoOperation::Rotate,
+            GizmoOperation::TranslateAxis,
+            GizmoOperation::TranslatePlane,
+            GizmoOperation::ScaleUniform,
+        ];
+        assert_eq!(
+            pick(&style, &[ring, cube(5.1, false)]),
+            GizmoOperation::ScaleAxis
+        );
+        assert_eq!(pick(&style, &[ring, cone(4.9)]), GizmoOperation::Rotate);
+        let trackball = RankedHit::exact(GizmoOperation::RotateTrackball, GizmoAxis::X, 4.9);
+        assert_eq!(style.pick_rank(GizmoOperation::RotateTrackball), 5);
+        assert_eq!(
+            pick(&style, &[trackball, cone(5.0)]),
+            GizmoOperation::TranslateAxis
+        );
+    }
+
+    #[cfg(feature = "uniform-scale")]
+    #[test]
+    fn center_handle_priority_decides_between_overlapping_handles() {
+        use crate::types::AxisToggles;
+
+        // Only the two center handles, both under a ray down the Z axis.
+        let style = TransformGizmoStyle {
+            show_rotate: false,
+            show_translate_view: true,
+            translate_axes: AxisToggles::none(),
+            scale_axes: AxisToggles::none(),
+            ..default()
+        };
+        let frame = GizmoFrame::new(&GlobalTransform::IDENTITY, TransformGizmoSpace::World);
+        let pick = |x: f32, priority| {
+            let ray = Ray3d {
+                origin: Vec3::new(x, 0.05, 10.0),
+                direction: Dir3::NEG_Z,
+            };
+            let style = TransformGizmoStyle {
+                center_handle_priority: priority,
+                ..style.clone()
+            };
+            pick_gizmo_handle(&ray, &frame, &style).map(|hit| hit.op)
+        };
+
+        assert_eq!(
+            pick(0.05, GizmoCenterHandle::TranslateView),
+            Some(GizmoOperation::TranslateView)
+        );
+        assert_eq!(
+            pick(0.05, GizmoCenterHandle::ScaleUniform),
+            Some(GizmoOperation::ScaleUniform)
+        );
+        // Outside the view translation hit radius only uniform scale is hit.
+        assert_eq!(
+            pick(0.3, GizmoCenterHandle::TranslateView),
+            Some(GizmoOperation::ScaleUniform)
+        );
+    }
+
+    #[cfg(feature = "translate")]
+    #[test]
+    fn view_translation_moves_in_the_camera_plane_and_snaps_both_directions() {
+        let camera = Vec3::new(0.0, 0.0, 10.0);
+        let frame = GizmoFrame::new(&GlobalTransform::IDENTITY, TransformGizmoSpace::World)
+            .with_view_dir(Vec3::NEG_Z)
+            .with_camera_position(camera);
+        let ray_to = |point: Vec3| Ray3d {
+            origin: camera,
+            direction: Dir3::new(point - camera).unwrap(),
+        };
+        let grab = |transform: &Transform| {
+            begin_drag_from_ray(
+                &ray_to(Vec3::new(0.1, 0.1, 0.0)),
+                GizmoOperation::TranslateView,
+                GizmoAxis::X,
+                Entity::PLACEHOLDER,
+                &frame,
+                &GlobalTransform::IDENTITY,
+                transform,
+            )
+        };
+        let cursor = ray_to(Vec3::new(0.65, 0.3, 0.0));
+
+        let mut transform = Transform::IDENTITY;
+        let mut drag = grab(&transform);
+        apply_drag(
+            &mut drag,
+            &cursor,
+            &TransformGizmoSnap::default(),
+            &mut transform,
+        );
+        assert!(transform
+            .translation
+            .abs_diff_eq(Vec3::new(0.55, 0.2, 0.0), 1.0e-4));
+
+        let mut transform = Transform::IDENTITY;
+        let mut drag = grab(&transform);
+        apply_drag(&mut drag, &cursor, &absolute_snap(0.25), &mut transform);
+        assert!(transform
+            .translation
+            .abs_diff_eq(Vec3::new(0.5, 0.25, 0.0), 1.0e-4));
+    }
+
+    #[cfg(feature = "translate")]
+    fn absolute_snap(step: f32) -> TransformGizmoSnap {
+        use crate::types::AxisSnap;
+
+        TransformGizmoSnap {
+            translate: AxisSnap::uniform(step),
+            rotate: AxisSnap::uniform(step),
+            scale: AxisSnap::uniform(step),
+            mode: SnapMode::Absolute,
+            ..default()
+        }
+    }
+
+    #[cfg(feature = "translate")]
+    #[test]
+    fn absolute_axis_snap_lands_on_grid_lines() {
+        let start = Transform::from_xyz(0.37, 0.0, 0.0);
+        let mut transform = start;
+        let mut drag = TransformGizmoDrag::translate_x(start);
+
+        apply_drag_motion(
+            &mut drag,
+            Vec3::X * 0.6,
+            &absolute_snap(0.5),
+            &mut transform,
+            None,
+        );
+        assert!((transform.translation.x - 1.0).abs() < 1.0e-5);
+
+        // Relative snapping keeps the start's offset from the grid.
+        apply_drag_motion(
+            &mut drag,
+            Vec3::X * 0.6,
+            &TransformGizmoSnap {
+                mode: SnapMode::Relative,
+                ..absolute_snap(0.5)
+            },
+            &mut transform,
+            None,
+        );
+        assert!((transform.translation.x - 0.87).abs() < 1.0e-5);
+    }
+
+    #[cfg(feature = "translate")]
+    #[test]
+    fn absolute_snap_along_a_local_axis_measures_from_the_world_origin() {
+        // A local X axis along the world diagonal of the XY plane.
+        let axis_dir = Vec3::new(1.0, 1.0, 0.0).normalize();
+        let start = Transform::from_xyz(0.2, 0.1, 0.0);
+        let mut transform = start;
+        let mut drag = TransformGizmoDrag::translate_x(start);
+        drag.axis_dir = axis_dir;
+
+        apply_drag_motion(
+            &mut drag,
+            axis_dir * 0.4,
+            &absolute_snap(0.25),
+            &mut transform,
+            None,
+        );
+        let along = transform.translation.dot(axis_dir);
+        assert!(((along / 0.25).round() * 0.25 - along).abs() < 1.0e-5);
+        // Off-axis offset from the start is untouched.
+        let off_axis = |t: Vec3| t - axis_dir * t.dot(axis_dir);
+        assert!(off_axis(transform.translation).abs_diff_eq(off_axis(start.translation), 1.0e-5));
+    }
+
+    #[cfg(feature = "planes")]
+    #[test]
+    fn absolute_plane_snap_snaps_both_components() {
+        let start = Transform::from_xyz(0.37, 0.0, -0.12);
+        let mut transform = start;
+        let mut drag = TransformGizmoDrag::translate_x(start);
+        drag.op = GizmoOperation::TranslatePlane;
+        drag.plane_normal = Vec3::Y;
+        drag.plane_dir1 = Vec3::X;
+        drag.plane_dir2 = Vec3::Z;
+        drag.plane_axis1 = GizmoAxis::X;
+        drag.plane_axis2 = GizmoAxis::Z;
+
+        apply_drag_motion(
+            &mut drag,
+            Vec3::new(0.6, 0.0, 0.9),
+            &absolute_snap(0.5),
+            &mut transform,
+            None,
+        );
+        assert!(transform
+            .translation
+            .abs_diff_eq(Vec3::new(1.0, 0.0, 1.0), 1.0e-5));
+    }
+
+    #[cfg(feature = "translate")]
+    #[test]
+    fn releasing_the_snap_key_mid_drag_stops_snapping_without_a_jump() {
+        use crate::types::{AxisSnap, SnapActivation};
+
+        let snap = TransformGizmoSnap {
+            translate: AxisSnap::uniform(1.0),
+            activation: SnapActivation::WhileKeyHeld(KeyCode::ControlLeft),
+            fine_modifier: Some((KeyCode::ShiftLeft, 4.0)),
+            ..default()
+        };
+        let start = Transform::default();
+        let mut transform = start;
+        let mut drag = TransformGizmoDrag::translate_x(start);
+        let mut keys = ButtonInput::<KeyCode>::default();
+        let mut step = |keys: &ButtonInput<KeyCode>, x: f32| {
+            apply_drag_motion(
+                &mut drag,
+                Vec3::X * x,
+                &snap.resolve(keys),
+                &mut transform,
+                None,
+            );
+            transform.translation.x
+        };
+
+        assert_eq!(step(&keys, 0.6), 0.6);
+        keys.press(KeyCode::ControlLeft);
+        assert_eq!(step(&keys, 0.7), 1.0);
+        keys.press(KeyCode::ShiftLeft);
+        assert_eq!(step(&keys, 0.7), 0.75);
+        keys.release(KeyCode::ControlLeft);
+        assert!((step(&keys, 0.8) - 0.8).abs() < 1.0e-6);
+    }
+
+    #[cfg(feature = "translate")]
+    #[test]
+    fn disabled_snapping_keeps_its_increments_for_later() {
+        let mut snap = TransformGizmoSnap::standard();
+        let keys = ButtonInput::<KeyCode>::default();
+        let start = Transform::default();
+        let mut transform = start;
+        let mut drag = TransformGizmoDrag::translate_x(start);
+        let mut step = |snap: &TransformGizmoSnap, x: f32| {
+            apply_drag_motion(
+                &mut drag,
+                Vec3::X * x,
+                &snap.resolve(&keys),
+                &mut transform,
+                None,
+            );
+            transform.translation.x
+        };
+
+        assert_eq!(step(&snap, 0.7), 0.5);
+        snap.enable_all(false);
+        assert!((step(&snap, 0.7) - 0.7).abs() < 1.0e-6);
+        snap.enable_all(true);
+        assert_eq!(step(&snap, 0.7), 0.5);
+    }
+
+    #[cfg(all(feature = "translate", feature = "uniform-scale"))]
+    #[test]
+    fn uniform_scale_snap_lands_on_multiples_of_the_step() {
+        let start = Transform::from_scale(Vec3::splat(0.37));
+        let mut transform = start;
+        let mut drag = TransformGizmoDrag::translate_x(start);
+        drag.op = GizmoOperation::ScaleUniform;
+        drag.start_t = 1.0;
+
+        apply_drag_motion(
+            &mut drag,
+            Vec3::X * 2.0,
+            &absolute_snap(0.25),
+            &mut transform,
+            None,
+        );
+        assert!(transform.scale.abs_diff_eq(Vec3::splat(0.75), 1.0e-5));
+    }
+
+    /// Scale after dragging a scale handle that started one unit out on X,
+    /// of a target scaled 2, to `cursor`.
+    #[cfg(all(feature = "translate", feature = "scale"))]
+    fn scale_dragged_to(op: GizmoOperation, cursor: Vec3, snap: &TransformGizmoSnap) -> Vec3 {
+        let start = Transform::from_scale(Vec3::splat(2.0));
+        let mut transform = start;
+        let mut drag = TransformGizmoDrag::translate_x(start);
+        drag.op = op;
+        drag.start_t = 1.0;
+        drag.start_vector = Vec3::X;
+        apply_drag_motion(&mut drag, cursor, snap, &mut transform, None);
+        transform.scale
+    }
+
+    #[cfg(all(feature = "translate", feature = "scale"))]
+    #[test]
+    fn scale_axis_drags_stop_short_of_zero_by_default() {
+        let snap = TransformGizmoSnap {
+            min_scale: 0.05,
+            ..default()
+        };
+        let scale_x = |x: f32| scale_dragged_to(GizmoOperation::ScaleAxis, Vec3::X * x, &snap).x;
+
+        // The scale follows the ratio of distances from the origin.
+        assert_eq!(scale_x(0.5), 1.0);
+        assert_eq!(scale_x(3.0), 6.0);
+        // Crossing the origin holds at the minimum on the starting side.
+        assert_eq!(scale_x(0.0), 0.05);
+        assert_eq!(scale_x(-1.0), 0.05);
+        assert_eq!(scale_x(-3.0), 0.05);
+    }
+
+    #[cfg(all(feature = "translate", feature = "scale"))]
+    #[test]
+    fn scale_axis_drags_mirror_through_the_origin_when_allowed() {
+        use crate::types::AxisSnap;
+
+        let mut snap = TransformGizmoSnap {
+            min_scale: 0.05,
+            allow_negative_scale: true,
+            ..default()
+        };
+        let scale_x = |snap: &TransformGizmoSnap, x: f32| {
+            scale_dragged_to(GizmoOperation::ScaleAxis, Vec3::X * x, snap).x
+        };
+
+        // A factor of exactly -1 at the mirrored distance.
+        assert_eq!(scale_x(&snap, -1.0), -2.0);
+        assert_eq!(scale_x(&snap, -0.01), -0.05);
+        assert_eq!(scale_x(&snap, 0.01), 0.05);
+
+        // Snapping rounds the magnitude, on either side.
+        snap.scale = AxisSnap::uniform(0.5);
+        assert_eq!(scale_x(&snap, 0.7), 1.5);
+        assert_eq!(scale_x(&snap, -0.7), -1.5);
+        assert_eq!(scale_x(&snap, -0.1), -0.05);
+    }
+
+    #[cfg(all(feature = "translate", feature = "uniform-scale"))]
+    #[test]
+    fn uniform_scale_mirrors_only_when_allowed() {
+        let mut snap = TransformGizmoSnap::default();
+        let behind = Vec3::NEG_X * 1.5;
+        let scaled = |snap: &TransformGizmoSnap| {
+            scale_dragged_to(GizmoOperation::ScaleUniform, behind, snap)
+        };
+
+        assert_eq!(scaled(&snap), Vec3::splat(3.0));
+        snap.allow_negative_scale = true;
+        assert_eq!(scaled(&snap), Vec3::splat(-3.0));
+    }
+
+    #[cfg(feature = "uniform-scale")]
+    #[test]
+    fn uniform_scale_follows_screen_distance_in_every_direction() {
+        let camera = Vec3::new(3.0, 4.0, 5.0);
+        let forward = -camera.normalize();
+        let frame = GizmoFrame::new(&GlobalTransform::IDENTITY, TransformGizmoSpace::World)
+            .with_view_dir(forward)
+            .with_camera_position(camera);
+        // Points around the origin in the plane facing the camera, which all
+        // sit `radius` pixels times a constant away from it on screen.
+        let (right, up) = axis_basis(forward);
+        let ray_to = |angle: f32, radius: f32| Ray3d {
+            origin: camera,
+            direction: Dir3::new((right * angle.cos() + up * angle.sin()) * radius - camera)
+                .unwrap(),
+        };
+
+        let start = Transform::IDENTITY;
+        let mut drag = begin_drag_from_ray(
+            &ray_to(0.3, 1.0),
+            GizmoOperation::ScaleUniform,
+            GizmoAxis::X,
+            Entity::PLACEHOLDER,
+            &frame,
+            &GlobalTransform::IDENTITY,
+            &start,
+        );
+        let snap = TransformGizmoSnap::default();
+        for angle in [0.3, 1.5, 3.0, 4.4, 5.9] {
+            for (radius, factor) in [(2.0, 2.0), (0.5, 0.5), (0.0, 0.001)] {
+                let mut transform = start;
+                apply_drag(&mut drag, &ray_to(angle, radius), &snap, &mut transform);
+                assert!(
+                    transform.scale.abs_diff_eq(Vec3::splat(factor), 1.0e-4),
+                    "angle {angle}, radius {radius}: {}",
+                    transform.scale
+                );
+            }
+        }
+    }
+
+    #[cfg(all(feature = "translate", feature = "rotate"))]
+    #[test]
+    fn absolute_rotate_snap_lands_on_multiples_of_the_step() {
+        use std::f32::consts::FRAC_PI_4;
+
+        let start = Transform::from_rotation(Quat::from_rotation_z(0.3));
+        let mut transform = start;
+        let mut drag = TransformGizmoDrag::translate_x(start);
+        drag.op = GizmoOperation::Rotate;
+        drag.axis = GizmoAxis::Z;
+        drag.axis_dir = Vec3::Z;
+
+        // Drag 0.4 rad: 0.7 rad snaps to the nearest multiple of PI/4.
+        let (t1, t2) = axis_basis(Vec3::Z);
+        apply_drag_motion(
+            &mut drag,
+            t1 * 0.4_f32.cos() + t2 * 0.4_f32.sin(),
+            &absolute_snap(FRAC_PI_4),
+            &mut transform,
+            None,
+        );
+        let (axis, angle) = transform.rotation.to_axis_angle();
+        assert!(axis.abs_diff_eq(Vec3::Z, 1.0e-4));
+        assert!((angle - FRAC_PI_4).abs() < 1.0e-4);
+    }
+
+    #[cfg(feature = "rotate")]
+    #[test]
+    fn edge_on_rings_turn_with_the_cursor_around_the_origin_on_screen() {
+        // Looking down -Z, the X ring is seen exactly edge-on.
+        let camera = Vec3::new(0.0, 0.0, 10.0);
+        let frame = GizmoFrame::new(&GlobalTransform::IDENTITY, TransformGizmoSpace::World)
+            .with_view_dir(Vec3::NEG_Z)
+            .with_camera_position(camera);
+        let ray_to = |angle: f32| Ray3d {
+            origin: camera,
+            direction: Dir3::new(Vec3::new(angle.cos(), angle.sin(), 0.0) * 2.0 - camera).unwrap(),
+        };
+
+        let start = Transform::IDENTITY;
+        let mut drag = begin_drag_from_ray(
+            &ray_to(0.0),
+            GizmoOperation::Rotate,
+            GizmoAxis::X,
+            Entity::PLACEHOLDER,
+            &frame,
+            &GlobalTransform::IDENTITY,
+            &start,
+        );
+        assert!(drag.screen_rotation);
+
+        // Circling the origin on screen turns the target steadily, without
+        // flipping as the cursor crosses the ring's edge-on line.
+        let snap = TransformGizmoSnap::default();
+        for step in 1..=36 {
+            let angle = (step * 10) as f32 * std::f32::consts::PI / 180.0;
+            let mut transform = start;
+            apply_drag(&mut drag, &ray_to(angle), &snap, &mut transform);
+            assert!((drag.rotation_angle - angle).abs() < 1.0e-3, "step {step}");
+            let expected = Quat::from_rotation_x(angle);
+            assert!(
+                transform.rotation.angle_between(expected) < 1.0e-3,
+                "step {step}"
+            );
+        }
+    }
+
+    #[cfg(feature = "rotate")]
+    #[test]
+    fn rotation_drags_keep_counting_past_half_a_turn() {
+        use crate::types::AxisSnap;
+
+        let start = Transform::IDENTITY;
+        let frame = GizmoFrame::new(&GlobalTransform::IDENTITY, TransformGizmoSpace::World)
+            .with_view_dir(Vec3::NEG_Z);
+        let (t1, t2) = axis_basis(Vec3::Z);
+        let mut drag = begin_drag_from_ray(
+            &Ray3d::new(t1 + Vec3::Z * 5.0, Dir3::NEG_Z),
+            GizmoOperation::Rotate,
+            GizmoAxis::Z,
+            Entity::PLACEHOLDER,
+            &frame,
+            &GlobalTransform::IDENTITY,
+            &start,
+        );
+        assert!(!drag.screen_rotation);
 
-/// End the drag operation when the mouse button is released.
-pub fn end_drag(buttons: Res<ButtonInput<MouseButton>>, mut state: ResMut<TransformGizmoState>) {
-    if buttons.just_released(MouseButton::Left) {
-        state.drag = None;
+        // Spin one and a half turns clockwise, then a little further.
+        let snap = TransformGizmoSnap {
+            rotate: AxisSnap::uniform(15.0_f32.to_radians()),
+            ..default()
+        };
+        let mut transform = start;
+        for degrees in (0..=547).step_by(7).map(|d| -(d as f32)) {
+            let angle = degrees.to_radians();
+            let v = t1 * angle.cos() + t2 * angle.sin();
+            apply_drag_motion(&mut drag, v, &snap, &mut transform, None);
+        }
+        assert!((drag.rotation_angle - (-546.0_f32).to_radians()).abs() < 1.0e-3);
+        let expected = Quat::from_rotation_z((-540.0_f32).to_radians());
+        assert!(transform.rotation.angle_between(expected) < 1.0e-3);
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    #[cfg(feature = "rotate")]
+    #[test]
+    fn snapped_rotation_drags_report_whole_turns() {
+        use crate::types::{AxisSnap, GizmoDelta};
+
+        let start = Transform::IDENTITY;
+        let frame = GizmoFrame::new(&GlobalTransform::IDENTITY, TransformGizmoSpace::World)
+            .with_view_dir(Vec3::NEG_Z);
+        let (t1, t2) = axis_basis(Vec3::Z);
+        let mut drag = begin_drag_from_ray(
+            &Ray3d::new(t1 + Vec3::Z * 5.0, Dir3::NEG_Z),
+            GizmoOperation::Rotate,
+            GizmoAxis::Z,
+            Entity::PLACEHOLDER,
+            &frame,
+            &GlobalTransform::IDENTITY,
+            &start,
+        );
+
+        // Two full turns snapped to quarter turns land on 720°, not 0°.
+        let snap = TransformGizmoSnap {
+            rotate: AxisSnap::uniform(90.0_f32.to_radians()),
+            ..default()
+        };
+        let mut transform = start;
+        for degrees in (0..=725).step_by(5).map(|d| d as f32) {
+            let angle = degrees.to_radians();
+            let v = t1 * angle.cos() + t2 * angle.sin();
+            apply_drag_motion(&mut drag, v, &snap, &mut transform, None);
+        }
+        drag.last_transform = transform;
+        let GizmoDelta::Rotation { angle, .. } = drag.delta() else {
+            panic!("expected a rotation");
+        };
+        assert!((angle.to_degrees() - 720.0).abs() < 1.0e-2, "{angle}");
+    }
+
+    #[cfg(all(feature = "translate", feature = "rotate", feature = "scale"))]
+    #[test]
+    fn state_drag_delta_reports_the_total_change_of_the_drag() {
+        use crate::types::GizmoDelta;
+
+        let start = Transform::from_xyz(1.0, 0.0, 0.0);
+        let snap = TransformGizmoSnap::default();
+        let delta_after = |mut drag: TransformGizmoDrag, motions: &[Vec3]| {
+            let mut transform = start;
+            for v in motions {
+                apply_drag_motion(&mut drag, *v, &snap, &mut transform, None);
+            }
+            drag.last_transform = transform;
+            let state = TransformGizmoState {
+                drag: Some(drag),
+                ..default()
+            };
+            state.drag_delta().unwrap()
+        };
+        assert_eq!(TransformGizmoState::default().drag_delta(), None);
+
+        let moved = delta_after(TransformGizmoDrag::translate_x(start), &[Vec3::X * 1.5]);
+        let GizmoDelta::Translation(offset) = moved else {
+            panic!("expected a translation, got {moved:?}");
+        };
+        assert!(offset.abs_diff_eq(Vec3::X * 1.5, 1.0e-5));
+        assert_eq!(moved.to_string(), "Δx = 1.50  Δy = 0.00  Δz = 0.00");
+
+        // A turn and a bit around Z counts past the half turn.
+        let mut drag = TransformGizmoDrag::translate_x(start);
+        drag.op = GizmoOperation::Rotate;
+        drag.axis = GizmoAxis::Z;
+        drag.axis_dir = Vec3::Z;
+        let (t1, t2) = axis_basis(Vec3::Z);
+        let motions: Vec<_> = (0..=40)
+            .map(|step| (step * 10) as f32 * std::f32::consts::PI / 180.0)
+            .map(|angle| t1 * angle.cos() + t2 * angle.sin())
+            .collect();
+        let GizmoDelta::Rotation { axis, angle } = delta_after(drag, &motions) else {
+            panic!("expected a rotation");
+        };
+        assert!(axis.abs_diff_eq(Vec3::Z, 1.0e-5));
+        assert!((angle.to_degrees() - 400.0).abs() < 1.0e-2, "{angle}");
+        let turned = GizmoDelta::Rotation { axis, angle }.rotation();
+        assert!(turned.abs_diff_eq(Quat::from_rotation_z(40f32.to_radians()), 1.0e-4));
+
+        let mut drag = TransformGizmoDrag::translate_x(start);
+        drag.op = GizmoOperation::ScaleAxis;
+        drag.start_t = 1.0;
+        let scaled = delta_after(drag, &[Vec3::X * 2.0]);
+        assert_eq!(scaled, GizmoDelta::Scale(Vec3::new(2.0, 1.0, 1.0)));
+    }
+
+    #[cfg(feature = "translate")]
+    #[test]
+    fn endpoint_drag_keeps_opposite_end_fixed_under_rotated_parent() {
+        use crate::types::AxisSnap;
+
+        let endpoints = GizmoEndpoints::new(GizmoAxis::Z, 0.5);
+        let parent = GlobalTransform::from(
+            Transform::from_xyz(1.0, 2.0, 0.0)
+                .with_rotation(Quat::from_rotation_x(0.8) * Quat::from_rotation_y(-0.4)),
+        );
+        let start = Transform::from_xyz(0.5, 0.0, 0.0).with_rotation(Quat::from_rotation_y(0.3));
+        let end_world = |transform: &Transform, end| {
+            parent.transform_point(transform.transform_point(endpoints.local_position(end)))
+        };
+        let fixed_world = end_world(&start, EndpointSign::Negative);
+
+        let mut drag = TransformGizmoDrag::translate_x(start);
+        drag.op = GizmoOperation::Endpoint {
+            end: EndpointSign::Positive,
+        };
+        drag.axis = GizmoAxis::Z;
+        drag.origin = end_world(&start, EndpointSign::Positive);
+        drag.endpoints = Some(endpoints);
+        drag.fixed_endpoint =
+            start.transform_point(endpoints.local_position(EndpointSign::Negative));
+
+        let mut transform = start;
+        for frame in 1..=40 {
+            let cursor = Vec3::new(0.1, -0.05, 0.08) * frame as f32;
+            apply_drag_motion(
+                &mut drag,
+                cursor,
+                &TransformGizmoSnap::default(),
+                &mut transform,
+                Some(&parent),
+            );
+            let fixed = end_world(&transform, EndpointSign::Negative);
+            assert!(
+                fixed.distance(fixed_world) < 1.0e-4,
+                "fixed end moved at frame {frame}"
+            );
+            let moved = end_world(&transform, EndpointSign::Positive);
+            assert!(moved.distance(drag.origin + cursor) < 1.0e-3);
+        }
+
+        // Snapping applies to the dragged end's position.
+        let snap = TransformGizmoSnap {
+            translate: AxisSnap::uniform(0.25),
+            ..default()
+        };
+        apply_drag_motion(
+            &mut drag,
+            Vec3::new(0.33, 0.61, -0.2),
+            &snap,
+            &mut transform,
+            Some(&parent),
+        );
+        let moved = end_world(&transform, EndpointSign::Positive);
+        assert!(moved.abs_diff_eq((moved / 0.25).round() * 0.25, 1.0e-4));
+        assert!(end_world(&transform, EndpointSign::Negative).distance(fixed_world) < 1.0e-4);
+    }
+
+    #[cfg(feature = "translate")]
+    #[test]
+    fn drag_continues_across_reparent_without_jumping() {
+        let snap = TransformGizmoSnap::default();
+        let start = Transform::from_xyz(1.0, 0.0, 0.0);
+        let mut transform = start;
+        let mut drag = TransformGizmoDrag::translate_x(start);
+        drag.history.push(start);
+
+        let cursor = |frame: usize| Vec3::new(0.1 * frame as f32, 0.0, 0.0);
+        for frame in 1..=5 {
+            apply_drag_motion(&mut drag, cursor(frame), &snap, &mut transform, None);
+            drag.history.record(transform);
+        }
+
+        // Move the target under a rotated, scaled parent mid-drag.
+        let parent = Transform::from_xyz(0.0, 0.0, 3.0)
+            .with_rotation(Quat::from_rotation_y(std::f32::consts::FRAC_PI_2))
+            .with_scale(Vec3::splat(2.0))
+            .compute_affine();
+        transform = nearest_transform(parent.inverse() * transform.compute_affine());
+        rebase_drag_to_parent(&mut drag, None, Some(parent), false);
+        let parent_global = GlobalTransform::from(parent);
+
+        let mut previous = parent_global.transform_point(transform.translation);
+        assert!(previous.abs_diff_eq(Vec3::new(1.5, 0.0, 0.0), 1.0e-4));
+        for frame in 6..=10 {
+            apply_drag_motion(
+                &mut drag,
+                cursor(frame),
+                &snap,
+                &mut transform,
+                Some(&parent_global),
+            );
+            let world = parent_global.transform_point(transform.translation);
+            assert!(
+                (world - previous).abs_diff_eq(Vec3::X * 0.1, 1.0e-4),
+                "jump at frame {frame}"
+            );
+            previous = world;
+        }
+        assert!(previous.abs_diff_eq(Vec3::new(2.0, 0.0, 0.0), 1.0e-4));
+
+        // Cancelling or scrubbing restores poses in the new parent space.
+        let initial = parent_global.transform_point(drag.initial_transform.translation);
+        assert!(initial.abs_diff_eq(start.translation, 1.0e-4));
+        let oldest = drag.history.get(drag.history.len() - 1).unwrap();
+        assert!(parent_global
+            .transform_point(oldest.translation)
+            .abs_diff_eq(start.translation, 1.0e-4));
+        assert_eq!(drag.start_parent, Some(parent_global));
+    }
+
+    /// Release the mouse over `end_drag` with `drag` in progress, returning
+    /// the messages it wrote.
+    fn release(world: &mut World, drag: TransformGizmoDrag) -> Vec<GizmoDragEnded> {
+        use bevy::ecs::message::Messages;
+        use bevy::ecs::system::RunSystemOnce;
+
+        world.init_resource::<Messages<GizmoDragEnded>>();
+        world.init_resource::<Messages<GizmoHandleClicked>>();
+        world.init_resource::<TransformGizmoConfig>();
+        world.insert_resource(TransformGizmoState {
+            drag: Some(drag),
+            ..default()
+        });
+        let button = world.resource::<TransformGizmoConfig>().drag_input.button;
+        let mut buttons = ButtonInput::<MouseButton>::default();
+        buttons.press(button);
+        buttons.release(button);
+        world.insert_resource(buttons);
+        world.run_system_once(end_drag).unwrap();
+
+        assert!(world.resource::<TransformGizmoState>().drag.is_none());
+        let messages = world.resource::<Messages<GizmoDragEnded>>();
+        messages.iter_current_update_messages().copied().collect()
+    }
+
+    #[cfg(feature = "translate")]
+    #[test]
+    fn escape_cancels_the_drag_and_restores_the_initial_transform() {
+        use bevy::ecs::message::Messages;
+        use bevy::ecs::system::RunSystemOnce;
+
+        let mut world = World::new();
+        let start = Transform::from_xyz(1.0, 0.0, 0.0);
+        let moved = Transform::from_xyz(3.0, 0.0, 0.0);
+        let target = world.spawn((moved, TransformGizmoTarget)).id();
+        let mut drag = TransformGizmoDrag::translate_x(start);
+        drag.target = target;
+        drag.last_transform = moved;
+
+        world.init_resource::<Messages<GizmoDragging>>();
+        world.init_resource::<Messages<GizmoDragEnded>>();
+        world.init_resource::<Messages<GizmoTransformRequest>>();
+        world.init_resource::<TransformGizmoKeybinds>();
+        world.init_resource::<TransformGizmoConfig>();
+        world.init_resource::<GizmoCameraSelection>();
+        world.init_resource::<GizmoPointer>();
+        world.insert_resource(TransformGizmoState {
+            drag: Some(drag),
+            ..default()
+        });
+        let mut keys = ButtonInput::<KeyCode>::default();
+        keys.press(KeyCode::Escape);
+        world.insert_resource(keys);
+        world.run_system_once(apply_drag_keybinds).unwrap();
+
+        assert!(world.resource::<TransformGizmoState>().drag.is_none());
+        assert_eq!(*world.get::<Transform>(target).unwrap(), start);
+        let ended: Vec<_> = world
+            .resource::<Messages<GizmoDragEnded>>()
+            .iter_current_update_messages()
+            .copied()
+            .collect();
+        assert_eq!(ended.len(), 1);
+        assert!(ended[0].cancelled);
+        assert_eq!(ended[0].final_transform, start);
+        let delta = world
+            .resource::<Messages<GizmoDragging>>()
+            .iter_current_update_messages()
+            .map(|dragging| dragging.delta_translation)
+            .sum::<Vec3>();
+        assert!(delta.abs_diff_eq(Vec3::new(-2.0, 0.0, 0.0), 1.0e-6));
+    }
+
+    #[cfg(feature = "translate")]
+    #[test]
+    fn switching_axis_mid_drag_continues_without_jumping() {
+        let snap = TransformGizmoSnap::default();
+        let mut transform = Transform::default();
+        let mut drag = TransformGizmoDrag::translate_x(transform);
+        apply_drag_motion(&mut drag, Vec3::X, &snap, &mut transform, None);
+        assert_eq!(transform.translation, Vec3::X);
+
+        // Camera looks down -Z; the cursor is somewhere off the Y axis.
+        let ray = Ray3d::new(Vec3::new(1.3, 0.4, 10.0), Dir3::NEG_Z);
+        reconstrain_drag(
+            &mut drag,
+            GizmoOperation::TranslateAxis,
+            GizmoAxis::Y,
+            TransformGizmoSpace::World,
+            &GlobalTransform::from_xyz(0.0, 0.0, 10.0),
+            &ray,
+            &transform,
+            None,
+        );
+        assert_eq!(drag.handle().axis, GizmoAxis::Y);
+
+        let cursor = Vec3::new(0.3, 0.4, 0.0);
+        apply_drag_motion(&mut drag, cursor, &snap, &mut transform, None);
+        assert!(transform.translation.abs_diff_eq(Vec3::X, 1.0e-6));
+        apply_drag_motion(&mut drag, cursor + Vec3::Y, &snap, &mut transform, None);
+        assert!(transform
+            .translation
+            .abs_diff_eq(Vec3::new(1.0, 1.0, 0.0), 1.0e-6));
+
+        assert_eq!(
+            constrained_operation(GizmoOperation::TranslateAxis, true),
+            operation_compiled(GizmoOperation::TranslatePlane)
+                .then_some(GizmoOperation::TranslatePlane)
+        );
+        assert_eq!(
+            constrained_operation(
+                GizmoOperation::Endpoint {
+                    end: EndpointSign::Positive
+                },
+                false
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn drag_end_reports_initial_and_final_transforms() {
+        let mut world = World::new();
+        let start = Transform::from_xyz(1.0, 0.0, 0.0);
+        let moved = start
+            .with_translation(Vec3::new(2.5, 0.0, 0.0))
+            .with_rotation(Quat::from_rotation_y(0.3));
+        let target = world.spawn(moved).id();
+        let mut drag = TransformGizmoDrag::translate_x(start);
+        drag.target = target;
+        drag.last_transform = moved;
+        drag.origin_delta = Vec3::X * 1.5;
+        drag.pivot_delta = Vec3::X * 1.5;
+
+        let ended = release(&mut world, drag);
+        assert_eq!(
+            ended,
+            vec![GizmoDragEnded {
+                handle: GizmoHandleId::new(target, GizmoOperation::TranslateAxis, GizmoAxis::X),
+                initial_transform: start,
+                final_transform: moved,
+                origin_delta: Vec3::X * 1.5,
+                pivot_delta: Vec3::X * 1.5,
+                cancelled: false,
+            }]
+        );
+    }
+
+    #[cfg(feature = "translate")]
+    #[test]
+    fn drags_follow_the_configured_button() {
+        use bevy::ecs::message::Messages;
+        use bevy::ecs::system::RunSystemOnce;
+
+        let mut world = World::new();
+        world.init_resource::<Messages<GizmoDragEnded>>();
+        world.init_resource::<Messages<GizmoHandleClicked>>();
+        world.insert_resource(TransformGizmoConfig {
+            drag_input: GizmoDragInput {
+                button: MouseButton::Right,
+                ..default()
+            },
+            ..default()
+        });
+        let target = world.spawn(Transform::default()).id();
+        let mut drag = TransformGizmoDrag::translate_x(Transform::default());
+        drag.target = target;
+        world.insert_resource(TransformGizmoState {
+            drag: Some(drag.clone()),
+            ..default()
+        });
+
+        // Releasing the left button leaves a right-button drag alone.
+        let mut buttons = ButtonInput::<MouseButton>::default();
+        buttons.press(MouseButton::Left);
+        buttons.release(MouseButton::Left);
+        world.insert_resource(buttons);
+        world.run_system_once(end_drag).unwrap();
+        assert!(world.resource::<TransformGizmoState>().drag.is_some());
+
+        assert_eq!(release(&mut world, drag).len(), 1);
+    }
+
+    #[test]
+    fn modifier_keys_gate_drag_starts() {
+        let input = GizmoDragInput {
+            required_modifiers: vec![KeyCode::AltLeft],
+            blocked_modifiers: vec![KeyCode::ControlLeft],
+            ..default()
+        };
+        let mut keys = ButtonInput::<KeyCode>::default();
+        assert!(!input.modifiers_allow(&keys));
+        keys.press(KeyCode::AltLeft);
+        assert!(input.modifiers_allow(&keys));
+        keys.press(KeyCode::ControlLeft);
+        assert!(!input.modifiers_allow(&keys));
+        assert!(GizmoDragInput::default().modifiers_allow(&ButtonInput::default()));
+    }
+
+    #[test]
+    fn drag_end_fires_with_last_known_transform_after_despawn() {
+        let mut world = World::new();
+        let start = Transform::from_xyz(-1.0, 2.0, 0.0);
+        let last = Transform::from_xyz(0.5, 2.0, 0.0);
+        let target = world.spawn(last).id();
+        let mut drag = TransformGizmoDrag::translate_x(start);
+        drag.target = target;
+        drag.last_transform = last;
+        world.despawn(target);
+
+        let ended = release(&mut world, drag);
+        assert_eq!(ended.len(), 1);
+        assert_eq!(ended[0].handle.target, target);
+        assert_eq!(ended[0].initial_transform, start);
+        assert_eq!(ended[0].final_transform, last);
+    }
+
+    #[test]
+    fn drag_delta_reports_frame_to_frame_change() {
+        let drag = TransformGizmoDrag::translate_x(Transform::default());
+        let before = Transform::from_xyz(1.0, 0.0, 0.0).with_scale(Vec3::new(2.0, 0.0, 1.0));
+        let after = Transform::from_xyz(1.5, 0.0, -1.0)
+            .with_rotation(Quat::from_rotation_x(0.2))
+            .with_scale(Vec3::new(3.0, 4.0, 1.0));
+
+        let delta = drag_delta(&drag, &before, &after);
+        assert!(delta
+            .delta_translation
+            .abs_diff_eq(Vec3::new(0.5, 0.0, -1.0), 1.0e-6));
+        assert!((delta.delta_rotation * before.rotation).abs_diff_eq(after.rotation, 1.0e-6));
+        // The zero Y scale cannot be divided by and reports no change.
+        assert_eq!(delta.delta_scale, Vec3::new(1.5, 1.0, 1.0));
+    }
+
+    #[test]
+    fn drag_history_overwrites_oldest_entries_at_capacity() {
+        let mut history = DragHistory::new(4, 1);
+        for i in 0..10 {
+            history.record(Transform::from_xyz(i as f32, 0.0, 0.0));
+        }
+        assert_eq!(history.len(), 4);
+        assert_eq!(history.get(0).unwrap().translation.x, 9.0);
+        assert_eq!(history.get(3).unwrap().translation.x, 6.0);
+        assert!(history.get(4).is_none());
+    }
+
+    #[cfg(feature = "translate")]
+    #[test]
+    fn world_x_drag_under_turned_scaled_parent_follows_the_cursor() {
+        let parent = GlobalTransform::from(
+            Transform::from_xyz(1.0, 0.0, -2.0)
+                .with_rotation(Quat::from_rotation_y(std::f32::consts::FRAC_PI_2))
+                .with_scale(Vec3::splat(2.0)),
+        );
+        let start = Transform::from_xyz(0.5, 0.25, 0.0);
+        let start_world = parent.transform_point(start.translation);
+        let mut transform = start;
+        let mut drag = TransformGizmoDrag::translate_x(start);
+        drag.origin = start_world;
+        drag.plane_origin = start_world;
+
+        for frame in 1..=20 {
+            let cursor = Vec3::X * 0.1 * frame as f32;
+            apply_drag_motion(
+                &mut drag,
+                cursor,
+                &TransformGizmoSnap::default(),
+                &mut transform,
+                Some(&parent),
+            );
+            let world = parent.transform_point(transform.translation);
+            assert!(
+                world.abs_diff_eq(start_world + cursor, 1.0e-5),
+                "child at {world} on frame {frame}"
+            );
+        }
+    }
+
+    #[cfg(all(feature = "translate", feature = "rotate"))]
+    #[test]
+    fn world_rotation_under_turned_scaled_parent_is_conjugated_into_parent_space() {
+        let parent = GlobalTransform::from(
+            Transform::from_rotation(Quat::from_rotation_y(std::f32::consts::FRAC_PI_2))
+                .with_scale(Vec3::splat(2.0)),
+        );
+        let start = Transform::from_rotation(Quat::from_rotation_z(0.3));
+        let mut transform = start;
+        let mut drag = TransformGizmoDrag::translate_x(start);
+        drag.op = GizmoOperation::Rotate;
+        drag.axis_dir = Vec3::X;
+        drag.plane_normal = Vec3::X;
+        drag.start_rotation = parent.rotation() * start.rotation;
+        let (t1, _) = axis_basis(Vec3::X);
+
+        // Swing the cursor half a radian about world X from where the drag
+        // started.
+        let cursor = Quat::from_axis_angle(Vec3::X, 0.5) * t1;
+        apply_drag_motion(
+            &mut drag,
+            cursor,
+            &TransformGizmoSnap::default(),
+            &mut transform,
+            Some(&parent),
+        );
+
+        let world_rotation = parent.rotation() * transform.rotation;
+        let expected = Quat::from_axis_angle(Vec3::X, 0.5) * drag.start_rotation;
+        assert!(world_rotation.abs_diff_eq(expected, 1.0e-5));
+    }
+
+    /// A parent stretched along Y, and a child turned 45 degrees about Z
+    /// under it, so the child is sheared.
+    #[cfg(any(feature = "translate", feature = "rotate"))]
+    fn sheared_child() -> (GlobalTransform, Transform, GizmoFrame) {
+        let parent = GlobalTransform::from(Transform::from_scale(Vec3::new(1.0, 3.0, 1.0)));
+        let start = Transform::from_rotation(Quat::from_rotation_z(std::f32::consts::FRAC_PI_4));
+        let frame = GizmoFrame::from_context(
+            &parent.mul_transform(start),
+            TransformGizmoSpace::Local,
+            Some(&parent),
+            None,
+        );
+        (parent, start, frame)
+    }
+
+    #[cfg(feature = "rotate")]
+    #[test]
+    fn rotation_under_non_uniformly_scaled_parent_turns_about_the_drawn_axis() {
+        use crate::types::GizmoDelta;
+
+        let quarter = std::f32::consts::FRAC_PI_2;
+        let (parent, start, frame) = sheared_child();
+        for (axis, expected) in [
+            (
+                GizmoAxis::Z,
+                Quat::from_rotation_z(3.0 * std::f32::consts::FRAC_PI_4),
+            ),
+            (
+                GizmoAxis::X,
+                start.rotation * Quat::from_rotation_x(quarter),
+            ),
+        ] {
+            let axis_dir = frame.axis_dir(axis, AxisKind::Rotate).normalize();
+            let frame = frame.with_view_dir(-axis_dir);
+            let (t1, t2) = axis_basis(axis_dir);
+            let mut drag = begin_drag_from_ray(
+                &Ray3d::new(
+                    frame.origin + t1 + axis_dir * 5.0,
+                    Dir3::new(-axis_dir).unwrap(),
+                ),
+                GizmoOperation::Rotate,
+                axis,
+                Entity::PLACEHOLDER,
+                &frame,
+                &parent.mul_transform(start),
+                &start,
+            );
+            drag.start_parent = Some(parent);
+
+            // A quarter turn about the ring's axis on screen.
+            let mut transform = start;
+            apply_drag_motion(
+                &mut drag,
+                t2,
+                &TransformGizmoSnap::default(),
+                &mut transform,
+                Some(&parent),
+            );
+            assert!(
+                transform.rotation.abs_diff_eq(expected, 1.0e-5)
+                    || transform.rotation.abs_diff_eq(-expected, 1.0e-5),
+                "{axis:?}: {:?}",
+                transform.rotation
+            );
+
+            drag.last_transform = transform;
+            let GizmoDelta::Rotation { angle, .. } = drag.delta() else {
+                panic!("{axis:?}: not a rotation");
+            };
+            assert!((angle - quarter).abs() < 1.0e-4, "{axis:?}: {angle}");
+        }
+    }
 
+    #[cfg(feature = "translate")]
+    #[test]
+    fn translate_axes_follow_a_sheared_child() {
+        let (parent, start, frame) = sheared_child();
+        let x = frame.axis_dir(GizmoAxis::X, AxisKind::Translate);
+        let drawn = parent
+            .affine()
+            .transform_vector3(start.rotation * Vec3::X)
+            .normalize();
+        assert!(x.abs_diff_eq(drawn, 1.0e-5), "{x} is not {drawn}");
+
+        // Dragging the X cone moves the child along the drawn axis.
+        let frame = frame.with_view_dir(Vec3::NEG_Z);
+        let mut drag = begin_drag_from_ray(
+            &Ray3d::new(frame.origin + x + Vec3::Z * 5.0, Dir3::NEG_Z),
+            GizmoOperation::TranslateAxis,
+            GizmoAxis::X,
+            Entity::PLACEHOLDER,
+            &frame,
+            &parent.mul_transform(start),
+            &start,
+        );
+        let mut transform = start;
+        apply_drag_motion(
+            &mut drag,
+            x * 3.0,
+            &TransformGizmoSnap::default(),
+            &mut transform,
+            Some(&parent),
+        );
+        let moved = parent.transform_point(transform.translation)
+            - parent.transform_point(start.translation);
+        assert!(moved.abs_diff_eq(x * 2.0, 1.0e-4), "moved by {moved}");
+    }
+
+    #[cfg(feature = "translate")]
     #[test]
     fn world_drag_delta_is_converted_to_parent_local_space() {
         let parent = GlobalTransform::from(
@@ -638,4 +5589,692 @@ mod tests {
             .transform_vector3(local_delta)
             .abs_diff_eq(world_delta, 1.0e-5));
     }
+
+    #[test]
+    fn gizmo_spaces_take_their_axes_from_the_context() {
+        let rotation = Quat::from_rotation_y(0.5);
+        let target = GlobalTransform::from(Transform::from_rotation(rotation));
+        let parent = GlobalTransform::from(Transform::from_rotation(Quat::from_rotation_x(0.7)));
+        let camera = GlobalTransform::from(
+            Transform::from_xyz(3.0, 4.0, 5.0).looking_at(Vec3::ZERO, Vec3::Y),
+        );
+        let axes = |space, parent, camera| {
+            let frame = GizmoFrame::from_context(&target, space, parent, camera);
+            let axis = |axis| frame.axis_dir(axis, AxisKind::Translate);
+            (axis(GizmoAxis::X), axis(GizmoAxis::Z))
+        };
+        let rotated = |rotation: Quat| (rotation * Vec3::X, rotation * Vec3::Z);
+        let close = |(x, z): (Vec3, Vec3), (ex, ez): (Vec3, Vec3)| {
+            x.abs_diff_eq(ex, 1.0e-5) && z.abs_diff_eq(ez, 1.0e-5)
+        };
+
+        let parent_space = TransformGizmoSpace::Parent;
+        assert!(close(
+            axes(parent_space, Some(&parent), None),
+            rotated(parent.rotation())
+        ));
+        assert!(close(axes(parent_space, None, None), (Vec3::X, Vec3::Z)));
+        // View axes run right and toward the viewer.
+        let view = TransformGizmoSpace::View;
+        assert!(close(
+            axes(view, None, Some(&camera)),
+            (*camera.right(), *camera.back())
+        ));
+        assert!(close(axes(view, None, None), (Vec3::X, Vec3::Z)));
+        let custom = Quat::from_rotation_z(1.2);
+        assert!(close(
+            axes(TransformGizmoSpace::Custom(custom), None, None),
+            rotated(custom)
+        ));
+
+        // Scale stays local whatever the space.
+        let frame = GizmoFrame::from_context(&target, view, None, Some(&camera));
+        assert!(frame
+            .axis_dir(GizmoAxis::X, AxisKind::Scale)
+            .abs_diff_eq(rotation * Vec3::X, 1.0e-5));
+    }
+
+    #[cfg(all(feature = "translate", feature = "planes"))]
+    #[test]
+    fn view_space_z_plane_drags_move_in_the_screen_plane() {
+        let camera = GlobalTransform::from(
+            Transform::from_xyz(3.0, 4.0, 5.0).looking_at(Vec3::ZERO, Vec3::Y),
+        );
+        let frame = GizmoFrame::from_context(
+            &GlobalTransform::IDENTITY,
+            TransformGizmoSpace::View,
+            None,
+            Some(&camera),
+        )
+        .with_view_dir(*camera.forward())
+        .with_camera_position(camera.translation());
+        let ray_at = |point: Vec3| {
+            Ray3d::new(
+                camera.translation(),
+                Dir3::new(point - camera.translation()).unwrap(),
+            )
+        };
+
+        let start = Transform::IDENTITY;
+        let grab = (*camera.right() + *camera.up()) * 0.3;
+        let mut drag = begin_drag_from_ray(
+            &ray_at(grab),
+            GizmoOperation::TranslatePlane,
+            GizmoAxis::Z,
+            Entity::PLACEHOLDER,
+            &frame,
+            &GlobalTransform::IDENTITY,
+            &start,
+        );
+        assert!(drag.plane_normal.abs_diff_eq(*camera.back(), 1.0e-5));
+
+        let mut transform = start;
+        let snap = TransformGizmoSnap::default();
+        let offset = *camera.right() * 0.5 - *camera.up() * 0.25;
+        apply_drag(&mut drag, &ray_at(grab + offset), &snap, &mut transform);
+        assert!(
+            transform.translation.abs_diff_eq(offset, 1.0e-4),
+            "{}",
+            transform.translation
+        );
+    }
+
+    /// 100 targets on a grid in front of a perspective camera, the camera's
+    /// sizing, and a pointer ray aimed into the middle of the grid.
+    fn hover_scene(style: &TransformGizmoStyle) -> (Vec<GlobalTransform>, GizmoSizing, Ray3d) {
+        let camera = GlobalTransform::from(
+            Transform::from_xyz(0.0, 8.0, 30.0).looking_at(Vec3::ZERO, Vec3::Y),
+        );
+        let clip_from_view =
+            Mat4::perspective_infinite_reverse_rh(std::f32::consts::FRAC_PI_4, 16.0 / 9.0, 0.1);
+        let sizing = GizmoSizing::from_projection(
+            style,
+            clip_from_view,
+            &camera,
+            Some(Vec2::new(1280.0, 720.0)),
+        );
+        let targets = (0..100)
+            .map(|i| {
+                let (column, row) = ((i % 10) as f32, (i / 10) as f32);
+                GlobalTransform::from(
+                    Transform::from_xyz(column * 3.0 - 13.5, 0.0, row * -3.0)
+                        .with_rotation(Quat::from_rotation_y(i as f32 * 0.3)),
+                )
+            })
+            .collect();
+        let ray = Ray3d::new(
+            camera.translation(),
+            Dir3::new(Vec3::new(1.5, 0.5, -12.0) - camera.translation()).unwrap(),
+        );
+        (targets, sizing, ray)
+    }
+
+    /// One hover hit test over `targets`, as `update_hovered_axis` runs it.
+    fn hover_pass(
+        targets: &[GlobalTransform],
+        sizing: &GizmoSizing,
+        style: &TransformGizmoStyle,
+        ray: &Ray3d,
+    ) -> Option<GizmoHandleId> {
+        let gizmos = targets.iter().zip(1..).filter_map(|(transform, index)| {
+            let (frame, parts) = target_frame(
+                transform,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                TransformGizmoSpace::Local,
+                sizing,
+            );
+            let detail = sizing.detail(&frame, &frame.sized_style(style), None);
+            (detail == GizmoDetail::Full).then_some((Entity::from_raw_u32(index)?, frame, parts))
+        });
+        pick_handle(
+            ray,
+            gizmos,
+            style,
+            HoverBias::default(),
+            0.0,
+            |_, _| 0.0,
+            &mut 0,
+            &mut 0,
+        )
+    }
+
+    #[test]
+    fn hover_hit_testing_does_not_allocate() {
+        let style = TransformGizmoStyle::default();
+        let (targets, sizing, ray) = hover_scene(&style);
+        // Warm up once, so nothing lazily initialized is counted.
+        hover_pass(&targets, &sizing, &style, &ray);
+
+        let (_, allocations) = crate::alloc_counter::allocations_during(|| {
+            hover_pass(&targets, &sizing, &style, &ray)
+        });
+        assert_eq!(allocations, 0);
+    }
+
+    /// Hover cost over 100 targets. Run with
+    /// `cargo test --release hover_benchmark -- --ignored --nocapture`.
+    #[test]
+    #[ignore]
+    fn hover_benchmark() {
+        const PASSES: u32 = 10_000;
+        let style = TransformGizmoStyle::default();
+        let (targets, sizing, ray) = hover_scene(&style);
+
+        let start = std::time::Instant::now();
+        for _ in 0..PASSES {
+            std::hint::black_box(hover_pass(
+                std::hint::black_box(&targets),
+                &sizing,
+                &style,
+                &ray,
+            ));
+        }
+        println!(
+            "hover over {} targets: {:?} per pass",
+            targets.len(),
+            start.elapsed() / PASSES
+        );
+    }
+
+    #[cfg(feature = "translate")]
+    #[test]
+    fn longer_axes_set_after_startup_stay_hoverable() {
+        let mut harness = GizmoTestHarness::new();
+        harness.style_mut().axis_length *= 2.0;
+        harness.step();
+        let start = harness.grab(GizmoOperation::TranslateAxis, GizmoAxis::X);
+        harness.drag(start, start + harness.screen_axis(Vec3::X) * 60.0);
+        assert!(harness.target_transform().translation.x > 0.1);
+    }
+
+    #[cfg(feature = "translate")]
+    #[test]
+    fn paused_drags_resume_without_jumping() {
+        let mut harness = GizmoTestHarness::new();
+        let start = harness.grab(GizmoOperation::TranslateAxis, GizmoAxis::X);
+        let axis = harness.screen_axis(Vec3::X);
+        harness.press_left();
+        harness.step();
+        harness.move_cursor(start + axis * 30.0);
+        harness.step();
+        let moved = harness.target_transform().translation.x;
+        assert!(moved > 0.1, "{moved}");
+
+        harness.leave_window();
+        harness.step();
+        assert!(harness.drag_state().is_some_and(|drag| drag.paused));
+
+        // Coming back further along the axis picks up where the target was.
+        harness.move_cursor(start + axis * 60.0);
+        harness.step();
+        assert_eq!(harness.target_transform().translation.x, moved);
+        harness.move_cursor(start + axis * 90.0);
+        harness.step();
+        let resumed = harness.target_transform().translation.x;
+        assert!(
+            resumed > moved * 1.5 && resumed < moved * 2.5,
+            "{moved} {resumed}"
+        );
+    }
+
+    #[cfg(feature = "translate")]
+    #[test]
+    fn losing_focus_can_cancel_the_drag() {
+        use crate::types::{DragInterruptPolicy, GizmoDragEnded};
+        use bevy::ecs::message::Messages;
+
+        let mut harness = GizmoTestHarness::new();
+        harness
+            .app_mut()
+            .world_mut()
+            .resource_mut::<TransformGizmoConfig>()
+            .on_focus_loss = DragInterruptPolicy::Cancel;
+        let start = harness.grab(GizmoOperation::TranslateAxis, GizmoAxis::X);
+        harness.press_left();
+        harness.step();
+        harness.move_cursor(start + harness.screen_axis(Vec3::X) * 60.0);
+        harness.step();
+        assert!(harness.target_transform().translation.x > 0.1);
+
+        harness.set_focused(false);
+        harness.step();
+        assert!(harness.drag_state().is_none());
+        assert_eq!(harness.target_transform(), Transform::IDENTITY);
+        let ended: Vec<_> = harness
+            .app_mut()
+            .world_mut()
+            .resource_mut::<Messages<GizmoDragEnded>>()
+            .drain()
+            .collect();
+        assert!(matches!(ended.as_slice(), [ended] if ended.cancelled));
+    }
+
+    /// Starts an X translate drag under `policy`, drags it 30 pixels, then
+    /// lifts the target by half a unit from outside the drag and drags on to
+    /// 60 pixels. Returns the harness, the screen direction of X, where the
+    /// drag started on screen, and the target's X before the lift.
+    #[cfg(feature = "translate")]
+    fn drag_through_external_lift(
+        policy: crate::types::ExternalChangePolicy,
+    ) -> (GizmoTestHarness, Vec2, Vec2, f32) {
+        let mut harness = GizmoTestHarness::new();
+        harness
+            .app_mut()
+            .world_mut()
+            .resource_mut::<TransformGizmoConfig>()
+            .external_change_policy = policy;
+        let start = harness.grab(GizmoOperation::TranslateAxis, GizmoAxis::X);
+        let axis = harness.screen_axis(Vec3::X);
+        harness.press_left();
+        harness.step();
+        harness.move_cursor(start + axis * 30.0);
+        harness.step();
+        let moved = harness.target_transform().translation.x;
+        assert!(moved > 0.1, "{moved}");
+
+        let target = harness.target();
+        harness
+            .app_mut()
+            .world_mut()
+            .get_mut::<Transform>(target)
+            .unwrap()
+            .translation
+            .y += 0.5;
+        harness.move_cursor(start + axis * 60.0);
+        harness.step();
+        (harness, axis, start, moved)
+    }
+
+    #[cfg(feature = "translate")]
+    #[test]
+    fn drags_overwrite_external_changes_by_default() {
+        let (harness, _, _, moved) =
+            drag_through_external_lift(crate::types::ExternalChangePolicy::GizmoWins);
+        let translation = harness.target_transform().translation;
+        assert_eq!(translation.y, 0.0);
+        assert!(translation.x > moved * 1.5, "{moved} {translation}");
+    }
+
+    #[cfg(feature = "translate")]
+    #[test]
+    fn rebased_drags_carry_on_from_external_changes() {
+        let (mut harness, axis, start, moved) =
+            drag_through_external_lift(crate::types::ExternalChangePolicy::Rebase);
+        // The frame of the change only takes up the new baseline.
+        assert_eq!(
+            harness.target_transform().translation,
+            Vec3::new(moved, 0.5, 0.0)
+        );
+        // The gizmo was lifted along with the target.
+        let drag = harness.drag_state().unwrap();
+        assert!(drag.origin.abs_diff_eq(Vec3::Y * 0.5, 1.0e-4));
+
+        harness.move_cursor(start + axis * 90.0);
+        harness.step();
+        let translation = harness.target_transform().translation;
+        assert!((translation.y - 0.5).abs() < 1.0e-4, "{translation}");
+        assert!(translation.x > moved * 1.5, "{moved} {translation}");
+    }
+
+    #[cfg(feature = "translate")]
+    #[test]
+    fn aborted_drags_leave_external_changes_in_place() {
+        use crate::types::GizmoDragEnded;
+        use bevy::ecs::message::Messages;
+
+        let (mut harness, _, _, moved) =
+            drag_through_external_lift(crate::types::ExternalChangePolicy::Abort);
+        assert!(harness.drag_state().is_none());
+        let lifted = Transform::from_xyz(moved, 0.5, 0.0);
+        assert_eq!(harness.target_transform(), lifted);
+        let ended: Vec<_> = harness
+            .app_mut()
+            .world_mut()
+            .resource_mut::<Messages<GizmoDragEnded>>()
+            .drain()
+            .collect();
+        assert!(matches!(
+            ended.as_slice(),
+            [ended] if ended.cancelled && ended.final_transform == lifted
+        ));
+    }
+
+    #[cfg(feature = "translate")]
+    #[test]
+    fn overlapping_gizmos_hover_steadily() {
+        let mut harness = GizmoTestHarness::new();
+        let target = harness.target();
+        // A second gizmo offset across the view by less than a cone's hit
+        // radius, so both cones are hit at nearly the same distance.
+        let camera = harness.camera();
+        let world = harness.app().world();
+        let offset = world.get::<GlobalTransform>(camera).unwrap().up() * 0.04;
+        harness.style_mut().draw_inactive_targets = true;
+        harness
+            .app_mut()
+            .world_mut()
+            .spawn((Transform::from_translation(offset), TransformGizmoTarget));
+        harness.step();
+        assert_eq!(harness.state().active_target, Some(target));
+
+        // Rest the cursor halfway between the two X cones, trembling toward
+        // one and then the other.
+        let style = TransformGizmoStyle::default();
+        let cone = Vec3::X * (style.axis_length + style.translate_cone_length * 0.5);
+        let own = harness.world_to_cursor(cone).unwrap();
+        let other = harness.world_to_cursor(cone + offset).unwrap();
+        let between = own.lerp(other, 0.5);
+        let tremble = (other - own).normalize() * 0.4;
+        let mut hovered = None;
+        for frame in 0..100 {
+            let sign = if frame % 2 == 0 { 1.0 } else { -1.0 };
+            harness.move_cursor(between + tremble * sign);
+            harness.step();
+            let now = harness.state().hovered_target.zip(harness.hovered());
+            assert!(now.is_some(), "frame {frame}");
+            assert_eq!(*hovered.get_or_insert(now), now, "frame {frame}");
+        }
+        // The tie goes to the active target.
+        assert_eq!(hovered.flatten().map(|(hovered, _)| hovered), Some(target));
+    }
+
+    #[cfg(feature = "translate")]
+    #[test]
+    fn hovering_and_dragging_an_inactive_target_keeps_the_selection_when_asked() {
+        let mut harness = GizmoTestHarness::new();
+        harness.style_mut().draw_inactive_targets = true;
+        let target = harness.target();
+        // Select another target out of view, leaving the harness target's
+        // gizmo drawn but inactive.
+        let selected = harness
+            .app_mut()
+            .world_mut()
+            .spawn((
+                Transform::from_xyz(0.0, 0.0, 50.0),
+                TransformGizmoTarget,
+                GizmoActive,
+            ))
+            .id();
+        harness
+            .app_mut()
+            .world_mut()
+            .entity_mut(target)
+            .remove::<GizmoActive>();
+        harness
+            .app_mut()
+            .world_mut()
+            .resource_mut::<TransformGizmoConfig>()
+            .select_on_drag = false;
+        harness.step();
+
+        let start = harness.grab(GizmoOperation::TranslateAxis, GizmoAxis::X);
+        assert_eq!(harness.state().hovered_target, Some(target));
+        assert_eq!(harness.state().active_target, Some(selected));
+
+        harness.drag(start, start + harness.screen_axis(Vec3::X) * 60.0);
+        harness.step();
+        assert!(harness.target_transform().translation.x > 0.1);
+        let world = harness.app().world();
+        assert!(!world.entity(target).contains::<GizmoActive>());
+        assert!(world.entity(selected).contains::<GizmoActive>());
+        assert_eq!(harness.state().active_target, Some(selected));
+    }
+
+    #[cfg(feature = "translate")]
+    #[test]
+    fn locked_targets_cannot_be_hovered_or_dragged() {
+        use crate::types::GizmoLocked;
+
+        let mut harness = GizmoTestHarness::new();
+        let start = harness.grab(GizmoOperation::TranslateAxis, GizmoAxis::X);
+        let target = harness.target();
+        harness
+            .app_mut()
+            .world_mut()
+            .entity_mut(target)
+            .insert(GizmoLocked);
+        harness.step();
+        assert_eq!(harness.hovered(), None);
+        // Its handles are still laid out on screen, as the gizmo is drawn.
+        assert!(harness
+            .handle_position(GizmoOperation::TranslateAxis, GizmoAxis::X)
+            .is_some());
+
+        harness.drag(start, start + harness.screen_axis(Vec3::X) * 60.0);
+        assert_eq!(harness.target_transform(), Transform::IDENTITY);
+
+        harness
+            .app_mut()
+            .world_mut()
+            .entity_mut(target)
+            .remove::<GizmoLocked>();
+        harness.grab(GizmoOperation::TranslateAxis, GizmoAxis::X);
+    }
+
+    #[cfg(all(feature = "translate", feature = "rotate"))]
+    #[test]
+    fn constrained_targets_only_offer_allowed_handles() {
+        use crate::types::GizmoConstraints;
+
+        let mut harness = GizmoTestHarness::new();
+        let target = harness.target();
+        harness
+            .app_mut()
+            .world_mut()
+            .entity_mut(target)
+            .insert(GizmoConstraints::translate_plane(GizmoAxis::Y));
+        harness.step();
+        for (op, axis) in [
+            (GizmoOperation::TranslateAxis, GizmoAxis::Y),
+            (GizmoOperation::Rotate, GizmoAxis::Y),
+        ] {
+            assert!(harness.handle_position(op, axis).is_none(), "{op:?} {axis}");
+        }
+
+        let start = harness.grab(GizmoOperation::TranslateAxis, GizmoAxis::X);
+        harness.drag(start, start + harness.screen_axis(Vec3::X) * 60.0);
+        let translation = harness.target_transform().translation;
+        assert!(translation.x > 0.1);
+        assert_eq!(translation.y, 0.0);
+    }
+
+    #[cfg(feature = "translate")]
+    #[test]
+    fn limited_targets_are_pinned_at_the_limit_after_snapping() {
+        use crate::{GizmoLimits, NudgeSettings, TransformGizmoSnap};
+
+        let mut harness = GizmoTestHarness::new();
+        let target = harness.target();
+        let world = harness.app_mut().world_mut();
+        world.entity_mut(target).insert(GizmoLimits {
+            translation: Some((Vec3::splat(-0.3), Vec3::splat(0.3))),
+            ..default()
+        });
+        // Whole-unit snapping alone would carry the target past the limit.
+        *world.resource_mut::<TransformGizmoSnap>() =
+            TransformGizmoSnap::builder().translate(1.0).build();
+
+        let start = harness.grab(GizmoOperation::TranslateAxis, GizmoAxis::X);
+        harness.press_left();
+        harness.step();
+        harness.move_cursor(start + harness.screen_axis(Vec3::X) * 200.0);
+        harness.step();
+        assert_eq!(
+            harness.target_transform().translation,
+            Vec3::new(0.3, 0.0, 0.0)
+        );
+        let drag = harness.drag_state().unwrap();
+        assert_eq!(drag.limit_hit, Some(GizmoAxis::X));
+
+        // Back within the limits the drag follows the cursor again.
+        harness.move_cursor(start);
+        harness.step();
+        assert_eq!(harness.drag_state().unwrap().limit_hit, None);
+        harness.move_cursor(start + harness.screen_axis(Vec3::X) * 200.0);
+        harness.step();
+        harness.release_left();
+        harness.step();
+        assert_eq!(harness.target_transform().translation.x, 0.3);
+
+        // Keyboard nudges stop at the same limit.
+        let world = harness.app_mut().world_mut();
+        world.resource_mut::<TransformGizmoConfig>().keyboard_nudge = Some(NudgeSettings {
+            repeat_delay: 60.0,
+            ..default()
+        });
+        world
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(KeyCode::ArrowRight);
+        harness.step();
+        assert_eq!(harness.target_transform().translation.x, 0.3);
+    }
+
+    #[cfg(feature = "translate")]
+    #[test]
+    fn gizmos_below_the_minimum_screen_size_are_not_hovered() {
+        let mut harness = GizmoTestHarness::new();
+        let start = harness.grab(GizmoOperation::TranslateAxis, GizmoAxis::X);
+
+        harness.style_mut().min_screen_size = 10_000.0;
+        harness.step();
+        assert_eq!(harness.hovered(), None);
+        harness.drag(start, start + harness.screen_axis(Vec3::X) * 60.0);
+        assert_eq!(harness.target_transform(), Transform::IDENTITY);
+
+        harness.style_mut().min_screen_size = 0.0;
+        harness.grab(GizmoOperation::TranslateAxis, GizmoAxis::X);
+    }
+
+    #[cfg(feature = "translate")]
+    #[test]
+    fn handles_released_within_the_drag_threshold_are_clicks() {
+        use crate::types::{GizmoDragEnded, GizmoDragStarted, GizmoHandleClicked};
+        use bevy::ecs::message::Messages;
+
+        fn drain<M: Message>(harness: &mut GizmoTestHarness) -> Vec<M> {
+            harness
+                .app_mut()
+                .world_mut()
+                .resource_mut::<Messages<M>>()
+                .drain()
+                .collect()
+        }
+
+        let mut harness = GizmoTestHarness::new();
+        harness
+            .app_mut()
+            .world_mut()
+            .resource_mut::<TransformGizmoConfig>()
+            .drag_threshold_pixels = 5.0;
+        let start = harness.grab(GizmoOperation::TranslateAxis, GizmoAxis::X);
+        let along = harness.screen_axis(Vec3::X);
+        harness.press_left();
+        harness.step();
+        harness.move_cursor(start + along * 4.0);
+        harness.step();
+        assert!(harness.drag_state().is_some_and(|drag| drag.is_pending()));
+        harness.release_left();
+        harness.step();
+
+        assert_eq!(harness.target_transform(), Transform::IDENTITY);
+        assert_eq!(
+            drain::<GizmoHandleClicked>(&mut harness),
+            [GizmoHandleClicked {
+                handle: GizmoHandleId::new(
+                    harness.target(),
+                    GizmoOperation::TranslateAxis,
+                    GizmoAxis::X
+                ),
+            }]
+        );
+        assert!(drain::<GizmoDragStarted>(&mut harness).is_empty());
+        assert!(drain::<GizmoDragEnded>(&mut harness).is_empty());
+
+        // Past the threshold the drag starts where the cursor is, and then
+        // follows it from there.
+        harness.grab(GizmoOperation::TranslateAxis, GizmoAxis::X);
+        harness.press_left();
+        harness.step();
+        harness.move_cursor(start + along * 20.0);
+        harness.step();
+        assert!(harness.drag_state().is_some_and(|drag| !drag.is_pending()));
+        assert_eq!(harness.target_transform(), Transform::IDENTITY);
+        assert_eq!(drain::<GizmoDragStarted>(&mut harness).len(), 1);
+        harness.move_cursor(start + along * 80.0);
+        harness.step();
+        let moved = harness.target_transform().translation.x;
+        harness.release_left();
+        harness.step();
+        assert!(moved > 0.1);
+        assert!(drain::<GizmoHandleClicked>(&mut harness).is_empty());
+        assert_eq!(drain::<GizmoDragEnded>(&mut harness).len(), 1);
+    }
+
+    #[cfg(feature = "translate")]
+    #[test]
+    fn switching_space_mid_drag_waits_for_the_drag_to_end() {
+        use crate::TransformGizmoSpace;
+
+        fn set_space(harness: &mut GizmoTestHarness, space: TransformGizmoSpace) {
+            let world = harness.app_mut().world_mut();
+            world.resource_mut::<TransformGizmoState>().space = space;
+        }
+        /// Screen direction of the X cone from the target's origin.
+        fn cone_direction(harness: &GizmoTestHarness) -> Vec2 {
+            let origin = harness.target_transform().translation;
+            let cone = harness
+                .handle_position(GizmoOperation::TranslateAxis, GizmoAxis::X)
+                .unwrap();
+            (cone - harness.world_to_cursor(origin).unwrap()).normalize()
+        }
+        fn axis_direction(harness: &GizmoTestHarness, dir: Vec3) -> Vec2 {
+            let origin = harness.target_transform().translation;
+            let from = harness.world_to_cursor(origin).unwrap();
+            (harness.world_to_cursor(origin + dir).unwrap() - from).normalize()
+        }
+
+        let mut harness = GizmoTestHarness::new();
+        let turned = Quat::from_rotation_y(std::f32::consts::FRAC_PI_4);
+        let local_x = turned * Vec3::X;
+        let target = harness.target();
+        harness
+            .app_mut()
+            .world_mut()
+            .entity_mut(target)
+            .insert(Transform::from_rotation(turned));
+        set_space(&mut harness, TransformGizmoSpace::Local);
+        harness.step();
+
+        let cone = harness.grab(GizmoOperation::TranslateAxis, GizmoAxis::X);
+        harness.press_left();
+        harness.step();
+        set_space(&mut harness, TransformGizmoSpace::World);
+        harness.move_cursor(cone + axis_direction(&harness, local_x) * 60.0);
+        harness.step();
+
+        // The target still moves along its local X, and the gizmo is still
+        // laid out along it.
+        let moved = harness.target_transform().translation;
+        assert!(moved.length() > 0.1, "{moved}");
+        assert!(moved.normalize().abs_diff_eq(local_x, 1.0e-3), "{moved}");
+        let drawn = cone_direction(&harness);
+        assert!(
+            drawn.abs_diff_eq(axis_direction(&harness, local_x), 1.0e-2),
+            "{drawn}"
+        );
+
+        // Once released, the new space applies.
+        harness.release_left();
+        harness.step();
+        let drawn = cone_direction(&harness);
+        assert!(
+            drawn.abs_diff_eq(axis_direction(&harness, Vec3::X), 1.0e-2),
+            "{drawn}"
+        );
+    }
 }
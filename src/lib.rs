@@ -44,8 +44,92 @@
 //! - **Scaling**: Scale entities per-axis or uniformly
 //! - **Coordinate Spaces**: World or local space manipulation
 //! - **Snap-to-Grid**: Optional snapping for precise positioning
+//! - **Visual Pivots**: Anchor the gizmo at a mesh's visual center or a
+//!   world-space point such as a hinge via [`GizmoVisualPivot`], or at every
+//!   target's bounds center via [`GizmoAnchor`]
+//! - **Edit Channels**: Edit a companion component (socket or collider offsets)
+//!   instead of `Transform` via [`TransformGizmoAppExt::register_gizmo_channel`]
+//! - **Reparenting**: Move a target under a new parent without changing its
+//!   world pose, even mid-drag, via [`GizmoReparentRequest`]
+//! - **Blocked Dragging**: Stop translate drags at scene geometry reported by
+//!   a [`GizmoSweepProvider`]
+//! - **Endpoint Handles**: Stretch beams and pipes between their ends via
+//!   [`GizmoEndpoints`]
+//! - **Preferences**: Keep per-target gizmo components across despawns and
+//!   scene reloads via [`GizmoStableId`] and [`GizmoPreferenceStore`]
+//! - **Constant Screen Size**: Keep the gizmo the same size on screen at any
+//!   zoom, in perspective and orthographic views, via [`GizmoScaleMode`]
+//! - **Undo/Redo**: Record drags and step back and forth through them via
+//!   [`GizmoHistory`]
+//! - **Click to Select**: Pick the active target by clicking it via
+//!   [`GizmoSelectable`] and [`TransformGizmoConfig::click_to_select`]
+//! - **Euler Angles**: Show rotations in a properties panel without sign flips
+//!   near gimbal lock via [`GizmoEulerTracker`]
+//! - **Keyboard Nudging**: Step the active target by the snap increments with
+//!   the arrow and page keys via [`TransformGizmoConfig::keyboard_nudge`]
+//! - **Smoothing**: Ease dragged targets toward the drag for smooth recorded
+//!   motion via [`TransformGizmoConfig::smoothing`]
+//! - **Align Snapping**: Alt-drag a target onto another target or a
+//!   [`GizmoSnapPoint`] via [`TransformGizmoSnap::align`]
+//! - **Limits**: Keep targets above the floor and within sensible rotations
+//!   and sizes via [`GizmoLimits`]
+//! - **Drag Messages**: Mirror drags in another app, such as a shared editing
+//!   session, via [`GizmoTransformMessage`]
 //! - **Customizable**: Full control over colors, sizes, and visibility
 //!
+//! # Cargo Features
+//!
+//! Each handle family can be compiled out for minimal integrations. All of
+//! them are enabled by default:
+//!
+//! - `translate`: axis translation cones
+//! - `planes`: planar translation rectangles (implies `translate`)
+//! - `rotate`: per-axis rotation arcs
+//! - `scale`: per-axis scale cubes
+//! - `uniform-scale`: uniform scale square (implies `scale`)
+//!
+//! Features are a hard ceiling: a disabled family has no drawing, hover
+//! testing, or drag code in the binary, and the matching `show_*` style flags
+//! have no effect. Runtime flags such as [`TransformGizmoStyle::show_scale`]
+//! only toggle handles within the compiled set. With every handle feature
+//! disabled the gizmo draws only its axis lines and origin marker.
+//!
+//! ```toml
+//! bevy_transform_tools = { version = "0.3", default-features = false, features = ["translate", "rotate"] }
+//! ```
+//!
+//! The optional `egui` feature adds [`TransformGizmoEguiPlugin`], a
+//! `bevy_egui` side panel for the tool, space, snapping, and style, and
+//! [`gizmo_settings_ui`] to draw the same controls in a window of your own.
+//!
+//! The optional `ui` feature adds [`GizmoBlocksInput`], a marker for
+//! `bevy_ui` nodes that the gizmo ignores the pointer over, so clicks on
+//! panels laid over the viewport never start drags underneath.
+//!
+//! The optional `mesh-raycast` feature lets translate drags snap to the
+//! meshes under the cursor (see [`SurfaceSnapSettings`]) without a
+//! [`GizmoSurfaceRaycast`] callback, using `bevy_picking`'s mesh ray cast.
+//!
+//! The optional `serde` feature derives `Serialize`/`Deserialize` for
+//! [`GizmoPreferenceStore`] and the components it records, so preferences
+//! can be saved with editor session state. It also covers the settings
+//! resources, marker components, and [`GizmoTransformMessage`].
+//!
+//! The optional `states` feature adds [`GizmoInteractionState`], a Bevy
+//! state that follows the gizmo through idle, hovering, and dragging, for
+//! `OnEnter`/`OnExit` schedules and `in_state` conditions. Without it,
+//! [`gizmo_is_dragging`], [`gizmo_is_hovering`], and [`gizmo_over_handle`]
+//! are run conditions over the same lifecycle.
+//!
+//! The optional `test-utils` feature adds `test_utils::GizmoTestHarness`,
+//! a headless app that runs the plugin's hover and drag systems against a
+//! mocked window, for testing interaction without a GPU or display.
+//!
+//! [`TransformGizmoStyle`], [`TransformGizmoSnap`], the types they contain,
+//! and the [`TransformGizmoTarget`], [`GizmoActive`], and
+//! [`TransformGizmoCamera`] markers derive `Reflect` and are registered by the
+//! plugin, so they load from scenes and show up in inspectors.
+//!
 //! # Configuration
 //!
 //! The gizmo can be configured through several resources:
@@ -53,38 +137,326 @@
 //! - [`TransformGizmoState`]: Current mode, selected target, and drag state
 //! - [`TransformGizmoStyle`]: Visual appearance (colors, sizes, visibility)
 //! - [`TransformGizmoSnap`]: Snap-to-grid increments for each operation
+//! - [`TransformGizmoConfig`]: Interaction behaviour such as drag history scrubbing
+//! - [`TransformGizmoKeybinds`]: Keys that re-constrain or cancel an active drag
+//!
+//! # System Ordering
+//!
+//! Drag handling runs in `Update` under [`TransformGizmoSystems::Input`], so
+//! edits are propagated and rendered in the same frame. Within it,
+//! [`TransformGizmoSystems::Sync`] picks the camera and active target before
+//! [`TransformGizmoSystems::Drag`] starts, moves, and ends drags. Hover and drawing run
+//! in `PostUpdate` after transform propagation ([`TransformGizmoSystems::Hover`]
+//! and [`TransformGizmoSystems::Draw`]), so targets moved by your own systems
+//! are hit tested where they are rendered. For targets moved in
+//! `FixedUpdate`, [`TransformGizmoConfig::hover_velocity_compensation`]
+//! removes the remaining one-frame lag, and
+//! [`TransformGizmoConfig::drag_follows_external_motion`] keeps drags on
+//! moving parents relative to the parent.
+//! [`TransformGizmoConfig::external_change_policy`] decides whether a drag
+//! overwrites, follows, or gives way to other systems moving its target.
+//!
+//! Drags use the mouse button in [`TransformGizmoConfig::drag_input`], left
+//! by default, optionally gated on modifier keys. While
+//! [`TransformGizmoState::is_interacting`] is `true` the gizmo owns the
+//! pointer, so camera controllers and UI should ignore the click.
+//!
+//! Interaction reads the cursor from [`GizmoPointer`], which also carries pen
+//! pressure and tilt. [`TransformGizmoConfig::pressure_precision`] scales drag
+//! motion by pressure and [`TransformGizmoConfig::tilt_axis_bias`] lets tilt
+//! pick between overlapping axis handles; both are inert for mouse input.
+//!
+//! Per-frame costs (hover time, hit tests, drawn segments) are collected in
+//! [`GizmoFrameStats`] and published as Bevy diagnostics under the paths in
+//! [`TransformGizmoDiagnostics`], so `LogDiagnosticsPlugin` reports them.
+//!
+//! Drags are reported as messages: [`GizmoDragStarted`] when a handle is
+//! grabbed, [`GizmoDragging`] with per-frame deltas while it moves, and
+//! [`GizmoDragEnded`] with the start and final transforms for undo, also when
+//! the target is despawned mid-drag. A handle released before the cursor
+//! moves past [`TransformGizmoConfig::drag_threshold_pixels`] is a
+//! [`GizmoHandleClicked`] instead, and leaves the target alone.
+//!
+//! Every handle has a stable [`GizmoHandleId`] (target, operation, axis),
+//! carried by all three drag messages. For tutorial and
+//! onboarding UIs, [`TransformGizmoConfig::handle_screen_info`] fills
+//! [`GizmoHandleScreenInfo`] with each handle's on-screen position, and
+//! [`GizmoHandleLookup`] finds the handle at a screen position; both use the
+//! hover hit test's own handle layout. For tooltips, [`GizmoScreenInfo`]
+//! always holds the gizmo origin and the hovered handle on screen.
+//!
+//! When several [`TransformGizmoCamera`] entities are active, the one with the
+//! highest [`GizmoCameraPriority`] drives the gizmo (ties go to the lowest
+//! entity id) and a warning lists the cameras if the choice is ambiguous.
+//! [`GizmoCameraSelection`] exposes the camera currently in use.
 
 #![warn(missing_docs)]
 
+#[cfg(any(feature = "translate", feature = "scale"))]
+use bevy::camera::visibility::VisibilitySystems;
 use bevy::prelude::*;
+use bevy::transform::TransformSystems;
 
+mod align;
+#[cfg(test)]
+mod alloc_counter;
+mod camera;
+mod channel;
+mod diagnostics;
 mod draw;
+#[cfg(feature = "egui")]
+mod egui_panel;
+#[cfg(feature = "translate")]
+mod endpoints;
+mod euler;
+mod external;
 mod gizmo_frame;
+#[cfg(any(feature = "translate", feature = "rotate", feature = "scale"))]
+mod handle_geometry;
+mod handles;
+mod history;
 mod interaction;
 mod math;
+#[cfg(any(feature = "translate", feature = "scale"))]
+mod meshes;
+mod nudge;
+mod origin_dot;
+mod pointer;
+mod preferences;
+mod presets;
+mod proportional;
+mod remote;
+mod reparent;
+mod selection;
+mod smoothing;
+#[cfg(feature = "states")]
+mod states;
+mod surface;
+mod sweep;
+#[cfg(any(test, feature = "test-utils"))]
+pub mod test_utils;
 mod types;
+#[cfg(feature = "ui")]
+mod ui_blocking;
+mod visual_pivot;
 
 // Re-export all public types
 pub use types::{
-    AxisColors, AxisSnap, AxisToggles, GizmoActive, GizmoAxis, GizmoOperation, GizmoStateColors,
-    TransformGizmoCamera, TransformGizmoDrag, TransformGizmoMode, TransformGizmoSnap,
-    TransformGizmoSpace, TransformGizmoState, TransformGizmoStyle, TransformGizmoTarget,
+    AlignSnapSettings, AngleUnit, AxisColors, AxisSnap, AxisToggles, DragHistory,
+    DragHistorySettings, DragInterruptPolicy, DragIsolation, EndpointSign, ExternalChangePolicy,
+    GizmoActive, GizmoActivePriority, GizmoAlignLock, GizmoAnchor, GizmoAnchorOverride, GizmoAxis,
+    GizmoCameraPriority, GizmoCameraSelection, GizmoCenterHandle, GizmoChannelParts,
+    GizmoColorTint, GizmoConstraints, GizmoDelta, GizmoDepthMode, GizmoDragEnded, GizmoDragInput,
+    GizmoDragStarted, GizmoDragging, GizmoDuplicated, GizmoEditChannel, GizmoEndpoints,
+    GizmoFrameStats, GizmoHandleClicked, GizmoHandleId, GizmoHandleScreen, GizmoHandleScreenInfo,
+    GizmoHit, GizmoLimits, GizmoLocked, GizmoMessageEmission, GizmoOperation,
+    GizmoOriginClickAction, GizmoOriginClicked, GizmoPointer, GizmoRenderMode,
+    GizmoReparentRejected, GizmoReparentRequest, GizmoReparented, GizmoScaleMode, GizmoScreenInfo,
+    GizmoStateColors, GizmoVisualPivot, HitTestMode, NudgeAxes, NudgeSettings, PressureCurve,
+    ReparentRejection, RotationRingStyle, RulerStyle, SmoothingSettings, SnapActivation,
+    SnapGridStyle, SnapMode, SurfaceSnapSettings, TransformGizmoCamera, TransformGizmoConfig,
+    TransformGizmoConfigGroup, TransformGizmoDrag, TransformGizmoGuideConfigGroup,
+    TransformGizmoKeybinds, TransformGizmoMode, TransformGizmoSnap, TransformGizmoSnapBuilder,
+    TransformGizmoSpace, TransformGizmoState, TransformGizmoStyle, TransformGizmoSystems,
+    TransformGizmoTarget, VisualPivotSource,
 };
 
+use crate::camera::select_gizmo_camera;
+use crate::diagnostics::{publish_gizmo_diagnostics, register_gizmo_diagnostics};
 use crate::draw::draw_gizmo;
-use crate::interaction::{begin_drag, configure_gizmos, drag_gizmo, end_drag, update_hovered_axis};
+use crate::euler::track_euler_angles;
+use crate::handles::{update_gizmo_screen_info, update_handle_screen_info};
+use crate::history::{apply_gizmo_history, record_gizmo_history};
+use crate::nudge::nudge_active_target;
+use crate::origin_dot::{click_origin_dot, OriginDotPress};
+use crate::pointer::update_gizmo_pointer;
+use crate::preferences::sync_gizmo_preferences;
+use crate::proportional::{carry_active_targets, CarriedTargets};
+use crate::remote::{emit_transform_messages, TransformMessageEmitter};
+use crate::reparent::process_reparent_requests;
+use crate::selection::select_on_click;
+use crate::smoothing::smooth_gizmo_targets;
+pub use align::{GizmoAlignSnapped, GizmoSnapPoint};
+pub use channel::{GizmoChannelAccessor, TransformGizmoAppExt};
+pub use diagnostics::TransformGizmoDiagnostics;
+#[cfg(feature = "egui")]
+pub use egui_panel::{gizmo_settings_ui, TransformGizmoEguiPlugin};
+pub use euler::GizmoEulerTracker;
+pub use external::{GizmoExternalDriver, GizmoTransformRequest};
+pub use gizmo_frame::{plane_axes, AxisKind, GizmoFrame};
+pub use handles::GizmoHandleLookup;
+pub use history::{GizmoEdit, GizmoHistory, GizmoHistoryEntry};
+pub use interaction::{apply_drag, begin_drag_from_ray, operation_compiled, pick_gizmo_handle};
+pub use math::{
+    closest_points_ray_segment, ray_circle_closest_approach, ray_disc_intersection,
+    world_units_per_pixel_at,
+};
+pub use preferences::{DuplicateStableIds, GizmoPreferenceStore, GizmoPreferences, GizmoStableId};
+pub use proportional::{FalloffCurve, GizmoProportionalEdit};
+pub use remote::{GizmoMessageTarget, GizmoTransformMessage};
+pub use selection::{GizmoActiveCommandsExt, GizmoSelectable};
+pub use smoothing::GizmoTargetTransform;
+#[cfg(feature = "states")]
+pub use states::GizmoInteractionState;
+pub use surface::{GizmoSnapSurface, GizmoSurfaceRaycast, SurfaceHit, SurfaceRaycastFn};
+pub use sweep::{GizmoBlockedTranslation, GizmoSweepProvider, SweepHit};
+#[cfg(feature = "ui")]
+pub use ui_blocking::GizmoBlocksInput;
+
+/// Former name of [`TransformGizmoSystems`].
+#[deprecated(note = "renamed to `TransformGizmoSystems`")]
+pub type TransformGizmoSet = TransformGizmoSystems;
+
+use crate::interaction::{
+    apply_drag_keybinds, begin_drag, configure_gizmos, drag_ended, drag_gizmo, end_drag,
+    update_hovered_axis,
+};
+use crate::visual_pivot::update_visual_pivots;
+
+/// What [`sync_active_target`] picks the active target from.
+type ActiveCandidate = (Entity, Option<&'static GizmoActivePriority>);
 
 /// Syncs [`GizmoActive`] component with [`TransformGizmoState::active_target`].
 ///
 /// This system finds entities with both `TransformGizmoTarget` and `GizmoActive`,
-/// and sets the first one as the active target in the state resource.
+/// and sets one of them as the active target in the state resource, see
+/// [`pick_active_target`]: the current target stays while no other has a
+/// higher [`GizmoActivePriority`], else the highest priority wins and ties go
+/// to the lowest entity id. The active target is cleared once no entity has
+/// `GizmoActive`.
+///
+/// A drag keeps the gizmo on the dragged entity until it ends, even if
+/// `GizmoActive` moves elsewhere. If the dragged entity is despawned or loses
+/// `TransformGizmoTarget`, the drag is aborted with a [`GizmoDragEnded`], and
+/// hover on a lost target is cleared.
 fn sync_active_target(
     mut state: ResMut<TransformGizmoState>,
-    query: Query<Entity, (With<TransformGizmoTarget>, With<GizmoActive>)>,
+    query: Query<ActiveCandidate, (With<TransformGizmoTarget>, With<GizmoActive>)>,
+    targets: Query<(), With<TransformGizmoTarget>>,
+    mut ended: MessageWriter<GizmoDragEnded>,
 ) {
-    // Find the first entity with GizmoActive
-    if let Some(entity) = query.iter().next() {
-        state.active_target = Some(entity);
+    let lost = |entity: Entity| !targets.contains(entity);
+
+    if state.drag.as_ref().is_some_and(|drag| lost(drag.target)) {
+        if let Some(drag) = state.drag.take().filter(|drag| !drag.is_pending()) {
+            ended.write(drag_ended(&drag, drag.last_transform));
+        }
+    }
+
+    let hover_lost = match state.hovered_target {
+        Some(entity) => lost(entity),
+        None => state.hovered_op.is_some() && state.active_target.is_some_and(lost),
+    };
+    if hover_lost {
+        state.hovered_target = None;
+        state.hovered_axis = None;
+        state.hovered_op = None;
+    }
+
+    // Keep the dragged entity, else pick among the entities with
+    // GizmoActive, or none once the last one lost it.
+    let active = match &state.drag {
+        Some(drag) => Some(drag.target),
+        None => pick_active_target(
+            state.active_target,
+            query
+                .iter()
+                .map(|(entity, priority)| (entity, priority.map_or(0, |priority| priority.0))),
+        ),
+    };
+    if state.active_target != active {
+        state.active_target = active;
+    }
+}
+
+/// The target the gizmo sits on among the active `candidates` (entity and
+/// [`GizmoActivePriority`]): `current` while it is one of them and none has a
+/// higher priority, else the highest priority, with ties going to the lowest
+/// entity id.
+fn pick_active_target(
+    current: Option<Entity>,
+    candidates: impl IntoIterator<Item = (Entity, i32)>,
+) -> Option<Entity> {
+    let rank =
+        |(entity, priority): (Entity, i32)| (priority, std::cmp::Reverse(entity.index_u32()));
+    let mut current_priority = None;
+    let best = candidates
+        .into_iter()
+        .inspect(|(entity, priority)| {
+            if Some(*entity) == current {
+                current_priority = Some(*priority);
+            }
+        })
+        .max_by_key(|candidate| rank(*candidate))?;
+    match (current, current_priority) {
+        (Some(current), Some(priority)) if priority >= best.1 => Some(current),
+        _ => Some(best.0),
+    }
+}
+
+/// Run condition that is `true` while [`TransformGizmoState::enabled`] is
+/// on.
+///
+/// The plugin's hover, drag, click selection, and drawing systems run under
+/// it. Add it to your own gizmo-related systems to switch them off with the
+/// gizmo:
+///
+/// ```ignore
+/// app.add_systems(Update, highlight_hovered.run_if(gizmo_enabled));
+/// ```
+pub fn gizmo_enabled(state: Res<TransformGizmoState>) -> bool {
+    state.enabled
+}
+
+/// Run condition that is `true` while a handle is pressed or dragged, see
+/// [`TransformGizmoState::is_dragging`].
+///
+/// ```ignore
+/// app.add_systems(Update, autosave.run_if(not(gizmo_is_dragging)));
+/// ```
+pub fn gizmo_is_dragging(state: Res<TransformGizmoState>) -> bool {
+    state.is_dragging()
+}
+
+/// Run condition that is `true` while a handle is under the cursor and no
+/// drag is active.
+pub fn gizmo_is_hovering(state: Res<TransformGizmoState>) -> bool {
+    state.is_hovering() && !state.is_dragging()
+}
+
+/// Run condition that is `true` while a handle is under the cursor, or was
+/// when the active drag started, see [`TransformGizmoState::is_hovering`].
+pub fn gizmo_over_handle(state: Res<TransformGizmoState>) -> bool {
+    state.is_hovering()
+}
+
+/// Ends the drag, where it is, and clears hover and the handle screen
+/// positions once [`TransformGizmoState::enabled`] is turned off.
+///
+/// The drag is not rolled back: the [`GizmoDragEnded`] reports the last
+/// transform it applied. Selection and mode are left alone so turning the
+/// gizmo back on restores them.
+fn release_disabled_gizmo(
+    mut state: ResMut<TransformGizmoState>,
+    mut screen_info: ResMut<GizmoHandleScreenInfo>,
+    mut origin_press: ResMut<OriginDotPress>,
+    mut ended: MessageWriter<GizmoDragEnded>,
+) {
+    if state.drag.is_some() {
+        if let Some(drag) = state.drag.take().filter(|drag| !drag.is_pending()) {
+            ended.write(drag_ended(&drag, drag.last_transform));
+        }
+    }
+    if state.hovered_target.is_some() || state.hovered_op.is_some() {
+        state.hovered_target = None;
+        state.hovered_axis = None;
+        state.hovered_op = None;
+    }
+    if screen_info.camera.is_some() || !screen_info.handles.is_empty() {
+        *screen_info = GizmoHandleScreenInfo::default();
+    }
+    if origin_press.0.is_some() {
+        origin_press.0 = None;
     }
 }
 
@@ -94,6 +466,28 @@ fn sync_active_target(
 /// The plugin registers the necessary resources and systems for gizmo
 /// rendering and interaction.
 ///
+/// # System Sets
+///
+/// The plugin's systems run in the [`TransformGizmoSystems`] sets:
+///
+/// - `Update`: [`TransformGizmoSystems::Sync`], then
+///   [`TransformGizmoSystems::Drag`], both inside
+///   [`TransformGizmoSystems::Input`]
+/// - `PostUpdate`, after transform propagation:
+///   [`TransformGizmoSystems::Hover`], then [`TransformGizmoSystems::Draw`]
+///
+/// Order your systems against the sets in the same schedule:
+///
+/// ```ignore
+/// app.add_systems(Update, select_from_outliner.before(TransformGizmoSystems::Drag))
+///     .add_systems(PostUpdate, highlight_hovered.after(TransformGizmoSystems::Hover));
+/// ```
+///
+/// Systems in `Update` see the hover state of the previous frame.
+///
+/// Hover, dragging, click selection, and drawing only run while
+/// [`TransformGizmoState::enabled`] is on; see [`gizmo_enabled`].
+///
 /// # Example
 ///
 /// ```ignore
@@ -109,21 +503,468 @@ pub struct TransformGizmoPlugin;
 
 impl Plugin for TransformGizmoPlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<TransformGizmoState>()
-            .init_resource::<TransformGizmoStyle>()
-            .init_resource::<TransformGizmoSnap>()
-            .add_systems(Startup, configure_gizmos)
+        add_gizmo_interaction(app);
+        app.init_gizmo_group::<TransformGizmoConfigGroup>()
+            .init_gizmo_group::<TransformGizmoGuideConfigGroup>()
             .add_systems(
-                Update,
+                PostUpdate,
                 (
-                    sync_active_target,
-                    update_hovered_axis,
+                    configure_gizmos,
+                    draw_gizmo.run_if(gizmo_enabled),
+                    publish_gizmo_diagnostics,
+                )
+                    .chain()
+                    .in_set(TransformGizmoSystems::Draw),
+            );
+        #[cfg(any(feature = "translate", feature = "scale"))]
+        app.add_systems(
+            PostUpdate,
+            meshes::sync_gizmo_meshes
+                .in_set(TransformGizmoSystems::Draw)
+                .before(VisibilitySystems::VisibilityPropagate),
+        );
+        register_gizmo_types(app);
+        register_gizmo_diagnostics(app);
+        #[cfg(feature = "ui")]
+        ui_blocking::register_ui_blocking(app);
+        #[cfg(feature = "states")]
+        states::register_interaction_state(app);
+    }
+}
+
+/// Add the resources, messages, and systems that pick, hover, and drag
+/// handles, without any drawing.
+///
+/// [`TransformGizmoPlugin`] builds on this; the `test-utils` harness uses it
+/// alone, since it needs neither a renderer nor gizmo line drawing.
+pub(crate) fn add_gizmo_interaction(app: &mut App) {
+    app.init_resource::<TransformGizmoState>()
+        .init_resource::<TransformGizmoStyle>()
+        .init_resource::<TransformGizmoSnap>()
+        .init_resource::<TransformGizmoConfig>()
+        .init_resource::<TransformGizmoKeybinds>()
+        .init_resource::<GizmoCameraSelection>()
+        .init_resource::<GizmoPointer>()
+        .init_resource::<GizmoHandleScreenInfo>()
+        .init_resource::<GizmoScreenInfo>()
+        .init_resource::<GizmoPreferenceStore>()
+        .init_resource::<GizmoFrameStats>()
+        .init_resource::<OriginDotPress>()
+        .init_resource::<GizmoProportionalEdit>()
+        .init_resource::<CarriedTargets>()
+        .init_resource::<TransformMessageEmitter>()
+        .add_message::<GizmoDragStarted>()
+        .add_message::<GizmoDragging>()
+        .add_message::<GizmoDragEnded>()
+        .add_message::<GizmoDuplicated>()
+        .add_message::<GizmoOriginClicked>()
+        .add_message::<GizmoHandleClicked>()
+        .add_message::<GizmoTransformRequest>()
+        .add_message::<GizmoTransformMessage>()
+        .add_message::<GizmoReparentRequest>()
+        .add_message::<GizmoReparented>()
+        .add_message::<GizmoReparentRejected>()
+        .add_message::<GizmoAlignSnapped>()
+        .configure_sets(
+            Update,
+            (TransformGizmoSystems::Sync, TransformGizmoSystems::Drag)
+                .chain()
+                .in_set(TransformGizmoSystems::Input),
+        )
+        .configure_sets(
+            PostUpdate,
+            (TransformGizmoSystems::Hover, TransformGizmoSystems::Draw)
+                .chain()
+                .after(TransformSystems::Propagate),
+        )
+        .add_systems(
+            Update,
+            (
+                sync_gizmo_preferences,
+                update_gizmo_pointer,
+                select_gizmo_camera,
+                sync_active_target,
+                select_on_click.run_if(gizmo_enabled),
+            )
+                .chain()
+                .in_set(TransformGizmoSystems::Sync),
+        )
+        .add_systems(
+            Update,
+            (
+                (
+                    click_origin_dot,
                     begin_drag,
+                    apply_drag_keybinds,
                     drag_gizmo,
-                    end_drag,
-                    draw_gizmo,
+                    carry_active_targets,
                 )
-                    .chain(),
-            );
+                    .chain()
+                    .run_if(gizmo_enabled),
+                track_euler_angles,
+                process_reparent_requests,
+                end_drag.run_if(gizmo_enabled),
+                nudge_active_target.run_if(gizmo_enabled),
+                record_gizmo_history,
+                apply_gizmo_history,
+                smooth_gizmo_targets,
+                emit_transform_messages,
+            )
+                .chain()
+                .in_set(TransformGizmoSystems::Drag),
+        )
+        .add_systems(
+            PostUpdate,
+            (
+                release_disabled_gizmo.run_if(not(gizmo_enabled)),
+                update_visual_pivots,
+                (update_hovered_axis, update_handle_screen_info)
+                    .chain()
+                    .run_if(gizmo_enabled),
+                update_gizmo_screen_info,
+            )
+                .chain()
+                .in_set(TransformGizmoSystems::Hover),
+        );
+}
+
+/// Register the reflected settings types and marker components, so they can
+/// be inspected and saved in scenes.
+fn register_gizmo_types(app: &mut App) {
+    app.register_type::<TransformGizmoTarget>()
+        .register_type::<GizmoActive>()
+        .register_type::<TransformGizmoCamera>()
+        .register_type::<TransformGizmoMode>()
+        .register_type::<TransformGizmoSpace>()
+        .register_type::<TransformGizmoStyle>()
+        .register_type::<TransformGizmoConfigGroup>()
+        .register_type::<TransformGizmoGuideConfigGroup>()
+        .register_type::<TransformGizmoSnap>()
+        .register_type::<GizmoColorTint>()
+        .register_type::<GizmoLocked>()
+        .register_type::<GizmoConstraints>()
+        .register_type::<GizmoLimits>()
+        .register_type::<GizmoStateColors>()
+        .register_type::<AxisColors>()
+        .register_type::<AxisToggles>()
+        .register_type::<SnapGridStyle>()
+        .register_type::<RulerStyle>()
+        .register_type::<SurfaceSnapSettings>()
+        .register_type::<GizmoSnapSurface>()
+        .register_type::<AlignSnapSettings>()
+        .register_type::<GizmoSnapPoint>()
+        .register_type::<AxisSnap>()
+        .register_type::<SnapMode>()
+        .register_type::<SnapActivation>()
+        .register_type::<AngleUnit>()
+        .register_type::<GizmoOriginClickAction>()
+        .register_type::<GizmoProportionalEdit>()
+        .register_type::<GizmoEulerTracker>()
+        .register_type::<FalloffCurve>()
+        .register_type::<GizmoScaleMode>()
+        .register_type::<GizmoRenderMode>()
+        .register_type::<DragIsolation>()
+        .register_type::<GizmoDepthMode>()
+        .register_type::<GizmoCenterHandle>()
+        .register_type::<GizmoAnchor>()
+        .register_type::<GizmoAnchorOverride>()
+        .register_type::<GizmoAxis>()
+        .register_type::<GizmoOperation>()
+        .register_type::<EndpointSign>()
+        .register_type::<HitTestMode>()
+        .register_type::<RotationRingStyle>();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::ecs::message::Messages;
+    use bevy::ecs::system::RunSystemOnce;
+    use std::any::TypeId;
+
+    #[test]
+    fn settings_and_markers_are_reflectable() {
+        let mut app = App::new();
+        register_gizmo_types(&mut app);
+
+        let registry = app.world().resource::<AppTypeRegistry>().read();
+        for marker in [
+            TypeId::of::<TransformGizmoTarget>(),
+            TypeId::of::<GizmoActive>(),
+            TypeId::of::<TransformGizmoCamera>(),
+        ] {
+            assert!(registry.get_type_data::<ReflectComponent>(marker).is_some());
+        }
+        for resource in [
+            TypeId::of::<TransformGizmoStyle>(),
+            TypeId::of::<TransformGizmoSnap>(),
+        ] {
+            assert!(registry
+                .get_type_data::<ReflectResource>(resource)
+                .is_some());
+        }
+
+        let style = TransformGizmoStyle {
+            rotation_ring_style: RotationRingStyle::FullCircle,
+            ..default()
+        };
+        let copy = TransformGizmoStyle::from_reflect(style.as_partial_reflect()).unwrap();
+        assert_eq!(copy.rotation_ring_style, RotationRingStyle::FullCircle);
+    }
+
+    #[test]
+    fn gizmo_lines_are_configured_in_their_own_group_whenever_the_style_changes() {
+        use bevy::gizmos::config::{DefaultGizmoConfigGroup, GizmoConfig, GizmoConfigStore};
+
+        let mut world = World::new();
+        world.init_resource::<TransformGizmoStyle>();
+        let mut store = GizmoConfigStore::default();
+        store.insert(GizmoConfig::default(), DefaultGizmoConfigGroup);
+        store.insert(GizmoConfig::default(), TransformGizmoConfigGroup);
+        store.insert(GizmoConfig::default(), TransformGizmoGuideConfigGroup);
+        world.insert_resource(store);
+        let configure = world.register_system(configure_gizmos);
+        let widths = |world: &World| {
+            let store = world.resource::<GizmoConfigStore>();
+            (
+                store.config::<TransformGizmoConfigGroup>().0.line.width,
+                store.config::<DefaultGizmoConfigGroup>().0.line.width,
+            )
+        };
+        let untouched = GizmoConfig::default().line.width;
+
+        world.run_system(configure).unwrap();
+        let style_width = world.resource::<TransformGizmoStyle>().line_width;
+        assert_eq!(widths(&world), (style_width, untouched));
+
+        world.resource_mut::<TransformGizmoStyle>().line_width = 1.5;
+        world.run_system(configure).unwrap();
+        assert_eq!(widths(&world), (1.5, untouched));
+
+        // Unchanged styles are left alone, so the group can be tuned directly.
+        world
+            .resource_mut::<GizmoConfigStore>()
+            .config_mut::<TransformGizmoConfigGroup>()
+            .0
+            .line
+            .width = 7.0;
+        world.run_system(configure).unwrap();
+        assert_eq!(widths(&world), (7.0, untouched));
+
+        world
+            .resource_mut::<TransformGizmoStyle>()
+            .draw_in_default_gizmo_group = true;
+        world.run_system(configure).unwrap();
+        assert_eq!(widths(&world), (7.0, 1.5));
+    }
+
+    #[test]
+    fn depth_mode_sets_the_handle_and_guide_groups_apart() {
+        use bevy::gizmos::config::{GizmoConfig, GizmoConfigStore};
+
+        let mut world = World::new();
+        world.insert_resource(TransformGizmoStyle {
+            depth_mode: GizmoDepthMode::Split {
+                handles_on_top: true,
+                guides_on_top: false,
+            },
+            depth_bias: -0.1,
+            ..default()
+        });
+        let mut store = GizmoConfigStore::default();
+        store.insert(GizmoConfig::default(), TransformGizmoConfigGroup);
+        store.insert(GizmoConfig::default(), TransformGizmoGuideConfigGroup);
+        world.insert_resource(store);
+        world.run_system_once(configure_gizmos).unwrap();
+
+        let store = world.resource::<GizmoConfigStore>();
+        assert_eq!(
+            store.config::<TransformGizmoConfigGroup>().0.depth_bias,
+            -1.0
+        );
+        assert_eq!(
+            store
+                .config::<TransformGizmoGuideConfigGroup>()
+                .0
+                .depth_bias,
+            -0.1
+        );
+    }
+
+    #[test]
+    fn active_target_follows_the_gizmo_active_marker() {
+        let mut world = World::new();
+        world.init_resource::<TransformGizmoState>();
+        world.init_resource::<Messages<GizmoDragEnded>>();
+        let first = world.spawn((TransformGizmoTarget, GizmoActive)).id();
+        let second = world.spawn(TransformGizmoTarget).id();
+
+        world.run_system_once(sync_active_target).unwrap();
+        assert_eq!(
+            world.resource::<TransformGizmoState>().active_target,
+            Some(first)
+        );
+
+        // A second active entity does not steal the gizmo.
+        world.entity_mut(second).insert(GizmoActive);
+        world.run_system_once(sync_active_target).unwrap();
+        assert_eq!(
+            world.resource::<TransformGizmoState>().active_target,
+            Some(first)
+        );
+
+        world.entity_mut(first).remove::<GizmoActive>();
+        world.run_system_once(sync_active_target).unwrap();
+        assert_eq!(
+            world.resource::<TransformGizmoState>().active_target,
+            Some(second)
+        );
+
+        world.entity_mut(second).remove::<GizmoActive>();
+        world.run_system_once(sync_active_target).unwrap();
+        assert_eq!(world.resource::<TransformGizmoState>().active_target, None);
+    }
+
+    #[test]
+    fn targets_gaining_gizmo_active_together_pick_the_same_winner() {
+        let active_target = |world: &mut World| {
+            world.run_system_once(sync_active_target).unwrap();
+            world.resource::<TransformGizmoState>().active_target
+        };
+        for later_archetype_first in [false, true] {
+            let mut world = World::new();
+            world.init_resource::<TransformGizmoState>();
+            world.init_resource::<Messages<GizmoDragEnded>>();
+            let low = world.spawn(TransformGizmoTarget).id();
+            let high = world.spawn((TransformGizmoTarget, Name::new("high"))).id();
+            // Whichever archetype was made first is queried first.
+            if later_archetype_first {
+                world.entity_mut(high).insert(GizmoActive);
+                world.entity_mut(low).insert(GizmoActive);
+            } else {
+                world.entity_mut(low).insert(GizmoActive);
+                world.entity_mut(high).insert(GizmoActive);
+            }
+            assert_eq!(active_target(&mut world), Some(low));
+
+            // A higher priority takes the gizmo over, the current target
+            // keeps it on a tie.
+            world.entity_mut(high).insert(GizmoActivePriority(1));
+            assert_eq!(active_target(&mut world), Some(high));
+            world.entity_mut(low).insert(GizmoActivePriority(1));
+            assert_eq!(active_target(&mut world), Some(high));
+
+            world.commands().set_gizmo_active(low);
+            world.flush();
+            assert!(world.get::<GizmoActive>(high).is_none());
+            assert_eq!(active_target(&mut world), Some(low));
+
+            world.commands().clear_gizmo_active();
+            world.flush();
+            assert!(world.get::<GizmoActive>(low).is_none());
+            assert_eq!(active_target(&mut world), None);
+        }
+    }
+
+    #[cfg(feature = "translate")]
+    #[test]
+    fn a_lost_drag_target_aborts_the_drag_and_clears_hover() {
+        use bevy::ecs::message::Messages;
+
+        let mut world = World::new();
+        world.init_resource::<TransformGizmoState>();
+        world.init_resource::<Messages<GizmoDragEnded>>();
+        let first = world.spawn((TransformGizmoTarget, GizmoActive)).id();
+        let second = world.spawn(TransformGizmoTarget).id();
+        let frame = GizmoFrame::new(&GlobalTransform::IDENTITY, TransformGizmoSpace::World)
+            .with_view_dir(Vec3::NEG_Z);
+        let drag = begin_drag_from_ray(
+            &Ray3d::new(Vec3::new(0.5, 0.0, 10.0), Dir3::NEG_Z),
+            GizmoOperation::TranslateAxis,
+            GizmoAxis::X,
+            first,
+            &frame,
+            &GlobalTransform::IDENTITY,
+            &Transform::default(),
+        );
+        *world.resource_mut::<TransformGizmoState>() = TransformGizmoState {
+            active_target: Some(first),
+            hovered_target: Some(first),
+            hovered_axis: Some(GizmoAxis::X),
+            hovered_op: Some(GizmoOperation::TranslateAxis),
+            drag: Some(drag),
+            ..default()
+        };
+
+        // Moving the marker mid-drag leaves the gizmo on the dragged entity.
+        world.entity_mut(first).remove::<GizmoActive>();
+        world.entity_mut(second).insert(GizmoActive);
+        world.run_system_once(sync_active_target).unwrap();
+        let state = world.resource::<TransformGizmoState>();
+        assert_eq!(state.active_target, Some(first));
+        assert!(state.is_dragging() && state.is_hovering());
+
+        world.despawn(first);
+        world.run_system_once(sync_active_target).unwrap();
+        let state = world.resource::<TransformGizmoState>();
+        assert!(!state.is_dragging() && !state.is_hovering());
+        assert_eq!(state.hovered_target, None);
+        assert_eq!(state.active_target, Some(second));
+        let ended: Vec<_> = world
+            .resource::<Messages<GizmoDragEnded>>()
+            .iter_current_update_messages()
+            .map(|ended| ended.handle.target)
+            .collect();
+        assert_eq!(ended, [first]);
+    }
+
+    #[cfg(feature = "translate")]
+    #[test]
+    fn disabling_the_gizmo_ends_the_drag_and_keeps_the_selection() {
+        use bevy::ecs::message::Messages;
+
+        let mut world = World::new();
+        world.init_resource::<GizmoHandleScreenInfo>();
+        world.init_resource::<OriginDotPress>();
+        world.init_resource::<Messages<GizmoDragEnded>>();
+        let target = world.spawn((TransformGizmoTarget, GizmoActive)).id();
+        let frame = GizmoFrame::new(&GlobalTransform::IDENTITY, TransformGizmoSpace::World)
+            .with_view_dir(Vec3::NEG_Z);
+        let drag = begin_drag_from_ray(
+            &Ray3d::new(Vec3::new(0.5, 0.0, 10.0), Dir3::NEG_Z),
+            GizmoOperation::TranslateAxis,
+            GizmoAxis::X,
+            target,
+            &frame,
+            &GlobalTransform::IDENTITY,
+            &Transform::default(),
+        );
+        world.insert_resource(TransformGizmoState {
+            enabled: false,
+            mode: TransformGizmoMode::Rotate,
+            active_target: Some(target),
+            hovered_target: Some(target),
+            hovered_axis: Some(GizmoAxis::X),
+            hovered_op: Some(GizmoOperation::TranslateAxis),
+            drag: Some(drag),
+            ..default()
+        });
+        assert!(!world.run_system_once(gizmo_enabled).unwrap());
+
+        world.run_system_once(release_disabled_gizmo).unwrap();
+        let state = world.resource::<TransformGizmoState>();
+        assert!(!state.is_dragging() && !state.is_hovering());
+        assert_eq!(state.hovered_target, None);
+        assert_eq!(state.active_target, Some(target));
+        assert_eq!(state.mode, TransformGizmoMode::Rotate);
+        let ended: Vec<_> = world
+            .resource::<Messages<GizmoDragEnded>>()
+            .iter_current_update_messages()
+            .map(|ended| ended.handle.target)
+            .collect();
+        assert_eq!(ended, [target]);
+
+        world.resource_mut::<TransformGizmoState>().enabled = true;
+        assert!(world.run_system_once(gizmo_enabled).unwrap());
     }
 }
@@ -1,6 +1,6 @@
 //! Math utilities for gizmo hit testing and intersection calculations.
 
-use bevy::math::Ray3d;
+use bevy::math::{Affine3A, Ray3d};
 use bevy::prelude::*;
 
 /// Threshold for considering vectors as parallel or zero-length.
@@ -12,6 +12,9 @@ const PLANE_EPSILON: f32 = 1e-5;
 /// Threshold for choosing perpendicular helper vector.
 const AXIS_PARALLEL_THRESHOLD: f32 = 0.9;
 
+/// Maximum Newton iterations when extracting the rotation of a matrix.
+const POLAR_ITERATIONS: usize = 32;
+
 /// Build an orthonormal basis (t1, t2) in the plane perpendicular to `axis`.
 pub fn axis_basis(axis: Vec3) -> (Vec3, Vec3) {
     let axis = axis.normalize_or_zero();
@@ -31,30 +34,201 @@ pub fn axis_basis(axis: Vec3) -> (Vec3, Vec3) {
     (t1, t2)
 }
 
-/// Solve intersection between a ray and a sphere. Returns distance along the
-/// ray if there is an intersection, otherwise `None`.
-pub fn ray_sphere_intersection(ray: &Ray3d, center: Vec3, radius: f32) -> Option<f32> {
+/// Distances along a ray to where it enters and leaves a sphere, nearest
+/// first. The entry is negative when the ray starts inside the sphere.
+/// Returns `None` if the ray misses the sphere or the sphere lies entirely
+/// behind the ray's origin.
+pub fn ray_sphere_intersections(ray: &Ray3d, center: Vec3, radius: f32) -> Option<(f32, f32)> {
     let m = ray.origin - center;
     let b = m.dot(*ray.direction);
-    let c = m.length_squared() - radius * radius;
 
-    // Exit if ray origin is outside sphere (c > 0) and ray is pointing away
-    // from sphere (b > 0).
-    if c > 0.0 && b > 0.0 {
+    // `b * b - c`, from the ray's closest approach to the center so it keeps
+    // its precision when the origin is far away, as on orthographic cameras.
+    let discr = radius * radius - (m - *ray.direction * b).length_squared();
+    if discr < 0.0 {
         return None;
     }
 
-    let discr = b * b - c;
-    if discr < 0.0 {
+    let root = discr.sqrt();
+    let far = -b + root;
+    (far >= 0.0).then_some((-b - root, far))
+}
+
+/// Solve intersection between a ray and a sphere. Returns distance along the
+/// ray if there is an intersection, otherwise `None`.
+///
+/// A ray starting inside the sphere gets the distance to its closest
+/// approach to the center, or `0.0` if the center is behind it, so handles
+/// around the camera still sort by depth instead of all hitting at `0.0`.
+pub fn ray_sphere_intersection(ray: &Ray3d, center: Vec3, radius: f32) -> Option<f32> {
+    let (near, _) = ray_sphere_intersections(ray, center, radius)?;
+    if near >= 0.0 {
+        Some(near)
+    } else {
+        Some((center - ray.origin).dot(*ray.direction).max(0.0))
+    }
+}
+
+/// `ray` started `reach` before its closest approach to `center`, unless it
+/// already starts closer, and how far its origin moved.
+///
+/// Orthographic cameras cast rays from their near plane, which can be
+/// hundreds of units from the gizmo. Hit tests square distances, so from
+/// that far they lose the precision small handles need. Nothing within
+/// `reach` of `center` is skipped, and adding the shift to a hit distance
+/// measures it from the original origin again.
+pub fn ray_near(ray: &Ray3d, center: Vec3, reach: f32) -> (Ray3d, f32) {
+    let shift = ((center - ray.origin).dot(*ray.direction) - reach).max(0.0);
+    let near = Ray3d {
+        origin: ray.get_point(shift),
+        direction: ray.direction,
+    };
+    (near, shift)
+}
+
+/// Intersect a ray with a solid cone. The base disc is centered on `base`
+/// and the tip lies `height` along the unit vector `axis`. Returns the
+/// distance along the ray to the nearest hit (`0.0` when the ray starts
+/// inside the cone), otherwise `None`.
+#[cfg(feature = "translate")]
+pub fn ray_cone_intersection(
+    ray: &Ray3d,
+    base: Vec3,
+    axis: Vec3,
+    height: f32,
+    radius: f32,
+) -> Option<f32> {
+    if height <= EPSILON || radius <= 0.0 {
         return None;
     }
+    // Work from the tip, with `down` pointing into the cone. A point `p` is
+    // inside the (double) cone when `(p.down)^2 * (1 + k^2) >= p.p`.
+    let tip = base + axis * height;
+    let down = -axis;
+    let slope = 1.0 + (radius / height).powi(2);
+    let dir = *ray.direction;
+    let offset = ray.origin - tip;
+    let depth_at = |t: f32| (offset + dir * t).dot(down);
 
-    let t = -b - discr.sqrt();
-    if t < 0.0 {
-        Some(0.0)
+    let d_down = dir.dot(down);
+    let o_down = offset.dot(down);
+    let c = slope * o_down * o_down - offset.length_squared();
+    if c >= 0.0 && (0.0..=height).contains(&o_down) {
+        return Some(0.0);
+    }
+
+    let mut best: Option<f32> = None;
+    let mut consider = |t: f32| {
+        if t >= 0.0 && best.is_none_or(|best| t < best) {
+            best = Some(t);
+        }
+    };
+
+    // Slanted surface.
+    let a = slope * d_down * d_down - dir.length_squared();
+    let b = 2.0 * (slope * d_down * o_down - dir.dot(offset));
+    let mut surface = |t: f32| {
+        if (0.0..=height).contains(&depth_at(t)) {
+            consider(t);
+        }
+    };
+    if a.abs() < EPSILON {
+        if b.abs() > EPSILON {
+            surface(-c / b);
+        }
     } else {
-        Some(t)
+        let discr = b * b - 4.0 * a * c;
+        if discr >= 0.0 {
+            let root = discr.sqrt();
+            surface((-b - root) / (2.0 * a));
+            surface((-b + root) / (2.0 * a));
+        }
     }
+
+    // Base disc.
+    if d_down.abs() > EPSILON {
+        let t = (height - o_down) / d_down;
+        if (ray.origin + dir * t).distance_squared(base) <= radius * radius {
+            consider(t);
+        }
+    }
+
+    best
+}
+
+/// Intersect a ray with a capsule around the segment from `start` to `end`.
+/// Returns the distance along the ray to the nearest hit (`0.0` when the ray
+/// starts inside), otherwise `None`.
+#[cfg(feature = "translate")]
+pub fn ray_capsule_intersection(ray: &Ray3d, start: Vec3, end: Vec3, radius: f32) -> Option<f32> {
+    let segment = end - start;
+    let length = segment.length();
+    let caps = [start, end]
+        .into_iter()
+        .filter_map(|center| ray_sphere_intersections(ray, center, radius))
+        .map(|(near, _)| near.max(0.0));
+    if length < EPSILON {
+        return caps.min_by(f32::total_cmp);
+    }
+
+    // Infinite cylinder around the segment, limited to its length.
+    let axis = segment / length;
+    let dir = *ray.direction;
+    let offset = ray.origin - start;
+    let dir_perp = dir - axis * dir.dot(axis);
+    let offset_perp = offset - axis * offset.dot(axis);
+    let a = dir_perp.length_squared();
+    let b = 2.0 * dir_perp.dot(offset_perp);
+    let c = offset_perp.length_squared() - radius * radius;
+    let along = |t: f32| (offset + dir * t).dot(axis);
+    let body = if a < EPSILON {
+        // Parallel to the segment: only a hit from inside, the caps cover
+        // the ends.
+        (c <= 0.0 && (0.0..=length).contains(&along(0.0))).then_some(0.0)
+    } else {
+        let discr = b * b - 4.0 * a * c;
+        (discr >= 0.0)
+            .then(|| (-b - discr.sqrt()) / (2.0 * a))
+            // A negative entry is a hit only when the ray starts inside.
+            .map(|t| if c <= 0.0 { t.max(0.0) } else { t })
+            .filter(|t| *t >= 0.0 && (0.0..=length).contains(&along(*t)))
+    };
+
+    body.into_iter().chain(caps).min_by(f32::total_cmp)
+}
+
+/// Intersect a ray with an oriented box centered on `center`, with the unit
+/// vectors `axes` as its edges and `half_extents` along each of them.
+/// Returns the distance along the ray to the nearest hit (`0.0` when the ray
+/// starts inside the box), otherwise `None`.
+#[cfg(feature = "scale")]
+pub fn ray_obb_intersection(
+    ray: &Ray3d,
+    center: Vec3,
+    axes: [Vec3; 3],
+    half_extents: Vec3,
+) -> Option<f32> {
+    let to_center = center - ray.origin;
+    let mut enter = 0.0_f32;
+    let mut exit = f32::MAX;
+    for (axis, half) in axes.into_iter().zip(half_extents.to_array()) {
+        let e = axis.dot(to_center);
+        let f = axis.dot(*ray.direction);
+        if f.abs() < EPSILON {
+            // Parallel to this slab: the origin must already be inside it.
+            if e.abs() > half {
+                return None;
+            }
+            continue;
+        }
+        let (t0, t1) = ((e - half) / f, (e + half) / f);
+        enter = enter.max(t0.min(t1));
+        exit = exit.min(t0.max(t1));
+        if enter > exit {
+            return None;
+        }
+    }
+    Some(enter)
 }
 
 /// Intersect a ray with a plane. Returns the intersection point, if any.
@@ -70,3 +244,831 @@ pub fn ray_plane_intersection(ray: &Ray3d, plane_origin: Vec3, plane_normal: Vec
         Some(ray.origin + *ray.direction * t)
     }
 }
+
+/// Intersect a ray with a flat ring (an annulus) centered on `center` in the
+/// plane normal to `normal`, between `inner_radius` and `outer_radius`.
+/// Returns the distance along the ray and the point hit, or `None` if the
+/// ray misses, runs parallel to the plane, or only crosses it behind its
+/// origin. An `inner_radius` of `0.0` makes a full disc.
+pub fn ray_disc_intersection(
+    ray: &Ray3d,
+    center: Vec3,
+    normal: Vec3,
+    inner_radius: f32,
+    outer_radius: f32,
+) -> Option<(f32, Vec3)> {
+    let normal = normal.normalize_or_zero();
+    let denom = normal.dot(*ray.direction);
+    if denom.abs() < PLANE_EPSILON {
+        return None;
+    }
+    let t = (center - ray.origin).dot(normal) / denom;
+    if t < 0.0 {
+        return None;
+    }
+    let point = ray.get_point(t);
+    let radius = point.distance(center);
+    (inner_radius..=outer_radius)
+        .contains(&radius)
+        .then_some((t, point))
+}
+
+/// Coarse samples along the arc in [`ray_arc_closest_approach`].
+const ARC_SAMPLES: usize = 16;
+
+/// Refinement steps after the coarse samples in [`ray_arc_closest_approach`].
+const ARC_REFINEMENTS: usize = 8;
+
+/// Closest approach of a ray to the circle of `radius` around `center` in
+/// the plane normal to `normal`: the distance along the ray (never behind
+/// its origin) and how far the ray passes from the circle there.
+///
+/// Unlike intersecting the circle's plane, this keeps working when the ray
+/// runs nearly parallel to the plane and the circle looks like a line. A
+/// zero `normal` has no circle, and is infinitely far from every ray.
+pub fn ray_circle_closest_approach(
+    ray: &Ray3d,
+    center: Vec3,
+    normal: Vec3,
+    radius: f32,
+) -> (f32, f32) {
+    if normal.length_squared() < EPSILON {
+        let t = (center - ray.origin).dot(*ray.direction).max(0.0);
+        return (t, f32::INFINITY);
+    }
+    let basis = axis_basis(normal);
+    let (t, distance, _) =
+        ray_arc_closest_approach(ray, center, basis, radius, 0.0, std::f32::consts::TAU);
+    (t, distance)
+}
+
+/// Closest approach of a ray to the arc of `radius` around `center`, in the
+/// plane of the orthonormal `basis`, running `sweep` radians from
+/// `start_angle` (measured from the first basis vector toward the second).
+/// Returns the distance along the ray (never behind its origin), how far
+/// the ray passes from the arc there, and the angle of the arc's point.
+///
+/// The nearest of a few points along the arc is refined by alternately
+/// taking the closest point on the ray and on the arc, which is far cheaper
+/// than solving the exact quartic and plenty for picking.
+pub(crate) fn ray_arc_closest_approach(
+    ray: &Ray3d,
+    center: Vec3,
+    basis: (Vec3, Vec3),
+    radius: f32,
+    start_angle: f32,
+    sweep: f32,
+) -> (f32, f32, f32) {
+    let (t1, t2) = basis;
+    let along_ray = |point: Vec3| (point - ray.origin).dot(*ray.direction).max(0.0);
+    let point_at = |angle: f32| center + (t1 * angle.cos() + t2 * angle.sin()) * radius;
+    let gap = |angle: f32| {
+        let point = point_at(angle);
+        point.distance_squared(ray.get_point(along_ray(point)))
+    };
+
+    let middle = start_angle + sweep * 0.5;
+    let mut angle = (0..=ARC_SAMPLES)
+        .map(|i| start_angle + sweep * i as f32 / ARC_SAMPLES as f32)
+        .min_by(|a, b| gap(*a).total_cmp(&gap(*b)))
+        .unwrap_or(middle);
+    for _ in 0..ARC_REFINEMENTS {
+        let offset = ray.get_point(along_ray(point_at(angle))) - center;
+        let (x, y) = (offset.dot(t1), offset.dot(t2));
+        if x * x + y * y < EPSILON {
+            // On the axis, where every angle is as near.
+            break;
+        }
+        let diff = (y.atan2(x) - middle + std::f32::consts::PI).rem_euclid(std::f32::consts::TAU)
+            - std::f32::consts::PI;
+        angle = middle + diff.clamp(-sweep * 0.5, sweep * 0.5);
+    }
+
+    let point = point_at(angle);
+    let t = along_ray(point);
+    (t, ray.get_point(t).distance(point), angle)
+}
+
+/// Closest points between a ray and the segment from `a` to `b`: the
+/// distance along the ray (never behind its origin), the fraction of the way
+/// from `a` to `b`, and the distance between the two points.
+///
+/// A degenerate segment is the point `a`. For a segment parallel to the ray
+/// the point nearest the ray's origin is used.
+pub fn closest_points_ray_segment(ray: &Ray3d, a: Vec3, b: Vec3) -> (f32, f32, f32) {
+    let dir = *ray.direction;
+    let segment = b - a;
+    let offset = ray.origin - a;
+    let length_squared = segment.length_squared();
+    let c = dir.dot(offset);
+
+    let (t_ray, t_segment) = if length_squared < EPSILON {
+        ((-c).max(0.0), 0.0)
+    } else {
+        let b_ = dir.dot(segment);
+        let f = segment.dot(offset);
+        let denom = length_squared - b_ * b_;
+        let t_ray = if denom > EPSILON * length_squared {
+            ((b_ * f - c * length_squared) / denom).max(0.0)
+        } else {
+            0.0
+        };
+        // Clamping the segment's end moves the ray's closest point too.
+        let t_segment = (b_ * t_ray + f) / length_squared;
+        if t_segment < 0.0 {
+            ((-c).max(0.0), 0.0)
+        } else if t_segment > 1.0 {
+            ((b_ - c).max(0.0), 1.0)
+        } else {
+            (t_ray, t_segment)
+        }
+    };
+
+    let distance = ray.get_point(t_ray).distance(a + segment * t_segment);
+    (t_ray, t_segment, distance)
+}
+
+/// Signed angle, in radians, of `rotation`'s twist around `axis`.
+///
+/// This is the rotation's component about `axis` in a swing-twist
+/// decomposition, in `[-PI, PI]`.
+pub fn twist_angle(rotation: Quat, axis: Vec3) -> f32 {
+    let rotation = if rotation.w < 0.0 {
+        -rotation
+    } else {
+        rotation
+    };
+    let along = Vec3::new(rotation.x, rotation.y, rotation.z).dot(axis.normalize_or_zero());
+    2.0 * along.atan2(rotation.w)
+}
+
+/// Componentwise ratio `scale / start_scale`, treating degenerate starts as 1.
+pub fn scale_ratio(scale: Vec3, start_scale: Vec3) -> Vec3 {
+    let ratio = |value: f32, start: f32| {
+        if start.abs() > EPSILON {
+            value / start
+        } else {
+            1.0
+        }
+    };
+    Vec3::new(
+        ratio(scale.x, start_scale.x),
+        ratio(scale.y, start_scale.y),
+        ratio(scale.z, start_scale.z),
+    )
+}
+
+/// Largest ratio between a transform's longest and shortest scaled axis
+/// that still counts as uniform scale.
+const UNIFORM_SCALE_RATIO: f32 = 1.001;
+
+/// Whether `transform` scales some axis noticeably more than another, so
+/// rotated children shear.
+pub fn has_non_uniform_scale(transform: &GlobalTransform) -> bool {
+    let linear = transform.affine().matrix3;
+    let lengths = Vec3::new(
+        linear.x_axis.length(),
+        linear.y_axis.length(),
+        linear.z_axis.length(),
+    );
+    lengths.max_element() > lengths.min_element() * UNIFORM_SCALE_RATIO
+}
+
+/// The unit axis in the space of `parent` that its affine maps onto
+/// `world_axis`, and the sign a turn takes about it: `-1.0` if the parent
+/// mirrors.
+///
+/// A child turned about this axis turns about `world_axis` on screen, even
+/// when the parent's non-uniform scale shears it, unlike conjugating the
+/// turn by the parent's rotation.
+pub fn axis_in_parent(parent: &GlobalTransform, world_axis: Vec3) -> (Vec3, f32) {
+    let linear = parent.affine().matrix3;
+    let axis = (linear.inverse() * world_axis).normalize_or_zero();
+    let sign = if linear.determinant() < 0.0 {
+        -1.0
+    } else {
+        1.0
+    };
+    (axis, sign)
+}
+
+/// Coordinates of `v` along `dir1` and `dir2`, two directions spanning a
+/// plane that need not be perpendicular, ignoring the part of `v` off the
+/// plane. For perpendicular unit directions these are plain dot products.
+#[cfg(feature = "translate")]
+pub fn plane_coordinates(v: Vec3, dir1: Vec3, dir2: Vec3) -> Vec2 {
+    let (a, b, c) = (dir1.dot(dir1), dir1.dot(dir2), dir2.dot(dir2));
+    let (p, q) = (v.dot(dir1), v.dot(dir2));
+    let det = a * c - b * b;
+    if det.abs() < EPSILON {
+        return Vec2::new(p, q);
+    }
+    Vec2::new((c * p - b * q) / det, (a * q - b * p) / det)
+}
+
+/// Decompose an affine matrix into the closest [`Transform`].
+///
+/// Matrices with shear, such as a rotated child under a non-uniformly scaled
+/// parent, have no exact `Transform`. The rotation is the orthogonal polar
+/// factor of the linear part (the nearest rotation matrix) and each scale
+/// component is the stretch of the matching column along its rotated axis.
+/// Mirroring is carried by a negative X scale.
+pub fn nearest_transform(affine: Affine3A) -> Transform {
+    let translation = Vec3::from(affine.translation);
+    let mut linear = Mat3::from(affine.matrix3);
+    let mirrored = linear.determinant() < 0.0;
+    if mirrored {
+        linear.x_axis = -linear.x_axis;
+    }
+
+    // Newton iteration converges to the orthogonal polar factor.
+    let mut orthogonal = linear;
+    for _ in 0..POLAR_ITERATIONS {
+        let inverse_transpose = orthogonal.inverse().transpose();
+        if !inverse_transpose.is_finite() {
+            // Degenerate (zero scale) matrix: fall back to column lengths.
+            let (scale, rotation, translation) = affine.to_scale_rotation_translation();
+            return Transform {
+                translation,
+                rotation,
+                scale,
+            };
+        }
+        let next = (orthogonal + inverse_transpose) * 0.5;
+        let converged = next.abs_diff_eq(orthogonal, EPSILON);
+        orthogonal = next;
+        if converged {
+            break;
+        }
+    }
+
+    let rotation = Quat::from_mat3(&orthogonal).normalize();
+    let axes = Mat3::from_quat(rotation);
+    let mut scale = Vec3::new(
+        axes.x_axis.dot(linear.x_axis),
+        axes.y_axis.dot(linear.y_axis),
+        axes.z_axis.dot(linear.z_axis),
+    );
+    if mirrored {
+        scale.x = -scale.x;
+    }
+
+    Transform {
+        translation,
+        rotation,
+        scale,
+    }
+}
+
+/// World-space size of one logical pixel at `point`, as seen by a camera.
+///
+/// Returns separate horizontal and vertical factors, since off-center and
+/// anisotropic projections differ per axis. The conversion goes through the
+/// camera's actual projection matrix rather than FOV formulas, so oblique and
+/// sheared projections are handled correctly. Returns `None` if the point is
+/// behind the near plane or the camera has no resolvable viewport.
+pub fn world_units_per_pixel_at(
+    point: Vec3,
+    camera: &Camera,
+    camera_transform: &GlobalTransform,
+) -> Option<Vec2> {
+    let viewport_size = camera.logical_viewport_size()?;
+    world_units_per_pixel(
+        camera.clip_from_view(),
+        camera_transform,
+        viewport_size,
+        point,
+    )
+}
+
+/// Matrix-level implementation of [`world_units_per_pixel_at`].
+///
+/// Each factor is measured by offsetting the point's projection by one pixel,
+/// casting the pixel's ray back through the inverse projection, and
+/// intersecting it with the plane of constant view depth through the point.
+pub fn world_units_per_pixel(
+    clip_from_view: Mat4,
+    camera_transform: &GlobalTransform,
+    viewport_size: Vec2,
+    point: Vec3,
+) -> Option<Vec2> {
+    if viewport_size.x <= 0.0 || viewport_size.y <= 0.0 {
+        return None;
+    }
+
+    let world_from_view = Mat4::from(camera_transform.affine());
+    let view_from_world = world_from_view.inverse();
+    let view_from_clip = clip_from_view.inverse();
+
+    let view_point = view_from_world.transform_point3(point);
+    let clip = clip_from_view * view_point.extend(1.0);
+    if clip.w <= EPSILON {
+        return None;
+    }
+    let ndc = clip.truncate() / clip.w;
+    if !ndc.is_finite() || ndc.z > 1.0 + PLANE_EPSILON {
+        return None;
+    }
+
+    // Two depths along each pixel ray; both must map to finite view points
+    // (NDC depth 0 is at infinity for Bevy's reverse-Z perspective).
+    let depth_a = ndc.z;
+    let depth_b = if ndc.z > PLANE_EPSILON {
+        ndc.z * 0.5
+    } else {
+        0.5
+    };
+
+    let neighbor = |offset: Vec2| -> Option<Vec3> {
+        let xy = ndc.truncate() + offset;
+        let unproject = |depth: f32| -> Option<Vec3> {
+            let v = view_from_clip * Vec4::new(xy.x, xy.y, depth, 1.0);
+            if v.w.abs() <= EPSILON {
+                return None;
+            }
+            Some(v.truncate() / v.w)
+        };
+        let a = unproject(depth_a)?;
+        let b = unproject(depth_b)?;
+        let dz = b.z - a.z;
+        let along = if dz.abs() > EPSILON {
+            a + (b - a) * ((view_point.z - a.z) / dz)
+        } else {
+            a
+        };
+        Some(world_from_view.transform_point3(along))
+    };
+
+    let pixel_ndc = Vec2::new(2.0 / viewport_size.x, 2.0 / viewport_size.y);
+    let right = neighbor(Vec2::new(pixel_ndc.x, 0.0))?;
+    let up = neighbor(Vec2::new(0.0, pixel_ndc.y))?;
+    let factors = Vec2::new(right.distance(point), up.distance(point));
+    factors.is_finite().then_some(factors)
+}
+
+/// Rotation that rolls a trackball (arcball) sphere of `radius` from under
+/// the cursor offset `from` to under `to`, both in the plane facing the
+/// camera along `toward_camera`, with its angle multiplied by
+/// `sensitivity`.
+///
+/// Offsets outside the sphere's silhouette land on its rim, so circling
+/// around the outside turns about the view direction.
+#[cfg(feature = "rotate")]
+pub fn trackball_rotation(
+    from: Vec3,
+    to: Vec3,
+    toward_camera: Vec3,
+    radius: f32,
+    sensitivity: f32,
+) -> Quat {
+    let on_sphere = |v: Vec3| {
+        let flat = v - toward_camera * v.dot(toward_camera);
+        let height = radius * radius - flat.length_squared();
+        if height > 0.0 {
+            (flat + toward_camera * height.sqrt()).normalize_or_zero()
+        } else {
+            flat.normalize_or_zero()
+        }
+    };
+    let (from, to) = (on_sphere(from), on_sphere(to));
+    if from == Vec3::ZERO || to == Vec3::ZERO {
+        return Quat::IDENTITY;
+    }
+    let (axis, angle) = Quat::from_rotation_arc(from, to).to_axis_angle();
+    Quat::from_axis_angle(axis, angle * sensitivity)
+}
+
+/// Direction from `point` toward the camera's eye. Orthographic projections
+/// have no eye, so every point looks back along the view direction.
+pub fn toward_camera(
+    clip_from_view: Mat4,
+    camera_transform: &GlobalTransform,
+    point: Vec3,
+) -> Vec3 {
+    // Perspective projections put view depth into clip w.
+    if clip_from_view.w_axis.w == 0.0 {
+        (camera_transform.translation() - point).normalize_or_zero()
+    } else {
+        *camera_transform.back()
+    }
+}
+
+/// Logical viewport position and NDC depth of a world-space point.
+///
+/// Matrix-level counterpart of `Camera::world_to_viewport` (relative to the
+/// viewport's top-left corner). Returns `None` for points behind the camera.
+pub fn viewport_position(
+    clip_from_view: Mat4,
+    camera_transform: &GlobalTransform,
+    viewport_size: Vec2,
+    point: Vec3,
+) -> Option<(Vec2, f32)> {
+    let view_from_world = camera_transform.affine().inverse();
+    let clip = clip_from_view * view_from_world.transform_point3(point).extend(1.0);
+    if clip.w <= EPSILON {
+        return None;
+    }
+    let ndc = clip.truncate() / clip.w;
+    if !ndc.is_finite() {
+        return None;
+    }
+    let position = Vec2::new(
+        (ndc.x + 1.0) * 0.5 * viewport_size.x,
+        (1.0 - ndc.y) * 0.5 * viewport_size.y,
+    );
+    Some((position, ndc.z))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nearest_transform_round_trips_rotation_and_scale() {
+        let transform = Transform::from_xyz(1.0, -2.0, 3.0)
+            .with_rotation(Quat::from_euler(EulerRot::YXZ, 0.4, -0.7, 1.1))
+            .with_scale(Vec3::new(2.0, 0.5, -1.5));
+        let decomposed = nearest_transform(transform.compute_affine());
+        assert!(decomposed
+            .compute_affine()
+            .abs_diff_eq(transform.compute_affine(), 1.0e-4));
+    }
+
+    #[test]
+    fn nearest_transform_keeps_translation_and_orthogonality_under_shear() {
+        // A rotated child under a non-uniformly scaled parent is sheared.
+        let parent = Transform::from_scale(Vec3::new(3.0, 1.0, 1.0)).compute_affine();
+        let child = Transform::from_xyz(0.5, 1.0, 0.0)
+            .with_rotation(Quat::from_rotation_z(0.6))
+            .compute_affine();
+        let sheared = parent * child;
+
+        let decomposed = nearest_transform(sheared);
+        assert!(decomposed
+            .translation
+            .abs_diff_eq(Vec3::from(sheared.translation), 1.0e-5));
+        assert!(decomposed.rotation.is_normalized());
+        assert!(decomposed.scale.cmpgt(Vec3::ZERO).all());
+        // A symmetric stretch applied after a rotation leaves the nearest
+        // rotation equal to the child's own.
+        assert!(
+            decomposed
+                .rotation
+                .angle_between(Quat::from_rotation_z(0.6))
+                .abs()
+                < 1.0e-3
+        );
+    }
+
+    const VIEWPORT: Vec2 = Vec2::new(800.0, 600.0);
+
+    fn camera_at_origin() -> GlobalTransform {
+        GlobalTransform::IDENTITY
+    }
+
+    #[test]
+    fn perspective_units_per_pixel_match_fov_formula() {
+        let fov = std::f32::consts::FRAC_PI_4;
+        let projection = Mat4::perspective_infinite_reverse_rh(fov, VIEWPORT.x / VIEWPORT.y, 0.1);
+        let depth = 10.0;
+        let factors = world_units_per_pixel(
+            projection,
+            &camera_at_origin(),
+            VIEWPORT,
+            Vec3::new(0.0, 0.0, -depth),
+        )
+        .unwrap();
+
+        let expected = 2.0 * depth * (fov * 0.5).tan() / VIEWPORT.y;
+        assert!((factors.x - expected).abs() < 1.0e-4);
+        assert!((factors.y - expected).abs() < 1.0e-4);
+    }
+
+    #[test]
+    fn orthographic_units_per_pixel_ignore_depth() {
+        let projection = Mat4::orthographic_rh(-8.0, 8.0, -3.0, 3.0, 0.0, 100.0);
+        for depth in [1.0, 50.0] {
+            let factors = world_units_per_pixel(
+                projection,
+                &camera_at_origin(),
+                VIEWPORT,
+                Vec3::new(1.0, 0.5, -depth),
+            )
+            .unwrap();
+            assert!((factors.x - 16.0 / VIEWPORT.x).abs() < 1.0e-5);
+            assert!((factors.y - 6.0 / VIEWPORT.y).abs() < 1.0e-5);
+        }
+    }
+
+    /// Infinite reverse-Z frustum with arbitrary left/right/bottom/top at `near`.
+    fn off_center(left: f32, right: f32, bottom: f32, top: f32, near: f32) -> Mat4 {
+        Mat4::from_cols(
+            Vec4::new(2.0 * near / (right - left), 0.0, 0.0, 0.0),
+            Vec4::new(0.0, 2.0 * near / (top - bottom), 0.0, 0.0),
+            Vec4::new(
+                (right + left) / (right - left),
+                (top + bottom) / (top - bottom),
+                0.0,
+                -1.0,
+            ),
+            Vec4::new(0.0, 0.0, near, 0.0),
+        )
+    }
+
+    #[test]
+    fn off_center_frustum_uses_per_axis_extents() {
+        let near = 0.1;
+        let projection = off_center(-0.02, 0.14, -0.03, 0.06, near);
+        let depth = 4.0;
+        let factors = world_units_per_pixel(
+            projection,
+            &camera_at_origin(),
+            VIEWPORT,
+            Vec3::new(0.5, 0.2, -depth),
+        )
+        .unwrap();
+
+        let expected_x = 0.16 * depth / near / VIEWPORT.x;
+        let expected_y = 0.09 * depth / near / VIEWPORT.y;
+        assert!((factors.x - expected_x).abs() < 1.0e-4);
+        assert!((factors.y - expected_y).abs() < 1.0e-4);
+    }
+
+    #[test]
+    fn oblique_near_plane_keeps_lateral_scale() {
+        let near = 0.1;
+        let fov = 1.0;
+        let standard = Mat4::perspective_infinite_reverse_rh(fov, 1.0, near);
+        // Tilt the near plane: depth now also depends on view-space x.
+        let mut oblique = standard;
+        oblique.x_axis.z = 0.05;
+
+        let point = Vec3::new(0.5, -0.25, -5.0);
+        let viewport = Vec2::splat(512.0);
+        let expected =
+            world_units_per_pixel(standard, &camera_at_origin(), viewport, point).unwrap();
+        let factors = world_units_per_pixel(oblique, &camera_at_origin(), viewport, point).unwrap();
+        assert!(factors.abs_diff_eq(expected, 1.0e-4));
+
+        let analytic = 2.0 * 5.0 * (fov * 0.5).tan() / 512.0;
+        assert!((factors.y - analytic).abs() < 1.0e-4);
+    }
+
+    #[test]
+    fn points_behind_the_near_plane_have_no_pixel_size() {
+        let projection = Mat4::perspective_infinite_reverse_rh(1.0, 1.0, 0.5);
+        let camera = GlobalTransform::from(
+            Transform::from_xyz(0.0, 2.0, 0.0).looking_at(Vec3::new(0.0, 2.0, -1.0), Vec3::Y),
+        );
+        for point in [Vec3::new(0.0, 2.0, 3.0), Vec3::new(0.0, 2.0, -0.1)] {
+            assert!(world_units_per_pixel(projection, &camera, VIEWPORT, point).is_none());
+        }
+    }
+
+    #[cfg(feature = "translate")]
+    #[test]
+    fn ray_cone_hits_the_slanted_surface_and_the_base() {
+        // Base disc of radius 0.5 at the origin, tip at x = 1.
+        let hit = |origin: Vec3, direction: Dir3| {
+            ray_cone_intersection(
+                &Ray3d::new(origin, direction),
+                Vec3::ZERO,
+                Vec3::X,
+                1.0,
+                0.5,
+            )
+        };
+
+        // Halfway up, the cone is 0.25 wide.
+        let t = hit(Vec3::new(0.5, 5.0, 0.0), Dir3::NEG_Y).unwrap();
+        assert!((t - 4.75).abs() < 1.0e-5);
+        assert_eq!(hit(Vec3::new(0.5, 5.0, 0.3), Dir3::NEG_Y), None);
+        // Past the tip there is nothing to hit, even within the base radius.
+        assert_eq!(hit(Vec3::new(1.2, 5.0, 0.0), Dir3::NEG_Y), None);
+
+        let t = hit(Vec3::new(-5.0, 0.2, 0.0), Dir3::X).unwrap();
+        assert!((t - 5.0).abs() < 1.0e-5);
+        assert_eq!(hit(Vec3::new(-1.0, 0.0, 0.0), Dir3::NEG_X), None);
+        assert_eq!(hit(Vec3::new(0.2, 0.0, 0.0), Dir3::Y), Some(0.0));
+    }
+
+    #[cfg(feature = "translate")]
+    #[test]
+    fn ray_capsule_hits_the_body_and_the_caps() {
+        let hit = |origin: Vec3| {
+            ray_capsule_intersection(
+                &Ray3d::new(origin, Dir3::NEG_Y),
+                Vec3::ZERO,
+                Vec3::new(2.0, 0.0, 0.0),
+                0.5,
+            )
+        };
+
+        assert!((hit(Vec3::new(1.0, 5.0, 0.0)).unwrap() - 4.5).abs() < 1.0e-5);
+        assert!((hit(Vec3::new(2.3, 5.0, 0.0)).unwrap() - 4.6).abs() < 1.0e-5);
+        assert_eq!(hit(Vec3::new(3.0, 5.0, 0.0)), None);
+        assert_eq!(hit(Vec3::new(1.0, -5.0, 0.0)), None);
+    }
+
+    #[cfg(feature = "scale")]
+    #[test]
+    fn ray_obb_respects_the_box_orientation() {
+        let rotation = Quat::from_rotation_z(std::f32::consts::FRAC_PI_4);
+        let axes = [rotation * Vec3::X, rotation * Vec3::Y, rotation * Vec3::Z];
+        let center = Vec3::new(1.0, 0.0, 0.0);
+        let hit = |origin: Vec3, direction: Dir3| {
+            ray_obb_intersection(
+                &Ray3d::new(origin, direction),
+                center,
+                axes,
+                Vec3::splat(0.5),
+            )
+        };
+
+        // Turned 45 degrees, the top corner sticks up to 0.5 * sqrt(2).
+        let t = hit(Vec3::new(1.0, 5.0, 0.0), Dir3::NEG_Y).unwrap();
+        assert!((t - (5.0 - 0.5 * std::f32::consts::SQRT_2)).abs() < 1.0e-5);
+        // An axis-aligned box would be missed here; the rotated one is not.
+        let t = hit(Vec3::new(1.6, 5.0, 0.0), Dir3::NEG_Y).unwrap();
+        assert!((t - (5.0 - (0.5 * std::f32::consts::SQRT_2 - 0.6))).abs() < 1.0e-5);
+        assert_eq!(hit(Vec3::new(1.0, 5.0, 0.6), Dir3::NEG_Y), None);
+        assert_eq!(hit(Vec3::new(1.0, 5.0, 0.0), Dir3::Y), None);
+        assert_eq!(hit(center, Dir3::Y), Some(0.0));
+    }
+
+    #[test]
+    fn rays_starting_inside_a_sphere_sort_by_their_closest_approach() {
+        let ray = Ray3d::new(Vec3::new(0.0, 0.0, 0.5), Dir3::NEG_Z);
+        let (near, far) = ray_sphere_intersections(&ray, Vec3::ZERO, 1.0).unwrap();
+        assert!((near + 0.5).abs() < 1.0e-5 && (far - 1.5).abs() < 1.0e-5);
+        assert_eq!(ray_sphere_intersection(&ray, Vec3::ZERO, 1.0), Some(0.5));
+
+        // A sphere around the origin whose center is further along the ray
+        // sorts behind one centered nearer, instead of both hitting at zero.
+        let farther = ray_sphere_intersection(&ray, Vec3::new(0.0, 0.0, -0.2), 1.0).unwrap();
+        assert!((farther - 0.7).abs() < 1.0e-5, "{farther}");
+
+        // The center is behind the origin, but the ray still leaves the
+        // sphere ahead of it.
+        let back = Ray3d::new(Vec3::new(0.0, 0.0, 0.5), Dir3::Z);
+        assert_eq!(ray_sphere_intersection(&back, Vec3::ZERO, 1.0), Some(0.0));
+    }
+
+    #[test]
+    fn spheres_behind_the_ray_are_missed() {
+        let ray = Ray3d::new(Vec3::new(0.0, 0.0, 5.0), Dir3::Z);
+        assert_eq!(ray_sphere_intersections(&ray, Vec3::ZERO, 1.0), None);
+        assert_eq!(ray_sphere_intersection(&ray, Vec3::ZERO, 1.0), None);
+    }
+
+    #[test]
+    fn tangent_rays_touch_the_sphere_once() {
+        let ray = Ray3d::new(Vec3::new(0.0, 1.0, 5.0), Dir3::NEG_Z);
+        let (near, far) = ray_sphere_intersections(&ray, Vec3::ZERO, 1.0).unwrap();
+        assert!((near - 5.0).abs() < 1.0e-5 && (far - 5.0).abs() < 1.0e-5);
+        let grazing = Ray3d::new(Vec3::new(0.0, 1.001, 5.0), Dir3::NEG_Z);
+        assert_eq!(ray_sphere_intersection(&grazing, Vec3::ZERO, 1.0), None);
+    }
+
+    #[test]
+    fn small_spheres_keep_their_size_from_far_away() {
+        // An orthographic camera's near plane, a thousand units back.
+        let ray = Ray3d::new(Vec3::new(0.0, 0.0, 1000.0), Dir3::NEG_Z);
+        assert_eq!(
+            ray_sphere_intersection(&ray, Vec3::new(0.07, 0.0, 0.0), 0.05),
+            None
+        );
+        let t = ray_sphere_intersection(&ray, Vec3::new(0.03, 0.0, 0.0), 0.05).unwrap();
+        assert!((t - 999.96).abs() < 1.0e-3, "{t}");
+    }
+
+    #[cfg(feature = "translate")]
+    #[test]
+    fn ray_near_keeps_hits_and_their_distance() {
+        let ray = Ray3d::new(Vec3::new(0.2, 0.01, 1000.0), Dir3::NEG_Z);
+        let center = Vec3::new(0.0, 0.0, 0.0);
+        let (near, shift) = ray_near(&ray, center, 3.0);
+        assert!((shift - 997.0).abs() < 1.0e-3);
+        assert_eq!(near.direction, ray.direction);
+
+        // A thin cone along X, hit from far away through the shifted ray.
+        let t = ray_cone_intersection(&near, Vec3::ZERO, Vec3::X, 0.3, 0.1).unwrap();
+        assert!((t + shift - 1000.0).abs() < 0.1, "{}", t + shift);
+
+        // A ray that already starts near the center is left alone.
+        let close = Ray3d::new(Vec3::new(0.0, 0.0, 2.0), Dir3::NEG_Z);
+        assert_eq!(ray_near(&close, center, 3.0).1, 0.0);
+    }
+
+    #[test]
+    fn ray_disc_hits_only_between_its_radii() {
+        let ray = |x: f32| Ray3d::new(Vec3::new(x, 5.0, 0.0), Dir3::NEG_Y);
+        let (t, point) = ray_disc_intersection(&ray(1.5), Vec3::ZERO, Vec3::Y, 1.0, 2.0).unwrap();
+        assert!((t - 5.0).abs() < 1.0e-5);
+        assert!(point.abs_diff_eq(Vec3::new(1.5, 0.0, 0.0), 1.0e-5));
+        assert!(ray_disc_intersection(&ray(0.5), Vec3::ZERO, Vec3::Y, 1.0, 2.0).is_none());
+        assert!(ray_disc_intersection(&ray(2.5), Vec3::ZERO, Vec3::Y, 1.0, 2.0).is_none());
+        // A full disc, and the same disc with a flipped, unnormalized normal.
+        assert!(ray_disc_intersection(&ray(0.0), Vec3::ZERO, Vec3::Y, 0.0, 2.0).is_some());
+        assert!(
+            ray_disc_intersection(&ray(1.5), Vec3::ZERO, Vec3::NEG_Y * 3.0, 1.0, 2.0).is_some()
+        );
+    }
+
+    #[test]
+    fn ray_disc_degenerate_inputs_miss() {
+        let ray = Ray3d::new(Vec3::new(1.5, 5.0, 0.0), Dir3::NEG_Y);
+        // Zero normal.
+        assert!(ray_disc_intersection(&ray, Vec3::ZERO, Vec3::ZERO, 1.0, 2.0).is_none());
+        // Ray parallel to the plane, even when it lies in it.
+        let parallel = Ray3d::new(Vec3::new(-5.0, 0.0, 0.0), Dir3::X);
+        assert!(ray_disc_intersection(&parallel, Vec3::ZERO, Vec3::Y, 0.0, 2.0).is_none());
+        // Disc behind the ray's origin.
+        let away = Ray3d::new(Vec3::new(1.5, 5.0, 0.0), Dir3::Y);
+        assert!(ray_disc_intersection(&away, Vec3::ZERO, Vec3::Y, 1.0, 2.0).is_none());
+    }
+
+    #[test]
+    fn ray_circle_approach_matches_crossings_of_the_plane() {
+        // Through the plane 0.1 outside the circle, at an angle.
+        let target = Vec3::new(0.0, 0.0, 2.1);
+        let origin = Vec3::new(3.0, 4.0, 5.0);
+        let ray = Ray3d::new(origin, Dir3::new(target - origin).unwrap());
+        let (t, distance) = ray_circle_closest_approach(&ray, Vec3::ZERO, Vec3::Y, 2.0);
+        assert!(distance <= 0.1 + 1.0e-4, "{distance}");
+        assert!(distance > 0.0);
+        assert!((ray.get_point(t) - target).length() < 0.2, "{t}");
+    }
+
+    #[test]
+    fn ray_circle_approach_works_edge_on() {
+        // In the circle's plane, where a plane intersection has no answer.
+        let ray = Ray3d::new(Vec3::new(1.0, 0.0, 10.0), Dir3::NEG_Z);
+        let (t, distance) = ray_circle_closest_approach(&ray, Vec3::ZERO, Vec3::Y, 2.0);
+        assert!(distance < 1.0e-4, "{distance}");
+        // The near crossing, at z = sqrt(3).
+        assert!((t - (10.0 - 3.0_f32.sqrt())).abs() < 1.0e-3, "{t}");
+
+        // Just above the plane, passing over the circle.
+        let above = Ray3d::new(Vec3::new(1.0, 0.05, 10.0), Dir3::NEG_Z);
+        let (_, distance) = ray_circle_closest_approach(&above, Vec3::ZERO, Vec3::Y, 2.0);
+        assert!((distance - 0.05).abs() < 1.0e-3, "{distance}");
+    }
+
+    #[test]
+    fn ray_circle_approach_degenerate_inputs() {
+        let ray = Ray3d::new(Vec3::new(0.0, 5.0, 0.0), Dir3::NEG_Y);
+        // Zero normal: no circle to approach.
+        let (_, distance) = ray_circle_closest_approach(&ray, Vec3::ZERO, Vec3::ZERO, 2.0);
+        assert_eq!(distance, f32::INFINITY);
+        // Down the axis, every point of the circle is equally near.
+        let (t, distance) = ray_circle_closest_approach(&ray, Vec3::ZERO, Vec3::Y, 2.0);
+        assert!((t - 5.0).abs() < 1.0e-4 && (distance - 2.0).abs() < 1.0e-4);
+        // Circle behind the ray's origin: measured from the origin.
+        let away = Ray3d::new(Vec3::new(2.0, 1.0, 0.0), Dir3::Y);
+        let (t, distance) = ray_circle_closest_approach(&away, Vec3::ZERO, Vec3::Y, 2.0);
+        assert_eq!(t, 0.0);
+        assert!((distance - 1.0).abs() < 1.0e-4, "{distance}");
+        // A zero radius is the center.
+        let (_, distance) = ray_circle_closest_approach(&away, Vec3::ZERO, Vec3::Y, 0.0);
+        assert!((distance - 5.0_f32.sqrt()).abs() < 1.0e-4, "{distance}");
+    }
+
+    #[test]
+    fn closest_points_ray_segment_clamps_to_both() {
+        let ray = Ray3d::new(Vec3::new(0.0, 1.0, 5.0), Dir3::NEG_Z);
+        // Crossing above the middle of the segment.
+        let (t_ray, t_segment, distance) =
+            closest_points_ray_segment(&ray, Vec3::new(-1.0, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0));
+        assert!((t_ray - 5.0).abs() < 1.0e-5);
+        assert!((t_segment - 0.5).abs() < 1.0e-5);
+        assert!((distance - 1.0).abs() < 1.0e-5);
+        // Past the segment's end.
+        let (_, t_segment, distance) =
+            closest_points_ray_segment(&ray, Vec3::new(1.0, 0.0, 0.0), Vec3::new(3.0, 0.0, 0.0));
+        assert_eq!(t_segment, 0.0);
+        assert!((distance - 2.0_f32.sqrt()).abs() < 1.0e-5);
+        // Segment behind the ray's origin.
+        let (t_ray, _, distance) =
+            closest_points_ray_segment(&ray, Vec3::new(-1.0, 1.0, 8.0), Vec3::new(1.0, 1.0, 8.0));
+        assert_eq!(t_ray, 0.0);
+        assert!((distance - 3.0).abs() < 1.0e-5);
+    }
+
+    #[test]
+    fn closest_points_ray_segment_degenerate_inputs() {
+        let ray = Ray3d::new(Vec3::new(0.0, 1.0, 5.0), Dir3::NEG_Z);
+        // A zero-length segment is a point.
+        let point = Vec3::new(0.0, 0.0, 2.0);
+        let (t_ray, t_segment, distance) = closest_points_ray_segment(&ray, point, point);
+        assert!((t_ray - 3.0).abs() < 1.0e-5);
+        assert_eq!(t_segment, 0.0);
+        assert!((distance - 1.0).abs() < 1.0e-5);
+        // A parallel segment keeps its distance to the ray.
+        let (t_ray, t_segment, distance) =
+            closest_points_ray_segment(&ray, Vec3::new(0.0, 0.0, 0.0), Vec3::new(0.0, 0.0, -4.0));
+        assert!((distance - 1.0).abs() < 1.0e-5);
+        assert!((0.0..=1.0).contains(&t_segment));
+        assert!(t_ray >= 0.0);
+    }
+}
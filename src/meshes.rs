@@ -0,0 +1,437 @@
+//! Solid mesh handles.
+//!
+//! With [`GizmoRenderMode::Mesh`] the translation cones, plane handles, and
+//! scale cubes of the active target are real mesh entities instead of
+//! `Gizmos` lines. They are spawned once under an internal root entity and
+//! moved to the active target's gizmo frame every frame; their material
+//! colors follow the same idle/hover/active rules as the lines.
+
+use bevy::light::NotShadowCaster;
+use bevy::prelude::*;
+
+use crate::channel::GizmoChannelValue;
+#[cfg(feature = "scale")]
+use crate::draw::scale_handle_factor;
+use crate::draw::{backface_alpha, gizmo_display_color, is_handle_involved, isolation_alpha};
+use crate::gizmo_frame::{GizmoDetail, GizmoFrame, GizmoSizing};
+#[cfg(feature = "planes")]
+use crate::handle_geometry::plane_rect;
+#[cfg(feature = "scale")]
+use crate::handle_geometry::scale_cube;
+#[cfg(feature = "translate")]
+use crate::handle_geometry::translate_cone;
+use crate::interaction::{held_plane_signs, target_frame};
+use crate::types::{
+    GizmoAxis, GizmoCameraSelection, GizmoColorTint, GizmoConstraints, GizmoEditChannel,
+    GizmoEndpoints, GizmoLocked, GizmoOperation, GizmoRenderMode, TransformGizmoCamera,
+    TransformGizmoState, TransformGizmoStyle, TransformGizmoTarget,
+};
+use crate::visual_pivot::{anchor_pivot, AnchorData};
+
+/// Root entity of the mesh handles, placed at the gizmo origin.
+#[derive(Component)]
+pub(crate) struct GizmoMeshRoot;
+
+/// One mesh handle: the operation and axis it stands for.
+#[derive(Component, Clone, Copy)]
+pub(crate) struct GizmoMeshPart {
+    op: GizmoOperation,
+    axis: GizmoAxis,
+}
+
+/// Keeps the target and camera queries disjoint from the mesh entities,
+/// whose `GlobalTransform` is written directly.
+type NotMeshHandle = (Without<GizmoMeshRoot>, Without<GizmoMeshPart>);
+
+/// What [`sync_gizmo_meshes`] lays the active target's handles out from.
+type MeshedTarget = (
+    &'static GlobalTransform,
+    AnchorData,
+    Option<&'static GizmoEditChannel>,
+    Option<&'static GizmoChannelValue>,
+    Option<&'static GizmoEndpoints>,
+    Option<&'static ChildOf>,
+    Option<&'static GizmoConstraints>,
+    Option<&'static GizmoColorTint>,
+    Has<GizmoLocked>,
+);
+
+/// What [`sync_gizmo_meshes`] writes on the mesh root.
+type MeshRootData = (
+    &'static mut Transform,
+    &'static mut GlobalTransform,
+    &'static mut Visibility,
+);
+
+/// What [`sync_gizmo_meshes`] writes on each handle mesh.
+type MeshPartData = (
+    &'static GizmoMeshPart,
+    &'static mut Transform,
+    &'static mut GlobalTransform,
+    &'static mut Visibility,
+    &'static MeshMaterial3d<StandardMaterial>,
+);
+
+/// Handles that get a mesh, in spawn order.
+fn mesh_parts() -> Vec<GizmoMeshPart> {
+    let mut parts = Vec::new();
+    for axis in GizmoAxis::ALL {
+        #[cfg(feature = "translate")]
+        parts.push(GizmoMeshPart {
+            op: GizmoOperation::TranslateAxis,
+            axis,
+        });
+        #[cfg(feature = "planes")]
+        parts.push(GizmoMeshPart {
+            op: GizmoOperation::TranslatePlane,
+            axis,
+        });
+        #[cfg(feature = "scale")]
+        parts.push(GizmoMeshPart {
+            op: GizmoOperation::ScaleAxis,
+            axis,
+        });
+    }
+    parts
+}
+
+/// Unit mesh for a handle; [`part_transform`] scales it to the style's size.
+fn part_mesh(op: GizmoOperation) -> Mesh {
+    match op {
+        GizmoOperation::TranslatePlane => Rectangle::new(1.0, 1.0).into(),
+        GizmoOperation::ScaleAxis => Cuboid::from_length(1.0).into(),
+        _ => Cone {
+            radius: 1.0,
+            height: 1.0,
+        }
+        .into(),
+    }
+}
+
+/// World transform of a mesh handle, or `None` when the handle is hidden.
+///
+/// The shapes match the lines drawn in [`GizmoRenderMode::Lines`]: the cone
+/// sits on the end of the axis line, the plane square starts at
+/// `translate_plane_offset` along both plane axes (or is centered on the
+/// origin in [`planar_2d`](TransformGizmoStyle::planar_2d)), and the cube is
+/// centered at `scale_cube_offset` of the axis length.
+fn part_transform(
+    part: GizmoMeshPart,
+    frame: &GizmoFrame,
+    style: &TransformGizmoStyle,
+) -> Option<Transform> {
+    if style.hidden_in_2d(part.op, part.axis) || !frame.allows(part.op, part.axis) {
+        return None;
+    }
+    match part.op {
+        #[cfg(feature = "translate")]
+        GizmoOperation::TranslateAxis => {
+            if !style.translate_axes.enabled(part.axis) {
+                return None;
+            }
+            let cone = translate_cone(frame, style, part.axis)?;
+            Some(Transform {
+                translation: cone.center(),
+                rotation: Quat::from_rotation_arc(Vec3::Y, cone.axis_dir),
+                scale: Vec3::new(cone.radius, cone.length, cone.radius),
+            })
+        }
+        #[cfg(feature = "planes")]
+        GizmoOperation::TranslatePlane => {
+            if !style.show_translate_planes || !style.translate_axes.enabled(part.axis) {
+                return None;
+            }
+            let plane = plane_rect(frame, style, part.axis)?;
+            let (dir1, normal) = (plane.dir1, plane.dir1.cross(plane.dir2).try_normalize()?);
+            let size = plane.far - plane.near;
+            Some(Transform {
+                translation: plane.center(),
+                rotation: Quat::from_mat3(&Mat3::from_cols(dir1, normal.cross(dir1), normal)),
+                scale: Vec3::new(size, size, 1.0),
+            })
+        }
+        #[cfg(feature = "scale")]
+        GizmoOperation::ScaleAxis => {
+            if !style.scale_axes.enabled(part.axis) {
+                return None;
+            }
+            let cube = scale_cube(frame, style, part.axis)?;
+            let [ax, ay, az] = cube.axes;
+            Some(Transform {
+                translation: cube.center,
+                rotation: Quat::from_mat3(&Mat3::from_cols(ax, ay, az)),
+                scale: Vec3::splat(cube.half_size * 2.0),
+            })
+        }
+        _ => None,
+    }
+}
+
+/// Whether the style and the target's edit channel show this kind of handle.
+fn part_shown(
+    op: GizmoOperation,
+    style: &TransformGizmoStyle,
+    translate: bool,
+    scale: bool,
+) -> bool {
+    match op {
+        GizmoOperation::ScaleAxis => style.show_scale && scale,
+        _ => style.show_translate && translate,
+    }
+}
+
+/// Spawn the mesh root and one entity per handle, hidden until synced.
+fn spawn_mesh_handles(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<StandardMaterial>,
+    style: &TransformGizmoStyle,
+) {
+    commands
+        .spawn((
+            Name::new("Transform Gizmo Meshes"),
+            GizmoMeshRoot,
+            Transform::default(),
+            Visibility::Hidden,
+        ))
+        .with_children(|root| {
+            for part in mesh_parts() {
+                let material = StandardMaterial {
+                    unlit: true,
+                    alpha_mode: AlphaMode::Blend,
+                    cull_mode: None,
+                    depth_bias: style.mesh_depth_bias,
+                    ..default()
+                };
+                root.spawn((
+                    part,
+                    Mesh3d(meshes.add(part_mesh(part.op))),
+                    MeshMaterial3d(materials.add(material)),
+                    Transform::default(),
+                    Visibility::Hidden,
+                    NotShadowCaster,
+                ));
+            }
+        });
+}
+
+/// Keep the mesh handles on the active target in [`GizmoRenderMode::Mesh`].
+///
+/// The root is spawned the first time mesh mode is selected and hidden
+/// whenever there is no active target, no gizmo camera, the gizmo is
+/// disabled, or the style is switched back to lines. This runs after
+/// transform propagation, so it writes each entity's `GlobalTransform` next
+/// to its `Transform`.
+#[allow(clippy::too_many_arguments)]
+pub fn sync_gizmo_meshes(
+    mut commands: Commands,
+    state: Res<TransformGizmoState>,
+    style: Res<TransformGizmoStyle>,
+    targets: Query<MeshedTarget, (With<TransformGizmoTarget>, NotMeshHandle)>,
+    global_transforms: Query<&GlobalTransform, NotMeshHandle>,
+    camera_selection: Res<GizmoCameraSelection>,
+    cameras: Query<(&Camera, &GlobalTransform), (With<TransformGizmoCamera>, NotMeshHandle)>,
+    mut roots: Query<MeshRootData, (With<GizmoMeshRoot>, Without<GizmoMeshPart>)>,
+    mut parts: Query<MeshPartData, Without<GizmoMeshRoot>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let mesh_mode = style.render_mode == GizmoRenderMode::Mesh;
+    let Ok((mut root_transform, mut root_global, mut root_visibility)) = roots.single_mut() else {
+        if mesh_mode {
+            spawn_mesh_handles(&mut commands, &mut meshes, &mut materials, &style);
+        }
+        return;
+    };
+
+    let active = state
+        .active_target
+        .filter(|_| mesh_mode && state.enabled)
+        .and_then(|entity| targets.get(entity).ok().map(|data| (entity, data)))
+        .filter(|(_, (.., locked))| !locked || style.show_locked_gizmos);
+    let camera = camera_selection
+        .camera()
+        .and_then(|camera| cameras.get(camera).ok());
+    let (
+        Some((
+            entity,
+            (
+                transform,
+                anchor,
+                channel,
+                channel_value,
+                endpoints,
+                parent,
+                constraints,
+                tint,
+                locked,
+            ),
+        )),
+        Some((camera, camera_transform)),
+    ) = (active, camera)
+    else {
+        root_visibility.set_if_neq(Visibility::Hidden);
+        return;
+    };
+
+    let sizing = GizmoSizing::new(&style, camera, camera_transform);
+    let (frame, channel_parts) = target_frame(
+        transform,
+        parent.and_then(|parent| global_transforms.get(parent.parent()).ok()),
+        anchor_pivot(anchor, &style).as_ref(),
+        channel,
+        channel_value,
+        endpoints,
+        constraints,
+        state.frame_space(entity),
+        &sizing,
+    );
+    let frame = held_plane_signs(frame, &state, entity);
+    let mut sized = frame.sized_style(&style);
+    if locked {
+        sized = std::borrow::Cow::Owned(sized.locked());
+    } else if let Some(tint) = tint {
+        sized = std::borrow::Cow::Owned(sized.tinted(tint.0));
+    }
+    // Bevy already frustum-culls the meshes; only the distance cutoff is left.
+    if sizing.detail(&frame, &sized, None) != GizmoDetail::Full {
+        root_visibility.set_if_neq(Visibility::Hidden);
+        return;
+    }
+
+    let origin = Transform::from_translation(frame.origin);
+    *root_transform = origin;
+    *root_global = GlobalTransform::from(origin);
+    root_visibility.set_if_neq(Visibility::Inherited);
+
+    for (part, mut local, mut global, mut visibility, material) in parts.iter_mut() {
+        let world = if part_shown(
+            part.op,
+            &sized,
+            channel_parts.translate,
+            channel_parts.scale,
+        ) {
+            part_transform(*part, &frame, &sized)
+        } else {
+            None
+        };
+        #[cfg(feature = "scale")]
+        let world = world.map(|mut world| {
+            let factor = scale_handle_factor(&state, &sized, entity, part.op, part.axis);
+            world.translation = frame.origin + (world.translation - frame.origin) * factor;
+            world
+        });
+        let involved = is_handle_involved(&state, entity, part.op, part.axis);
+        let alpha = backface_alpha(&state, &sized, &frame, entity, part.op, part.axis)
+            * isolation_alpha(&state, &sized, entity, involved);
+        let world = world.filter(|_| alpha > 0.0);
+        let Some(world) = world else {
+            visibility.set_if_neq(Visibility::Hidden);
+            continue;
+        };
+        visibility.set_if_neq(Visibility::Inherited);
+        *local = world.with_translation(world.translation - frame.origin);
+        *global = GlobalTransform::from(world);
+
+        let group = match part.op {
+            GizmoOperation::ScaleAxis => &sized.scale,
+            _ => &sized.translate,
+        };
+        let color = gizmo_display_color(&state, &sized, entity, group, part.axis, part.op);
+        let color = color.with_alpha(color.alpha() * alpha);
+        // Only touch the asset when something changed, so it is not
+        // re-uploaded every frame.
+        let stale = materials.get(&material.0).is_some_and(|material| {
+            material.base_color != color || material.depth_bias != sized.mesh_depth_bias
+        });
+        if stale {
+            if let Some(mut material) = materials.get_mut(&material.0) {
+                material.base_color = color;
+                material.depth_bias = sized.mesh_depth_bias;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::TransformGizmoSpace;
+
+    fn world_frame() -> GizmoFrame {
+        GizmoFrame::new(&GlobalTransform::IDENTITY, TransformGizmoSpace::World)
+    }
+
+    #[cfg(feature = "translate")]
+    #[test]
+    fn cone_mesh_spans_the_line_cone() {
+        let style = TransformGizmoStyle::default();
+        let part = GizmoMeshPart {
+            op: GizmoOperation::TranslateAxis,
+            axis: GizmoAxis::Z,
+        };
+        let transform = part_transform(part, &world_frame(), &style).unwrap();
+
+        // The unit cone's tip is at +0.5 Y and its base at -0.5 Y.
+        let tip = transform.transform_point(Vec3::Y * 0.5);
+        let base = transform.transform_point(Vec3::NEG_Y * 0.5);
+        let base_rim = transform.transform_point(Vec3::new(1.0, -0.5, 0.0));
+        let expected_tip = Vec3::Z * (style.axis_length + style.translate_cone_length);
+        assert!(tip.abs_diff_eq(expected_tip, 1.0e-5));
+        assert!(base.abs_diff_eq(Vec3::Z * style.axis_length, 1.0e-5));
+        assert!((base_rim.distance(base) - style.translate_cone_radius).abs() < 1.0e-5);
+    }
+
+    #[cfg(feature = "planes")]
+    #[test]
+    fn plane_mesh_covers_the_line_square() {
+        let style = TransformGizmoStyle::default();
+        let part = GizmoMeshPart {
+            op: GizmoOperation::TranslatePlane,
+            axis: GizmoAxis::Y,
+        };
+        let transform = part_transform(part, &world_frame(), &style).unwrap();
+
+        let near = style.translate_plane_offset;
+        let far = near + style.translate_plane_size;
+        let corners = [
+            Vec2::new(-0.5, -0.5),
+            Vec2::new(0.5, -0.5),
+            Vec2::new(0.5, 0.5),
+            Vec2::new(-0.5, 0.5),
+        ]
+        .map(|corner| transform.transform_point(corner.extend(0.0)));
+        for corner in corners {
+            assert!(corner.y.abs() < 1.0e-5);
+            for value in [corner.x, corner.z] {
+                assert!((value - near).abs() < 1.0e-5 || (value - far).abs() < 1.0e-5);
+            }
+        }
+
+        let hidden = TransformGizmoStyle {
+            show_translate_planes: false,
+            ..default()
+        };
+        assert!(part_transform(part, &world_frame(), &hidden).is_none());
+    }
+
+    #[cfg(feature = "scale")]
+    #[test]
+    fn cube_mesh_follows_disabled_axes() {
+        let mut style = TransformGizmoStyle::default();
+        let part = GizmoMeshPart {
+            op: GizmoOperation::ScaleAxis,
+            axis: GizmoAxis::X,
+        };
+        let transform = part_transform(part, &world_frame(), &style).unwrap();
+        assert!(transform.translation.abs_diff_eq(
+            Vec3::X * style.axis_length * style.scale_cube_offset,
+            1.0e-5
+        ));
+        assert_eq!(transform.scale, Vec3::splat(style.scale_cube_size));
+
+        style.scale_axes.x = false;
+        assert!(part_transform(part, &world_frame(), &style).is_none());
+        assert!(!part_shown(part.op, &style, true, false));
+    }
+}
@@ -0,0 +1,495 @@
+//! Keyboard nudging of the active target.
+//!
+//! With [`TransformGizmoConfig::keyboard_nudge`] set, the arrow and page keys
+//! step the active target along the gizmo's axes by the current snap
+//! increments, for the last fine adjustments of a placement.
+
+use bevy::prelude::*;
+
+use crate::channel::{edit_global, secondary_channel, GizmoChannelValue};
+use crate::external::{GizmoExternalDriver, GizmoTransformRequest};
+use crate::gizmo_frame::{AxisKind, GizmoFrame};
+use crate::history::{GizmoEdit, GizmoHistory, GizmoHistoryEntry};
+use crate::math::axis_in_parent;
+use crate::types::{
+    GizmoAxis, GizmoCameraSelection, GizmoChannelParts, GizmoConstraints, GizmoEditChannel,
+    GizmoLimits, GizmoLocked, GizmoOperation, NudgeAxes, NudgeSettings, TransformGizmoCamera,
+    TransformGizmoConfig, TransformGizmoMode, TransformGizmoSnap, TransformGizmoState,
+    TransformGizmoTarget,
+};
+
+/// The nudge keys, as (negative, positive) pairs: Left/Right, Down/Up, and
+/// PageDown/PageUp.
+const KEY_PAIRS: [(KeyCode, KeyCode); 3] = [
+    (KeyCode::ArrowLeft, KeyCode::ArrowRight),
+    (KeyCode::ArrowDown, KeyCode::ArrowUp),
+    (KeyCode::PageDown, KeyCode::PageUp),
+];
+
+/// Shortest repeat interval, so a zero interval cannot stall a frame.
+const MIN_REPEAT_INTERVAL: f32 = 1.0e-3;
+
+/// The nudge key being held and the edit it has made so far.
+#[derive(Default)]
+pub struct NudgePress {
+    key: Option<KeyCode>,
+    held: f32,
+    next_repeat: f32,
+    edit: Option<GizmoEdit>,
+}
+
+impl NudgePress {
+    /// Start holding `key`, which nudges once straight away.
+    fn press(&mut self, key: KeyCode, settings: &NudgeSettings) -> u32 {
+        self.key = Some(key);
+        self.held = 0.0;
+        self.next_repeat = settings.repeat_delay;
+        1
+    }
+
+    /// Nudges due after `key` has been held `delta` seconds longer.
+    fn hold(&mut self, delta: f32, settings: &NudgeSettings) -> u32 {
+        self.held += delta;
+        let mut repeats = 0;
+        while self.held >= self.next_repeat {
+            self.next_repeat += settings.repeat_interval.max(MIN_REPEAT_INTERVAL);
+            repeats += 1;
+        }
+        repeats
+    }
+
+    /// Stop holding the key, returning the finished edit.
+    fn release(&mut self) -> Option<GizmoEdit> {
+        self.key = None;
+        self.edit.take()
+    }
+}
+
+/// What [`nudge_active_target`] edits on the active target.
+type NudgeTarget = (
+    &'static GlobalTransform,
+    Option<&'static mut Transform>,
+    Option<&'static GizmoEditChannel>,
+    Option<&'static mut GizmoChannelValue>,
+    Option<&'static ChildOf>,
+    Has<GizmoExternalDriver>,
+    Option<&'static GizmoConstraints>,
+    Option<&'static GizmoLimits>,
+);
+
+/// Step the active target when a nudge key is pressed or held, see
+/// [`NudgeSettings`].
+#[allow(clippy::too_many_arguments)]
+pub fn nudge_active_target(
+    time: Res<Time>,
+    keys: Res<ButtonInput<KeyCode>>,
+    config: Res<TransformGizmoConfig>,
+    snap: Res<TransformGizmoSnap>,
+    state: Res<TransformGizmoState>,
+    camera_selection: Res<GizmoCameraSelection>,
+    cameras: Query<&GlobalTransform, With<TransformGizmoCamera>>,
+    mut targets: Query<NudgeTarget, (With<TransformGizmoTarget>, Without<GizmoLocked>)>,
+    global_transforms: Query<&GlobalTransform>,
+    history: Option<ResMut<GizmoHistory>>,
+    mut requests: MessageWriter<GizmoTransformRequest>,
+    mut press: Local<NudgePress>,
+) {
+    let mut history = history;
+    let mut finish = |press: &mut NudgePress| {
+        let edit = press.release();
+        if let (Some(edit), Some(history)) = (edit, history.as_mut()) {
+            if edit.before != edit.after {
+                history.push(GizmoHistoryEntry { edits: vec![edit] });
+            }
+        }
+    };
+
+    let Some(settings) = config.keyboard_nudge.filter(|_| state.drag.is_none()) else {
+        finish(&mut press);
+        return;
+    };
+    if press.key.is_some_and(|key| !keys.pressed(key)) {
+        finish(&mut press);
+    }
+    let pressed = KEY_PAIRS
+        .iter()
+        .flat_map(|&(negative, positive)| [negative, positive])
+        .find(|&key| keys.just_pressed(key));
+    let nudges = match (pressed, press.key) {
+        (Some(key), _) => {
+            finish(&mut press);
+            press.press(key, &settings)
+        }
+        (None, Some(_)) => press.hold(time.delta_secs(), &settings),
+        (None, None) => 0,
+    };
+    let Some(key) = press.key.filter(|_| nudges > 0) else {
+        return;
+    };
+
+    let Some(entity) = state.active_target else {
+        return;
+    };
+    let Ok((global, transform, channel, channel_value, parent, external, constraints, limits)) =
+        targets.get_mut(entity)
+    else {
+        return;
+    };
+
+    // Edit the same transform a drag would: a secondary channel's value
+    // relative to the target, a world-space copy of an externally driven
+    // target, or the target's own `Transform` in its parent's space.
+    let secondary = secondary_channel(channel, channel_value.as_deref());
+    let external = external && secondary.is_none();
+    let (local, parent_global, parts) = match (secondary, transform.as_deref()) {
+        (Some(value), _) => (value.local, Some(*global), value.parts),
+        _ if external => (global.compute_transform(), None, GizmoChannelParts::ALL),
+        (None, Some(transform)) => {
+            let parent_global =
+                parent.and_then(|parent| global_transforms.get(parent.parent()).ok().copied());
+            (*transform, parent_global, GizmoChannelParts::ALL)
+        }
+        (None, None) => return,
+    };
+    let edit_global = edit_global(global, secondary);
+    let secondary = secondary.is_some();
+    let (kind, allowed) = match state.mode {
+        TransformGizmoMode::Translate => (AxisKind::Translate, parts.translate),
+        TransformGizmoMode::Rotate => (AxisKind::Rotate, parts.rotate),
+        TransformGizmoMode::Scale => (AxisKind::Scale, parts.scale),
+    };
+    if !allowed {
+        return;
+    }
+
+    let camera = camera_selection
+        .camera()
+        .and_then(|camera| cameras.get(camera).ok());
+    let frame = GizmoFrame::from_context(&edit_global, state.space, parent_global.as_ref(), camera);
+    let axes = GizmoAxis::ALL.map(|axis| frame.axis_dir(axis, kind));
+    let camera = camera.filter(|_| settings.axes == NudgeAxes::CameraRelative);
+    let Some((axis, sign)) = key_axis(key, axes, camera) else {
+        return;
+    };
+    let op = match state.mode {
+        TransformGizmoMode::Translate => GizmoOperation::TranslateAxis,
+        TransformGizmoMode::Rotate => GizmoOperation::Rotate,
+        TransformGizmoMode::Scale => GizmoOperation::ScaleAxis,
+    };
+    if constraints.is_some_and(|constraints| !constraints.allows(op, axis)) {
+        return;
+    }
+
+    let snap = snap.resolve(&keys);
+    let (increment, default_step) = match state.mode {
+        TransformGizmoMode::Translate => (&snap.translate, settings.default_translate_step),
+        TransformGizmoMode::Rotate => (&snap.rotate, settings.default_rotate_step),
+        TransformGizmoMode::Scale => (&snap.scale, settings.default_scale_step),
+    };
+    let mut step = increment
+        .get(axis)
+        .filter(|step| *step > 0.0)
+        .unwrap_or(default_step);
+    if keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]) {
+        step *= settings.fast_multiplier;
+    }
+
+    let world_axis = axes[axis.index()];
+    let mut nudged = local;
+    for _ in 0..nudges {
+        nudged = nudge(
+            state.mode,
+            nudged,
+            parent_global.as_ref(),
+            axis,
+            world_axis,
+            sign * step,
+            snap.min_scale,
+        );
+    }
+    if let Some(limits) = limits.filter(|_| !secondary) {
+        limits.clamp(&mut nudged, parent_global.as_ref());
+    }
+    if let Some(constraints) = constraints.filter(|_| !secondary) {
+        nudged.translation = constraints.lock_translation(local.translation, nudged.translation);
+    }
+
+    // Holding a key on one target is one edit; switching targets mid-hold
+    // starts another.
+    if press.edit.is_some_and(|edit| edit.entity != entity) {
+        finish(&mut press);
+        press.key = Some(key);
+    }
+    let edit = press.edit.get_or_insert(GizmoEdit {
+        entity,
+        channel: channel.copied().unwrap_or_default(),
+        before: local,
+        after: local,
+    });
+    edit.after = nudged;
+    match (channel_value, transform) {
+        (Some(mut value), _) if secondary => {
+            value.local = nudged;
+            value.dirty = true;
+        }
+        _ if external => {
+            requests.write(GizmoTransformRequest {
+                target: entity,
+                transform: nudged,
+            });
+        }
+        (_, Some(mut transform)) => *transform = nudged,
+        _ => {}
+    }
+}
+
+/// The gizmo axis `key` steps along and the direction it steps in.
+///
+/// `axes` are the gizmo's unit axes in world space. With a `camera` the key
+/// pairs take the axes closest to the camera's right and forward directions
+/// on the ground plane, and the last one, in that order; without one they
+/// take X, Z, and Y.
+fn key_axis(
+    key: KeyCode,
+    axes: [Vec3; 3],
+    camera: Option<&GlobalTransform>,
+) -> Option<(GizmoAxis, f32)> {
+    let (pair, positive) =
+        KEY_PAIRS
+            .iter()
+            .enumerate()
+            .find_map(|(pair, &(negative, positive))| {
+                (key == negative || key == positive).then_some((pair, key == positive))
+            })?;
+    let sign = if positive { 1.0 } else { -1.0 };
+    let Some(camera) = camera else {
+        return Some(match pair {
+            0 => (GizmoAxis::X, sign),
+            1 => (GizmoAxis::Z, -sign),
+            _ => (GizmoAxis::Y, sign),
+        });
+    };
+
+    let ground = |direction: Vec3| Vec3::new(direction.x, 0.0, direction.z).normalize_or_zero();
+    let right = ground(*camera.right()).normalize_or(Vec3::X);
+    let forward = [*camera.forward(), *camera.up()]
+        .map(ground)
+        .into_iter()
+        .find(|forward| *forward != Vec3::ZERO)
+        .unwrap_or(Vec3::NEG_Z);
+    let mut free = GizmoAxis::ALL.to_vec();
+    let mut closest = |direction: Vec3| {
+        let dot = |axis: &GizmoAxis| axes[axis.index()].dot(direction);
+        let index = (0..free.len())
+            .max_by(|a, b| dot(&free[*a]).abs().total_cmp(&dot(&free[*b]).abs()))
+            .unwrap_or_default();
+        let axis = free.remove(index);
+        let side = if dot(&axis) < 0.0 { -1.0 } else { 1.0 };
+        (axis, side)
+    };
+    let (right_axis, right_side) = closest(right);
+    let (forward_axis, forward_side) = closest(forward);
+    let (up_axis, up_side) = closest(Vec3::Y);
+    Some(match pair {
+        0 => (right_axis, sign * right_side),
+        1 => (forward_axis, sign * forward_side),
+        _ => (up_axis, sign * up_side),
+    })
+}
+
+/// `local` stepped by `amount` in `mode` along `axis`, whose world direction
+/// is `world_axis`. `parent` is the space `local` is in.
+fn nudge(
+    mode: TransformGizmoMode,
+    mut local: Transform,
+    parent: Option<&GlobalTransform>,
+    axis: GizmoAxis,
+    world_axis: Vec3,
+    amount: f32,
+    min_scale: f32,
+) -> Transform {
+    match mode {
+        TransformGizmoMode::Translate => {
+            let world_delta = world_axis * amount;
+            local.translation += parent.map_or(world_delta, |parent| {
+                parent.affine().inverse().transform_vector3(world_delta)
+            });
+        }
+        TransformGizmoMode::Rotate => {
+            let (local_axis, sign) = parent.map_or((world_axis, 1.0), |parent| {
+                axis_in_parent(parent, world_axis)
+            });
+            local.rotation =
+                (Quat::from_axis_angle(local_axis, amount * sign) * local.rotation).normalize();
+        }
+        TransformGizmoMode::Scale => {
+            // Scale steps grow or shrink the axis, keeping its sign.
+            let index = axis.index();
+            let current = local.scale[index];
+            let side = if current < 0.0 { -1.0 } else { 1.0 };
+            local.scale[index] = side * (current.abs() + amount).max(min_scale);
+        }
+    }
+    local
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const AXES: [Vec3; 3] = [Vec3::X, Vec3::Y, Vec3::Z];
+
+    #[test]
+    fn held_keys_repeat_after_the_delay() {
+        let settings = NudgeSettings {
+            repeat_delay: 0.4,
+            repeat_interval: 0.1,
+            ..default()
+        };
+        let mut press = NudgePress::default();
+        assert_eq!(press.press(KeyCode::ArrowUp, &settings), 1);
+        assert_eq!(press.hold(0.3, &settings), 0);
+        assert_eq!(press.hold(0.15, &settings), 1);
+        assert_eq!(press.hold(0.02, &settings), 0);
+        // A long frame catches up on every repeat it covered.
+        assert_eq!(press.hold(0.25, &settings), 3);
+        assert!(press.release().is_none());
+        assert_eq!(press.key, None);
+    }
+
+    #[test]
+    fn absolute_keys_map_to_fixed_axes() {
+        assert_eq!(
+            key_axis(KeyCode::ArrowRight, AXES, None),
+            Some((GizmoAxis::X, 1.0))
+        );
+        assert_eq!(
+            key_axis(KeyCode::ArrowUp, AXES, None),
+            Some((GizmoAxis::Z, -1.0))
+        );
+        assert_eq!(
+            key_axis(KeyCode::PageDown, AXES, None),
+            Some((GizmoAxis::Y, -1.0))
+        );
+        assert_eq!(key_axis(KeyCode::KeyA, AXES, None), None);
+    }
+
+    #[test]
+    fn camera_relative_keys_follow_the_view() {
+        // Looking along +X from above: screen right is +Z and up the screen
+        // is +X, away from the camera.
+        let camera = GlobalTransform::from(
+            Transform::from_xyz(-10.0, 5.0, 0.0).looking_at(Vec3::ZERO, Vec3::Y),
+        );
+        let map = |key| key_axis(key, AXES, Some(&camera));
+        assert_eq!(map(KeyCode::ArrowRight), Some((GizmoAxis::Z, 1.0)));
+        assert_eq!(map(KeyCode::ArrowLeft), Some((GizmoAxis::Z, -1.0)));
+        assert_eq!(map(KeyCode::ArrowUp), Some((GizmoAxis::X, 1.0)));
+        assert_eq!(map(KeyCode::PageUp), Some((GizmoAxis::Y, 1.0)));
+
+        // Straight down, up the screen is the camera's up on the ground.
+        let top = GlobalTransform::from(
+            Transform::from_xyz(0.0, 10.0, 0.0).looking_at(Vec3::ZERO, Vec3::X),
+        );
+        assert_eq!(
+            key_axis(KeyCode::ArrowUp, AXES, Some(&top)),
+            Some((GizmoAxis::X, 1.0))
+        );
+    }
+
+    #[test]
+    fn nudges_respect_the_parent_space() {
+        let parent = GlobalTransform::from(
+            Transform::from_rotation(Quat::from_rotation_y(std::f32::consts::FRAC_PI_2))
+                .with_scale(Vec3::splat(2.0)),
+        );
+        let start = Transform::from_xyz(1.0, 0.0, 0.0);
+        let moved = nudge(
+            TransformGizmoMode::Translate,
+            start,
+            Some(&parent),
+            GizmoAxis::X,
+            Vec3::X,
+            1.0,
+            0.01,
+        );
+        let world =
+            parent.transform_point(moved.translation) - parent.transform_point(start.translation);
+        assert!(world.abs_diff_eq(Vec3::X, 1.0e-5), "moved by {world}");
+
+        let turned = nudge(
+            TransformGizmoMode::Rotate,
+            start,
+            Some(&parent),
+            GizmoAxis::Y,
+            Vec3::Y,
+            0.5,
+            0.01,
+        );
+        assert!(turned
+            .rotation
+            .abs_diff_eq(Quat::from_rotation_y(0.5), 1.0e-5));
+    }
+
+    #[test]
+    fn scale_nudges_keep_the_sign_and_minimum() {
+        let start = Transform::from_scale(Vec3::new(-1.0, 0.2, 1.0));
+        let scale = |axis, amount| {
+            nudge(
+                TransformGizmoMode::Scale,
+                start,
+                None,
+                axis,
+                Vec3::ZERO,
+                amount,
+                0.05,
+            )
+            .scale
+        };
+        assert_eq!(scale(GizmoAxis::X, 0.5), Vec3::new(-1.5, 0.2, 1.0));
+        assert_eq!(scale(GizmoAxis::Y, -0.5), Vec3::new(-1.0, 0.05, 1.0));
+    }
+
+    #[test]
+    fn nudge_keys_step_the_active_target_by_the_snap_increment() {
+        use crate::test_utils::GizmoTestHarness;
+
+        fn keys(harness: &mut GizmoTestHarness) -> Mut<'_, ButtonInput<KeyCode>> {
+            harness.app_mut().world_mut().resource_mut()
+        }
+
+        let mut harness = GizmoTestHarness::new();
+        let world = harness.app_mut().world_mut();
+        world.resource_mut::<TransformGizmoConfig>().keyboard_nudge = Some(NudgeSettings {
+            repeat_delay: 60.0,
+            ..default()
+        });
+        *world.resource_mut::<TransformGizmoSnap>() =
+            TransformGizmoSnap::builder().translate(0.5).build();
+        world.insert_resource(GizmoHistory::default());
+        let tap = |harness: &mut GizmoTestHarness, key: KeyCode| {
+            keys(harness).press(key);
+            harness.step();
+            keys(harness).release(key);
+            harness.step();
+        };
+
+        // The camera looks along -Z from the +X side, so Right is +X and Up
+        // moves away from it.
+        tap(&mut harness, KeyCode::ArrowRight);
+        tap(&mut harness, KeyCode::ArrowUp);
+        let translation = harness.target_transform().translation;
+        assert!(
+            translation.abs_diff_eq(Vec3::new(0.5, 0.0, -0.5), 1.0e-5),
+            "{translation}"
+        );
+        let history = harness.app().world().resource::<GizmoHistory>();
+        assert_eq!(history.len(), 2);
+
+        // Shift takes ten steps at once.
+        keys(&mut harness).press(KeyCode::ShiftLeft);
+        tap(&mut harness, KeyCode::PageUp);
+        let translation = harness.target_transform().translation;
+        assert!((translation.y - 5.0).abs() < 1.0e-5, "{translation}");
+    }
+}
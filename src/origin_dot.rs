@@ -0,0 +1,211 @@
+//! Clicking the origin dot.
+//!
+//! A press on the hovered origin dot that is released within
+//! [`ORIGIN_CLICK_SLOP`] logical pixels of where it started applies
+//! [`TransformGizmoStyle::origin_click_action`] to the target and writes a
+//! [`GizmoOriginClicked`]. A press that moves farther is dropped, so the dot
+//! never starts a drag.
+
+use bevy::input::mouse::MouseButton;
+use bevy::input::ButtonInput;
+use bevy::prelude::*;
+
+use crate::channel::GizmoChannelValue;
+use crate::types::{
+    GizmoActive, GizmoEditChannel, GizmoOperation, GizmoOriginClickAction, GizmoOriginClicked,
+    GizmoPointer, TransformGizmoConfig, TransformGizmoState, TransformGizmoStyle,
+    TransformGizmoTarget,
+};
+
+/// How far in logical pixels the cursor may move between pressing and
+/// releasing the origin dot for the press to still count as a click.
+const ORIGIN_CLICK_SLOP: f32 = 4.0;
+
+/// The origin dot press waiting for its release: the target and the cursor
+/// position it was pressed at.
+#[derive(Resource, Debug, Default)]
+pub(crate) struct OriginDotPress(pub(crate) Option<(Entity, Vec2)>);
+
+/// What [`click_origin_dot`] reads and edits on a target.
+type OriginDotTarget = (
+    &'static mut Transform,
+    Option<&'static GizmoEditChannel>,
+    Option<&'static mut GizmoChannelValue>,
+    Option<&'static ChildOf>,
+);
+
+/// Turn a press and release on the origin dot into a click.
+#[allow(clippy::too_many_arguments)]
+pub fn click_origin_dot(
+    mut commands: Commands,
+    buttons: Res<ButtonInput<MouseButton>>,
+    keys: Res<ButtonInput<KeyCode>>,
+    config: Res<TransformGizmoConfig>,
+    style: Res<TransformGizmoStyle>,
+    mut state: ResMut<TransformGizmoState>,
+    pointer: Res<GizmoPointer>,
+    mut press: ResMut<OriginDotPress>,
+    mut targets: Query<OriginDotTarget, With<TransformGizmoTarget>>,
+    mut clicked: MessageWriter<GizmoOriginClicked>,
+) {
+    let button = config.drag_input.button;
+    if buttons.just_pressed(button)
+        && config.drag_input.modifiers_allow(&keys)
+        && state.drag.is_none()
+        && state.hovered_op == Some(GizmoOperation::OriginDot)
+    {
+        press.0 = state.hovered_target.zip(pointer.position);
+        return;
+    }
+
+    let Some((target, start)) = press.0 else {
+        return;
+    };
+    let moved = pointer
+        .position
+        .is_none_or(|position| position.distance(start) > ORIGIN_CLICK_SLOP);
+    if moved || (!buttons.pressed(button) && !buttons.just_released(button)) {
+        press.0 = None;
+        return;
+    }
+    if !buttons.just_released(button) {
+        return;
+    }
+    press.0 = None;
+
+    match style.origin_click_action {
+        GizmoOriginClickAction::None => {}
+        GizmoOriginClickAction::ResetRotation => {
+            reset_edited(&mut targets, target, |edited| {
+                edited.rotation = Quat::IDENTITY
+            });
+        }
+        GizmoOriginClickAction::ResetScale => {
+            reset_edited(&mut targets, target, |edited| edited.scale = Vec3::ONE);
+        }
+        GizmoOriginClickAction::SelectParent => {
+            let parent = targets
+                .get(target)
+                .ok()
+                .and_then(|(.., parent)| parent)
+                .map(ChildOf::parent)
+                .filter(|parent| targets.contains(*parent));
+            if let Some(parent) = parent {
+                commands.entity(target).try_remove::<GizmoActive>();
+                commands.entity(parent).insert(GizmoActive);
+                state.active_target = Some(parent);
+            }
+        }
+    }
+    clicked.write(GizmoOriginClicked { target });
+}
+
+/// Apply `reset` to the transform the gizmo edits on `target`: its
+/// secondary channel's if it uses one, else its `Transform`.
+fn reset_edited(
+    targets: &mut Query<OriginDotTarget, With<TransformGizmoTarget>>,
+    target: Entity,
+    reset: impl FnOnce(&mut Transform),
+) {
+    let Ok((mut transform, channel, channel_value, _)) = targets.get_mut(target) else {
+        return;
+    };
+    match channel_value {
+        Some(mut value) if channel == Some(&GizmoEditChannel::Secondary) => {
+            let before = value.local;
+            reset(&mut value.local);
+            value.dirty |= value.local != before;
+        }
+        _ => reset(&mut transform),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::message::Messages;
+
+    use super::*;
+    use crate::test_utils::GizmoTestHarness;
+    use crate::types::GizmoAxis;
+
+    /// A harness whose origin dot is the only handle at the origin, clicked
+    /// with `action` on a turned target, hovered and ready to press.
+    fn hovering_the_dot(action: GizmoOriginClickAction) -> (GizmoTestHarness, Vec2) {
+        let mut harness = GizmoTestHarness::new();
+        let mut style = harness.style_mut();
+        style.show_scale_uniform = false;
+        style.origin_click_action = action;
+        let target = harness.target();
+        harness
+            .app_mut()
+            .world_mut()
+            .get_mut::<Transform>(target)
+            .unwrap()
+            .rotation = Quat::from_rotation_y(0.5);
+        harness.step();
+        let dot = harness
+            .handle_position(GizmoOperation::OriginDot, GizmoAxis::X)
+            .unwrap();
+        harness.move_cursor(dot);
+        harness.step();
+        assert_eq!(
+            harness.hovered(),
+            Some((GizmoOperation::OriginDot, GizmoAxis::X))
+        );
+        (harness, dot)
+    }
+
+    fn clicks(harness: &mut GizmoTestHarness) -> Vec<GizmoOriginClicked> {
+        harness
+            .app_mut()
+            .world_mut()
+            .resource_mut::<Messages<GizmoOriginClicked>>()
+            .drain()
+            .collect()
+    }
+
+    #[test]
+    fn clicking_the_origin_dot_applies_its_action() {
+        let (mut harness, _) = hovering_the_dot(GizmoOriginClickAction::ResetRotation);
+        harness.press_left();
+        harness.step();
+        assert!(harness.drag_state().is_none());
+        harness.release_left();
+        harness.step();
+
+        assert_eq!(harness.target_transform().rotation, Quat::IDENTITY);
+        let target = harness.target();
+        assert_eq!(clicks(&mut harness), [GizmoOriginClicked { target }]);
+    }
+
+    #[test]
+    fn dragging_off_the_origin_dot_is_not_a_click() {
+        let (mut harness, dot) = hovering_the_dot(GizmoOriginClickAction::ResetRotation);
+        harness.press_left();
+        harness.step();
+        harness.move_cursor(dot + Vec2::new(40.0, 0.0));
+        harness.step();
+        harness.release_left();
+        harness.step();
+
+        assert!(harness.drag_state().is_none());
+        assert_eq!(
+            harness.target_transform().rotation,
+            Quat::from_rotation_y(0.5)
+        );
+        assert!(clicks(&mut harness).is_empty());
+    }
+
+    #[cfg(feature = "uniform-scale")]
+    #[test]
+    fn the_uniform_scale_handle_covers_the_origin_dot() {
+        let mut harness = GizmoTestHarness::new();
+        let dot = harness.world_to_cursor(Vec3::ZERO).unwrap();
+        harness.move_cursor(dot);
+        harness.step();
+        assert_eq!(
+            harness.hovered(),
+            Some((GizmoOperation::ScaleUniform, GizmoAxis::X))
+        );
+    }
+}
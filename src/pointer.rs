@@ -0,0 +1,122 @@
+//! Pointer input for the gizmo.
+//!
+//! Interaction systems read [`GizmoPointer`] instead of the window directly,
+//! so pen pressure and tilt can be fed from any source.
+
+use bevy::input::mouse::{MouseScrollUnit, MouseWheel};
+use bevy::input::touch::{ForceTouch, TouchInput, TouchPhase};
+use bevy::prelude::*;
+
+use crate::types::GizmoPointer;
+
+/// Pixel-precise wheel movement, as reported by touchpads, that counts as
+/// one line.
+const PIXELS_PER_SCROLL_LINE: f32 = 20.0;
+
+/// Refresh [`GizmoPointer`] from the window under the cursor, pen force, and
+/// the mouse wheel.
+pub fn update_gizmo_pointer(
+    mut pointer: ResMut<GizmoPointer>,
+    windows: Query<(Entity, &Window)>,
+    mut touches: MessageReader<TouchInput>,
+    mut wheel: MessageReader<MouseWheel>,
+) {
+    if pointer.manual {
+        touches.clear();
+        wheel.clear();
+        return;
+    }
+
+    // A window can keep a stale cursor position after the cursor leaves it,
+    // so the focused one wins.
+    let hovered = windows
+        .iter()
+        .filter_map(|(entity, window)| {
+            window
+                .cursor_position()
+                .map(|position| (window.focused, entity, position))
+        })
+        .max_by_key(|(focused, ..)| *focused);
+    let (window, position) = hovered.map_or((None, None), |(_, entity, position)| {
+        (Some(entity), Some(position))
+    });
+    if pointer.position != position || pointer.window != window {
+        pointer.position = position;
+        pointer.window = window;
+    }
+
+    for touch in touches.read() {
+        let pressure = match touch.phase {
+            TouchPhase::Ended | TouchPhase::Canceled => None,
+            TouchPhase::Started | TouchPhase::Moved => touch.force.map(force_to_pressure),
+        };
+        if pointer.pressure != pressure {
+            pointer.pressure = pressure;
+        }
+    }
+
+    let scroll = wheel
+        .read()
+        .map(|wheel| match wheel.unit {
+            MouseScrollUnit::Line => wheel.y,
+            MouseScrollUnit::Pixel => wheel.y / PIXELS_PER_SCROLL_LINE,
+        })
+        .sum();
+    if pointer.scroll != scroll {
+        pointer.scroll = scroll;
+    }
+}
+
+/// Normalized `[0, 1]` pressure from a touch force reading.
+fn force_to_pressure(force: ForceTouch) -> f32 {
+    let pressure = match force {
+        ForceTouch::Calibrated {
+            force,
+            max_possible_force,
+            ..
+        } if max_possible_force > 0.0 => force / max_possible_force,
+        ForceTouch::Calibrated { .. } => 1.0,
+        ForceTouch::Normalized(force) => force,
+    };
+    (pressure as f32).clamp(0.0, 1.0)
+}
+
+#[cfg(all(test, feature = "translate"))]
+mod tests {
+    use super::*;
+    use crate::test_utils::GizmoTestHarness;
+    use crate::types::{GizmoAxis, GizmoOperation};
+
+    #[test]
+    fn captured_pointers_leave_the_gizmo_alone() {
+        let mut harness = GizmoTestHarness::new();
+        let start = harness.grab(GizmoOperation::TranslateAxis, GizmoAxis::X);
+        let set_captured = |harness: &mut GizmoTestHarness, captured: bool| {
+            harness
+                .app_mut()
+                .world_mut()
+                .resource_mut::<GizmoPointer>()
+                .captured = captured;
+        };
+
+        set_captured(&mut harness, true);
+        harness.step();
+        assert_eq!(harness.hovered(), None);
+        harness.press_left();
+        harness.step();
+        assert!(harness.drag_state().is_none());
+        harness.release_left();
+        harness.step();
+
+        // A drag begun before the pointer was captured carries on.
+        set_captured(&mut harness, false);
+        harness.grab(GizmoOperation::TranslateAxis, GizmoAxis::X);
+        harness.press_left();
+        harness.step();
+        set_captured(&mut harness, true);
+        harness.move_cursor(start + harness.screen_axis(Vec3::X) * 60.0);
+        harness.step();
+        assert!(harness.drag_state().is_some());
+        assert!(harness.target_transform().translation.x > 0.1);
+    }
+}
@@ -0,0 +1,476 @@
+//! Per-entity gizmo preferences that outlive the entity.
+//!
+//! Scene reloads despawn and respawn every entity, taking per-target gizmo
+//! components with them. Targets tagged with a [`GizmoStableId`] have those
+//! components mirrored into the [`GizmoPreferenceStore`], keyed by the id
+//! rather than the `Entity`, and re-applied when a target with the same id
+//! appears again.
+
+use bevy::ecs::system::SystemParam;
+use bevy::platform::collections::{HashMap, HashSet};
+use bevy::prelude::*;
+
+use crate::sweep::GizmoBlockedTranslation;
+use crate::types::{
+    GizmoActive, GizmoEditChannel, GizmoEndpoints, GizmoVisualPivot, TransformGizmoConfig,
+    TransformGizmoState, TransformGizmoTarget,
+};
+
+/// Identity of a target that is stable across despawns and scene reloads,
+/// such as a scene node path or an asset GUID.
+///
+/// # Example
+///
+/// ```ignore
+/// commands.spawn((
+///     Transform::default(),
+///     TransformGizmoTarget,
+///     GizmoStableId::from("level/props/crate_03"),
+/// ));
+/// ```
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Component, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GizmoStableId(pub String);
+
+impl From<&str> for GizmoStableId {
+    fn from(id: &str) -> Self {
+        Self(id.to_owned())
+    }
+}
+
+impl From<String> for GizmoStableId {
+    fn from(id: String) -> Self {
+        Self(id)
+    }
+}
+
+impl From<u64> for GizmoStableId {
+    fn from(id: u64) -> Self {
+        Self(id.to_string())
+    }
+}
+
+/// The per-target gizmo components recorded for one [`GizmoStableId`].
+///
+/// `None` means the component is absent, and is restored as absent.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct GizmoPreferences {
+    /// The target's [`GizmoVisualPivot`].
+    pub visual_pivot: Option<GizmoVisualPivot>,
+    /// The target's [`GizmoEditChannel`].
+    pub edit_channel: Option<GizmoEditChannel>,
+    /// The target's [`GizmoBlockedTranslation`].
+    pub blocked_translation: Option<GizmoBlockedTranslation>,
+    /// The target's [`GizmoEndpoints`].
+    pub endpoints: Option<GizmoEndpoints>,
+}
+
+/// How targets sharing a [`GizmoStableId`] are treated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DuplicateStableIds {
+    /// Only the first target bound to an id is restored and recorded; later
+    /// ones are left as spawned.
+    #[default]
+    FirstOnly,
+    /// Every target with the id is restored, and changes on any of them are
+    /// recorded.
+    All,
+}
+
+/// Gizmo preferences of [`GizmoStableId`] targets, kept across despawns.
+///
+/// When a target with a stable id appears, its stored preferences are
+/// applied, and it becomes the active target again if its id was active.
+/// Later changes to those components, including removals, are recorded
+/// back. Despawning a target keeps its entry; entries are only dropped by
+/// [`evict`](Self::evict) and [`clear`](Self::clear).
+///
+/// With the `serde` feature the store can be saved with editor session
+/// state and inserted back as a resource on the next run.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Resource, Debug, Clone, Default, PartialEq)]
+pub struct GizmoPreferenceStore {
+    entries: HashMap<GizmoStableId, GizmoPreferences>,
+    active: Option<GizmoStableId>,
+}
+
+impl GizmoPreferenceStore {
+    /// Stored preferences for `id`.
+    pub fn get(&self, id: &GizmoStableId) -> Option<&GizmoPreferences> {
+        self.entries.get(id)
+    }
+
+    /// Store preferences for `id`, applied the next time a target with the
+    /// id appears.
+    pub fn insert(&mut self, id: GizmoStableId, preferences: GizmoPreferences) {
+        self.entries.insert(id, preferences);
+    }
+
+    /// Drop the entry for `id`, returning it. Also forgets the id as the
+    /// active one.
+    pub fn evict(&mut self, id: &GizmoStableId) -> Option<GizmoPreferences> {
+        if self.active.as_ref() == Some(id) {
+            self.active = None;
+        }
+        self.entries.remove(id)
+    }
+
+    /// Drop every entry and the active id.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.active = None;
+    }
+
+    /// Id of the target that was last active, if it had one.
+    pub fn active_id(&self) -> Option<&GizmoStableId> {
+        self.active.as_ref()
+    }
+
+    /// All stored entries.
+    pub fn iter(&self) -> impl Iterator<Item = (&GizmoStableId, &GizmoPreferences)> {
+        self.entries.iter()
+    }
+
+    /// Number of stored entries.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether no entries are stored.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+/// Which entity each id is bound to, and which duplicate ids were reported.
+#[derive(Default)]
+pub struct StableIdBindings {
+    entities: HashMap<GizmoStableId, Entity>,
+    warned: HashSet<GizmoStableId>,
+}
+
+/// Removals of the recorded components.
+#[derive(SystemParam)]
+pub struct PreferenceRemovals<'w, 's> {
+    visual_pivot: RemovedComponents<'w, 's, GizmoVisualPivot>,
+    edit_channel: RemovedComponents<'w, 's, GizmoEditChannel>,
+    blocked_translation: RemovedComponents<'w, 's, GizmoBlockedTranslation>,
+    endpoints: RemovedComponents<'w, 's, GizmoEndpoints>,
+    active: RemovedComponents<'w, 's, GizmoActive>,
+}
+
+type TrackedTarget = (
+    Entity,
+    &'static GizmoStableId,
+    Option<&'static GizmoVisualPivot>,
+    Option<&'static GizmoEditChannel>,
+    Option<&'static GizmoBlockedTranslation>,
+    Option<&'static GizmoEndpoints>,
+    Has<GizmoActive>,
+);
+
+type PreferencesChanged = Or<(
+    Changed<GizmoVisualPivot>,
+    Changed<GizmoEditChannel>,
+    Changed<GizmoBlockedTranslation>,
+    Changed<GizmoEndpoints>,
+    Added<GizmoActive>,
+)>;
+
+type AppearedFilter = (
+    With<TransformGizmoTarget>,
+    With<GizmoStableId>,
+    Or<(Added<TransformGizmoTarget>, Added<GizmoStableId>)>,
+);
+
+/// Restore stored preferences onto newly appeared [`GizmoStableId`]
+/// targets, then record changes on existing ones.
+#[allow(clippy::too_many_arguments)]
+pub fn sync_gizmo_preferences(
+    mut commands: Commands,
+    config: Res<TransformGizmoConfig>,
+    mut store: ResMut<GizmoPreferenceStore>,
+    mut state: ResMut<TransformGizmoState>,
+    mut bindings: Local<StableIdBindings>,
+    targets: Query<TrackedTarget, With<TransformGizmoTarget>>,
+    appeared: Query<Entity, AppearedFilter>,
+    changed: Query<Entity, (With<TransformGizmoTarget>, PreferencesChanged)>,
+    active: Query<Entity, (With<TransformGizmoTarget>, With<GizmoActive>)>,
+    mut removals: PreferenceRemovals,
+) {
+    let mut restored: HashSet<Entity> = HashSet::default();
+    for entity in &appeared {
+        let Ok((_, id, ..)) = targets.get(entity) else {
+            continue;
+        };
+        let bound = bindings.entities.get(id).copied();
+        if let Some(bound) = bound.filter(|&bound| bound != entity && targets.contains(bound)) {
+            if bindings.warned.insert(id.clone()) {
+                warn!(
+                    "GizmoStableId {:?} is used by both {bound} and {entity}; {}",
+                    id.0,
+                    match config.duplicate_stable_ids {
+                        DuplicateStableIds::FirstOnly => "preferences follow the first only",
+                        DuplicateStableIds::All => "preferences are shared by all of them",
+                    },
+                );
+            }
+            if config.duplicate_stable_ids == DuplicateStableIds::FirstOnly {
+                continue;
+            }
+        } else {
+            bindings.entities.insert(id.clone(), entity);
+        }
+
+        let Some(preferences) = store.entries.get(id) else {
+            // Nothing stored yet: record it as spawned below.
+            continue;
+        };
+        apply_preferences(&mut commands.entity(entity), preferences);
+        if store.active.as_ref() == Some(id) {
+            for other in active.iter().filter(|&other| other != entity) {
+                commands.entity(other).remove::<GizmoActive>();
+            }
+            commands.entity(entity).insert(GizmoActive);
+            state.active_target = Some(entity);
+        }
+        restored.insert(entity);
+    }
+
+    let dirty: HashSet<Entity> = changed
+        .iter()
+        .chain(appeared.iter())
+        .chain(removals.visual_pivot.read())
+        .chain(removals.edit_channel.read())
+        .chain(removals.blocked_translation.read())
+        .chain(removals.endpoints.read())
+        .filter(|entity| !restored.contains(entity))
+        .collect();
+    for entity in dirty {
+        // Despawned targets keep their entry.
+        let Ok((entity, id, visual_pivot, edit_channel, blocked_translation, endpoints, is_active)) =
+            targets.get(entity)
+        else {
+            continue;
+        };
+        if !records(&config, &bindings, id, entity) {
+            continue;
+        }
+        let preferences = GizmoPreferences {
+            visual_pivot: visual_pivot.copied(),
+            edit_channel: edit_channel.copied(),
+            blocked_translation: blocked_translation.copied(),
+            endpoints: endpoints.copied(),
+        };
+        if store.entries.get(id) != Some(&preferences) {
+            store.entries.insert(id.clone(), preferences);
+        }
+        if is_active && store.active.as_ref() != Some(id) {
+            store.active = Some(id.clone());
+        }
+    }
+
+    for entity in removals.active.read() {
+        let Ok((entity, id, .., is_active)) = targets.get(entity) else {
+            continue;
+        };
+        if !is_active
+            && store.active.as_ref() == Some(id)
+            && records(&config, &bindings, id, entity)
+        {
+            store.active = None;
+        }
+    }
+}
+
+/// Whether changes on `entity` are written to the entry for `id`.
+fn records(
+    config: &TransformGizmoConfig,
+    bindings: &StableIdBindings,
+    id: &GizmoStableId,
+    entity: Entity,
+) -> bool {
+    config.duplicate_stable_ids == DuplicateStableIds::All
+        || bindings.entities.get(id) == Some(&entity)
+}
+
+/// Insert the stored components and remove the ones stored as absent.
+fn apply_preferences(entity: &mut EntityCommands, preferences: &GizmoPreferences) {
+    match preferences.visual_pivot {
+        Some(pivot) => entity.insert(pivot),
+        None => entity.remove::<GizmoVisualPivot>(),
+    };
+    match preferences.edit_channel {
+        Some(channel) => entity.insert(channel),
+        None => entity.remove::<GizmoEditChannel>(),
+    };
+    match preferences.blocked_translation {
+        Some(blocked) => entity.insert(blocked),
+        None => entity.remove::<GizmoBlockedTranslation>(),
+    };
+    match preferences.endpoints {
+        Some(endpoints) => entity.insert(endpoints),
+        None => entity.remove::<GizmoEndpoints>(),
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::GizmoAxis;
+
+    fn app() -> App {
+        let mut app = App::new();
+        app.init_resource::<TransformGizmoConfig>()
+            .init_resource::<TransformGizmoState>()
+            .init_resource::<GizmoPreferenceStore>()
+            .add_systems(Update, sync_gizmo_preferences);
+        app
+    }
+
+    fn overrides(world: &World, entity: Entity) -> GizmoPreferences {
+        GizmoPreferences {
+            visual_pivot: world.get::<GizmoVisualPivot>(entity).copied(),
+            edit_channel: world.get::<GizmoEditChannel>(entity).copied(),
+            blocked_translation: world.get::<GizmoBlockedTranslation>(entity).copied(),
+            endpoints: world.get::<GizmoEndpoints>(entity).copied(),
+        }
+    }
+
+    #[test]
+    fn overrides_and_active_status_survive_respawn() {
+        let mut app = app();
+        let id = GizmoStableId::from("props/beam");
+        let target = app
+            .world_mut()
+            .spawn((Transform::default(), TransformGizmoTarget, id.clone()))
+            .id();
+        let other = app
+            .world_mut()
+            .spawn((
+                Transform::default(),
+                TransformGizmoTarget,
+                GizmoStableId::from(7),
+            ))
+            .id();
+        app.update();
+
+        app.world_mut().entity_mut(target).insert((
+            GizmoVisualPivot::manual(Vec3::new(0.5, 1.0, -2.0)),
+            GizmoEditChannel::Secondary,
+            GizmoBlockedTranslation(true),
+            GizmoEndpoints::new(GizmoAxis::Z, 1.5),
+            GizmoActive,
+        ));
+        app.update();
+        let configured = overrides(app.world(), target);
+        assert_eq!(app.world().resource::<GizmoPreferenceStore>().len(), 2);
+
+        app.world_mut().entity_mut(target).despawn();
+        app.world_mut().entity_mut(other).despawn();
+        app.update();
+
+        let respawned = app
+            .world_mut()
+            .spawn((Transform::default(), TransformGizmoTarget, id.clone()))
+            .id();
+        app.update();
+        app.update();
+
+        assert_eq!(overrides(app.world(), respawned), configured);
+        assert!(app.world().get::<GizmoActive>(respawned).is_some());
+        assert_eq!(
+            app.world().resource::<TransformGizmoState>().active_target,
+            Some(respawned)
+        );
+        assert_eq!(
+            app.world().resource::<GizmoPreferenceStore>().active_id(),
+            Some(&id)
+        );
+    }
+
+    #[test]
+    fn removed_overrides_are_recorded_as_absent() {
+        let mut app = app();
+        let id = GizmoStableId::from(42);
+        let target = app
+            .world_mut()
+            .spawn((
+                Transform::default(),
+                TransformGizmoTarget,
+                id.clone(),
+                GizmoBlockedTranslation(false),
+            ))
+            .id();
+        app.update();
+        app.world_mut()
+            .entity_mut(target)
+            .remove::<GizmoBlockedTranslation>();
+        app.update();
+
+        let store = app.world().resource::<GizmoPreferenceStore>();
+        assert_eq!(store.get(&id), Some(&GizmoPreferences::default()));
+    }
+
+    #[test]
+    fn duplicate_ids_restore_the_first_target_only() {
+        let mut app = app();
+        let id = GizmoStableId::from("door");
+        app.world_mut()
+            .resource_mut::<GizmoPreferenceStore>()
+            .insert(
+                id.clone(),
+                GizmoPreferences {
+                    blocked_translation: Some(GizmoBlockedTranslation(true)),
+                    ..default()
+                },
+            );
+        let first = app
+            .world_mut()
+            .spawn((Transform::default(), TransformGizmoTarget, id.clone()))
+            .id();
+        app.update();
+        let second = app
+            .world_mut()
+            .spawn((Transform::default(), TransformGizmoTarget, id.clone()))
+            .id();
+        app.update();
+
+        assert!(app.world().get::<GizmoBlockedTranslation>(first).is_some());
+        assert!(app.world().get::<GizmoBlockedTranslation>(second).is_none());
+
+        // Edits on the duplicate do not leak into the first target's entry.
+        app.world_mut()
+            .entity_mut(second)
+            .insert(GizmoEditChannel::Secondary);
+        app.update();
+        let store = app.world().resource::<GizmoPreferenceStore>();
+        assert_eq!(store.get(&id).unwrap().edit_channel, None);
+    }
+
+    #[test]
+    fn entries_are_only_dropped_by_eviction() {
+        let mut app = app();
+        let id = GizmoStableId::from("lamp");
+        let target = app
+            .world_mut()
+            .spawn((
+                Transform::default(),
+                TransformGizmoTarget,
+                id.clone(),
+                GizmoActive,
+            ))
+            .id();
+        app.update();
+        app.world_mut().entity_mut(target).despawn();
+        app.update();
+
+        let mut store = app.world_mut().resource_mut::<GizmoPreferenceStore>();
+        assert!(store.get(&id).is_some());
+        assert_eq!(store.active_id(), Some(&id));
+        assert!(store.evict(&id).is_some());
+        assert!(store.is_empty());
+        assert_eq!(store.active_id(), None);
+    }
+}
@@ -0,0 +1,236 @@
+//! Ready-made [`TransformGizmoStyle`]s in the look of familiar editors.
+//!
+//! Each preset is a plain constructor built on the default style, so a
+//! project can start from one and adjust the fields it cares about:
+//!
+//! ```ignore
+//! app.insert_resource(TransformGizmoStyle {
+//!     line_width: 3.0,
+//!     ..TransformGizmoStyle::blender()
+//! });
+//! ```
+
+use bevy::prelude::*;
+
+use crate::types::{
+    AxisColors, GizmoScaleMode, GizmoStateColors, RotationRingStyle, TransformGizmoStyle,
+};
+
+/// Axis colors whose hover and active states all use one highlight color,
+/// the way Unity and Unreal light up a handle.
+fn highlighted(x: Color, y: Color, z: Color, hover: Color, active: Color) -> AxisColors {
+    AxisColors::new(
+        GizmoStateColors::new(x, hover, active),
+        GizmoStateColors::new(y, hover, active),
+        GizmoStateColors::new(z, hover, active),
+    )
+}
+
+impl TransformGizmoStyle {
+    /// Blender's look: long, thin axes in muted red, green, and blue that
+    /// brighten on hover, full rotation rings with the white view ring and
+    /// trackball, and a white origin dot.
+    pub fn blender() -> Self {
+        let colors = AxisColors::new(
+            GizmoStateColors::new(
+                Color::srgb(0.85, 0.27, 0.33),
+                Color::srgb(1.0, 0.55, 0.6),
+                Color::srgb(1.0, 0.85, 0.87),
+            ),
+            GizmoStateColors::new(
+                Color::srgb(0.5, 0.76, 0.15),
+                Color::srgb(0.72, 0.92, 0.42),
+                Color::srgb(0.9, 1.0, 0.8),
+            ),
+            GizmoStateColors::new(
+                Color::srgb(0.22, 0.5, 0.88),
+                Color::srgb(0.5, 0.72, 1.0),
+                Color::srgb(0.82, 0.9, 1.0),
+            ),
+        );
+        Self {
+            line_width: 2.0,
+            axis_lines: colors.clone(),
+            translate: colors.clone(),
+            rotate: colors.clone(),
+            scale: colors,
+            rotation_ring_style: RotationRingStyle::FullCircle,
+            rotation_arc_segments: 48,
+            show_rotate_view: true,
+            show_rotate_trackball: true,
+            origin_dot_colors: GizmoStateColors::new(
+                Color::WHITE,
+                Color::srgb(1.0, 0.85, 0.4),
+                Color::srgb(1.0, 1.0, 0.7),
+            ),
+            ..Self::default().with_handle_sizes(2.4, 0.3, 0.07, 0.15)
+        }
+    }
+
+    /// Unity's look: short, stubby axes in saturated red, green, and blue
+    /// that turn yellow under the cursor, and full rotation rings with the
+    /// view ring and the free-rotation trackball inside them.
+    pub fn unity() -> Self {
+        let yellow = Color::srgb(1.0, 0.92, 0.02);
+        let colors = highlighted(
+            Color::srgb(0.86, 0.24, 0.11),
+            Color::srgb(0.6, 0.95, 0.28),
+            Color::srgb(0.23, 0.52, 0.97),
+            yellow,
+            yellow,
+        );
+        Self {
+            line_width: 3.0,
+            axis_lines: colors.clone(),
+            translate: colors.clone(),
+            rotate: colors.clone(),
+            scale: colors,
+            rotation_ring_style: RotationRingStyle::FullCircle,
+            rotation_arc_segments: 40,
+            show_rotate_view: true,
+            show_rotate_trackball: true,
+            origin_dot_colors: GizmoStateColors::new(Color::srgb(0.8, 0.8, 0.8), yellow, yellow),
+            ..Self::default().with_handle_sizes(1.5, 0.45, 0.15, 0.25)
+        }
+    }
+
+    /// Unreal Engine's look: red, green, and blue axes that turn yellow
+    /// under the cursor, quarter arcs instead of full rotation rings, and
+    /// the screen-space translation circle and uniform scale square at the
+    /// center.
+    pub fn unreal() -> Self {
+        let yellow = Color::srgb(1.0, 1.0, 0.0);
+        let colors = highlighted(
+            Color::srgb(0.9, 0.15, 0.1),
+            Color::srgb(0.35, 0.75, 0.15),
+            Color::srgb(0.1, 0.35, 0.9),
+            yellow,
+            yellow,
+        );
+        Self {
+            line_width: 3.0,
+            axis_lines: colors.clone(),
+            translate: colors.clone(),
+            rotate: colors.clone(),
+            scale: colors,
+            rotation_ring_style: RotationRingStyle::Arc { degrees: 90.0 },
+            show_translate_view: true,
+            show_scale_uniform: true,
+            ..Self::default().with_handle_sizes(2.0, 0.45, 0.14, 0.2)
+        }
+    }
+
+    /// A small, uncluttered gizmo for dense scenes: the default colors on
+    /// thin, short axes sized in screen pixels, narrow rotation arcs, and
+    /// only the axis handles.
+    pub fn compact() -> Self {
+        Self {
+            line_width: 2.0,
+            scale_mode: GizmoScaleMode::ScreenSpace(60.0),
+            rotation_ring_style: RotationRingStyle::Arc { degrees: 20.0 },
+            rotation_arc_segments: 12,
+            show_translate_planes: false,
+            show_scale_uniform: false,
+            show_origin_dot: false,
+            ..Self::default().with_handle_sizes(1.2, 0.25, 0.08, 0.12)
+        }
+    }
+
+    /// This style with new axis, cone, and cube sizes, and the hit radii and
+    /// center handles that the default style derives from them
+    /// resized to match.
+    fn with_handle_sizes(
+        self,
+        axis_length: f32,
+        translate_cone_length: f32,
+        translate_cone_radius: f32,
+        scale_cube_size: f32,
+    ) -> Self {
+        let factor = axis_length / self.axis_length;
+        Self {
+            axis_length,
+            translate_cone_length,
+            translate_cone_radius,
+            translate_hit_radius: translate_cone_length * 0.9,
+            scale_cube_size,
+            scale_hit_radius: scale_cube_size * 0.9,
+            rotate_view_radius: axis_length * 1.25,
+            translate_plane_size: self.translate_plane_size * factor,
+            translate_plane_offset: self.translate_plane_offset * factor,
+            ..self
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn idle_x(style: &TransformGizmoStyle) -> Color {
+        style.translate.x.idle
+    }
+
+    #[test]
+    fn blender_has_long_thin_axes_and_full_rings() {
+        let style = TransformGizmoStyle::blender();
+        let default = TransformGizmoStyle::default();
+        assert!(style.axis_length > default.axis_length);
+        assert!(style.translate_cone_radius < default.translate_cone_radius);
+        assert_eq!(style.rotation_ring_style, RotationRingStyle::FullCircle);
+        assert!(style.show_rotate_view && style.show_rotate_trackball);
+        // Muted: no channel of the X color is at full strength.
+        let x = idle_x(&style).to_srgba();
+        assert!(x.red < 1.0 && x.green > 0.0 && x.blue > 0.0);
+    }
+
+    #[test]
+    fn unity_has_stubby_axes_and_yellow_hover() {
+        let style = TransformGizmoStyle::unity();
+        let blender = TransformGizmoStyle::blender();
+        assert!(style.axis_length < blender.axis_length);
+        assert!(style.translate_cone_radius > blender.translate_cone_radius);
+        let yellow = Color::srgb(1.0, 0.92, 0.02);
+        assert_eq!(style.translate.x.hover, yellow);
+        assert_eq!(style.rotate.z.hover, yellow);
+        assert_eq!(style.rotation_ring_style, RotationRingStyle::FullCircle);
+    }
+
+    #[test]
+    fn unreal_has_quarter_arcs_and_a_screen_translate_handle() {
+        let style = TransformGizmoStyle::unreal();
+        assert_eq!(
+            style.rotation_ring_style,
+            RotationRingStyle::Arc { degrees: 90.0 }
+        );
+        assert!(style.show_translate_view && style.show_scale_uniform);
+        assert_eq!(style.scale.y.hover, Color::srgb(1.0, 1.0, 0.0));
+    }
+
+    #[test]
+    fn compact_is_small_on_screen_with_only_axis_handles() {
+        let style = TransformGizmoStyle::compact();
+        assert_eq!(style.scale_mode, GizmoScaleMode::ScreenSpace(60.0));
+        assert!(style.axis_length < TransformGizmoStyle::default().axis_length);
+        assert!(!style.show_translate_planes && !style.show_scale_uniform);
+        assert!(!style.show_origin_dot);
+        assert_eq!(idle_x(&style), idle_x(&TransformGizmoStyle::default()));
+    }
+
+    #[test]
+    fn presets_keep_hit_bounds_around_their_handles() {
+        for style in [
+            TransformGizmoStyle::blender(),
+            TransformGizmoStyle::unity(),
+            TransformGizmoStyle::unreal(),
+            TransformGizmoStyle::compact(),
+        ] {
+            let reach = style.axis_length + style.translate_cone_length;
+            assert!(style.effective_bounds_radius() >= reach);
+            assert_eq!(
+                style.translate_hit_radius,
+                style.translate_cone_length * 0.9
+            );
+            assert_eq!(style.rotate_view_radius, style.axis_length * 1.25);
+        }
+    }
+}
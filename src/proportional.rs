@@ -0,0 +1,421 @@
+//! Carrying other active targets along with a drag.
+//!
+//! Every [`TransformGizmoTarget`] with [`GizmoActive`] besides the dragged
+//! one follows the drag: the change the drag has made to its target since it
+//! started, taken about the gizmo's pivot, is applied to each of them. With
+//! [`GizmoProportionalEdit`] enabled, targets farther from the pivot get a
+//! smaller share of it, like Blender's proportional editing.
+
+use bevy::math::Affine3A;
+use bevy::prelude::*;
+
+use crate::math::nearest_transform;
+use crate::types::{
+    GizmoActive, GizmoDragEnded, GizmoDragStarted, GizmoEditChannel, GizmoPointer,
+    TransformGizmoDrag, TransformGizmoState, TransformGizmoTarget,
+};
+
+/// Smallest influence radius the scroll wheel shrinks
+/// [`GizmoProportionalEdit::radius`] to.
+const MIN_PROPORTIONAL_RADIUS: f32 = 1.0e-3;
+
+/// How the share of a drag a target gets falls off with its distance from
+/// the pivot, see [`GizmoProportionalEdit`].
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Default)]
+pub enum FalloffCurve {
+    /// Eases out near the pivot and in near the edge of the radius.
+    #[default]
+    Smooth,
+    /// Falls off evenly with distance.
+    Linear,
+    /// Drops quickly away from the pivot.
+    Sharp,
+    /// The full drag everywhere inside the radius.
+    Constant,
+}
+
+impl FalloffCurve {
+    /// Share of the drag, in `[0, 1]`, a target `distance` from the pivot
+    /// gets within `radius`.
+    ///
+    /// ```
+    /// # use bevy_transform_tools::FalloffCurve;
+    /// assert_eq!(FalloffCurve::Linear.weight(0.5, 2.0), 0.75);
+    /// assert_eq!(FalloffCurve::Smooth.weight(3.0, 2.0), 0.0);
+    /// ```
+    pub fn weight(self, distance: f32, radius: f32) -> f32 {
+        if radius <= 0.0 || distance >= radius {
+            return 0.0;
+        }
+        let t = (distance / radius).max(0.0);
+        match self {
+            FalloffCurve::Smooth => 1.0 - t * t * (3.0 - 2.0 * t),
+            FalloffCurve::Linear => 1.0 - t,
+            FalloffCurve::Sharp => (1.0 - t) * (1.0 - t),
+            FalloffCurve::Constant => 1.0,
+        }
+    }
+}
+
+/// Proportional editing of the other active targets during a drag.
+///
+/// While `enabled`, each [`GizmoActive`] target other than the dragged one
+/// gets the share of the drag `falloff` gives its distance from the pivot,
+/// and none outside `radius`. Distances are measured when the drag starts,
+/// so targets never fall out of the radius as they move. The mouse wheel
+/// grows and shrinks the radius during the drag, and the radius is drawn as
+/// a circle around the gizmo. Disabled, every active target follows the
+/// drag in full.
+///
+/// ```ignore
+/// fn toggle_proportional(keys: Res<ButtonInput<KeyCode>>, mut edit: ResMut<GizmoProportionalEdit>) {
+///     if keys.just_pressed(KeyCode::KeyO) {
+///         edit.enabled = !edit.enabled;
+///     }
+/// }
+/// ```
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Resource, Clone, Debug, PartialEq, Reflect)]
+#[reflect(Resource, Default)]
+pub struct GizmoProportionalEdit {
+    /// Whether the share of the drag falls off with distance.
+    pub enabled: bool,
+    /// Distance from the pivot, in world units, at which the share of the
+    /// drag reaches zero.
+    pub radius: f32,
+    /// How the share falls off between the pivot and `radius`.
+    pub falloff: FalloffCurve,
+    /// Factor each line of mouse wheel scrolled up multiplies `radius` by
+    /// during a drag. `1.0` keeps the radius fixed.
+    pub scroll_step: f32,
+    /// Color of the influence circle.
+    pub color: Color,
+}
+
+impl Default for GizmoProportionalEdit {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            radius: 2.0,
+            falloff: FalloffCurve::Smooth,
+            scroll_step: 1.1,
+            color: Color::srgba(1.0, 1.0, 1.0, 0.5),
+        }
+    }
+}
+
+/// An active target following the drag.
+#[derive(Debug, Clone, Copy)]
+struct CarriedTarget {
+    entity: Entity,
+    /// Its parent's global transform when the drag started.
+    parent: Option<GlobalTransform>,
+    /// Its `Transform` when the drag started.
+    start: Transform,
+    /// Its distance from the pivot when the drag started.
+    distance: f32,
+}
+
+/// The targets the current drag carries, captured when it started.
+#[derive(Resource, Debug, Default)]
+pub(crate) struct CarriedTargets {
+    /// The dragged target.
+    target: Option<Entity>,
+    /// World-space pivot the drag started about.
+    pivot: Vec3,
+    carried: Vec<CarriedTarget>,
+}
+
+/// What [`carry_active_targets`] moves: every other active target.
+type CarriedQuery = (Entity, &'static mut Transform, Option<&'static ChildOf>);
+
+/// Move the other active targets along with this frame's drag.
+///
+/// Targets whose ancestor is the dragged target or another carried target
+/// are left to their parent. Drags of a secondary channel carry nothing.
+#[allow(clippy::too_many_arguments)]
+pub fn carry_active_targets(
+    mut carried: ResMut<CarriedTargets>,
+    mut proportional: ResMut<GizmoProportionalEdit>,
+    state: Res<TransformGizmoState>,
+    pointer: Res<GizmoPointer>,
+    mut started: MessageReader<GizmoDragStarted>,
+    mut ended: MessageReader<GizmoDragEnded>,
+    mut targets: Query<CarriedQuery, (With<TransformGizmoTarget>, With<GizmoActive>)>,
+    parents: Query<&ChildOf>,
+    global_transforms: Query<&GlobalTransform>,
+) {
+    for ended in ended.read() {
+        if carried.target != Some(ended.handle.target) {
+            continue;
+        }
+        // A cancelled drag puts its target back, so the carried ones go back
+        // with it.
+        if ended.cancelled {
+            for target in &carried.carried {
+                if let Ok((_, mut transform, _)) = targets.get_mut(target.entity) {
+                    transform.set_if_neq(target.start);
+                }
+            }
+        }
+        *carried = CarriedTargets::default();
+    }
+
+    let Some(drag) = state.drag.as_ref() else {
+        return;
+    };
+    if started
+        .read()
+        .any(|started| started.handle.target == drag.target)
+    {
+        capture(&mut carried, drag, &targets, &parents, &global_transforms);
+    }
+    if carried.target != Some(drag.target) || carried.carried.is_empty() {
+        return;
+    }
+
+    if proportional.enabled && pointer.scroll != 0.0 {
+        let radius = proportional.radius * proportional.scroll_step.powf(pointer.scroll);
+        proportional.radius = radius.max(MIN_PROPORTIONAL_RADIUS);
+    }
+
+    let world = |transform: Transform| {
+        drag.start_parent
+            .map_or(transform.compute_affine(), |parent| {
+                parent.affine() * transform.compute_affine()
+            })
+    };
+    let change = world(drag.last_transform) * world(drag.initial_transform).inverse();
+    for target in &carried.carried {
+        let weight = if proportional.enabled {
+            proportional
+                .falloff
+                .weight(target.distance, proportional.radius)
+        } else {
+            1.0
+        };
+        let moved = share_of(change, carried.pivot, weight) * world_of(target);
+        let local = nearest_transform(
+            target
+                .parent
+                .map_or(moved, |parent| parent.affine().inverse() * moved),
+        );
+        if let Ok((_, mut transform, _)) = targets.get_mut(target.entity) {
+            transform.set_if_neq(local);
+        }
+    }
+}
+
+/// Record the targets `drag` carries and their distances from its pivot.
+fn capture(
+    carried: &mut CarriedTargets,
+    drag: &TransformGizmoDrag,
+    targets: &Query<CarriedQuery, (With<TransformGizmoTarget>, With<GizmoActive>)>,
+    parents: &Query<&ChildOf>,
+    global_transforms: &Query<&GlobalTransform>,
+) {
+    *carried = CarriedTargets {
+        target: Some(drag.target),
+        pivot: drag.origin,
+        carried: Vec::new(),
+    };
+    if drag.channel != GizmoEditChannel::Transform {
+        return;
+    }
+    let moves_with = |entity: Entity| entity == drag.target || targets.contains(entity);
+    for (entity, transform, parent) in targets {
+        if entity == drag.target || parents.iter_ancestors(entity).any(moves_with) {
+            continue;
+        }
+        let parent = parent.and_then(|parent| global_transforms.get(parent.parent()).ok());
+        let target = CarriedTarget {
+            entity,
+            parent: parent.copied(),
+            start: *transform,
+            distance: 0.0,
+        };
+        let distance = Vec3::from(world_of(&target).translation).distance(drag.origin);
+        carried.carried.push(CarriedTarget { distance, ..target });
+    }
+}
+
+/// `target`'s world-space transform when the drag started.
+fn world_of(target: &CarriedTarget) -> Affine3A {
+    let local = target.start.compute_affine();
+    target
+        .parent
+        .map_or(local, |parent| parent.affine() * local)
+}
+
+/// `weight` of the world-space `change`, taken about `pivot`: the pivot
+/// moves that share of the way, and turns and scales by that share about
+/// itself.
+fn share_of(change: Affine3A, pivot: Vec3, weight: f32) -> Affine3A {
+    if weight >= 1.0 {
+        return change;
+    }
+    let about_pivot = Affine3A::from_translation(-pivot);
+    let parts = nearest_transform(about_pivot * change * about_pivot.inverse());
+    let partial = Transform {
+        translation: parts.translation * weight,
+        rotation: Quat::IDENTITY.slerp(parts.rotation, weight),
+        scale: Vec3::ONE.lerp(parts.scale, weight),
+    };
+    about_pivot.inverse() * partial.compute_affine() * about_pivot
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "translate")]
+    use crate::test_utils::GizmoTestHarness;
+    #[cfg(feature = "translate")]
+    use crate::types::{GizmoAxis, GizmoOperation};
+
+    /// A harness with another active target at each of `others`, pressing
+    /// the X translate arrow of its own target.
+    #[cfg(feature = "translate")]
+    fn pressing_with_others(others: &[Vec3]) -> (GizmoTestHarness, Vec<Entity>, Vec2) {
+        let mut harness = GizmoTestHarness::new();
+        harness.style_mut().draw_inactive_targets = false;
+        let others = others
+            .iter()
+            .map(|&at| {
+                harness
+                    .app_mut()
+                    .world_mut()
+                    .spawn((
+                        Transform::from_translation(at),
+                        TransformGizmoTarget,
+                        GizmoActive,
+                    ))
+                    .id()
+            })
+            .collect();
+        harness.step();
+        let arrow = harness
+            .handle_position(GizmoOperation::TranslateAxis, GizmoAxis::X)
+            .unwrap();
+        harness.move_cursor(arrow);
+        harness.step();
+        harness.press_left();
+        harness.step();
+        assert!(harness.drag_state().is_some());
+        (harness, others, arrow)
+    }
+
+    /// Move the cursor 60 pixels along the X axis on screen.
+    #[cfg(feature = "translate")]
+    fn drag_along_x(harness: &mut GizmoTestHarness, arrow: Vec2) {
+        let origin = harness.world_to_cursor(Vec3::ZERO).unwrap();
+        let along = (harness.world_to_cursor(Vec3::X).unwrap() - origin).normalize();
+        harness.move_cursor(arrow + along * 60.0);
+        harness.step();
+    }
+
+    #[cfg(feature = "translate")]
+    fn translation(harness: &GizmoTestHarness, entity: Entity) -> Vec3 {
+        harness
+            .app()
+            .world()
+            .get::<Transform>(entity)
+            .unwrap()
+            .translation
+    }
+
+    #[cfg(feature = "translate")]
+    #[test]
+    fn other_active_targets_follow_the_drag() {
+        let (mut harness, others, arrow) = pressing_with_others(&[Vec3::new(-3.0, 0.0, 0.0)]);
+        drag_along_x(&mut harness, arrow);
+        let moved = harness.target_transform().translation;
+        assert!(moved.x > 0.1, "{moved}");
+        assert!(
+            translation(&harness, others[0]).abs_diff_eq(moved - Vec3::new(3.0, 0.0, 0.0), 1.0e-4)
+        );
+
+        // Cancelling puts them back along with the target.
+        harness
+            .app_mut()
+            .world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(KeyCode::Escape);
+        harness.step();
+        assert_eq!(translation(&harness, others[0]), Vec3::new(-3.0, 0.0, 0.0));
+    }
+
+    #[cfg(feature = "translate")]
+    #[test]
+    fn proportional_editing_fades_the_drag_with_distance() {
+        let near = Vec3::new(-3.0, 0.0, 0.0);
+        let far = Vec3::new(-5.0, 0.0, 0.0);
+        let (mut harness, others, arrow) = pressing_with_others(&[near, far]);
+        *harness
+            .app_mut()
+            .world_mut()
+            .resource_mut::<GizmoProportionalEdit>() = GizmoProportionalEdit {
+            enabled: true,
+            radius: 4.0,
+            falloff: FalloffCurve::Linear,
+            ..default()
+        };
+        drag_along_x(&mut harness, arrow);
+
+        // A quarter of the way in from the edge of the radius.
+        let moved = harness.target_transform().translation;
+        assert!(translation(&harness, others[0]).abs_diff_eq(near + moved * 0.25, 1.0e-4));
+        assert!(translation(&harness, others[1]).abs_diff_eq(far, 1.0e-6));
+    }
+
+    #[cfg(feature = "translate")]
+    #[test]
+    fn scrolling_during_a_drag_grows_the_radius() {
+        let (mut harness, ..) = pressing_with_others(&[Vec3::new(-3.0, 0.0, 0.0)]);
+        let mut edit = harness
+            .app_mut()
+            .world_mut()
+            .resource_mut::<GizmoProportionalEdit>();
+        edit.enabled = true;
+        let radius = edit.radius;
+        harness.scroll(2.0);
+        harness.step();
+        let edit = harness.app().world().resource::<GizmoProportionalEdit>();
+        assert!((edit.radius - radius * edit.scroll_step.powi(2)).abs() < 1.0e-4);
+    }
+
+    #[test]
+    fn falloff_curves_start_full_and_reach_zero_at_the_radius() {
+        for curve in [
+            FalloffCurve::Smooth,
+            FalloffCurve::Linear,
+            FalloffCurve::Sharp,
+            FalloffCurve::Constant,
+        ] {
+            assert_eq!(curve.weight(0.0, 2.0), 1.0, "{curve:?}");
+            assert_eq!(curve.weight(2.0, 2.0), 0.0, "{curve:?}");
+            let mid = curve.weight(1.0, 2.0);
+            assert!((0.0..=1.0).contains(&mid), "{curve:?}: {mid}");
+        }
+        assert_eq!(FalloffCurve::Smooth.weight(1.0, 2.0), 0.5);
+        assert_eq!(FalloffCurve::Sharp.weight(1.0, 2.0), 0.25);
+    }
+
+    #[test]
+    fn a_share_of_a_turn_turns_part_way_about_the_pivot() {
+        let pivot = Vec3::new(1.0, 0.0, 0.0);
+        let turn = Quat::from_rotation_y(std::f32::consts::FRAC_PI_2);
+        let change = Affine3A::from_translation(pivot)
+            * Affine3A::from_quat(turn)
+            * Affine3A::from_translation(-pivot);
+
+        let half = share_of(change, pivot, 0.5);
+        assert!(half.transform_point3(pivot).abs_diff_eq(pivot, 1.0e-5));
+        let (_, rotation, _) = half.to_scale_rotation_translation();
+        assert!(
+            rotation.angle_between(Quat::from_rotation_y(std::f32::consts::FRAC_PI_4)) < 1.0e-4
+        );
+        assert_eq!(share_of(change, pivot, 1.0), change);
+    }
+}
@@ -0,0 +1,294 @@
+//! Serializable transform messages for mirroring drags in another app.
+//!
+//! With [`TransformGizmoConfig::message_emission`] on, drags write
+//! [`GizmoTransformMessage`]s carrying their target's new `Transform`, for the
+//! app to send to another process or machine, such as a second editor in a
+//! shared session, which applies them to its own copy of the target rather
+//! than diffing transforms. All messages of one drag share a `drag_id`, so
+//! the other side can coalesce them into a single undo step.
+
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use crate::preferences::GizmoStableId;
+use crate::types::{
+    GizmoAxis, GizmoDragEnded, GizmoDragStarted, GizmoEditChannel, GizmoMessageEmission,
+    GizmoOperation, TransformGizmoConfig, TransformGizmoSpace, TransformGizmoState,
+};
+
+/// The target a [`GizmoTransformMessage`] is about.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum GizmoMessageTarget {
+    /// The target's [`GizmoStableId`], which another app can find its own
+    /// copy of the target by.
+    Stable(GizmoStableId),
+    /// The target's entity in the writing app, for targets without a
+    /// [`GizmoStableId`]. Only meaningful to apps whose entities match.
+    Entity(Entity),
+}
+
+/// A drag changed its target's `Transform` to `new_transform`.
+///
+/// Written as often as [`TransformGizmoConfig::message_emission`] says, with
+/// a last message when the drag ends. Only drags of a target's own
+/// `Transform` write these: drags of a secondary channel or a
+/// [`GizmoExternalDriver`](crate::GizmoExternalDriver), other active targets
+/// carried along by a drag, and undo and redo do not.
+///
+/// ```ignore
+/// fn apply_remote_drags(
+///     mut messages: MessageReader<GizmoTransformMessage>,
+///     mut targets: Query<(&GizmoStableId, &mut Transform)>,
+/// ) {
+///     for message in messages.read() {
+///         let GizmoMessageTarget::Stable(id) = &message.target else {
+///             continue;
+///         };
+///         if let Some((_, mut transform)) = targets.iter_mut().find(|(other, _)| *other == id) {
+///             *transform = message.transform();
+///         }
+///     }
+/// }
+/// ```
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Message, Clone, Debug, PartialEq)]
+pub struct GizmoTransformMessage {
+    /// The dragged target.
+    pub target: GizmoMessageTarget,
+    /// The dragged handle's operation.
+    pub op: GizmoOperation,
+    /// The dragged handle's axis.
+    pub axis: GizmoAxis,
+    /// The space the drag's axes were taken in.
+    pub space: TransformGizmoSpace,
+    /// The target's new translation, rotation, and scale, relative to its
+    /// parent like its `Transform`.
+    pub new_transform: (Vec3, Quat, Vec3),
+    /// Shared by every message of one drag, and one more than the previous
+    /// drag's, wrapping at `u32::MAX`.
+    pub drag_id: u32,
+    /// Whether this is the drag's last message. A cancelled drag's last
+    /// message puts the target back where the drag started.
+    pub ended: bool,
+}
+
+impl GizmoTransformMessage {
+    /// [`new_transform`](Self::new_transform) as a `Transform`.
+    pub fn transform(&self) -> Transform {
+        let (translation, rotation, scale) = self.new_transform;
+        Transform {
+            translation,
+            rotation,
+            scale,
+        }
+    }
+}
+
+/// The drag [`emit_transform_messages`] writes messages for.
+#[derive(Debug, Clone)]
+struct EmittedDrag {
+    entity: Entity,
+    target: GizmoMessageTarget,
+    op: GizmoOperation,
+    axis: GizmoAxis,
+    space: TransformGizmoSpace,
+    drag_id: u32,
+    /// The transform the last message carried, or the drag's initial one.
+    sent: Transform,
+    /// When the last message was written.
+    sent_at: Option<Duration>,
+}
+
+impl EmittedDrag {
+    fn message(&self, transform: Transform, ended: bool) -> GizmoTransformMessage {
+        GizmoTransformMessage {
+            target: self.target.clone(),
+            op: self.op,
+            axis: self.axis,
+            space: self.space,
+            new_transform: (transform.translation, transform.rotation, transform.scale),
+            drag_id: self.drag_id,
+            ended,
+        }
+    }
+}
+
+/// The drag messages are written for, and the id the next drag gets.
+#[derive(Resource, Debug, Default)]
+pub(crate) struct TransformMessageEmitter {
+    next_drag_id: u32,
+    drag: Option<EmittedDrag>,
+}
+
+/// Write [`GizmoTransformMessage`]s for the current drag, as often as
+/// [`TransformGizmoConfig::message_emission`] says.
+///
+/// Runs after smoothing, so a smoothed drag's messages carry its goal.
+#[allow(clippy::too_many_arguments)]
+pub fn emit_transform_messages(
+    config: Res<TransformGizmoConfig>,
+    state: Res<TransformGizmoState>,
+    time: Res<Time<Real>>,
+    mut emitter: ResMut<TransformMessageEmitter>,
+    mut started: MessageReader<GizmoDragStarted>,
+    mut ended: MessageReader<GizmoDragEnded>,
+    stable_ids: Query<&GizmoStableId>,
+    mut messages: MessageWriter<GizmoTransformMessage>,
+) {
+    let interval = match config.message_emission {
+        GizmoMessageEmission::Off => {
+            started.clear();
+            ended.clear();
+            emitter.drag = None;
+            return;
+        }
+        GizmoMessageEmission::EveryFrame => Some(Duration::ZERO),
+        GizmoMessageEmission::Interval(interval) => Some(interval),
+        GizmoMessageEmission::OnEnd => None,
+    };
+
+    for ended in ended.read() {
+        if let Some(drag) = emitter
+            .drag
+            .take_if(|drag| drag.entity == ended.handle.target)
+        {
+            messages.write(drag.message(ended.final_transform, true));
+        }
+    }
+    for started in started.read() {
+        let Some(drag) = state.drag.as_ref().filter(|drag| {
+            drag.target == started.handle.target
+                && drag.channel == GizmoEditChannel::Transform
+                && !drag.external
+        }) else {
+            continue;
+        };
+        let drag_id = emitter.next_drag_id;
+        emitter.next_drag_id = drag_id.wrapping_add(1);
+        emitter.drag = Some(EmittedDrag {
+            entity: drag.target,
+            target: stable_ids
+                .get(drag.target)
+                .map_or(GizmoMessageTarget::Entity(drag.target), |id| {
+                    GizmoMessageTarget::Stable(id.clone())
+                }),
+            op: drag.op,
+            axis: drag.axis,
+            space: drag.space.unwrap_or(state.space),
+            drag_id,
+            sent: drag.initial_transform,
+            sent_at: None,
+        });
+    }
+
+    let Some(interval) = interval else {
+        return;
+    };
+    let Some(drag) = state.drag.as_ref().filter(|drag| !drag.is_pending()) else {
+        return;
+    };
+    let now = time.elapsed();
+    let Some(emitted) = emitter.drag.as_mut().filter(|emitted| {
+        emitted.entity == drag.target
+            && emitted.sent != drag.last_transform
+            && emitted
+                .sent_at
+                .is_none_or(|sent_at| now.saturating_sub(sent_at) >= interval)
+    }) else {
+        return;
+    };
+    messages.write(emitted.message(drag.last_transform, false));
+    emitted.sent = drag.last_transform;
+    emitted.sent_at = Some(now);
+}
+
+#[cfg(all(test, feature = "rotate"))]
+mod tests {
+    use super::*;
+    use crate::test_utils::GizmoTestHarness;
+    use crate::types::TransformGizmoSystems;
+
+    #[test]
+    fn transform_messages_replay_a_local_rotation() {
+        #[derive(Resource, Default)]
+        struct Received(Vec<GizmoTransformMessage>);
+
+        fn receive(
+            mut messages: MessageReader<GizmoTransformMessage>,
+            mut received: ResMut<Received>,
+        ) {
+            received.0.extend(messages.read().cloned());
+        }
+
+        let mut harness = GizmoTestHarness::new();
+        let target = harness.target();
+        let start = Transform::from_xyz(0.5, 0.0, 0.0).with_rotation(Quat::from_euler(
+            EulerRot::XYZ,
+            0.4,
+            0.6,
+            0.0,
+        ));
+        let app = harness.app_mut();
+        app.init_resource::<Received>()
+            .add_systems(Update, receive.after(TransformGizmoSystems::Drag));
+        let world = app.world_mut();
+        world
+            .entity_mut(target)
+            .insert((start, GizmoStableId::from("crate")));
+        world
+            .resource_mut::<TransformGizmoConfig>()
+            .message_emission = GizmoMessageEmission::EveryFrame;
+        world.resource_mut::<TransformGizmoState>().space = TransformGizmoSpace::Local;
+        harness.step();
+
+        let grabbed = harness.grab(GizmoOperation::Rotate, GizmoAxis::Y);
+        let radial = grabbed - harness.world_to_cursor(start.translation).unwrap();
+        harness.drag(grabbed, grabbed + radial.perp().normalize() * 40.0);
+
+        // Replaying the messages onto a copy of the target reproduces the
+        // drag, a turn about the target's own Y axis.
+        let received =
+            std::mem::take(&mut harness.app_mut().world_mut().resource_mut::<Received>().0);
+        assert!(received.len() > 1, "{received:?}");
+        let mut mirror = start;
+        for message in &received {
+            assert_eq!(message.target, GizmoMessageTarget::Stable("crate".into()));
+            assert_eq!(
+                (message.op, message.axis),
+                (GizmoOperation::Rotate, GizmoAxis::Y)
+            );
+            assert_eq!(message.space, TransformGizmoSpace::Local);
+            assert_eq!(message.drag_id, received[0].drag_id);
+            mirror = message.transform();
+        }
+        let ended: Vec<_> = received.iter().map(|message| message.ended).collect();
+        assert_eq!(ended.iter().filter(|ended| **ended).count(), 1);
+        assert_eq!(ended.last(), Some(&true));
+        assert_eq!(mirror, harness.target_transform());
+        let local_y = start.rotation * Vec3::Y;
+        let (turned_about, angle) = (mirror.rotation * start.rotation.inverse()).to_axis_angle();
+        assert!(angle > 0.05, "{angle}");
+        assert!(
+            turned_about.cross(local_y).length() < 1.0e-3,
+            "{turned_about}"
+        );
+
+        // Emitting only on end, the next drag writes one message, with the
+        // next id.
+        harness
+            .app_mut()
+            .world_mut()
+            .resource_mut::<TransformGizmoConfig>()
+            .message_emission = GizmoMessageEmission::OnEnd;
+        let grabbed = harness.grab(GizmoOperation::Rotate, GizmoAxis::Y);
+        let radial = grabbed - harness.world_to_cursor(start.translation).unwrap();
+        harness.drag(grabbed, grabbed + radial.perp().normalize() * 40.0);
+        let last = &harness.app().world().resource::<Received>().0;
+        assert_eq!(last.len(), 1);
+        assert_eq!(last[0].drag_id, received[0].drag_id + 1);
+        assert!(last[0].ended);
+        assert_eq!(last[0].transform(), harness.target_transform());
+    }
+}
@@ -0,0 +1,258 @@
+//! Reparenting targets without moving them.
+//!
+//! Processes [`GizmoReparentRequest`] messages: the target's world pose is
+//! recomputed from the local `Transform` chain (so values written by a drag
+//! earlier this frame are included even though `GlobalTransform` has not been
+//! propagated yet), re-expressed relative to the new parent, and any active
+//! drag on the target is rebased into the new parent space.
+
+use bevy::math::Affine3A;
+use bevy::prelude::*;
+
+use crate::interaction::rebase_drag_to_parent;
+use crate::math::nearest_transform;
+use crate::types::{
+    GizmoEditChannel, GizmoReparentRejected, GizmoReparentRequest, GizmoReparented,
+    ReparentRejection, TransformGizmoConfig, TransformGizmoState,
+};
+
+/// Apply pending [`GizmoReparentRequest`]s.
+///
+/// A request whose target or new parent cannot be resolved yet (for example
+/// an entity spawned through commands that have not been applied) is retried
+/// once on the next frame before being rejected.
+#[allow(clippy::too_many_arguments)]
+pub fn process_reparent_requests(
+    mut commands: Commands,
+    mut requests: MessageReader<GizmoReparentRequest>,
+    mut deferred: Local<Vec<GizmoReparentRequest>>,
+    config: Res<TransformGizmoConfig>,
+    mut state: ResMut<TransformGizmoState>,
+    mut transforms: Query<&mut Transform>,
+    parents: Query<&ChildOf>,
+    mut reparented: MessageWriter<GizmoReparented>,
+    mut rejected: MessageWriter<GizmoReparentRejected>,
+) {
+    let retries = std::mem::take(&mut *deferred);
+    let pending = retries
+        .into_iter()
+        .map(|request| (request, true))
+        .chain(requests.read().map(|request| (*request, false)));
+
+    for (request, retried) in pending {
+        let GizmoReparentRequest { target, new_parent } = request;
+        let reject = |reason| GizmoReparentRejected {
+            target,
+            new_parent,
+            reason,
+        };
+
+        if let Some(parent) = new_parent {
+            if parent == target || parents.iter_ancestors(parent).any(|a| a == target) {
+                rejected.write(reject(ReparentRejection::CycleWithTarget));
+                continue;
+            }
+        }
+
+        // `Some(None)` for the root, `None` if the parent cannot be resolved.
+        let parent_world = |parent: Option<Entity>| match parent {
+            Some(parent) => world_affine(&transforms, &parents, parent).map(Some),
+            None => Some(None),
+        };
+        let old_parent = parents.get(target).ok().map(ChildOf::parent);
+        let (Some(world), Some(old_parent_world), Some(new_parent_world)) = (
+            world_affine(&transforms, &parents, target),
+            parent_world(old_parent),
+            parent_world(new_parent),
+        ) else {
+            if retried {
+                rejected.write(reject(ReparentRejection::MissingEntity));
+            } else {
+                deferred.push(request);
+            }
+            continue;
+        };
+
+        if old_parent != new_parent {
+            let local = new_parent_world.map_or(world, |parent| parent.inverse() * world);
+            if let Ok(mut transform) = transforms.get_mut(target) {
+                *transform = nearest_transform(local);
+            }
+            match new_parent {
+                Some(parent) => commands.entity(target).insert(ChildOf(parent)),
+                None => commands.entity(target).remove::<ChildOf>(),
+            };
+
+            // Secondary channels are edited relative to the target itself,
+            // whose world pose is unchanged.
+            if let Some(drag) = state
+                .drag
+                .as_mut()
+                .filter(|drag| drag.target == target && drag.channel == GizmoEditChannel::Transform)
+            {
+                rebase_drag_to_parent(
+                    drag,
+                    old_parent_world,
+                    new_parent_world,
+                    config.drag_follows_external_motion,
+                );
+            }
+        }
+
+        reparented.write(GizmoReparented {
+            target,
+            old_parent,
+            new_parent,
+        });
+    }
+}
+
+/// World affine of `entity`, composed from local transforms up the hierarchy.
+fn world_affine(
+    transforms: &Query<&mut Transform>,
+    parents: &Query<&ChildOf>,
+    entity: Entity,
+) -> Option<Affine3A> {
+    let mut world = transforms.get(entity).ok()?.compute_affine();
+    for ancestor in parents.iter_ancestors(entity) {
+        world = transforms.get(ancestor).ok()?.compute_affine() * world;
+    }
+    Some(world)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::ecs::message::Messages;
+
+    fn app() -> App {
+        let mut app = App::new();
+        app.init_resource::<TransformGizmoConfig>()
+            .init_resource::<TransformGizmoState>()
+            .add_message::<GizmoReparentRequest>()
+            .add_message::<GizmoReparented>()
+            .add_message::<GizmoReparentRejected>()
+            .add_systems(Update, process_reparent_requests);
+        app
+    }
+
+    fn world_of(world: &World, entity: Entity) -> Affine3A {
+        let mut affine = world.get::<Transform>(entity).unwrap().compute_affine();
+        let mut current = entity;
+        while let Some(parent) = world.get::<ChildOf>(current) {
+            current = parent.parent();
+            affine = world.get::<Transform>(current).unwrap().compute_affine() * affine;
+        }
+        affine
+    }
+
+    #[test]
+    fn reparenting_preserves_world_pose_under_rotated_scaled_parent() {
+        let mut app = app();
+        let world = app.world_mut();
+        let old_parent = world
+            .spawn(Transform::from_xyz(-2.0, 1.0, 0.0).with_rotation(Quat::from_rotation_x(0.4)))
+            .id();
+        let target = world
+            .spawn((
+                Transform::from_xyz(1.0, 0.5, -1.0)
+                    .with_rotation(Quat::from_rotation_z(0.3))
+                    .with_scale(Vec3::new(1.0, 2.0, 0.5)),
+                ChildOf(old_parent),
+            ))
+            .id();
+        let grandparent = world.spawn(Transform::from_xyz(0.0, 0.0, 5.0)).id();
+        let new_parent = world
+            .spawn((
+                Transform::from_xyz(3.0, 0.0, 0.0)
+                    .with_rotation(Quat::from_rotation_y(1.1))
+                    .with_scale(Vec3::splat(2.5)),
+                ChildOf(grandparent),
+            ))
+            .id();
+
+        let before = world_of(app.world(), target);
+        app.world_mut().write_message(GizmoReparentRequest {
+            target,
+            new_parent: Some(new_parent),
+        });
+        app.update();
+
+        assert_eq!(
+            app.world().get::<ChildOf>(target).map(ChildOf::parent),
+            Some(new_parent)
+        );
+        let after = world_of(app.world(), target);
+        assert!(after.abs_diff_eq(before, 1.0e-4));
+
+        let messages = app.world().resource::<Messages<GizmoReparented>>();
+        let completed: Vec<_> = messages.iter_current_update_messages().copied().collect();
+        assert_eq!(
+            completed,
+            vec![GizmoReparented {
+                target,
+                old_parent: Some(old_parent),
+                new_parent: Some(new_parent),
+            }]
+        );
+    }
+
+    #[test]
+    fn reparenting_under_a_descendant_is_rejected() {
+        let mut app = app();
+        let world = app.world_mut();
+        let target = world.spawn(Transform::from_xyz(1.0, 0.0, 0.0)).id();
+        let child = world
+            .spawn((Transform::from_xyz(0.0, 1.0, 0.0), ChildOf(target)))
+            .id();
+        let grandchild = world.spawn((Transform::default(), ChildOf(child))).id();
+
+        app.world_mut().write_message(GizmoReparentRequest {
+            target,
+            new_parent: Some(grandchild),
+        });
+        app.update();
+
+        assert!(app.world().get::<ChildOf>(target).is_none());
+        let messages = app.world().resource::<Messages<GizmoReparentRejected>>();
+        let rejections: Vec<_> = messages.iter_current_update_messages().copied().collect();
+        assert_eq!(
+            rejections,
+            vec![GizmoReparentRejected {
+                target,
+                new_parent: Some(grandchild),
+                reason: ReparentRejection::CycleWithTarget,
+            }]
+        );
+    }
+
+    #[test]
+    fn unresolved_parent_is_retried_for_one_frame() {
+        let mut app = app();
+        let target = app.world_mut().spawn(Transform::default()).id();
+        let not_ready = app.world_mut().spawn_empty().id();
+
+        app.world_mut().write_message(GizmoReparentRequest {
+            target,
+            new_parent: Some(not_ready),
+        });
+        app.update();
+        let rejections = app.world().resource::<Messages<GizmoReparentRejected>>();
+        assert_eq!(rejections.iter_current_update_messages().count(), 0);
+
+        app.world_mut()
+            .entity_mut(not_ready)
+            .insert(Transform::from_xyz(0.0, 2.0, 0.0));
+        app.update();
+        assert_eq!(
+            app.world().get::<ChildOf>(target).map(ChildOf::parent),
+            Some(not_ready)
+        );
+        assert!(app
+            .world()
+            .get::<Transform>(target)
+            .unwrap()
+            .translation
+            .abs_diff_eq(Vec3::new(0.0, -2.0, 0.0), 1.0e-5));
+    }
+}
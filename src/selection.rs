@@ -0,0 +1,185 @@
+//! Click-to-select for gizmo targets.
+//!
+//! With [`TransformGizmoConfig::click_to_select`] enabled, a left click that
+//! does not land on a gizmo handle moves [`GizmoActive`] to the nearest
+//! [`GizmoSelectable`] target under the cursor. Hit testing is a bounding
+//! sphere per target, so no mesh picking backend is needed.
+
+use bevy::input::mouse::MouseButton;
+use bevy::input::ButtonInput;
+use bevy::prelude::*;
+
+use crate::camera::pointer_ray;
+use crate::math::ray_sphere_intersection;
+use crate::types::{
+    GizmoActive, GizmoCameraSelection, GizmoPointer, TransformGizmoCamera, TransformGizmoConfig,
+    TransformGizmoState, TransformGizmoTarget,
+};
+
+/// Makes a [`TransformGizmoTarget`] selectable by clicking on it.
+///
+/// The target is hit tested as a world-space sphere of `radius` around its
+/// origin. Requires [`TransformGizmoConfig::click_to_select`].
+///
+/// # Example
+///
+/// ```ignore
+/// commands.spawn((
+///     Mesh3d(cube),
+///     Transform::from_xyz(0.0, 0.5, 0.0),
+///     TransformGizmoTarget,
+///     GizmoSelectable { radius: 0.9 },
+/// ));
+/// ```
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+pub struct GizmoSelectable {
+    /// Radius of the hit sphere, in world units.
+    pub radius: f32,
+}
+
+/// [`Commands`] methods that move [`GizmoActive`] between targets.
+///
+/// Both take effect when the commands are applied, and the gizmo follows in
+/// the next [`TransformGizmoSystems::Sync`](crate::TransformGizmoSystems::Sync).
+///
+/// ```ignore
+/// fn select_from_outliner(mut commands: Commands, clicked: Res<OutlinerClick>) {
+///     match clicked.entity {
+///         Some(entity) => commands.set_gizmo_active(entity),
+///         None => commands.clear_gizmo_active(),
+///     }
+/// }
+/// ```
+pub trait GizmoActiveCommandsExt {
+    /// Make `entity` the only entity with [`GizmoActive`], removing it from
+    /// every other one in the same command.
+    fn set_gizmo_active(&mut self, entity: Entity);
+
+    /// Remove [`GizmoActive`] from every entity.
+    fn clear_gizmo_active(&mut self);
+}
+
+impl GizmoActiveCommandsExt for Commands<'_, '_> {
+    fn set_gizmo_active(&mut self, entity: Entity) {
+        self.queue(move |world: &mut World| set_gizmo_active(world, Some(entity)));
+    }
+
+    fn clear_gizmo_active(&mut self) {
+        self.queue(|world: &mut World| set_gizmo_active(world, None));
+    }
+}
+
+/// Remove [`GizmoActive`] from every entity but `entity`, and add it to
+/// `entity` if it still exists.
+fn set_gizmo_active(world: &mut World, entity: Option<Entity>) {
+    let previous: Vec<Entity> = world
+        .query_filtered::<Entity, With<GizmoActive>>()
+        .iter(world)
+        .filter(|previous| Some(*previous) != entity)
+        .collect();
+    for previous in previous {
+        world.entity_mut(previous).remove::<GizmoActive>();
+    }
+    if let Some(mut entity) = entity.and_then(|entity| world.get_entity_mut(entity).ok()) {
+        entity.insert(GizmoActive);
+    }
+}
+
+/// The selectable target nearest along `ray`, if any.
+pub(crate) fn pick_selectable(
+    ray: &Ray3d,
+    candidates: impl IntoIterator<Item = (Entity, Vec3, f32)>,
+) -> Option<Entity> {
+    candidates
+        .into_iter()
+        .filter_map(|(entity, center, radius)| {
+            ray_sphere_intersection(ray, center, radius).map(|t| (entity, t))
+        })
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(entity, _)| entity)
+}
+
+/// Move [`GizmoActive`] to the clicked target.
+///
+/// Clicks on a hovered handle are left to `begin_drag`, and clicks during a
+/// drag are ignored, so selection never changes under an active drag.
+#[allow(clippy::too_many_arguments)]
+pub fn select_on_click(
+    mut commands: Commands,
+    buttons: Res<ButtonInput<MouseButton>>,
+    config: Res<TransformGizmoConfig>,
+    mut state: ResMut<TransformGizmoState>,
+    camera_selection: Res<GizmoCameraSelection>,
+    cameras: Query<(&Camera, &GlobalTransform), With<TransformGizmoCamera>>,
+    pointer: Res<GizmoPointer>,
+    selectables: Query<(Entity, &GlobalTransform, &GizmoSelectable), With<TransformGizmoTarget>>,
+) {
+    if !config.click_to_select || !buttons.just_pressed(config.drag_input.button) {
+        return;
+    }
+    if state.drag.is_some() || state.hovered_op.is_some() {
+        return;
+    }
+
+    let Some((camera, camera_transform)) = camera_selection
+        .camera()
+        .and_then(|camera| cameras.get(camera).ok())
+    else {
+        return;
+    };
+    let Some(ray) = pointer_ray(&camera_selection, camera, camera_transform, &pointer) else {
+        return;
+    };
+
+    let hit = pick_selectable(
+        &ray,
+        selectables.iter().map(|(entity, transform, selectable)| {
+            (entity, transform.translation(), selectable.radius)
+        }),
+    );
+
+    match hit {
+        Some(entity) => {
+            commands.set_gizmo_active(entity);
+            state.active_target = Some(entity);
+        }
+        None if config.clear_selection_on_empty_click => {
+            commands.clear_gizmo_active();
+            state.active_target = None;
+        }
+        None => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ray_down_z() -> Ray3d {
+        Ray3d::new(Vec3::new(0.0, 0.0, 10.0), Dir3::NEG_Z)
+    }
+
+    #[test]
+    fn nearest_sphere_along_the_ray_wins() {
+        let mut world = World::new();
+        let far = world.spawn_empty().id();
+        let near = world.spawn_empty().id();
+        let hit = pick_selectable(
+            &ray_down_z(),
+            [
+                (far, Vec3::new(0.0, 0.0, -2.0), 1.0),
+                (near, Vec3::ZERO, 1.0),
+            ],
+        );
+        assert_eq!(hit, Some(near));
+    }
+
+    #[test]
+    fn clicks_outside_every_radius_miss() {
+        let hit = pick_selectable(
+            &ray_down_z(),
+            [(Entity::PLACEHOLDER, Vec3::new(1.5, 0.0, 0.0), 1.0)],
+        );
+        assert_eq!(hit, None);
+    }
+}
@@ -0,0 +1,207 @@
+//! Easing dragged targets toward the gizmo's edit.
+//!
+//! With [`TransformGizmoConfig::smoothing`] set, drags move a goal transform
+//! instead of the target's `Transform`, and the target follows the goal with
+//! a frame-rate independent exponential ease, for smooth motion when a
+//! manipulation is recorded.
+
+use bevy::prelude::*;
+
+use crate::types::{TransformGizmoConfig, TransformGizmoState};
+
+/// How close an eased target's translation, rotation, and scale must each be
+/// to the goal's before it lands on the goal.
+const SETTLE_EPSILON: f32 = 1.0e-4;
+
+/// The transform a smoothed drag is easing its target toward.
+///
+/// Inserted on the target when a drag starts with
+/// [`TransformGizmoConfig::smoothing`] set. The drag edits `goal`, which is
+/// also what its [`GizmoDragging`](crate::GizmoDragging) and
+/// [`GizmoDragEnded`](crate::GizmoDragEnded) messages report, while the
+/// target's `Transform` follows it. Removed once the target has settled on
+/// the goal after the drag, or as soon as something else writes the
+/// target's `Transform`, which then wins.
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+pub struct GizmoTargetTransform {
+    /// Where the drag has put the target, in its parent's space.
+    pub goal: Transform,
+    /// The target's `Transform` as the ease last left it.
+    eased: Transform,
+}
+
+impl GizmoTargetTransform {
+    /// Start easing a target that is at `transform`, toward itself.
+    pub(crate) fn new(transform: Transform) -> Self {
+        Self {
+            goal: transform,
+            eased: transform,
+        }
+    }
+}
+
+/// Ease each target with a [`GizmoTargetTransform`] toward its goal by this
+/// frame's share of [`SmoothingSettings::half_life`](crate::SmoothingSettings::half_life).
+///
+/// A target that is no longer dragged lands on the goal once it is close,
+/// and loses the component. With smoothing turned off it lands there
+/// straight away.
+pub fn smooth_gizmo_targets(
+    mut commands: Commands,
+    time: Res<Time>,
+    config: Res<TransformGizmoConfig>,
+    state: Res<TransformGizmoState>,
+    mut targets: Query<(Entity, &mut Transform, &mut GizmoTargetTransform)>,
+) {
+    let dragged = state.drag.as_ref().map(|drag| drag.target);
+    for (entity, mut transform, mut smoothed) in &mut targets {
+        if *transform != smoothed.eased {
+            commands.entity(entity).remove::<GizmoTargetTransform>();
+            continue;
+        }
+        let goal = smoothed.goal;
+        let next = match config.smoothing {
+            Some(settings) => ease(&transform, &goal, settings.factor(time.delta_secs())),
+            None => goal,
+        };
+        let settled = dragged != Some(entity) && settled(&next, &goal);
+        let next = if settled { goal } else { next };
+        transform.set_if_neq(next);
+        smoothed.eased = next;
+        if settled {
+            commands.entity(entity).remove::<GizmoTargetTransform>();
+        }
+    }
+}
+
+/// `from` moved the fraction `t` of the way to `goal`.
+fn ease(from: &Transform, goal: &Transform, t: f32) -> Transform {
+    Transform {
+        translation: from.translation.lerp(goal.translation, t),
+        rotation: from.rotation.slerp(goal.rotation, t),
+        scale: from.scale.lerp(goal.scale, t),
+    }
+}
+
+/// Whether `transform` is close enough to `goal` to land on it.
+fn settled(transform: &Transform, goal: &Transform) -> bool {
+    transform
+        .translation
+        .abs_diff_eq(goal.translation, SETTLE_EPSILON)
+        && transform.scale.abs_diff_eq(goal.scale, SETTLE_EPSILON)
+        && (transform
+            .rotation
+            .abs_diff_eq(goal.rotation, SETTLE_EPSILON)
+            || transform
+                .rotation
+                .abs_diff_eq(-goal.rotation, SETTLE_EPSILON))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::SmoothingSettings;
+
+    #[test]
+    fn easing_is_independent_of_the_frame_rate() {
+        let settings = SmoothingSettings { half_life: 0.1 };
+        let start = Transform::IDENTITY;
+        let goal = Transform::from_xyz(4.0, -2.0, 1.0)
+            .with_rotation(Quat::from_rotation_y(1.2))
+            .with_scale(Vec3::splat(3.0));
+
+        let once = ease(&start, &goal, settings.factor(0.1));
+        let mut twice = start;
+        for _ in 0..2 {
+            twice = ease(&twice, &goal, settings.factor(0.05));
+        }
+        // One half-life closes half the distance, however it is stepped.
+        assert!(once
+            .translation
+            .abs_diff_eq(Vec3::new(2.0, -1.0, 0.5), 1.0e-5));
+        assert!(once.scale.abs_diff_eq(Vec3::splat(2.0), 1.0e-5));
+        assert!(once
+            .rotation
+            .abs_diff_eq(Quat::from_rotation_y(0.6), 1.0e-5));
+        assert!(twice.translation.abs_diff_eq(once.translation, 1.0e-5));
+        assert!(twice.rotation.abs_diff_eq(once.rotation, 1.0e-5));
+        assert!(twice.scale.abs_diff_eq(once.scale, 1.0e-5));
+    }
+
+    #[test]
+    fn zero_half_life_follows_the_goal() {
+        let settings = SmoothingSettings { half_life: 0.0 };
+        assert_eq!(settings.factor(0.016), 1.0);
+        assert_eq!(settings.factor(0.0), 1.0);
+    }
+
+    #[cfg(feature = "translate")]
+    #[test]
+    fn smoothed_drags_ease_the_target_toward_the_goal() {
+        use crate::test_utils::GizmoTestHarness;
+        use crate::types::{GizmoAxis, GizmoOperation};
+        use bevy::time::TimeUpdateStrategy;
+        use std::time::Duration;
+
+        fn goal(harness: &GizmoTestHarness) -> Option<Transform> {
+            let world = harness.app().world();
+            world
+                .get::<GizmoTargetTransform>(harness.target())
+                .map(|smoothed| smoothed.goal)
+        }
+
+        let mut harness = GizmoTestHarness::new();
+        let world = harness.app_mut().world_mut();
+        world.resource_mut::<TransformGizmoConfig>().smoothing =
+            Some(SmoothingSettings { half_life: 0.1 });
+        world.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            50,
+        )));
+        let cone = harness.grab(GizmoOperation::TranslateAxis, GizmoAxis::Y);
+        harness.press_left();
+        harness.step();
+        harness.move_cursor(cone - Vec2::new(0.0, 80.0));
+        harness.step();
+
+        // The drag moved the goal, and the target is on its way there.
+        let moved = goal(&harness).unwrap();
+        let eased = harness.target_transform().translation.y;
+        assert!(moved.translation.y > 0.1, "{moved:?}");
+        assert!(
+            eased > 0.0 && eased < moved.translation.y,
+            "{eased} toward {moved:?}"
+        );
+
+        // After release it keeps easing until it lands on the goal.
+        harness.release_left();
+        harness.step();
+        assert!(harness.target_transform().translation.y < moved.translation.y);
+        for _ in 0..60 {
+            harness.step();
+        }
+        assert_eq!(harness.target_transform(), moved);
+        assert_eq!(goal(&harness), None);
+
+        // A cancelled drag eases back to where it started. The cone is
+        // near the top of the window now, so the cursor moves less.
+        let start = harness.target_transform();
+        let cone = harness.grab(GizmoOperation::TranslateAxis, GizmoAxis::Y);
+        harness.press_left();
+        harness.step();
+        harness.move_cursor(cone - Vec2::new(0.0, 40.0));
+        harness.step();
+        assert!(harness.target_transform().translation.y > start.translation.y);
+        harness
+            .app_mut()
+            .world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(KeyCode::Escape);
+        harness.step();
+        assert_eq!(goal(&harness), Some(start));
+        for _ in 0..60 {
+            harness.step();
+        }
+        assert_eq!(harness.target_transform(), start);
+        assert_eq!(goal(&harness), None);
+    }
+}
@@ -0,0 +1,103 @@
+//! The gizmo's interaction lifecycle as a Bevy state.
+//!
+//! With the `states` feature, [`GizmoInteractionState`] follows
+//! [`TransformGizmoState`], so apps can hang behavior off
+//! `OnEnter(GizmoInteractionState::Dragging)` or `in_state(...)` instead of
+//! polling the resource.
+
+use bevy::prelude::*;
+
+use crate::types::{TransformGizmoState, TransformGizmoSystems};
+
+/// What the pointer is doing with the gizmo, as a Bevy [`States`].
+///
+/// Updated in `PostUpdate` after [`TransformGizmoSystems::Hover`], so the
+/// transition is applied at the start of the next frame and systems in
+/// `Update` see the same state [`TransformGizmoState`] had at the end of the
+/// previous one. Needs Bevy's `StatesPlugin`, which `DefaultPlugins` adds,
+/// before [`TransformGizmoPlugin`](crate::TransformGizmoPlugin).
+///
+/// ```ignore
+/// app.add_systems(OnEnter(GizmoInteractionState::Dragging), pause_autosave)
+///     .add_systems(OnExit(GizmoInteractionState::Dragging), resume_autosave);
+/// ```
+#[derive(States, Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Reflect)]
+#[reflect(Default)]
+pub enum GizmoInteractionState {
+    /// No handle is under the cursor or dragged.
+    #[default]
+    Idle,
+    /// A handle is under the cursor, and none is dragged.
+    Hovering,
+    /// A handle is pressed or dragged.
+    Dragging,
+}
+
+impl GizmoInteractionState {
+    /// The stage of the lifecycle `state` is in.
+    pub fn of(state: &TransformGizmoState) -> Self {
+        if state.is_dragging() {
+            Self::Dragging
+        } else if state.is_hovering() {
+            Self::Hovering
+        } else {
+            Self::Idle
+        }
+    }
+}
+
+pub(crate) fn register_interaction_state(app: &mut App) {
+    app.init_state::<GizmoInteractionState>()
+        .register_type::<GizmoInteractionState>()
+        .add_systems(
+            PostUpdate,
+            update_interaction_state.after(TransformGizmoSystems::Hover),
+        );
+}
+
+/// Move [`GizmoInteractionState`] to the stage [`TransformGizmoState`] is
+/// in, if it changed.
+pub fn update_interaction_state(
+    state: Res<TransformGizmoState>,
+    current: Res<State<GizmoInteractionState>>,
+    mut next: ResMut<NextState<GizmoInteractionState>>,
+) {
+    let interaction = GizmoInteractionState::of(&state);
+    if *current.get() != interaction {
+        next.set(interaction);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{GizmoAxis, GizmoOperation};
+    use bevy::state::app::StatesPlugin;
+
+    #[test]
+    fn interaction_state_follows_the_gizmo_state() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, StatesPlugin))
+            .init_resource::<TransformGizmoState>();
+        register_interaction_state(&mut app);
+        let current = |app: &App| *app.world().resource::<State<GizmoInteractionState>>().get();
+
+        app.update();
+        assert_eq!(current(&app), GizmoInteractionState::Idle);
+
+        let mut state = app.world_mut().resource_mut::<TransformGizmoState>();
+        state.hovered_op = Some(GizmoOperation::TranslateAxis);
+        state.hovered_axis = Some(GizmoAxis::X);
+        // Set in `PostUpdate`, applied at the start of the next frame.
+        app.update();
+        app.update();
+        assert_eq!(current(&app), GizmoInteractionState::Hovering);
+
+        let mut state = app.world_mut().resource_mut::<TransformGizmoState>();
+        state.hovered_op = None;
+        state.hovered_axis = None;
+        app.update();
+        app.update();
+        assert_eq!(current(&app), GizmoInteractionState::Idle);
+    }
+}
@@ -0,0 +1,143 @@
+//! Surface snapping for plane and view translate drags.
+//!
+//! With [`TransformGizmoSnap::surface`](crate::TransformGizmoSnap::surface)
+//! set, these drags place the target where the cursor ray meets scene
+//! geometry instead of on the drag plane. Finding that point is delegated to
+//! a [`GizmoSurfaceRaycast`] callback, typically a physics engine's ray
+//! cast. With the `mesh-raycast` feature and no callback, the plugin casts
+//! against meshes itself through `bevy_picking`'s `MeshRayCast`.
+
+use std::fmt;
+
+#[cfg(feature = "translate")]
+use bevy::ecs::system::SystemParam;
+#[cfg(feature = "mesh-raycast")]
+use bevy::picking::mesh_picking::ray_cast::{MeshRayCast, MeshRayCastSettings, RayCastVisibility};
+use bevy::prelude::*;
+
+#[cfg(feature = "translate")]
+use crate::meshes::GizmoMeshPart;
+#[cfg(feature = "translate")]
+use crate::types::SurfaceSnapSettings;
+
+/// Where a cursor ray meets a surface, reported by a
+/// [`GizmoSurfaceRaycast`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SurfaceHit {
+    /// World-space point the ray hit.
+    pub position: Vec3,
+    /// World-space surface normal at the hit, pointing back toward the ray
+    /// origin.
+    pub normal: Vec3,
+}
+
+/// Boxed ray cast callback held by [`GizmoSurfaceRaycast`].
+pub type SurfaceRaycastFn =
+    Box<dyn Fn(Ray3d, &dyn Fn(Entity) -> bool) -> Option<SurfaceHit> + Send + Sync>;
+
+/// Ray cast callback used by surface-snapped translate drags.
+///
+/// Called with the world-space cursor ray and a filter; return the nearest
+/// hit on an entity the filter accepts, or `None` if the ray hits nothing.
+/// The filter rejects the dragged target, its descendants, the gizmo's own
+/// mesh handles and, with [`SurfaceSnapSettings::only_marked`], everything
+/// without a [`GizmoSnapSurface`].
+///
+/// ```ignore
+/// app.insert_resource(GizmoSurfaceRaycast::new(|ray, filter| {
+///     cast_ray(ray, filter).map(|hit| SurfaceHit {
+///         position: hit.point,
+///         normal: hit.normal,
+///     })
+/// }));
+/// ```
+#[derive(Resource)]
+pub struct GizmoSurfaceRaycast {
+    cast: SurfaceRaycastFn,
+}
+
+impl GizmoSurfaceRaycast {
+    /// Wrap a ray cast callback.
+    pub fn new(
+        cast: impl Fn(Ray3d, &dyn Fn(Entity) -> bool) -> Option<SurfaceHit> + Send + Sync + 'static,
+    ) -> Self {
+        let cast: SurfaceRaycastFn = Box::new(cast);
+        Self { cast }
+    }
+
+    /// Nearest hit of `ray` on an entity accepted by `filter`.
+    pub fn cast(&self, ray: Ray3d, filter: &dyn Fn(Entity) -> bool) -> Option<SurfaceHit> {
+        (self.cast)(ray, filter)
+    }
+}
+
+impl fmt::Debug for GizmoSurfaceRaycast {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GizmoSurfaceRaycast")
+            .finish_non_exhaustive()
+    }
+}
+
+/// Marks an entity, and its descendants, as a surface that drags snap to
+/// when [`SurfaceSnapSettings::only_marked`] is set.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Component, Clone, Copy, Debug, Default, Reflect)]
+#[reflect(Component, Default)]
+pub struct GizmoSnapSurface;
+
+/// Everything needed to cast a drag's cursor ray against scene surfaces.
+#[cfg(feature = "translate")]
+#[derive(SystemParam)]
+pub struct SurfaceRaycast<'w, 's> {
+    provider: Option<Res<'w, GizmoSurfaceRaycast>>,
+    marked: Query<'w, 's, (), With<GizmoSnapSurface>>,
+    handles: Query<'w, 's, (), With<GizmoMeshPart>>,
+    parents: Query<'w, 's, &'static ChildOf>,
+    #[cfg(feature = "mesh-raycast")]
+    meshes: MeshRayCast<'w, 's>,
+}
+
+#[cfg(feature = "translate")]
+impl SurfaceRaycast<'_, '_> {
+    /// Nearest surface hit by `ray` while dragging `target`, through the
+    /// [`GizmoSurfaceRaycast`] callback if there is one.
+    pub(crate) fn cast(
+        &mut self,
+        ray: Ray3d,
+        settings: &SurfaceSnapSettings,
+        target: Entity,
+    ) -> Option<SurfaceHit> {
+        let in_target = |entity: Entity| {
+            entity == target || self.parents.iter_ancestors(entity).any(|e| e == target)
+        };
+        let marked = |entity: Entity| {
+            self.marked.contains(entity)
+                || self
+                    .parents
+                    .iter_ancestors(entity)
+                    .any(|e| self.marked.contains(e))
+        };
+        let handle = |entity: Entity| self.handles.contains(entity);
+        let filter = |entity: Entity| {
+            !in_target(entity) && !handle(entity) && (!settings.only_marked || marked(entity))
+        };
+        if let Some(provider) = &self.provider {
+            return provider.cast(ray, &filter);
+        }
+        #[cfg(feature = "mesh-raycast")]
+        {
+            let settings = MeshRayCastSettings::default()
+                .with_visibility(RayCastVisibility::Visible)
+                .with_filter(&filter);
+            self.meshes
+                .cast_ray(ray, &settings)
+                .first()
+                .map(|(_, hit)| SurfaceHit {
+                    position: hit.point,
+                    normal: hit.normal,
+                })
+        }
+        #[cfg(not(feature = "mesh-raycast"))]
+        None
+    }
+}
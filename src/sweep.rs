@@ -0,0 +1,110 @@
+//! Collision-aware translate drags.
+//!
+//! The gizmo has no notion of scene geometry, so blocking is delegated to a
+//! user hook: a [`GizmoSweepProvider`] answers "where does this target first
+//! touch something when moved from here to there?", typically with a physics
+//! engine's shape cast. Translate drags on targets with blocking enabled stop
+//! at the reported contact, and plane drags can optionally slide along it.
+
+use std::fmt;
+
+use bevy::prelude::*;
+
+/// Slide steps allowed after the first contact of a plane drag.
+const SLIDE_ITERATIONS: usize = 2;
+
+/// First contact reported by a [`GizmoSweepProvider`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SweepHit {
+    /// World-space position of the target's origin at contact.
+    pub position: Vec3,
+    /// World-space surface normal at the contact, pointing back toward the
+    /// side the target came from.
+    pub normal: Vec3,
+}
+
+/// Sweep callback used by blocked translate drags.
+///
+/// Called with the target entity and the world-space positions of its
+/// origin before and after this frame's drag motion. Return the first
+/// blocking contact along that path, or `None` if the motion is free.
+///
+/// A sweep that starts in contact and moves along or away from the surface
+/// must not report a hit, otherwise the target sticks to walls it touches.
+///
+/// ```ignore
+/// app.insert_resource(GizmoSweepProvider::new(|entity, from, to| {
+///     shape_cast(entity, from, to).map(|hit| SweepHit {
+///         position: hit.point,
+///         normal: hit.normal,
+///     })
+/// }));
+/// ```
+#[derive(Resource)]
+pub struct GizmoSweepProvider {
+    sweep: Box<dyn Fn(Entity, Vec3, Vec3) -> Option<SweepHit> + Send + Sync>,
+}
+
+impl GizmoSweepProvider {
+    /// Wrap a sweep callback.
+    pub fn new(
+        sweep: impl Fn(Entity, Vec3, Vec3) -> Option<SweepHit> + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            sweep: Box::new(sweep),
+        }
+    }
+
+    /// First contact moving `entity` from `from` to `to`.
+    pub fn sweep(&self, entity: Entity, from: Vec3, to: Vec3) -> Option<SweepHit> {
+        (self.sweep)(entity, from, to)
+    }
+}
+
+impl fmt::Debug for GizmoSweepProvider {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GizmoSweepProvider").finish_non_exhaustive()
+    }
+}
+
+/// Per-target override of
+/// [`TransformGizmoConfig::blocked_translation`](crate::TransformGizmoConfig::blocked_translation).
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GizmoBlockedTranslation(pub bool);
+
+/// Move from `from` toward `to` until the first contact, returning where the
+/// target ends up and whether anything blocked it.
+///
+/// With `slide_plane` set (the normal of a plane drag's plane), the motion
+/// remaining after a contact is continued along the line where the contact
+/// surface meets the drag plane, for a bounded number of steps.
+pub(crate) fn sweep_translation(
+    provider: &GizmoSweepProvider,
+    entity: Entity,
+    from: Vec3,
+    to: Vec3,
+    slide_plane: Option<Vec3>,
+) -> (Vec3, bool) {
+    let mut from = from;
+    let mut to = to;
+    let mut blocked = false;
+    for _ in 0..=SLIDE_ITERATIONS {
+        let Some(hit) = provider.sweep(entity, from, to) else {
+            return (to, blocked);
+        };
+        blocked = true;
+        let Some(slide_dir) =
+            slide_plane.and_then(|normal| hit.normal.cross(normal).try_normalize())
+        else {
+            return (hit.position, true);
+        };
+        let remaining = (to - hit.position).dot(slide_dir);
+        if remaining.abs() <= f32::EPSILON {
+            return (hit.position, true);
+        }
+        from = hit.position;
+        to = hit.position + slide_dir * remaining;
+    }
+    (from, blocked)
+}
@@ -0,0 +1,460 @@
+//! Headless harness for driving the gizmo's interaction systems in tests.
+//!
+//! [`GizmoTestHarness`] builds a minimal [`App`] with the gizmo's hover and
+//! drag systems, a mocked primary window, and a camera, but no renderer,
+//! event loop, or gizmo drawing. Tests move the cursor, press and release
+//! the left button, step frames, and check hover, drag, and the target's
+//! transform:
+//!
+//! ```ignore
+//! let mut harness = GizmoTestHarness::new();
+//! let cone = harness
+//!     .handle_position(GizmoOperation::TranslateAxis, GizmoAxis::X)
+//!     .unwrap();
+//! harness.drag(cone, cone + Vec2::new(60.0, 0.0));
+//! assert!(harness.target_transform().translation.x > 0.0);
+//! ```
+//!
+//! Enable it with the `test-utils` feature, usually from `dev-dependencies`.
+
+use bevy::asset::AssetEvent;
+use bevy::input::mouse::{MouseScrollUnit, MouseWheel};
+use bevy::input::touch::{TouchInput, TouchPhase};
+use bevy::prelude::*;
+use bevy::render::camera::camera_system;
+use bevy::render::texture::ManualTextureViews;
+use bevy::transform::TransformSystems;
+use bevy::window::{PrimaryWindow, WindowCreated, WindowResized, WindowScaleFactorChanged};
+
+use crate::add_gizmo_interaction;
+use crate::types::{
+    GizmoActive, GizmoAxis, GizmoHandleId, GizmoHandleScreenInfo, GizmoOperation,
+    TransformGizmoCamera, TransformGizmoConfig, TransformGizmoDrag, TransformGizmoState,
+    TransformGizmoStyle, TransformGizmoSystems, TransformGizmoTarget,
+};
+
+/// Frames [`GizmoTestHarness::drag`] spreads the cursor motion over.
+const DRAG_STEPS: u32 = 4;
+
+/// A minimal app running the gizmo's interaction systems against a mocked
+/// window, for tests that need no GPU or display.
+///
+/// The app has the hover, drag, selection, and history systems of
+/// [`TransformGizmoPlugin`](crate::TransformGizmoPlugin), but not its
+/// drawing. It starts with one active [`TransformGizmoTarget`] at the origin
+/// and [`TransformGizmoConfig::handle_screen_info`] on, so
+/// [`handle_position`](Self::handle_position) can find handles on screen.
+/// [`TransformGizmoConfig::drag_threshold_pixels`] is `0.0`, so drags edit
+/// the target from the first frame the cursor moves.
+///
+/// Input is fed directly into `ButtonInput<MouseButton>`, and
+/// [`step`](Self::step) clears its just-pressed and just-released state
+/// after each frame, the way Bevy's input plugin would before the next one.
+pub struct GizmoTestHarness {
+    app: App,
+    window: Entity,
+    camera: Entity,
+    target: Entity,
+}
+
+impl Default for GizmoTestHarness {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GizmoTestHarness {
+    /// A harness whose camera looks at the origin from above and to the
+    /// side, so every handle is seen at an angle.
+    pub fn new() -> Self {
+        Self::with_camera(Transform::from_xyz(4.0, 3.0, 8.0).looking_at(Vec3::ZERO, Vec3::Y))
+    }
+
+    /// A harness whose perspective camera is at `camera_transform`.
+    pub fn with_camera(camera_transform: Transform) -> Self {
+        Self::with_projection(camera_transform, Projection::default())
+    }
+
+    /// A harness whose camera is at `camera_transform` and projects with
+    /// `projection`, for example an orthographic top-down view.
+    ///
+    /// Runs two frames, so cameras, transforms, and handle positions are
+    /// ready once this returns: the camera's viewport is only known after
+    /// the first, and the gizmo camera is selected from it in the second.
+    pub fn with_projection(camera_transform: Transform, projection: Projection) -> Self {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, bevy::transform::TransformPlugin))
+            .init_resource::<ButtonInput<MouseButton>>()
+            .init_resource::<ButtonInput<KeyCode>>()
+            .init_resource::<Assets<Image>>()
+            .init_resource::<ManualTextureViews>()
+            .add_message::<TouchInput>()
+            .add_message::<MouseWheel>()
+            .add_message::<WindowCreated>()
+            .add_message::<WindowResized>()
+            .add_message::<WindowScaleFactorChanged>()
+            .add_message::<AssetEvent<Image>>()
+            .add_systems(
+                PostUpdate,
+                camera_system
+                    .after(TransformSystems::Propagate)
+                    .before(TransformGizmoSystems::Hover),
+            );
+        #[cfg(feature = "mesh-raycast")]
+        app.init_resource::<Assets<Mesh>>();
+        add_gizmo_interaction(&mut app);
+        let mut config = app.world_mut().resource_mut::<TransformGizmoConfig>();
+        config.handle_screen_info = true;
+        config.drag_threshold_pixels = 0.0;
+
+        let window = app
+            .world_mut()
+            .spawn((Window::default(), PrimaryWindow))
+            .id();
+        let camera = app
+            .world_mut()
+            .spawn((
+                Camera3d::default(),
+                projection,
+                camera_transform,
+                TransformGizmoCamera,
+            ))
+            .id();
+        let target = app
+            .world_mut()
+            .spawn((Transform::IDENTITY, TransformGizmoTarget, GizmoActive))
+            .id();
+
+        let mut harness = Self {
+            app,
+            window,
+            camera,
+            target,
+        };
+        harness.step();
+        harness.step();
+        harness
+    }
+
+    /// The app, to read resources and components the accessors do not cover.
+    pub fn app(&self) -> &App {
+        &self.app
+    }
+
+    /// The app, to insert components, change settings, or add systems.
+    pub fn app_mut(&mut self) -> &mut App {
+        &mut self.app
+    }
+
+    /// The mocked primary window.
+    pub fn window(&self) -> Entity {
+        self.window
+    }
+
+    /// The gizmo camera.
+    pub fn camera(&self) -> Entity {
+        self.camera
+    }
+
+    /// The target the harness spawned.
+    pub fn target(&self) -> Entity {
+        self.target
+    }
+
+    /// The gizmo style, to show or hide handles before a test.
+    pub fn style_mut(&mut self) -> Mut<'_, TransformGizmoStyle> {
+        self.app.world_mut().resource_mut::<TransformGizmoStyle>()
+    }
+
+    /// Move the cursor to `position`, in logical window pixels. Takes effect
+    /// on the next [`step`](Self::step).
+    pub fn move_cursor(&mut self, position: Vec2) {
+        self.app
+            .world_mut()
+            .get_mut::<Window>(self.window)
+            .expect("the harness window was despawned")
+            .set_cursor_position(Some(position));
+    }
+
+    /// Move the cursor out of the window. Takes effect on the next
+    /// [`step`](Self::step).
+    pub fn leave_window(&mut self) {
+        self.app
+            .world_mut()
+            .get_mut::<Window>(self.window)
+            .expect("the harness window was despawned")
+            .set_cursor_position(None);
+    }
+
+    /// Give the window focus, or take it away. Takes effect on the next
+    /// [`step`](Self::step).
+    pub fn set_focused(&mut self, focused: bool) {
+        self.app
+            .world_mut()
+            .get_mut::<Window>(self.window)
+            .expect("the harness window was despawned")
+            .focused = focused;
+    }
+
+    /// Press the left mouse button. Takes effect on the next
+    /// [`step`](Self::step).
+    pub fn press_left(&mut self) {
+        self.app
+            .world_mut()
+            .resource_mut::<ButtonInput<MouseButton>>()
+            .press(MouseButton::Left);
+    }
+
+    /// Release the left mouse button. Takes effect on the next
+    /// [`step`](Self::step).
+    pub fn release_left(&mut self) {
+        self.app
+            .world_mut()
+            .resource_mut::<ButtonInput<MouseButton>>()
+            .release(MouseButton::Left);
+    }
+
+    /// Scroll the mouse wheel `lines` up, or down if negative. Takes effect
+    /// on the next [`step`](Self::step).
+    pub fn scroll(&mut self, lines: f32) {
+        let window = self.window;
+        self.app.world_mut().write_message(MouseWheel {
+            unit: MouseScrollUnit::Line,
+            x: 0.0,
+            y: lines,
+            window,
+            phase: TouchPhase::Moved,
+        });
+    }
+
+    /// Run one frame, then clear this frame's presses and releases.
+    pub fn step(&mut self) {
+        self.app.update();
+        let world = self.app.world_mut();
+        world.resource_mut::<ButtonInput<MouseButton>>().clear();
+        world.resource_mut::<ButtonInput<KeyCode>>().clear();
+    }
+
+    /// Drag from `from` to `to`: hover `from`, press, move to `to` over a
+    /// few frames, and release, stepping a frame after each.
+    pub fn drag(&mut self, from: Vec2, to: Vec2) {
+        self.move_cursor(from);
+        self.step();
+        self.press_left();
+        self.step();
+        for step in 1..=DRAG_STEPS {
+            self.move_cursor(from.lerp(to, step as f32 / DRAG_STEPS as f32));
+            self.step();
+        }
+        self.release_left();
+        self.step();
+    }
+
+    /// Logical window position of a handle of the harness target, as of the
+    /// last frame, if it is laid out and on screen.
+    pub fn handle_position(&self, op: GizmoOperation, axis: GizmoAxis) -> Option<Vec2> {
+        self.app
+            .world()
+            .resource::<GizmoHandleScreenInfo>()
+            .get(GizmoHandleId::new(self.target, op, axis))
+            .filter(|screen| screen.visible)
+            .map(|screen| screen.position)
+    }
+
+    /// Logical window position of a world-space point, if the harness
+    /// camera sees it.
+    pub fn world_to_cursor(&self, point: Vec3) -> Option<Vec2> {
+        let world = self.app.world();
+        let camera = world.get::<Camera>(self.camera)?;
+        let camera_transform = world.get::<GlobalTransform>(self.camera)?;
+        let viewport = camera.logical_viewport_rect()?;
+        let position = camera.world_to_viewport(camera_transform, point).ok()?;
+        Some(position + viewport.min)
+    }
+
+    /// The gizmo state: mode, selection, hover, and drag.
+    pub fn state(&self) -> &TransformGizmoState {
+        self.app.world().resource::<TransformGizmoState>()
+    }
+
+    /// The hovered handle, as of the last frame.
+    pub fn hovered(&self) -> Option<(GizmoOperation, GizmoAxis)> {
+        let state = self.state();
+        state.hovered_op.zip(state.hovered_axis)
+    }
+
+    /// The drag in progress, if any.
+    pub fn drag_state(&self) -> Option<&TransformGizmoDrag> {
+        self.state().drag.as_ref()
+    }
+
+    /// The local transform of the harness target.
+    pub fn target_transform(&self) -> Transform {
+        *self
+            .app
+            .world()
+            .get::<Transform>(self.target)
+            .expect("the harness target was despawned")
+    }
+}
+
+#[cfg(test)]
+impl GizmoTestHarness {
+    /// Screen direction of the world axis `dir` through the origin.
+    #[cfg(any(feature = "translate", feature = "scale"))]
+    pub(crate) fn screen_axis(&self, dir: Vec3) -> Vec2 {
+        let origin = self.world_to_cursor(Vec3::ZERO).unwrap();
+        (self.world_to_cursor(dir).unwrap() - origin).normalize()
+    }
+
+    /// Hover the handle, check it is the one hovered, and return its position.
+    #[cfg(any(feature = "translate", feature = "rotate", feature = "scale"))]
+    pub(crate) fn grab(&mut self, op: GizmoOperation, axis: GizmoAxis) -> Vec2 {
+        let position = self.handle_position(op, axis).unwrap();
+        self.move_cursor(position);
+        self.step();
+        assert_eq!(self.hovered(), Some((op, axis)));
+        position
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(any(feature = "translate", feature = "rotate", feature = "scale"))]
+    use bevy::camera::ScalingMode;
+
+    /// A top-down orthographic view, ten units tall, whose near plane is a
+    /// thousand units behind the camera like Bevy's 2D default. Handles seen
+    /// end-on or edge-on are faded out, as a top-down editor would.
+    #[cfg(any(feature = "translate", feature = "rotate", feature = "scale"))]
+    fn top_down_orthographic() -> GizmoTestHarness {
+        let mut harness = GizmoTestHarness::with_projection(
+            Transform::from_xyz(0.0, 10.0, 0.0).looking_at(Vec3::ZERO, Vec3::NEG_Z),
+            Projection::Orthographic(OrthographicProjection {
+                near: -1000.0,
+                scaling_mode: ScalingMode::FixedVertical {
+                    viewport_height: 10.0,
+                },
+                ..OrthographicProjection::default_3d()
+            }),
+        );
+        harness.style_mut().fade_backfacing = true;
+        harness.step();
+        harness
+    }
+
+    #[cfg(feature = "translate")]
+    fn check_translate_axis_drag(mut harness: GizmoTestHarness) {
+        let start = harness.grab(GizmoOperation::TranslateAxis, GizmoAxis::X);
+        harness.press_left();
+        harness.step();
+        assert!(harness.drag_state().is_some());
+
+        harness.move_cursor(start + harness.screen_axis(Vec3::X) * 60.0);
+        harness.step();
+        harness.release_left();
+        harness.step();
+        assert!(harness.drag_state().is_none());
+        let translation = harness.target_transform().translation;
+        assert!(translation.x > 0.1, "{translation}");
+        assert!(translation.y.abs() < 1.0e-4 && translation.z.abs() < 1.0e-4);
+    }
+
+    #[cfg(feature = "planes")]
+    fn check_translate_plane_drag(mut harness: GizmoTestHarness) {
+        let start = harness.grab(GizmoOperation::TranslatePlane, GizmoAxis::Y);
+        harness.drag(start, start + Vec2::new(50.0, 20.0));
+        let translation = harness.target_transform().translation;
+        assert!(translation.xz().length() > 0.1, "{translation}");
+        assert!(translation.y.abs() < 1.0e-4, "{translation}");
+    }
+
+    #[cfg(feature = "rotate")]
+    fn check_rotate_drag(mut harness: GizmoTestHarness, axis: GizmoAxis) {
+        let start = harness.grab(GizmoOperation::Rotate, axis);
+        // Along the ring, across the line from the center to the handle.
+        let radial = start - harness.world_to_cursor(Vec3::ZERO).unwrap();
+        harness.drag(start, start + radial.perp().normalize() * 40.0);
+        let transform = harness.target_transform();
+        let (turned_about, angle) = transform.rotation.to_axis_angle();
+        assert!(angle > 0.05, "{angle}");
+        assert!(
+            turned_about.cross(axis.to_vec3()).length() < 1.0e-3,
+            "{turned_about}"
+        );
+        assert!(transform.translation.abs_diff_eq(Vec3::ZERO, 1.0e-4));
+    }
+
+    #[cfg(feature = "scale")]
+    fn check_scale_axis_drag(mut harness: GizmoTestHarness) {
+        let start = harness.grab(GizmoOperation::ScaleAxis, GizmoAxis::X);
+        harness.drag(start, start + harness.screen_axis(Vec3::X) * 40.0);
+        let scale = harness.target_transform().scale;
+        assert!(scale.x > 1.05, "{scale}");
+        assert!((scale.y - 1.0).abs() < 1.0e-4 && (scale.z - 1.0).abs() < 1.0e-4);
+    }
+
+    #[cfg(feature = "uniform-scale")]
+    fn check_uniform_scale_drag(mut harness: GizmoTestHarness) {
+        let center = harness
+            .handle_position(GizmoOperation::ScaleUniform, GizmoAxis::X)
+            .unwrap();
+        // Off the exact center, which has no distance to scale by.
+        let start = center + Vec2::new(4.0, 0.0);
+        harness.move_cursor(start);
+        harness.step();
+        assert_eq!(
+            harness.hovered(),
+            Some((GizmoOperation::ScaleUniform, GizmoAxis::X))
+        );
+        harness.drag(start, center + Vec2::new(12.0, 0.0));
+        let scale = harness.target_transform().scale;
+        assert!(scale.x > 1.5, "{scale}");
+        assert!(scale.abs_diff_eq(Vec3::splat(scale.x), 1.0e-4), "{scale}");
+    }
+
+    #[test]
+    fn harness_starts_idle_on_its_target() {
+        let harness = GizmoTestHarness::new();
+        assert_eq!(harness.state().active_target, Some(harness.target()));
+        assert!(harness.drag_state().is_none());
+        assert_eq!(harness.target_transform(), Transform::IDENTITY);
+    }
+
+    #[cfg(feature = "translate")]
+    #[test]
+    fn translate_axis_drag_moves_along_the_axis() {
+        check_translate_axis_drag(GizmoTestHarness::new());
+        check_translate_axis_drag(top_down_orthographic());
+    }
+
+    #[cfg(feature = "planes")]
+    #[test]
+    fn translate_plane_drag_stays_in_the_plane() {
+        check_translate_plane_drag(GizmoTestHarness::new());
+        check_translate_plane_drag(top_down_orthographic());
+    }
+
+    #[cfg(feature = "rotate")]
+    #[test]
+    fn rotate_drag_turns_about_the_axis() {
+        check_rotate_drag(GizmoTestHarness::new(), GizmoAxis::X);
+        // Seen from above, the Y ring faces the camera.
+        check_rotate_drag(top_down_orthographic(), GizmoAxis::Y);
+    }
+
+    #[cfg(feature = "scale")]
+    #[test]
+    fn scale_axis_drag_scales_one_axis() {
+        check_scale_axis_drag(GizmoTestHarness::new());
+        check_scale_axis_drag(top_down_orthographic());
+    }
+
+    #[cfg(feature = "uniform-scale")]
+    #[test]
+    fn uniform_scale_drag_scales_every_axis() {
+        check_uniform_scale_drag(GizmoTestHarness::new());
+        check_uniform_scale_drag(top_down_orthographic());
+    }
+}
@@ -3,14 +3,22 @@
 //! This module contains all the public types used to configure and interact
 //! with the transform gizmo system.
 
+use bevy::platform::collections::HashMap;
 use bevy::prelude::*;
 use std::fmt;
+use std::time::Duration;
+
+use crate::gizmo_frame::plane_axes;
+use crate::math::{axis_in_parent, has_non_uniform_scale, scale_ratio, twist_angle};
+use crate::preferences::DuplicateStableIds;
 
 /// Which transform component the gizmo is currently editing for UI purposes.
 ///
 /// This is mostly useful for external UI to display the current mode.
 /// Interaction logic uses [`GizmoOperation`] internally.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Default)]
 pub enum TransformGizmoMode {
     /// Translation mode - move entities along axes or planes.
     #[default]
@@ -32,13 +40,48 @@ impl fmt::Display for TransformGizmoMode {
 }
 
 /// Coordinate space used by the gizmo axes.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+///
+/// Scale handles always follow the target's local axes.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Default, Reflect)]
+#[reflect(Default)]
 pub enum TransformGizmoSpace {
     /// Axes aligned to world coordinates (global X/Y/Z).
     World,
     /// Axes aligned to the target entity's local rotation.
     #[default]
     Local,
+    /// Axes aligned to the rotation of the target's parent, or the world
+    /// for targets without one. A secondary channel's parent is the target
+    /// itself.
+    Parent,
+    /// Axes aligned to the gizmo camera: X right, Y up, and Z toward the
+    /// viewer, so the Z plane handle moves in the screen plane.
+    View,
+    /// Axes of a fixed rotation, such as a gimbal or a tool's frame.
+    Custom(Quat),
+}
+
+impl TransformGizmoSpace {
+    /// The space after this one when cycling World, Local, Parent, and
+    /// View, as the examples' space key does. A custom space cycles to
+    /// World.
+    ///
+    /// ```
+    /// # use bevy_transform_tools::TransformGizmoSpace;
+    /// assert_eq!(TransformGizmoSpace::Parent.next(), TransformGizmoSpace::View);
+    /// assert_eq!(TransformGizmoSpace::View.next(), TransformGizmoSpace::World);
+    /// ```
+    pub fn next(self) -> Self {
+        match self {
+            TransformGizmoSpace::World => TransformGizmoSpace::Local,
+            TransformGizmoSpace::Local => TransformGizmoSpace::Parent,
+            TransformGizmoSpace::Parent => TransformGizmoSpace::View,
+            TransformGizmoSpace::View | TransformGizmoSpace::Custom(_) => {
+                TransformGizmoSpace::World
+            }
+        }
+    }
 }
 
 impl fmt::Display for TransformGizmoSpace {
@@ -46,10 +89,47 @@ impl fmt::Display for TransformGizmoSpace {
         match self {
             TransformGizmoSpace::Local => f.write_str("Local"),
             TransformGizmoSpace::World => f.write_str("World"),
+            TransformGizmoSpace::Parent => f.write_str("Parent"),
+            TransformGizmoSpace::View => f.write_str("View"),
+            TransformGizmoSpace::Custom(_) => f.write_str("Custom"),
         }
     }
 }
 
+/// System sets used by [`TransformGizmoPlugin`](crate::TransformGizmoPlugin).
+///
+/// Order your own systems against these to control what the gizmo sees.
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TransformGizmoSystems {
+    /// Camera selection, target sync, and drag handling, in `Update`.
+    ///
+    /// Contains [`TransformGizmoSystems::Sync`] followed by
+    /// [`TransformGizmoSystems::Drag`]. Transforms written here are
+    /// propagated in the same frame.
+    Input,
+    /// Preference restore, pointer and camera selection, active target sync,
+    /// and click selection, in `Update` inside
+    /// [`TransformGizmoSystems::Input`].
+    ///
+    /// Selection logic that should decide the target a click drags belongs
+    /// before [`TransformGizmoSystems::Drag`], or before this set to also
+    /// take part in click selection.
+    Sync,
+    /// Drag start, keyboard constraints, drag motion, reparenting, drag end,
+    /// and undo/redo history, in `Update` inside
+    /// [`TransformGizmoSystems::Input`], after [`TransformGizmoSystems::Sync`].
+    ///
+    /// Runs before transform propagation, so dragged targets are rendered
+    /// where the gizmo is drawn in the same frame.
+    Drag,
+    /// Visual pivot refresh and hover detection, in `PostUpdate` after
+    /// transform propagation, so hover tests the positions being rendered.
+    Hover,
+    /// Gizmo drawing and diagnostics, in `PostUpdate` after
+    /// [`TransformGizmoSystems::Hover`].
+    Draw,
+}
+
 /// Marker component for cameras used by the transform gizmo.
 ///
 /// Add this to any camera whose view should be used for gizmo interaction.
@@ -65,9 +145,98 @@ impl fmt::Display for TransformGizmoSpace {
 ///     TransformGizmoCamera,
 /// ));
 /// ```
-#[derive(Component)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Component, Default, Reflect)]
+#[reflect(Component, Default)]
 pub struct TransformGizmoCamera;
 
+/// Explicit priority for a [`TransformGizmoCamera`].
+///
+/// When several gizmo cameras are eligible, the one with the highest priority
+/// drives hover, dragging, and drawing. Cameras without this component count
+/// as priority `0`; remaining ties are broken by entity id so the choice is
+/// stable between runs.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub struct GizmoCameraPriority(pub i32);
+
+/// The camera currently driving the transform gizmo.
+///
+/// Updated once per frame before any other gizmo system runs, so hover,
+/// dragging, and drawing always agree on the camera in use. A camera whose
+/// viewport contains the [`GizmoPointer`] is preferred over the others, so
+/// each window or split-screen view can be edited by moving the cursor into
+/// it; the selection is held while a drag is active.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct GizmoCameraSelection {
+    pub(crate) camera: Option<Entity>,
+    pub(crate) window: Option<Entity>,
+    pub(crate) warned: Vec<Entity>,
+}
+
+impl GizmoCameraSelection {
+    /// The selected camera entity, if any camera is eligible.
+    pub fn camera(&self) -> Option<Entity> {
+        self.camera
+    }
+
+    /// The window the selected camera renders to, or `None` if it renders
+    /// to an image or texture.
+    pub fn window(&self) -> Option<Entity> {
+        self.window
+    }
+}
+
+/// Selects what the gizmo edits on a target.
+///
+/// Secondary channels are registered with
+/// [`TransformGizmoAppExt::register_gizmo_channel`](crate::TransformGizmoAppExt::register_gizmo_channel).
+/// Targets set to [`GizmoEditChannel::Secondary`] without a registered channel
+/// component fall back to editing their `Transform`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GizmoEditChannel {
+    /// Edit the entity's `Transform`.
+    #[default]
+    Transform,
+    /// Edit the entity's registered secondary component.
+    Secondary,
+}
+
+/// Which parts of a pseudo-transform a secondary channel supports.
+///
+/// Handles for unsupported parts are hidden while the channel is edited.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GizmoChannelParts {
+    /// Translation handles (axes and planes).
+    pub translate: bool,
+    /// Rotation arcs.
+    pub rotate: bool,
+    /// Scale handles (axes and uniform).
+    pub scale: bool,
+}
+
+impl GizmoChannelParts {
+    /// Every part supported.
+    pub const ALL: Self = Self {
+        translate: true,
+        rotate: true,
+        scale: true,
+    };
+
+    /// Translation and rotation only.
+    pub const TRANSLATE_ROTATE: Self = Self {
+        translate: true,
+        rotate: true,
+        scale: false,
+    };
+}
+
+impl Default for GizmoChannelParts {
+    fn default() -> Self {
+        Self::ALL
+    }
+}
+
 /// Marks an entity as controllable by the transform gizmo.
 ///
 /// Entities with this component can be manipulated via gizmo handles.
@@ -84,14 +253,20 @@ pub struct TransformGizmoCamera;
 ///     GizmoActive,  // This entity is the active gizmo target
 /// ));
 /// ```
-#[derive(Component)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Component, Clone, Copy, Default, Reflect)]
+#[reflect(Component, Default)]
 pub struct TransformGizmoTarget;
 
 /// Marks a [`TransformGizmoTarget`] as the currently active/selected target.
 ///
 /// The gizmo will be rendered on entities that have both `TransformGizmoTarget`
-/// and `GizmoActive`. Only one entity should have this at a time; if multiple
-/// exist, the first one found is used.
+/// and `GizmoActive`. When several have it, the gizmo sits on one of them,
+/// [`TransformGizmoState::active_target`], and the others follow its drags.
+/// The gizmo stays on its target while that keeps `GizmoActive`, unless
+/// another active target has a higher [`GizmoActivePriority`]. Otherwise it
+/// moves to the active target with the highest priority, and ties go to the
+/// lowest entity id, so the choice is the same between runs.
 ///
 /// # Example
 ///
@@ -105,15 +280,378 @@ pub struct TransformGizmoTarget;
 ///     GizmoActive,
 /// ));
 ///
-/// // To switch selection, remove GizmoActive from one entity and add to another:
-/// commands.entity(old_target).remove::<GizmoActive>();
-/// commands.entity(new_target).insert(GizmoActive);
+/// // To switch selection, move GizmoActive from every other entity to one:
+/// commands.set_gizmo_active(new_target);
 /// ```
-#[derive(Component)]
+///
+/// See [`GizmoActiveCommandsExt`](crate::GizmoActiveCommandsExt).
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Component, Default, Reflect)]
+#[reflect(Component, Default)]
 pub struct GizmoActive;
 
+/// Explicit priority for an active [`TransformGizmoTarget`].
+///
+/// When several targets have [`GizmoActive`], the gizmo moves to the one
+/// with the highest priority. Targets without this component count as
+/// priority `0`; remaining ties are broken by entity id.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub struct GizmoActivePriority(pub i32);
+
+/// Multiplies every color of the gizmo drawn on this target by a tint, for
+/// example to match the color coding of an entity category.
+///
+/// ```ignore
+/// commands.spawn((PointLight::default(), TransformGizmoTarget, GizmoColorTint(Color::srgb(1.0, 0.9, 0.3))));
+/// ```
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Component, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct GizmoColorTint(pub Color);
+
+/// Marks a [`TransformGizmoTarget`] whose gizmo can be seen but not touched.
+///
+/// Its handles are never hovered or dragged, and its gizmo is drawn in
+/// [`TransformGizmoStyle::locked_color`], or hidden when
+/// [`TransformGizmoStyle::show_locked_gizmos`] is off.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Component, Debug, Clone, Copy, Default, Reflect)]
+#[reflect(Component, Default)]
+pub struct GizmoLocked;
+
+/// Limits which handles the gizmo of a [`TransformGizmoTarget`] offers, for
+/// entities that should only ever be turned or slid along the ground.
+///
+/// A handle is shown, hovered, and dragged only if both the style and the
+/// constraints allow it. The axes are the gizmo's, in whichever
+/// [`TransformGizmoSpace`] it is drawn. The plane handles need both of
+/// their axes, and the screen-facing, trackball, and uniform scale handles
+/// every axis of their kind.
+///
+/// [`lock_to_plane`](Self::lock_to_plane) also keeps that component of the
+/// target's `Transform` translation where it was, after snapping, for
+/// drags and keyboard nudges alike. It is the axis of the target's parent
+/// space, which matches the gizmo's in world or parent space.
+///
+/// ```ignore
+/// // A turret that only turns about Y.
+/// commands.spawn((turret, TransformGizmoTarget, GizmoConstraints::rotate_only(GizmoAxis::Y)));
+/// // A pickup that only slides along the ground.
+/// commands.spawn((pickup, TransformGizmoTarget, GizmoConstraints::translate_plane(GizmoAxis::Y)));
+/// ```
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Component, Debug, Clone, Copy, Reflect)]
+#[reflect(Component, Default)]
+pub struct GizmoConstraints {
+    /// Axes the target can be moved along.
+    pub translate: AxisToggles,
+    /// Axes the target can be turned about.
+    pub rotate: AxisToggles,
+    /// Axes the target can be scaled along.
+    pub scale: AxisToggles,
+    /// Normal of the plane translation is held to: its axis handle is
+    /// removed, and so are the plane handles that leave the plane.
+    pub lock_to_plane: Option<GizmoAxis>,
+}
+
+impl Default for GizmoConstraints {
+    fn default() -> Self {
+        Self {
+            translate: AxisToggles::all(),
+            rotate: AxisToggles::all(),
+            scale: AxisToggles::all(),
+            lock_to_plane: None,
+        }
+    }
+}
+
+impl GizmoConstraints {
+    /// Only rotation about `axis`.
+    pub fn rotate_only(axis: GizmoAxis) -> Self {
+        let mut rotate = AxisToggles::none();
+        match axis {
+            GizmoAxis::X => rotate.x = true,
+            GizmoAxis::Y => rotate.y = true,
+            GizmoAxis::Z => rotate.z = true,
+        }
+        Self {
+            translate: AxisToggles::none(),
+            rotate,
+            scale: AxisToggles::none(),
+            lock_to_plane: None,
+        }
+    }
+
+    /// Only translation in the plane with normal `normal`.
+    pub fn translate_plane(normal: GizmoAxis) -> Self {
+        Self {
+            translate: AxisToggles::all(),
+            rotate: AxisToggles::none(),
+            scale: AxisToggles::none(),
+            lock_to_plane: Some(normal),
+        }
+    }
+
+    /// Whether the constraints allow the `(op, axis)` handle.
+    ///
+    /// ```
+    /// # use bevy_transform_tools::{GizmoAxis, GizmoConstraints, GizmoOperation};
+    /// let ground = GizmoConstraints::translate_plane(GizmoAxis::Y);
+    /// assert!(ground.allows(GizmoOperation::TranslateAxis, GizmoAxis::X));
+    /// assert!(!ground.allows(GizmoOperation::TranslateAxis, GizmoAxis::Y));
+    /// assert!(ground.allows(GizmoOperation::TranslatePlane, GizmoAxis::Y));
+    /// assert!(!ground.allows(GizmoOperation::TranslatePlane, GizmoAxis::X));
+    /// ```
+    pub fn allows(&self, op: GizmoOperation, axis: GizmoAxis) -> bool {
+        let all = |toggles: &AxisToggles| toggles.x && toggles.y && toggles.z;
+        let translates = |axis| self.translate.enabled(axis) && self.lock_to_plane != Some(axis);
+        match op {
+            GizmoOperation::TranslateAxis => translates(axis),
+            GizmoOperation::TranslatePlane => {
+                let (a1, a2) = plane_axes(axis);
+                translates(a1) && translates(a2)
+            }
+            GizmoOperation::TranslateView => all(&self.translate) && self.lock_to_plane.is_none(),
+            GizmoOperation::Endpoint { .. } => {
+                all(&self.translate) && self.lock_to_plane.is_none() && self.scale.enabled(axis)
+            }
+            GizmoOperation::Rotate => self.rotate.enabled(axis),
+            GizmoOperation::RotateView | GizmoOperation::RotateTrackball => all(&self.rotate),
+            GizmoOperation::ScaleAxis => self.scale.enabled(axis),
+            GizmoOperation::ScaleUniform => all(&self.scale),
+            GizmoOperation::OriginDot => true,
+        }
+    }
+
+    /// `translation` with its [`lock_to_plane`](Self::lock_to_plane)
+    /// component put back to `before`'s.
+    pub fn lock_translation(&self, before: Vec3, mut translation: Vec3) -> Vec3 {
+        if let Some(normal) = self.lock_to_plane {
+            translation[normal.index()] = before[normal.index()];
+        }
+        translation
+    }
+}
+
+/// Ranges the gizmo keeps a [`TransformGizmoTarget`]'s `Transform` within,
+/// for props that must stay above the floor or at a sensible size.
+///
+/// Drags and keyboard nudges pin each limited component at the boundary it
+/// would cross, after snapping, so a snapped value past a limit lands on
+/// the limit itself. A drag held at a limit reports the pinned axis in
+/// [`TransformGizmoDrag::limit_hit`] and tints its handle with
+/// [`TransformGizmoStyle::blocked_color`]. Secondary channel values are not
+/// limited.
+///
+/// ```ignore
+/// // A crate that stays above the floor, between half and twice its size.
+/// commands.spawn((
+///     crate_bundle,
+///     TransformGizmoTarget,
+///     GizmoLimits {
+///         translation: Some((Vec3::new(f32::MIN, 0.0, f32::MIN), Vec3::MAX)),
+///         world_translation: true,
+///         scale: Some((Vec3::splat(0.5), Vec3::splat(2.0))),
+///         ..default()
+///     },
+/// ));
+/// ```
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Component, Debug, Clone, Copy, Default, PartialEq, Reflect)]
+#[reflect(Component, Default)]
+pub struct GizmoLimits {
+    /// Minimum and maximum translation, in the parent's space, or in world
+    /// space with [`world_translation`](Self::world_translation).
+    pub translation: Option<(Vec3, Vec3)>,
+    /// Whether `translation` is in world space instead of the parent's.
+    pub world_translation: bool,
+    /// Minimum and maximum rotation as `EulerRot::XYZ` angles, in radians.
+    pub rotation_euler: Option<(Vec3, Vec3)>,
+    /// Minimum and maximum scale.
+    pub scale: Option<(Vec3, Vec3)>,
+}
+
+impl GizmoLimits {
+    /// Clamp every limited component of `transform` into its range, and
+    /// return the axis of the first one that had to be pinned.
+    ///
+    /// `parent` is the global transform of the space `transform` is in, for
+    /// world-space translation limits. Components already in range are
+    /// left exactly as they are.
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_transform_tools::{GizmoAxis, GizmoLimits};
+    /// let limits = GizmoLimits {
+    ///     translation: Some((Vec3::new(-10.0, 0.0, -10.0), Vec3::splat(10.0))),
+    ///     ..default()
+    /// };
+    /// let mut transform = Transform::from_xyz(2.0, -1.5, 0.0);
+    /// assert_eq!(limits.clamp(&mut transform, None), Some(GizmoAxis::Y));
+    /// assert_eq!(transform.translation, Vec3::new(2.0, 0.0, 0.0));
+    /// ```
+    pub fn clamp(
+        &self,
+        transform: &mut Transform,
+        parent: Option<&GlobalTransform>,
+    ) -> Option<GizmoAxis> {
+        let mut pinned = None;
+        let mut clamp = |value: Vec3, (min, max): (Vec3, Vec3)| {
+            let clamped = value.max(min).min(max);
+            if pinned.is_none() {
+                pinned = GizmoAxis::ALL
+                    .into_iter()
+                    .find(|axis| clamped[axis.index()] != value[axis.index()]);
+            }
+            (clamped != value).then_some(clamped)
+        };
+
+        if let Some(range) = self.translation {
+            let to_world = parent
+                .filter(|_| self.world_translation)
+                .map(GlobalTransform::affine);
+            let translation = to_world.map_or(transform.translation, |affine| {
+                affine.transform_point3(transform.translation)
+            });
+            if let Some(clamped) = clamp(translation, range) {
+                transform.translation =
+                    to_world.map_or(clamped, |affine| affine.inverse().transform_point3(clamped));
+            }
+        }
+        if let Some(range) = self.rotation_euler {
+            let (x, y, z) = transform.rotation.to_euler(EulerRot::XYZ);
+            if let Some(clamped) = clamp(Vec3::new(x, y, z), range) {
+                transform.rotation =
+                    Quat::from_euler(EulerRot::XYZ, clamped.x, clamped.y, clamped.z);
+            }
+        }
+        if let Some(range) = self.scale {
+            if let Some(clamped) = clamp(transform.scale, range) {
+                transform.scale = clamped;
+            }
+        }
+        pinned
+    }
+}
+
+/// Where a [`GizmoVisualPivot`] takes its local offset from.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum VisualPivotSource {
+    /// The offset is set by hand and never recomputed.
+    #[default]
+    Manual,
+    /// The offset is the center of the target's own `Aabb`.
+    Bounds,
+    /// The offset is the center of the combined `Aabb`s of the target and all
+    /// of its descendants, expressed in the target's local space.
+    DescendantBounds,
+    /// The pivot stays at this world-space point, such as a door's hinge,
+    /// and the offset follows it as the target moves.
+    WorldPoint(Vec3),
+}
+
+/// Anchors the gizmo at a point other than the target's transform origin.
+///
+/// Assets imported from glTF often have their node origin far away from the
+/// visible geometry. With this component the gizmo is drawn at the visual
+/// center instead, and rotation/scale drags pivot the mesh in place by
+/// adjusting the target's translation. The authored origin itself is left
+/// untouched in the data; only `Transform` values are rewritten.
+///
+/// # Example
+///
+/// ```ignore
+/// // The mesh lives on a child node offset from the root.
+/// commands
+///     .spawn((
+///         Transform::default(),
+///         TransformGizmoTarget,
+///         GizmoActive,
+///         GizmoVisualPivot::from_descendant_bounds(),
+///     ))
+///     .with_children(|p| {
+///         p.spawn((Mesh3d(mesh), Transform::from_xyz(3.0, 0.0, 0.0)));
+///     });
+/// ```
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct GizmoVisualPivot {
+    /// Pivot position in the target's local space.
+    pub local_offset: Vec3,
+    /// How `local_offset` is kept up to date.
+    pub source: VisualPivotSource,
+}
+
+impl GizmoVisualPivot {
+    /// Creates a pivot at a fixed offset in the target's local space.
+    pub fn manual(local_offset: Vec3) -> Self {
+        Self {
+            local_offset,
+            source: VisualPivotSource::Manual,
+        }
+    }
+
+    /// Creates a pivot that follows the center of the target's own `Aabb`.
+    pub fn from_bounds() -> Self {
+        Self {
+            local_offset: Vec3::ZERO,
+            source: VisualPivotSource::Bounds,
+        }
+    }
+
+    /// Creates a pivot that follows the combined bounds of the target and its
+    /// descendants.
+    pub fn from_descendant_bounds() -> Self {
+        Self {
+            local_offset: Vec3::ZERO,
+            source: VisualPivotSource::DescendantBounds,
+        }
+    }
+
+    /// Creates a pivot pinned at a point in world space, to rotate and scale
+    /// the target about it without reparenting.
+    ///
+    /// The local offset is filled in from the target's transform on the next
+    /// update.
+    pub fn world_point(point: Vec3) -> Self {
+        Self {
+            local_offset: Vec3::ZERO,
+            source: VisualPivotSource::WorldPoint(point),
+        }
+    }
+
+    /// Returns the pivot position in world space for the given target transform.
+    pub fn world_position(&self, transform: &GlobalTransform) -> Vec3 {
+        transform.transform_point(self.local_offset)
+    }
+}
+
+/// Where a target's gizmo sits, see [`TransformGizmoStyle::anchor`].
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Default, Reflect)]
+#[reflect(Default)]
+pub enum GizmoAnchor {
+    /// At the target's transform origin.
+    #[default]
+    Origin,
+    /// At the center of the target's own `Aabb`, or at its origin if it has
+    /// none.
+    BoundsCenter,
+    /// At this offset in the target's local space.
+    Custom(Vec3),
+}
+
+/// Overrides [`TransformGizmoStyle::anchor`] for one target. A
+/// [`GizmoVisualPivot`] on the target still takes precedence.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Component, Debug, Clone, Copy, PartialEq, Default, Reflect)]
+#[reflect(Component, Default)]
+pub struct GizmoAnchorOverride(pub GizmoAnchor);
+
 /// Identifies which axis (X, Y, or Z) a gizmo handle operates on.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
 pub enum GizmoAxis {
     /// The X axis (typically red).
     X,
@@ -124,6 +662,15 @@ pub enum GizmoAxis {
 }
 
 impl GizmoAxis {
+    /// The three axes, in order, for iterating over them.
+    ///
+    /// ```
+    /// # use bevy_transform_tools::GizmoAxis;
+    /// let names: Vec<String> = GizmoAxis::ALL.iter().map(|axis| axis.to_string()).collect();
+    /// assert_eq!(names, ["X", "Y", "Z"]);
+    /// ```
+    pub const ALL: [GizmoAxis; 3] = [GizmoAxis::X, GizmoAxis::Y, GizmoAxis::Z];
+
     /// Converts the axis to its corresponding unit vector.
     pub fn to_vec3(self) -> Vec3 {
         match self {
@@ -132,13 +679,108 @@ impl GizmoAxis {
             GizmoAxis::Z => Vec3::Z,
         }
     }
+
+    /// The axis's position in [`ALL`](Self::ALL), which is also its index
+    /// into a `Vec3`.
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_transform_tools::GizmoAxis;
+    /// let scale = Vec3::new(1.0, 2.0, 3.0);
+    /// assert_eq!(scale[GizmoAxis::Z.index()], 3.0);
+    /// ```
+    pub const fn index(self) -> usize {
+        match self {
+            GizmoAxis::X => 0,
+            GizmoAxis::Y => 1,
+            GizmoAxis::Z => 2,
+        }
+    }
+
+    /// The axis at `index` in [`ALL`](Self::ALL), if there is one.
+    ///
+    /// ```
+    /// # use bevy_transform_tools::GizmoAxis;
+    /// assert_eq!(GizmoAxis::from_index(1), Some(GizmoAxis::Y));
+    /// assert_eq!(GizmoAxis::from_index(3), None);
+    /// ```
+    pub const fn from_index(index: usize) -> Option<Self> {
+        match index {
+            0 => Some(GizmoAxis::X),
+            1 => Some(GizmoAxis::Y),
+            2 => Some(GizmoAxis::Z),
+            _ => None,
+        }
+    }
+
+    /// The other two axes, in order. They bound the plane this axis is the
+    /// normal of, the plane a [`GizmoOperation::TranslatePlane`] handle on
+    /// this axis moves in.
+    ///
+    /// ```
+    /// # use bevy_transform_tools::GizmoAxis;
+    /// assert_eq!(GizmoAxis::Y.others(), (GizmoAxis::X, GizmoAxis::Z));
+    /// ```
+    pub const fn others(self) -> (GizmoAxis, GizmoAxis) {
+        match self {
+            GizmoAxis::X => (GizmoAxis::Y, GizmoAxis::Z),
+            GizmoAxis::Y => (GizmoAxis::X, GizmoAxis::Z),
+            GizmoAxis::Z => (GizmoAxis::X, GizmoAxis::Y),
+        }
+    }
+
+    /// The axis `v` points most along, either way, or `None` for a zero or
+    /// non-finite vector. Ties go to the earlier axis.
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_transform_tools::GizmoAxis;
+    /// assert_eq!(GizmoAxis::from_vec3(Vec3::new(0.2, -0.9, 0.3)), Some(GizmoAxis::Y));
+    /// assert_eq!(GizmoAxis::from_vec3(Vec3::ZERO), None);
+    /// ```
+    pub fn from_vec3(v: Vec3) -> Option<Self> {
+        if !v.is_finite() || v == Vec3::ZERO {
+            return None;
+        }
+        let v = v.abs();
+        Some(if v.x >= v.y && v.x >= v.z {
+            GizmoAxis::X
+        } else if v.y >= v.z {
+            GizmoAxis::Y
+        } else {
+            GizmoAxis::Z
+        })
+    }
+
+    /// The axis's idle color in the default [`AxisColors`]: red, green, or
+    /// blue. Read a style's own colors through
+    /// [`AxisColors::for_axis`] to follow a customized look.
+    ///
+    /// ```
+    /// # use bevy_transform_tools::{AxisColors, GizmoAxis};
+    /// assert_eq!(GizmoAxis::X.color(), AxisColors::default().x.idle);
+    /// ```
+    pub fn color(self) -> Color {
+        AxisColors::default().for_axis(self).idle
+    }
+}
+
+impl fmt::Display for GizmoAxis {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GizmoAxis::X => f.write_str("X"),
+            GizmoAxis::Y => f.write_str("Y"),
+            GizmoAxis::Z => f.write_str("Z"),
+        }
+    }
 }
 
 /// The type of operation being performed by the gizmo.
 ///
 /// This distinguishes between different manipulation modes like axis-constrained
 /// translation vs planar translation, or per-axis scaling vs uniform scaling.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
 pub enum GizmoOperation {
     /// Translation constrained to a single axis.
     TranslateAxis,
@@ -146,10 +788,375 @@ pub enum GizmoOperation {
     TranslatePlane,
     /// Rotation around a single axis.
     Rotate,
+    /// Rotation around the camera's view direction, from the screen-facing
+    /// outer ring.
+    RotateView,
+    /// Free rotation from dragging inside the rotation rings, away from the
+    /// other handles, with the cursor rolling a trackball (arcball) sphere.
+    RotateTrackball,
     /// Scaling along a single axis.
     ScaleAxis,
     /// Uniform scaling on all axes simultaneously.
     ScaleUniform,
+    /// Translation in the plane through the origin that faces the camera,
+    /// from the screen-facing circle at the origin.
+    TranslateView,
+    /// Dragging one end of a [`GizmoEndpoints`] target while the other end
+    /// stays fixed.
+    Endpoint {
+        /// Which end is dragged.
+        end: EndpointSign,
+    },
+    /// The origin dot, which is clicked rather than dragged, see
+    /// [`TransformGizmoStyle::origin_click_action`]. Its axis is always
+    /// [`GizmoAxis::X`].
+    OriginDot,
+}
+
+impl GizmoOperation {
+    /// Whether the operation moves the target without turning or scaling
+    /// it: the axis, plane, and view translate handles.
+    ///
+    /// Endpoint drags, which stretch the target, and the origin dot are
+    /// none of translate, rotate, or scale.
+    ///
+    /// ```
+    /// # use bevy_transform_tools::GizmoOperation;
+    /// assert!(GizmoOperation::TranslatePlane.is_translate());
+    /// assert!(!GizmoOperation::OriginDot.is_translate());
+    /// ```
+    pub const fn is_translate(self) -> bool {
+        matches!(
+            self,
+            GizmoOperation::TranslateAxis
+                | GizmoOperation::TranslatePlane
+                | GizmoOperation::TranslateView
+        )
+    }
+
+    /// Whether the operation turns the target: the axis rings, the
+    /// screen-facing ring, and the trackball.
+    ///
+    /// ```
+    /// # use bevy_transform_tools::GizmoOperation;
+    /// assert!(GizmoOperation::RotateTrackball.is_rotate());
+    /// ```
+    pub const fn is_rotate(self) -> bool {
+        matches!(
+            self,
+            GizmoOperation::Rotate | GizmoOperation::RotateView | GizmoOperation::RotateTrackball
+        )
+    }
+
+    /// Whether the operation scales the target, per axis or uniformly.
+    ///
+    /// ```
+    /// # use bevy_transform_tools::GizmoOperation;
+    /// assert!(GizmoOperation::ScaleUniform.is_scale());
+    /// ```
+    pub const fn is_scale(self) -> bool {
+        matches!(
+            self,
+            GizmoOperation::ScaleAxis | GizmoOperation::ScaleUniform
+        )
+    }
+
+    /// The mode the operation belongs to. Endpoint drags and the origin dot,
+    /// which move the target's position, count as
+    /// [`Translate`](TransformGizmoMode::Translate).
+    ///
+    /// ```
+    /// # use bevy_transform_tools::{GizmoOperation, TransformGizmoMode};
+    /// assert_eq!(GizmoOperation::RotateView.mode(), TransformGizmoMode::Rotate);
+    /// assert_eq!(GizmoOperation::OriginDot.mode(), TransformGizmoMode::Translate);
+    /// ```
+    pub const fn mode(self) -> TransformGizmoMode {
+        if self.is_rotate() {
+            TransformGizmoMode::Rotate
+        } else if self.is_scale() {
+            TransformGizmoMode::Scale
+        } else {
+            TransformGizmoMode::Translate
+        }
+    }
+}
+
+/// One of the two ends of a [`GizmoEndpoints`] target, along the negative
+/// or positive direction of its long axis.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+pub enum EndpointSign {
+    /// The end at `-local_axis * half_length`.
+    Negative,
+    /// The end at `+local_axis * half_length`.
+    Positive,
+}
+
+impl EndpointSign {
+    /// `-1.0` or `1.0`.
+    pub fn signum(self) -> f32 {
+        match self {
+            EndpointSign::Negative => -1.0,
+            EndpointSign::Positive => 1.0,
+        }
+    }
+
+    /// The other end.
+    pub fn opposite(self) -> Self {
+        match self {
+            EndpointSign::Negative => EndpointSign::Positive,
+            EndpointSign::Positive => EndpointSign::Negative,
+        }
+    }
+}
+
+/// Describes a target defined by two endpoints (a beam, pipe, or rope
+/// segment) and adds a handle at each end.
+///
+/// Dragging an end solves the target's translation, rotation, and scale
+/// along `local_axis` so that end follows the cursor while the other stays
+/// fixed. The ends sit at `±local_axis * half_length_basis` in the target's
+/// local space, so their distance at scale `1.0` is twice
+/// `half_length_basis`.
+///
+/// ```ignore
+/// // A cylinder mesh is 2 units tall along Y by default.
+/// commands.spawn((
+///     Mesh3d(meshes.add(Cylinder::new(0.1, 2.0))),
+///     Transform::default(),
+///     TransformGizmoTarget,
+///     GizmoEndpoints::new(GizmoAxis::Y, 1.0),
+/// ));
+/// ```
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct GizmoEndpoints {
+    /// The target's long axis, in local space.
+    pub local_axis: GizmoAxis,
+    /// Half the distance between the ends at scale `1.0`.
+    pub half_length_basis: f32,
+}
+
+impl GizmoEndpoints {
+    /// Creates an endpoint description.
+    pub fn new(local_axis: GizmoAxis, half_length_basis: f32) -> Self {
+        Self {
+            local_axis,
+            half_length_basis,
+        }
+    }
+
+    /// Position of `end` in the target's local space.
+    pub fn local_position(&self, end: EndpointSign) -> Vec3 {
+        self.local_axis.to_vec3() * (end.signum() * self.half_length_basis)
+    }
+}
+
+/// Stable identity of a single gizmo handle: which target, operation, and axis.
+///
+/// The axis of [`GizmoOperation::ScaleUniform`],
+/// [`GizmoOperation::RotateView`], [`GizmoOperation::RotateTrackball`],
+/// [`GizmoOperation::TranslateView`], and [`GizmoOperation::OriginDot`] is
+/// always [`GizmoAxis::X`], and the axis of [`GizmoOperation::Endpoint`] is
+/// the target's [`GizmoEndpoints::local_axis`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GizmoHandleId {
+    /// The target the handle belongs to.
+    pub target: Entity,
+    /// The operation the handle performs.
+    pub op: GizmoOperation,
+    /// The axis the handle operates on (the plane normal for planar handles).
+    pub axis: GizmoAxis,
+}
+
+impl GizmoHandleId {
+    /// Creates a handle id.
+    pub fn new(target: Entity, op: GizmoOperation, axis: GizmoAxis) -> Self {
+        Self { target, op, axis }
+    }
+}
+
+impl fmt::Display for GizmoHandleId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.op {
+            GizmoOperation::ScaleUniform
+            | GizmoOperation::RotateView
+            | GizmoOperation::RotateTrackball
+            | GizmoOperation::TranslateView
+            | GizmoOperation::OriginDot => {
+                write!(f, "{:?} on {}", self.op, self.target)
+            }
+            op => write!(f, "{:?} {} on {}", op, self.axis, self.target),
+        }
+    }
+}
+
+/// A gizmo handle hit by a ray, as returned by
+/// [`pick_gizmo_handle`](crate::pick_gizmo_handle).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GizmoHit {
+    /// The operation the handle performs.
+    pub op: GizmoOperation,
+    /// The axis the handle operates on, as in [`GizmoHandleId::axis`].
+    pub axis: GizmoAxis,
+    /// Distance along the ray to the hit, in units of the ray's direction.
+    pub distance: f32,
+    /// World-space point where the ray hit the handle.
+    ///
+    /// For a ray that only passed within a handle's hit radius, this is
+    /// where it entered that padded volume rather than a point on the drawn
+    /// shape.
+    pub point: Vec3,
+}
+
+/// Where a gizmo handle appears on screen.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GizmoHandleScreen {
+    /// Logical position of the handle's center on the camera's render
+    /// target, in the same coordinates as the cursor.
+    pub position: Vec2,
+    /// Approximate on-screen radius of the handle's clickable area, in pixels.
+    pub radius: f32,
+    /// Whether the handle's center is in front of the camera and inside the
+    /// viewport. Gizmos draw over scene geometry, so this is not an occlusion
+    /// test against meshes.
+    pub visible: bool,
+}
+
+/// Screen-space layout of every gizmo handle, for tutorial and onboarding UIs.
+///
+/// Only filled while [`TransformGizmoConfig::handle_screen_info`] is set.
+/// Updated in [`TransformGizmoSystems::Hover`] from the same handle layout the
+/// hover test uses, so a handle listed here is clickable where it is listed.
+#[derive(Resource, Clone, Debug, Default)]
+pub struct GizmoHandleScreenInfo {
+    /// The camera the positions were projected through.
+    pub camera: Option<Entity>,
+    /// Screen info for each handle.
+    pub handles: HashMap<GizmoHandleId, GizmoHandleScreen>,
+}
+
+impl GizmoHandleScreenInfo {
+    /// Screen info for `handle`, if it is currently laid out.
+    pub fn get(&self, handle: GizmoHandleId) -> Option<&GizmoHandleScreen> {
+        self.handles.get(&handle)
+    }
+}
+
+/// Where the gizmo and its hovered handle are on screen, for tooltips.
+///
+/// Unlike [`GizmoHandleScreenInfo`] this is always filled, but only for the
+/// active target's origin and the one handle under the cursor. Updated in
+/// [`TransformGizmoSystems::Hover`] after the hover test, and only changed
+/// when a value does. Positions are logical pixels on the camera's render
+/// target, in the same coordinates as the cursor.
+#[derive(Resource, Clone, Debug, Default, PartialEq)]
+pub struct GizmoScreenInfo {
+    /// The camera the positions were projected through, `None` while the
+    /// gizmo is disabled.
+    pub camera: Option<Entity>,
+    /// The active target's gizmo origin, or `None` without an active target
+    /// or when the origin is behind the camera.
+    pub origin: Option<Vec2>,
+    /// The hovered handle, or the one being dragged, and where it appears.
+    /// `None` when no handle is hovered or its center is behind the camera.
+    pub hovered: Option<(GizmoHandleId, GizmoHandleScreen)>,
+}
+
+/// Written when a drag starts on a handle.
+///
+/// With [`TransformGizmoConfig::drag_threshold_pixels`] set, that is once
+/// the cursor has moved past the threshold, not when the handle is pressed.
+#[derive(Message, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GizmoDragStarted {
+    /// The handle being dragged.
+    pub handle: GizmoHandleId,
+}
+
+/// Written when the origin dot of `target` is clicked without dragging,
+/// after its [`TransformGizmoStyle::origin_click_action`] is applied.
+#[derive(Message, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GizmoOriginClicked {
+    /// The target whose origin dot was clicked.
+    pub target: Entity,
+}
+
+/// Written when a handle is pressed and released without the cursor moving
+/// past [`TransformGizmoConfig::drag_threshold_pixels`]. The target is left
+/// as it was, and no [`GizmoDragStarted`] or [`GizmoDragEnded`] is written.
+#[derive(Message, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GizmoHandleClicked {
+    /// The handle that was clicked.
+    pub handle: GizmoHandleId,
+}
+
+/// Written when a drag starts on a copy of the grabbed target, see
+/// [`GizmoDragInput::duplicate_modifiers`].
+///
+/// The copy gets every component of `source` that implements `Clone` or
+/// `Reflect`, and copies of its children, except [`GizmoActive`] and
+/// [`GizmoStableId`](crate::GizmoStableId). It is spawned before the drag's
+/// first frame, so later systems can fix it up, for example to rename it or
+/// record its creation for undo. The drag's messages name the copy.
+#[derive(Message, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GizmoDuplicated {
+    /// The target whose handle was grabbed, left in place.
+    pub source: Entity,
+    /// The new entity the drag moves.
+    pub copy: Entity,
+}
+
+/// Written by every drag frame that changed the edited transform.
+///
+/// Deltas are from the previous frame's value to this frame's, in the space
+/// the drag edits: the target's parent for `Transform`, or the target itself
+/// for secondary channels.
+#[derive(Message, Clone, Copy, Debug, PartialEq)]
+pub struct GizmoDragging {
+    /// The handle being dragged.
+    pub handle: GizmoHandleId,
+    /// Change in translation.
+    pub delta_translation: Vec3,
+    /// Change in rotation, applied on the left of the previous rotation.
+    pub delta_rotation: Quat,
+    /// Per-axis ratio of the new scale to the previous one, `1.0` on axes
+    /// whose previous scale was zero.
+    pub delta_scale: Vec3,
+    /// World-space displacement of the target's origin since the drag
+    /// started, see [`TransformGizmoDrag::origin_delta`].
+    pub origin_delta: Vec3,
+    /// World-space displacement of the visual pivot since the drag started,
+    /// see [`TransformGizmoDrag::pivot_delta`].
+    pub pivot_delta: Vec3,
+}
+
+/// Written when a drag ends, whether released or dropped (for example by a
+/// channel switch or the target being despawned).
+///
+/// Both transforms are values of the edited transform (the target's
+/// `Transform` or its secondary channel value), so an undo entry can restore
+/// `initial_transform`. If the target is gone, `final_transform` is the last
+/// value the drag wrote.
+#[derive(Message, Clone, Copy, Debug, PartialEq)]
+pub struct GizmoDragEnded {
+    /// The handle that was dragged.
+    pub handle: GizmoHandleId,
+    /// The edited transform when the drag started.
+    pub initial_transform: Transform,
+    /// The edited transform when the drag ended.
+    pub final_transform: Transform,
+    /// World-space displacement of the target's origin over the whole drag,
+    /// see [`TransformGizmoDrag::origin_delta`]. Zero once a cancel has
+    /// restored `initial_transform`.
+    pub origin_delta: Vec3,
+    /// World-space displacement of the visual pivot over the whole drag, see
+    /// [`TransformGizmoDrag::pivot_delta`].
+    pub pivot_delta: Vec3,
+    /// Whether the drag was cancelled, so it is not recorded in history.
+    /// Cancelling with [`TransformGizmoKeybinds::cancel`] restores
+    /// `initial_transform`; [`ExternalChangePolicy::Abort`] leaves
+    /// `final_transform` where the external change put it.
+    pub cancelled: bool,
 }
 
 /// Information about an active drag operation.
@@ -160,6 +1167,20 @@ pub enum GizmoOperation {
 pub struct TransformGizmoDrag {
     /// The entity being manipulated.
     pub target: Entity,
+    /// The channel being edited; the drag ends if the target switches channel.
+    pub channel: GizmoEditChannel,
+    /// The [`TransformGizmoCamera`] the drag was started from. Its view keeps
+    /// driving the drag until it ends, even once the cursor crosses into
+    /// another camera's viewport. `None` for drags started by a custom
+    /// picker through [`begin_drag_from_ray`](crate::begin_drag_from_ray).
+    pub camera: Option<Entity>,
+    /// The space the drag's axes were taken in, which the gizmo of the
+    /// target keeps being laid out in until the drag ends, whatever
+    /// [`TransformGizmoState::space`] is switched to meanwhile. The view
+    /// space is held as the camera's rotation at drag start. `None` follows
+    /// the state's space, as drags from
+    /// [`begin_drag_from_ray`](crate::begin_drag_from_ray) do.
+    pub space: Option<TransformGizmoSpace>,
     /// The type of operation being performed.
     pub op: GizmoOperation,
     /// The primary axis involved in the operation.
@@ -180,6 +1201,11 @@ pub struct TransformGizmoDrag {
     pub plane_axis1: GizmoAxis,
     /// Second axis for planar snapping.
     pub plane_axis2: GizmoAxis,
+    /// Signs that turned each translate axis towards the camera when the
+    /// drag started. The plane handle keeps facing that way, and
+    /// `plane_dir1`/`plane_dir2` are flipped by them, even if the camera
+    /// moves mid-drag.
+    pub plane_signs: Vec3,
     /// The target's world-space translation when the drag started.
     pub start_translation: Vec3,
     /// The target's world-space rotation when the drag started.
@@ -188,26 +1214,448 @@ pub struct TransformGizmoDrag {
     pub start_scale: Vec3,
     /// The target's parent-local translation when the drag started.
     pub start_local_translation: Vec3,
+    /// The target's parent-local rotation when the drag started.
+    pub start_local_rotation: Quat,
     /// The target's parent-local scale when the drag started.
     pub start_local_scale: Vec3,
+    /// The target's local transform when the drag started.
+    ///
+    /// Unlike the `start_*` fields this is never rebased (for example by
+    /// history scrubbing), so it always holds the value to restore on cancel.
+    pub initial_transform: Transform,
     /// Initial parameter value (distance or angle) at drag start.
     pub start_t: f32,
-    /// Initial vector from origin to hit point (for planar/rotation ops).
+    /// Initial vector from origin to hit point (for planar, rotation, and
+    /// uniform scale ops).
     pub start_vector: Vec3,
+    /// Whether a rotation drag turns with the cursor's angle around the
+    /// origin on screen instead of its position on the ring. Chosen when the
+    /// drag starts, for rings seen nearly edge-on.
+    pub screen_rotation: bool,
+    /// Rotation in radians since `start_t`, unwrapped so it keeps counting
+    /// past half a turn in either direction.
+    pub rotation_angle: f32,
+    /// Radius of the trackball sphere a trackball drag rolls, in world
+    /// units.
+    pub trackball_radius: f32,
+    /// Multiplier on the angle a trackball drag turns, see
+    /// [`TransformGizmoStyle::rotate_trackball_sensitivity`].
+    pub trackball_sensitivity: f32,
+    /// World-space visual pivot captured at drag start, if the target has a
+    /// [`GizmoVisualPivot`]. Rotation and scale are applied about this point.
+    pub pivot: Option<Vec3>,
+    /// World-space displacement of the target's origin since the drag started.
+    ///
+    /// Rotation and scale drags swing the origin around a visual pivot, and
+    /// leave it, and this, at zero without one.
+    pub origin_delta: Vec3,
+    /// World-space displacement of the visual pivot since the drag started.
+    ///
+    /// Equal to `origin_delta` when the target has no visual pivot. Rotation
+    /// and scale drags keep the pivot in place, so this stays zero for them.
+    pub pivot_delta: Vec3,
+    /// Rolling buffer of transforms applied during this drag.
+    pub history: DragHistory,
+    /// Global transform of the space the target is edited in (its parent,
+    /// or the owner of a secondary channel) when the drag started.
+    pub start_parent: Option<GlobalTransform>,
+    /// Raw cursor offset from `origin` on the interaction plane last frame.
+    pub last_cursor_offset: Vec3,
+    /// Cursor offset after precision scaling, accumulated frame by frame so
+    /// changes in precision never make the target jump.
+    pub precise_cursor_offset: Vec3,
+    /// Whether this frame's translation was stopped or deflected by a
+    /// blocking contact.
+    pub blocked: bool,
+    /// The axis of the component this frame's edit was pinned at, by the
+    /// target's [`GizmoLimits`].
+    pub limit_hit: Option<GizmoAxis>,
+    /// The target's [`GizmoEndpoints`] for endpoint drags.
+    pub endpoints: Option<GizmoEndpoints>,
+    /// Parent-local position of the end held in place during an endpoint
+    /// drag.
+    pub fixed_endpoint: Vec3,
+    /// The edited transform as of the latest drag frame.
+    pub last_transform: Transform,
+    /// Whether the target is a [`GizmoExternalDriver`](crate::GizmoExternalDriver)
+    /// whose edits are published as
+    /// [`GizmoTransformRequest`](crate::GizmoTransformRequest)s. Its
+    /// transforms are in world space and `start_parent` is `None`.
+    pub external: bool,
+    /// Whether the drag is paused by
+    /// [`DragInterruptPolicy::Pause`]. It resumes from the target's current
+    /// transform once the cursor is back.
+    pub paused: bool,
+    /// Where the handle was pressed, in logical pixels, while the cursor
+    /// has not yet moved past
+    /// [`TransformGizmoConfig::drag_threshold_pixels`]. The target is not
+    /// edited until then.
+    pub pending_from: Option<Vec2>,
+    /// Where the drag has snapped its target onto another entity, while
+    /// [`AlignSnapSettings::modifier`] is held.
+    pub aligned: Option<GizmoAlignLock>,
 }
 
-/// Global state for the transform gizmo system.
+/// An align drag's snap onto another entity, see
+/// [`TransformGizmoDrag::aligned`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GizmoAlignLock {
+    /// The entity the target snapped onto.
+    pub entity: Entity,
+    /// World position the target snapped to.
+    pub position: Vec3,
+    /// World position the drag would have put the target at without the
+    /// snap.
+    pub unsnapped: Vec3,
+}
+
+impl TransformGizmoDrag {
+    /// The handle being dragged.
+    pub fn handle(&self) -> GizmoHandleId {
+        GizmoHandleId::new(self.target, self.op, self.axis)
+    }
+
+    /// Signed world-space distance a translate axis drag has moved the
+    /// gizmo along `axis_dir`, for a HUD to print. `None` for other
+    /// operations.
+    pub fn axis_distance(&self) -> Option<f32> {
+        (self.op == GizmoOperation::TranslateAxis).then(|| self.pivot_delta.dot(self.axis_dir))
+    }
+
+    /// Signed world-space distances a plane or view translate drag has
+    /// moved the gizmo along `plane_dir1` and `plane_dir2`, for a HUD to
+    /// print. `None` for other operations.
+    pub fn plane_distance(&self) -> Option<Vec2> {
+        matches!(
+            self.op,
+            GizmoOperation::TranslatePlane | GizmoOperation::TranslateView
+        )
+        .then(|| {
+            Vec2::new(
+                self.pivot_delta.dot(self.plane_dir1),
+                self.pivot_delta.dot(self.plane_dir2),
+            )
+        })
+    }
+
+    /// Whether the handle is pressed but the cursor has not yet moved past
+    /// [`TransformGizmoConfig::drag_threshold_pixels`].
+    pub fn is_pending(&self) -> bool {
+        self.pending_from.is_some()
+    }
+
+    /// Total change made by the drag so far, as of its latest frame.
+    ///
+    /// Measured from the `start_*` fields, so it restarts when the drag is
+    /// rebased, for example by history scrubbing or an axis key.
+    pub fn delta(&self) -> GizmoDelta {
+        match self.op {
+            GizmoOperation::TranslateAxis
+            | GizmoOperation::TranslatePlane
+            | GizmoOperation::TranslateView
+            | GizmoOperation::Endpoint { .. }
+            | GizmoOperation::OriginDot => GizmoDelta::Translation(self.origin_delta),
+            GizmoOperation::Rotate | GizmoOperation::RotateView => {
+                // The twist is within half a turn; the drag's own unwrapped
+                // angle says how many whole turns to add.
+                let twist = match self.start_parent {
+                    // Sheared targets have no world rotation to compare, so
+                    // measure the turn about the matching axis in parent space.
+                    Some(parent) if has_non_uniform_scale(&parent) => {
+                        let (axis, sign) = axis_in_parent(&parent, self.axis_dir);
+                        let turned =
+                            self.last_transform.rotation * self.start_local_rotation.inverse();
+                        twist_angle(turned, axis) * sign
+                    }
+                    parent => {
+                        let parent = parent.map_or(Quat::IDENTITY, |p| p.rotation());
+                        let turned =
+                            parent * self.last_transform.rotation * self.start_rotation.inverse();
+                        twist_angle(turned, self.axis_dir)
+                    }
+                };
+                let turns = ((self.rotation_angle - twist) / std::f32::consts::TAU).round();
+                GizmoDelta::Rotation {
+                    axis: self.axis_dir,
+                    angle: twist + turns * std::f32::consts::TAU,
+                }
+            }
+            GizmoOperation::RotateTrackball => {
+                let parent = self.start_parent.map_or(Quat::IDENTITY, |p| p.rotation());
+                let turned = parent * self.last_transform.rotation * self.start_rotation.inverse();
+                let (axis, angle) = turned.to_axis_angle();
+                GizmoDelta::Rotation { axis, angle }
+            }
+            GizmoOperation::ScaleAxis | GizmoOperation::ScaleUniform => GizmoDelta::Scale(
+                scale_ratio(self.last_transform.scale, self.start_local_scale),
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+impl TransformGizmoDrag {
+    /// A world-space X translation drag of a placeholder target from
+    /// `start`, for tests that drive a drag without hit testing a gizmo.
+    pub(crate) fn translate_x(start: Transform) -> Self {
+        Self {
+            target: Entity::PLACEHOLDER,
+            channel: GizmoEditChannel::Transform,
+            camera: None,
+            space: None,
+            op: GizmoOperation::TranslateAxis,
+            axis: GizmoAxis::X,
+            origin: start.translation,
+            axis_dir: Vec3::X,
+            plane_normal: Vec3::Z,
+            plane_origin: start.translation,
+            plane_dir1: Vec3::ZERO,
+            plane_dir2: Vec3::ZERO,
+            plane_axis1: GizmoAxis::X,
+            plane_axis2: GizmoAxis::Y,
+            plane_signs: Vec3::ONE,
+            start_translation: start.translation,
+            start_rotation: start.rotation,
+            start_scale: start.scale,
+            start_local_translation: start.translation,
+            start_local_rotation: start.rotation,
+            start_local_scale: start.scale,
+            initial_transform: start,
+            start_t: 0.0,
+            start_vector: Vec3::ZERO,
+            screen_rotation: false,
+            rotation_angle: 0.0,
+            trackball_radius: 1.0,
+            trackball_sensitivity: 1.0,
+            pivot: None,
+            origin_delta: Vec3::ZERO,
+            pivot_delta: Vec3::ZERO,
+            history: DragHistory::new(256, 1),
+            start_parent: None,
+            last_cursor_offset: Vec3::ZERO,
+            precise_cursor_offset: Vec3::ZERO,
+            blocked: false,
+            limit_hit: None,
+            endpoints: None,
+            fixed_endpoint: Vec3::ZERO,
+            last_transform: start,
+            external: false,
+            paused: false,
+            pending_from: None,
+            aligned: None,
+        }
+    }
+}
+
+/// Total change made by the active drag, see
+/// [`TransformGizmoState::drag_delta`].
 ///
-/// This resource tracks the current mode, which entity is selected, what's
+/// Displays as a short readout such as `Δx = 1.50  Δy = 0.00  Δz = 0.00`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GizmoDelta {
+    /// World-space displacement of the target's origin.
+    Translation(Vec3),
+    /// Rotation around a world-space axis, in radians. Not wrapped, so a
+    /// drag that spun one and a half turns reports `3π`.
+    Rotation {
+        /// Unit axis of the rotation.
+        axis: Vec3,
+        /// Signed angle in radians.
+        angle: f32,
+    },
+    /// Per-axis ratio of the current local scale to the starting one.
+    Scale(Vec3),
+}
+
+impl GizmoDelta {
+    /// World-space displacement, or zero for a rotation or scale.
+    pub fn translation(&self) -> Vec3 {
+        match *self {
+            GizmoDelta::Translation(offset) => offset,
+            _ => Vec3::ZERO,
+        }
+    }
+
+    /// Rotation as a quaternion, or identity for a translation or scale.
+    /// Whole turns cancel out, unlike in [`GizmoDelta::Rotation`]'s angle.
+    pub fn rotation(&self) -> Quat {
+        match *self {
+            GizmoDelta::Rotation { axis, angle } => {
+                Quat::from_axis_angle(axis, angle.rem_euclid(std::f32::consts::TAU))
+            }
+            _ => Quat::IDENTITY,
+        }
+    }
+
+    /// Per-axis scale factor, or one for a translation or rotation.
+    pub fn scale(&self) -> Vec3 {
+        match *self {
+            GizmoDelta::Scale(factor) => factor,
+            _ => Vec3::ONE,
+        }
+    }
+}
+
+impl fmt::Display for GizmoDelta {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GizmoDelta::Translation(d) => {
+                write!(f, "Δx = {:.2}  Δy = {:.2}  Δz = {:.2}", d.x, d.y, d.z)
+            }
+            GizmoDelta::Rotation { angle, .. } => write!(f, "Δθ = {:.1}°", angle.to_degrees()),
+            GizmoDelta::Scale(s) => {
+                write!(f, "sx = {:.2}  sy = {:.2}  sz = {:.2}", s.x, s.y, s.z)
+            }
+        }
+    }
+}
+
+/// Fixed-capacity ring buffer of transforms applied during a drag.
+///
+/// The buffer is allocated once when the drag starts and then overwrites its
+/// oldest entries, so recording never allocates mid-drag. Stepping back moves
+/// a read cursor through the buffer; recording a new transform after stepping
+/// back discards the entries that were stepped over.
+#[derive(Debug, Clone, Default)]
+pub struct DragHistory {
+    samples: Vec<Transform>,
+    capacity: usize,
+    head: usize,
+    len: usize,
+    offset: usize,
+    interval: u32,
+    pending: u32,
+}
+
+impl DragHistory {
+    /// Creates an empty buffer holding at most `capacity` transforms, keeping
+    /// one of every `interval` recorded changes.
+    pub fn new(capacity: usize, interval: u32) -> Self {
+        Self {
+            samples: Vec::with_capacity(capacity),
+            capacity,
+            head: 0,
+            len: 0,
+            offset: 0,
+            interval: interval.max(1),
+            pending: 0,
+        }
+    }
+
+    /// Number of transforms currently stored.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if no transforms are stored.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Maximum number of transforms kept.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// How many steps back from the newest entry the scrub cursor sits.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Returns the transform `steps_back` entries before the newest one.
+    pub fn get(&self, steps_back: usize) -> Option<Transform> {
+        if steps_back >= self.len {
+            return None;
+        }
+        let index = (self.head + self.capacity - 1 - steps_back) % self.capacity;
+        self.samples.get(index).copied()
+    }
+
+    /// Records an applied transform, honouring the sampling interval.
+    pub fn record(&mut self, transform: Transform) {
+        if self.capacity == 0 {
+            return;
+        }
+        self.pending += 1;
+        if self.pending < self.interval && self.offset == 0 {
+            return;
+        }
+        self.pending = 0;
+        self.push(transform);
+    }
+
+    /// Records a transform unconditionally, ignoring the sampling interval.
+    pub fn push(&mut self, transform: Transform) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.offset > 0 {
+            // Drop the entries that were stepped over.
+            self.head = (self.head + self.capacity - self.offset) % self.capacity;
+            self.len -= self.offset;
+            self.offset = 0;
+        }
+        if self.head < self.samples.len() {
+            self.samples[self.head] = transform;
+        } else {
+            self.samples.push(transform);
+        }
+        self.head = (self.head + 1) % self.capacity;
+        self.len = (self.len + 1).min(self.capacity);
+    }
+
+    /// Moves the scrub cursor one entry back and returns that transform.
+    pub fn step_back(&mut self) -> Option<Transform> {
+        if self.offset + 1 >= self.len {
+            return None;
+        }
+        self.offset += 1;
+        self.get(self.offset)
+    }
+
+    /// Moves the scrub cursor one entry forward and returns that transform.
+    pub fn step_forward(&mut self) -> Option<Transform> {
+        if self.offset == 0 {
+            return None;
+        }
+        self.offset -= 1;
+        self.get(self.offset)
+    }
+
+    /// Rewrites every stored transform in place, for example when the target
+    /// moves into a different parent space mid-drag.
+    pub(crate) fn remap(&mut self, mut f: impl FnMut(Transform) -> Transform) {
+        for sample in &mut self.samples {
+            *sample = f(*sample);
+        }
+    }
+}
+
+/// Global state for the transform gizmo system.
+///
+/// This resource tracks the current mode, which entity is selected, what's
 /// being hovered, and any active drag operation.
-#[derive(Resource, Clone, Default)]
+#[derive(Resource, Clone)]
 pub struct TransformGizmoState {
-    /// Current editing mode (Translate/Rotate/Scale) for UI display.
+    /// Master switch for the gizmo. While `false` nothing is hovered,
+    /// dragged, clicked, or drawn, and those systems do not run at all. A
+    /// drag in progress when it is turned off ends where it is, with a
+    /// [`GizmoDragEnded`]. The selection, mode, and space are kept, so the
+    /// gizmo comes back as it was.
+    pub enabled: bool,
+    /// Current editing mode (Translate/Rotate/Scale) for UI display. Also
+    /// picks what [`TransformGizmoConfig::keyboard_nudge`] changes.
     pub mode: TransformGizmoMode,
-    /// Coordinate space for gizmo axes (World or Local).
+    /// Coordinate space for gizmo axes. Switching it during a drag takes
+    /// effect once the drag ends, see [`TransformGizmoDrag::space`].
     pub space: TransformGizmoSpace,
-    /// The currently active target entity, if any.
+    /// The currently active target entity, if any. Follows [`GizmoActive`];
+    /// hover never changes it.
     pub active_target: Option<Entity>,
+    /// The target whose handle is under the cursor, if any. Only differs
+    /// from `active_target` with
+    /// [`TransformGizmoStyle::draw_inactive_targets`].
+    pub hovered_target: Option<Entity>,
     /// The axis currently being hovered, if any.
     pub hovered_axis: Option<GizmoAxis>,
     /// The operation type currently being hovered, if any.
@@ -216,207 +1664,1495 @@ pub struct TransformGizmoState {
     pub drag: Option<TransformGizmoDrag>,
 }
 
-/// Colors for a single gizmo element in different interaction states.
+impl Default for TransformGizmoState {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            mode: TransformGizmoMode::default(),
+            space: TransformGizmoSpace::default(),
+            active_target: None,
+            hovered_target: None,
+            hovered_axis: None,
+            hovered_op: None,
+            drag: None,
+        }
+    }
+}
+
+impl TransformGizmoState {
+    /// The handle under the cursor, if any.
+    pub fn hovered_handle(&self) -> Option<GizmoHandleId> {
+        Some(GizmoHandleId::new(
+            self.hovered_target?,
+            self.hovered_op?,
+            self.hovered_axis?,
+        ))
+    }
+
+    /// Whether the gizmo owns the pointer: a handle is hovered or a drag is
+    /// active.
+    ///
+    /// Camera controllers and UI can skip clicks while this is `true`. Hover
+    /// is updated in `PostUpdate`, so systems in `Update` see the previous
+    /// frame's hover, which is also what the next click will hit.
+    pub fn is_interacting(&self) -> bool {
+        self.is_hovering() || self.is_dragging()
+    }
+
+    /// Whether a handle is being dragged.
+    ///
+    /// ```
+    /// # use bevy_transform_tools::TransformGizmoState;
+    /// let state = TransformGizmoState::default();
+    /// assert!(!state.is_dragging() && !state.is_hovering());
+    /// assert_eq!(state.dragged_entity(), None);
+    /// assert_eq!(state.current_operation(), None);
+    /// ```
+    pub fn is_dragging(&self) -> bool {
+        self.drag.is_some()
+    }
+
+    /// Whether a handle is under the cursor. Hover is not updated during a
+    /// drag, so this keeps its value from when the drag started.
+    pub fn is_hovering(&self) -> bool {
+        self.hovered_op.is_some()
+    }
+
+    /// The space the gizmo of `target` is laid out in: the one its drag
+    /// holds while it is dragged, else [`space`](Self::space).
+    pub fn frame_space(&self, target: Entity) -> TransformGizmoSpace {
+        self.drag
+            .as_ref()
+            .filter(|drag| drag.target == target)
+            .and_then(|drag| drag.space)
+            .unwrap_or(self.space)
+    }
+
+    /// The target of the active drag, if any.
+    pub fn dragged_entity(&self) -> Option<Entity> {
+        self.drag.as_ref().map(|drag| drag.target)
+    }
+
+    /// The operation being dragged, or else the one under the cursor.
+    ///
+    /// ```
+    /// # use bevy_transform_tools::{GizmoAxis, GizmoOperation, TransformGizmoState};
+    /// let state = TransformGizmoState {
+    ///     hovered_op: Some(GizmoOperation::TranslateAxis),
+    ///     hovered_axis: Some(GizmoAxis::X),
+    ///     ..Default::default()
+    /// };
+    /// assert!(state.is_hovering() && !state.is_dragging());
+    /// assert_eq!(state.current_operation(), Some(GizmoOperation::TranslateAxis));
+    /// ```
+    pub fn current_operation(&self) -> Option<GizmoOperation> {
+        self.drag.as_ref().map(|drag| drag.op).or(self.hovered_op)
+    }
+
+    /// Total change made by the active drag so far, if one is active.
+    ///
+    /// Derived on every call from the drag's start values and the transform
+    /// it applied last, so it is `None` as soon as the drag ends or is
+    /// cancelled. See [`TransformGizmoDrag::delta`].
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_transform_tools::{
+    /// #     apply_drag, begin_drag_from_ray, GizmoAxis, GizmoFrame, GizmoOperation,
+    /// #     TransformGizmoSnap, TransformGizmoSpace, TransformGizmoState,
+    /// # };
+    /// let frame = GizmoFrame::new(&GlobalTransform::IDENTITY, TransformGizmoSpace::World)
+    ///     .with_view_dir(Vec3::NEG_Z);
+    /// let ray_at = |x: f32| Ray3d::new(Vec3::new(x, 0.0, 10.0), Dir3::NEG_Z);
+    /// let start = Transform::default();
+    /// let mut drag = begin_drag_from_ray(
+    ///     &ray_at(0.5),
+    ///     GizmoOperation::TranslateAxis,
+    ///     GizmoAxis::X,
+    ///     Entity::PLACEHOLDER,
+    ///     &frame,
+    ///     &GlobalTransform::IDENTITY,
+    ///     &start,
+    /// );
+    /// let mut transform = start;
+    /// apply_drag(&mut drag, &ray_at(2.0), &TransformGizmoSnap::default(), &mut transform);
+    ///
+    /// let state = TransformGizmoState {
+    ///     drag: Some(drag),
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(state.dragged_entity(), Some(Entity::PLACEHOLDER));
+    /// let delta = state.drag_delta().unwrap();
+    /// assert!(delta.translation().abs_diff_eq(Vec3::X * 1.5, 1.0e-5));
+    /// assert_eq!(delta.rotation(), Quat::IDENTITY);
+    /// assert_eq!(delta.scale(), Vec3::ONE);
+    /// ```
+    pub fn drag_delta(&self) -> Option<GizmoDelta> {
+        self.drag.as_ref().map(TransformGizmoDrag::delta)
+    }
+}
+
+/// Request to move a target under a new parent while preserving its world pose.
+///
+/// Processed during [`TransformGizmoSystems::Input`]. The target's local
+/// `Transform` is recomputed against the new parent, and an active drag on
+/// the target is rebased into the new parent space so it continues without a
+/// jump. A [`GizmoReparented`] message is written on success and a
+/// [`GizmoReparentRejected`] message otherwise.
+///
+/// A local `Transform` cannot express shear, so when the new parent (or the
+/// old one) has non-uniform scale combined with rotation the world pose is
+/// approximated by the nearest rotation and per-axis scale.
+#[derive(Message, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GizmoReparentRequest {
+    /// The entity to move.
+    pub target: Entity,
+    /// The new parent, or `None` to make the target a root entity.
+    pub new_parent: Option<Entity>,
+}
+
+/// Written after a [`GizmoReparentRequest`] has been applied.
+#[derive(Message, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GizmoReparented {
+    /// The entity that was moved.
+    pub target: Entity,
+    /// Its parent before the request.
+    pub old_parent: Option<Entity>,
+    /// Its parent after the request.
+    pub new_parent: Option<Entity>,
+}
+
+/// Written when a [`GizmoReparentRequest`] cannot be applied.
+#[derive(Message, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GizmoReparentRejected {
+    /// The entity the request targeted.
+    pub target: Entity,
+    /// The requested parent.
+    pub new_parent: Option<Entity>,
+    /// Why the request was rejected.
+    pub reason: ReparentRejection,
+}
+
+/// Reason a [`GizmoReparentRequest`] was rejected.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReparentRejection {
+    /// The new parent is the target itself or one of its descendants.
+    CycleWithTarget,
+    /// The target or new parent has no `Transform`, or does not exist.
+    MissingEntity,
+}
+
+impl fmt::Display for ReparentRejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReparentRejection::CycleWithTarget => {
+                f.write_str("new parent is the target or one of its descendants")
+            }
+            ReparentRejection::MissingEntity => f.write_str("target or new parent is missing"),
+        }
+    }
+}
+
+/// Colors for a single gizmo element in different interaction states.
+///
+/// Each gizmo handle can have different colors for idle, hovered, and
+/// actively dragged states to provide visual feedback.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug, Reflect)]
+#[reflect(Default)]
+pub struct GizmoStateColors {
+    /// Color when the element is not being interacted with.
+    pub idle: Color,
+    /// Color when the mouse is hovering over the element.
+    pub hover: Color,
+    /// Color when the element is being actively dragged.
+    pub active: Color,
+}
+
+impl GizmoStateColors {
+    /// Creates a new color set with the specified colors.
+    pub fn new(idle: Color, hover: Color, active: Color) -> Self {
+        Self {
+            idle,
+            hover,
+            active,
+        }
+    }
+}
+
+impl Default for GizmoStateColors {
+    fn default() -> Self {
+        Self {
+            idle: Color::srgb(0.8, 0.8, 0.8),
+            hover: Color::srgb(1.0, 1.0, 1.0),
+            active: Color::srgb(1.0, 1.0, 0.8),
+        }
+    }
+}
+
+/// Colors for each axis (X, Y, Z) of a gizmo handle group.
+///
+/// This allows customizing the appearance of translation, rotation, and
+/// scale handles independently for each axis.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug, Reflect)]
+#[reflect(Default)]
+pub struct AxisColors {
+    /// Colors for the X axis (typically red tones).
+    pub x: GizmoStateColors,
+    /// Colors for the Y axis (typically green tones).
+    pub y: GizmoStateColors,
+    /// Colors for the Z axis (typically blue tones).
+    pub z: GizmoStateColors,
+}
+
+impl AxisColors {
+    /// Creates axis colors where all three axes use the same color set.
+    pub fn uniform(colors: GizmoStateColors) -> Self {
+        Self {
+            x: colors.clone(),
+            y: colors.clone(),
+            z: colors,
+        }
+    }
+
+    /// Creates axis colors with distinct colors for each axis.
+    pub fn new(x: GizmoStateColors, y: GizmoStateColors, z: GizmoStateColors) -> Self {
+        Self { x, y, z }
+    }
+
+    /// Returns the colors for a specific axis.
+    pub fn for_axis(&self, axis: GizmoAxis) -> &GizmoStateColors {
+        match axis {
+            GizmoAxis::X => &self.x,
+            GizmoAxis::Y => &self.y,
+            GizmoAxis::Z => &self.z,
+        }
+    }
+}
+
+impl Default for AxisColors {
+    fn default() -> Self {
+        Self {
+            x: GizmoStateColors::new(
+                Color::srgb(1.0, 0.25, 0.25),
+                Color::srgb(1.0, 0.7, 0.7),
+                Color::srgb(1.0, 0.9, 0.9),
+            ),
+            y: GizmoStateColors::new(
+                Color::srgb(0.25, 1.0, 0.25),
+                Color::srgb(0.7, 1.0, 0.7),
+                Color::srgb(0.9, 1.0, 0.9),
+            ),
+            z: GizmoStateColors::new(
+                Color::srgb(0.25, 0.5, 1.0),
+                Color::srgb(0.7, 0.8, 1.0),
+                Color::srgb(0.9, 0.95, 1.0),
+            ),
+        }
+    }
+}
+
+/// Grid drawn while a drag snaps, see [`TransformGizmoStyle::snap_grid`].
+///
+/// Lines and ticks are spaced by the snap step in effect and laid out along
+/// the drag's own axes, so a grid in local space turns with the target.
+/// Lengths are in the same units as the rest of the style.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug, Reflect)]
+#[reflect(Default)]
+pub struct SnapGridStyle {
+    /// Whether to draw the grid.
+    pub enabled: bool,
+    /// Distance from the gizmo origin to the edge of the grid.
+    pub extent: f32,
+    /// Distance from the gizmo origin at which lines start to fade out.
+    /// They are fully transparent at `extent`.
+    pub fade_distance: f32,
+    /// Every how many steps a line or tick is major, counted from the
+    /// position the snap is measured from. `0` draws no major lines.
+    pub major_every: u32,
+    /// Length of the ticks along an axis or around a ring. Major ticks are
+    /// twice as long.
+    pub tick_length: f32,
+    /// Color of minor lines and ticks.
+    pub minor_color: Color,
+    /// Color of major lines and ticks.
+    pub major_color: Color,
+}
+
+impl Default for SnapGridStyle {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            extent: 2.0,
+            fade_distance: 1.0,
+            major_every: 5,
+            tick_length: 0.08,
+            minor_color: Color::srgba(1.0, 1.0, 1.0, 0.15),
+            major_color: Color::srgba(1.0, 1.0, 1.0, 0.4),
+        }
+    }
+}
+
+/// Ruler drawn along a translate drag, see [`TransformGizmoStyle::ruler`].
+///
+/// Ticks run from where the drag started to where the target is now, one
+/// per snap step in effect, or per
+/// [`TransformGizmoStyle::ruler_default_step`] while the drag does not snap.
+/// A plane drag gets a ruler along each of its two axes.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug, Reflect)]
+#[reflect(Default)]
+pub struct RulerStyle {
+    /// Whether to draw the ruler.
+    pub enabled: bool,
+    /// Every how many steps a tick is major, counted from the drag's start.
+    /// `0` draws no major ticks.
+    pub major_every: u32,
+    /// Length of minor ticks. Major ticks are twice as long.
+    pub tick_length: f32,
+    /// Most ticks drawn along each axis. Further ticks are dropped, so a
+    /// long fling does not draw thousands of them.
+    pub max_ticks: u32,
+    /// Color of minor ticks.
+    pub minor_color: Color,
+    /// Color of major ticks.
+    pub major_color: Color,
+}
+
+impl Default for RulerStyle {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            major_every: 5,
+            tick_length: 0.06,
+            max_ticks: 200,
+            minor_color: Color::srgba(1.0, 1.0, 1.0, 0.35),
+            major_color: Color::srgba(1.0, 1.0, 1.0, 0.7),
+        }
+    }
+}
+
+/// Per-axis enable/disable toggles for gizmo handles.
+///
+/// Use this to selectively show or hide individual axis handles.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Debug, Default, Reflect)]
+#[reflect(Default)]
+pub struct AxisToggles {
+    /// Whether the X axis handle is enabled.
+    pub x: bool,
+    /// Whether the Y axis handle is enabled.
+    pub y: bool,
+    /// Whether the Z axis handle is enabled.
+    pub z: bool,
+}
+
+impl AxisToggles {
+    /// Creates toggles with all axes enabled.
+    pub fn all() -> Self {
+        Self {
+            x: true,
+            y: true,
+            z: true,
+        }
+    }
+
+    /// Creates toggles with all axes disabled.
+    pub fn none() -> Self {
+        Self {
+            x: false,
+            y: false,
+            z: false,
+        }
+    }
+
+    /// Returns whether a specific axis is enabled.
+    pub fn enabled(&self, axis: GizmoAxis) -> bool {
+        match axis {
+            GizmoAxis::X => self.x,
+            GizmoAxis::Y => self.y,
+            GizmoAxis::Z => self.z,
+        }
+    }
+}
+
+/// Optional per-axis snapping increments.
+///
+/// When set, transform operations will snap to multiples of the specified
+/// values. Use `None` for an axis to disable snapping on that axis.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Debug, Default, Reflect)]
+#[reflect(Default)]
+pub struct AxisSnap {
+    /// Snap increment for the X axis, or `None` to disable.
+    pub x: Option<f32>,
+    /// Snap increment for the Y axis, or `None` to disable.
+    pub y: Option<f32>,
+    /// Snap increment for the Z axis, or `None` to disable.
+    pub z: Option<f32>,
+}
+
+impl AxisSnap {
+    /// Creates a snap configuration with no snapping on any axis.
+    pub fn none() -> Self {
+        Self {
+            x: None,
+            y: None,
+            z: None,
+        }
+    }
+
+    /// Creates a snap configuration with the same increment on all axes.
+    pub fn uniform(increment: f32) -> Self {
+        Self {
+            x: Some(increment),
+            y: Some(increment),
+            z: Some(increment),
+        }
+    }
+
+    /// Creates a rotation snap configuration with the same increment on all
+    /// axes, given in degrees and stored in radians.
+    ///
+    /// ```
+    /// # use bevy_transform_tools::AxisSnap;
+    /// let snap = AxisSnap::uniform_degrees(15.0);
+    /// assert_eq!(snap.y, Some(15f32.to_radians()));
+    /// ```
+    pub fn uniform_degrees(degrees: f32) -> Self {
+        Self::uniform(degrees.to_radians())
+    }
+
+    /// Returns the snap increment for a specific axis.
+    pub fn get(&self, axis: GizmoAxis) -> Option<f32> {
+        match axis {
+            GizmoAxis::X => self.x,
+            GizmoAxis::Y => self.y,
+            GizmoAxis::Z => self.z,
+        }
+    }
+    fn divided(self, divisor: f32) -> Self {
+        Self {
+            x: self.x.map(|step| step / divisor),
+            y: self.y.map(|step| step / divisor),
+            z: self.z.map(|step| step / divisor),
+        }
+    }
+}
+
+/// Settings for scrubbing back through a drag's transform history.
+///
+/// Off by default. Turn it on to record each drag's transforms and step
+/// through them with the comma and period keys without ending the drag:
+///
+/// ```
+/// # use bevy_transform_tools::{DragHistorySettings, TransformGizmoConfig};
+/// let config = TransformGizmoConfig {
+///     drag_history: DragHistorySettings {
+///         enabled: true,
+///         ..Default::default()
+///     },
+///     ..Default::default()
+/// };
+/// # assert!(config.drag_history.enabled);
+/// ```
+#[derive(Clone, Debug)]
+pub struct DragHistorySettings {
+    /// Whether transforms are recorded and the step keys are active.
+    /// Defaults to `false`.
+    pub enabled: bool,
+    /// Maximum number of transforms kept per drag.
+    pub capacity: usize,
+    /// Record one of every N applied changes.
+    pub sample_interval: u32,
+    /// Key that steps the target back through the history.
+    pub step_back_key: KeyCode,
+    /// Key that steps the target forward again.
+    pub step_forward_key: KeyCode,
+}
+
+impl Default for DragHistorySettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            capacity: 256,
+            sample_interval: 1,
+            step_back_key: KeyCode::Comma,
+            step_forward_key: KeyCode::Period,
+        }
+    }
+}
+
+/// Mouse button and modifier keys that drive gizmo drags.
+///
+/// `begin_drag`, `drag_gizmo`, `end_drag`, and click-to-select all read this,
+/// so they always agree on the button.
+#[derive(Clone, Debug)]
+pub struct GizmoDragInput {
+    /// Button that starts, holds, and ends a drag.
+    pub button: MouseButton,
+    /// If non-empty, a drag only starts while at least one of these keys is
+    /// held.
+    pub required_modifiers: Vec<KeyCode>,
+    /// A drag never starts while any of these keys is held, leaving the
+    /// click to other systems (for example Ctrl+click to multi-select).
+    pub blocked_modifiers: Vec<KeyCode>,
+    /// Holding any of these keys when a translate drag starts drags a copy
+    /// of the target instead, leaving the original in place. See
+    /// [`GizmoDuplicated`]. Empty disables duplication.
+    pub duplicate_modifiers: Vec<KeyCode>,
+}
+
+impl Default for GizmoDragInput {
+    fn default() -> Self {
+        Self {
+            button: MouseButton::Left,
+            required_modifiers: Vec::new(),
+            blocked_modifiers: Vec::new(),
+            duplicate_modifiers: vec![KeyCode::ShiftLeft, KeyCode::ShiftRight],
+        }
+    }
+}
+
+impl GizmoDragInput {
+    /// Whether the held `keys` allow a drag to start.
+    pub fn modifiers_allow(&self, keys: &ButtonInput<KeyCode>) -> bool {
+        (self.required_modifiers.is_empty() || keys.any_pressed(self.required_modifiers.clone()))
+            && !keys.any_pressed(self.blocked_modifiers.clone())
+    }
+
+    /// Whether the held `keys` make a translate drag drag a copy of its
+    /// target.
+    pub fn duplicates(&self, keys: &ButtonInput<KeyCode>) -> bool {
+        keys.any_pressed(self.duplicate_modifiers.clone())
+    }
+}
+
+/// Pointer state consumed by the gizmo interaction systems.
+///
+/// By default the plugin fills this every frame from the cursor of whichever
+/// window it is over and from pen force reported through touch input. Set
+/// [`GizmoPointer::manual`] to feed it yourself, for example from raw winit
+/// tablet events on platforms without native pen support.
+#[derive(Resource, Clone, Debug, Default, PartialEq)]
+pub struct GizmoPointer {
+    /// Cursor position in logical pixels from the window's top-left corner,
+    /// if over a window.
+    pub position: Option<Vec2>,
+    /// The window the cursor is over. `None` matches any window, so a
+    /// manually fed pointer works without setting it.
+    pub window: Option<Entity>,
+    /// Pen pressure in `[0, 1]`, if a pen is in contact.
+    pub pressure: Option<f32>,
+    /// Pen tilt as a screen-space direction (x right, y down, like cursor
+    /// coordinates) whose length is the amount of tilt.
+    pub tilt: Option<Vec2>,
+    /// Mouse wheel movement this frame, in lines; positive scrolls up.
+    pub scroll: f32,
+    /// The cursor is over UI that claims it, such as the settings panel of
+    /// the `egui` feature.
+    /// Hover, new drags, and click selection ignore the pointer while set;
+    /// a drag in progress carries on. Whoever sets it clears it: the plugin
+    /// never touches it.
+    pub captured: bool,
+    /// When `true` the plugin leaves every field untouched.
+    pub manual: bool,
+}
+
+/// Maps pen pressure to the drag precision factor.
+///
+/// The factor is `min_factor + (max_factor - min_factor) * pressure^exponent`,
+/// so light pressure gives fine adjustment and firm pressure normal speed.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PressureCurve {
+    /// Precision factor at zero pressure.
+    pub min_factor: f32,
+    /// Precision factor at full pressure.
+    pub max_factor: f32,
+    /// Curve exponent; values above 1 keep more of the range fine.
+    pub exponent: f32,
+}
+
+impl PressureCurve {
+    /// Precision factor for `pressure`, clamped to `[0, 1]`.
+    pub fn factor(&self, pressure: f32) -> f32 {
+        let pressure = pressure.clamp(0.0, 1.0);
+        self.min_factor + (self.max_factor - self.min_factor) * pressure.powf(self.exponent)
+    }
+}
+
+impl Default for PressureCurve {
+    fn default() -> Self {
+        Self {
+            min_factor: 0.1,
+            max_factor: 1.0,
+            exponent: 1.0,
+        }
+    }
+}
+
+/// Keys that act on an active drag.
+///
+/// Axis keys re-constrain the drag to that axis of the current tool (move,
+/// rotate, or scale); held together with a plane modifier they constrain a
+/// move to the plane perpendicular to the axis. The drag continues from
+/// where the target is, so switching never makes it jump. Set a key to
+/// `None` to disable it.
+#[derive(Resource, Clone, Debug)]
+pub struct TransformGizmoKeybinds {
+    /// Constrain the drag to the X axis.
+    pub constrain_x: Option<KeyCode>,
+    /// Constrain the drag to the Y axis.
+    pub constrain_y: Option<KeyCode>,
+    /// Constrain the drag to the Z axis.
+    pub constrain_z: Option<KeyCode>,
+    /// Keys that turn an axis key into a plane constraint while held.
+    pub plane_modifiers: Vec<KeyCode>,
+    /// Cancel the drag and restore the target's transform from when it
+    /// started.
+    pub cancel: Option<KeyCode>,
+}
+
+impl Default for TransformGizmoKeybinds {
+    fn default() -> Self {
+        Self {
+            constrain_x: Some(KeyCode::KeyX),
+            constrain_y: Some(KeyCode::KeyY),
+            constrain_z: Some(KeyCode::KeyZ),
+            plane_modifiers: vec![KeyCode::ShiftLeft, KeyCode::ShiftRight],
+            cancel: Some(KeyCode::Escape),
+        }
+    }
+}
+
+/// Interaction behaviour configuration for the transform gizmo.
+///
+/// Visual appearance lives in [`TransformGizmoStyle`]; this resource controls
+/// how input is turned into transform edits.
+#[derive(Resource, Clone, Debug)]
+pub struct TransformGizmoConfig {
+    /// Mouse button and modifiers that drive drags.
+    pub drag_input: GizmoDragInput,
+    /// In-drag history scrubbing.
+    pub drag_history: DragHistorySettings,
+    /// Extrapolate each target's origin by its last frame-to-frame motion
+    /// when hit testing, so fast-moving targets hover where they are drawn
+    /// next rather than where they were.
+    pub hover_velocity_compensation: bool,
+    /// Fraction the hit radius of the hovered handle grows by while it
+    /// stays hovered, so a cursor resting between two overlapping handles
+    /// does not flicker between them. Another handle takes over only once
+    /// the cursor leaves the grown radius or that handle's hit is strictly
+    /// closer.
+    pub hover_hysteresis: f32,
+    /// Carry an active drag along with motion of the target's parent (for
+    /// example a moving platform), so the user's delta is applied relative
+    /// to the parent instead of fighting its motion.
+    pub drag_follows_external_motion: bool,
+    /// What an active drag does when something else (an animation, physics,
+    /// or network sync) changes the transform it edits, see
+    /// [`ExternalChangePolicy`].
+    pub external_change_policy: ExternalChangePolicy,
+    /// Scale drag motion by pen pressure. Inert when `None` or when the
+    /// pointer reports no pressure.
+    pub pressure_precision: Option<PressureCurve>,
+    /// Weight (in world units of hit distance) by which pen tilt toward an
+    /// axis handle's on-screen direction favours that handle when handles
+    /// overlap. Inert at `0.0` or when the pointer reports no tilt.
+    pub tilt_axis_bias: f32,
+    /// Fill [`GizmoHandleScreenInfo`] every frame.
+    pub handle_screen_info: bool,
+    /// Stop translate drags at the first contact reported by the
+    /// [`GizmoSweepProvider`](crate::GizmoSweepProvider). Targets can
+    /// override this with [`GizmoBlockedTranslation`](crate::GizmoBlockedTranslation).
+    /// Inert without a provider.
+    pub blocked_translation: bool,
+    /// When a blocked plane drag makes contact, slide along the contact
+    /// surface instead of stopping.
+    pub slide_on_contact: bool,
+    /// How the [`GizmoPreferenceStore`](crate::GizmoPreferenceStore) treats
+    /// several targets sharing one [`GizmoStableId`](crate::GizmoStableId).
+    pub duplicate_stable_ids: DuplicateStableIds,
+    /// Clicks of [`GizmoDragInput::button`] that miss every gizmo handle
+    /// select the
+    /// [`GizmoSelectable`](crate::GizmoSelectable) target under the cursor
+    /// by moving [`GizmoActive`] to it.
+    pub click_to_select: bool,
+    /// With [`click_to_select`](Self::click_to_select), a click that hits
+    /// no selectable target clears the selection.
+    pub clear_selection_on_empty_click: bool,
+    /// What happens to an active drag when the cursor leaves the camera's
+    /// window or the window loses focus.
+    pub on_focus_loss: DragInterruptPolicy,
+    /// Dragging a handle of an inactive target (see
+    /// [`TransformGizmoStyle::draw_inactive_targets`]) moves [`GizmoActive`]
+    /// to it. When off, the selection is left alone; the dragged target
+    /// holds the gizmo only until the drag ends. Hovering never changes the
+    /// selection.
+    pub select_on_drag: bool,
+    /// Move, turn, or scale the active target with the arrow and page keys,
+    /// see [`NudgeSettings`]. Off when `None`.
+    pub keyboard_nudge: Option<NudgeSettings>,
+    /// Ease dragged targets toward the drag instead of moving them there at
+    /// once, see [`SmoothingSettings`]. Off when `None`.
+    pub smoothing: Option<SmoothingSettings>,
+    /// Logical pixels the cursor must move from where a handle is pressed
+    /// before the drag starts editing the target. A release before then is
+    /// a [`GizmoHandleClicked`] instead of a drag. `0.0` starts dragging on
+    /// the press. Duplicate drags always start on the press, as their copy
+    /// is made then.
+    pub drag_threshold_pixels: f32,
+    /// How often drags write
+    /// [`GizmoTransformMessage`](crate::GizmoTransformMessage)s, for
+    /// mirroring them in another app. Off by default.
+    pub message_emission: GizmoMessageEmission,
+}
+
+impl Default for TransformGizmoConfig {
+    fn default() -> Self {
+        Self {
+            drag_input: GizmoDragInput::default(),
+            drag_history: DragHistorySettings::default(),
+            hover_velocity_compensation: false,
+            hover_hysteresis: 0.2,
+            drag_follows_external_motion: false,
+            external_change_policy: ExternalChangePolicy::default(),
+            pressure_precision: None,
+            tilt_axis_bias: 0.0,
+            handle_screen_info: false,
+            blocked_translation: false,
+            slide_on_contact: false,
+            duplicate_stable_ids: DuplicateStableIds::default(),
+            click_to_select: false,
+            clear_selection_on_empty_click: false,
+            on_focus_loss: DragInterruptPolicy::default(),
+            select_on_drag: true,
+            keyboard_nudge: None,
+            smoothing: None,
+            drag_threshold_pixels: 3.0,
+            message_emission: GizmoMessageEmission::Off,
+        }
+    }
+}
+
+/// Keyboard nudging of the active target, see
+/// [`TransformGizmoConfig::keyboard_nudge`].
+///
+/// Left/Right, Up/Down, and PageUp/PageDown each step the target along one
+/// axis of the gizmo's [`TransformGizmoSpace`], in the current
+/// [`TransformGizmoMode`]: translation moves by the axis's translate snap
+/// increment, rotation turns about the axis by its rotate increment, and
+/// scale adds its scale increment to the target's local scale on that axis.
+/// With snapping off, or no increment for the axis, the `default_*` step is
+/// used instead. Holding Shift multiplies the step by
+/// [`fast_multiplier`](Self::fast_multiplier).
+///
+/// A held key repeats after [`repeat_delay`](Self::repeat_delay). Each press
+/// and its repeats are one [`GizmoHistory`](crate::GizmoHistory) entry,
+/// recorded when the key is released. Nothing is nudged during a drag.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct NudgeSettings {
+    /// Which axis each key pair steps along.
+    pub axes: NudgeAxes,
+    /// Translate step, in world units, without a snap increment.
+    pub default_translate_step: f32,
+    /// Rotate step, in radians, without a snap increment.
+    pub default_rotate_step: f32,
+    /// Scale step without a snap increment.
+    pub default_scale_step: f32,
+    /// Factor applied to the step while Shift is held.
+    pub fast_multiplier: f32,
+    /// Seconds a key is held before it starts repeating.
+    pub repeat_delay: f32,
+    /// Seconds between repeats of a held key.
+    pub repeat_interval: f32,
+}
+
+impl Default for NudgeSettings {
+    fn default() -> Self {
+        Self {
+            axes: NudgeAxes::default(),
+            default_translate_step: 0.1,
+            default_rotate_step: 15.0_f32.to_radians(),
+            default_scale_step: 0.1,
+            fast_multiplier: 10.0,
+            repeat_delay: 0.4,
+            repeat_interval: 0.05,
+        }
+    }
+}
+
+/// How [`NudgeSettings`] maps the nudge keys to gizmo axes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NudgeAxes {
+    /// Left/Right step along the gizmo axis closest to the camera's right
+    /// on the ground (XZ) plane, Up/Down along the remaining axis closest to
+    /// its forward direction there, away from the camera for Up, and
+    /// PageUp/PageDown along the last axis, upward for PageUp.
+    #[default]
+    CameraRelative,
+    /// Left/Right step along X, Up/Down along Z (Up toward `-Z`, Bevy's
+    /// forward), and PageUp/PageDown along Y, whatever the camera.
+    Absolute,
+}
+
+/// Easing of dragged targets, see [`TransformGizmoConfig::smoothing`].
+///
+/// A drag of a target's `Transform` moves the goal in its
+/// [`GizmoTargetTransform`](crate::GizmoTargetTransform), and the target
+/// closes the remaining distance to it exponentially, whatever the frame
+/// rate: translation and scale are interpolated linearly and rotation
+/// spherically. After the drag ends the target keeps easing until it settles
+/// on the goal, and a cancelled drag eases back to where it started.
+/// Secondary channels and [`GizmoExternalDriver`](crate::GizmoExternalDriver)
+/// targets are edited directly.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SmoothingSettings {
+    /// Seconds the target takes to close half of its distance to the goal.
+    /// At zero it follows the drag exactly.
+    pub half_life: f32,
+}
+
+impl Default for SmoothingSettings {
+    fn default() -> Self {
+        Self { half_life: 0.08 }
+    }
+}
+
+impl SmoothingSettings {
+    /// The fraction of its remaining distance to the goal a target closes
+    /// in `delta` seconds.
+    pub fn factor(&self, delta: f32) -> f32 {
+        if self.half_life > 0.0 {
+            1.0 - (-delta / self.half_life).exp2()
+        } else {
+            1.0
+        }
+    }
+}
+
+/// What an interrupted drag does; see
+/// [`TransformGizmoConfig::on_focus_loss`].
+///
+/// The drag-cancel key in [`TransformGizmoKeybinds`] always cancels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DragInterruptPolicy {
+    /// Restore the target's transform from when the drag started and end
+    /// the drag with [`GizmoDragEnded::cancelled`] set, so it is not
+    /// recorded in history.
+    Cancel,
+    /// Hold the target where it is until the cursor is back, then carry on
+    /// from there without jumping to the cursor.
+    #[default]
+    Pause,
+    /// End the drag where it is, as if the button were released.
+    Commit,
+}
+
+/// What a drag does when its target's transform is changed by something
+/// other than the drag; see [`TransformGizmoConfig::external_change_policy`].
+///
+/// A change is the edited transform differing from
+/// [`TransformGizmoDrag::last_transform`], the value the drag last wrote.
+/// Targets driven through a [`GizmoExternalDriver`](crate::GizmoExternalDriver)
+/// are never seen to change, as the drag does not read their transform back.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ExternalChangePolicy {
+    /// Keep applying the drag from where it started, overwriting the change.
+    #[default]
+    GizmoWins,
+    /// Carry the drag along with the change: the gizmo moves with the
+    /// target, and further cursor motion is applied on top of where the
+    /// change left it.
+    Rebase,
+    /// End the drag with [`GizmoDragEnded::cancelled`] set, leaving the
+    /// target where the change put it.
+    Abort,
+}
+
+/// How often drags write [`GizmoTransformMessage`](crate::GizmoTransformMessage)s;
+/// see [`TransformGizmoConfig::message_emission`].
+///
+/// Every mode but `Off` writes a last message, with
+/// [`ended`](crate::GizmoTransformMessage::ended) set, when the drag ends.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GizmoMessageEmission {
+    /// Write no messages.
+    #[default]
+    Off,
+    /// Write a message every frame the drag changes its target.
+    EveryFrame,
+    /// Write a message at most once per interval while the drag changes its
+    /// target, for a link that cannot take one every frame.
+    Interval(Duration),
+    /// Write only the last message, when the drag ends.
+    OnEnd,
+}
+
+/// Per-frame cost counters for the gizmo systems.
+///
+/// Overwritten every frame by hover and draw, including frames where they
+/// return early (counts are then zero). With the plugin installed, the same
+/// values are published to Bevy's `DiagnosticsStore` under the paths in
+/// [`TransformGizmoDiagnostics`](crate::TransformGizmoDiagnostics).
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq)]
+pub struct GizmoFrameStats {
+    /// Time spent in hover detection, in microseconds.
+    pub hover_time_us: f64,
+    /// Targets whose coarse bounds were hit by the cursor ray.
+    pub targets_tested: u32,
+    /// Exact handle intersection tests performed during hover.
+    pub exact_intersections: u32,
+    /// Line segments submitted while drawing.
+    pub draw_segments: u32,
+    /// Time spent drawing, in microseconds.
+    pub draw_time_us: f64,
+}
+
+/// What translation and rotation snapping rounds to a multiple of the step.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Default)]
+pub enum SnapMode {
+    /// The change since the drag started, so a target starting off the grid
+    /// stays off it by the same amount.
+    #[default]
+    Relative,
+    /// The resulting value, so the target lands on grid lines. Translation
+    /// is measured along each drag axis from the world origin, and rotation
+    /// as the orientation's angle around the drag axis.
+    Absolute,
+}
+
+/// When the increments in [`TransformGizmoSnap`] apply.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Default)]
+pub enum SnapActivation {
+    /// Snap whenever an increment is set.
+    #[default]
+    Always,
+    /// Snap only while this key is held, so snapping is momentary during a
+    /// drag.
+    WhileKeyHeld(KeyCode),
+}
+
+/// Unit that [`TransformGizmoSnap::rotate`] increments were given in, so a
+/// settings UI can show them the same way.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Default)]
+pub enum AngleUnit {
+    /// Radians, the unit the increments are stored in.
+    #[default]
+    Radians,
+    /// Degrees.
+    Degrees,
+}
+
+/// Snapping configuration for all transform operations.
+///
+/// This resource controls snap-to-grid behavior for translation, rotation,
+/// and scaling operations. Scale snapping always rounds the resulting scale
+/// value, whatever the [`SnapMode`].
+///
+/// The default snaps nothing. Start from [`standard`](Self::standard),
+/// [`fine`](Self::fine), or [`coarse`](Self::coarse) increments, or set them
+/// one by one with [`builder`](Self::builder).
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Resource, Clone, Reflect)]
+#[reflect(Resource, Default)]
+pub struct TransformGizmoSnap {
+    /// Snap increments for translation (in world units).
+    pub translate: AxisSnap,
+    /// Snap increments for rotation (in radians).
+    pub rotate: AxisSnap,
+    /// Unit the rotation increments were given in. Only for display; the
+    /// increments are in radians either way.
+    pub rotate_unit: AngleUnit,
+    /// Snap increments for scale (as multipliers).
+    pub scale: AxisSnap,
+    /// Whether any of the increments apply. Turning this off keeps them for
+    /// when snapping is turned back on.
+    pub enabled: bool,
+    /// Whether translation and rotation snap the change or the result.
+    pub mode: SnapMode,
+    /// Whether snapping is always on or only while a key is held.
+    pub activation: SnapActivation,
+    /// While this key is held, every increment is divided by the given
+    /// divisor for finer snapping.
+    pub fine_modifier: Option<(KeyCode, f32)>,
+    /// Plane and view translate drags place the target on the scene surface
+    /// under the cursor instead of the drag plane, falling back to the plane
+    /// where the cursor misses every surface. Applies whatever the
+    /// [`activation`](Self::activation), and replaces translate snapping
+    /// while on a surface.
+    pub surface: Option<SurfaceSnapSettings>,
+    /// Translate drags snap the target onto other targets and
+    /// [`GizmoSnapPoint`](crate::GizmoSnapPoint)s near the cursor while a
+    /// key is held. Applies whatever the [`activation`](Self::activation),
+    /// and replaces translate and surface snapping while snapped.
+    pub align: Option<AlignSnapSettings>,
+    /// Smallest magnitude a scale drag leaves on any axis, so scale never
+    /// reaches zero. Applies whatever the [`activation`](Self::activation).
+    pub min_scale: f32,
+    /// Let scale drags through the gizmo origin mirror the target, passing
+    /// through a factor of exactly -1 at the mirrored distance. Off, scale
+    /// stops at [`min_scale`](Self::min_scale) on the side it started.
+    pub allow_negative_scale: bool,
+}
+
+/// How translate drags snap to scene surfaces, see
+/// [`TransformGizmoSnap::surface`].
 ///
-/// Each gizmo handle can have different colors for idle, hovered, and
-/// actively dragged states to provide visual feedback.
-#[derive(Clone, Debug)]
-pub struct GizmoStateColors {
-    /// Color when the element is not being interacted with.
-    pub idle: Color,
-    /// Color when the mouse is hovering over the element.
-    pub hover: Color,
-    /// Color when the element is being actively dragged.
-    pub active: Color,
+/// Surfaces are found by a [`GizmoSurfaceRaycast`](crate::GizmoSurfaceRaycast)
+/// callback, or by casting against meshes with the `mesh-raycast` feature.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Default, PartialEq, Reflect)]
+#[reflect(Default)]
+pub struct SurfaceSnapSettings {
+    /// Only snap to entities marked with
+    /// [`GizmoSnapSurface`](crate::GizmoSnapSurface), or whose ancestor is.
+    pub only_marked: bool,
+    /// Turn the target so its local Y axis follows the surface normal,
+    /// keeping its heading as far as possible.
+    pub align_to_normal: bool,
+    /// Distance from the surface to the target's origin, along the normal.
+    pub offset: f32,
 }
 
-impl GizmoStateColors {
-    /// Creates a new color set with the specified colors.
-    pub fn new(idle: Color, hover: Color, active: Color) -> Self {
+/// How translate drags snap onto other entities, see
+/// [`TransformGizmoSnap::align`].
+///
+/// While [`modifier`](Self::modifier) is held, the target jumps to the
+/// nearest other [`TransformGizmoTarget`] or
+/// [`GizmoSnapPoint`](crate::GizmoSnapPoint) within
+/// [`threshold_pixels`](Self::threshold_pixels) of the cursor on screen.
+/// Letting go of the key, or moving away, carries on dragging from there.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Reflect)]
+#[reflect(Default)]
+pub struct AlignSnapSettings {
+    /// Key held to snap onto other entities.
+    pub modifier: KeyCode,
+    /// Key held as well to take the other entity's rotation too.
+    pub rotation_modifier: Option<KeyCode>,
+    /// How close to the cursor, in logical pixels, an entity has to be on
+    /// screen to snap onto.
+    pub threshold_pixels: f32,
+}
+
+impl Default for AlignSnapSettings {
+    fn default() -> Self {
         Self {
-            idle,
-            hover,
-            active,
+            modifier: KeyCode::AltLeft,
+            rotation_modifier: Some(KeyCode::ShiftLeft),
+            threshold_pixels: 20.0,
         }
     }
 }
 
-impl Default for GizmoStateColors {
+impl Default for TransformGizmoSnap {
     fn default() -> Self {
         Self {
-            idle: Color::srgb(0.8, 0.8, 0.8),
-            hover: Color::srgb(1.0, 1.0, 1.0),
-            active: Color::srgb(1.0, 1.0, 0.8),
+            translate: AxisSnap::none(),
+            rotate: AxisSnap::none(),
+            rotate_unit: AngleUnit::default(),
+            scale: AxisSnap::none(),
+            enabled: true,
+            mode: SnapMode::default(),
+            activation: SnapActivation::default(),
+            fine_modifier: None,
+            surface: None,
+            align: Some(AlignSnapSettings::default()),
+            min_scale: 0.001,
+            allow_negative_scale: false,
         }
     }
 }
 
-/// Colors for each axis (X, Y, Z) of a gizmo handle group.
-///
-/// This allows customizing the appearance of translation, rotation, and
-/// scale handles independently for each axis.
-#[derive(Clone, Debug)]
-pub struct AxisColors {
-    /// Colors for the X axis (typically red tones).
-    pub x: GizmoStateColors,
-    /// Colors for the Y axis (typically green tones).
-    pub y: GizmoStateColors,
-    /// Colors for the Z axis (typically blue tones).
-    pub z: GizmoStateColors,
-}
+impl TransformGizmoSnap {
+    /// Snaps translation to 0.5 units, rotation to 15°, and scale to 0.1.
+    ///
+    /// ```
+    /// # use bevy_transform_tools::TransformGizmoSnap;
+    /// let snap = TransformGizmoSnap::standard();
+    /// assert_eq!(snap.translate.x, Some(0.5));
+    /// assert_eq!(snap.rotate.x, Some(15f32.to_radians()));
+    /// assert_eq!(snap.scale.x, Some(0.1));
+    /// ```
+    pub fn standard() -> Self {
+        Self::increments(0.5, 15.0, 0.1)
+    }
 
-impl AxisColors {
-    /// Creates axis colors where all three axes use the same color set.
-    pub fn uniform(colors: GizmoStateColors) -> Self {
-        Self {
-            x: colors.clone(),
-            y: colors.clone(),
-            z: colors,
-        }
+    /// Snaps translation to 0.1 units, rotation to 5°, and scale to 0.05.
+    ///
+    /// ```
+    /// # use bevy_transform_tools::TransformGizmoSnap;
+    /// let snap = TransformGizmoSnap::fine();
+    /// assert_eq!(snap.translate.x, Some(0.1));
+    /// assert_eq!(snap.rotate.x, Some(5f32.to_radians()));
+    /// assert_eq!(snap.scale.x, Some(0.05));
+    /// ```
+    pub fn fine() -> Self {
+        Self::increments(0.1, 5.0, 0.05)
     }
 
-    /// Creates axis colors with distinct colors for each axis.
-    pub fn new(x: GizmoStateColors, y: GizmoStateColors, z: GizmoStateColors) -> Self {
-        Self { x, y, z }
+    /// Snaps translation to 1 unit, rotation to 45°, and scale to 0.25.
+    ///
+    /// ```
+    /// # use bevy_transform_tools::TransformGizmoSnap;
+    /// let snap = TransformGizmoSnap::coarse();
+    /// assert_eq!(snap.translate.x, Some(1.0));
+    /// assert_eq!(snap.rotate.x, Some(45f32.to_radians()));
+    /// assert_eq!(snap.scale.x, Some(0.25));
+    /// ```
+    pub fn coarse() -> Self {
+        Self::increments(1.0, 45.0, 0.25)
     }
 
-    /// Returns the colors for a specific axis.
-    pub fn for_axis(&self, axis: GizmoAxis) -> &GizmoStateColors {
-        match axis {
-            GizmoAxis::X => &self.x,
-            GizmoAxis::Y => &self.y,
-            GizmoAxis::Z => &self.z,
-        }
+    /// Starts a snap configuration that snaps nothing until increments are
+    /// set.
+    ///
+    /// ```
+    /// # use bevy_transform_tools::{AngleUnit, TransformGizmoSnap};
+    /// let snap = TransformGizmoSnap::builder()
+    ///     .translate(0.5)
+    ///     .rotate_degrees(15.0)
+    ///     .build();
+    /// assert_eq!(snap.translate.z, Some(0.5));
+    /// assert_eq!(snap.rotate.z, Some(15f32.to_radians()));
+    /// assert_eq!(snap.rotate_unit, AngleUnit::Degrees);
+    /// assert_eq!(snap.scale.z, None);
+    /// ```
+    pub fn builder() -> TransformGizmoSnapBuilder {
+        TransformGizmoSnapBuilder::default()
     }
-}
 
-impl Default for AxisColors {
-    fn default() -> Self {
-        Self {
-            x: GizmoStateColors::new(
-                Color::srgb(1.0, 0.25, 0.25),
-                Color::srgb(1.0, 0.7, 0.7),
-                Color::srgb(1.0, 0.9, 0.9),
-            ),
-            y: GizmoStateColors::new(
-                Color::srgb(0.25, 1.0, 0.25),
-                Color::srgb(0.7, 1.0, 0.7),
-                Color::srgb(0.9, 1.0, 0.9),
-            ),
-            z: GizmoStateColors::new(
-                Color::srgb(0.25, 0.5, 1.0),
-                Color::srgb(0.7, 0.8, 1.0),
-                Color::srgb(0.9, 0.95, 1.0),
-            ),
+    /// Sets the same rotation increment, in degrees, on all axes.
+    ///
+    /// ```
+    /// # use bevy_transform_tools::{AngleUnit, TransformGizmoSnap};
+    /// let mut snap = TransformGizmoSnap::default();
+    /// snap.set_rotation_degrees(90.0);
+    /// assert_eq!(snap.rotate.x, Some(90f32.to_radians()));
+    /// assert_eq!(snap.rotate_unit, AngleUnit::Degrees);
+    /// ```
+    pub fn set_rotation_degrees(&mut self, degrees: f32) {
+        self.rotate = AxisSnap::uniform_degrees(degrees);
+        self.rotate_unit = AngleUnit::Degrees;
+    }
+
+    /// Turns translate, rotate, and scale snapping on or off together,
+    /// keeping their increments. Surface snapping is unaffected.
+    ///
+    /// ```
+    /// # use bevy_transform_tools::TransformGizmoSnap;
+    /// let mut snap = TransformGizmoSnap::standard();
+    /// snap.enable_all(false);
+    /// assert!(!snap.enabled);
+    /// assert_eq!(snap.translate.x, Some(0.5));
+    /// ```
+    pub fn enable_all(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    fn increments(translate: f32, rotate_degrees: f32, scale: f32) -> Self {
+        Self::builder()
+            .translate(translate)
+            .rotate_degrees(rotate_degrees)
+            .scale(scale)
+            .build()
+    }
+
+    /// The increments in effect with `keys` held.
+    ///
+    /// Evaluated every frame of a drag. Snapping is applied to the drag's
+    /// total motion since it started, so pressing or releasing a key mid-drag
+    /// changes the result without accumulating an offset. With
+    /// [`enabled`](Self::enabled) off, no increments are in effect.
+    pub fn resolve(&self, keys: &ButtonInput<KeyCode>) -> Self {
+        let active = self.enabled
+            && match self.activation {
+                SnapActivation::Always => true,
+                SnapActivation::WhileKeyHeld(key) => keys.pressed(key),
+            };
+        if !active {
+            return Self {
+                translate: AxisSnap::none(),
+                rotate: AxisSnap::none(),
+                scale: AxisSnap::none(),
+                ..self.clone()
+            };
+        }
+        match self.fine_modifier {
+            Some((key, divisor)) if divisor > 0.0 && keys.pressed(key) => Self {
+                translate: self.translate.divided(divisor),
+                rotate: self.rotate.divided(divisor),
+                scale: self.scale.divided(divisor),
+                ..self.clone()
+            },
+            _ => self.clone(),
         }
     }
 }
 
-/// Per-axis enable/disable toggles for gizmo handles.
-///
-/// Use this to selectively show or hide individual axis handles.
-#[derive(Clone, Copy, Debug, Default)]
-pub struct AxisToggles {
-    /// Whether the X axis handle is enabled.
-    pub x: bool,
-    /// Whether the Y axis handle is enabled.
-    pub y: bool,
-    /// Whether the Z axis handle is enabled.
-    pub z: bool,
+/// Builds a [`TransformGizmoSnap`] one setting at a time, see
+/// [`TransformGizmoSnap::builder`]. Unset increments don't snap.
+#[derive(Clone, Default)]
+pub struct TransformGizmoSnapBuilder {
+    snap: TransformGizmoSnap,
 }
 
-impl AxisToggles {
-    /// Creates toggles with all axes enabled.
-    pub fn all() -> Self {
-        Self {
-            x: true,
-            y: true,
-            z: true,
-        }
+impl TransformGizmoSnapBuilder {
+    /// Snaps translation to `increment` world units on all axes.
+    pub fn translate(mut self, increment: f32) -> Self {
+        self.snap.translate = AxisSnap::uniform(increment);
+        self
     }
 
-    /// Creates toggles with all axes disabled.
-    pub fn none() -> Self {
-        Self {
-            x: false,
-            y: false,
-            z: false,
-        }
+    /// Snaps rotation to `radians` about all axes.
+    pub fn rotate(mut self, radians: f32) -> Self {
+        self.snap.rotate = AxisSnap::uniform(radians);
+        self.snap.rotate_unit = AngleUnit::Radians;
+        self
     }
 
-    /// Returns whether a specific axis is enabled.
-    pub fn enabled(&self, axis: GizmoAxis) -> bool {
-        match axis {
-            GizmoAxis::X => self.x,
-            GizmoAxis::Y => self.y,
-            GizmoAxis::Z => self.z,
-        }
+    /// Snaps rotation to `degrees` about all axes.
+    pub fn rotate_degrees(mut self, degrees: f32) -> Self {
+        self.snap.set_rotation_degrees(degrees);
+        self
+    }
+
+    /// Snaps scale to multiples of `increment` on all axes.
+    pub fn scale(mut self, increment: f32) -> Self {
+        self.snap.scale = AxisSnap::uniform(increment);
+        self
+    }
+
+    /// Sets whether translation and rotation snap the change or the result.
+    pub fn mode(mut self, mode: SnapMode) -> Self {
+        self.snap.mode = mode;
+        self
+    }
+
+    /// Snaps only while `key` is held.
+    pub fn while_key_held(mut self, key: KeyCode) -> Self {
+        self.snap.activation = SnapActivation::WhileKeyHeld(key);
+        self
+    }
+
+    /// Divides every increment by `divisor` while `key` is held.
+    pub fn fine_modifier(mut self, key: KeyCode, divisor: f32) -> Self {
+        self.snap.fine_modifier = Some((key, divisor));
+        self
+    }
+
+    /// The finished snap configuration.
+    pub fn build(self) -> TransformGizmoSnap {
+        self.snap
     }
 }
 
-/// Optional per-axis snapping increments.
+/// What clicking the origin dot does to its target, see
+/// [`TransformGizmoStyle::origin_click_action`].
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Default)]
+pub enum GizmoOriginClickAction {
+    /// Nothing beyond the [`GizmoOriginClicked`] message.
+    #[default]
+    None,
+    /// Reset the edited rotation to identity.
+    ResetRotation,
+    /// Reset the edited scale to one.
+    ResetScale,
+    /// Make the target's parent the active target, if it is a
+    /// [`TransformGizmoTarget`].
+    SelectParent,
+}
+
+/// How the world-unit sizes in [`TransformGizmoStyle`] are applied.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Default)]
+pub enum GizmoScaleMode {
+    /// Sizes are world units multiplied by this factor, so the gizmo
+    /// shrinks and grows with camera distance.
+    Fixed(f32),
+    /// Sizes are scaled per target so the axis lines span this many logical
+    /// pixels on screen, whatever the camera distance or projection.
+    ScreenSpace(f32),
+}
+
+impl Default for GizmoScaleMode {
+    fn default() -> Self {
+        Self::Fixed(1.0)
+    }
+}
+
+/// How [`TransformGizmoStyle`] draws the translation cones, scale cubes, and
+/// plane handles.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Default)]
+pub enum GizmoRenderMode {
+    /// Immediate-mode lines through Bevy's `Gizmos`, redrawn every frame.
+    #[default]
+    Lines,
+    /// Unlit mesh entities that the plugin spawns once and moves to the
+    /// active target each frame. Axis lines, rotation arcs, and inactive
+    /// targets are still drawn as lines.
+    Mesh,
+}
+
+/// How the handles that are not part of the active drag are drawn, see
+/// [`TransformGizmoStyle::drag_isolation`].
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Default, Reflect)]
+#[reflect(Default)]
+pub enum DragIsolation {
+    /// Draw every handle as usual.
+    #[default]
+    None,
+    /// Multiply the other handles' alpha by this factor.
+    DimOthers(f32),
+    /// Skip the other handles entirely.
+    HideOthers,
+}
+
+/// Whether gizmo lines are drawn over the scene or hidden behind the
+/// geometry in front of them, see [`TransformGizmoStyle::depth_mode`].
 ///
-/// When set, transform operations will snap to multiples of the specified
-/// values. Use `None` for an axis to disable snapping on that axis.
-#[derive(Clone, Copy, Debug, Default)]
-pub struct AxisSnap {
-    /// Snap increment for the X axis, or `None` to disable.
-    pub x: Option<f32>,
-    /// Snap increment for the Y axis, or `None` to disable.
-    pub y: Option<f32>,
-    /// Snap increment for the Z axis, or `None` to disable.
-    pub z: Option<f32>,
+/// Handles are the axis lines, cones, planes, cubes, rings, and origin dot.
+/// Guides are the drag feedback, the full axis guide, the snap grid, and the
+/// proportional editing radius.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Default)]
+pub enum GizmoDepthMode {
+    /// Everything is depth tested, offset by
+    /// [`TransformGizmoStyle::depth_bias`].
+    RespectDepth,
+    /// Everything is drawn over the scene, even from inside the target's
+    /// own mesh.
+    #[default]
+    AlwaysOnTop,
+    /// Handles and guides each either drawn over the scene or depth tested.
+    Split {
+        /// Whether the handles are drawn over the scene.
+        handles_on_top: bool,
+        /// Whether the guides are drawn over the scene.
+        guides_on_top: bool,
+    },
 }
 
-impl AxisSnap {
-    /// Creates a snap configuration with no snapping on any axis.
-    pub fn none() -> Self {
-        Self {
-            x: None,
-            y: None,
-            z: None,
+impl GizmoDepthMode {
+    /// Whether the handles are drawn over the scene.
+    pub fn handles_on_top(self) -> bool {
+        match self {
+            GizmoDepthMode::RespectDepth => false,
+            GizmoDepthMode::AlwaysOnTop => true,
+            GizmoDepthMode::Split { handles_on_top, .. } => handles_on_top,
         }
     }
 
-    /// Creates a snap configuration with the same increment on all axes.
-    pub fn uniform(increment: f32) -> Self {
-        Self {
-            x: Some(increment),
-            y: Some(increment),
-            z: Some(increment),
+    /// Whether the guides are drawn over the scene.
+    pub fn guides_on_top(self) -> bool {
+        match self {
+            GizmoDepthMode::RespectDepth => false,
+            GizmoDepthMode::AlwaysOnTop => true,
+            GizmoDepthMode::Split { guides_on_top, .. } => guides_on_top,
         }
     }
+}
 
-    /// Returns the snap increment for a specific axis.
-    pub fn get(&self, axis: GizmoAxis) -> Option<f32> {
-        match axis {
-            GizmoAxis::X => self.x,
-            GizmoAxis::Y => self.y,
-            GizmoAxis::Z => self.z,
+/// Where hover compares the cursor with the gizmo handles.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Default)]
+pub enum HitTestMode {
+    /// Cast the cursor ray against the handles' shapes, padded by the
+    /// world-unit hit radii of [`TransformGizmoStyle`].
+    #[default]
+    World,
+    /// Project the handles onto the screen and pick those within
+    /// [`TransformGizmoStyle::hover_pixel_radius`] of the cursor, so thin
+    /// handles stay grabbable at any distance. Overlapping handles go to
+    /// the one closest to the camera.
+    Screen,
+}
+
+/// Which of the two handles at the gizmo origin wins when the cursor is
+/// over both.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Default)]
+pub enum GizmoCenterHandle {
+    /// The screen-facing view translation circle.
+    #[default]
+    TranslateView,
+    /// The uniform scale square.
+    ScaleUniform,
+}
+
+/// Shape of the per-axis rotation handles.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Default)]
+pub enum RotationRingStyle {
+    /// A short arc between the two other axes.
+    Arc {
+        /// Angular extent of the arc.
+        degrees: f32,
+    },
+    /// A full circle around the axis, grabbable anywhere along it.
+    FullCircle,
+}
+
+impl Default for RotationRingStyle {
+    fn default() -> Self {
+        Self::Arc { degrees: 30.0 }
+    }
+}
+
+impl RotationRingStyle {
+    /// Angular extent of each handle, in radians.
+    #[cfg(feature = "rotate")]
+    pub(crate) fn extent_radians(self) -> f32 {
+        match self {
+            RotationRingStyle::Arc { degrees } => degrees.to_radians(),
+            RotationRingStyle::FullCircle => std::f32::consts::TAU,
         }
     }
 }
 
-/// Snapping configuration for all transform operations.
+/// Gizmo config group the transform gizmo draws its handles through.
 ///
-/// This resource controls snap-to-grid behavior for translation, rotation,
-/// and scaling operations.
-#[derive(Resource, Clone, Default)]
-pub struct TransformGizmoSnap {
-    /// Snap increments for translation (in world units).
-    pub translate: AxisSnap,
-    /// Snap increments for rotation (in radians).
-    pub rotate: AxisSnap,
-    /// Snap increments for scale (as multipliers).
-    pub scale: AxisSnap,
-}
+/// The plugin keeps its line width and depth bias in sync with
+/// [`TransformGizmoStyle`], so Bevy's [`DefaultGizmoConfigGroup`] and the
+/// debug lines drawn through it are left alone.
+#[derive(Default, Reflect, GizmoConfigGroup)]
+#[reflect(Default)]
+pub struct TransformGizmoConfigGroup;
+
+/// Gizmo config group the transform gizmo draws its guides through, such as
+/// the drag feedback and snap grid, so they can be depth tested apart from
+/// the handles. See [`GizmoDepthMode`].
+#[derive(Default, Reflect, GizmoConfigGroup)]
+#[reflect(Default)]
+pub struct TransformGizmoGuideConfigGroup;
 
 /// Visual style and sizing configuration for the transform gizmo.
 ///
 /// This resource controls all aspects of gizmo appearance including colors,
 /// sizes, and which elements are visible. Modify this at runtime to customize
 /// the gizmo appearance.
-#[derive(Resource, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Resource, Clone, Reflect)]
+#[reflect(Resource, Default)]
 pub struct TransformGizmoStyle {
     // === Visibility toggles ===
     /// Whether to draw the primary XYZ axis lines.
@@ -433,15 +3169,74 @@ pub struct TransformGizmoStyle {
     pub show_scale: bool,
     /// Which axes have scale handles enabled.
     pub scale_axes: AxisToggles,
+    /// Lay the gizmo out for a 2D camera looking down `-Z`: the Z cone and
+    /// cube, the X and Y rings, the view ring, the trackball, and the XZ and
+    /// YZ planes are hidden, and the XY plane handle becomes a square centered on the
+    /// origin that moves the target freely in its XY plane.
+    pub planar_2d: bool,
+    /// Fade the cones, cubes, plane handles, and rings the camera sees
+    /// nearly end-on: an axis pointing almost straight at or away from it,
+    /// or a plane or ring seen almost edge-on. Faded handles cannot be
+    /// hovered or grabbed; the one being dragged stays as it is.
+    pub fade_backfacing: bool,
+    /// How close, in degrees, a handle's axis may come to the line of sight
+    /// (or a plane or ring's surface to it) before
+    /// [`fade_backfacing`](Self::fade_backfacing) fades it.
+    pub backface_fade_angle: f32,
+    /// Alpha multiplier for handles faded by
+    /// [`fade_backfacing`](Self::fade_backfacing). `0.0` hides them.
+    pub backface_fade_alpha: f32,
 
     // === General styling ===
     /// Line width for gizmo rendering (in pixels).
     pub line_width: f32,
-    /// Depth bias to draw gizmos on top of regular geometry.
-    /// Negative values bring the gizmo closer to the camera.
+    /// Which lines are drawn over the scene and which are hidden behind the
+    /// geometry in front of them.
+    pub depth_mode: GizmoDepthMode,
+    /// Depth bias of the lines `depth_mode` depth tests, between `-1.0` and
+    /// `1.0`. Negative values bring them closer to the camera.
     pub depth_bias: f32,
+    /// Draw through Bevy's [`DefaultGizmoConfigGroup`] instead of
+    /// [`TransformGizmoConfigGroup`] and [`TransformGizmoGuideConfigGroup`],
+    /// applying `line_width` and the handles' depth to it as older versions
+    /// did. Every other gizmo drawn through the default group picks up those
+    /// settings too.
+    pub draw_in_default_gizmo_group: bool,
     /// Length of each axis line (in world units).
     pub axis_length: f32,
+    /// How the world-unit sizes below are scaled. Hover, dragging, and
+    /// drawing all use the scaled sizes.
+    pub scale_mode: GizmoScaleMode,
+    /// Whether the cones, cubes, and plane handles of the active target are
+    /// drawn as lines or as solid meshes.
+    pub render_mode: GizmoRenderMode,
+    /// Depth bias of the solid handle meshes in
+    /// [`GizmoRenderMode::Mesh`]. Positive values pull them towards the
+    /// camera, in front of the geometry around the target.
+    pub mesh_depth_bias: f32,
+    /// Where each target's gizmo sits, unless it has a [`GizmoVisualPivot`]
+    /// or a [`GizmoAnchorOverride`]. Rotation and scale drags turn and scale
+    /// the target about that point.
+    pub anchor: GizmoAnchor,
+
+    // === Axis lines ===
+    /// Where the axis lines start, as a fraction of `axis_length` from the
+    /// origin, to keep them clear of the handles around the origin. They
+    /// always end at the base of the translation cones.
+    pub axis_line_start: f32,
+    /// Break each axis line around the scale cube on it, using
+    /// `scale_cube_offset` and `scale_cube_size`, instead of running it
+    /// through the cube.
+    pub axis_line_gap_for_scale_cubes: bool,
+    /// Let the axis lines grab a translation along their axis too, within
+    /// [`translate_hit_radius`](Self::translate_hit_radius) of the line,
+    /// while [`show_axis_lines`](Self::show_axis_lines) is on. Line hits rank
+    /// behind handles the cursor is directly over, as near misses of the
+    /// cones do. Only [`HitTestMode::World`] picks lines.
+    pub pick_axis_lines: bool,
+    /// While an axis translation drag is active, draw a dimmed guide along
+    /// the drag axis through the origin, far past both ends of the gizmo.
+    pub show_full_axis_during_drag: bool,
 
     // === Colors ===
     /// Colors for the main axis lines.
@@ -452,36 +3247,116 @@ pub struct TransformGizmoStyle {
     pub rotate: AxisColors,
     /// Colors for scale handles.
     pub scale: AxisColors,
+    /// Color of the dragged handle and its axis lines while a blocked
+    /// translate drag is in contact, or a drag is held at one of the
+    /// target's [`GizmoLimits`].
+    pub blocked_color: Color,
 
     // === Translation cone handles ===
     /// Length of the translation cone from base to tip.
     pub translate_cone_length: f32,
     /// Radius of the translation cone at its base.
     pub translate_cone_radius: f32,
-    /// Hit detection radius for translation cones.
+    /// Most line segments around the base of a translation cone, picked
+    /// by its size on screen like
+    /// [`rotation_arc_segments`](Self::rotation_arc_segments).
+    pub translate_cone_segments: usize,
+    /// Fewest line segments around the base of a translation cone.
+    pub translate_cone_segments_min: usize,
+    /// Radius of the capsule around each translation cone that still picks
+    /// it when the cursor is just off the cone. Near misses rank behind
+    /// handles the cursor is directly over.
     pub translate_hit_radius: f32,
+    /// Draw an "X", "Y", or "Z" just beyond each translation cone tip,
+    /// facing the camera, so axes can be told apart without relying on
+    /// color. A label is hidden while its axis is being dragged.
+    pub show_axis_labels: bool,
+    /// Height of the axis labels.
+    pub axis_label_size: f32,
+    /// Color of the axis labels, or `None` for each axis's idle translate
+    /// color.
+    pub axis_label_color: Option<Color>,
 
     // === Scale cube handles ===
     /// Edge length of the scale cube handles.
     pub scale_cube_size: f32,
     /// Position of scale cubes as a fraction of axis_length.
     pub scale_cube_offset: f32,
-    /// Hit detection radius for scale cubes.
+    /// Half size of the box around each scale cube that still picks it when
+    /// the cursor is just off the cube. Near misses rank behind handles the
+    /// cursor is directly over.
     pub scale_hit_radius: f32,
+    /// Push the scale cubes along their axes by the scale factor while a
+    /// scale drag is active, with a guide line back to where they rest, and
+    /// grow the uniform scale square with a uniform drag. The handles
+    /// return to rest when the drag ends.
+    pub scale_handles_follow_drag: bool,
 
     // === Rotation arc handles ===
-    /// Angular extent of each rotation arc (in degrees).
-    pub rotation_arc_degrees: f32,
-    /// Number of line segments per rotation arc.
+    /// Whether rotation handles are short arcs or full circles.
+    pub rotation_ring_style: RotationRingStyle,
+    /// Most line segments per rotation arc or ring, drawn once the arc is
+    /// long enough on screen to need them.
+    ///
+    /// A gizmo small on screen gets fewer, down to
+    /// [`rotation_arc_segments_min`](Self::rotation_arc_segments_min), and
+    /// none gets more than 256 whatever this asks for.
     pub rotation_arc_segments: usize,
+    /// Fewest line segments per rotation arc or ring, however small the
+    /// gizmo is on screen.
+    pub rotation_arc_segments_min: usize,
     /// Visual thickness of rotation arcs.
     pub rotation_arc_thickness: f32,
-    /// Hit detection thickness for rotation arcs.
+    /// Hit detection thickness for rotation arcs and the view ring.
     pub rotation_hit_thickness: f32,
+    /// Ignore rotation ring hits on the far side of the gizmo: a hit is
+    /// dropped when the ring's outward direction there points away from the
+    /// camera by more than this cosine (`0.0` drops the whole back half).
+    /// `None` keeps every hit, which short arcs need to stay reachable when
+    /// seen from behind.
+    pub rotation_backface_cutoff: Option<f32>,
+    /// Whether to show the screen-facing outer ring that rotates around the
+    /// camera's view direction.
+    pub show_rotate_view: bool,
+    /// Radius of the screen-facing rotation ring.
+    pub rotate_view_radius: f32,
+    /// Colors for the screen-facing rotation ring.
+    pub rotate_view_colors: GizmoStateColors,
+    /// Whether dragging inside the rotation rings, where no other handle is
+    /// under the cursor, turns the target freely like a trackball.
+    pub show_rotate_trackball: bool,
+    /// Multiplier on the angle a trackball drag turns for the same cursor
+    /// motion. `1.0` keeps the point under the cursor on the sphere.
+    pub rotate_trackball_sensitivity: f32,
+    /// Colors for the camera-facing outline of the trackball, drawn around
+    /// the rotation rings.
+    pub rotate_trackball_colors: GizmoStateColors,
 
     // === Hit detection ===
-    /// Bounding sphere radius for early-out hit testing.
-    pub bounds_radius: f32,
+    /// Bounding sphere radius for early-out hit testing. `None` derives it
+    /// from the handle sizes; see
+    /// [`effective_bounds_radius`](Self::effective_bounds_radius).
+    pub bounds_radius: Option<f32>,
+    /// Whether hover tests handles against the cursor ray in world units or
+    /// against the cursor position in pixels.
+    pub hit_test_mode: HitTestMode,
+    /// Distance in logical pixels from a handle's projected outline within
+    /// which [`HitTestMode::Screen`] picks it. Not scaled by
+    /// [`scale_mode`](Self::scale_mode).
+    pub hover_pixel_radius: f32,
+    /// Which handle wins where handles overlap, first to last.
+    ///
+    /// A hit within [`pick_priority_tolerance`](Self::pick_priority_tolerance)
+    /// of the nearest one goes to the earliest listed operation. Near misses,
+    /// where the ray only passes within a cone's or cube's hit radius, count
+    /// only when no handle listed earlier is hit at all. View rotation ranks
+    /// with [`GizmoOperation::Rotate`], and view translation and endpoints with
+    /// [`GizmoOperation::TranslateAxis`]; unlisted operations rank last.
+    pub pick_priority: [GizmoOperation; 5],
+    /// How much nearer, in world units, a hit must be than one listed earlier
+    /// in [`pick_priority`](Self::pick_priority) to win over it. In
+    /// [`HitTestMode::Screen`] this compares the depth of the nearest points.
+    pub pick_priority_tolerance: f32,
 
     // === Planar translation handles ===
     /// Whether to show planar translation handles (XY, XZ, YZ planes).
@@ -493,6 +3368,20 @@ pub struct TransformGizmoStyle {
     /// Hit detection padding for planar handles.
     pub translate_plane_hit_thickness: f32,
 
+    // === View translation handle ===
+    /// Whether to show the screen-facing circle at the origin that moves the
+    /// target in the plane facing the camera.
+    pub show_translate_view: bool,
+    /// Radius of the view translation circle.
+    pub translate_view_radius: f32,
+    /// Hit detection radius for the view translation handle.
+    pub translate_view_hit_radius: f32,
+    /// Colors for the view translation handle.
+    pub translate_view_colors: GizmoStateColors,
+    /// Which center handle is picked when the view translation and uniform
+    /// scale handles overlap under the cursor.
+    pub center_handle_priority: GizmoCenterHandle,
+
     // === Uniform scale handle ===
     /// Whether to show the uniform scale handle at the origin.
     pub show_scale_uniform: bool,
@@ -503,13 +3392,87 @@ pub struct TransformGizmoStyle {
     /// Colors for the uniform scale handle.
     pub scale_uniform_colors: GizmoStateColors,
 
+    // === Endpoint handles ===
+    /// Whether to show endpoint handles on targets with [`GizmoEndpoints`].
+    pub show_endpoints: bool,
+    /// Diameter of the circle drawn at each endpoint.
+    pub endpoint_size: f32,
+    /// Hit detection radius for endpoint handles.
+    pub endpoint_hit_radius: f32,
+    /// Colors for endpoint handles.
+    pub endpoint_colors: GizmoStateColors,
+
     // === Origin marker ===
     /// Whether to draw the origin marker.
     pub show_origin_dot: bool,
-    /// Size of the origin marker.
+    /// Size of the origin marker. Hover picks it within this distance of
+    /// the origin, where no other handle is hit.
     pub origin_dot_size: f32,
-    /// Color of the origin marker.
-    pub origin_dot_color: Color,
+    /// Colors of the origin marker, which it shows `active` while pressed.
+    pub origin_dot_colors: GizmoStateColors,
+    /// What clicking the origin marker without dragging does to its target.
+    /// A [`GizmoOriginClicked`] message is written whatever the action.
+    pub origin_click_action: GizmoOriginClickAction,
+
+    // === Drag feedback ===
+    /// Whether to draw guides while dragging that show how far the target
+    /// has moved, turned, or scaled since the drag started.
+    pub show_drag_feedback: bool,
+    /// Color of the dashed translation guide, the ghost of the starting
+    /// origin, and the scale factor line.
+    pub drag_guide_color: Color,
+    /// Color of the pie swept out on the ring by a rotation drag.
+    pub drag_fill_color: Color,
+    /// Length of each dash of the translation guide.
+    pub drag_guide_dash_length: f32,
+    /// Whether a rotation drag's feedback labels the swept pie with the
+    /// angle turned so far, in whole degrees. Whole turns are counted, so
+    /// two full spins read 720°.
+    pub show_rotation_readout: bool,
+    /// Height of the rotation readout's digits.
+    pub rotation_readout_size: f32,
+    /// Grid of snap positions drawn while a translate or rotate drag snaps.
+    pub snap_grid: SnapGridStyle,
+    /// Ruler of ticks along a translate drag, from its start to the target.
+    pub ruler: RulerStyle,
+    /// World-space spacing of the [`ruler`](Self::ruler)'s ticks while the
+    /// drag does not snap.
+    pub ruler_default_step: f32,
+    /// Color of the line from where an align drag would have put its target
+    /// to the entity it snapped onto, see [`TransformGizmoSnap::align`].
+    pub align_line_color: Color,
+    /// How the handles not involved in a drag are drawn while it lasts. With
+    /// anything but [`DragIsolation::None`], an axis translation drag also
+    /// draws the full axis guide, as with `show_full_axis_during_drag`.
+    pub drag_isolation: DragIsolation,
+
+    // === Inactive targets ===
+    /// Draw and hit test a gizmo on every [`TransformGizmoTarget`], not just
+    /// the active one. Grabbing a handle on an inactive target makes it the
+    /// active one.
+    pub draw_inactive_targets: bool,
+    /// Alpha multiplier for the gizmos of inactive targets when
+    /// [`draw_inactive_targets`](Self::draw_inactive_targets) is on.
+    pub inactive_target_alpha: f32,
+
+    // === Culling ===
+    /// Skip drawing and hover testing for gizmos whose bounds are outside
+    /// the camera's view frustum.
+    pub cull_offscreen: bool,
+    /// Gizmos whose axes would be shorter than this on screen, in logical
+    /// pixels, are not drawn in full or hover tested. `0.0` keeps every
+    /// gizmo whole.
+    pub min_screen_size: f32,
+    /// Draw gizmos below [`min_screen_size`](Self::min_screen_size) as
+    /// just their origin dot, instead of not at all.
+    pub distant_origin_dot: bool,
+
+    // === Locked targets ===
+    /// Draw the gizmo of a [`GizmoLocked`] target, in `locked_color`, so it
+    /// can be seen but not grabbed.
+    pub show_locked_gizmos: bool,
+    /// Color every line and handle of a locked target's gizmo is drawn in.
+    pub locked_color: Color,
 }
 
 impl Default for TransformGizmoStyle {
@@ -524,7 +3487,6 @@ impl Default for TransformGizmoStyle {
 
         let translate_hit_radius = translate_cone_length * 0.9;
         let scale_hit_radius = scale_cube_size * 0.9;
-        let bounds_radius = axis_length + translate_cone_length + scale_cube_size;
 
         let translate_plane_size = 0.5;
         let translate_plane_offset = 0.35;
@@ -538,7 +3500,11 @@ impl Default for TransformGizmoStyle {
         let scale_uniform_hit_radius = 0.35;
 
         let origin_dot_size = 0.1;
-        let origin_dot_color = Color::srgb(1.0, 0.6, 0.2);
+        let origin_dot_colors = GizmoStateColors::new(
+            Color::srgb(1.0, 0.6, 0.2),
+            Color::srgb(1.0, 0.8, 0.4),
+            Color::srgb(1.0, 1.0, 0.7),
+        );
 
         Self {
             show_axis_lines: true,
@@ -548,44 +3514,336 @@ impl Default for TransformGizmoStyle {
             rotate_axes: AxisToggles::all(),
             show_scale: true,
             scale_axes: AxisToggles::all(),
+            planar_2d: false,
+            fade_backfacing: false,
+            backface_fade_angle: 10.0,
+            backface_fade_alpha: 0.2,
 
             line_width: 4.0,
-            depth_bias: -1.0,
+            depth_mode: GizmoDepthMode::AlwaysOnTop,
+            depth_bias: 0.0,
+            draw_in_default_gizmo_group: false,
             axis_length,
+            scale_mode: GizmoScaleMode::default(),
+            render_mode: GizmoRenderMode::default(),
+            mesh_depth_bias: 1000.0,
+            anchor: GizmoAnchor::default(),
+            axis_line_start: 0.0,
+            axis_line_gap_for_scale_cubes: true,
+            pick_axis_lines: false,
+            show_full_axis_during_drag: false,
 
             axis_lines: axis_colors.clone(),
             translate: axis_colors.clone(),
             rotate: axis_colors.clone(),
             scale: axis_colors,
+            blocked_color: Color::srgb(1.0, 0.3, 0.1),
 
             translate_cone_length,
             translate_cone_radius,
+            translate_cone_segments: 16,
+            translate_cone_segments_min: 6,
             translate_hit_radius,
+            show_axis_labels: false,
+            axis_label_size: 0.2,
+            axis_label_color: None,
 
             scale_cube_size,
             scale_cube_offset,
             scale_hit_radius,
+            scale_handles_follow_drag: true,
 
-            rotation_arc_degrees: 30.0,
+            rotation_ring_style: RotationRingStyle::default(),
             rotation_arc_segments: 20,
+            rotation_arc_segments_min: 4,
             rotation_arc_thickness: 0.05,
             rotation_hit_thickness: 0.25,
+            rotation_backface_cutoff: None,
+            show_rotate_view: false,
+            rotate_view_radius: axis_length * 1.25,
+            rotate_view_colors: GizmoStateColors::new(
+                Color::srgba(0.8, 0.8, 0.8, 0.8),
+                Color::srgba(1.0, 1.0, 1.0, 1.0),
+                Color::srgba(1.0, 0.9, 0.8, 1.0),
+            ),
+            show_rotate_trackball: false,
+            rotate_trackball_sensitivity: 1.0,
+            rotate_trackball_colors: GizmoStateColors::new(
+                Color::srgba(0.8, 0.8, 0.8, 0.25),
+                Color::srgba(1.0, 1.0, 1.0, 0.6),
+                Color::srgba(1.0, 0.9, 0.8, 0.8),
+            ),
 
-            bounds_radius,
+            bounds_radius: None,
+            hit_test_mode: HitTestMode::default(),
+            hover_pixel_radius: 8.0,
+            pick_priority: [
+                GizmoOperation::TranslatePlane,
+                GizmoOperation::TranslateAxis,
+                GizmoOperation::Rotate,
+                GizmoOperation::ScaleAxis,
+                GizmoOperation::ScaleUniform,
+            ],
+            pick_priority_tolerance: 0.25,
 
             show_translate_planes: true,
             translate_plane_size,
             translate_plane_offset,
             translate_plane_hit_thickness: 0.1,
 
+            show_translate_view: false,
+            translate_view_radius: 0.2,
+            translate_view_hit_radius: 0.25,
+            translate_view_colors: GizmoStateColors::new(
+                Color::srgba(0.9, 0.9, 0.9, 0.8),
+                Color::srgba(1.0, 1.0, 1.0, 1.0),
+                Color::srgba(1.0, 0.9, 0.8, 1.0),
+            ),
+            center_handle_priority: GizmoCenterHandle::default(),
+
             show_scale_uniform: true,
             scale_uniform_size,
             scale_uniform_hit_radius,
             scale_uniform_colors,
 
+            show_endpoints: true,
+            endpoint_size: 0.2,
+            endpoint_hit_radius: 0.25,
+            endpoint_colors: GizmoStateColors::new(
+                Color::srgba(1.0, 0.8, 0.2, 0.9),
+                Color::srgba(1.0, 0.9, 0.4, 1.0),
+                Color::srgba(1.0, 1.0, 0.7, 1.0),
+            ),
+
             show_origin_dot: true,
             origin_dot_size,
-            origin_dot_color,
+            origin_dot_colors,
+            origin_click_action: GizmoOriginClickAction::default(),
+
+            show_drag_feedback: true,
+            drag_guide_color: Color::srgba(1.0, 1.0, 1.0, 0.7),
+            drag_fill_color: Color::srgba(1.0, 0.85, 0.3, 0.35),
+            drag_guide_dash_length: 0.1,
+            show_rotation_readout: true,
+            rotation_readout_size: 0.15,
+            snap_grid: SnapGridStyle::default(),
+            ruler: RulerStyle::default(),
+            ruler_default_step: 1.0,
+            align_line_color: Color::srgba(1.0, 0.85, 0.2, 0.9),
+            drag_isolation: DragIsolation::default(),
+
+            draw_inactive_targets: false,
+            inactive_target_alpha: 0.35,
+
+            cull_offscreen: true,
+            min_screen_size: 0.0,
+            distant_origin_dot: true,
+
+            show_locked_gizmos: true,
+            locked_color: Color::srgba(0.5, 0.5, 0.5, 0.6),
         }
     }
 }
+
+impl TransformGizmoStyle {
+    /// Radius around the gizmo origin that holds the hit area of every
+    /// handle at its origin or along its axes: the
+    /// [`bounds_radius`](Self::bounds_radius) override if set, otherwise the
+    /// farthest of the cone tips, scale cubes, plane squares, rotation rings,
+    /// and center handles, each with its hit padding.
+    pub fn effective_bounds_radius(&self) -> f32 {
+        if let Some(radius) = self.bounds_radius {
+            return radius;
+        }
+        let cones = self.axis_length + self.translate_cone_length + self.translate_hit_radius;
+        let cubes = self.axis_length * self.scale_cube_offset
+            + self.scale_cube_size
+            + self.scale_hit_radius;
+        let planes = (self.translate_plane_offset + self.translate_plane_size)
+            * std::f32::consts::SQRT_2
+            + self.translate_plane_hit_thickness;
+        let rings = self.axis_length + self.rotation_hit_thickness;
+        let center = (self.translate_view_radius + self.translate_view_hit_radius).max(
+            self.scale_uniform_size * std::f32::consts::SQRT_2 + self.scale_uniform_hit_radius,
+        );
+        cones.max(cubes).max(planes).max(rings).max(center)
+    }
+
+    /// Position of `op`'s family in [`pick_priority`](Self::pick_priority),
+    /// or its length if unlisted.
+    pub(crate) fn pick_rank(&self, op: GizmoOperation) -> usize {
+        let family = match op {
+            GizmoOperation::RotateView => GizmoOperation::Rotate,
+            GizmoOperation::TranslateView | GizmoOperation::Endpoint { .. } => {
+                GizmoOperation::TranslateAxis
+            }
+            op => op,
+        };
+        self.pick_priority
+            .iter()
+            .position(|&listed| listed == family)
+            .unwrap_or(self.pick_priority.len())
+    }
+
+    /// This style with every world-unit size multiplied by `factor`.
+    pub(crate) fn scaled(&self, factor: f32) -> Self {
+        Self {
+            axis_length: self.axis_length * factor,
+            translate_cone_length: self.translate_cone_length * factor,
+            translate_cone_radius: self.translate_cone_radius * factor,
+            translate_hit_radius: self.translate_hit_radius * factor,
+            axis_label_size: self.axis_label_size * factor,
+            scale_cube_size: self.scale_cube_size * factor,
+            scale_hit_radius: self.scale_hit_radius * factor,
+            rotation_arc_thickness: self.rotation_arc_thickness * factor,
+            rotation_hit_thickness: self.rotation_hit_thickness * factor,
+            rotate_view_radius: self.rotate_view_radius * factor,
+            bounds_radius: self.bounds_radius.map(|radius| radius * factor),
+            pick_priority_tolerance: self.pick_priority_tolerance * factor,
+            translate_plane_size: self.translate_plane_size * factor,
+            translate_plane_offset: self.translate_plane_offset * factor,
+            translate_plane_hit_thickness: self.translate_plane_hit_thickness * factor,
+            translate_view_radius: self.translate_view_radius * factor,
+            translate_view_hit_radius: self.translate_view_hit_radius * factor,
+            scale_uniform_size: self.scale_uniform_size * factor,
+            scale_uniform_hit_radius: self.scale_uniform_hit_radius * factor,
+            endpoint_size: self.endpoint_size * factor,
+            endpoint_hit_radius: self.endpoint_hit_radius * factor,
+            origin_dot_size: self.origin_dot_size * factor,
+            drag_guide_dash_length: self.drag_guide_dash_length * factor,
+            rotation_readout_size: self.rotation_readout_size * factor,
+            snap_grid: SnapGridStyle {
+                extent: self.snap_grid.extent * factor,
+                fade_distance: self.snap_grid.fade_distance * factor,
+                tick_length: self.snap_grid.tick_length * factor,
+                ..self.snap_grid.clone()
+            },
+            ruler: RulerStyle {
+                tick_length: self.ruler.tick_length * factor,
+                ..self.ruler.clone()
+            },
+            ..self.clone()
+        }
+    }
+
+    /// This style with every hit radius and thickness, and the hover pixel
+    /// radius, multiplied by `factor`.
+    pub(crate) fn widened(&self, factor: f32) -> Self {
+        Self {
+            translate_hit_radius: self.translate_hit_radius * factor,
+            scale_hit_radius: self.scale_hit_radius * factor,
+            rotation_hit_thickness: self.rotation_hit_thickness * factor,
+            hover_pixel_radius: self.hover_pixel_radius * factor,
+            translate_plane_hit_thickness: self.translate_plane_hit_thickness * factor,
+            translate_view_hit_radius: self.translate_view_hit_radius * factor,
+            scale_uniform_hit_radius: self.scale_uniform_hit_radius * factor,
+            endpoint_hit_radius: self.endpoint_hit_radius * factor,
+            ..self.clone()
+        }
+    }
+
+    /// This style with the alpha of every color multiplied by `alpha`.
+    pub(crate) fn faded(&self, alpha: f32) -> Self {
+        self.map_colors(|color| color.with_alpha(color.alpha() * alpha))
+    }
+
+    /// This style with every color multiplied by `tint`, as a
+    /// [`GizmoColorTint`] applies it.
+    pub(crate) fn tinted(&self, tint: Color) -> Self {
+        let tint = tint.to_linear();
+        self.map_colors(|color| {
+            let color = color.to_linear();
+            LinearRgba::new(
+                color.red * tint.red,
+                color.green * tint.green,
+                color.blue * tint.blue,
+                color.alpha * tint.alpha,
+            )
+            .into()
+        })
+    }
+
+    /// This style with every color replaced by
+    /// [`locked_color`](Self::locked_color), keeping each color's alpha, as
+    /// a [`GizmoLocked`] target's gizmo is drawn.
+    pub(crate) fn locked(&self) -> Self {
+        let locked = self.locked_color;
+        self.map_colors(|color| locked.with_alpha(locked.alpha() * color.alpha()))
+    }
+
+    /// This style with `map` applied to every color.
+    fn map_colors(&self, map: impl Fn(Color) -> Color) -> Self {
+        let map_states = |colors: &GizmoStateColors| GizmoStateColors {
+            idle: map(colors.idle),
+            hover: map(colors.hover),
+            active: map(colors.active),
+        };
+        let map_axes = |colors: &AxisColors| AxisColors {
+            x: map_states(&colors.x),
+            y: map_states(&colors.y),
+            z: map_states(&colors.z),
+        };
+        Self {
+            axis_lines: map_axes(&self.axis_lines),
+            translate: map_axes(&self.translate),
+            rotate: map_axes(&self.rotate),
+            scale: map_axes(&self.scale),
+            blocked_color: map(self.blocked_color),
+            axis_label_color: self.axis_label_color.map(&map),
+            locked_color: map(self.locked_color),
+            rotate_view_colors: map_states(&self.rotate_view_colors),
+            rotate_trackball_colors: map_states(&self.rotate_trackball_colors),
+            translate_view_colors: map_states(&self.translate_view_colors),
+            scale_uniform_colors: map_states(&self.scale_uniform_colors),
+            endpoint_colors: map_states(&self.endpoint_colors),
+            origin_dot_colors: map_states(&self.origin_dot_colors),
+            drag_guide_color: map(self.drag_guide_color),
+            drag_fill_color: map(self.drag_fill_color),
+            align_line_color: map(self.align_line_color),
+            snap_grid: SnapGridStyle {
+                minor_color: map(self.snap_grid.minor_color),
+                major_color: map(self.snap_grid.major_color),
+                ..self.snap_grid.clone()
+            },
+            ruler: RulerStyle {
+                minor_color: map(self.ruler.minor_color),
+                major_color: map(self.ruler.major_color),
+                ..self.ruler.clone()
+            },
+            ..self.clone()
+        }
+    }
+
+    /// Whether [`planar_2d`](Self::planar_2d) hides the `(op, axis)`
+    /// handle, which does nothing useful when seen straight down the Z axis.
+    pub(crate) fn hidden_in_2d(&self, op: GizmoOperation, axis: GizmoAxis) -> bool {
+        self.planar_2d
+            && match op {
+                GizmoOperation::TranslateAxis | GizmoOperation::ScaleAxis => axis == GizmoAxis::Z,
+                GizmoOperation::Rotate | GizmoOperation::TranslatePlane => axis != GizmoAxis::Z,
+                GizmoOperation::RotateView | GizmoOperation::RotateTrackball => true,
+                _ => false,
+            }
+    }
+
+    /// Near and far edges of the plane handle squares along both of their
+    /// plane axes. With [`planar_2d`](Self::planar_2d) the square is
+    /// centered on the origin and reaches as far out as the XY plane handle
+    /// would, leaving room around the center handles inside it.
+    #[cfg(feature = "planes")]
+    pub(crate) fn translate_plane_extent(&self) -> (f32, f32) {
+        let near = self.translate_plane_offset;
+        let far = near + self.translate_plane_size;
+        if self.planar_2d {
+            (-far, far)
+        } else {
+            (near, far)
+        }
+    }
+
+    /// Whether `entity` gets a gizmo: the active target always does, others
+    /// only with [`draw_inactive_targets`](Self::draw_inactive_targets).
+    pub(crate) fn shows_target(&self, state: &TransformGizmoState, entity: Entity) -> bool {
+        self.draw_inactive_targets || state.active_target == Some(entity)
+    }
+}
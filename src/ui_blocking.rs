@@ -0,0 +1,110 @@
+//! `bevy_ui` input blocking.
+//!
+//! Nodes marked [`GizmoBlocksInput`] capture the gizmo's pointer while the
+//! cursor is over them, so clicks on buttons and panels laid over the
+//! viewport never start drags on the handles underneath.
+
+use bevy::prelude::*;
+
+use crate::types::{GizmoPointer, TransformGizmoSystems};
+
+/// Marks a `bevy_ui` node that the gizmo ignores the pointer over.
+///
+/// While any marked node is hovered or pressed,
+/// [`GizmoPointer::captured`] is set: hover clears and no drag can start,
+/// but a drag already in progress carries on when the cursor crosses onto
+/// the node. Mark the root of a panel rather than each of its buttons; a
+/// node with only children that block picking never reports a hover.
+///
+/// ```ignore
+/// commands.spawn((
+///     Node {
+///         width: Val::Px(240.0),
+///         height: Val::Percent(100.0),
+///         ..default()
+///     },
+///     BackgroundColor(Color::srgb(0.1, 0.1, 0.12)),
+///     GizmoBlocksInput,
+/// ));
+/// ```
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Component, Clone, Copy, Default, Reflect)]
+#[reflect(Component, Default)]
+#[require(Interaction)]
+pub struct GizmoBlocksInput;
+
+pub(crate) fn register_ui_blocking(app: &mut App) {
+    app.register_type::<GizmoBlocksInput>().add_systems(
+        Update,
+        block_pointer_over_ui.before(TransformGizmoSystems::Sync),
+    );
+}
+
+/// Capture the pointer while it is over a [`GizmoBlocksInput`] node.
+///
+/// Only the frames the cursor enters or leaves the nodes write
+/// [`GizmoPointer::captured`], so this clears nothing it did not set and
+/// other UI integrations can share the flag.
+pub fn block_pointer_over_ui(
+    mut pointer: ResMut<GizmoPointer>,
+    nodes: Query<&Interaction, With<GizmoBlocksInput>>,
+    mut blocking: Local<bool>,
+) {
+    let over = nodes
+        .iter()
+        .any(|interaction| *interaction != Interaction::None);
+    if over != *blocking {
+        pointer.captured = over;
+        *blocking = over;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn app() -> App {
+        let mut app = App::new();
+        app.init_resource::<GizmoPointer>()
+            .add_systems(Update, block_pointer_over_ui);
+        app
+    }
+
+    fn captured(app: &App) -> bool {
+        app.world().resource::<GizmoPointer>().captured
+    }
+
+    #[test]
+    fn hovered_nodes_capture_the_pointer() {
+        let mut app = app();
+        let node = app
+            .world_mut()
+            .spawn((GizmoBlocksInput, Interaction::Hovered))
+            .id();
+        app.update();
+        assert!(captured(&app));
+
+        *app.world_mut().get_mut::<Interaction>(node).unwrap() = Interaction::Pressed;
+        app.update();
+        assert!(captured(&app));
+
+        *app.world_mut().get_mut::<Interaction>(node).unwrap() = Interaction::None;
+        app.update();
+        assert!(!captured(&app));
+
+        // Unmarked nodes never capture it.
+        app.world_mut().spawn(Interaction::Hovered);
+        app.update();
+        assert!(!captured(&app));
+    }
+
+    #[test]
+    fn captures_set_elsewhere_are_left_alone() {
+        let mut app = app();
+        app.world_mut().spawn(GizmoBlocksInput);
+        app.world_mut().resource_mut::<GizmoPointer>().captured = true;
+        app.update();
+        app.update();
+        assert!(captured(&app));
+    }
+}
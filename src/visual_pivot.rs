@@ -0,0 +1,367 @@
+//! Visual pivot handling.
+//!
+//! This module keeps [`GizmoVisualPivot`] offsets in sync with the bounds of
+//! the target (and optionally its descendants) and provides the math used to
+//! rotate and scale a target about a point other than its origin.
+
+use bevy::camera::primitives::Aabb;
+use bevy::prelude::*;
+
+use crate::types::{
+    GizmoAnchor, GizmoAnchorOverride, GizmoVisualPivot, TransformGizmoState, TransformGizmoStyle,
+    VisualPivotSource,
+};
+
+/// Recompute bounds-derived and world-pinned visual pivots.
+///
+/// Offsets are expressed in the target's local space, so bounds offsets only
+/// change when the geometry or the child hierarchy changes, and world points
+/// whenever the target moves. Targets that are being dragged are skipped so
+/// the pivot stays fixed for the whole interaction.
+pub fn update_visual_pivots(
+    state: Res<TransformGizmoState>,
+    mut pivots: Query<(Entity, &GlobalTransform, &mut GizmoVisualPivot)>,
+    bounds: Query<(&Aabb, &GlobalTransform)>,
+    children: Query<&Children>,
+) {
+    for (entity, global, mut pivot) in pivots.iter_mut() {
+        if state
+            .drag
+            .as_ref()
+            .is_some_and(|drag| drag.target == entity)
+        {
+            continue;
+        }
+
+        let to_local = global.affine().inverse();
+        let include_descendants = match pivot.source {
+            VisualPivotSource::Manual => continue,
+            VisualPivotSource::Bounds => false,
+            VisualPivotSource::DescendantBounds => true,
+            VisualPivotSource::WorldPoint(point) => {
+                let offset = to_local.transform_point3(point);
+                if pivot.local_offset != offset {
+                    pivot.local_offset = offset;
+                }
+                continue;
+            }
+        };
+
+        let mut min = Vec3::splat(f32::MAX);
+        let mut max = Vec3::splat(f32::MIN);
+        let mut found = false;
+
+        let mut accumulate = |entity: Entity| {
+            let Ok((aabb, aabb_global)) = bounds.get(entity) else {
+                return;
+            };
+            let center = Vec3::from(aabb.center);
+            let half = Vec3::from(aabb.half_extents);
+            for i in 0..8 {
+                let corner = center
+                    + Vec3::new(
+                        if i & 1 == 0 { -half.x } else { half.x },
+                        if i & 2 == 0 { -half.y } else { half.y },
+                        if i & 4 == 0 { -half.z } else { half.z },
+                    );
+                let world = aabb_global.transform_point(corner);
+                let local = to_local.transform_point3(world);
+                min = min.min(local);
+                max = max.max(local);
+            }
+            found = true;
+        };
+
+        accumulate(entity);
+        if include_descendants {
+            for descendant in children.iter_descendants(entity) {
+                accumulate(descendant);
+            }
+        }
+
+        if !found {
+            continue;
+        }
+
+        let offset = (min + max) * 0.5;
+        if pivot.local_offset != offset {
+            pivot.local_offset = offset;
+        }
+    }
+}
+
+/// What decides where a target's gizmo sits: its visual pivot, anchor
+/// override, and bounds.
+pub(crate) type AnchorData = (
+    Option<&'static GizmoVisualPivot>,
+    Option<&'static GizmoAnchorOverride>,
+    Option<&'static Aabb>,
+);
+
+/// The pivot a target's gizmo sits at, from its [`AnchorData`]: its own
+/// [`GizmoVisualPivot`], else where its [`GizmoAnchorOverride`] or the
+/// `style`'s [`GizmoAnchor`] puts it. `None` is the target's origin.
+///
+/// Drags take the pivot's world position when they start, so it holds still
+/// while they scale the target.
+pub(crate) fn anchor_pivot(
+    (pivot, anchor, aabb): (
+        Option<&GizmoVisualPivot>,
+        Option<&GizmoAnchorOverride>,
+        Option<&Aabb>,
+    ),
+    style: &TransformGizmoStyle,
+) -> Option<GizmoVisualPivot> {
+    if let Some(pivot) = pivot {
+        return Some(*pivot);
+    }
+    match anchor.map_or(style.anchor, |anchor| anchor.0) {
+        GizmoAnchor::Origin => None,
+        GizmoAnchor::BoundsCenter => aabb.map(|aabb| GizmoVisualPivot::manual(aabb.center.into())),
+        GizmoAnchor::Custom(offset) => Some(GizmoVisualPivot::manual(offset)),
+    }
+}
+
+/// World translation of a target after rotating it by `delta` about `pivot`.
+#[cfg(feature = "rotate")]
+pub fn rotate_about_pivot(start_translation: Vec3, pivot: Vec3, delta: Quat) -> Vec3 {
+    pivot + delta * (start_translation - pivot)
+}
+
+/// World translation of a target after scaling it by `factor` (expressed in
+/// the target's local axes) about `pivot`.
+#[cfg(feature = "scale")]
+pub fn scale_about_pivot(
+    start_translation: Vec3,
+    start_rotation: Quat,
+    pivot: Vec3,
+    factor: Vec3,
+) -> Vec3 {
+    let local = start_rotation.inverse() * (start_translation - pivot);
+    pivot + start_rotation * (local * factor)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "scale")]
+    use crate::math::scale_ratio;
+    #[cfg(feature = "rotate")]
+    use std::f32::consts::FRAC_PI_2;
+
+    #[cfg(feature = "rotate")]
+    #[test]
+    fn rotating_about_visual_pivot_keeps_mesh_center_in_place() {
+        // A child 1 unit along X from its parent, with its mesh centered on
+        // the parent's origin.
+        let parent = GlobalTransform::from(Transform::from_xyz(2.0, 0.0, 0.0));
+        let local = Transform::from_xyz(1.0, 0.0, 0.0);
+        let global = parent.mul_transform(local);
+        let pivot = GizmoVisualPivot::manual(Vec3::new(-1.0, 0.0, 0.0));
+        let pivot_world = pivot.world_position(&global);
+        assert!(pivot_world.abs_diff_eq(Vec3::new(2.0, 0.0, 0.0), 1.0e-5));
+
+        // A quarter turn about Y swings the child from +X to -Z of the mesh.
+        let delta = Quat::from_rotation_y(FRAC_PI_2);
+        let world_translation = rotate_about_pivot(global.translation(), pivot_world, delta);
+        assert!(world_translation.abs_diff_eq(Vec3::new(2.0, 0.0, -1.0), 1.0e-5));
+
+        let new_local = Transform {
+            translation: parent
+                .affine()
+                .inverse()
+                .transform_point3(world_translation),
+            rotation: parent.rotation().inverse() * delta * global.rotation(),
+            scale: local.scale,
+        };
+        assert!(new_local
+            .translation
+            .abs_diff_eq(Vec3::new(0.0, 0.0, -1.0), 1.0e-5));
+        let new_global = parent.mul_transform(new_local);
+        assert!(pivot
+            .world_position(&new_global)
+            .abs_diff_eq(Vec3::new(2.0, 0.0, 0.0), 1.0e-5));
+    }
+
+    #[cfg(feature = "rotate")]
+    #[test]
+    fn rotating_about_a_world_point_orbits_the_target_around_it() {
+        use bevy::ecs::system::RunSystemOnce;
+
+        let mut world = World::new();
+        world.init_resource::<TransformGizmoState>();
+        let start = Transform::from_xyz(2.0, 0.0, 0.0);
+        // A hinge one unit to the right of the cube.
+        let hinge = Vec3::new(3.0, 0.0, 0.0);
+        let cube = world
+            .spawn((
+                start,
+                GlobalTransform::from(start),
+                GizmoVisualPivot::world_point(hinge),
+            ))
+            .id();
+        world.run_system_once(update_visual_pivots).unwrap();
+        let pivot = *world.get::<GizmoVisualPivot>(cube).unwrap();
+        assert!(pivot.local_offset.abs_diff_eq(Vec3::X, 1.0e-5));
+        let pivot_world = pivot.world_position(&GlobalTransform::from(start));
+        assert!(pivot_world.abs_diff_eq(hinge, 1.0e-5));
+
+        let delta = Quat::from_rotation_y(FRAC_PI_2);
+        let translation = rotate_about_pivot(start.translation, pivot_world, delta);
+        assert!(translation.abs_diff_eq(Vec3::new(3.0, 0.0, 1.0), 1.0e-5));
+
+        // Once moved, the offset follows the hinge in the turned cube's axes.
+        let turned = Transform::from_translation(translation).with_rotation(delta);
+        world
+            .entity_mut(cube)
+            .insert((turned, GlobalTransform::from(turned)));
+        world.run_system_once(update_visual_pivots).unwrap();
+        let pivot = world.get::<GizmoVisualPivot>(cube).unwrap();
+        assert!(pivot
+            .world_position(&GlobalTransform::from(turned))
+            .abs_diff_eq(hinge, 1.0e-5));
+    }
+
+    #[cfg(feature = "scale")]
+    #[test]
+    fn scaling_about_visual_pivot_keeps_mesh_center_in_place() {
+        let rotation = Quat::from_rotation_z(0.4);
+        let start = Transform::from_xyz(2.0, 0.0, 0.0)
+            .with_rotation(rotation)
+            .with_scale(Vec3::new(1.0, 3.0, 1.0));
+        let pivot = GizmoVisualPivot::manual(Vec3::new(0.0, 1.0, 0.0));
+        let pivot_world = pivot.world_position(&GlobalTransform::from(start));
+
+        let new_scale = start.scale * Vec3::new(2.0, 1.0, 1.0);
+        let factor = scale_ratio(new_scale, start.scale);
+        let translation = scale_about_pivot(start.translation, rotation, pivot_world, factor);
+
+        let scaled = GlobalTransform::from(Transform {
+            translation,
+            rotation,
+            scale: new_scale,
+        });
+        assert!(pivot
+            .world_position(&scaled)
+            .abs_diff_eq(pivot_world, 1.0e-4));
+    }
+
+    #[test]
+    fn a_visual_pivot_then_the_override_then_the_style_anchor_the_gizmo() {
+        let aabb = Aabb::from_min_max(Vec3::new(-1.0, 0.0, -1.0), Vec3::new(1.0, 4.0, 1.0));
+        let mut style = TransformGizmoStyle::default();
+        let offset = |pivot: Option<GizmoVisualPivot>| pivot.map(|pivot| pivot.local_offset);
+
+        assert_eq!(
+            offset(anchor_pivot((None, None, Some(&aabb)), &style)),
+            None
+        );
+        style.anchor = GizmoAnchor::BoundsCenter;
+        assert_eq!(
+            offset(anchor_pivot((None, None, Some(&aabb)), &style)),
+            Some(Vec3::new(0.0, 2.0, 0.0))
+        );
+        // Without bounds the gizmo falls back to the origin.
+        assert_eq!(offset(anchor_pivot((None, None, None), &style)), None);
+
+        let custom = GizmoAnchorOverride(GizmoAnchor::Custom(Vec3::Z));
+        assert_eq!(
+            offset(anchor_pivot((None, Some(&custom), Some(&aabb)), &style)),
+            Some(Vec3::Z)
+        );
+        let pivot = GizmoVisualPivot::manual(Vec3::X);
+        assert_eq!(
+            offset(anchor_pivot(
+                (Some(&pivot), Some(&custom), Some(&aabb)),
+                &style
+            )),
+            Some(Vec3::X)
+        );
+    }
+
+    #[cfg(feature = "rotate")]
+    #[test]
+    fn rotating_a_child_about_its_visual_pivot_keeps_the_mesh_in_place() {
+        use crate::test_utils::GizmoTestHarness;
+        use crate::types::{GizmoAxis, GizmoOperation, TransformGizmoSnap};
+
+        // The mesh's bounds center is 3 units from the target's origin, along
+        // its stretched X axis, and on the world origin under a turned parent.
+        let mut harness = GizmoTestHarness::new();
+        let target = harness.target();
+        let world = harness.app_mut().world_mut();
+        let parent = world
+            .spawn(Transform::from_rotation(Quat::from_euler(
+                EulerRot::XYZ,
+                0.3,
+                0.0,
+                0.4,
+            )))
+            .id();
+        world.entity_mut(target).insert((
+            Transform::from_xyz(-3.0, 0.0, 0.0).with_scale(Vec3::new(2.0, 1.0, 1.0)),
+            Aabb::from_min_max(Vec3::new(1.0, -0.5, -0.5), Vec3::new(2.0, 0.5, 0.5)),
+            GizmoVisualPivot::from_bounds(),
+            ChildOf(parent),
+        ));
+        *world.resource_mut::<TransformGizmoSnap>() =
+            TransformGizmoSnap::builder().rotate(FRAC_PI_2).build();
+        harness.step();
+
+        let global = |harness: &GizmoTestHarness| {
+            *harness
+                .app()
+                .world()
+                .get::<GlobalTransform>(target)
+                .unwrap()
+        };
+        let mesh_center = Vec3::new(1.5, 0.0, 0.0);
+        let start = global(&harness);
+        assert!(start
+            .transform_point(mesh_center)
+            .abs_diff_eq(Vec3::ZERO, 1.0e-5));
+
+        // Turn the ring a quarter turn about the pivot. The gizmo is in local
+        // space, so the ring's axis is the parent's turned Y.
+        let cursor = harness.grab(GizmoOperation::Rotate, GizmoAxis::Y);
+        harness.press_left();
+        harness.step();
+        let axis = harness.drag_state().unwrap().axis_dir;
+        let world = harness.app().world();
+        let ray = world
+            .get::<Camera>(harness.camera())
+            .unwrap()
+            .viewport_to_world(world.get(harness.camera()).unwrap(), cursor)
+            .unwrap();
+        let grabbed = ray.get_point(
+            ray.intersect_plane(Vec3::ZERO, InfinitePlane3d::new(axis))
+                .unwrap(),
+        );
+        let quarter = Quat::from_axis_angle(axis, FRAC_PI_2);
+        for step in 1..=3 {
+            let turned = Quat::from_axis_angle(axis, FRAC_PI_2 * step as f32 / 3.0) * grabbed;
+            let cursor = harness.world_to_cursor(turned).unwrap();
+            harness.move_cursor(cursor);
+            harness.step();
+        }
+        let drag = harness.drag_state().unwrap();
+        let (origin_delta, pivot_delta) = (drag.origin_delta, drag.pivot_delta);
+        harness.release_left();
+        harness.step();
+
+        let end = global(&harness);
+        let turn = end.rotation() * start.rotation().inverse();
+        assert!(turn.angle_between(quarter) < 1.0e-4, "{turn}");
+        assert!(end
+            .transform_point(mesh_center)
+            .abs_diff_eq(Vec3::ZERO, 1.0e-4));
+        let orbited = quarter * start.translation();
+        assert!(
+            end.translation().abs_diff_eq(orbited, 1.0e-4),
+            "{}",
+            end.translation()
+        );
+        assert!(origin_delta.abs_diff_eq(orbited - start.translation(), 1.0e-4));
+        assert_eq!(pivot_delta, Vec3::ZERO);
+    }
+}